@@ -1,9 +1,15 @@
-use camino::Utf8PathBuf;
+use std::time::Instant;
+
+use axoasset::LocalAsset;
+use camino::{Utf8Path, Utf8PathBuf};
 use clap::Parser;
 
-use oranda::config::Config;
+use oranda::config::{Config, ConfigOptions};
 
 use oranda::errors::*;
+use oranda::report::{self, ReportFormat};
+use oranda::site::archive;
+use oranda::site::rebuild::Incremental;
 use oranda::site::Site;
 
 #[derive(Debug, Parser)]
@@ -26,6 +32,21 @@ pub struct Build {
     /// support it, such as installer source files.
     #[arg(long)]
     json_only: bool,
+    /// Inline the CSS, JavaScript and small images each page uses, so that every page is one
+    /// self-contained HTML file. Same as `--set build.single_file=true`.
+    #[arg(long)]
+    single_file: bool,
+    /// Print a summary of the build in this format to stdout when it's done: the pages built,
+    /// bytes written, components, releases fetched, and warnings.
+    #[arg(long, value_enum)]
+    report: Option<ReportFormat>,
+    /// Write the build summary to this file instead of stdout. Implies `--report json`.
+    #[arg(long)]
+    report_file: Option<Utf8PathBuf>,
+    /// Pack the built site into an archive at this path, with a manifest of every file and its
+    /// hash. The format is picked by the extension: `.tar.gz`, `.tgz` or `.zip`.
+    #[arg(long, value_name = "PATH")]
+    archive: Option<Utf8PathBuf>,
 }
 
 impl Build {
@@ -34,17 +55,48 @@ impl Build {
             project_root: project_root.unwrap_or(Utf8PathBuf::from("./")),
             config_path: config_path.unwrap_or(Utf8PathBuf::from("./oranda.json")),
             json_only: false,
+            single_file: false,
+            report: None,
+            report_file: None,
+            archive: None,
         }
     }
 
-    pub fn run(&self) -> Result<()> {
-        if let Some(config) = Site::get_workspace_config()? {
-            let sites = Site::build_multi(&config, self.json_only)?;
+    /// Whether the build report goes to stdout, where the logs would get in its way
+    pub fn reports_to_stdout(&self) -> bool {
+        self.report.is_some() && self.report_file.is_none()
+    }
+
+    pub fn run(&self, options: &ConfigOptions) -> Result<()> {
+        self.run_incremental(options, None)
+    }
+
+    /// Like [`Build::run`][], but for `oranda dev`, which passes what it's kept from the builds
+    /// before, with the plan for this one
+    pub fn run_incremental(
+        &self,
+        options: &ConfigOptions,
+        incremental: Option<&Incremental>,
+    ) -> Result<()> {
+        let started = Instant::now();
+        let reporting = self.report.is_some() || self.report_file.is_some();
+        if reporting {
+            report::start();
+        }
+        let dist_dir = if let Some(mut config) = Site::get_workspace_config(options)? {
+            let mut members = Site::workspace_members(&config, options)?;
+            if self.single_file {
+                config.build.single_file = true;
+                for member in &mut members {
+                    member.config.build.single_file = true;
+                }
+            }
+            let sites = Site::build_members(&members, self.json_only, incremental)?;
             if config.workspace.generate_index && !self.json_only {
                 tracing::info!("Building workspace index page...");
                 let mut member_data = Vec::new();
                 for site in &sites {
-                    // Unwrap here because `Site::build_multi` always sets `workspace_data = Some(_)`.
+                    // Unwrap here because `Site::build_members` always sets `workspace_data = Some(_)`.
                     // It's only set to `None` on a _single_ page build, which can't happen in this
                     // code path.
                     member_data.push(site.workspace_data.clone().unwrap());
@@ -60,17 +112,40 @@ impl Build {
                 config.build.dist_dir
             );
             tracing::info!(success = true, "{}", &msg);
+            config.build.dist_dir
         } else {
-            let config = Config::build(&self.config_path)?;
+            let mut config = Config::build(&self.config_path, options)?;
+            if self.single_file {
+                config.build.single_file = true;
+            }
             if self.json_only {
                 Site::build_single_json_only(&config, None)?;
             } else {
-                Site::build_single(&config, None)?.write(Some(&config))?;
+                Site::build_single_for_dist(&config, None, incremental)?.write(Some(&config))?;
             }
-            let msg = format!("Your site build is located in `{}`.", {
-                config.build.dist_dir
-            });
+            let msg = format!("Your site build is located in `{}`.", config.build.dist_dir);
             tracing::info!(success = true, "{}", &msg);
+            config.build.dist_dir
+        };
+
+        if let Some(path) = &self.archive {
+            let manifest = archive::archive_dir(Utf8Path::new(&dist_dir), path)?;
+            tracing::info!(
+                success = true,
+                "Packed {} file(s) into `{}`.",
+                manifest.files.len(),
+                path
+            );
+        }
+
+        if reporting {
+            let report = report::build_report(Utf8Path::new(&dist_dir), started.elapsed())?;
+            let json = serde_json::to_string_pretty(&report)?;
+            if let Some(path) = &self.report_file {
+                LocalAsset::write_new_all(&format!("{json}\n"), path)?;
+            } else {
+                println!("{json}");
+            }
         }
         Ok(())
     }
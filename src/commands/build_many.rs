@@ -0,0 +1,37 @@
+use camino::Utf8PathBuf;
+use clap::Parser;
+
+use oranda::config::{ConfigOptions, SitesManifest};
+use oranda::data::workspaces;
+use oranda::errors::*;
+use oranda::site::Site;
+
+#[derive(Debug, Parser)]
+pub struct BuildMany {
+    /// The JSON file listing the projects to build, and where to put them.
+    #[arg(long, default_value = "./sites.json")]
+    manifest: Utf8PathBuf,
+    /// Only build the artifacts JSON file (if applicable) of every site, and other files that
+    /// may be used to support it, such as installer source files.
+    #[arg(long)]
+    json_only: bool,
+}
+
+impl BuildMany {
+    pub fn run(&self, options: &ConfigOptions) -> Result<()> {
+        let manifest = SitesManifest::load(&self.manifest)?;
+        let root_path = SitesManifest::root_dir(&self.manifest)?;
+        let sites = workspaces::from_manifest(&manifest, &root_path, options)?;
+        tracing::info!("Building {} site(s)...", sites.len());
+        for site in Site::build_members(&sites, self.json_only, None)? {
+            site.write(None)?;
+        }
+
+        let msg = format!(
+            "Your site builds are located in `{}`.",
+            root_path.join(&manifest.dist_dir)
+        );
+        tracing::info!(success = true, "{}", &msg);
+        Ok(())
+    }
+}
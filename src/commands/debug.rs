@@ -0,0 +1,52 @@
+use camino::Utf8PathBuf;
+use clap::{Parser, Subcommand};
+
+use oranda::config::{Config, ConfigOptions};
+use oranda::errors::*;
+use oranda::site::{debug, Site};
+
+#[derive(Debug, Subcommand)]
+pub enum DebugCommand {
+    /// Prints the template contexts oranda builds for your pages, as JSON.
+    Context(DebugContext),
+}
+
+#[derive(Debug, Parser)]
+pub struct DebugContext {
+    /// Only print the context for this page.
+    #[arg(long, value_parser = clap::builder::PossibleValuesParser::new(debug::PAGES))]
+    page: Option<String>,
+    /// DO NOT USE: Path to the root dir of the project.
+    #[clap(long, hide = true, default_value = "./oranda.json")]
+    config_path: Utf8PathBuf,
+}
+
+#[derive(Debug, Parser)]
+pub struct Debug {
+    #[command(subcommand)]
+    command: DebugCommand,
+}
+
+impl Debug {
+    pub fn run(&self, options: &ConfigOptions) -> Result<()> {
+        match &self.command {
+            DebugCommand::Context(context) => context.run(options),
+        }
+    }
+}
+
+impl DebugContext {
+    fn run(&self, options: &ConfigOptions) -> Result<()> {
+        let contexts = if let Some(workspace_config) = Site::get_workspace_config(options)? {
+            debug::workspace_contexts(&workspace_config, options)?
+        } else {
+            debug::contexts(&Config::build(&self.config_path, options)?)?
+        };
+        let json = match &self.page {
+            Some(page) => serde_json::to_string_pretty(&contexts[page.as_str()])?,
+            None => serde_json::to_string_pretty(&contexts)?,
+        };
+        println!("{json}");
+        Ok(())
+    }
+}
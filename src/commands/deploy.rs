@@ -0,0 +1,67 @@
+use camino::{Utf8Path, Utf8PathBuf};
+use clap::Parser;
+use url::Url;
+
+use oranda::config::{Config, ConfigOptions};
+use oranda::deploy::s3::{self, S3Target};
+use oranda::deploy::{self, DeployOptions};
+use oranda::errors::*;
+use oranda::site::writer;
+
+#[derive(Debug, Parser)]
+pub struct Deploy {
+    /// Where to upload the site, like `s3://my-bucket/some/prefix`.
+    target: S3Target,
+    /// The region the bucket is in. Defaults to AWS_REGION, then AWS_DEFAULT_REGION, then
+    /// us-east-1.
+    #[arg(long)]
+    region: Option<String>,
+    /// Upload to S3-compatible storage at this URL instead of AWS, like Cloudflare R2 or MinIO.
+    #[arg(long)]
+    endpoint: Option<Url>,
+    /// Invalidate this CloudFront distribution once the site is uploaded.
+    #[arg(long, value_name = "ID")]
+    cloudfront_distribution: Option<String>,
+    /// Print what would be uploaded, with the headers it'd be served with, without uploading it.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl Deploy {
+    pub fn run(&self, config_options: &ConfigOptions) -> Result<()> {
+        let config = Config::build(&Utf8PathBuf::from("./oranda.json"), config_options)?;
+        let dist_dir = Utf8Path::new(&config.build.dist_dir);
+        if !dist_dir.is_dir() {
+            return Err(OrandaError::BuildNotFound {
+                dist_dir: config.build.dist_dir.clone(),
+            });
+        }
+        if self.dry_run {
+            for path in writer::files_in(dist_dir)? {
+                println!(
+                    "{}/{}  ({}, {})",
+                    self.target.bucket,
+                    self.target.key(&path),
+                    s3::content_type(&path),
+                    s3::cache_control(&path)
+                );
+            }
+            return Ok(());
+        }
+
+        let options = DeployOptions {
+            target: self.target.clone(),
+            region: self.region.clone(),
+            endpoint: self.endpoint.clone(),
+            cloudfront_distribution: self.cloudfront_distribution.clone(),
+        };
+        tracing::info!("Uploading `{}` to {}...", dist_dir, self.target);
+        let uploaded = deploy::deploy(dist_dir, &options)?;
+        tracing::info!(
+            success = true,
+            "Uploaded {uploaded} file(s) to {}.",
+            self.target
+        );
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use std::net::SocketAddr;
+use std::net::IpAddr;
 use std::path::PathBuf;
 use std::thread::sleep;
 use std::time::Duration;
@@ -6,16 +6,17 @@ use std::time::Duration;
 use axoproject::WorkspaceSearch;
 use camino::Utf8PathBuf;
 use clap::Parser;
-use miette::Report;
 use oranda::site::mdbook::mdbook_dir;
+use oranda::site::rebuild::{DependencyGraph, Incremental, Input, Rebuild};
 
 use crate::commands::{Build, Serve};
 use oranda::data::workspaces;
 use oranda::data::workspaces::WorkspaceData;
+use oranda::dev_status::{DevStatusHandle, STATUS_PATH};
 use oranda::paths::determine_path;
 use oranda::site::Site;
 use oranda::{
-    config::Config,
+    config::{Config, ConfigOptions},
     errors::*,
     site::mdbook::{custom_theme, load_mdbook},
 };
@@ -25,6 +26,13 @@ pub struct Dev {
     /// The port for the file server to be launched on
     #[arg(long)]
     port: Option<u16>,
+    /// The address for the file server to listen on. Use 0.0.0.0 to let other devices on your
+    /// network see the site
+    #[arg(long)]
+    host: Option<IpAddr>,
+    /// Serve the site over HTTPS, with a self-signed certificate
+    #[arg(long)]
+    https: bool,
     /// DO NOT USE: Path to the root dir of the project
     ///
     /// This flag exists for internal testing. It is incorrectly implemented for actual
@@ -48,31 +56,32 @@ pub struct Dev {
 }
 
 impl Dev {
-    pub fn run(self) -> Result<()> {
+    pub fn run(self, options: &ConfigOptions) -> Result<()> {
         let root_path = Utf8PathBuf::from_path_buf(std::env::current_dir()?).unwrap_or_default();
-        let (config, mut paths_to_watch) = if let Ok(Some(config)) = Site::get_workspace_config() {
+        let (config, mut graph) = if let Ok(Some(config)) = Site::get_workspace_config(options) {
             let mut workspace_config_path = root_path.clone();
             workspace_config_path.push("oranda-workspace.json");
-            let members = workspaces::from_config(&config, &root_path, &workspace_config_path)?;
-            let mut ret = Vec::new();
+            let members =
+                workspaces::from_config(&config, &root_path, &workspace_config_path, options)?;
+            let mut ret = DependencyGraph::new();
             for member in members {
-                let mut paths =
+                let graph =
                     self.collect_paths_for_site(&member.config, &root_path, Some(member.clone()))?;
-                ret.append(&mut paths);
+                ret.extend(graph);
             }
             // Also watch oranda-workspace.json
-            ret.push(Utf8PathBuf::from("oranda-workspace.json"));
+            ret.add(Utf8PathBuf::from("oranda-workspace.json"), Input::Config);
 
             // Watch individual manifest files
             let project = axoproject::get_workspaces(&root_path, Some(&root_path));
             if let WorkspaceSearch::Found(workspace) = project.rust {
                 for package in workspace.package_info {
-                    ret.push(package.manifest_path);
+                    ret.add(package.manifest_path, Input::Config);
                 }
             }
             if let WorkspaceSearch::Found(workspace) = project.javascript {
                 for package in workspace.package_info {
-                    ret.push(package.manifest_path);
+                    ret.add(package.manifest_path, Input::Config);
                 }
             }
 
@@ -83,15 +92,15 @@ impl Dev {
                     .config_path
                     .clone()
                     .unwrap_or(Utf8PathBuf::from("./oranda.json")),
+                options,
             )?;
             let ret = self.collect_paths_for_site(&config, &root_path, None)?;
             (config, ret)
         };
 
         // Watch for any user-provided paths
-        if let Some(include_paths) = &self.include_paths {
-            let mut include_paths = include_paths.clone();
-            paths_to_watch.append(&mut include_paths);
+        for path in self.include_paths.iter().flatten() {
+            graph.add(path.clone(), Input::Other);
         }
 
         let (tx, rx) = std::sync::mpsc::channel();
@@ -101,7 +110,7 @@ impl Dev {
         let mut debouncer = notify_debouncer_mini::new_debouncer(Duration::from_secs(1), None, tx)?;
         let watcher = debouncer.watcher();
         let mut existing_paths = vec![];
-        for path in paths_to_watch {
+        for path in graph.paths() {
             let path = PathBuf::from(path);
             // If no path exists, oranda won't work anyways, so there's not much need to let the user know
             // (they'll know either way ;) )
@@ -120,27 +129,47 @@ impl Dev {
         );
         tracing::debug!("Files watched: {:?}", existing_paths);
 
+        let status = DevStatusHandle::new();
+        let dist_dir = Utf8PathBuf::from(&config.build.dist_dir);
+        let mut incremental = Incremental::new();
         if !self.no_first_build {
-            Build::new(self.project_root.clone(), self.config_path.clone()).run()?;
+            status.build_started(vec![]);
+            let result = Build::new(self.project_root.clone(), self.config_path.clone())
+                .run_incremental(options, Some(&incremental));
+            status.build_finished(&result, &dist_dir);
+            result?;
         }
 
         let (ws_tx, ws_rx) = std::sync::mpsc::channel();
         // Spawn the serve process out into a separate thread so that we can loop through received
         // events on this thread.
+        let serve = Serve::new(self.port, self.host, self.https);
+        let origin = serve.origin();
+        let serve_status = status.clone();
+        let serve_options = options.clone();
         let _thread_handle = std::thread::spawn(move || {
-            Serve::new(self.port).run_with_livereload(ws_rx).unwrap();
+            serve
+                .run_with_livereload(ws_rx, serve_status, &serve_options)
+                .unwrap();
         });
-        let addr = SocketAddr::from(([127, 0, 0, 1], self.port.unwrap_or(7979)));
         let msg = if config.build.path_prefix.is_some() {
             format!(
-                "Your project is available at: http://{}/{}",
-                addr,
+                "Your project is available at: {}/{}",
+                origin,
                 config.build.path_prefix.unwrap()
             )
         } else {
-            format!("Your project is available at: http://{}", addr)
+            format!("Your project is available at: {}", origin)
         };
         tracing::info!(success = true, "{}", &msg);
+        tracing::info!("Build status for editor integrations: {origin}{STATUS_PATH}");
+        // Without a first build, there's nothing to rebuild only parts of yet. After that, this
+        // is whatever a failed rebuild didn't get to, which the next one makes too.
+        let mut pending = if self.no_first_build {
+            Rebuild::everything()
+        } else {
+            Rebuild::default()
+        };
         loop {
             // Wait for all debounced events to arrive
             let first_event = rx.recv().expect("channel shut down incorrectly");
@@ -164,14 +193,30 @@ impl Dev {
                 .collect();
 
             if !paths.is_empty() {
-                tracing::info!("Path(s) {:?} changed, rebuilding...", paths);
+                let changed = paths
+                    .iter()
+                    .filter_map(|path| Utf8PathBuf::from_path_buf(path.clone()).ok())
+                    .collect::<Vec<_>>();
+                let mut plan = graph.affected(&changed);
+                plan.merge(&pending);
+                tracing::info!("Path(s) {:?} changed, rebuilding {plan}...", paths);
+                incremental.set_plan(plan.clone());
 
-                if let Err(e) =
-                    Build::new(self.project_root.clone(), self.config_path.clone()).run()
-                {
-                    eprintln!("{:?}", Report::new(e));
+                status.build_started(
+                    paths
+                        .iter()
+                        .map(|path| path.display().to_string())
+                        .collect(),
+                );
+                let result = Build::new(self.project_root.clone(), self.config_path.clone())
+                    .run_incremental(options, Some(&incremental));
+                status.build_finished(&result, &dist_dir);
+                if let Err(e) = result {
+                    oranda::diagnostics::report(e);
+                    pending = plan;
                     continue;
                 } else {
+                    pending = Rebuild::default();
                     // Reload page (this goes into the serve thread, which has spawned a subthread
                     // to handle messages)
                     ws_tx
@@ -187,14 +232,14 @@ impl Dev {
         config: &Config,
         root_path: &Utf8PathBuf,
         workspace: Option<WorkspaceData>,
-    ) -> Result<Vec<Utf8PathBuf>> {
+    ) -> Result<DependencyGraph> {
         let config = config.clone();
         let member_path = workspace.as_ref().map(|w| &w.path);
-        let mut paths_to_watch = vec![];
+        let mut graph = DependencyGraph::new();
 
         // Watch for the readme file
         if let Some(path) = determine_path(root_path, &member_path, config.project.readme_path)? {
-            paths_to_watch.push(path);
+            graph.add(path, Input::Readme);
         }
 
         // Watch for the oranda config file
@@ -203,26 +248,45 @@ impl Dev {
             .clone()
             .unwrap_or_else(|| Utf8PathBuf::from("./oranda.json"));
         if let Some(path) = determine_path(root_path, &member_path, cfg_file)? {
-            paths_to_watch.push(path);
+            graph.add(path, Input::Config);
         }
 
         // Watch for the funding.md page and the funding.yml file
         if let Some(funding) = &config.components.funding {
             if let Some(path) = &funding.yml_path {
                 if let Some(path) = determine_path(root_path, &member_path, path)? {
-                    paths_to_watch.push(path);
+                    graph.add(path, Input::Funding);
                 }
             }
             if let Some(path) = &funding.md_path {
                 if let Some(path) = determine_path(root_path, &member_path, path)? {
-                    paths_to_watch.push(path);
+                    graph.add(path, Input::Funding);
                 }
             }
         }
 
+        // Watch for the action's metadata
+        if let Some(path) = config
+            .components
+            .github_action
+            .as_ref()
+            .and_then(|action| action.path.as_ref())
+        {
+            if let Some(path) = determine_path(root_path, &member_path, path)? {
+                graph.add(path, Input::GithubAction);
+            }
+        }
+
+        // Watch for the demo, so that rebuilding it shows up on the site
+        if let Some(demo) = &config.components.demo {
+            if let Some(path) = determine_path(root_path, &member_path, &demo.path)? {
+                graph.add(path, Input::Demo);
+            }
+        }
+
         // Watch for additional pages, if we have any
         if !config.build.additional_pages.is_empty() {
-            let mut additional_pages = config
+            let additional_pages = config
                 .build
                 .additional_pages
                 .values()
@@ -235,12 +299,14 @@ impl Dev {
                     }
                 })
                 .collect::<Result<Vec<Utf8PathBuf>>>()?;
-            paths_to_watch.append(&mut additional_pages);
+            for path in additional_pages {
+                graph.add(path, Input::AdditionalPage);
+            }
         }
 
         // Watch for the mdbook directory, if we have it
         if let Some(book_cfg) = &config.components.mdbook {
-            let path = mdbook_dir(workspace.as_ref(), book_cfg)?;
+            let path = mdbook_dir(&config.root_dir, book_cfg)?;
             let md = load_mdbook(&path)?;
             // watch book.toml and /src/
             let book_path = determine_path(
@@ -254,10 +320,10 @@ impl Dev {
                 md.source_dir().display().to_string(),
             )?;
             if let Some(path) = book_path {
-                paths_to_watch.push(path);
+                graph.add(path, Input::Mdbook);
             }
             if let Some(path) = source_path {
-                paths_to_watch.push(path);
+                graph.add(path, Input::Mdbook);
             }
 
             // If we're not clobbering the theme, also watch the theme dir
@@ -269,11 +335,20 @@ impl Dev {
                     md.theme_dir().display().to_string(),
                 )?;
                 if let Some(path) = theme_path {
-                    paths_to_watch.push(path);
+                    graph.add(path, Input::Mdbook);
+                }
+            }
+        }
+
+        // Watch whatever the docs command builds from
+        if let Some(docs_cfg) = &config.components.docs {
+            for path in &docs_cfg.watch {
+                if let Some(path) = determine_path(root_path, &member_path, path)? {
+                    graph.add(path, Input::Docs);
                 }
             }
         }
 
-        Ok(paths_to_watch)
+        Ok(graph)
     }
 }
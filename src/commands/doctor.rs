@@ -0,0 +1,51 @@
+use camino::Utf8PathBuf;
+use clap::Parser;
+
+use oranda::config::ConfigOptions;
+use oranda::diagnostics;
+use oranda::doctor::{self, Status};
+use oranda::errors::*;
+
+#[derive(Debug, Parser)]
+pub struct Doctor {
+    /// Skip the checks that need the network, like whether your repository can be reached.
+    #[arg(long)]
+    offline: bool,
+    /// DO NOT USE: Path to the root dir of the project.
+    #[clap(long, hide = true, default_value = "./oranda.json")]
+    config_path: Utf8PathBuf,
+}
+
+impl Doctor {
+    pub fn run(&self, options: &ConfigOptions) -> Result<()> {
+        let (config, mut findings) = doctor::check_config(&self.config_path, options);
+        if let Some(config) = &config {
+            if !self.offline {
+                findings.extend(doctor::remote_checks(config));
+            }
+        }
+
+        let mut errors = 0;
+        let mut warnings = 0;
+        for finding in findings {
+            match finding.status {
+                Status::Error => errors += 1,
+                Status::Warning => warnings += 1,
+                Status::Ok => {}
+            }
+            match finding.to_error() {
+                Some(error) => diagnostics::report(error),
+                None => tracing::info!(success = true, "{}: {}", finding.check, finding.message),
+            }
+        }
+        if errors > 0 {
+            return Err(OrandaError::DoctorFailed { count: errors });
+        }
+        if warnings > 0 {
+            tracing::info!("Nothing will break your build, but have a look at the warnings above.");
+        } else {
+            tracing::info!(success = true, "Everything looks good!");
+        }
+        Ok(())
+    }
+}
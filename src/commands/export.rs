@@ -0,0 +1,92 @@
+use axoasset::LocalAsset;
+use camino::Utf8PathBuf;
+use clap::{Parser, Subcommand};
+
+use oranda::config::{Config, ConfigOptions};
+use oranda::errors::*;
+use oranda::export;
+use oranda::export::announcement::AnnouncementFormat;
+
+#[derive(Debug, Subcommand)]
+pub enum ExportCommand {
+    /// Renders a release's changelog entry and install instructions as an HTML email.
+    Email(Email),
+    /// Converts a release's changelog entry into a post for Discord, Slack or Mastodon.
+    Announcement(Announcement),
+    /// Prints a page of your site to a PDF, with a headless Chrome, Chromium or Edge.
+    Pdf(Pdf),
+}
+
+#[derive(Debug, Parser)]
+pub struct Email {
+    /// The release to announce, like `v1.2.3`. Defaults to the latest release.
+    #[arg(long)]
+    release: Option<String>,
+}
+
+#[derive(Debug, Parser)]
+pub struct Announcement {
+    /// The platform to write the announcement for.
+    #[arg(long, value_enum)]
+    format: AnnouncementFormat,
+    /// The release to announce, like `v1.2.3`. Defaults to the latest release.
+    #[arg(long)]
+    release: Option<String>,
+}
+
+#[derive(Debug, Parser)]
+pub struct Pdf {
+    /// The page to print, like `changelog` or `docs/setup.html`. Use `/` for the index page.
+    #[arg(long, default_value = "changelog")]
+    page: String,
+}
+
+#[derive(Debug, Parser)]
+pub struct Export {
+    /// What to export.
+    #[command(subcommand)]
+    kind: ExportCommand,
+    /// Path to the output file.
+    #[arg(short, long)]
+    #[clap(global = true)]
+    output_path: Option<Utf8PathBuf>,
+}
+
+impl Export {
+    pub fn run(&self, options: &ConfigOptions) -> Result<()> {
+        let config = Config::build(&Utf8PathBuf::from("./oranda.json"), options)?;
+        let path = self
+            .output_path
+            .clone()
+            .unwrap_or_else(|| self.default_path());
+        match &self.kind {
+            ExportCommand::Email(email) => {
+                let release = export::find_release(&config, email.release.as_deref())?;
+                let contents = export::email::render(&release, &config)?;
+                LocalAsset::write_new_all(&contents, &path)?;
+            }
+            ExportCommand::Announcement(announcement) => {
+                let release = export::find_release(&config, announcement.release.as_deref())?;
+                let contents =
+                    export::announcement::render(&release, &config, announcement.format)?;
+                LocalAsset::write_new_all(&contents, &path)?;
+            }
+            // The browser writes this one
+            ExportCommand::Pdf(pdf) => export::pdf::export(&config, &pdf.page, &path)?,
+        }
+        tracing::info!(success = true, "Your export is located at `{}`.", path);
+        Ok(())
+    }
+
+    fn default_path(&self) -> Utf8PathBuf {
+        match &self.kind {
+            ExportCommand::Email(_) => Utf8PathBuf::from("release-email.html"),
+            ExportCommand::Announcement(_) => Utf8PathBuf::from("release-announcement.txt"),
+            ExportCommand::Pdf(pdf) => {
+                let name = pdf.page.trim_matches('/').trim_end_matches(".html");
+                let name = if name.is_empty() { "index" } else { name };
+                Utf8PathBuf::from(format!("{}.pdf", name.replace('/', "-")))
+            }
+        }
+    }
+}
@@ -1,12 +1,26 @@
 mod build;
+mod build_many;
+mod debug;
+mod deploy;
 mod dev;
+mod doctor;
+mod export;
 mod generate;
+mod org;
 mod print;
 mod serve;
+mod theme;
 
 pub use build::Build;
+pub use build_many::BuildMany;
+pub use debug::Debug;
+pub use deploy::Deploy;
 pub use dev::Dev;
+pub use doctor::Doctor;
+pub use export::Export;
 pub use generate::Generate;
+pub use org::Org;
 pub use print::ConfigSchema;
 pub use print::GenerateCss;
 pub use serve::Serve;
+pub use theme::Theme;
@@ -0,0 +1,39 @@
+use camino::Utf8PathBuf;
+use clap::Parser;
+
+use oranda::config::{Config, ConfigOptions};
+use oranda::data::org;
+use oranda::errors::*;
+use oranda::site::Site;
+
+#[derive(Debug, Parser)]
+pub struct Org {
+    /// The GitHub organization to build a portal for.
+    org: String,
+    /// Only list repos with this topic. Can be given more than once, to list repos with any of
+    /// the topics.
+    #[arg(long = "topic")]
+    topics: Vec<String>,
+    /// The title of the portal. Defaults to the organization's name.
+    #[arg(long)]
+    title: Option<String>,
+    #[clap(hide = true)]
+    #[arg(long, default_value = "./oranda-workspace.json")]
+    config_path: Utf8PathBuf,
+}
+
+impl Org {
+    pub fn run(&self, options: &ConfigOptions) -> Result<()> {
+        let mut config = Config::build_workspace_root(&self.config_path, options)?;
+        config.workspace.name = Some(self.title.clone().unwrap_or_else(|| self.org.clone()));
+
+        tracing::info!("Fetching the repositories of {}...", self.org);
+        let projects = org::fetch(&config.root_dir, &self.org, &self.topics)?;
+        tracing::info!("Building a portal for {} project(s)...", projects.len());
+        Site::build_and_write_org_index(&config, &self.org, &projects)?;
+
+        let msg = format!("Your portal is located in `{}`.", config.build.dist_dir);
+        tracing::info!(success = true, "{}", &msg);
+        Ok(())
+    }
+}
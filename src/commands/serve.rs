@@ -1,34 +1,64 @@
 use camino::{Utf8Path, Utf8PathBuf};
-use std::net::SocketAddr;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::sync::mpsc::Receiver;
 use std::thread;
 
-use oranda::config::Config;
+use oranda::config::{Config, ConfigOptions};
+use oranda::dev_status::{DevStatusHandle, STATUS_PATH};
 use oranda::errors::*;
+use oranda::tls;
 
-use axum::{http::StatusCode, routing::get_service, Router};
+use axum::routing::{get, get_service};
+use axum::{http::StatusCode, Json, Router};
+use axum_server::tls_rustls::RustlsConfig;
 
 use clap::Parser;
 use tower_http::services::ServeDir;
 use tower_livereload::LiveReloadLayer;
 
-#[derive(Debug, Default, Parser)]
+#[derive(Debug, Parser)]
 pub struct Serve {
     #[arg(long, default_value = "7979")]
     port: u16,
+    /// The address to listen on. Use 0.0.0.0 to let other devices on your network see the site
+    #[arg(long, default_value = "127.0.0.1")]
+    host: IpAddr,
+    /// Serve the site over HTTPS, with a self-signed certificate
+    #[arg(long)]
+    https: bool,
 }
 
 impl Serve {
-    pub fn new(port: Option<u16>) -> Self {
+    pub fn new(port: Option<u16>, host: Option<IpAddr>, https: bool) -> Self {
         Serve {
             port: port.unwrap_or(7979),
+            host: host.unwrap_or(Ipv4Addr::LOCALHOST.into()),
+            https,
         }
     }
 
-    pub fn run(&self) -> Result<()> {
-        let config = Self::build_config()?;
+    /// Where the site can be opened. A server listening on every interface is shown by this
+    /// machine's address on the network, since that's what other devices would use.
+    pub fn origin(&self) -> String {
+        let scheme = if self.https { "https" } else { "http" };
+        let host = if self.host.is_unspecified() {
+            tls::lan_address().unwrap_or(Ipv4Addr::LOCALHOST.into())
+        } else {
+            self.host
+        };
+        format!("{scheme}://{}", SocketAddr::new(host, self.port))
+    }
+
+    pub fn run(&self, options: &ConfigOptions) -> Result<()> {
+        let config = Self::build_config(options)?;
         if Utf8Path::new(&config.build.dist_dir).is_dir() {
-            self.serve(&config.build.dist_dir, &config.build.path_prefix, None)?;
+            self.serve(
+                &config.root_dir,
+                &config.build.dist_dir,
+                &config.build.path_prefix,
+                None,
+                None,
+            )?;
             Ok(())
         } else {
             Err(OrandaError::BuildNotFound {
@@ -37,14 +67,23 @@ impl Serve {
         }
     }
 
-    pub fn run_with_livereload(&self, rx: Receiver<()>) -> Result<()> {
-        let config = Self::build_config()?;
+    /// Serves the site, reloading it whenever `rx` receives a message, along with the dev
+    /// server's build status
+    pub fn run_with_livereload(
+        &self,
+        rx: Receiver<()>,
+        status: DevStatusHandle,
+        options: &ConfigOptions,
+    ) -> Result<()> {
+        let config = Self::build_config(options)?;
         if Utf8Path::new(&config.build.dist_dir).is_dir() {
             let livereload = LiveReloadLayer::new();
             self.serve(
+                &config.root_dir,
                 &config.build.dist_dir,
                 &config.build.path_prefix,
                 Some((livereload, rx)),
+                Some(status),
             )?;
 
             Ok(())
@@ -58,9 +97,11 @@ impl Serve {
     #[tokio::main]
     async fn serve(
         &self,
+        root_dir: &Utf8Path,
         dist_dir: &str,
         path_prefix: &Option<String>,
         livereload: Option<(LiveReloadLayer, Receiver<()>)>,
+        status: Option<DevStatusHandle>,
     ) -> Result<()> {
         let serve_dir =
             get_service(ServeDir::new(dist_dir)).handle_error(|error: std::io::Error| async move {
@@ -76,6 +117,9 @@ impl Serve {
             "/".to_string()
         };
         let mut app = Router::new().nest_service(&prefix_route, serve_dir);
+        if let Some(status) = status {
+            app = app.route(STATUS_PATH, get(move || async move { Json(status.get()) }));
+        }
         if let Some(livereload) = livereload {
             let (livereload, rx) = livereload;
             let reloader = livereload.reloader();
@@ -89,26 +133,36 @@ impl Serve {
             });
         }
 
-        let addr = SocketAddr::from(([127, 0, 0, 1], self.port));
+        let addr = SocketAddr::new(self.host, self.port);
         let msg = format!(
-            "Your project is available at: http://{}/{}",
-            addr,
+            "Your project is available at: {}/{}",
+            self.origin(),
             path_prefix.as_ref().unwrap_or(&String::new())
         );
         tracing::info!(success = true, "{}", &msg);
-        axum::Server::bind(&addr)
-            .serve(app.into_make_service())
-            .await
-            .expect("failed to start server");
+        if self.https {
+            let cert = tls::self_signed(root_dir, &tls::names_for(self.host))?;
+            let tls_config =
+                RustlsConfig::from_pem(cert.cert_pem.into_bytes(), cert.key_pem.into_bytes())
+                    .await?;
+            axum_server::bind_rustls(addr, tls_config)
+                .serve(app.into_make_service())
+                .await?;
+        } else {
+            axum::Server::bind(&addr)
+                .serve(app.into_make_service())
+                .await
+                .expect("failed to start server");
+        }
         Ok(())
     }
 
-    fn build_config() -> Result<Config> {
+    fn build_config(options: &ConfigOptions) -> Result<Config> {
         let workspace_config_path = &Utf8PathBuf::from("./oranda-workspace.json");
         if workspace_config_path.exists() {
-            Config::build(workspace_config_path)
+            Config::build(workspace_config_path, options)
         } else {
-            Config::build(&Utf8PathBuf::from("./oranda.json"))
+            Config::build(&Utf8PathBuf::from("./oranda.json"), options)
         }
     }
 }
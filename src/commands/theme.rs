@@ -0,0 +1,75 @@
+use axoasset::LocalAsset;
+use camino::Utf8PathBuf;
+use clap::{Parser, Subcommand};
+
+use oranda::errors::*;
+use oranda::site::theme_test;
+
+#[derive(Debug, Subcommand)]
+pub enum ThemeCommand {
+    /// Renders a set of sample sites with your templates and compares them to saved snapshots.
+    Test(ThemeTest),
+}
+
+#[derive(Debug, Parser)]
+pub struct ThemeTest {
+    /// The directory containing your templates, like `build.template_dir`.
+    dir: Utf8PathBuf,
+    /// Where to keep the snapshots. Defaults to `snapshots` inside the template directory.
+    #[arg(long)]
+    snapshot_dir: Option<Utf8PathBuf>,
+    /// Overwrite snapshots that changed instead of failing.
+    #[arg(long)]
+    accept: bool,
+}
+
+#[derive(Debug, Parser)]
+pub struct Theme {
+    #[command(subcommand)]
+    command: ThemeCommand,
+}
+
+impl Theme {
+    pub fn run(&self) -> Result<()> {
+        match &self.command {
+            ThemeCommand::Test(test) => test.run(),
+        }
+    }
+}
+
+impl ThemeTest {
+    fn run(&self) -> Result<()> {
+        let snapshot_dir = self
+            .snapshot_dir
+            .clone()
+            .unwrap_or_else(|| self.dir.join("snapshots"));
+        let mut changed = vec![];
+        for snapshot in theme_test::render_fixtures(&self.dir)? {
+            let path = theme_test::snapshot_path(&snapshot_dir, &snapshot);
+            let new_path = Utf8PathBuf::from(format!("{path}.new"));
+            if new_path.exists() {
+                LocalAsset::remove_file(&new_path)?;
+            }
+            if !path.exists() {
+                tracing::info!("Writing new snapshot {}", snapshot.name);
+                LocalAsset::write_new_all(&snapshot.contents, &path)?;
+            } else if LocalAsset::load_string(&path)? != snapshot.contents {
+                if self.accept {
+                    tracing::info!("Updating snapshot {}", snapshot.name);
+                    LocalAsset::write_new_all(&snapshot.contents, &path)?;
+                } else {
+                    LocalAsset::write_new_all(&snapshot.contents, &new_path)?;
+                    changed.push(snapshot.name);
+                }
+            }
+        }
+        if !changed.is_empty() {
+            return Err(OrandaError::ThemeSnapshotsChanged {
+                count: changed.len(),
+                names: changed.join(", "),
+            });
+        }
+        tracing::info!(success = true, "Theme snapshots are up to date.");
+        Ok(())
+    }
+}
@@ -1,9 +1,9 @@
 use crate::config::workspace::WorkspaceMember;
 use axoproject::{PackageIdx, WorkspaceInfo, WorkspaceSearch};
-use camino::{Utf8Path, Utf8PathBuf};
-use std::path::PathBuf;
+use camino::Utf8Path;
 
-use super::ProjectLayer;
+use super::{requirements, ProjectLayer};
+use crate::diagnostics;
 use crate::errors::*;
 
 /// Info gleaned from axoproject
@@ -18,26 +18,38 @@ pub struct AxoprojectLayer {
 }
 
 impl AxoprojectLayer {
-    /// Load package information about a single-package workspace, where the package is equal to the
-    /// current working dir. This is in opposition to our workspace support, which needs to be
+    /// Load package information about a single-package workspace, where the package is the one
+    /// in `project_root`. This is in opposition to our workspace support, which needs to be
     /// explicitly enabled. axoproject is workspace-aware, but we don't use the multi-package
     /// workspace functionality it gives us when ran like this.
-    pub fn load(project_root: Option<PathBuf>) -> Result<Option<AxoprojectLayer>> {
-        // Start in the project root, or failing that current dir
-        let start_dir = project_root.unwrap_or_else(|| {
-            std::env::current_dir().expect("couldn't get current working dir!?")
-        });
-        let start_dir = Utf8PathBuf::from_path_buf(start_dir).expect("project path isn't utf8!?");
+    pub fn load(project_root: &Utf8Path) -> Result<Option<AxoprojectLayer>> {
+        let start_dir = project_root.to_owned();
 
         let workspace = Self::get_best_workspace(&start_dir);
         let Some(workspace) = workspace else {
-            return Ok(None);
+            // We can't build a site for a Python project from its pyproject.toml yet, but we
+            // can at least say which Python it needs
+            let requirements = requirements::from_pyproject(&start_dir);
+            if requirements.is_empty() {
+                return Ok(None);
+            }
+            return Ok(Some(AxoprojectLayer {
+                project: Some(ProjectLayer {
+                    requirements: Some(requirements),
+                    ..Default::default()
+                }),
+                cargo_dist: None,
+                members: None,
+            }));
         };
         let project = Self::get_root_package(&start_dir, workspace);
 
         if let Some((workspace, pkg)) = project {
             // Cool we found the best possible match, now extract all the values we care about from it
             let package = workspace.package(pkg);
+            let mut requirements =
+                requirements::from_manifest(&package.manifest_path, &workspace.manifest_path);
+            requirements.extend(requirements::from_pyproject(&start_dir));
 
             // If there's a [workspace.metadata.dist] table, we can auto-enable cargo-dist
             // If there's no [workspace.metadata] table at all, inconclusive.
@@ -54,6 +66,7 @@ impl AxoprojectLayer {
                     version: package.version.as_ref().map(|v| v.to_string()),
                     license: package.license.clone(),
                     readme_path: package.readme_file.as_ref().map(|v| v.to_string()),
+                    requirements: Some(requirements),
                 }),
                 cargo_dist,
                 members: None,
@@ -78,6 +91,8 @@ impl AxoprojectLayer {
             let member = WorkspaceMember {
                 path: package.package_root.clone().into(),
                 slug: slug::slugify(package.name.clone()),
+                weight: None,
+                config: None,
             };
             members.push(member);
         }
@@ -152,7 +167,7 @@ impl AxoprojectLayer {
                     manifest_path,
                     cause,
                 };
-                eprintln!("{:?}", miette::Report::new(warning));
+                diagnostics::report(warning);
                 None
             }
             WorkspaceSearch::Missing(cause) => {
@@ -1,8 +1,13 @@
 use indexmap::IndexMap;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 
 use super::{ApplyLayer, ApplyOptExt, ApplyValExt};
+use crate::errors::*;
+
+/// The sidebar file we look for if none is configured
+const DEFAULT_SIDEBAR: &str = "_sidebar.md";
 
 #[derive(Debug, Clone)]
 /// Information about how the pages should be built (complete version)
@@ -21,6 +26,38 @@ pub struct BuildConfig {
     ///
     /// We use IndexMap to respect the order the user provided.
     pub additional_pages: IndexMap<String, String>,
+    /// Whether to show git authorship info on additional pages
+    pub page_history: bool,
+    /// Whether to also write the site's data as JSON under `api/`
+    pub emit_json: bool,
+    /// Whether to install a service worker that caches the site for reading offline, and
+    /// serves a fallback page for what it hasn't cached
+    pub offline_support: bool,
+    /// Relative path to a dir of templates that replace or add to the built-in ones
+    pub template_dir: Option<String>,
+    /// URL prefixes that templates may fetch JSON from with `fetch_json`
+    pub fetch_json_allowlist: Vec<String>,
+    /// Relative path to a markdown file defining the sidebar for additional pages
+    pub sidebar: Option<String>,
+    /// How many days after their last commit additional pages are considered outdated
+    pub stale_after_days: Option<u32>,
+    /// The directory that pages marked `private: true` are written to
+    pub private_path: String,
+    /// The host to write access rules for private pages for, which has to be chosen if there
+    /// are any private pages
+    pub private_host: Option<PrivateHost>,
+    /// The name of the preview this is a build of, like "pr-42"
+    pub preview: Option<String>,
+    /// The dir that markdown files may include other files from
+    pub include_root: Option<String>,
+    /// Whether to build pages that work in old browsers and text browsers
+    pub compat: bool,
+    /// Whether to inline CSS, JavaScript and small images into every page
+    pub single_file: bool,
+    /// The URL the site is served at, if it's not `project.homepage`
+    pub base_url: Option<String>,
+    /// Whether to write a `CNAME` file with the base URL's domain, for GitHub Pages
+    pub cname: bool,
 }
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -49,6 +86,114 @@ pub struct BuildLayer {
     /// These pages will be listed in the given order after "home" and before
     /// other pages that oranda automatically adds like "install" and "funding".
     pub additional_pages: Option<IndexMap<String, String>>,
+    /// Whether to show who last updated each of your additional pages and when, alongside
+    /// a list of everyone who has contributed to it, based on your git history.
+    ///
+    /// This is false by default
+    pub page_history: Option<bool>,
+    /// Whether to write the data gathered for your site (releases, artifacts, funding, pages
+    /// and nav) as JSON files under `api/` in your dist dir, alongside the HTML.
+    ///
+    /// This is false by default
+    pub emit_json: Option<bool>,
+    /// Whether to install a service worker on your site, which keeps your home page, styles,
+    /// changelog and newest release notes (and every page a visitor reads) around for reading
+    /// offline, and shows an "offline" page (styled like the rest of your site) instead of the
+    /// browser's error page for the pages it doesn't have.
+    ///
+    /// This is false by default
+    pub offline_support: Option<bool>,
+    /// Relative path to a dir of your own templates, which take precedence over oranda's
+    /// built-in templates with the same name (for example, "layout.html.j2" or
+    /// "includes/nav.html.j2"). Templates with new names can be included or extended from
+    /// the overridden ones.
+    pub template_dir: Option<String>,
+    /// URL prefixes, like "https://api.example.com/stats/", that templates are allowed to
+    /// fetch JSON from with the `fetch_json(url)` function.
+    ///
+    /// Requests to any other URL fail the build. This is empty by default, which disables
+    /// `fetch_json` entirely.
+    pub fetch_json_allowlist: Option<Vec<String>>,
+    /// Relative path to a markdown file that defines a sidebar for your additional pages, as a
+    /// (nested) list of links to them, like mdbook's SUMMARY.md.
+    ///
+    /// Pages linked from the sidebar are built even if they aren't in `additional_pages`, so
+    /// the top nav can stay short while the sidebar holds the rest of your docs.
+    ///
+    /// This is "./_sidebar.md" by default, if that file exists
+    pub sidebar: Option<String>,
+    /// How many days after it was last changed (according to your git history) an additional
+    /// page should be considered outdated. Outdated pages get a banner saying so, and are
+    /// listed at the end of the build.
+    ///
+    /// Pages can also set a `review_by` date in their frontmatter, which applies whether or
+    /// not this is set.
+    ///
+    /// This is disabled by default
+    pub stale_after_days: Option<u32>,
+    /// The directory, relative to your dist dir, that additional pages with `private: true` in
+    /// their frontmatter are written to, so that your host can restrict access to all of them
+    /// at once.
+    ///
+    /// Private pages are left out of the sitemap, tag pages and `api/pages.json`.
+    ///
+    /// This is "internal" by default
+    pub private_path: Option<String>,
+    /// The host your site is deployed to, so we can write its config for restricting access
+    /// to `private_path`. Set this to "none" to handle access some other way.
+    ///
+    /// There's no default: if you have private pages, the build fails until you've chosen one,
+    /// so that they're never published by accident
+    pub private_host: Option<PrivateHost>,
+    /// Builds the site as a preview, like "pr-42" for a pull request's preview deployment.
+    ///
+    /// Previews are built under their name (after `path_prefix`, if there is one), have a
+    /// banner saying they're a preview, linking to the pull request if the name looks like
+    /// "pr-42" and your repository is on GitHub, tell search engines not to index them, and
+    /// don't include analytics. This is usually set with `oranda build --preview pr-42`.
+    pub preview: Option<String>,
+    /// The directory that markdown files can include other files from, with
+    /// `<!-- include: path.md -->` or `{{ include file="path.md" }}`. Including a file outside
+    /// of it fails the build.
+    ///
+    /// This is your project's directory by default, or the workspace's root directory for
+    /// workspace members, so that they can share files.
+    pub include_root: Option<String>,
+    /// Builds pages that also work in old browsers and text browsers like lynx or w3m: plain
+    /// fallback styles are added for browsers that don't understand oranda's CSS, things that
+    /// need JavaScript come with `<noscript>` alternatives, and the install widget becomes a
+    /// plain list of every platform's install options.
+    ///
+    /// This is false by default
+    pub compat: Option<bool>,
+    /// Inlines the stylesheets, scripts and small images (up to 100 KiB) each page uses into
+    /// the page itself, so that every page is a single self-contained HTML file that can be
+    /// passed around and opened without a network connection. Anything hosted elsewhere, like
+    /// web fonts, is left as a link.
+    ///
+    /// This is usually set with `oranda build --single-file`. This is false by default
+    pub single_file: Option<bool>,
+    /// The URL your site is served at, like "https://oranda.axo.dev". It's used for each
+    /// page's canonical URL, and for the links in your sitemap, RSS feeds and link previews,
+    /// which have to be absolute.
+    ///
+    /// This is your `project.homepage` by default, unless that's your repository.
+    pub base_url: Option<String>,
+    /// Whether to write a `CNAME` file with the domain of your `base_url`, which GitHub Pages
+    /// reads to serve your site from a custom domain.
+    ///
+    /// This is false by default
+    pub cname: Option<bool>,
+}
+
+/// Hosts we know how to restrict access to private pages on
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PrivateHost {
+    /// Writes a `_headers` file with basic auth for the private pages
+    Netlify,
+    /// Doesn't write anything, for when access is restricted some other way
+    None,
 }
 
 impl Default for BuildConfig {
@@ -58,6 +203,21 @@ impl Default for BuildConfig {
             static_dir: "static".to_owned(),
             path_prefix: None,
             additional_pages: Default::default(),
+            page_history: false,
+            emit_json: false,
+            offline_support: false,
+            template_dir: None,
+            fetch_json_allowlist: Vec::new(),
+            sidebar: None,
+            stale_after_days: None,
+            private_path: "internal".to_owned(),
+            private_host: None,
+            preview: None,
+            include_root: None,
+            compat: false,
+            single_file: false,
+            base_url: None,
+            cname: false,
         }
     }
 }
@@ -70,11 +230,60 @@ impl ApplyLayer for BuildConfig {
             static_dir,
             path_prefix,
             additional_pages,
+            page_history,
+            emit_json,
+            offline_support,
+            template_dir,
+            fetch_json_allowlist,
+            sidebar,
+            stale_after_days,
+            private_path,
+            private_host,
+            preview,
+            include_root,
+            compat,
+            single_file,
+            base_url,
+            cname,
         } = layer;
         self.dist_dir.apply_val(dist_dir);
         self.static_dir.apply_val(static_dir);
         self.path_prefix.apply_opt(path_prefix);
         // In the future this might want to be `extend`
         self.additional_pages.apply_val(additional_pages);
+        self.page_history.apply_val(page_history);
+        self.emit_json.apply_val(emit_json);
+        self.offline_support.apply_val(offline_support);
+        self.template_dir.apply_opt(template_dir);
+        self.fetch_json_allowlist.apply_val(fetch_json_allowlist);
+        self.sidebar.apply_opt(sidebar);
+        self.stale_after_days.apply_opt(stale_after_days);
+        self.private_path.apply_val(private_path);
+        self.private_host.apply_opt(private_host);
+        self.preview.apply_opt(preview);
+        self.include_root.apply_opt(include_root);
+        self.compat.apply_val(compat);
+        self.single_file.apply_val(single_file);
+        self.base_url.apply_opt(base_url);
+        self.cname.apply_val(cname);
+    }
+}
+
+impl BuildConfig {
+    /// Checks that the sidebar exists, or picks up `_sidebar.md` if there is one
+    pub fn find_paths(&mut self, start_dir: &Path) -> Result<()> {
+        match &self.sidebar {
+            Some(path) => {
+                if !start_dir.join(path).is_file() {
+                    return Err(OrandaError::PathDoesNotExist { path: path.clone() });
+                }
+            }
+            None => {
+                if start_dir.join(DEFAULT_SIDEBAR).is_file() {
+                    self.sidebar = Some(DEFAULT_SIDEBAR.to_owned());
+                }
+            }
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,53 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ApplyLayer, ApplyValExt};
+
+/// Config for the project activity widget (complete version)
+#[derive(Debug, Clone)]
+pub struct ActivityConfig {
+    /// How many of the latest discussions to list
+    pub discussions: usize,
+    /// Whether to show how long issues wait for a first response
+    pub response_time: bool,
+}
+
+/// The config for showing your repository's open issues, latest discussions and response time
+/// on your front page
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ActivityLayer {
+    /// How many of your repository's latest GitHub Discussions to list. Discussions can only be
+    /// fetched with a token in the `GITHUB_TOKEN` environment variable. Set this to 0 to not
+    /// list any.
+    ///
+    /// Defaults to 3
+    pub discussions: Option<usize>,
+    /// Whether to show how long your latest issues waited for a first response from someone
+    /// other than whoever opened them.
+    ///
+    /// Defaults to true
+    pub response_time: Option<bool>,
+}
+
+impl Default for ActivityConfig {
+    fn default() -> Self {
+        ActivityConfig {
+            discussions: 3,
+            response_time: true,
+        }
+    }
+}
+
+impl ApplyLayer for ActivityConfig {
+    type Layer = ActivityLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let ActivityLayer {
+            discussions,
+            response_time,
+        } = layer;
+        self.discussions.apply_val(discussions);
+        self.response_time.apply_val(response_time);
+    }
+}
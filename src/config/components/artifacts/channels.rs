@@ -0,0 +1,18 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A release channel shown on the install page next to your latest stable release, like
+/// nightly builds
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelConfig {
+    /// The tag of the release to show, usually a rolling pre-release like "nightly" that's
+    /// replaced on every build.
+    ///
+    /// If not set, we show the newest pre-release.
+    pub tag: Option<String>,
+    /// The GitHub repository to find the release in, if it isn't `project.repository`
+    pub repository: Option<String>,
+    /// A line shown under the channel's heading, like "Built from main every night"
+    pub description: Option<String>,
+}
@@ -0,0 +1,63 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ApplyLayer, ApplyValExt};
+
+/// Extra rules for classifying the files in a release (complete version)
+#[derive(Debug, Clone, Default)]
+pub struct MatchingConfig {
+    pub installers: Vec<String>,
+    pub archives: Vec<String>,
+    pub checksums: Vec<String>,
+    pub ignored: Vec<String>,
+}
+
+/// Regular expressions for classifying the files in your releases, for when their names
+/// don't follow the conventions we auto-detect.
+///
+/// Each one is matched against the whole file name, like `"\\.pkg$"` or
+/// `"^myapp-setup-.*\\.exe$"`. These take precedence over the built-in rules, which still
+/// apply to files that none of these match.
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MatchingLayer {
+    /// Files that are native installers (like a .msi), recommended as a download
+    pub installers: Option<Vec<String>>,
+    /// Archives containing your binaries (like a .tar.gz), recommended as a download for the
+    /// target triple in their name
+    pub archives: Option<Vec<String>>,
+    /// Checksum files. If removing the matched part from the end of a checksum file's name
+    /// gives the name of another file (like `app.zip.b3` for `app.zip`), it's shown as that
+    /// file's checksum.
+    pub checksums: Option<Vec<String>>,
+    /// Files to never suggest as a way to install your project
+    pub ignored: Option<Vec<String>>,
+}
+
+impl ApplyLayer for MatchingConfig {
+    type Layer = MatchingLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let MatchingLayer {
+            installers,
+            archives,
+            checksums,
+            ignored,
+        } = layer;
+        self.installers.apply_val(installers);
+        self.archives.apply_val(archives);
+        self.checksums.apply_val(checksums);
+        self.ignored.apply_val(ignored);
+    }
+}
+
+impl MatchingConfig {
+    /// Every pattern, for validating them all at once
+    pub fn patterns(&self) -> impl Iterator<Item = &String> {
+        self.installers
+            .iter()
+            .chain(&self.archives)
+            .chain(&self.checksums)
+            .chain(&self.ignored)
+    }
+}
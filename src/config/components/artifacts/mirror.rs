@@ -0,0 +1,63 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ApplyLayer, ApplyValExt};
+
+/// Config for mirroring release files on the site itself (complete version)
+#[derive(Debug, Clone)]
+pub struct MirrorConfig {
+    /// Glob patterns for the files to mirror. Empty means every file.
+    pub files: Vec<String>,
+    /// Files larger than this many megabytes aren't mirrored
+    pub max_size_mb: u64,
+    /// Whether to skip files we can't find a checksum for
+    pub require_checksum: bool,
+}
+
+/// Settings for serving your latest release's files from your site instead of from
+/// where they were published
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MirrorLayer {
+    /// Glob patterns (like `"*.tar.gz"`) for the files to mirror.
+    ///
+    /// By default every file in the release is mirrored.
+    pub files: Option<Vec<String>>,
+    /// The maximum size of a file to mirror, in megabytes. Larger files keep linking to
+    /// where they were published.
+    ///
+    /// This defaults to 100.
+    pub max_size_mb: Option<u64>,
+    /// Whether to only mirror files that have a checksum file (like cargo-dist's
+    /// `.sha256` files) in the release.
+    ///
+    /// Files with a checksum are always verified, and not mirrored if they don't match.
+    ///
+    /// This defaults to false.
+    pub require_checksum: Option<bool>,
+}
+
+impl Default for MirrorConfig {
+    fn default() -> Self {
+        MirrorConfig {
+            files: Vec::new(),
+            max_size_mb: 100,
+            require_checksum: false,
+        }
+    }
+}
+
+impl ApplyLayer for MirrorConfig {
+    type Layer = MirrorLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let MirrorLayer {
+            files,
+            max_size_mb,
+            require_checksum,
+        } = layer;
+        self.files.apply_val(files);
+        self.max_size_mb.apply_val(max_size_mb);
+        self.require_checksum.apply_val(require_checksum);
+    }
+}
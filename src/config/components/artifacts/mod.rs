@@ -1,10 +1,19 @@
+use indexmap::IndexMap;
+use regex::Regex;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::config::{ApplyLayer, ApplyValExt};
+use crate::config::{ApplyBoolLayerExt, ApplyLayer, ApplyValExt, BoolOr};
+use crate::errors::*;
 
+mod channels;
+mod matching;
+mod mirror;
 mod package_managers;
-pub use package_managers::{PackageManagersConfig, PackageManagersLayer};
+pub use channels::ChannelConfig;
+pub use matching::{MatchingConfig, MatchingLayer};
+pub use mirror::{MirrorConfig, MirrorLayer};
+pub use package_managers::{Distro, DistroPackage, PackageManagersConfig, PackageManagersLayer};
 
 #[derive(Debug, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
@@ -41,6 +50,14 @@ pub struct ArtifactsConfig {
     pub match_package_names: bool,
     pub package_managers: PackageManagersConfig,
     pub hidden: Vec<String>,
+    pub check_downloads: bool,
+    pub mirror: Option<MirrorConfig>,
+    pub install_counter: bool,
+    pub downloads_badge: bool,
+    pub matching: MatchingConfig,
+    pub target_labels: IndexMap<String, String>,
+    pub channels: IndexMap<String, ChannelConfig>,
+    pub qr_codes: bool,
 }
 
 /// Setting for downloadable artifacts, installers, and package-managers
@@ -131,6 +148,99 @@ pub struct ArtifactsLayer {
     ///
     /// Example (hide auto-detect shell scripts): `"hidden": ["shell", "powershell"]`
     pub hidden: Option<Vec<String>>,
+    /// Whether to check that every file attached to your releases can still be downloaded
+    ///
+    /// GitHub lets you delete assets from old releases (and retention policies sometimes do
+    /// it for you). If this is enabled, we send a HEAD request for every file at build time,
+    /// and hide the ones that 404 from the install page and widget. Each release's changelog
+    /// page will also list its downloads, marking the ones that are gone.
+    ///
+    /// This defaults to false, since it means a request per file per release.
+    pub check_downloads: Option<bool>,
+    /// Whether to download the files of your latest release at build time, and serve them
+    /// from your site under `dl/<version>/` instead of linking to where they were published.
+    ///
+    /// This is useful if your releases are hosted somewhere that's slow to download from in
+    /// parts of the world. A `SHA256SUMS` file is written next to the mirrored files.
+    ///
+    /// This is disabled by default. It can be enabled with `"mirror": true`, or with more
+    /// precise settings using `"mirror": { ... }`.
+    pub mirror: Option<BoolOr<MirrorLayer>>,
+    /// Whether to add a tiny image to each platform in the install widget, so you can count
+    /// how often people look at the install instructions for each platform in your web
+    /// server's access logs.
+    ///
+    /// The images are static files at `counter/<version>/<target>.gif`, so no JavaScript or
+    /// third-party service is involved. They're only loaded once a platform's instructions
+    /// are shown, and every release gets new ones so that browser caches don't hide visitors.
+    ///
+    /// This defaults to false.
+    pub install_counter: Option<bool>,
+    /// Whether to show how many times your releases have been downloaded in total on your
+    /// index page, next to the install widget.
+    ///
+    /// The counts come from GitHub, so this does nothing for other release sources. Download
+    /// counts for each file are available to templates either way.
+    ///
+    /// This defaults to false.
+    pub downloads_badge: Option<bool>,
+    /// Regular expressions for classifying the files in your releases, for when their names
+    /// don't follow the conventions `auto` knows about.
+    ///
+    /// For example, to recommend `.pkg` files as installers, and never suggest the
+    /// `-debug` builds:
+    ///
+    /// ```json
+    /// {
+    ///   "components": {
+    ///     "artifacts": {
+    ///       "matching": {
+    ///         "installers": ["\\.pkg$"],
+    ///         "ignored": ["-debug"]
+    ///       }
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    pub matching: Option<MatchingLayer>,
+    /// Labels for target triples, like `"x86_64-unknown-linux-musl": "Linux (static)"`
+    ///
+    /// These replace the labels we'd otherwise show. Triples we don't know about can be added
+    /// here too, and we'll look for them in the names of your files like any other.
+    pub target_labels: Option<IndexMap<String, String>>,
+    /// Release channels to show on the install page besides your latest stable release,
+    /// keyed by their name
+    ///
+    /// For example, to add a "Nightly" section for the files of a rolling `nightly` tag:
+    ///
+    /// ```json
+    /// {
+    ///   "components": {
+    ///     "artifacts": {
+    ///       "channels": {
+    ///         "Nightly": {
+    ///           "tag": "nightly",
+    ///           "description": "Built from the main branch every night. These may be broken!"
+    ///         }
+    ///       }
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    pub channels: Option<IndexMap<String, ChannelConfig>>,
+    /// Whether to show QR codes on the install page, so that people can download your app
+    /// to their phone without typing out a link.
+    ///
+    /// There's a code next to each file that's for a phone or tablet (`.apk`, `.xapk` and `.ipa`
+    /// files, and anything built for an Android or iOS target), and one for your site's install
+    /// page if `project.homepage` is set. The codes are SVGs, made at build time.
+    ///
+    /// We don't recognize `.apk` and `.ipa` files as installers, or Android and iOS target
+    /// triples, on our own, so you may need to add them to `matching.installers` and
+    /// `target_labels` for them to show up at all.
+    ///
+    /// This defaults to false.
+    pub qr_codes: Option<bool>,
 }
 
 impl Default for ArtifactsConfig {
@@ -141,6 +251,14 @@ impl Default for ArtifactsConfig {
             match_package_names: false,
             package_managers: PackageManagersConfig::default(),
             hidden: vec![],
+            check_downloads: false,
+            mirror: None,
+            install_counter: false,
+            downloads_badge: false,
+            matching: MatchingConfig::default(),
+            target_labels: IndexMap::new(),
+            channels: IndexMap::new(),
+            qr_codes: false,
         }
     }
 }
@@ -154,6 +272,14 @@ impl ApplyLayer for ArtifactsConfig {
             match_package_names,
             package_managers,
             hidden,
+            check_downloads,
+            mirror,
+            install_counter,
+            downloads_badge,
+            matching,
+            target_labels,
+            channels,
+            qr_codes,
         } = layer;
 
         self.auto.apply_val(auto);
@@ -162,6 +288,14 @@ impl ApplyLayer for ArtifactsConfig {
         self.package_managers.apply_val_layer(package_managers);
         // In the future this might want to be `extend`
         self.hidden.apply_val(hidden);
+        self.check_downloads.apply_val(check_downloads);
+        self.mirror.apply_bool_layer(mirror);
+        self.install_counter.apply_val(install_counter);
+        self.downloads_badge.apply_val(downloads_badge);
+        self.matching.apply_val_layer(matching);
+        self.target_labels.apply_val(target_labels);
+        self.channels.apply_val(channels);
+        self.qr_codes.apply_val(qr_codes);
     }
 }
 
@@ -169,4 +303,18 @@ impl ArtifactsConfig {
     pub fn has_some(&self) -> bool {
         self.cargo_dist || self.auto || !self.package_managers.is_empty()
     }
+
+    /// Make sure the patterns in `matching` are valid regular expressions
+    pub fn validate(config: &Option<Self>) -> Result<()> {
+        let Some(this) = config else { return Ok(()) };
+        for pattern in this.matching.patterns() {
+            if let Err(e) = Regex::new(pattern) {
+                return Err(OrandaError::InvalidArtifactPattern {
+                    pattern: pattern.clone(),
+                    details: e.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
 }
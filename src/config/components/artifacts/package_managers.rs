@@ -9,6 +9,7 @@ use crate::config::{ApplyLayer, ApplyValExt};
 pub struct PackageManagersConfig {
     pub preferred: IndexMap<String, String>,
     pub additional: IndexMap<String, String>,
+    pub distros: IndexMap<Distro, DistroPackage>,
 }
 /// Package managers to display
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -22,6 +23,80 @@ pub struct PackageManagersLayer {
     ///
     /// See docs for the parent "package_managers" field for details
     pub additional: Option<IndexMap<String, String>>,
+    /// Packages in Linux distributions, shown as a grid on the install page
+    ///
+    /// For example:
+    ///
+    /// ```json
+    /// {
+    ///   "debian": {
+    ///     "package": "axolotlsay",
+    ///     "repository": "deb [signed-by=/usr/share/keyrings/axolotlsay.gpg] https://apt.example.com stable main",
+    ///     "key": "https://apt.example.com/key.gpg"
+    ///   },
+    ///   "fedora": { "package": "axolotlsay", "repository": "axodotdev/axolotlsay" },
+    ///   "aur": { "package": "axolotlsay-bin" },
+    ///   "nix": { "package": "axolotlsay" }
+    /// }
+    /// ```
+    pub distros: Option<IndexMap<Distro, DistroPackage>>,
+}
+
+/// A Linux distribution (or distribution-like package collection) we know how to install
+/// packages from
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Distro {
+    Debian,
+    Ubuntu,
+    Fedora,
+    Arch,
+    Aur,
+    Nix,
+}
+
+impl Distro {
+    /// The id used in config, like "debian"
+    pub fn id(self) -> &'static str {
+        match self {
+            Distro::Debian => "debian",
+            Distro::Ubuntu => "ubuntu",
+            Distro::Fedora => "fedora",
+            Distro::Arch => "arch",
+            Distro::Aur => "aur",
+            Distro::Nix => "nix",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Distro::Debian => "Debian",
+            Distro::Ubuntu => "Ubuntu",
+            Distro::Fedora => "Fedora",
+            Distro::Arch => "Arch Linux",
+            Distro::Aur => "AUR",
+            Distro::Nix => "Nix",
+        }
+    }
+}
+
+/// How to find your package in a distribution
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DistroPackage {
+    /// The name of the package (for nix, the attribute in nixpkgs)
+    pub package: String,
+    /// Where the package is published, if it's not in the distribution's own repositories.
+    ///
+    /// For Debian and Ubuntu this is the line for your apt sources list, and for Fedora it's
+    /// the name of your copr project, like "owner/project".
+    pub repository: Option<String>,
+    /// The URL of the key your apt repository is signed with
+    pub key: Option<String>,
+    /// The install command(s) to show, if you'd rather not use the ones we come up with
+    pub command: Option<String>,
+    /// A path or URL to a logo to show, instead of the distribution's name
+    pub logo: Option<String>,
 }
 
 impl Default for PackageManagersConfig {
@@ -29,6 +104,7 @@ impl Default for PackageManagersConfig {
         PackageManagersConfig {
             preferred: IndexMap::default(),
             additional: IndexMap::default(),
+            distros: IndexMap::default(),
         }
     }
 }
@@ -39,10 +115,12 @@ impl ApplyLayer for PackageManagersConfig {
         let PackageManagersLayer {
             preferred,
             additional,
+            distros,
         } = layer;
         // In the future these might want to be `extend`
         self.preferred.apply_val(preferred);
         self.additional.apply_val(additional);
+        self.distros.apply_val(distros);
     }
 }
 
@@ -54,6 +132,6 @@ impl PackageManagersConfig {
         self.has("npm") || self.has("npx")
     }
     pub fn is_empty(&self) -> bool {
-        self.preferred.is_empty() && self.additional.is_empty()
+        self.preferred.is_empty() && self.additional.is_empty() && self.distros.is_empty()
     }
 }
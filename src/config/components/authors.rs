@@ -0,0 +1,60 @@
+use indexmap::IndexMap;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ApplyLayer, ApplyValExt};
+
+/// Config for author profiles (complete version)
+#[derive(Debug, Clone)]
+pub struct AuthorsConfig {
+    /// Author profiles, keyed by the id posts refer to them by
+    pub people: IndexMap<String, AuthorProfile>,
+}
+
+/// The config for author profiles, used for bylines and the "authors" page
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AuthorsLayer {
+    /// A map from an id (like "jdoe") to an author's profile.
+    ///
+    /// Blog posts can refer to an author by their id, their name, or their GitHub username
+    /// in their `author` or `authors` frontmatter keys. Releases are attributed to whoever
+    /// published them on GitHub, matched by `github` username.
+    pub people: Option<IndexMap<String, AuthorProfile>>,
+}
+
+/// A single author's profile
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AuthorProfile {
+    /// The name to show in bylines
+    pub name: String,
+    /// A path or URL to an avatar image.
+    ///
+    /// If not set, we'll use the GitHub avatar for `github` if that's set.
+    pub avatar: Option<String>,
+    /// The author's GitHub username
+    pub github: Option<String>,
+    /// A short bio for the authors page
+    pub bio: Option<String>,
+    /// Links to show on the authors page, as a map from label to URL
+    #[serde(default)]
+    pub links: IndexMap<String, String>,
+}
+
+impl Default for AuthorsConfig {
+    fn default() -> Self {
+        AuthorsConfig {
+            people: IndexMap::new(),
+        }
+    }
+}
+
+impl ApplyLayer for AuthorsConfig {
+    type Layer = AuthorsLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let AuthorsLayer { people } = layer;
+        self.people.apply_val(people);
+    }
+}
@@ -0,0 +1,62 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::config::{ApplyLayer, ApplyValExt};
+use crate::errors::*;
+
+/// The default location criterion writes its results to
+const CRITERION_DIR: &str = "target/criterion";
+
+/// Config for the benchmarks page (complete version)
+#[derive(Debug, Clone)]
+pub struct BenchmarksConfig {
+    /// Paths or URLs to benchmark results
+    pub files: Vec<String>,
+}
+
+/// The config for rendering benchmark results on a benchmarks page
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BenchmarksLayer {
+    /// A list of benchmark results to render. Each entry can be a path or a URL (e.g. to a
+    /// file attached to one of your releases) pointing to either:
+    ///
+    /// - a JSON file exported with `hyperfine --export-json`
+    /// - a criterion `estimates.json` file
+    /// - a directory containing criterion results, like `target/criterion`
+    ///
+    /// If not set we will use "./target/criterion" if it exists.
+    pub files: Option<Vec<String>>,
+}
+
+impl Default for BenchmarksConfig {
+    fn default() -> Self {
+        BenchmarksConfig { files: Vec::new() }
+    }
+}
+
+impl ApplyLayer for BenchmarksConfig {
+    type Layer = BenchmarksLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let BenchmarksLayer { files } = layer;
+        self.files.apply_val(files);
+    }
+}
+
+impl BenchmarksConfig {
+    /// If benchmarks are enabled but no files are set, we try to find criterion's output
+    pub fn find_paths(config: &mut Option<Self>, start_dir: &Path) -> Result<()> {
+        // If this is None, we were never enabled and shouldn't auto-detect
+        let Some(this) = config else { return Ok(()) };
+
+        if this.files.is_empty() {
+            let default_dir = start_dir.join(CRITERION_DIR);
+            if default_dir.is_dir() {
+                this.files.push(default_dir.display().to_string());
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::config::{ApplyLayer, ApplyOptExt, ApplyValExt};
+use crate::errors::*;
+
+/// The directory we look for posts in if none is configured
+const DEFAULT_POSTS_DIR: &str = "posts";
+
+/// Config for the blog (complete version)
+#[derive(Debug, Clone)]
+pub struct BlogConfig {
+    /// Path to the directory containing posts
+    pub path: Option<String>,
+    /// How many posts to list on each page of the blog index
+    pub posts_per_page: usize,
+    /// Whether to generate an RSS feed for the blog
+    pub rss_feed: bool,
+}
+
+/// The config for publishing blog posts on your site
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BlogLayer {
+    /// Path to a directory of markdown files to publish as blog posts.
+    ///
+    /// Each post can start with a YAML frontmatter block (between two `---` lines) with
+    /// `title`, `date` (`YYYY-MM-DD`), `author`, `authors`, `tags`, `description` and `draft`
+    /// keys.
+    ///
+    /// By default we look for a "./posts/" directory.
+    pub path: Option<String>,
+    /// How many posts to show on each page of the blog index.
+    ///
+    /// defaults to 10
+    pub posts_per_page: Option<usize>,
+    /// Whether to generate an RSS feed for the blog under `blog.rss`.
+    ///
+    /// defaults to true
+    pub rss_feed: Option<bool>,
+}
+
+impl Default for BlogConfig {
+    fn default() -> Self {
+        BlogConfig {
+            path: None,
+            posts_per_page: 10,
+            rss_feed: true,
+        }
+    }
+}
+
+impl ApplyLayer for BlogConfig {
+    type Layer = BlogLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let BlogLayer {
+            path,
+            posts_per_page,
+            rss_feed,
+        } = layer;
+        self.path.apply_opt(path);
+        self.posts_per_page.apply_val(posts_per_page);
+        self.rss_feed.apply_val(rss_feed);
+    }
+}
+
+impl BlogConfig {
+    /// If we don't have a posts directory, try to find it. If we fail, we disable the blog.
+    pub fn find_paths(config: &mut Option<Self>, start_dir: &Path) -> Result<()> {
+        // If this is None, we were force-disabled and shouldn't auto-detect
+        let Some(this) = config else { return Ok(()) };
+
+        match &this.path {
+            Some(path) => {
+                if !start_dir.join(path).is_dir() {
+                    return Err(OrandaError::PathDoesNotExist { path: path.clone() });
+                }
+            }
+            None => {
+                if start_dir.join(DEFAULT_POSTS_DIR).is_dir() {
+                    this.path = Some(DEFAULT_POSTS_DIR.to_owned());
+                } else {
+                    *config = None;
+                }
+            }
+        }
+        Ok(())
+    }
+}
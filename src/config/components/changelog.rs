@@ -7,8 +7,20 @@ use serde::{Deserialize, Serialize};
 pub struct ChangelogConfig {
     /// Whether to attempt to read from the local changelog file
     pub read_changelog_file: bool,
+    /// Changelog files to read from instead of the autodetected one
+    pub files: Vec<String>,
     /// Whether to generate a RSS file
     pub rss_feed: bool,
+    /// Whether to generate a separate RSS file with only prereleases
+    pub prerelease_rss_feed: bool,
+    /// Whether to write the changelog as JSON
+    pub json: bool,
+    /// Whether to write the "what's new" widget
+    pub widget: bool,
+    /// Where to get changelog entries from when there's nothing else to go on
+    pub source: ChangelogSource,
+    /// Guides for upgrading to the releases that need one
+    pub upgrade_guides: Vec<UpgradeGuide>,
 }
 
 /// The config for generating a separate changelog page
@@ -18,15 +30,79 @@ pub struct ChangelogLayer {
     /// it, and try and match version headings to release versions that we
     /// detect.
     pub read_changelog_file: Option<bool>,
+    /// A list of changelog files to read from, instead of the one we autodetect.
+    ///
+    /// If more than one of them has an entry for a release, the entries get merged, with each
+    /// one labeled with the name of the directory its file is in. This is useful for workspaces
+    /// that keep a separate CHANGELOG.md for each package.
+    pub files: Option<Vec<String>>,
     /// Whether to generate a RSS file under `changelog.rss`.
     pub rss_feed: Option<bool>,
+    /// Whether to generate a separate RSS file under `changelog-prerelease.rss` that only
+    /// contains prereleases, for people who want to test upcoming versions.
+    ///
+    /// defaults to false
+    pub prerelease_rss_feed: Option<bool>,
+    /// Whether to write every release's changelog entry, as both markdown and HTML, to
+    /// `changelog.json`, so that other tools (like an in-app "what's new" dialog) can reuse
+    /// them.
+    ///
+    /// defaults to true
+    pub json: Option<bool>,
+    /// Whether to write `widgets/whats-new.html`, a small page with the notes for the latest
+    /// release that your app can show in an iframe (or fetch) after it's been updated.
+    ///
+    /// defaults to true
+    pub widget: Option<bool>,
+    /// Where to get changelog entries from, if neither your changelog file nor your
+    /// release bodies have anything for a given release.
+    ///
+    /// Setting this to `"git"` makes oranda group the conventional commit messages
+    /// between a release's tag and the previous tag into feature/fix/chore sections.
+    pub source: Option<ChangelogSource>,
+    /// Migration guides for your breaking releases. Each one is rendered as its own page,
+    /// and linked from the changelog entries of the releases it's for, like
+    /// `{ "path": "docs/upgrading-to-2.md", "versions": ">=2.0.0, <2.1.0" }`.
+    pub upgrade_guides: Option<Vec<UpgradeGuide>>,
+}
+
+/// A guide for upgrading to some releases
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UpgradeGuide {
+    /// The markdown file with the guide
+    pub path: String,
+    /// The releases the guide is for, as a semver requirement like `">=2.0.0, <2.1.0"` or
+    /// `"^2"`. Releases whose tags don't parse as versions (after a leading `v` or package
+    /// name) never match.
+    pub versions: String,
+    /// What to call the guide in the changelog. Defaults to "Upgrade guide".
+    pub title: Option<String>,
+}
+
+/// Where changelog entries come from. Possible values:
+///
+/// - "releases" (default): Only use the changelog file and release bodies.
+/// - "git": Additionally fall back to generating entries from conventional commits in the
+///   local git history.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangelogSource {
+    Releases,
+    Git,
 }
 
 impl Default for ChangelogConfig {
     fn default() -> Self {
         ChangelogConfig {
             read_changelog_file: true,
+            files: Vec::new(),
             rss_feed: true,
+            prerelease_rss_feed: false,
+            json: true,
+            widget: true,
+            source: ChangelogSource::Releases,
+            upgrade_guides: Vec::new(),
         }
     }
 }
@@ -37,9 +113,21 @@ impl ApplyLayer for ChangelogConfig {
         // This is intentionally written slightly cumbersome to make you update this
         let ChangelogLayer {
             read_changelog_file,
+            files,
             rss_feed,
+            prerelease_rss_feed,
+            json,
+            widget,
+            source,
+            upgrade_guides,
         } = layer;
         self.read_changelog_file.apply_val(read_changelog_file);
+        self.files.apply_val(files);
         self.rss_feed.apply_val(rss_feed);
+        self.prerelease_rss_feed.apply_val(prerelease_rss_feed);
+        self.json.apply_val(json);
+        self.widget.apply_val(widget);
+        self.source.apply_val(source);
+        self.upgrade_guides.apply_val(upgrade_guides);
     }
 }
@@ -0,0 +1,97 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ApplyLayer, ApplyOptExt, ApplyValExt};
+
+/// Config for comment threads on release pages and blog posts (complete version)
+#[derive(Debug, Clone)]
+pub struct CommentsConfig {
+    /// Where the comments are kept
+    pub provider: CommentsProvider,
+    /// The provider's theme, or None for the one that follows the visitor's color scheme
+    pub theme: Option<String>,
+}
+
+/// The config for comment threads on release pages and blog posts, kept on GitHub
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CommentsLayer {
+    /// Where the comments are kept.
+    ///
+    /// Defaults to utterances, with your project's repository
+    pub provider: Option<CommentsProvider>,
+    /// The name of the provider's theme to use, like "github-dark".
+    ///
+    /// If not set we will use the one that follows the visitor's light or dark mode.
+    pub theme: Option<String>,
+}
+
+/// A comments provider
+///
+/// Comments providers are mutually exclusive -- you can pick at most one.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentsProvider {
+    /// Use giscus, which keeps comments in GitHub Discussions
+    Giscus(Giscus),
+    /// Use utterances, which keeps comments in GitHub issues
+    Utterances(Utterances),
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Giscus {
+    /// The repository with the discussions, like "axodotdev/oranda"
+    ///
+    /// Defaults to your project's repository
+    pub repo: Option<String>,
+    /// The repository's ID, from giscus.app
+    pub repo_id: String,
+    /// The name of the discussion category new threads are started in
+    pub category: String,
+    /// The category's ID, from giscus.app
+    pub category_id: String,
+    /// How pages are matched to discussions, like "pathname" or "title"
+    ///
+    /// Defaults to "pathname"
+    pub mapping: Option<String>,
+    /// Whether to show reactions to the page above the comments
+    ///
+    /// Defaults to true
+    pub reactions: Option<bool>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Utterances {
+    /// The repository with the issues, like "axodotdev/oranda"
+    ///
+    /// Defaults to your project's repository
+    pub repo: Option<String>,
+    /// How pages are matched to issues, like "pathname" or "title"
+    ///
+    /// Defaults to "pathname"
+    pub issue_term: Option<String>,
+    /// The label given to the issues utterances opens
+    pub label: Option<String>,
+}
+
+impl Default for CommentsConfig {
+    fn default() -> Self {
+        CommentsConfig {
+            provider: CommentsProvider::Utterances(Utterances::default()),
+            theme: None,
+        }
+    }
+}
+
+impl ApplyLayer for CommentsConfig {
+    type Layer = CommentsLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let CommentsLayer { provider, theme } = layer;
+        // Providers don't have anything in common, so a new one clobbers the old one
+        self.provider.apply_val(provider);
+        self.theme.apply_opt(theme);
+    }
+}
@@ -0,0 +1,98 @@
+use camino::Utf8PathBuf;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::config::{ApplyLayer, ApplyOptExt};
+use crate::errors::*;
+
+/// The files people look for around a project's community, like its code of conduct
+const CODE_OF_CONDUCT: &str = "CODE_OF_CONDUCT.md";
+const CONTRIBUTING: &str = "CONTRIBUTING.md";
+const GOVERNANCE: &str = "GOVERNANCE.md";
+
+/// Config for the code of conduct, contributing and governance pages (complete version)
+#[derive(Debug, Clone, Default)]
+pub struct CommunityConfig {
+    pub code_of_conduct: Option<String>,
+    pub contributing: Option<String>,
+    pub governance: Option<String>,
+}
+
+/// The config for turning your code of conduct, contributing guide and governance docs into
+/// pages, linked from the footer
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CommunityLayer {
+    /// A relative path to your code of conduct
+    ///
+    /// By default we try to find this at "./CODE_OF_CONDUCT.md" or
+    /// "./.github/CODE_OF_CONDUCT.md"
+    pub code_of_conduct: Option<String>,
+    /// A relative path to your contributing guide
+    ///
+    /// By default we try to find this at "./CONTRIBUTING.md" or "./.github/CONTRIBUTING.md"
+    pub contributing: Option<String>,
+    /// A relative path to a description of how your project is governed
+    ///
+    /// By default we try to find this at "./GOVERNANCE.md" or "./.github/GOVERNANCE.md"
+    pub governance: Option<String>,
+}
+
+impl ApplyLayer for CommunityConfig {
+    type Layer = CommunityLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let CommunityLayer {
+            code_of_conduct,
+            contributing,
+            governance,
+        } = layer;
+        self.code_of_conduct.apply_opt(code_of_conduct);
+        self.contributing.apply_opt(contributing);
+        self.governance.apply_opt(governance);
+    }
+}
+
+impl CommunityConfig {
+    /// If any paths are missing, try to auto-detect them. If we don't find any of the files,
+    /// there are no pages to build, so the component turns itself off.
+    pub fn find_paths(config: &mut Option<Self>, start_dir: &Path) -> Result<()> {
+        // If this is None, we were force-disabled and shouldn't auto-detect
+        let Some(this) = config else { return Ok(()) };
+
+        // This is intentionally written slightly cumbersome to make you update this
+        let CommunityConfig {
+            code_of_conduct,
+            contributing,
+            governance,
+        } = this;
+        for (path, file_name) in [
+            (code_of_conduct, CODE_OF_CONDUCT),
+            (contributing, CONTRIBUTING),
+            (governance, GOVERNANCE),
+        ] {
+            if path.is_none() {
+                *path = find_file(start_dir, file_name);
+            }
+        }
+
+        if this.code_of_conduct.is_none()
+            && this.contributing.is_none()
+            && this.governance.is_none()
+        {
+            *config = None;
+        }
+        Ok(())
+    }
+}
+
+/// Looks for `file_name` in the project's directory, then in its `.github` directory, like
+/// GitHub does
+fn find_file(start_dir: &Path, file_name: &str) -> Option<String> {
+    ["", ".github/"]
+        .iter()
+        .map(|dir| Utf8PathBuf::from(format!("{}/{dir}{file_name}", start_dir.display())))
+        .find(|path| path.exists())
+        .map(|path| path.to_string())
+}
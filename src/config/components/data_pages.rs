@@ -0,0 +1,91 @@
+use indexmap::IndexMap;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::config::{ApplyLayer, ApplyValExt};
+use crate::errors::*;
+
+/// The template data pages are rendered with if they don't name one
+pub const DEFAULT_DATA_PAGE_TEMPLATE: &str = "data_page.html";
+
+/// Config for pages rendered from data files (complete version)
+#[derive(Debug, Clone, Default)]
+pub struct DataPagesConfig {
+    pub pages: IndexMap<String, DataPage>,
+}
+
+/// The config for pages rendered from JSON, TOML or YAML files
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DataPagesLayer {
+    /// A map from a page's path on your site (like "plugins", for "/plugins/") to the page
+    pub pages: Option<IndexMap<String, DataPage>>,
+}
+
+/// A single page rendered from a data file
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DataPage {
+    /// Relative path to the JSON, TOML or YAML file with the page's data
+    pub data: String,
+    /// The template to render the page with, like "plugins.html" for a "plugins.html.j2" in
+    /// your `build.template_dir`. The data is available to it as `page.data`.
+    ///
+    /// Defaults to a built-in template that shows a list of objects as a table, and anything
+    /// else as JSON.
+    pub template: Option<String>,
+    /// The page's title, and its name in the nav
+    ///
+    /// Defaults to the page's path
+    pub title: Option<String>,
+    /// Whether to link to the page in the nav, after your additional pages
+    ///
+    /// Defaults to true
+    pub nav: Option<bool>,
+    /// Where the page goes in the nav, among your additional pages. Lower weights come first,
+    /// and pages with the same weight keep the order they're configured in.
+    ///
+    /// Defaults to 0
+    pub weight: Option<i32>,
+}
+
+impl DataPage {
+    pub fn template(&self) -> &str {
+        self.template
+            .as_deref()
+            .unwrap_or(DEFAULT_DATA_PAGE_TEMPLATE)
+    }
+
+    pub fn in_nav(&self) -> bool {
+        self.nav.unwrap_or(true)
+    }
+
+    pub fn weight(&self) -> i32 {
+        self.weight.unwrap_or(0)
+    }
+}
+
+impl ApplyLayer for DataPagesConfig {
+    type Layer = DataPagesLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let DataPagesLayer { pages } = layer;
+        self.pages.apply_val(pages);
+    }
+}
+
+impl DataPagesConfig {
+    /// Make sure every page's data file exists
+    pub fn validate(config: &Option<Self>, start_dir: &Path) -> Result<()> {
+        let Some(this) = config else { return Ok(()) };
+        for page in this.pages.values() {
+            if !start_dir.join(&page.data).is_file() {
+                return Err(OrandaError::PathDoesNotExist {
+                    path: page.data.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ApplyLayer, ApplyValExt};
+use crate::errors::*;
+
+/// Config for embedding a web demo on the front page (complete version)
+#[derive(Debug, Clone)]
+pub struct DemoConfig {
+    /// The directory with the demo, relative to the oranda.json
+    pub path: String,
+    /// The page to embed, relative to `path`
+    pub entry: String,
+    /// The heading above the demo
+    pub title: String,
+    /// How tall the demo's frame is, in pixels
+    pub height: u32,
+}
+
+/// The config for putting a web demo of your project, like a WASM build of your CLI or an
+/// example using your library, on your site and embedding it on your front page
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DemoLayer {
+    /// The directory your demo is built to, relative to your oranda.json, like "./www/dist" or
+    /// the "pkg" directory wasm-pack made plus an index.html that loads it
+    ///
+    /// Its contents are copied to "/demo/" on your site.
+    pub path: Option<String>,
+    /// The page in `path` to embed
+    ///
+    /// Defaults to "index.html"
+    pub entry: Option<String>,
+    /// The heading to show above the demo
+    ///
+    /// Defaults to "Try it out"
+    pub title: Option<String>,
+    /// How tall the demo should be on the page, in pixels
+    ///
+    /// Defaults to 480
+    pub height: Option<u32>,
+}
+
+impl Default for DemoConfig {
+    fn default() -> Self {
+        DemoConfig {
+            path: String::new(),
+            entry: "index.html".to_owned(),
+            title: "Try it out".to_owned(),
+            height: 480,
+        }
+    }
+}
+
+impl ApplyLayer for DemoConfig {
+    type Layer = DemoLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let DemoLayer {
+            path,
+            entry,
+            title,
+            height,
+        } = layer;
+        self.path.apply_val(path);
+        self.entry.apply_val(entry);
+        self.title.apply_val(title);
+        self.height.apply_val(height);
+    }
+}
+
+impl DemoConfig {
+    /// We can't guess where a demo is built to, so make sure we've been told
+    pub fn validate(config: &Option<Self>) -> Result<()> {
+        let Some(this) = config else { return Ok(()) };
+        if this.path.trim().is_empty() || this.entry.trim().is_empty() {
+            return Err(OrandaError::DemoConfigInvalid);
+        }
+        Ok(())
+    }
+}
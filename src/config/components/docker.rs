@@ -0,0 +1,145 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ApplyLayer, ApplyValExt};
+use crate::errors::*;
+
+/// Config for the Docker section of the install page (complete version)
+#[derive(Debug, Clone)]
+pub struct DockerConfig {
+    /// The image's name without a registry, like "axodotdev/oranda"
+    pub image: String,
+    /// The registries the image is published to, in order of preference
+    pub registries: Vec<DockerRegistry>,
+    /// The tags to show, the first of which is used in the commands
+    pub tags: Vec<String>,
+    /// Options for `docker run`, placed before the image
+    pub run_args: String,
+    /// Whether to fetch the image's tags from the registry while building
+    pub fetch_tags: bool,
+    /// How many fetched tags to show
+    pub max_tags: usize,
+}
+
+/// The config for showing how to pull and run your Docker image on the install page
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DockerLayer {
+    /// The name of your image, without a registry or tag, like "axodotdev/oranda"
+    pub image: Option<String>,
+    /// The registries your image is published to. The first one is used for the commands
+    /// we show, and every one gets a link. Possible values are "dockerhub", "ghcr" and "quay".
+    ///
+    /// Defaults to ["dockerhub"]
+    pub registries: Option<Vec<DockerRegistry>>,
+    /// The tags to show. The first one is used in the `docker pull` and `docker run` commands.
+    ///
+    /// Defaults to ["latest"]
+    pub tags: Option<Vec<String>>,
+    /// Options to pass to `docker run`, placed before the image, like "--rm -p 8080:8080"
+    ///
+    /// Defaults to "--rm -it"
+    pub run_args: Option<String>,
+    /// Whether to fetch the tags your image has from the first registry while building, and
+    /// list them below the commands.
+    ///
+    /// If the registry can't be reached we warn about it and only show `tags`.
+    ///
+    /// Defaults to false
+    pub fetch_tags: Option<bool>,
+    /// How many fetched tags to list.
+    ///
+    /// Defaults to 10
+    pub max_tags: Option<usize>,
+}
+
+/// A container registry. Possible values:
+///
+/// - "dockerhub": Docker Hub (docker.io)
+/// - "ghcr": GitHub Container Registry (ghcr.io)
+/// - "quay": Quay (quay.io)
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DockerRegistry {
+    DockerHub,
+    Ghcr,
+    Quay,
+}
+
+impl DockerRegistry {
+    pub fn name(self) -> &'static str {
+        match self {
+            DockerRegistry::DockerHub => "Docker Hub",
+            DockerRegistry::Ghcr => "GitHub Container Registry",
+            DockerRegistry::Quay => "Quay",
+        }
+    }
+
+    /// The image as it's pulled from this registry. Docker Hub is the default, so its images
+    /// don't need a host.
+    pub fn reference(self, image: &str) -> String {
+        match self {
+            DockerRegistry::DockerHub => image.to_owned(),
+            DockerRegistry::Ghcr => format!("ghcr.io/{image}"),
+            DockerRegistry::Quay => format!("quay.io/{image}"),
+        }
+    }
+
+    /// The image's page on the registry's website
+    pub fn url(self, image: &str) -> String {
+        match self {
+            DockerRegistry::DockerHub if !image.contains('/') => {
+                format!("https://hub.docker.com/_/{image}")
+            }
+            DockerRegistry::DockerHub => format!("https://hub.docker.com/r/{image}"),
+            // This redirects to the package's page on GitHub
+            DockerRegistry::Ghcr => format!("https://ghcr.io/{image}"),
+            DockerRegistry::Quay => format!("https://quay.io/repository/{image}"),
+        }
+    }
+}
+
+impl Default for DockerConfig {
+    fn default() -> Self {
+        DockerConfig {
+            image: String::new(),
+            registries: vec![DockerRegistry::DockerHub],
+            tags: vec!["latest".to_owned()],
+            run_args: "--rm -it".to_owned(),
+            fetch_tags: false,
+            max_tags: 10,
+        }
+    }
+}
+
+impl ApplyLayer for DockerConfig {
+    type Layer = DockerLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let DockerLayer {
+            image,
+            registries,
+            tags,
+            run_args,
+            fetch_tags,
+            max_tags,
+        } = layer;
+        self.image.apply_val(image);
+        self.registries.apply_val(registries);
+        self.tags.apply_val(tags);
+        self.run_args.apply_val(run_args);
+        self.fetch_tags.apply_val(fetch_tags);
+        self.max_tags.apply_val(max_tags);
+    }
+}
+
+impl DockerConfig {
+    /// We can't guess the image, so make sure we've been told about it
+    pub fn validate(config: &Option<Self>) -> Result<()> {
+        let Some(this) = config else { return Ok(()) };
+        if this.image.trim().is_empty() || this.registries.is_empty() || this.tags.is_empty() {
+            return Err(OrandaError::DockerConfigInvalid);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ApplyLayer, ApplyValExt};
+use crate::errors::*;
+
+/// Config for building docs with an external tool (complete version)
+#[derive(Debug, Clone)]
+pub struct DocsConfig {
+    /// The shell command that builds the docs
+    pub command: String,
+    /// Where the command writes the built docs, relative to `path`
+    pub output_dir: String,
+    /// The directory to run the command in
+    pub path: String,
+    /// Paths that `oranda dev` should rebuild the docs for when they change
+    pub watch: Vec<String>,
+}
+
+/// The config for building docs with a tool other than mdbook, like Docusaurus, mkdocs or
+/// Sphinx, and serving them on your site
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DocsLayer {
+    /// The command that builds your docs, like "mkdocs build" or "npm run build"
+    ///
+    /// This is run with `sh -c` (`cmd /C` on Windows), so it can use pipes, `&&` and so on.
+    pub command: Option<String>,
+    /// The directory your docs are built to, relative to `path`, like "site" for mkdocs or
+    /// "_build/html" for Sphinx
+    ///
+    /// Its contents are copied to "/docs/" on your site.
+    pub output_dir: Option<String>,
+    /// The directory to run `command` in, relative to your oranda.json
+    ///
+    /// Defaults to "./"
+    pub path: Option<String>,
+    /// Files or directories that `oranda dev` should watch, rebuilding your docs when they
+    /// change, like "./docs/" and "./mkdocs.yml". These are relative to your oranda.json.
+    ///
+    /// Don't include `output_dir` here, or every build will trigger another one!
+    pub watch: Option<Vec<String>>,
+}
+
+impl Default for DocsConfig {
+    fn default() -> Self {
+        DocsConfig {
+            command: String::new(),
+            output_dir: String::new(),
+            path: "./".to_owned(),
+            watch: Vec::new(),
+        }
+    }
+}
+
+impl ApplyLayer for DocsConfig {
+    type Layer = DocsLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let DocsLayer {
+            command,
+            output_dir,
+            path,
+            watch,
+        } = layer;
+        self.command.apply_val(command);
+        self.output_dir.apply_val(output_dir);
+        self.path.apply_val(path);
+        self.watch.apply_val(watch);
+    }
+}
+
+impl DocsConfig {
+    /// There's nothing we can guess for these docs, so make sure we've been told how to
+    /// build them
+    pub fn validate(config: &Option<Self>) -> Result<()> {
+        let Some(this) = config else { return Ok(()) };
+        if this.command.trim().is_empty() || this.output_dir.trim().is_empty() {
+            return Err(OrandaError::DocsConfigInvalid);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ApplyLayer, ApplyValExt};
+
+/// Config for the feature flags page (complete version)
+#[derive(Debug, Clone)]
+pub struct FeaturesConfig {
+    /// The Cargo.toml to read `[features]` from
+    pub manifest_path: String,
+    /// Features to leave off the page
+    pub hidden: Vec<String>,
+}
+
+/// The config for the feature flags page
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FeaturesLayer {
+    /// The Cargo.toml to read `[features]` from.
+    ///
+    /// defaults to "./Cargo.toml"
+    pub manifest_path: Option<String>,
+    /// Features to leave off the page, like ones that only exist for your tests.
+    ///
+    /// Features whose names start with an underscore are always left off.
+    pub hidden: Option<Vec<String>>,
+}
+
+impl Default for FeaturesConfig {
+    fn default() -> Self {
+        FeaturesConfig {
+            manifest_path: "./Cargo.toml".to_owned(),
+            hidden: vec![],
+        }
+    }
+}
+
+impl ApplyLayer for FeaturesConfig {
+    type Layer = FeaturesLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let FeaturesLayer {
+            manifest_path,
+            hidden,
+        } = layer;
+        self.manifest_path.apply_val(manifest_path);
+        self.hidden.apply_val(hidden);
+    }
+}
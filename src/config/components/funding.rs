@@ -1,9 +1,10 @@
 use camino::Utf8PathBuf;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 
-use crate::config::{ApplyLayer, ApplyOptExt};
+use crate::config::{ApplyLayer, ApplyOptExt, ApplyValExt};
 use crate::data::funding::FundingType;
 use crate::errors::*;
 
@@ -13,6 +14,10 @@ pub struct FundingConfig {
     pub preferred_funding: Option<FundingType>,
     pub yml_path: Option<String>,
     pub md_path: Option<String>,
+    pub monthly_goal: Option<u64>,
+    pub names: HashMap<FundingType, String>,
+    pub order: Vec<FundingType>,
+    pub links: Vec<FundingLink>,
 }
 /// Settings for displaying funding information on your page
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -31,6 +36,64 @@ pub struct FundingLayer {
     ///
     /// By default we try to find this at "./funding.md"
     pub md_path: Option<String>,
+    /// A monthly funding goal in US dollars. If set, we fetch the GitHub Sponsors profile
+    /// listed under `github` in your FUNDING.yml and show the goal along with your sponsor
+    /// count and tiers. The progress bar follows the goal on your Sponsors profile.
+    ///
+    /// GitHub's API requires authentication for this, so it's only fetched if the
+    /// `GITHUB_TOKEN` environment variable is set.
+    pub monthly_goal: Option<u64>,
+    /// Display names to use instead of our defaults, keyed by funding platform,
+    /// e.g. `{ "github": "Sponsor me on GitHub" }`
+    pub names: Option<HashMap<FundingType, String>>,
+    /// The order to list funding platforms in, e.g. `["polar", "github"]`.
+    ///
+    /// Platforms you don't list are shown after the ones you do. Links from `links`
+    /// are placed wherever "custom" is.
+    pub order: Option<Vec<FundingType>>,
+    /// Additional funding links that can't be expressed in FUNDING.yml, like Stripe
+    /// payment links.
+    pub links: Option<Vec<FundingLink>>,
+}
+
+/// A custom funding link
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FundingLink {
+    /// The text to show for this link
+    pub label: String,
+    /// Where this link goes
+    pub url: String,
+    /// Either the name of one of our built-in icons ("github", "patreon", "opencollective",
+    /// "kofi", "tidelift", "liberapay", "polar", "buymeacoffee", "thanksdev", "stripe",
+    /// "web"), or the URL of an image.
+    ///
+    /// Images aren't copied into your site, so a local image has to be one your site already
+    /// serves, like a file in `build.static_dir`, linked with an absolute path
+    /// ("/static/stripe.svg").
+    pub icon: Option<String>,
+}
+
+/// Icons that [`FundingLink::icon`][] can refer to by name
+pub const BUILTIN_FUNDING_ICONS: &[&str] = &[
+    "github",
+    "patreon",
+    "opencollective",
+    "kofi",
+    "tidelift",
+    "liberapay",
+    "polar",
+    "buymeacoffee",
+    "thanksdev",
+    "stripe",
+    "web",
+];
+
+impl FundingLink {
+    /// Whether `icon` links to an image, rather than naming one of our icons
+    pub fn icon_is_url(icon: &str) -> bool {
+        icon.contains("://") || icon.starts_with('/')
+    }
 }
 
 impl Default for FundingConfig {
@@ -39,6 +102,10 @@ impl Default for FundingConfig {
             preferred_funding: None,
             yml_path: None,
             md_path: None,
+            monthly_goal: None,
+            names: HashMap::new(),
+            order: Vec::new(),
+            links: Vec::new(),
         }
     }
 }
@@ -50,10 +117,18 @@ impl ApplyLayer for FundingConfig {
             preferred_funding,
             yml_path,
             md_path,
+            monthly_goal,
+            names,
+            order,
+            links,
         } = layer;
         self.preferred_funding.apply_opt(preferred_funding);
         self.yml_path.apply_opt(yml_path);
         self.md_path.apply_opt(md_path);
+        self.monthly_goal.apply_opt(monthly_goal);
+        self.names.apply_val(names);
+        self.order.apply_val(order);
+        self.links.apply_val(links);
     }
 }
 
@@ -84,9 +159,28 @@ impl FundingConfig {
             preferred_funding,
             yml_path,
             md_path,
+            monthly_goal,
+            names,
+            order,
+            links,
         } = this;
-        let cant_find_files = yml_path.is_none() && md_path.is_none();
-        let has_user_config = preferred_funding.is_some();
+        for link in links.iter() {
+            if let Some(icon) = &link.icon {
+                if !BUILTIN_FUNDING_ICONS.contains(&icon.as_str())
+                    && !FundingLink::icon_is_url(icon)
+                {
+                    return Err(OrandaError::FundingIconInvalid {
+                        label: link.label.clone(),
+                        icon: icon.clone(),
+                    });
+                }
+            }
+        }
+        let cant_find_files = yml_path.is_none() && md_path.is_none() && links.is_empty();
+        let has_user_config = preferred_funding.is_some()
+            || monthly_goal.is_some()
+            || !names.is_empty()
+            || !order.is_empty();
         if cant_find_files {
             // The config is unusable.
             //
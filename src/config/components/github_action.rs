@@ -0,0 +1,86 @@
+use camino::Utf8PathBuf;
+use indexmap::IndexMap;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::config::{ApplyLayer, ApplyOptExt, ApplyValExt};
+use crate::errors::*;
+
+/// Config for showing how to use the project as a GitHub Action (complete version)
+#[derive(Debug, Clone)]
+pub struct GithubActionConfig {
+    /// The path to the action's metadata file
+    pub path: Option<String>,
+    /// What to put before the `@` in `uses:`, if not the repository
+    pub uses: Option<String>,
+    /// Values for the action's inputs in the example
+    pub with: IndexMap<String, String>,
+}
+
+/// The config for showing a `uses:` snippet for your GitHub Action on your front page
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GithubActionLayer {
+    /// The path to your action's metadata file
+    ///
+    /// By default we try to find this at "./action.yml" or "./action.yaml"
+    pub path: Option<String>,
+    /// What to put before the `@` in the `uses:` line, like "my-org/my-repo/subdir" for an
+    /// action that isn't at the root of its repository.
+    ///
+    /// Defaults to the owner and name of `project.repository`
+    pub uses: Option<String>,
+    /// Values to show for your action's inputs in the example, like
+    /// `{ "token": "${{ secrets.GITHUB_TOKEN }}" }`.
+    ///
+    /// Required inputs are always shown, with their default if they have one. Optional ones
+    /// are only shown if you give them a value here.
+    pub with: Option<IndexMap<String, String>>,
+}
+
+impl Default for GithubActionConfig {
+    fn default() -> Self {
+        GithubActionConfig {
+            path: None,
+            uses: None,
+            with: IndexMap::new(),
+        }
+    }
+}
+
+impl ApplyLayer for GithubActionConfig {
+    type Layer = GithubActionLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let GithubActionLayer { path, uses, with } = layer;
+        self.path.apply_opt(path);
+        self.uses.apply_opt(uses);
+        self.with.apply_val(with);
+    }
+}
+
+impl GithubActionConfig {
+    /// If we have an action.yml, try to find it. If we fail, this isn't an action, so we
+    /// disable this.
+    pub fn find_paths(config: &mut Option<Self>, start_dir: &Path) -> Result<()> {
+        // If this is None, we were force-disabled and shouldn't auto-detect
+        let Some(this) = config else { return Ok(()) };
+
+        if let Some(path) = &this.path {
+            if !Utf8PathBuf::from(path).exists() {
+                return Err(OrandaError::PathDoesNotExist { path: path.clone() });
+            }
+            return Ok(());
+        }
+        for name in ["action.yml", "action.yaml"] {
+            let path = Utf8PathBuf::from(format!("{}/{name}", start_dir.display()));
+            if path.exists() {
+                this.path = Some(path.to_string());
+                return Ok(());
+            }
+        }
+        *config = None;
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ApplyLayer, ApplyOptExt};
+
+/// Config for the project health strip (complete version)
+#[derive(Debug, Clone)]
+pub struct HealthConfig {
+    /// The workflow file to report the status of, or None for the latest run of any workflow
+    pub workflow: Option<String>,
+    /// The branch to report the status of, or None for the repository's default branch
+    pub branch: Option<String>,
+    /// Where to get code coverage from, if anywhere
+    pub coverage: Option<CoverageService>,
+}
+
+/// The config for showing CI status and code coverage on your front page
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct HealthLayer {
+    /// The file name of the GitHub Actions workflow to report the status of, e.g. "ci.yml".
+    ///
+    /// If not set we will use the latest run of any workflow.
+    pub workflow: Option<String>,
+    /// The branch to report CI status and coverage for.
+    ///
+    /// If not set we will use your repository's default branch.
+    pub branch: Option<String>,
+    /// The service to fetch your code coverage percentage from.
+    ///
+    /// If not set, coverage isn't shown.
+    pub coverage: Option<CoverageService>,
+}
+
+/// A code coverage service. Possible values:
+///
+/// - "codecov": Fetch coverage from codecov.io
+/// - "coveralls": Fetch coverage from coveralls.io
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CoverageService {
+    Codecov,
+    Coveralls,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        HealthConfig {
+            workflow: None,
+            branch: None,
+            coverage: None,
+        }
+    }
+}
+
+impl ApplyLayer for HealthConfig {
+    type Layer = HealthLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let HealthLayer {
+            workflow,
+            branch,
+            coverage,
+        } = layer;
+        self.workflow.apply_opt(workflow);
+        self.branch.apply_opt(branch);
+        self.coverage.apply_opt(coverage);
+    }
+}
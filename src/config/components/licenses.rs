@@ -0,0 +1,107 @@
+use std::path::Path;
+
+use camino::Utf8PathBuf;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ApplyLayer, ApplyOptExt, ApplyValExt};
+use crate::errors::*;
+
+/// Config for the licenses page (complete version)
+#[derive(Debug, Clone)]
+pub struct LicensesConfig {
+    /// The Cargo.toml whose dependencies to list
+    pub manifest_path: String,
+    /// A `cargo about generate --format json` report to use instead of `cargo metadata`
+    pub report: Option<String>,
+    /// Whether to run cargo-about during the build instead of reading `report`
+    pub generate_report: bool,
+    /// Dependencies to leave off the page
+    pub exclude: Vec<String>,
+    /// The project's own license files, or None if we haven't looked for them yet
+    pub project_licenses: Option<Vec<String>>,
+}
+
+/// The config for the licenses page
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct LicensesLayer {
+    /// The Cargo.toml whose dependencies to list, which can be a workspace's.
+    ///
+    /// defaults to "./Cargo.toml"
+    pub manifest_path: Option<String>,
+    /// The path to a report made with `cargo about generate --format json`. When this is set,
+    /// the page lists the licenses in the report, along with their full text, instead of
+    /// asking `cargo metadata` what each dependency's license is.
+    pub report: Option<String>,
+    /// Whether to run `cargo about generate --format json` during the build, and list the
+    /// licenses in its report. This needs cargo-about to be installed, and takes precedence
+    /// over `report`.
+    pub generate_report: Option<bool>,
+    /// Dependencies to leave off the page, by crate name.
+    pub exclude: Option<Vec<String>>,
+    /// Your project's own license files, shown at the top of the page.
+    ///
+    /// By default we use the files in your project's directory whose names start with
+    /// "LICENSE", "LICENCE" or "COPYING", like "LICENSE-MIT" and "LICENSE-APACHE".
+    pub project_licenses: Option<Vec<String>>,
+}
+
+impl Default for LicensesConfig {
+    fn default() -> Self {
+        LicensesConfig {
+            manifest_path: "./Cargo.toml".to_owned(),
+            report: None,
+            generate_report: false,
+            exclude: vec![],
+            project_licenses: None,
+        }
+    }
+}
+
+impl ApplyLayer for LicensesConfig {
+    type Layer = LicensesLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let LicensesLayer {
+            manifest_path,
+            report,
+            generate_report,
+            exclude,
+            project_licenses,
+        } = layer;
+        self.manifest_path.apply_val(manifest_path);
+        self.report.apply_opt(report);
+        self.generate_report.apply_val(generate_report);
+        self.exclude.apply_val(exclude);
+        self.project_licenses.apply_opt(project_licenses);
+    }
+}
+
+impl LicensesConfig {
+    /// If the project's license files weren't specified, try to auto-detect them
+    pub fn find_paths(config: &mut Option<Self>, start_dir: &Path) -> Result<()> {
+        // If this is None, we were force-disabled and shouldn't auto-detect
+        let Some(this) = config else { return Ok(()) };
+        if this.project_licenses.is_some() {
+            return Ok(());
+        }
+        let mut found = vec![];
+        if let Ok(entries) = std::fs::read_dir(start_dir) {
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_uppercase();
+                let is_license = ["LICENSE", "LICENCE", "COPYING"]
+                    .iter()
+                    .any(|prefix| name.starts_with(prefix));
+                if is_license && entry.path().is_file() {
+                    if let Ok(path) = Utf8PathBuf::from_path_buf(entry.path()) {
+                        found.push(path.to_string());
+                    }
+                }
+            }
+        }
+        found.sort();
+        this.project_licenses = Some(found);
+        Ok(())
+    }
+}
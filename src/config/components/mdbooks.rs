@@ -1,3 +1,4 @@
+use indexmap::IndexMap;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
@@ -14,6 +15,8 @@ pub struct MdBookConfig {
     pub path: Option<String>,
     /// Whether to enable the custom oranda/axo theme
     pub theme: bool,
+    /// book.toml settings to override, keyed by their dotted path
+    pub overrides: IndexMap<String, serde_json::Value>,
 }
 
 /// The config for building and embedding an mdbook on your site
@@ -50,6 +53,28 @@ pub struct MdBookLayer {
     ///
     /// defaults to true
     pub theme: Option<bool>,
+    /// Settings to override in your book.toml when oranda builds your book, without
+    /// changing what `mdbook build` does.
+    ///
+    /// Keys are dotted paths into book.toml, and values are whatever that setting takes.
+    /// For example:
+    ///
+    /// ```json
+    /// {
+    ///   "components": {
+    ///     "mdbook": {
+    ///       "overrides": {
+    ///         "book.title": "My Project Docs",
+    ///         "output.html.git-repository-url": "https://github.com/me/my-project",
+    ///         "output.html.fold": { "enable": true }
+    ///       }
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    ///
+    /// These are applied after oranda's own theme settings, so they take precedence over them.
+    pub overrides: Option<IndexMap<String, serde_json::Value>>,
 }
 
 impl Default for MdBookConfig {
@@ -57,6 +82,7 @@ impl Default for MdBookConfig {
         MdBookConfig {
             path: None,
             theme: true,
+            overrides: IndexMap::new(),
         }
     }
 }
@@ -64,9 +90,14 @@ impl ApplyLayer for MdBookConfig {
     type Layer = MdBookLayer;
     fn apply_layer(&mut self, layer: Self::Layer) {
         // This is intentionally written slightly cumbersome to make you update this
-        let MdBookLayer { path, theme } = layer;
+        let MdBookLayer {
+            path,
+            theme,
+            overrides,
+        } = layer;
         self.path.apply_opt(path);
         self.theme.apply_val(theme);
+        self.overrides.apply_val(overrides);
     }
 }
 
@@ -94,9 +125,13 @@ impl MdBookConfig {
         }
 
         // This is intentionally written slightly cumbersome to make you update this
-        let MdBookConfig { path, theme } = this;
+        let MdBookConfig {
+            path,
+            theme,
+            overrides,
+        } = this;
         let cant_find_files = path.is_none();
-        let has_user_config = *theme != MdBookConfig::default().theme;
+        let has_user_config = *theme != MdBookConfig::default().theme || !overrides.is_empty();
         if cant_find_files {
             // The config is unusable.
             //
@@ -1,15 +1,54 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+mod activity;
 mod artifacts;
+mod authors;
+mod benchmarks;
+mod blog;
 mod changelog;
+mod comments;
+mod community;
+mod data_pages;
+mod demo;
+mod docker;
+mod docs;
+mod features;
 mod funding;
+mod github_action;
+mod health;
+mod licenses;
 mod mdbooks;
+mod roadmap;
+mod rustdoc;
+mod security;
+mod support_matrix;
 
-use crate::config::components::changelog::{ChangelogConfig, ChangelogLayer};
-pub use artifacts::{ArtifactsConfig, ArtifactsLayer, PackageManagersConfig, PackageManagersLayer};
-pub use funding::{FundingConfig, FundingLayer};
+pub use activity::{ActivityConfig, ActivityLayer};
+pub use artifacts::{
+    ArtifactsConfig, ArtifactsLayer, ChannelConfig, Distro, DistroPackage, MatchingConfig,
+    MatchingLayer, MirrorConfig, MirrorLayer, PackageManagersConfig, PackageManagersLayer,
+};
+pub use authors::{AuthorProfile, AuthorsConfig, AuthorsLayer};
+pub use benchmarks::{BenchmarksConfig, BenchmarksLayer};
+pub use blog::{BlogConfig, BlogLayer};
+pub use changelog::{ChangelogConfig, ChangelogLayer, ChangelogSource, UpgradeGuide};
+pub use comments::{CommentsConfig, CommentsLayer, CommentsProvider, Giscus, Utterances};
+pub use community::{CommunityConfig, CommunityLayer};
+pub use data_pages::{DataPage, DataPagesConfig, DataPagesLayer, DEFAULT_DATA_PAGE_TEMPLATE};
+pub use demo::{DemoConfig, DemoLayer};
+pub use docker::{DockerConfig, DockerLayer, DockerRegistry};
+pub use docs::{DocsConfig, DocsLayer};
+pub use features::{FeaturesConfig, FeaturesLayer};
+pub use funding::{FundingConfig, FundingLayer, FundingLink, BUILTIN_FUNDING_ICONS};
+pub use github_action::{GithubActionConfig, GithubActionLayer};
+pub use health::{CoverageService, HealthConfig, HealthLayer};
+pub use licenses::{LicensesConfig, LicensesLayer};
 pub use mdbooks::{MdBookConfig, MdBookLayer};
+pub use roadmap::{RoadmapConfig, RoadmapLayer};
+pub use rustdoc::{RustdocConfig, RustdocLayer};
+pub use security::{AdvisorySource, SecurityConfig, SecurityLayer};
+pub use support_matrix::{SupportMatrixConfig, SupportMatrixLayer, SupportStatus};
 
 use super::{ApplyBoolLayerExt, ApplyLayer, ApplyOptExt, BoolOr};
 
@@ -26,6 +65,14 @@ pub struct ComponentConfig {
     /// if we fail to auto-detect necessary information or if the user
     /// manually disables it.
     pub mdbook: Option<MdBookConfig>,
+    /// The config for docs built with an external tool
+    ///
+    /// This defaults to None, and is only enabled if the user asks for it.
+    pub docs: Option<DocsConfig>,
+    /// The config for rustdoc API docs
+    ///
+    /// This defaults to None, and is only enabled if the user asks for it.
+    pub rustdoc: Option<RustdocConfig>,
     /// The config for for the funding page
     ///
     /// This defaults to Some(Default) and is set to None
@@ -38,6 +85,76 @@ pub struct ComponentConfig {
     /// if we fail to auto-detect necessary information or if the user
     /// manually disables it.
     pub artifacts: Option<ArtifactsConfig>,
+    /// The config for the platform support matrix page
+    ///
+    /// This defaults to None, and is only enabled if the user asks for it.
+    pub support_matrix: Option<SupportMatrixConfig>,
+    /// The config for the feature flags page
+    ///
+    /// This defaults to None, and is only enabled if the user asks for it.
+    pub features: Option<FeaturesConfig>,
+    /// The config for the dependency licenses page
+    ///
+    /// This defaults to None, and is only enabled if the user asks for it.
+    pub licenses: Option<LicensesConfig>,
+    /// The config for the security page
+    ///
+    /// This defaults to None, and is only enabled if the user asks for it.
+    pub security: Option<SecurityConfig>,
+    /// The config for the code of conduct, contributing and governance pages
+    ///
+    /// This defaults to Some(Default) and is set to None
+    /// if we fail to auto-detect necessary information or if the user
+    /// manually disables it.
+    pub community: Option<CommunityConfig>,
+    /// The config for the benchmarks page
+    ///
+    /// This defaults to None, and is only enabled if the user asks for it.
+    pub benchmarks: Option<BenchmarksConfig>,
+    /// The config for the roadmap page
+    ///
+    /// This defaults to None, and is only enabled if the user asks for it.
+    pub roadmap: Option<RoadmapConfig>,
+    /// The config for the project health strip on the front page
+    ///
+    /// This defaults to None, and is only enabled if the user asks for it.
+    pub health: Option<HealthConfig>,
+    /// The config for the project activity widget on the front page
+    ///
+    /// This defaults to None, and is only enabled if the user asks for it.
+    pub activity: Option<ActivityConfig>,
+    /// The config for comment threads on release pages and blog posts
+    ///
+    /// This defaults to None, and is only enabled if the user asks for it.
+    pub comments: Option<CommentsConfig>,
+    /// The config for the blog
+    ///
+    /// This defaults to Some(Default) and is set to None
+    /// if we fail to auto-detect necessary information or if the user
+    /// manually disables it.
+    pub blog: Option<BlogConfig>,
+    /// The config for author profiles
+    ///
+    /// This defaults to None, and is only enabled if the user asks for it.
+    pub authors: Option<AuthorsConfig>,
+    /// The config for the Docker section of the install page
+    ///
+    /// This defaults to None, and is only enabled if the user asks for it.
+    pub docker: Option<DockerConfig>,
+    /// The config for the GitHub Action usage snippet on the front page
+    ///
+    /// This defaults to Some(Default) and is set to None
+    /// if we fail to auto-detect necessary information or if the user
+    /// manually disables it.
+    pub github_action: Option<GithubActionConfig>,
+    /// The config for the web demo on the front page
+    ///
+    /// This defaults to None, and is only enabled if the user asks for it.
+    pub demo: Option<DemoConfig>,
+    /// The config for pages rendered from data files
+    ///
+    /// This defaults to None, and is only enabled if the user asks for it.
+    pub data_pages: Option<DataPagesConfig>,
     /// Which source to fetch release data from.
     pub source: Option<ReleasesSource>,
 }
@@ -62,6 +179,23 @@ pub struct ComponentLayer {
     ///  
     /// More precise settings can be used with `"mdbook": { ... }`.
     pub mdbook: Option<BoolOr<MdBookLayer>>,
+    /// The config for building docs with another tool, like Docusaurus, mkdocs or Sphinx
+    ///
+    /// oranda runs your `command`, copies everything it writes to `output_dir` to "/docs/"
+    /// on your site, and links it as "docs" in the nav. Unlike the mdbook integration, the
+    /// docs aren't restyled to match your oranda theme.
+    ///
+    /// This feature is disabled by default. It can be enabled with `"docs": { ... }`, which
+    /// needs at least a `command` and an `output_dir`.
+    pub docs: Option<BoolOr<DocsLayer>>,
+    /// The config for your crate's API docs, built with rustdoc
+    ///
+    /// oranda runs `cargo doc --no-deps` (or uses docs you've already built), copies them to
+    /// "/rustdoc/" on your site, and links an "API" page in the nav that shows them.
+    ///
+    /// This feature is disabled by default. It can be enabled with `"rustdoc": true`, and
+    /// configured with `"rustdoc": { ... }`.
+    pub rustdoc: Option<BoolOr<RustdocLayer>>,
     /// The config for for the "funding" page
     ///
     /// This feature is enabled by default if we find a file at "funding.md"
@@ -173,6 +307,136 @@ pub struct ComponentLayer {
     /// turn on plain GitHub Releases integration. You either need to have cargo-dist
     /// integration enabled, or add a random package_manager to make us enable it.
     pub artifacts: Option<BoolOr<ArtifactsLayer>>,
+    /// The config for the "platforms" page, which shows a table of the platforms your
+    /// project supports and how well it supports them.
+    ///
+    /// Rows can be listed manually in `targets`, and are also added automatically for
+    /// every target your latest cargo-dist release was built for.
+    ///
+    /// This feature is disabled by default. It can be enabled by setting
+    /// `"support_matrix": true`, or with more precise settings using
+    /// `"support_matrix": { ... }`.
+    pub support_matrix: Option<BoolOr<SupportMatrixLayer>>,
+    /// The config for the "features" page, which lists the feature flags of your crate, whether
+    /// they're on by default, and what they turn on.
+    ///
+    /// Features are read from the `[features]` table of your Cargo.toml. Each one is described
+    /// with the `## ` comments above it (the convention `document-features` uses), or else with
+    /// the list item about it in your crate docs in `src/lib.rs`, like
+    /// ``//! * `serde`: Serialize and deserialize everything``.
+    ///
+    /// This feature is disabled by default. It can be enabled by setting
+    /// `"features": true`, or with more precise settings using `"features": { ... }`.
+    pub features: Option<BoolOr<FeaturesLayer>>,
+    /// The config for the "licenses" page, which lists the licenses of the crates your project
+    /// depends on, and which crates use each one, so that anyone distributing your binaries
+    /// can see what they need to attribute.
+    ///
+    /// By default the licenses come from `cargo metadata`, leaving out your dev-dependencies
+    /// and your workspace's own crates. For the full text of each license, point `report` at
+    /// the output of `cargo about generate --format json`.
+    ///
+    /// This feature is disabled by default. It can be enabled by setting
+    /// `"licenses": true`, or with more precise settings using `"licenses": { ... }`.
+    pub licenses: Option<BoolOr<LicensesLayer>>,
+    /// The config for a "security" page, which shows your SECURITY.md, and optionally the
+    /// security advisories published for your project on GitHub or RustSec. It's linked from
+    /// the footer.
+    ///
+    /// This feature is disabled by default. It can be enabled by setting `"security": true`,
+    /// or with more precise settings using `"security": { ... }`.
+    pub security: Option<BoolOr<SecurityLayer>>,
+    /// The config for turning your CODE_OF_CONDUCT.md, CONTRIBUTING.md and GOVERNANCE.md into
+    /// pages, linked from the footer.
+    ///
+    /// By default we look for them in your project's directory and its `.github` directory, and
+    /// build a page for each one we find. Set this to `false` to turn that off.
+    pub community: Option<BoolOr<CommunityLayer>>,
+    /// The config for the "benchmarks" page, which renders the results of your criterion
+    /// or hyperfine benchmarks as a table.
+    ///
+    /// This feature is disabled by default. It can be enabled by setting
+    /// `"benchmarks": true`, in which case we will look for criterion's results in
+    /// "./target/criterion", or by listing result files with `"benchmarks": { ... }`.
+    pub benchmarks: Option<BoolOr<BenchmarksLayer>>,
+    /// The config for a "roadmap" page, which lists your repository's open milestones on
+    /// GitHub, with their due dates, progress and issues, fetched every time you build.
+    ///
+    /// This feature is disabled by default. It can be enabled by setting `"roadmap": true`,
+    /// or with more precise settings using `"roadmap": { ... }`.
+    pub roadmap: Option<BoolOr<RoadmapLayer>>,
+    /// The config for a "project health" strip on your front page, showing the status of
+    /// your latest CI run and your code coverage percentage.
+    ///
+    /// CI status is fetched from GitHub Actions, so this requires `project.repository` to be
+    /// a GitHub repository.
+    ///
+    /// This feature is disabled by default. It can be enabled by setting `"health": true`,
+    /// or with more precise settings using `"health": { ... }`.
+    pub health: Option<BoolOr<HealthLayer>>,
+    /// The config for a "project activity" widget on your front page, showing how many issues
+    /// are open in your repository, its latest discussions, and how long new issues take to get
+    /// a response, fetched every time you build.
+    ///
+    /// This requires `project.repository` to be a GitHub repository.
+    ///
+    /// This feature is disabled by default. It can be enabled by setting `"activity": true`,
+    /// or with more precise settings using `"activity": { ... }`.
+    pub activity: Option<BoolOr<ActivityLayer>>,
+    /// The config for comment threads at the bottom of every release page and blog post, kept
+    /// in GitHub Discussions with giscus or in GitHub issues with utterances.
+    ///
+    /// This feature is disabled by default. It can be enabled by setting `"comments": true`,
+    /// which uses utterances with your project's repository, or with more precise settings
+    /// using `"comments": { ... }`.
+    pub comments: Option<BoolOr<CommentsLayer>>,
+    /// The config for publishing markdown files as blog posts under "/blog/", with a
+    /// paginated index page and an RSS feed.
+    ///
+    /// This feature is enabled by default if we find a "./posts/" directory.
+    ///
+    /// It can be completely disabled by setting `"blog": false`.
+    ///
+    /// More precise settings can be used with `"blog": { ... }`.
+    pub blog: Option<BoolOr<BlogLayer>>,
+    /// The config for author profiles, which are used for the bylines of blog posts and
+    /// changelog entries, and listed on an "authors" page.
+    ///
+    /// This feature is disabled by default. It can be enabled by setting `"authors": true`,
+    /// in which case the authors page only lists the names we find in your posts and
+    /// releases, or with profiles using `"authors": { "people": { ... } }`.
+    pub authors: Option<BoolOr<AuthorsLayer>>,
+    /// The config for showing how to pull and run your Docker image, with links to the
+    /// registries it's published to, on the install page.
+    ///
+    /// This feature is disabled by default. It can be enabled with `"docker": { ... }`,
+    /// which needs at least an `image`.
+    pub docker: Option<BoolOr<DockerLayer>>,
+    /// The config for showing how to use your project as a GitHub Action, with a `uses:`
+    /// snippet pointing at your latest release and a table of the action's inputs, on your
+    /// front page.
+    ///
+    /// This feature is enabled by default if we find an "action.yml" or "action.yaml".
+    ///
+    /// It can be completely disabled by setting `"github_action": false`.
+    ///
+    /// More precise settings can be used with `"github_action": { ... }`.
+    pub github_action: Option<BoolOr<GithubActionLayer>>,
+    /// The config for copying a web demo of your project, like a WASM build, to "/demo/" on
+    /// your site, and embedding it on your front page.
+    ///
+    /// This feature is disabled by default. It can be enabled with `"demo": { ... }`,
+    /// which needs at least a `path`.
+    pub demo: Option<BoolOr<DemoLayer>>,
+    /// The config for pages rendered from your own data, like a plugin registry or a list of
+    /// download mirrors, kept in JSON, TOML or YAML files.
+    ///
+    /// Each page is rendered with a template of your choosing (see `build.template_dir`), or
+    /// with a built-in one that shows the data as a table.
+    ///
+    /// This feature is disabled by default. It can be enabled with
+    /// `"data_pages": { "pages": { ... } }`.
+    pub data_pages: Option<BoolOr<DataPagesLayer>>,
     /// Where we should attempt to fetch release data from.
     pub source: Option<ReleasesSource>,
 }
@@ -196,8 +460,26 @@ impl Default for ComponentConfig {
         ComponentConfig {
             changelog: Some(ChangelogConfig::default()),
             mdbook: Some(MdBookConfig::default()),
+            docs: None,
+            rustdoc: None,
             funding: Some(FundingConfig::default()),
             artifacts: Some(ArtifactsConfig::default()),
+            support_matrix: None,
+            features: None,
+            licenses: None,
+            security: None,
+            community: Some(CommunityConfig::default()),
+            benchmarks: None,
+            roadmap: None,
+            health: None,
+            activity: None,
+            comments: None,
+            blog: Some(BlogConfig::default()),
+            authors: None,
+            docker: None,
+            github_action: Some(GithubActionConfig::default()),
+            demo: None,
+            data_pages: None,
             source: Some(ReleasesSource::GitHub),
         }
     }
@@ -209,14 +491,50 @@ impl ApplyLayer for ComponentConfig {
         let ComponentLayer {
             changelog,
             mdbook,
+            docs,
+            rustdoc,
             funding,
             artifacts,
+            support_matrix,
+            features,
+            licenses,
+            security,
+            community,
+            benchmarks,
+            roadmap,
+            health,
+            activity,
+            comments,
+            blog,
+            authors,
+            docker,
+            github_action,
+            demo,
+            data_pages,
             source,
         } = layer;
         self.changelog.apply_bool_layer(changelog);
         self.mdbook.apply_bool_layer(mdbook);
+        self.docs.apply_bool_layer(docs);
+        self.rustdoc.apply_bool_layer(rustdoc);
         self.funding.apply_bool_layer(funding);
         self.artifacts.apply_bool_layer(artifacts);
+        self.support_matrix.apply_bool_layer(support_matrix);
+        self.features.apply_bool_layer(features);
+        self.licenses.apply_bool_layer(licenses);
+        self.security.apply_bool_layer(security);
+        self.community.apply_bool_layer(community);
+        self.benchmarks.apply_bool_layer(benchmarks);
+        self.roadmap.apply_bool_layer(roadmap);
+        self.health.apply_bool_layer(health);
+        self.activity.apply_bool_layer(activity);
+        self.comments.apply_bool_layer(comments);
+        self.blog.apply_bool_layer(blog);
+        self.authors.apply_bool_layer(authors);
+        self.docker.apply_bool_layer(docker);
+        self.github_action.apply_bool_layer(github_action);
+        self.demo.apply_bool_layer(demo);
+        self.data_pages.apply_bool_layer(data_pages);
         self.source.apply_opt(source);
     }
 }
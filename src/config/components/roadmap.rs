@@ -0,0 +1,51 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ApplyLayer, ApplyValExt};
+
+/// Config for the roadmap page (complete version)
+#[derive(Debug, Clone)]
+pub struct RoadmapConfig {
+    /// How many issues to list under each milestone
+    pub max_issues: usize,
+    /// Whether to list closed issues along with open ones
+    pub closed_issues: bool,
+}
+
+/// The config for a roadmap page, made from your repository's open milestones on GitHub
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RoadmapLayer {
+    /// How many issues to list under each milestone. Set this to 0 to only show the
+    /// milestones' progress.
+    ///
+    /// Defaults to 20
+    pub max_issues: Option<usize>,
+    /// Whether to list the issues in a milestone that have been closed, along with the open
+    /// ones.
+    ///
+    /// Defaults to true
+    pub closed_issues: Option<bool>,
+}
+
+impl Default for RoadmapConfig {
+    fn default() -> Self {
+        RoadmapConfig {
+            max_issues: 20,
+            closed_issues: true,
+        }
+    }
+}
+
+impl ApplyLayer for RoadmapConfig {
+    type Layer = RoadmapLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let RoadmapLayer {
+            max_issues,
+            closed_issues,
+        } = layer;
+        self.max_issues.apply_val(max_issues);
+        self.closed_issues.apply_val(closed_issues);
+    }
+}
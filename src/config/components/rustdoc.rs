@@ -0,0 +1,93 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ApplyLayer, ApplyOptExt, ApplyValExt};
+
+/// Config for embedding rustdoc API docs (complete version)
+#[derive(Debug, Clone)]
+pub struct RustdocConfig {
+    /// Whether to run `cargo doc` ourselves, instead of using docs that were already built
+    pub build: bool,
+    /// The directory of the crate to document
+    pub path: String,
+    /// Where the built docs are, relative to `path`. If None, we ask cargo where its target
+    /// dir is.
+    pub doc_dir: Option<String>,
+    /// The crate whose docs the "API" page shows. If None, this is the project name.
+    pub crate_name: Option<String>,
+    /// Whether to pick a rustdoc theme that matches the oranda theme
+    pub theme: bool,
+}
+
+/// The config for building your crate's API docs with rustdoc, and serving them on your site
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RustdocLayer {
+    /// Whether to build the docs with `cargo doc --no-deps`
+    ///
+    /// Set this to false if your docs are built some other way (say, in an earlier step of your
+    /// CI), and we'll just copy them from `doc_dir`.
+    ///
+    /// Defaults to true
+    pub build: Option<bool>,
+    /// The directory of the crate to document, relative to your oranda.json
+    ///
+    /// Defaults to "./"
+    pub path: Option<String>,
+    /// The directory the built docs are in, relative to `path`
+    ///
+    /// Defaults to the "doc" directory in cargo's target directory
+    pub doc_dir: Option<String>,
+    /// The name of the crate the "API" page should show, for workspaces with several crates
+    ///
+    /// Defaults to your project's name
+    pub crate_name: Option<String>,
+    /// Whether to set rustdoc's default theme to match your oranda theme
+    ///
+    /// This only applies when we build the docs. Visitors can still pick another theme in
+    /// rustdoc's settings.
+    ///
+    /// Defaults to true
+    pub theme: Option<bool>,
+}
+
+impl Default for RustdocConfig {
+    fn default() -> Self {
+        RustdocConfig {
+            build: true,
+            path: "./".to_owned(),
+            doc_dir: None,
+            crate_name: None,
+            theme: true,
+        }
+    }
+}
+
+impl ApplyLayer for RustdocConfig {
+    type Layer = RustdocLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let RustdocLayer {
+            build,
+            path,
+            doc_dir,
+            crate_name,
+            theme,
+        } = layer;
+        self.build.apply_val(build);
+        self.path.apply_val(path);
+        self.doc_dir.apply_opt(doc_dir);
+        self.crate_name.apply_opt(crate_name);
+        self.theme.apply_val(theme);
+    }
+}
+
+impl RustdocConfig {
+    /// The name of the directory rustdoc puts `crate_name`'s docs in
+    pub fn crate_dir_name(&self, project_name: &str) -> String {
+        self.crate_name
+            .as_deref()
+            .unwrap_or(project_name)
+            .replace('-', "_")
+    }
+}
@@ -0,0 +1,70 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ApplyLayer, ApplyOptExt, ApplyValExt};
+
+/// Config for the security page (complete version)
+#[derive(Debug, Clone)]
+pub struct SecurityConfig {
+    /// The security policy to render, relative to the oranda.json
+    pub policy: String,
+    /// Where to fetch published advisories from, if anywhere
+    pub advisories: Option<AdvisorySource>,
+    /// The crate to look up RustSec advisories for, or None for the project's name
+    pub crate_name: Option<String>,
+}
+
+/// The config for the security page, which shows your security policy and the advisories
+/// published for your project
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SecurityLayer {
+    /// The security policy to show at the top of the page
+    ///
+    /// Defaults to "./SECURITY.md"
+    pub policy: Option<String>,
+    /// Where to fetch the advisories published for your project from.
+    ///
+    /// If not set, advisories aren't listed.
+    pub advisories: Option<AdvisorySource>,
+    /// The crate to look up RustSec advisories for.
+    ///
+    /// If not set we will use your project's name.
+    pub crate_name: Option<String>,
+}
+
+/// A database of security advisories. Possible values:
+///
+/// - "github": The GitHub Security Advisories published in your repository
+/// - "rustsec": The RustSec advisories for your crate
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AdvisorySource {
+    GitHub,
+    RustSec,
+}
+
+impl Default for SecurityConfig {
+    fn default() -> Self {
+        SecurityConfig {
+            policy: "./SECURITY.md".to_owned(),
+            advisories: None,
+            crate_name: None,
+        }
+    }
+}
+
+impl ApplyLayer for SecurityConfig {
+    type Layer = SecurityLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let SecurityLayer {
+            policy,
+            advisories,
+            crate_name,
+        } = layer;
+        self.policy.apply_val(policy);
+        self.advisories.apply_opt(advisories);
+        self.crate_name.apply_opt(crate_name);
+    }
+}
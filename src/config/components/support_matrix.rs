@@ -0,0 +1,93 @@
+use indexmap::IndexMap;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ApplyLayer, ApplyValExt};
+
+/// Config for the platform support matrix page (complete version)
+#[derive(Debug, Clone)]
+pub struct SupportMatrixConfig {
+    /// Support status of each target triple, in the order they should be shown
+    pub targets: IndexMap<String, SupportStatus>,
+    /// Whether to add rows for the targets cargo-dist builds for
+    pub cargo_dist_targets: bool,
+    /// The status we give targets we pick up from cargo-dist
+    pub default_status: SupportStatus,
+}
+
+/// The config for the platform support matrix page
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SupportMatrixLayer {
+    /// A map from target triples (e.g. "x86_64-unknown-linux-gnu") to how well they're
+    /// supported. Targets will be shown in the given order.
+    pub targets: Option<IndexMap<String, SupportStatus>>,
+    /// Whether to add a row for every target your latest cargo-dist release was built for,
+    /// unless it's already listed in `targets`.
+    ///
+    /// defaults to true
+    pub cargo_dist_targets: Option<bool>,
+    /// The status to give targets we pick up from cargo-dist.
+    ///
+    /// defaults to "tier2"
+    pub default_status: Option<SupportStatus>,
+}
+
+/// How well a platform is supported. Possible values:
+///
+/// - "tier1": Guaranteed to work, and tested on every change.
+/// - "tier2": Guaranteed to build, but not necessarily tested.
+/// - "tested": Known to work, but without any guarantees.
+/// - "community": Maintained by the community rather than the project itself.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SupportStatus {
+    Tier1,
+    Tier2,
+    Tested,
+    Community,
+}
+
+impl SupportStatus {
+    /// All statuses, in the order they're shown as columns
+    pub const ALL: [SupportStatus; 4] = [
+        SupportStatus::Tier1,
+        SupportStatus::Tier2,
+        SupportStatus::Tested,
+        SupportStatus::Community,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SupportStatus::Tier1 => "Tier 1",
+            SupportStatus::Tier2 => "Tier 2",
+            SupportStatus::Tested => "Tested",
+            SupportStatus::Community => "Community",
+        }
+    }
+}
+
+impl Default for SupportMatrixConfig {
+    fn default() -> Self {
+        SupportMatrixConfig {
+            targets: IndexMap::new(),
+            cargo_dist_targets: true,
+            default_status: SupportStatus::Tier2,
+        }
+    }
+}
+
+impl ApplyLayer for SupportMatrixConfig {
+    type Layer = SupportMatrixLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let SupportMatrixLayer {
+            targets,
+            cargo_dist_targets,
+            default_status,
+        } = layer;
+        self.targets.apply_val(targets);
+        self.cargo_dist_targets.apply_val(cargo_dist_targets);
+        self.default_status.apply_val(default_status);
+    }
+}
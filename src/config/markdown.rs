@@ -0,0 +1,182 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{ApplyLayer, ApplyValExt};
+
+/// Config for how markdown is rendered (complete version)
+#[derive(Debug, Clone, Default)]
+pub struct MarkdownConfig {
+    pub extensions: MarkdownExtensions,
+    pub sponsor_copies: bool,
+    pub relative_links: RelativeLinks,
+    pub sanitize: MarkdownSanitize,
+}
+
+/// Settings for how your markdown is rendered
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MarkdownLayer {
+    /// Which markdown extensions, beyond CommonMark, to support
+    pub extensions: Option<MarkdownExtensionsLayer>,
+    /// Whether to also write the full version of additional pages with
+    /// `<!-- sponsors-only -->` sections to `build.private_path`, for your host to put behind a
+    /// password that you share with your sponsors. The public version of the page always
+    /// replaces those sections with a teaser linking to the funding page.
+    ///
+    /// Default is false
+    pub sponsor_copies: Option<bool>,
+    /// What to do with relative links and images, like `./docs/setup.md` or `assets/logo.png`,
+    /// in your README and additional pages, which are usually written for GitHub
+    pub relative_links: Option<RelativeLinksLayer>,
+    /// Which markdown to treat as untrusted, like release notes written by outside
+    /// contributors. Scripts and event handlers are always removed, but untrusted markdown
+    /// is also kept from including files, using shortcodes, embedding recordings or
+    /// restyling the page.
+    ///
+    /// Default is "basic", which trusts all of your markdown
+    pub sanitize: Option<MarkdownSanitize>,
+}
+
+/// Which markdown is untrusted, and sanitized more strictly
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MarkdownSanitize {
+    /// None of it
+    #[default]
+    Basic,
+    /// Release notes, from GitHub releases or your changelog, and milestone descriptions
+    Releases,
+    /// All of it
+    All,
+}
+
+/// Where relative links and images point to (complete version)
+#[derive(Debug, Clone)]
+pub struct RelativeLinks {
+    pub links: RelativeLinkTarget,
+    pub images: RelativeLinkTarget,
+}
+
+/// Where relative links and images point to. Links to Markdown files that are built as
+/// additional pages always point to those pages.
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RelativeLinksLayer {
+    /// Where links, like `[the docs](./docs/setup.md)`, point to.
+    ///
+    /// Default is "repo"
+    pub links: Option<RelativeLinkTarget>,
+    /// Where images, like `![logo](assets/logo.png)`, point to.
+    ///
+    /// Default is "bundle"
+    pub images: Option<RelativeLinkTarget>,
+}
+
+/// Where a relative link or image points to
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RelativeLinkTarget {
+    /// The file in your repository on GitHub (its raw contents, for images)
+    Repo,
+    /// A copy of the file in your dist dir, at the same path
+    Bundle,
+    /// Leave the link as it is
+    Keep,
+}
+
+impl Default for RelativeLinks {
+    fn default() -> Self {
+        RelativeLinks {
+            links: RelativeLinkTarget::Repo,
+            images: RelativeLinkTarget::Bundle,
+        }
+    }
+}
+
+/// Markdown extensions (complete version)
+#[derive(Debug, Clone)]
+pub struct MarkdownExtensions {
+    pub footnotes: bool,
+    pub tasklists: bool,
+    pub definition_lists: bool,
+    pub emoji: bool,
+}
+
+/// Which markdown extensions, beyond CommonMark, to support
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MarkdownExtensionsLayer {
+    /// Footnotes, like `Hello[^1]` and `[^1]: A footnote`, which are listed at the end of the
+    /// page with links back to where they were referenced.
+    ///
+    /// Default is true
+    pub footnotes: Option<bool>,
+    /// GitHub-style task lists, like `- [x] Done`, which are shown as checkboxes.
+    ///
+    /// Default is true
+    pub tasklists: Option<bool>,
+    /// Definition lists, with a term on one line and `: its definition` on the next.
+    ///
+    /// Default is true
+    pub definition_lists: Option<bool>,
+    /// Emoji shortcodes, like `:rocket:`, which are replaced with the emoji they stand for.
+    /// These are the same shortcodes GitHub supports.
+    ///
+    /// Default is true
+    pub emoji: Option<bool>,
+}
+
+impl Default for MarkdownExtensions {
+    fn default() -> Self {
+        MarkdownExtensions {
+            footnotes: true,
+            tasklists: true,
+            definition_lists: true,
+            emoji: true,
+        }
+    }
+}
+
+impl ApplyLayer for MarkdownConfig {
+    type Layer = MarkdownLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let MarkdownLayer {
+            extensions,
+            sponsor_copies,
+            relative_links,
+            sanitize,
+        } = layer;
+        self.extensions.apply_val_layer(extensions);
+        self.sponsor_copies.apply_val(sponsor_copies);
+        self.relative_links.apply_val_layer(relative_links);
+        self.sanitize.apply_val(sanitize);
+    }
+}
+
+impl ApplyLayer for RelativeLinks {
+    type Layer = RelativeLinksLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let RelativeLinksLayer { links, images } = layer;
+        self.links.apply_val(links);
+        self.images.apply_val(images);
+    }
+}
+
+impl ApplyLayer for MarkdownExtensions {
+    type Layer = MarkdownExtensionsLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let MarkdownExtensionsLayer {
+            footnotes,
+            tasklists,
+            definition_lists,
+            emoji,
+        } = layer;
+        self.footnotes.apply_val(footnotes);
+        self.tasklists.apply_val(tasklists);
+        self.definition_lists.apply_val(definition_lists);
+        self.emoji.apply_val(emoji);
+    }
+}
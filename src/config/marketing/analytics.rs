@@ -18,3 +18,11 @@ pub enum AnalyticsConfig {
     /// Use Umami Analytics
     Umami(Umami),
 }
+
+impl AnalyticsConfig {
+    /// Whether the provider sets cookies, which visitors in some places have to agree to first.
+    /// The others are cookieless.
+    pub fn sets_cookies(&self) -> bool {
+        matches!(self, AnalyticsConfig::Google(_))
+    }
+}
@@ -0,0 +1,60 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ApplyLayer, ApplyOptExt, ApplyValExt};
+
+/// The banner asking for consent before loading analytics that set cookies (complete version)
+#[derive(Debug, Serialize, Clone)]
+pub struct ConsentBannerConfig {
+    pub message: String,
+    pub accept_text: String,
+    pub decline_text: String,
+    pub privacy_policy: Option<String>,
+}
+/// Settings for a banner that asks visitors for consent before loading analytics that set
+/// cookies, like Google Analytics
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ConsentBannerLayer {
+    /// What the banner says
+    ///
+    /// Defaults to "This site uses cookies to understand how it's used."
+    pub message: Option<String>,
+    /// The text of the button that loads the analytics
+    ///
+    /// Defaults to "Accept"
+    pub accept_text: Option<String>,
+    /// The text of the button that doesn't
+    ///
+    /// Defaults to "Decline"
+    pub decline_text: Option<String>,
+    /// A link to your privacy policy, shown after the message
+    pub privacy_policy: Option<String>,
+}
+
+impl Default for ConsentBannerConfig {
+    fn default() -> Self {
+        ConsentBannerConfig {
+            message: "This site uses cookies to understand how it's used.".to_owned(),
+            accept_text: "Accept".to_owned(),
+            decline_text: "Decline".to_owned(),
+            privacy_policy: None,
+        }
+    }
+}
+impl ApplyLayer for ConsentBannerConfig {
+    type Layer = ConsentBannerLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let ConsentBannerLayer {
+            message,
+            accept_text,
+            decline_text,
+            privacy_policy,
+        } = layer;
+        self.message.apply_val(message);
+        self.accept_text.apply_val(accept_text);
+        self.decline_text.apply_val(decline_text);
+        self.privacy_policy.apply_opt(privacy_policy);
+    }
+}
@@ -0,0 +1,104 @@
+use indexmap::IndexMap;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ApplyLayer, ApplyOptExt, ApplyValExt};
+
+/// The footer at the bottom of every page (complete version)
+#[derive(Debug, Serialize, Clone)]
+pub struct FooterConfig {
+    pub columns: Vec<FooterColumn>,
+    pub copyright: Option<String>,
+    pub social: IndexMap<SocialPlatform, String>,
+    pub built_with_oranda: bool,
+}
+/// Settings for the footer at the bottom of every page
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FooterLayer {
+    /// Columns of links, each with a title
+    pub columns: Option<Vec<FooterColumn>>,
+    /// A copyright notice. `{year}` in it is replaced with the year the site is built in.
+    ///
+    /// Example: "© 2023-{year} Axo Developer Co."
+    pub copyright: Option<String>,
+    /// Links to where your project is on social platforms, shown as icons in the order they're
+    /// listed in
+    ///
+    /// Example: { "mastodon": "https://mastodon.social/@axodotdev" }
+    pub social: Option<IndexMap<SocialPlatform, String>>,
+    /// Whether to mention that the site was built with oranda
+    ///
+    /// Defaults to true
+    pub built_with_oranda: Option<bool>,
+}
+
+/// A column of links in the footer
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FooterColumn {
+    /// The heading of the column
+    pub title: String,
+    /// The links in the column, from their text to where they go. Links that aren't URLs are
+    /// relative to the root of your site.
+    pub links: IndexMap<String, String>,
+}
+
+/// A social platform we have an icon for
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SocialPlatform {
+    Mastodon,
+    Discord,
+    Matrix,
+    X,
+}
+
+impl SocialPlatform {
+    /// The id used in config, which is also the name of its icon
+    pub fn id(self) -> &'static str {
+        match self {
+            SocialPlatform::Mastodon => "mastodon",
+            SocialPlatform::Discord => "discord",
+            SocialPlatform::Matrix => "matrix",
+            SocialPlatform::X => "x",
+        }
+    }
+
+    /// The name we show to people
+    pub fn name(self) -> &'static str {
+        match self {
+            SocialPlatform::Mastodon => "Mastodon",
+            SocialPlatform::Discord => "Discord",
+            SocialPlatform::Matrix => "Matrix",
+            SocialPlatform::X => "X",
+        }
+    }
+}
+
+impl Default for FooterConfig {
+    fn default() -> Self {
+        FooterConfig {
+            columns: vec![],
+            copyright: None,
+            social: IndexMap::new(),
+            built_with_oranda: true,
+        }
+    }
+}
+impl ApplyLayer for FooterConfig {
+    type Layer = FooterLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let FooterLayer {
+            columns,
+            copyright,
+            social,
+            built_with_oranda,
+        } = layer;
+        self.columns.apply_val(columns);
+        self.copyright.apply_opt(copyright);
+        self.social.apply_val(social);
+        self.built_with_oranda.apply_val(built_with_oranda);
+    }
+}
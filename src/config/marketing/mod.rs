@@ -1,20 +1,36 @@
 pub use analytics::AnalyticsConfig;
+pub use consent::{ConsentBannerConfig, ConsentBannerLayer};
+pub use footer::{FooterColumn, FooterConfig, FooterLayer, SocialPlatform};
+pub use newsletter::{Buttondown, Mailchimp, NewsletterConfig, NewsletterForm, NewsletterProvider};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 pub use social::{SocialConfig, SocialLayer};
+pub use well_known::{FundingManifestConfig, WellKnownConfig, WellKnownLayer};
 
-use super::ApplyLayer;
+use super::{ApplyBoolLayerExt, ApplyLayer, BoolOr};
 
 mod analytics;
+mod consent;
+mod footer;
+mod newsletter;
 mod social;
+mod well_known;
 
 /// Marketing config (complete version)
 #[derive(Debug, Clone)]
 pub struct MarketingConfig {
     /// Analytics
     pub analytics: Option<AnalyticsConfig>,
+    /// A banner asking for consent before loading analytics that set cookies
+    pub consent_banner: Option<ConsentBannerConfig>,
     /// Social media
     pub social: SocialConfig,
+    /// `humans.txt` and `/.well-known/` files
+    pub well_known: WellKnownConfig,
+    /// The footer at the bottom of every page
+    pub footer: FooterConfig,
+    /// A newsletter signup form
+    pub newsletter: Option<NewsletterConfig>,
 }
 /// Settings for marketing/social/analytics
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -23,16 +39,36 @@ pub struct MarketingLayer {
     /// Settings for analytics
     ///
     /// Analytics providers are currently mututally exclusive -- you can pick at most one.
-    pub analytics: Option<AnalyticsConfig>,
+    /// `false` turns off analytics that an earlier layer, like the rest of the oranda.json
+    /// under a profile, turned on.
+    pub analytics: Option<BoolOr<AnalyticsConfig>>,
+    /// Settings for a banner that asks visitors for consent before loading analytics that set
+    /// cookies, like Google Analytics. Analytics that don't set cookies load right away.
+    pub consent_banner: Option<BoolOr<ConsentBannerLayer>>,
     /// Settings for social media integrations
     pub social: Option<SocialLayer>,
+    /// Settings for generating `humans.txt` and files in `/.well-known/`, like a funding
+    /// manifest
+    pub well_known: Option<WellKnownLayer>,
+    /// Settings for the footer at the bottom of every page: columns of links, a copyright
+    /// notice and social icons
+    pub footer: Option<FooterLayer>,
+    /// Settings for a newsletter signup form on the index and blog pages, so that people can
+    /// get an email when you release
+    ///
+    /// `false` turns off a form that an earlier layer turned on.
+    pub newsletter: Option<BoolOr<NewsletterConfig>>,
 }
 
 impl Default for MarketingConfig {
     fn default() -> Self {
         MarketingConfig {
             analytics: None,
+            consent_banner: None,
             social: SocialConfig::default(),
+            well_known: WellKnownConfig::default(),
+            footer: FooterConfig::default(),
+            newsletter: None,
         }
     }
 }
@@ -40,14 +76,31 @@ impl ApplyLayer for MarketingConfig {
     type Layer = MarketingLayer;
     fn apply_layer(&mut self, layer: Self::Layer) {
         // This is intentionally written slightly cumbersome to make you update this
-        let MarketingLayer { analytics, social } = layer;
+        let MarketingLayer {
+            analytics,
+            consent_banner,
+            social,
+            well_known,
+            footer,
+            newsletter,
+        } = layer;
 
         // FIXME: this is kinda goofy but there's not an obvious thing to do
         // if we need to change the enum variant and we care about preserving things.
         // So we just clobber the old value no matter what
-        if let Some(analytics) = analytics {
-            self.analytics = Some(analytics);
+        match analytics {
+            Some(BoolOr::Val(analytics)) => self.analytics = Some(analytics),
+            Some(BoolOr::Bool(false)) => self.analytics = None,
+            Some(BoolOr::Bool(true)) | None => {}
         }
+        self.consent_banner.apply_bool_layer(consent_banner);
         self.social.apply_val_layer(social);
+        self.well_known.apply_val_layer(well_known);
+        self.footer.apply_val_layer(footer);
+        match newsletter {
+            Some(BoolOr::Val(newsletter)) => self.newsletter = Some(newsletter),
+            Some(BoolOr::Bool(false)) => self.newsletter = None,
+            Some(BoolOr::Bool(true)) | None => {}
+        }
     }
 }
@@ -0,0 +1,61 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Settings for a newsletter signup form on the index and blog pages
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct NewsletterConfig {
+    /// Where people's email addresses are sent
+    pub provider: NewsletterProvider,
+    /// The heading of the signup form
+    ///
+    /// Defaults to "Get release announcements"
+    pub title: Option<String>,
+    /// A sentence or two under the heading, like how often you send emails
+    pub description: Option<String>,
+    /// The text of the signup button
+    ///
+    /// Defaults to "Subscribe"
+    pub button_text: Option<String>,
+}
+
+/// A newsletter provider
+///
+/// Newsletter providers are mutually exclusive -- you can pick at most one.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum NewsletterProvider {
+    /// Use Buttondown
+    Buttondown(Buttondown),
+    /// Use Mailchimp
+    Mailchimp(Mailchimp),
+    /// Send the form to any URL
+    Form(NewsletterForm),
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Buttondown {
+    /// Your Buttondown username
+    pub username: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Mailchimp {
+    /// The `action` of the form in your audience's embedded form code
+    ///
+    /// Example: "https://example.us1.list-manage.com/subscribe/post?u=abc&id=123"
+    pub action: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct NewsletterForm {
+    /// The URL the form is posted to
+    pub action: String,
+    /// The name of the field the email address is sent in
+    ///
+    /// Defaults to "email"
+    pub email_field: Option<String>,
+}
@@ -0,0 +1,61 @@
+use indexmap::IndexMap;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ApplyLayer, ApplyOptExt, ApplyValExt};
+
+/// Well-known files config (complete version)
+#[derive(Debug, Clone, Default)]
+pub struct WellKnownConfig {
+    pub humans_txt: bool,
+    pub funding_manifest: Option<FundingManifestConfig>,
+    pub files: IndexMap<String, String>,
+}
+
+/// Settings for generating `humans.txt` and files in `/.well-known/`, from what we already
+/// know about your project
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct WellKnownLayer {
+    /// Whether to write a `humans.txt`, crediting the people in `components.authors` and
+    /// describing your project.
+    ///
+    /// This is false by default
+    pub humans_txt: Option<bool>,
+    /// Writes a FLOSS/fund funding manifest (`funding.json`) from your funding page's links,
+    /// and lists it in `/.well-known/funding-manifest-urls`. This needs the funding component.
+    pub funding_manifest: Option<FundingManifestConfig>,
+    /// Other files to put in `/.well-known/`, as a map of their names there to the paths of
+    /// the files to copy, e.g. `{ "security.txt": "./SECURITY.txt" }`
+    pub files: Option<IndexMap<String, String>>,
+}
+
+/// The entity that accepts funding, for the funding manifest
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FundingManifestConfig {
+    /// Who receives the funding, defaults to your project's name
+    pub name: Option<String>,
+    /// An email address people can reach the entity at. The manifest format requires one.
+    pub email: String,
+    /// One of "individual", "group", "organisation" or "other", defaults to "individual"
+    #[serde(rename = "type")]
+    pub entity_type: Option<String>,
+    /// What the entity does, defaults to your project's description
+    pub description: Option<String>,
+}
+
+impl ApplyLayer for WellKnownConfig {
+    type Layer = WellKnownLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let WellKnownLayer {
+            humans_txt,
+            funding_manifest,
+            files,
+        } = layer;
+        self.humans_txt.apply_val(humans_txt);
+        self.funding_manifest.apply_opt(funding_manifest);
+        self.files.apply_val(files);
+    }
+}
@@ -49,6 +49,13 @@
 //! - **[`OrandaLayer`][]**, AKA "the custom layer", comes from an `oranda.json` file.
 //!   It's basically a complete replica of [`Config`][] but with way more Options.
 //!
+//! - **The Profile Layer** comes from the `profiles` in that same `oranda.json`, if one was
+//!   picked with `--profile` (see [`profiles`][]).
+//!
+//! - **The Override Layer** comes from `--set key=value` flags on the command line (see
+//!   [`overrides`][]). It's deserialized as an [`OrandaLayer`][] too, so it's validated the
+//!   same way the oranda.json is.
+//!
 //! - **The Autodetect Layer** is just a convention where configs have an opportunity
 //!   to try to find missing values, erroring out if they fail while the user
 //!   was clearly trying to enable the feature.
@@ -79,7 +86,8 @@
 
 use std::convert::identity;
 
-use camino::Utf8PathBuf;
+use axoasset::LocalAsset;
+use camino::{Utf8Path, Utf8PathBuf};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
@@ -89,24 +97,62 @@ use crate::errors::*;
 pub mod axoproject;
 mod builds;
 mod components;
+mod markdown;
 mod marketing;
 pub mod oranda_config;
+pub mod overrides;
+pub mod profiles;
 pub mod project;
+pub mod requirements;
+mod sites_manifest;
 pub mod style;
 mod workspace;
 
 pub use self::axoproject::AxoprojectLayer;
 pub use self::oranda_config::OrandaLayer;
-pub use builds::{BuildConfig, BuildLayer};
+pub use builds::{BuildConfig, BuildLayer, PrivateHost};
 pub use components::{
-    ArtifactsConfig, ArtifactsLayer, ComponentConfig, ComponentLayer, FundingConfig, FundingLayer,
-    MdBookConfig, MdBookLayer, PackageManagersConfig, PackageManagersLayer, ReleasesSource,
+    ActivityConfig, ActivityLayer, AdvisorySource, ArtifactsConfig, ArtifactsLayer, AuthorProfile,
+    AuthorsConfig, AuthorsLayer, BenchmarksConfig, BenchmarksLayer, BlogConfig, BlogLayer,
+    ChangelogConfig, ChangelogLayer, ChangelogSource, ChannelConfig, CommentsConfig, CommentsLayer,
+    CommentsProvider, CommunityConfig, CommunityLayer, ComponentConfig, ComponentLayer,
+    CoverageService, DataPage, DataPagesConfig, DataPagesLayer, DemoConfig, DemoLayer, Distro,
+    DistroPackage, DockerConfig, DockerLayer, DockerRegistry, DocsConfig, DocsLayer,
+    FeaturesConfig, FeaturesLayer, FundingConfig, FundingLayer, FundingLink, Giscus,
+    GithubActionConfig, GithubActionLayer, HealthConfig, HealthLayer, LicensesConfig,
+    LicensesLayer, MatchingConfig, MatchingLayer, MdBookConfig, MdBookLayer, MirrorConfig,
+    MirrorLayer, PackageManagersConfig, PackageManagersLayer, ReleasesSource, RoadmapConfig,
+    RoadmapLayer, RustdocConfig, RustdocLayer, SecurityConfig, SecurityLayer, SupportMatrixConfig,
+    SupportMatrixLayer, SupportStatus, UpgradeGuide, Utterances, BUILTIN_FUNDING_ICONS,
+    DEFAULT_DATA_PAGE_TEMPLATE,
+};
+pub use markdown::{
+    MarkdownConfig, MarkdownExtensions, MarkdownExtensionsLayer, MarkdownLayer, MarkdownSanitize,
+    RelativeLinkTarget, RelativeLinks, RelativeLinksLayer,
 };
-pub use marketing::{AnalyticsConfig, MarketingConfig, MarketingLayer, SocialConfig, SocialLayer};
+pub use marketing::{
+    AnalyticsConfig, Buttondown, ConsentBannerConfig, ConsentBannerLayer, FooterColumn,
+    FooterConfig, FooterLayer, FundingManifestConfig, Mailchimp, MarketingConfig, MarketingLayer,
+    NewsletterConfig, NewsletterForm, NewsletterProvider, SocialConfig, SocialLayer,
+    SocialPlatform, WellKnownConfig, WellKnownLayer,
+};
+pub use sites_manifest::SitesManifest;
 pub use workspace::{WorkspaceConfig, WorkspaceLayer, WorkspaceMember};
 
 pub use project::{ProjectConfig, ProjectLayer};
-pub use style::{StyleConfig, StyleLayer};
+pub use style::{StyleConfig, StyleLayer, WebManifestConfig, WebManifestLayer};
+
+use overrides::ConfigOverride;
+
+/// What the command line adds on top of the config files, for every `Config::build*` to apply.
+/// The default adds nothing, which is what building a site as a library usually wants.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigOptions {
+    /// The profile picked with `--profile`, if any (see [`profiles`][])
+    pub profile: Option<String>,
+    /// Values set with `--set key=value` and the like, in order, so later ones win
+    pub overrides: Vec<ConfigOverride>,
+}
 
 /// Top-level mega-config
 #[derive(Debug, Clone)]
@@ -119,24 +165,64 @@ pub struct Config {
     pub marketing: MarketingConfig,
     /// Info about layout/themes
     pub styles: StyleConfig,
+    /// Info about how markdown is rendered
+    pub markdown: MarkdownConfig,
     /// Additional optional components
     pub components: ComponentConfig,
     /// Workspace configuration
     pub workspace: WorkspaceConfig,
+    /// The directory the project lives in, which relative paths in the config are relative to.
+    /// This is the current directory, unless the config was made with [`Config::build_in`][],
+    /// or is a workspace member's, which is built from the workspace root. A default config's
+    /// is empty, which leaves relative paths relative.
+    pub root_dir: Utf8PathBuf,
 }
 
 impl Config {
-    pub fn build(config_path: &Utf8PathBuf) -> Result<Config> {
+    pub fn build(config_path: &Utf8PathBuf, options: &ConfigOptions) -> Result<Config> {
+        let root_dir = LocalAsset::current_dir()?;
+        Self::build_with_root(&root_dir, config_path, options)
+    }
+
+    /// Like [`Config::build`][], but for the project in `root_dir` instead of the current
+    /// directory. A relative `config_path` is relative to `root_dir` too.
+    pub fn build_in(root_dir: &Utf8Path, config_path: &Utf8Path) -> Result<Config> {
+        Self::build_in_with(root_dir, config_path, &ConfigOptions::default())
+    }
+
+    /// Like [`Config::build_in`][], with `options` applied over the config files
+    pub fn build_in_with(
+        root_dir: &Utf8Path,
+        config_path: &Utf8Path,
+        options: &ConfigOptions,
+    ) -> Result<Config> {
+        Self::build_with_root(root_dir, &root_dir.join(config_path), options)
+    }
+
+    fn build_with_root(
+        root_dir: &Utf8Path,
+        config_path: &Utf8PathBuf,
+        options: &ConfigOptions,
+    ) -> Result<Config> {
         // Load Layers
-        let custom = OrandaLayer::load(config_path)?;
-        let project = AxoprojectLayer::load(None)?;
+        let mut custom = OrandaLayer::load(config_path)?;
+        let profile = profiles::take_selected(&mut custom, options.profile.as_deref(), true)?;
+        let project = AxoprojectLayer::load(root_dir)?;
 
         // default layer
-        let mut cfg = Config::default();
+        let mut cfg = Config {
+            root_dir: root_dir.to_owned(),
+            ..Config::default()
+        };
         // axoproject layer
         cfg.apply_project_layer(project);
         // oranda.json layer
         cfg.apply_custom_layer(custom);
+        // --profile layer
+        cfg.apply_custom_layer(profile);
+        // --set layer
+        cfg.apply_custom_layer(overrides::to_layer(&options.overrides)?);
+        cfg.apply_preview();
         // auto-detect layer
         cfg.apply_autodetect_layer(None)?;
 
@@ -145,9 +231,14 @@ impl Config {
 
     /// Build out a config for the workspace root, which is only interested in what's in the
     /// oranda_workspace.json.
-    pub fn build_workspace_root(config_path: &Utf8PathBuf) -> Result<Config> {
+    pub fn build_workspace_root(
+        config_path: &Utf8PathBuf,
+        options: &ConfigOptions,
+    ) -> Result<Config> {
         // This loads the `oranda_workspace.json`
-        let conf = OrandaLayer::load(config_path)?;
+        let mut conf = OrandaLayer::load(config_path)?;
+        // Members can have the profile without the workspace having it too
+        let profile = profiles::take_selected(&mut conf, options.profile.as_deref(), false)?;
 
         // Does the loaded value exist and have a defined workspace member list?
         let (set_members, set_auto) = conf
@@ -167,6 +258,9 @@ impl Config {
 
         let mut cfg = Config::default();
         cfg.apply_custom_layer(conf);
+        cfg.apply_custom_layer(profile);
+        cfg.apply_custom_layer(overrides::to_layer(&options.overrides)?);
+        cfg.apply_preview();
 
         // If no members were set, attempt to set the member list from the
         // detected list
@@ -186,20 +280,28 @@ impl Config {
     /// Build out a config for a workspace member, which is interested in the following things,
     /// in ascending priority:
     /// - axoproject stuff
-    /// - "root" config keys that are inherited
-    /// - its own oranda.json
+    /// - "root" config keys that are inherited, if there's a root config
+    /// - its own oranda.json, with the root config's `config` for this member merged over it
     /// - autodetect
     #[instrument("workspace_page", fields(prefix = prefix))]
     pub fn build_workspace_member(
         config_path: &Utf8PathBuf,
-        root_config_path: &Utf8PathBuf,
+        root_config_path: Option<&Utf8PathBuf>,
         project_root: &Utf8PathBuf,
         workspace_member: &WorkspaceMember,
         prefix: Option<String>,
+        options: &ConfigOptions,
     ) -> Result<Config> {
-        let member_conf = OrandaLayer::load(config_path)?;
-        let root_conf = OrandaLayer::load(root_config_path)?;
-        let project = AxoprojectLayer::load(Some(project_root.into()))?;
+        let mut member_conf = workspace_member.load_layer(config_path)?;
+        let mut root_conf = match root_config_path {
+            Some(root_config_path) => OrandaLayer::load(root_config_path)?,
+            None => None,
+        };
+        let member_profile =
+            profiles::take_selected(&mut member_conf, options.profile.as_deref(), false)?;
+        let root_profile =
+            profiles::take_selected(&mut root_conf, options.profile.as_deref(), false)?;
+        let project = AxoprojectLayer::load(project_root)?;
 
         // Complain if the member config contains workspace keys, because those keys should be set
         // in the top-level workspace config file.
@@ -212,12 +314,23 @@ impl Config {
         let mut cfg = Config::default();
         cfg.apply_project_layer(project);
         cfg.apply_custom_layer(root_conf);
+        cfg.apply_custom_layer(root_profile);
         cfg.apply_custom_layer(member_conf);
+        cfg.apply_custom_layer(member_profile);
+        cfg.apply_custom_layer(overrides::to_layer(&options.overrides)?);
+        cfg.apply_preview();
         cfg.apply_autodetect_layer(Some(workspace_member))?;
+        cfg.root_dir = project_root.clone();
 
         Ok(cfg)
     }
 
+    /// Resolves a path from the config against [`Config::root_dir`][]. Absolute paths are
+    /// returned as they are.
+    pub fn path(&self, path: impl AsRef<Utf8Path>) -> Utf8PathBuf {
+        self.root_dir.join(path)
+    }
+
     /// Apply the layer of config we computed from project files
     fn apply_project_layer(&mut self, layer: Option<AxoprojectLayer>) {
         if let Some(layer) = layer {
@@ -244,28 +357,56 @@ impl Config {
                 build,
                 marketing,
                 styles,
+                markdown,
                 components,
                 workspace,
+                profiles: _,
                 _schema,
             } = layer;
             self.project.apply_val_layer(project);
             self.build.apply_val_layer(build);
             self.marketing.apply_val_layer(marketing);
             self.styles.apply_val_layer(styles);
+            self.markdown.apply_val_layer(markdown);
             self.components.apply_val_layer(components);
             self.workspace.apply_val_layer(workspace);
         }
     }
 
+    /// Turns a preview build's name into everything it implies: its own path prefix, and no
+    /// analytics. The banner and `noindex` are up to the templates.
+    pub fn apply_preview(&mut self) {
+        let Some(name) = &self.build.preview else {
+            return;
+        };
+        let name = name.trim_matches('/');
+        self.build.path_prefix = Some(match &self.build.path_prefix {
+            Some(prefix) => format!("{}/{name}", prefix.trim_end_matches('/')),
+            None => name.to_owned(),
+        });
+        self.marketing.analytics = None;
+    }
+
     /// Apply the layer of config that does auto-detection of missing values
     fn apply_autodetect_layer(&mut self, workspace_member: Option<&WorkspaceMember>) -> Result<()> {
         // Find out if we need to start in another directory, in case we're working under a
-        // workspace. If not, we start in the current directory.
+        // workspace. If not, we start in the project's directory.
         let start_dir = workspace_member
             .map(|m| m.path.clone())
-            .unwrap_or(".".into());
+            .unwrap_or_else(|| self.root_dir.clone().into());
+        self.build.find_paths(&start_dir)?;
         MdBookConfig::find_paths(&mut self.components.mdbook, &start_dir)?;
         FundingConfig::find_paths(&mut self.components.funding, &start_dir)?;
+        CommunityConfig::find_paths(&mut self.components.community, &start_dir)?;
+        LicensesConfig::find_paths(&mut self.components.licenses, &start_dir)?;
+        BenchmarksConfig::find_paths(&mut self.components.benchmarks, &start_dir)?;
+        BlogConfig::find_paths(&mut self.components.blog, &start_dir)?;
+        GithubActionConfig::find_paths(&mut self.components.github_action, &start_dir)?;
+        DocsConfig::validate(&self.components.docs)?;
+        DemoConfig::validate(&self.components.demo)?;
+        DataPagesConfig::validate(&self.components.data_pages, &start_dir)?;
+        DockerConfig::validate(&self.components.docker)?;
+        ArtifactsConfig::validate(&self.components.artifacts)?;
 
         Ok(())
     }
@@ -278,8 +419,11 @@ impl Default for Config {
             build: BuildConfig::default(),
             marketing: MarketingConfig::default(),
             styles: StyleConfig::default(),
+            markdown: MarkdownConfig::default(),
             components: ComponentConfig::default(),
             workspace: WorkspaceConfig::default(),
+            // Relative paths stay relative, rather than us looking up the current directory
+            root_dir: Utf8PathBuf::new(),
         }
     }
 }
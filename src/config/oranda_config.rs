@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use axoasset::SourceFile;
 use camino::Utf8PathBuf;
 use schemars::JsonSchema;
@@ -5,7 +7,11 @@ use serde::{Deserialize, Serialize};
 
 use crate::errors::*;
 
-use super::{BuildLayer, ComponentLayer, MarketingLayer, ProjectLayer, StyleLayer, WorkspaceLayer};
+use super::profiles::ProfileLayer;
+use super::{
+    BuildLayer, ComponentLayer, MarkdownLayer, MarketingLayer, ProjectLayer, StyleLayer,
+    WorkspaceLayer,
+};
 
 /// Configuration for `oranda` (typically stored in oranda.json)
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -22,10 +28,15 @@ pub struct OrandaLayer {
     pub marketing: Option<MarketingLayer>,
     /// Settings for themes/styles of the site
     pub styles: Option<StyleLayer>,
+    /// Settings for how your markdown is rendered
+    pub markdown: Option<MarkdownLayer>,
     /// Additional optional components
     pub components: Option<ComponentLayer>,
     /// Workspace configuration
     pub workspace: Option<WorkspaceLayer>,
+    /// Named sets of config values, like `dev` and `production`, that are applied on top of the
+    /// rest of this file when picked with `--profile`
+    pub profiles: Option<BTreeMap<String, ProfileLayer>>,
     /// Field that text-editors can use to fetch the schema for this struct
     ///
     /// We never use this, but we don't want to error out if its set.
@@ -0,0 +1,141 @@
+//! Config values set on the command line with `--set key=value`, or in the environment with
+//! `ORANDA_BUILD__PATH_PREFIX=value`
+//!
+//! These are applied right after the oranda.json, so CI can tweak a build (say, a preview
+//! deployment under its own `build.path_prefix`) without templating the config file. They only
+//! apply to the configs they're passed to, in [`ConfigOptions`][super::ConfigOptions].
+
+use std::str::FromStr;
+
+use serde_json::{Map, Value};
+
+use crate::errors::*;
+
+use super::OrandaLayer;
+
+/// A single `key=value` from `--set`, like `build.path_prefix=preview/pr-42`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigOverride {
+    /// The path to the value, like `["build", "path_prefix"]`
+    pub key: Vec<String>,
+    /// What we were given, which is read as JSON when it looks like it (`true`, `10`,
+    /// `["a", "b"]`) and as a string otherwise
+    pub value: String,
+}
+
+impl FromStr for ConfigOverride {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let Some((key, value)) = s.split_once('=') else {
+            return Err(format!(
+                "expected `key=value`, like `styles.theme=dark`, got `{s}`"
+            ));
+        };
+        let key: Vec<String> = key.trim().split('.').map(str::to_owned).collect();
+        if key.iter().any(String::is_empty) {
+            return Err(format!("`{}` isn't a valid config key", key.join(".")));
+        }
+        Ok(ConfigOverride {
+            key,
+            value: value.to_owned(),
+        })
+    }
+}
+
+impl std::fmt::Display for ConfigOverride {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.key.join("."), self.value)
+    }
+}
+
+/// What separates the parts of a key in an environment variable's name. Keys have single
+/// underscores in them already, like `path_prefix`.
+pub const ENV_SEPARATOR: &str = "__";
+
+/// The overrides set by `ORANDA_*` environment variables, like `ORANDA_STYLES__THEME=dark` for
+/// `styles.theme`. Only variables with at least two parts count, which leaves the ones that
+/// aren't config, like `ORANDA_CSS`, alone. They're sorted by name, since the environment has
+/// no order of its own.
+///
+/// Config building doesn't read the environment itself. The CLI passes these in
+/// [`ConfigOptions`][super::ConfigOptions], before the `--set` ones.
+pub fn from_env_vars(vars: impl IntoIterator<Item = (String, String)>) -> Vec<ConfigOverride> {
+    let mut overrides: Vec<ConfigOverride> = vars
+        .into_iter()
+        .filter_map(|(name, value)| {
+            let key = name.strip_prefix("ORANDA_")?;
+            let key: Vec<String> = key
+                .split(ENV_SEPARATOR)
+                .map(str::to_ascii_lowercase)
+                .collect();
+            (key.len() > 1 && !key.iter().any(String::is_empty))
+                .then_some(ConfigOverride { key, value })
+        })
+        .collect();
+    overrides.sort_by(|a, b| a.key.cmp(&b.key));
+    overrides
+}
+
+/// Turns overrides into a layer, in order, so later ones win
+pub fn to_layer(overrides: &[ConfigOverride]) -> Result<Option<OrandaLayer>> {
+    if overrides.is_empty() {
+        return Ok(None);
+    }
+    let mut root = Value::Object(Map::new());
+    for o in overrides {
+        let value = resolve_value(o)?;
+        insert(&mut root, &o.key, value);
+    }
+    let layer = serde_json::from_value(root).map_err(|e| OrandaError::ConfigOverrideInvalid {
+        key: overrides
+            .iter()
+            .map(|o| o.key.join("."))
+            .collect::<Vec<_>>()
+            .join(", "),
+        details: e.to_string(),
+    })?;
+    Ok(Some(layer))
+}
+
+/// Reads the value as JSON if that's something the config accepts at this key, and as a string
+/// otherwise, so that `project.version=1.0` doesn't become a number.
+fn resolve_value(o: &ConfigOverride) -> Result<Value> {
+    let fits = |value: &Value| {
+        let mut root = Value::Object(Map::new());
+        insert(&mut root, &o.key, value.clone());
+        serde_json::from_value::<OrandaLayer>(root)
+    };
+    if let Ok(json) = serde_json::from_str::<Value>(&o.value) {
+        if !json.is_string() && fits(&json).is_ok() {
+            return Ok(json);
+        }
+    }
+    let string = Value::String(o.value.clone());
+    fits(&string).map_err(|e| OrandaError::ConfigOverrideInvalid {
+        key: o.key.join("."),
+        details: e.to_string(),
+    })?;
+    Ok(string)
+}
+
+fn insert(root: &mut Value, key: &[String], value: Value) {
+    let mut current = root;
+    for part in &key[..key.len() - 1] {
+        let map = current
+            .as_object_mut()
+            .expect("override parents are objects");
+        let entry = map
+            .entry(part.clone())
+            .or_insert_with(|| Value::Object(Map::new()));
+        // `--set components.mdbook=false --set components.mdbook.path=book` means the latter
+        if !entry.is_object() {
+            *entry = Value::Object(Map::new());
+        }
+        current = entry;
+    }
+    current
+        .as_object_mut()
+        .expect("override parents are objects")
+        .insert(key[key.len() - 1].clone(), value);
+}
@@ -0,0 +1,93 @@
+//! Named sets of config values in the oranda.json, picked with `--profile`, so that a site can
+//! be built one way for local development and another for production without a second config
+//! file.
+//!
+//! A profile is applied right after the rest of the file it's in, and before `--set`.
+
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::*;
+
+use super::{
+    BuildLayer, ComponentLayer, MarkdownLayer, MarketingLayer, OrandaLayer, ProjectLayer,
+    StyleLayer,
+};
+
+/// Config values that a profile sets on top of the rest of the oranda.json
+///
+/// This takes the same keys as the top level of the oranda.json, except for `workspace`.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileLayer {
+    pub project: Option<ProjectLayer>,
+    pub build: Option<BuildLayer>,
+    pub marketing: Option<MarketingLayer>,
+    pub styles: Option<StyleLayer>,
+    pub markdown: Option<MarkdownLayer>,
+    pub components: Option<ComponentLayer>,
+}
+
+impl From<ProfileLayer> for OrandaLayer {
+    fn from(profile: ProfileLayer) -> Self {
+        let ProfileLayer {
+            project,
+            build,
+            marketing,
+            styles,
+            markdown,
+            components,
+        } = profile;
+        OrandaLayer {
+            project,
+            build,
+            marketing,
+            styles,
+            markdown,
+            components,
+            workspace: None,
+            profiles: None,
+            _schema: None,
+        }
+    }
+}
+
+/// Takes the profiles out of `layer`, returning the one called `name`, if one was picked, as a
+/// layer of its own. If `required`, it's an error for the file not to have it; workspace
+/// members only use it if they have it.
+pub fn take_selected(
+    layer: &mut Option<OrandaLayer>,
+    name: Option<&str>,
+    required: bool,
+) -> Result<Option<OrandaLayer>> {
+    let profiles = layer
+        .as_mut()
+        .and_then(|layer| layer.profiles.take())
+        .unwrap_or_default();
+    let Some(name) = name else {
+        return Ok(None);
+    };
+    select(profiles, name, required)
+}
+
+/// The profile called `name` out of `profiles`
+pub fn select(
+    mut profiles: BTreeMap<String, ProfileLayer>,
+    name: &str,
+    required: bool,
+) -> Result<Option<OrandaLayer>> {
+    match profiles.remove(name) {
+        Some(profile) => Ok(Some(profile.into())),
+        None if required => Err(OrandaError::UnknownProfile {
+            name: name.to_owned(),
+            available: if profiles.is_empty() {
+                "none".to_owned()
+            } else {
+                profiles.into_keys().collect::<Vec<_>>().join(", ")
+            },
+        }),
+        None => Ok(None),
+    }
+}
@@ -1,3 +1,4 @@
+use indexmap::IndexMap;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -25,13 +26,15 @@ pub struct ProjectConfig {
     pub readme_path: String,
     /// License of the project (probably SPDX format)
     pub license: Option<String>,
+    /// What the project needs to build or run, like "Rust" => "≥ 1.70"
+    pub requirements: IndexMap<String, String>,
 }
 
 /// Info about the project/application you're making a site for
 ///
 /// All of these values should automatically be sourced from your Cargo.toml or package.json
 /// whenever possible. You should only need to set these if you want to override the value.
-#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct ProjectLayer {
     /// Name of the project
@@ -85,6 +88,16 @@ pub struct ProjectLayer {
     ///
     /// This is automatically sourced from your Cargo.toml or package.json.
     pub license: Option<String>,
+    /// What your project needs to build or run, as a map from the name of the thing it needs
+    /// to the versions it works with, like `{ "Rust": "≥ 1.70", "glibc": "≥ 2.31" }`.
+    ///
+    /// These are shown on your install page, your front page's install section, and your
+    /// platforms page.
+    ///
+    /// This is automatically sourced from `rust-version` in your Cargo.toml, `engines` in
+    /// your package.json, or `requires-python` in your pyproject.toml. Anything you set here
+    /// is added to those, replacing any that have the same name.
+    pub requirements: Option<IndexMap<String, String>>,
 }
 
 impl Default for ProjectConfig {
@@ -97,6 +110,7 @@ impl Default for ProjectConfig {
             repository: None,
             readme_path: "README.md".to_owned(),
             license: None,
+            requirements: IndexMap::new(),
         }
     }
 }
@@ -113,6 +127,7 @@ impl ApplyLayer for ProjectConfig {
             repository,
             readme_path,
             license,
+            requirements,
         } = layer;
 
         // Always overwrite
@@ -123,5 +138,7 @@ impl ApplyLayer for ProjectConfig {
         self.repository.apply_opt(repository);
         self.readme_path.apply_val(readme_path);
         self.license.apply_opt(license);
+        // Detected requirements are only ever added to
+        self.requirements.extend(requirements.unwrap_or_default());
     }
 }
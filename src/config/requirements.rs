@@ -0,0 +1,97 @@
+//! Detects what a project needs to build or run, for `project.requirements`.
+//!
+//! This is autodetection, so a manifest we can't read or make sense of just means we didn't
+//! detect anything, rather than failing the build.
+
+use axoasset::LocalAsset;
+use camino::Utf8Path;
+use indexmap::IndexMap;
+
+/// Reads the requirements out of a package's Cargo.toml or package.json. `workspace_manifest`
+/// is where Cargo packages inherit `rust-version.workspace = true` from.
+pub fn from_manifest(
+    manifest_path: &Utf8Path,
+    workspace_manifest: &Utf8Path,
+) -> IndexMap<String, String> {
+    match manifest_path.file_name() {
+        Some("Cargo.toml") => rust_version(manifest_path, workspace_manifest)
+            .map(|version| IndexMap::from([("Rust".to_owned(), pretty(&version))]))
+            .unwrap_or_default(),
+        Some("package.json") => engines(manifest_path).unwrap_or_default(),
+        _ => IndexMap::new(),
+    }
+}
+
+/// Reads `requires-python` from the pyproject.toml in `dir`, if there is one
+pub fn from_pyproject(dir: &Utf8Path) -> IndexMap<String, String> {
+    let path = dir.join("pyproject.toml");
+    let requires_python = load_toml(&path).and_then(|pyproject| {
+        pyproject
+            .get("project")?
+            .get("requires-python")?
+            .as_str()
+            .map(str::to_owned)
+    });
+    requires_python
+        .map(|version| IndexMap::from([("Python".to_owned(), pretty(&version))]))
+        .unwrap_or_default()
+}
+
+fn load_toml(path: &Utf8Path) -> Option<toml::Value> {
+    if !path.is_file() {
+        return None;
+    }
+    toml::from_str(&LocalAsset::load_string(path).ok()?).ok()
+}
+
+fn rust_version(manifest_path: &Utf8Path, workspace_manifest: &Utf8Path) -> Option<String> {
+    let manifest = load_toml(manifest_path)?;
+    match manifest.get("package")?.get("rust-version")? {
+        toml::Value::String(version) => Some(format!(">={version}")),
+        // `rust-version.workspace = true`
+        _ => {
+            let workspace = load_toml(workspace_manifest)?;
+            let version = workspace
+                .get("workspace")?
+                .get("package")?
+                .get("rust-version")?
+                .as_str()?;
+            Some(format!(">={version}"))
+        }
+    }
+}
+
+fn engines(manifest_path: &Utf8Path) -> Option<IndexMap<String, String>> {
+    let manifest: serde_json::Value =
+        serde_json::from_str(&LocalAsset::load_string(manifest_path).ok()?).ok()?;
+    let engines = manifest.get("engines")?.as_object()?;
+    Some(
+        engines
+            .iter()
+            .filter_map(|(engine, version)| {
+                Some((engine_name(engine).to_owned(), pretty(version.as_str()?)))
+            })
+            .collect(),
+    )
+}
+
+/// The name people know a package.json engine by
+fn engine_name(engine: &str) -> &str {
+    match engine {
+        "node" => "Node.js",
+        "bun" => "Bun",
+        "deno" => "Deno",
+        "vscode" => "VS Code",
+        engine => engine,
+    }
+}
+
+/// Makes a version requirement read more like prose, so `>=1.70` becomes `≥ 1.70`
+pub fn pretty(requirement: &str) -> String {
+    requirement
+        .replace(">=", "≥ ")
+        .replace("<=", "≤ ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
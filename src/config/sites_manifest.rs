@@ -0,0 +1,49 @@
+use axoasset::SourceFile;
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::Deserialize;
+
+use crate::config::WorkspaceMember;
+use crate::errors::*;
+
+/// The list of unrelated projects `oranda build-many` builds, read from a `sites.json`
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SitesManifest {
+    /// Where to put the sites, each in a directory named after its slug. Relative to the
+    /// manifest.
+    ///
+    /// Defaults to "public"
+    #[serde(default = "default_dist_dir")]
+    pub dist_dir: String,
+    /// A path prefix to put in front of every site's slug, for hosting all of them somewhere
+    /// other than the root of a domain
+    pub path_prefix: Option<String>,
+    /// The projects to build. Their paths are relative to the manifest.
+    pub sites: Vec<WorkspaceMember>,
+}
+
+fn default_dist_dir() -> String {
+    "public".to_owned()
+}
+
+impl SitesManifest {
+    pub fn load(path: &Utf8Path) -> Result<Self> {
+        if !path.exists() {
+            return Err(OrandaError::FileNotFound {
+                filedesc: "sites manifest".to_owned(),
+                path: path.to_string(),
+            });
+        }
+        let manifest = SourceFile::load_local(path)?.deserialize_json()?;
+        Ok(manifest)
+    }
+
+    /// The directory the manifest's paths are relative to
+    pub fn root_dir(path: &Utf8Path) -> Result<Utf8PathBuf> {
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_str().is_empty() => dir,
+            _ => Utf8Path::new("."),
+        };
+        Ok(dir.canonicalize_utf8()?)
+    }
+}
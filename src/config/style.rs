@@ -1,7 +1,7 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::config::{ApplyLayer, ApplyOptExt};
+use crate::config::{ApplyBoolLayerExt, ApplyLayer, ApplyOptExt, BoolOr};
 use crate::site::{markdown::SyntaxTheme, oranda_theme::OrandaTheme};
 
 use super::ApplyValExt;
@@ -17,6 +17,7 @@ pub struct StyleConfig {
     pub oranda_css_version: String,
     pub logo: Option<String>,
     pub favicon: Option<String>,
+    pub web_manifest: Option<WebManifestConfig>,
 }
 /// Settings for styling your page
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -47,6 +48,96 @@ pub struct StyleLayer {
     pub logo: Option<String>,
     /// A relative path or URL to an image to use as the favicon of your site
     pub favicon: Option<String>,
+    /// Settings for a `site.webmanifest`, icons in every size browsers and phones ask for, made
+    /// from one image, and a theme color, so your site looks right when it's pinned or installed
+    ///
+    /// This is disabled by default. It can be enabled by setting `"web_manifest": true`, or
+    /// with more precise settings using `"web_manifest": { ... }`.
+    pub web_manifest: Option<BoolOr<WebManifestLayer>>,
+}
+
+/// Config for the web manifest and app icons (complete version)
+#[derive(Debug, Clone)]
+pub struct WebManifestConfig {
+    /// The image the icons are made from, or None to use the logo
+    pub icon: Option<String>,
+    /// The app's name, or None for the project's name
+    pub name: Option<String>,
+    /// The app's name under its icon on a home screen, or None for `name`
+    pub short_name: Option<String>,
+    /// The color of the browser's UI around the site, or None for the theme's background
+    pub theme_color: Option<String>,
+    /// The color of the splash screen while the site loads, or None for the theme's background
+    pub background_color: Option<String>,
+    /// How the site is shown once it's installed
+    pub display: String,
+}
+
+/// Settings for the web manifest and app icons
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct WebManifestLayer {
+    /// A relative path to a square PNG, JPEG or GIF image, at least 512x512, that the favicon,
+    /// the Apple touch icon and the manifest's icons are made from.
+    ///
+    /// Defaults to your logo, if it's one of those
+    pub icon: Option<String>,
+    /// The name of your site once it's installed.
+    ///
+    /// Defaults to your project's name
+    pub name: Option<String>,
+    /// A shorter name, for under your site's icon on a home screen.
+    ///
+    /// Defaults to `name`
+    pub short_name: Option<String>,
+    /// The color browsers and phones give their UI around your site, as a CSS color.
+    ///
+    /// Defaults to your theme's background color
+    pub theme_color: Option<String>,
+    /// The background color of the splash screen while your installed site loads, as a CSS
+    /// color.
+    ///
+    /// Defaults to your theme's background color
+    pub background_color: Option<String>,
+    /// How your site is shown once it's installed: "fullscreen", "standalone", "minimal-ui" or
+    /// "browser".
+    ///
+    /// Defaults to "standalone"
+    pub display: Option<String>,
+}
+
+impl Default for WebManifestConfig {
+    fn default() -> Self {
+        WebManifestConfig {
+            icon: None,
+            name: None,
+            short_name: None,
+            theme_color: None,
+            background_color: None,
+            display: "standalone".to_owned(),
+        }
+    }
+}
+
+impl ApplyLayer for WebManifestConfig {
+    type Layer = WebManifestLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let WebManifestLayer {
+            icon,
+            name,
+            short_name,
+            theme_color,
+            background_color,
+            display,
+        } = layer;
+        self.icon.apply_opt(icon);
+        self.name.apply_opt(name);
+        self.short_name.apply_opt(short_name);
+        self.theme_color.apply_opt(theme_color);
+        self.background_color.apply_opt(background_color);
+        self.display.apply_val(display);
+    }
 }
 
 impl Default for StyleConfig {
@@ -58,6 +149,7 @@ impl Default for StyleConfig {
             oranda_css_version: ORANDA_CSS_TAG.to_owned(),
             logo: None,
             favicon: None,
+            web_manifest: None,
         }
     }
 }
@@ -72,6 +164,7 @@ impl ApplyLayer for StyleConfig {
             oranda_css_version,
             logo,
             favicon,
+            web_manifest,
         } = layer;
 
         self.theme.apply_val(theme);
@@ -81,5 +174,6 @@ impl ApplyLayer for StyleConfig {
         self.additional_css.apply_val(additional_css);
         self.logo.apply_opt(logo);
         self.favicon.apply_opt(favicon);
+        self.web_manifest.apply_bool_layer(web_manifest);
     }
 }
@@ -1,6 +1,13 @@
-use crate::config::{ApplyLayer, ApplyOptExt, ApplyValExt};
+use crate::config::profiles::ProfileLayer;
+use crate::config::{ApplyLayer, ApplyOptExt, ApplyValExt, OrandaLayer};
+use crate::errors::*;
+use axoasset::SourceFile;
+use camino::Utf8PathBuf;
+use indexmap::IndexMap;
 use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::path::PathBuf;
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -19,15 +26,92 @@ pub struct WorkspaceLayer {
     pub auto: Option<bool>,
     /// The path to additional documentation to render
     pub docs_path: Option<String>,
+    /// Whether to show which members depend on which on the index page, read from their
+    /// Cargo.toml files
+    pub dependency_graph: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq, Eq)]
 #[serde(deny_unknown_fields)]
 pub struct WorkspaceMember {
     /// Slug for the generated URLs and directories
     pub slug: String,
     /// Path to the workspace member directory
     pub path: PathBuf,
+    /// Where the member goes on the index page. Lower weights come first, and members with the
+    /// same weight keep the order they're listed in.
+    ///
+    /// Defaults to 0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<i32>,
+    /// Config values for this member that win over its own oranda.json, to keep members
+    /// consistent from the workspace. Takes the same keys as the top level of oranda.json,
+    /// except `workspace`, and is merged into the member's config key by key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<ProfileLayer>")]
+    pub config: Option<OrderedJson>,
+}
+
+/// A JSON value whose objects keep their keys in the order they were written, so that merging
+/// config doesn't shuffle maps like `build.additional_pages`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum OrderedJson {
+    Object(IndexMap<String, OrderedJson>),
+    Array(Vec<OrderedJson>),
+    Other(Value),
+}
+
+impl OrderedJson {
+    fn deserialize<T: DeserializeOwned>(&self) -> serde_json::Result<T> {
+        serde_json::from_str(&serde_json::to_string(self)?)
+    }
+}
+
+impl WorkspaceMember {
+    pub fn weight(&self) -> i32 {
+        self.weight.unwrap_or(0)
+    }
+
+    /// Loads the member's own oranda.json at `config_path`, with the workspace's `config` for it
+    /// merged over it. Objects are merged key by key, maps like `build.additional_pages`
+    /// included, and anything else the workspace sets replaces the member's value.
+    pub fn load_layer(&self, config_path: &Utf8PathBuf) -> Result<Option<OrandaLayer>> {
+        // Load it on its own first, so that mistakes in it are reported against the file
+        let own = OrandaLayer::load(config_path)?;
+        let Some(inline) = &self.config else {
+            return Ok(own);
+        };
+        let invalid = |e: serde_json::Error| OrandaError::WorkspaceMemberConfigInvalid {
+            slug: self.slug.clone(),
+            details: e.to_string(),
+        };
+        inline.deserialize::<ProfileLayer>().map_err(invalid)?;
+
+        let mut merged = match own {
+            Some(_) => SourceFile::load_local(config_path)?.deserialize_json::<OrderedJson>()?,
+            None => OrderedJson::Object(IndexMap::new()),
+        };
+        merge_json(&mut merged, inline.clone());
+        merged.deserialize().map(Some).map_err(invalid)
+    }
+}
+
+/// Merges `overlay` into `base`, recursing into objects both of them have
+pub fn merge_json(base: &mut OrderedJson, overlay: OrderedJson) {
+    match (base, overlay) {
+        (OrderedJson::Object(base), OrderedJson::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -38,6 +122,7 @@ pub struct WorkspaceConfig {
     pub preferred_members: Vec<String>,
     pub auto: bool,
     pub docs_path: Option<String>,
+    pub dependency_graph: bool,
 }
 
 impl Default for WorkspaceConfig {
@@ -49,6 +134,7 @@ impl Default for WorkspaceConfig {
             preferred_members: Vec::new(),
             auto: false,
             docs_path: None,
+            dependency_graph: false,
         }
     }
 }
@@ -63,12 +149,14 @@ impl ApplyLayer for WorkspaceConfig {
             generate_index,
             auto,
             docs_path,
+            dependency_graph,
         } = layer;
         self.name.apply_opt(name);
         self.generate_index.apply_val(generate_index);
         self.members.apply_val(members);
         self.preferred_members.apply_val(preferred_members);
         self.auto.apply_val(auto);
-        self.docs_path = docs_path
+        self.docs_path = docs_path;
+        self.dependency_graph.apply_val(dependency_graph);
     }
 }
@@ -0,0 +1,385 @@
+//! Fetching open issues, recent discussions and response times for the project activity widget.
+//!
+//! The REST requests go through the HTTP cache, and the whole result is also kept in memory for
+//! a while, so that `oranda dev` doesn't fetch it again on every rebuild. Discussions are only
+//! available from the GraphQL API, so they're only fetched if there's a token in `GITHUB_TOKEN`.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use axoproject::GithubRepo;
+use camino::Utf8Path;
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+
+use crate::config::ActivityConfig;
+use crate::data::http_cache;
+use crate::data::sponsors::{GRAPHQL_URL, TOKEN_VAR};
+use crate::diagnostics;
+use crate::errors::*;
+
+/// How long fetched results are reused for
+const CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+/// How many of the latest issues to work out the response time from
+const ISSUES_PER_PAGE: usize = 30;
+/// How many of the latest issue comments to look for responses in
+const COMMENTS_PER_PAGE: usize = 100;
+
+static ACTIVITY_CACHE: RwLock<Vec<ActivityItem>> = RwLock::new(Vec::new());
+
+struct ActivityItem {
+    key: String,
+    fetched_at: Instant,
+    activity: ProjectActivity,
+}
+
+const DISCUSSIONS_QUERY: &str = r#"
+query($owner: String!, $name: String!, $count: Int!) {
+  repository(owner: $owner, name: $name) {
+    url
+    hasDiscussionsEnabled
+    discussions(first: $count, orderBy: { field: CREATED_AT, direction: DESC }) {
+      nodes { title url createdAt comments { totalCount } }
+    }
+  }
+}
+"#;
+
+/// Everything we could find out about the project's activity. Anything can be missing if it
+/// wasn't asked for or couldn't be fetched.
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct ProjectActivity {
+    pub open_issues: Option<u64>,
+    pub response_time: Option<ResponseTime>,
+    pub discussions: Vec<Discussion>,
+    /// Set if the repository has discussions turned on
+    pub discussions_url: Option<String>,
+}
+
+/// How long the latest issues waited for a first response, on average
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub struct ResponseTime {
+    pub hours: f64,
+    /// How many issues the average is of
+    pub issues: usize,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Discussion {
+    pub title: String,
+    pub url: String,
+    /// When the discussion was started, as YYYY-MM-DD
+    pub created_at: String,
+    pub comments: u64,
+}
+
+#[derive(Deserialize)]
+struct SearchResults {
+    total_count: u64,
+}
+
+#[derive(Deserialize)]
+struct Issue {
+    url: String,
+    user: Option<User>,
+    created_at: String,
+    pull_request: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct Comment {
+    issue_url: String,
+    user: Option<User>,
+    created_at: String,
+}
+
+#[derive(Deserialize)]
+struct User {
+    login: String,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GraphqlResponse {
+    data: Option<GraphqlData>,
+    #[serde(default)]
+    errors: Vec<GraphqlError>,
+}
+
+#[derive(Deserialize)]
+struct GraphqlError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct GraphqlData {
+    repository: Option<GraphqlRepository>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphqlRepository {
+    url: String,
+    has_discussions_enabled: bool,
+    discussions: GraphqlDiscussions,
+}
+
+#[derive(Deserialize)]
+struct GraphqlDiscussions {
+    nodes: Vec<GraphqlDiscussion>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphqlDiscussion {
+    title: String,
+    url: String,
+    created_at: String,
+    comments: GraphqlCount,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphqlCount {
+    total_count: u64,
+}
+
+impl ProjectActivity {
+    /// Fetches the activity of `repo`. Anything that fails is warned about and left out,
+    /// rather than failing the build. `root_dir` is the project's, for the HTTP cache.
+    pub fn fetch(root_dir: &Utf8Path, repo: &GithubRepo, config: &ActivityConfig) -> Self {
+        let key = format!(
+            "{}/{}:{}:{}",
+            repo.owner, repo.name, config.discussions, config.response_time
+        );
+        {
+            let cache = ACTIVITY_CACHE.read().unwrap();
+            if let Some(item) = cache
+                .iter()
+                .find(|i| i.key == key && i.fetched_at.elapsed() < CACHE_TTL)
+            {
+                return item.activity.clone();
+            }
+        }
+
+        let runtime = tokio::runtime::Handle::current();
+        let open_issues = runtime
+            .block_on(fetch_open_issues(root_dir, repo))
+            .map_err(diagnostics::report)
+            .ok();
+        let response_time = if config.response_time {
+            runtime
+                .block_on(fetch_response_time(root_dir, repo))
+                .map_err(diagnostics::report)
+                .ok()
+                .flatten()
+        } else {
+            None
+        };
+        let (discussions, discussions_url) = match std::env::var(TOKEN_VAR) {
+            Ok(token) if config.discussions > 0 => runtime
+                .block_on(fetch_discussions(repo, config.discussions, &token))
+                .map_err(diagnostics::report)
+                .unwrap_or_default(),
+            Ok(_) => Default::default(),
+            Err(_) => {
+                if config.discussions > 0 {
+                    tracing::info!(
+                        "Not fetching your latest discussions, GitHub needs a token in {TOKEN_VAR} for that"
+                    );
+                }
+                Default::default()
+            }
+        };
+        let activity = Self {
+            open_issues,
+            response_time,
+            discussions,
+            discussions_url,
+        };
+
+        let mut cache = ACTIVITY_CACHE.write().unwrap();
+        cache.retain(|i| i.key != key);
+        cache.push(ActivityItem {
+            key,
+            fetched_at: Instant::now(),
+            activity: activity.clone(),
+        });
+        activity
+    }
+}
+
+fn failed(details: impl ToString) -> OrandaError {
+    OrandaError::ActivityFetchFailed {
+        details: details.to_string(),
+    }
+}
+
+async fn get(root_dir: &Utf8Path, url: &str) -> Result<String> {
+    let body = http_cache::get_api(root_dir, url, "application/vnd.github+json")
+        .await
+        .map_err(failed)?;
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+async fn fetch_open_issues(root_dir: &Utf8Path, repo: &GithubRepo) -> Result<u64> {
+    // The repository's own `open_issues_count` includes pull requests, so ask the search API
+    let url = format!(
+        "https://api.github.com/search/issues?q=repo:{}/{}+is:issue+is:open&per_page=1",
+        repo.owner, repo.name
+    );
+    parse_issue_count(&get(root_dir, &url).await?)
+}
+
+async fn fetch_response_time(
+    root_dir: &Utf8Path,
+    repo: &GithubRepo,
+) -> Result<Option<ResponseTime>> {
+    let base = format!("https://api.github.com/repos/{}/{}", repo.owner, repo.name);
+    let issues = get(
+        root_dir,
+        &format!("{base}/issues?state=all&sort=created&direction=desc&per_page={ISSUES_PER_PAGE}"),
+    )
+    .await?;
+    let comments = get(
+        root_dir,
+        &format!("{base}/issues/comments?sort=created&direction=desc&per_page={COMMENTS_PER_PAGE}"),
+    )
+    .await?;
+    response_time(&issues, &comments)
+}
+
+async fn fetch_discussions(
+    repo: &GithubRepo,
+    count: usize,
+    token: &str,
+) -> Result<(Vec<Discussion>, Option<String>)> {
+    let body = serde_json::json!({
+        "query": DISCUSSIONS_QUERY,
+        "variables": { "owner": repo.owner, "name": repo.name, "count": count.min(100) },
+    });
+    let response = reqwest::Client::new()
+        .post(GRAPHQL_URL)
+        .header(reqwest::header::USER_AGENT, "oranda")
+        .bearer_auth(token)
+        .json(&body)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(failed)?
+        .text()
+        .await
+        .map_err(failed)?;
+    parse_discussions(&response)
+}
+
+/// Reads how many issues GitHub's search API found
+pub fn parse_issue_count(json: &str) -> Result<u64> {
+    let results: SearchResults = serde_json::from_str(json).map_err(failed)?;
+    Ok(results.total_count)
+}
+
+/// Works out how long the issues in `issues_json` waited for their first comment from someone
+/// other than whoever opened them, from the latest comments in `comments_json`, both as GitHub's
+/// REST API returns them, newest first. Issues that haven't had a response yet, and issues
+/// older than the oldest comment (whose first response might be missing), are left out.
+pub fn response_time(issues_json: &str, comments_json: &str) -> Result<Option<ResponseTime>> {
+    let issues: Vec<Issue> = serde_json::from_str(issues_json).map_err(failed)?;
+    let comments: Vec<Comment> = serde_json::from_str(comments_json).map_err(failed)?;
+    let comments = comments
+        .iter()
+        .filter_map(|comment| Some((comment, parse_date(&comment.created_at)?)))
+        .collect::<Vec<_>>();
+    // If we got a full page, there may be older comments we didn't see
+    let window_start = if comments.len() >= COMMENTS_PER_PAGE {
+        comments.iter().map(|(_, date)| *date).min()
+    } else {
+        None
+    };
+
+    // Who commented on each issue, and when, leaving out bots
+    let mut responses = HashMap::<&str, Vec<(&str, DateTime<FixedOffset>)>>::new();
+    for (comment, date) in &comments {
+        let Some(user) = &comment.user else { continue };
+        if user.kind.as_deref() == Some("Bot") {
+            continue;
+        }
+        responses
+            .entry(comment.issue_url.as_str())
+            .or_default()
+            .push((user.login.as_str(), *date));
+    }
+
+    let waits = issues
+        .iter()
+        .filter(|issue| issue.pull_request.is_none())
+        .filter_map(|issue| {
+            let opened = parse_date(&issue.created_at)?;
+            if window_start.is_some_and(|start| opened < start) {
+                return None;
+            }
+            let author = issue.user.as_ref().map(|user| user.login.as_str());
+            let responded = responses
+                .get(issue.url.as_str())?
+                .iter()
+                .filter(|(login, _)| Some(*login) != author)
+                .map(|(_, date)| *date)
+                .min()?;
+            Some((responded - opened).num_seconds().max(0) as f64 / 3600.0)
+        })
+        .collect::<Vec<_>>();
+    if waits.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(ResponseTime {
+        hours: waits.iter().sum::<f64>() / waits.len() as f64,
+        issues: waits.len(),
+    }))
+}
+
+/// Reads the latest discussions from a GraphQL response, and the link to all of them if the
+/// repository has discussions turned on
+pub fn parse_discussions(json: &str) -> Result<(Vec<Discussion>, Option<String>)> {
+    let response: GraphqlResponse = serde_json::from_str(json).map_err(failed)?;
+    let repository = response
+        .data
+        .and_then(|data| data.repository)
+        .ok_or_else(|| {
+            let messages = response
+                .errors
+                .iter()
+                .map(|e| e.message.as_str())
+                .collect::<Vec<_>>();
+            if messages.is_empty() {
+                failed("no such repository")
+            } else {
+                failed(messages.join(", "))
+            }
+        })?;
+    if !repository.has_discussions_enabled {
+        return Ok((vec![], None));
+    }
+    let discussions = repository
+        .discussions
+        .nodes
+        .into_iter()
+        .map(|discussion| Discussion {
+            created_at: discussion
+                .created_at
+                .split('T')
+                .next()
+                .unwrap_or_default()
+                .to_owned(),
+            title: discussion.title,
+            url: discussion.url,
+            comments: discussion.comments.total_count,
+        })
+        .collect();
+    Ok((discussions, Some(format!("{}/discussions", repository.url))))
+}
+
+fn parse_date(date: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(date).ok()
+}
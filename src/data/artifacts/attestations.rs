@@ -0,0 +1,187 @@
+//! Signatures, certificates and provenance attached to a Release
+//!
+//! Files like `my-app.tar.gz.sig` or `my-app.tar.gz.sigstore.json` aren't useful downloads on
+//! their own, so we attach them to the file they're for (see [`Attestations`][]) and suggest
+//! commands to check that file with them.
+
+use axoproject::GithubRepo;
+use serde::Serialize;
+
+use super::*;
+
+/// Detached signatures, like the ones `cosign sign-blob` writes
+const EXTS_FOR_SIGNATURE: &[&str] = &[".sig"];
+/// The certificates that go with keyless signatures
+const EXTS_FOR_CERTIFICATE: &[&str] = &[".pem", ".crt", ".cert"];
+/// Sigstore bundles, containing both a signature and its certificate
+const EXTS_FOR_SIGSTORE_BUNDLE: &[&str] = &[".sigstore.json", ".sigstore", ".bundle"];
+/// SLSA provenance, as written by slsa-github-generator
+const EXTS_FOR_PROVENANCE: &[&str] = &[".intoto.jsonl"];
+
+/// The public key that goes with non-keyless signatures, if it's attached to the Release
+const COSIGN_PUBLIC_KEY: &str = "cosign.pub";
+/// Who issues the certificates for keyless signing in GitHub Actions
+const GITHUB_ACTIONS_ISSUER: &str = "https://token.actions.githubusercontent.com";
+
+/// Files in the Release that can be used to verify a File
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct Attestations {
+    /// A detached signature
+    pub signature: Option<FileIdx>,
+    /// The certificate for `signature`
+    pub certificate: Option<FileIdx>,
+    /// A sigstore bundle, which can be used instead of `signature` and `certificate`
+    pub sigstore_bundle: Option<FileIdx>,
+    /// SLSA provenance covering this file (possibly along with others)
+    pub provenance: Option<FileIdx>,
+    /// Commands to verify the file with the above, to run where they've all been downloaded
+    pub verify_hints: Vec<String>,
+}
+
+impl Attestations {
+    /// Whether there's anything to verify the file with
+    pub fn is_empty(&self) -> bool {
+        self.signature.is_none()
+            && self.certificate.is_none()
+            && self.sigstore_bundle.is_none()
+            && self.provenance.is_none()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum AttestationKind {
+    Signature,
+    Certificate,
+    SigstoreBundle,
+    Provenance,
+}
+
+impl ReleaseArtifacts {
+    /// Attach signatures, certificates, sigstore bundles and provenance to the files they're for
+    ///
+    /// `repo` is where the Release was built, which is what keyless signatures from GitHub
+    /// Actions are checked against.
+    pub fn add_attestations(&mut self, repo: Option<&GithubRepo>) {
+        let mut release_provenance = None;
+        for idx in self.file_indices() {
+            let name = self.file(idx).name.clone();
+            let Some((kind, subject)) = attestation_kind(&name) else {
+                continue;
+            };
+            // These aren't installers, whatever their name looks like
+            self.file_mut(idx).infer = false;
+            if self.file(idx).unavailable {
+                continue;
+            }
+            let Some(subject) = self.file_idx(&subject.to_owned()) else {
+                // Provenance is usually written for all the files at once, as something like
+                // `multiple.intoto.jsonl`
+                if kind == AttestationKind::Provenance {
+                    release_provenance = Some(idx);
+                }
+                continue;
+            };
+            let attestations = &mut self.file_mut(subject).attestations;
+            match kind {
+                AttestationKind::Signature => attestations.signature = Some(idx),
+                AttestationKind::Certificate => attestations.certificate = Some(idx),
+                AttestationKind::SigstoreBundle => attestations.sigstore_bundle = Some(idx),
+                AttestationKind::Provenance => attestations.provenance = Some(idx),
+            }
+        }
+
+        let public_key = self.file_idx(&COSIGN_PUBLIC_KEY.to_owned());
+        for idx in self.file_indices() {
+            if attestation_kind(&self.file(idx).name).is_some() || Some(idx) == public_key {
+                continue;
+            }
+            if self.file(idx).attestations.provenance.is_none() {
+                self.file_mut(idx).attestations.provenance = release_provenance;
+            }
+            let verify_hints = self.verify_hints(idx, public_key, repo);
+            self.file_mut(idx).attestations.verify_hints = verify_hints;
+        }
+    }
+
+    fn verify_hints(
+        &self,
+        idx: FileIdx,
+        public_key: Option<FileIdx>,
+        repo: Option<&GithubRepo>,
+    ) -> Vec<String> {
+        let file = self.file(idx);
+        let attestations = &file.attestations;
+        let name = |idx: FileIdx| self.file(idx).name.clone();
+        // Keyless signatures are only worth checking against the workflows of the repo
+        let identity = repo.map(|repo| {
+            format!(
+                "--certificate-identity-regexp '^https://github.com/{}/{}/' --certificate-oidc-issuer {GITHUB_ACTIONS_ISSUER}",
+                repo.owner, repo.name
+            )
+        });
+
+        let mut hints = vec![];
+        match (
+            attestations.sigstore_bundle,
+            attestations.signature,
+            attestations.certificate,
+        ) {
+            (Some(bundle), _, _) => {
+                if let Some(identity) = &identity {
+                    hints.push(format!(
+                        "cosign verify-blob {} --bundle {} {identity}",
+                        file.name,
+                        name(bundle)
+                    ));
+                }
+            }
+            (None, Some(signature), Some(certificate)) => {
+                if let Some(identity) = &identity {
+                    hints.push(format!(
+                        "cosign verify-blob {} --signature {} --certificate {} {identity}",
+                        file.name,
+                        name(signature),
+                        name(certificate)
+                    ));
+                }
+            }
+            (None, Some(signature), None) => {
+                if let Some(key) = public_key {
+                    hints.push(format!(
+                        "cosign verify-blob {} --signature {} --key {}",
+                        file.name,
+                        name(signature),
+                        name(key)
+                    ));
+                }
+            }
+            _ => {}
+        }
+        if let (Some(provenance), Some(repo)) = (attestations.provenance, repo) {
+            hints.push(format!(
+                "slsa-verifier verify-artifact {} --provenance-path {} --source-uri github.com/{}/{}",
+                file.name,
+                name(provenance),
+                repo.owner,
+                repo.name
+            ));
+        }
+        hints
+    }
+}
+
+/// If this file looks like an attestation, returns what kind it is and the name of the file
+/// it's for
+fn attestation_kind(name: &str) -> Option<(AttestationKind, &str)> {
+    let kinds = [
+        (EXTS_FOR_SIGSTORE_BUNDLE, AttestationKind::SigstoreBundle),
+        (EXTS_FOR_SIGNATURE, AttestationKind::Signature),
+        (EXTS_FOR_CERTIFICATE, AttestationKind::Certificate),
+        (EXTS_FOR_PROVENANCE, AttestationKind::Provenance),
+    ];
+    kinds.into_iter().find_map(|(exts, kind)| {
+        exts.iter()
+            .find_map(|ext| name.strip_suffix(ext))
+            .map(|subject| (kind, subject))
+    })
+}
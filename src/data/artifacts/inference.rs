@@ -1,6 +1,9 @@
 use camino::Utf8PathBuf;
+use regex::Regex;
 
+use super::targets::targets_in_name;
 use super::*;
+use crate::config::MatchingConfig;
 
 // Architectures
 // const ARCH_X86: &str = "i686";
@@ -84,9 +87,44 @@ const KNOWN_WINDOWS_SCRIPT_EXTS: &[&str] = &[EXT_SCRIPT_POWERSHELL];
 const KNOWN_UNIX_SCRIPT_EXTS: &[&str] = &[EXT_SCRIPT_SHELL];
 pub(crate) const KNOWN_SCRIPT_EXTS: &[&str] = &[EXT_SCRIPT_SHELL, EXT_SCRIPT_POWERSHELL];
 
+/// The user's rules from `components.artifacts.matching`, compiled
+struct Matchers {
+    installers: Vec<Regex>,
+    archives: Vec<Regex>,
+    checksums: Vec<Regex>,
+    ignored: Vec<Regex>,
+}
+
+impl Matchers {
+    fn new(matching: &MatchingConfig) -> Self {
+        // The patterns were already checked when we loaded the config
+        let compile = |patterns: &[String]| {
+            patterns
+                .iter()
+                .filter_map(|pattern| Regex::new(pattern).ok())
+                .collect::<Vec<_>>()
+        };
+        Self {
+            installers: compile(&matching.installers),
+            archives: compile(&matching.archives),
+            checksums: compile(&matching.checksums),
+            ignored: compile(&matching.ignored),
+        }
+    }
+}
+
+fn any_match(patterns: &[Regex], name: &str) -> bool {
+    patterns.iter().any(|pattern| pattern.is_match(name))
+}
+
 impl ReleaseArtifacts {
     /// Infer installers/artifacts based solely on file names
-    pub fn add_inference(&mut self) {
+    ///
+    /// The rules in `config.matching` are checked before our own.
+    pub fn add_inference(&mut self, config: &ArtifactsConfig) {
+        let matchers = Matchers::new(&config.matching);
+        self.add_matched_checksums(&matchers);
+
         // Gotta clone this upfront to avoid borrowing stuff
         let app_name = self.app_name.clone();
         for file_idx in self.file_indices() {
@@ -104,20 +142,29 @@ impl ReleaseArtifacts {
             }
 
             // Search for target triples in the file name
-            let mut targets = vec![];
-            for target in KNOWN_TARGET_TRIPLES.iter().copied().flatten().copied() {
-                if file.name.contains(target) {
-                    targets.push(target.to_owned());
-                }
-            }
+            let mut targets = targets_in_name(&file.name, Some(config));
 
             let label;
             let description = String::new();
             let method;
             let preference;
 
-            // Try to detect what kind of file this is
-            if file.name.contains("install")
+            // Try to detect what kind of file this is, starting with the user's rules
+            if any_match(&matchers.installers, &file.name) {
+                if targets.is_empty() {
+                    targets = infer_targets_for_bundle(file);
+                }
+                label = infer_label_for_bundle(file);
+                preference = InstallerPreference::Native;
+                method = InstallMethod::Download { file: file_idx };
+            } else if any_match(&matchers.archives, &file.name) {
+                if targets.is_empty() {
+                    continue;
+                }
+                label = infer_label_for_archive(file);
+                preference = InstallerPreference::Archive;
+                method = InstallMethod::Download { file: file_idx };
+            } else if file.name.contains("install")
                 && KNOWN_SCRIPT_EXTS.iter().any(|ext| file.name.ends_with(ext))
             {
                 // Looks like an installer script! Recommend a ~curl|sh for it.
@@ -178,6 +225,38 @@ impl ReleaseArtifacts {
             self.add_installer(installer);
         }
     }
+
+    /// Stops ignored files and checksums from being inferred as installers, and attaches
+    /// checksums to the files they're for
+    fn add_matched_checksums(&mut self, matchers: &Matchers) {
+        for idx in self.file_indices() {
+            let name = self.file(idx).name.clone();
+            if any_match(&matchers.ignored, &name) {
+                self.file_mut(idx).infer = false;
+                continue;
+            }
+            let Some(found) = matchers
+                .checksums
+                .iter()
+                .find_map(|pattern| pattern.find(&name))
+            else {
+                continue;
+            };
+            self.file_mut(idx).infer = false;
+            // `app.zip.b3` is the checksum for `app.zip`, but we can't tell what `SHA256SUMS`
+            // is for
+            if found.end() != name.len() {
+                continue;
+            }
+            let Some(subject) = self.file_idx(&name[..found.start()].to_owned()) else {
+                continue;
+            };
+            let subject = self.file_mut(subject);
+            if subject.checksum_file.is_none() {
+                subject.checksum_file = Some(idx);
+            }
+        }
+    }
 }
 
 /// Given a file that appears to be a "bundle" but doesn't specify a target,
@@ -256,10 +335,10 @@ fn infer_run_hint_for_script(file: &File) -> String {
 
 /// Infer the label for a bundle
 fn infer_label_for_bundle(file: &File) -> String {
-    // For now just use the extension
+    // For now just use the extension (files the user told us are installers might not have one)
     Utf8PathBuf::from(&file.name)
         .extension()
-        .expect("we determined a file was a bundle based on its extension, but it had none?")
+        .unwrap_or(&file.name)
         .to_owned()
 }
 
@@ -0,0 +1,161 @@
+//! Serving a release's files from the site itself, see [`ReleaseArtifacts::mirror_files`][]
+
+use camino::Utf8PathBuf;
+use reqwest::header::USER_AGENT;
+use sha2::{Digest, Sha256, Sha512};
+
+use crate::config::{Config, MirrorConfig};
+use crate::diagnostics;
+use crate::errors::*;
+use crate::site::link;
+use crate::site::writer::SiteWriter;
+
+use super::{File, ReleaseArtifacts};
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+impl ReleaseArtifacts {
+    /// Downloads the files selected by the mirror config, writes them to `dl/<version>/` in the
+    /// site, and points their download links there. A `SHA256SUMS` file listing every mirrored
+    /// file is written alongside them.
+    ///
+    /// Files that have a checksum file in the release are verified against it. Any file that
+    /// fails to download or verify keeps its original link, with a warning.
+    pub fn mirror_files(
+        &mut self,
+        version: &str,
+        mirror: &MirrorConfig,
+        config: &Config,
+        writer: &mut dyn SiteWriter,
+    ) -> Result<()> {
+        let patterns = mirror
+            .files
+            .iter()
+            .map(|pattern| glob::Pattern::new(pattern))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| OrandaError::Other(format!("Invalid mirror file pattern: {e}")))?;
+        let dir = format!("dl/{version}");
+        let dest_dir = Utf8PathBuf::from(&dir);
+        let max_bytes = mirror.max_size_mb * BYTES_PER_MB;
+
+        let mut sums = Vec::new();
+        for idx in 0..self.files.len() {
+            let file = &self.files[idx];
+            if file.unavailable
+                || (!patterns.is_empty() && !patterns.iter().any(|p| p.matches(&file.name)))
+            {
+                continue;
+            }
+            let checksum_url = file
+                .checksum_file
+                .map(|checksum| self.files[checksum.0].download_url.clone());
+            if checksum_url.is_none() && mirror.require_checksum {
+                continue;
+            }
+
+            let future = download_and_verify(file, checksum_url.as_deref(), max_bytes);
+            match tokio::runtime::Handle::current().block_on(future) {
+                Ok((bytes, sha256)) => {
+                    let path = format!("{dir}/{}", file.name);
+                    writer.write(&dest_dir.join(&file.name), &bytes)?;
+                    sums.push(format!("{sha256}  {}", file.name));
+                    self.files[idx].download_url =
+                        link::generate_relative(&config.build.path_prefix, &path);
+                }
+                Err(details) => {
+                    let e = OrandaError::MirrorFailed {
+                        name: file.name.clone(),
+                        details,
+                    };
+                    diagnostics::report(e);
+                }
+            }
+        }
+
+        if !sums.is_empty() {
+            sums.push(String::new());
+            writer.write(&dest_dir.join("SHA256SUMS"), sums.join("\n").as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+/// Downloads a file, checking it against the checksum file at `checksum_url` if there is
+/// one. Returns the file's contents and its SHA-256.
+async fn download_and_verify(
+    file: &File,
+    checksum_url: Option<&str>,
+    max_bytes: u64,
+) -> std::result::Result<(Vec<u8>, String), String> {
+    let bytes = download(&file.download_url, max_bytes).await?;
+    if let Some(checksum_url) = checksum_url {
+        let checksums = download(checksum_url, BYTES_PER_MB).await?;
+        let checksums = String::from_utf8_lossy(&checksums);
+        let expected = expected_checksum(&checksums, &file.name)
+            .ok_or_else(|| format!("couldn't find its checksum in {checksum_url}"))?;
+        let actual = match expected.len() {
+            64 => format!("{:x}", Sha256::digest(&bytes)),
+            128 => format!("{:x}", Sha512::digest(&bytes)),
+            _ => {
+                return Err(format!(
+                    "{checksum_url} isn't a SHA-256 or SHA-512 checksum"
+                ))
+            }
+        };
+        if actual != expected {
+            return Err(format!(
+                "its checksum is {actual}, but {checksum_url} says it should be {expected}"
+            ));
+        }
+    }
+    let sha256 = format!("{:x}", Sha256::digest(&bytes));
+    Ok((bytes, sha256))
+}
+
+async fn download(url: &str, max_bytes: u64) -> std::result::Result<Vec<u8>, String> {
+    let too_large = || format!("it's larger than {} MB", max_bytes / BYTES_PER_MB);
+    let mut response = reqwest::Client::new()
+        .get(url)
+        .header(USER_AGENT, "oranda")
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| e.to_string())?;
+    if response.content_length().is_some_and(|len| len > max_bytes) {
+        return Err(too_large());
+    }
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+        bytes.extend_from_slice(&chunk);
+        if bytes.len() as u64 > max_bytes {
+            return Err(too_large());
+        }
+    }
+    Ok(bytes)
+}
+
+/// Finds the checksum for `name` in the contents of a checksum file. We accept the output of
+/// `sha256sum` and friends (`<hash>  <name>`, possibly for several files), as well as files
+/// that contain nothing but the hash.
+pub fn expected_checksum(contents: &str, name: &str) -> Option<String> {
+    let is_hash = |token: &str| token.len() >= 64 && token.chars().all(|c| c.is_ascii_hexdigit());
+    let mut only_hash = None;
+    let lines = contents.lines().filter(|line| !line.trim().is_empty());
+    for (i, line) in lines.enumerate() {
+        let mut tokens = line.split_whitespace();
+        let (Some(hash), file_name) = (tokens.next(), tokens.next()) else {
+            continue;
+        };
+        if !is_hash(hash) {
+            continue;
+        }
+        match file_name {
+            Some(file_name) if file_name.trim_start_matches('*') == name => {
+                return Some(hash.to_ascii_lowercase());
+            }
+            None if i == 0 => only_hash = Some(hash.to_ascii_lowercase()),
+            _ => {}
+        }
+    }
+    only_hash
+}
@@ -9,24 +9,43 @@
 //! * [`ReleaseArtifacts::add_inference`][] (in a different file)
 //! * [`ReleaseArtifacts::add_package_managers`][]
 //!
+//! Optionally, [`ReleaseArtifacts::check_downloads`][] can then weed out files that have been
+//! deleted from the Release, and [`ReleaseArtifacts::mirror_files`][] can copy them onto the
+//! site. [`ReleaseArtifacts::add_attestations`][] (in a different file) attaches signatures and
+//! provenance to the files they're for, and [`ReleaseArtifacts::add_sboms`][] (also in a
+//! different file) picks out SBOMs.
+//!
 //! One you've added all the data you want, call [`ReleaseArtifacts::select_installers`][]
 //! to compute the final result, which is stored in [`ReleaseArtifacts::installers_by_target`][].
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::OnceLock;
 
 use axoasset::{LocalAsset, RemoteAsset};
 use axoproject::platforms::KNOWN_TARGET_TRIPLES;
 use camino::Utf8PathBuf;
 use indexmap::IndexMap;
 use serde::{Serialize, Serializer};
+use tokio::sync::Semaphore;
 
 use crate::config::ArtifactsConfig;
 use crate::config::Config;
+use crate::data::artifacts_schema::{
+    FileJson, InstallMethodJson, InstallerJson, ReleaseArtifactsJson,
+};
 use crate::errors::*;
 
 use inference::KNOWN_SCRIPT_EXTS;
 
+pub use attestations::Attestations;
+pub use sbom::{Sbom, SbomFormat};
+pub use targets::{target_label, KnownTarget, KNOWN_TARGETS};
+
+pub mod attestations;
 pub mod inference;
+pub mod mirror;
+pub mod sbom;
+pub mod targets;
 
 /// A Target Triple like x86_64-pc-windows-msvc
 pub type TargetTriple = String;
@@ -37,6 +56,11 @@ pub type AppName = String;
 /// The name of a file
 pub type FileName = String;
 
+/// How many HEAD requests [`ReleaseArtifacts::check_downloads`][] sends at once, across all
+/// releases
+const MAX_DOWNLOAD_CHECKS: usize = 16;
+static DOWNLOAD_CHECKS: OnceLock<Semaphore> = OnceLock::new();
+
 /// Info about the artifacts of a Release
 #[derive(Debug, Default, Clone, Serialize)]
 pub struct ReleaseArtifacts {
@@ -52,12 +76,20 @@ pub struct ReleaseArtifacts {
     /// What installers to use for each target, in descending order
     /// (so recommend the first one, potentially show the others in tabs)
     targets: BTreeMap<TargetTriple, Vec<InstallerIdx>>,
+    /// Software Bills of Materials found in the Release
+    sboms: Vec<Sbom>,
 }
 
 /// A handle to a File (equivalent to a pointer into `ReleaseArtifacts::files`)
 #[derive(Debug, Copy, Clone, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct FileIdx(usize);
 
+impl From<FileIdx> for usize {
+    fn from(idx: FileIdx) -> usize {
+        idx.0
+    }
+}
+
 /// A File we found in the Release
 #[derive(Debug, Clone, Serialize)]
 pub struct File {
@@ -69,6 +101,13 @@ pub struct File {
     pub view_path: Option<String>,
     /// A file containing checksums for this one
     pub checksum_file: Option<FileIdx>,
+    /// Whether the file 404'd when we checked for it, see
+    /// [`ReleaseArtifacts::check_downloads`][]
+    pub unavailable: bool,
+    /// How many times the file has been downloaded, if the release source tells us
+    pub download_count: Option<u64>,
+    /// Signatures and provenance for this file, see [`ReleaseArtifacts::add_attestations`][]
+    pub attestations: Attestations,
     /// Whether artifact_inference should process this file
     ///
     /// Starts true, but can be set to false by other steps to avoid suggesting an installer twice
@@ -76,6 +115,18 @@ pub struct File {
     pub infer: bool,
 }
 
+impl File {
+    /// Convert to the stable `artifacts.json` representation
+    pub fn to_schema(&self) -> FileJson {
+        FileJson {
+            name: self.name.clone(),
+            download_url: self.download_url.clone(),
+            view_path: self.view_path.clone(),
+            checksum_file: self.checksum_file.map(|f| f.0),
+        }
+    }
+}
+
 /// A handle to an Installer (equivalent to a pointer into [`ReleaseArtifacts::installers`][])
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Serialize)]
 pub struct InstallerIdx(pub usize);
@@ -201,6 +252,12 @@ impl ReleaseArtifacts {
     pub fn files(&self) -> impl Iterator<Item = &File> {
         self.files.values()
     }
+    /// How many times the files of this release have been downloaded in total, if we know
+    pub fn download_count(&self) -> Option<u64> {
+        self.files()
+            .filter_map(|file| file.download_count)
+            .reduce(|a, b| a + b)
+    }
     /// Get an installer
     pub fn installer(&self, idx: InstallerIdx) -> &Installer {
         &self.installers[idx.0]
@@ -212,11 +269,80 @@ impl ReleaseArtifacts {
             .enumerate()
             .map(|(idx, ins)| (InstallerIdx(idx), ins))
     }
+    /// Get all the SBOMs
+    pub fn sboms(&self) -> &[Sbom] {
+        &self.sboms
+    }
     /// Get all target -> installer mappings
     pub fn installers_by_target(&self) -> &BTreeMap<TargetTriple, Vec<InstallerIdx>> {
         &self.targets
     }
 
+    /// Convert to the stable `artifacts.json` representation
+    pub fn to_schema(&self) -> ReleaseArtifactsJson {
+        ReleaseArtifactsJson {
+            files: self.files().map(File::to_schema).collect(),
+            installers: self
+                .installers
+                .iter()
+                .map(|installer| InstallerJson {
+                    label: installer.label.clone(),
+                    description: installer.description.clone(),
+                    app_name: installer.app_name.clone(),
+                    method: match &installer.method {
+                        InstallMethod::Download { file } => {
+                            InstallMethodJson::Download { file: file.0 }
+                        }
+                        InstallMethod::Run { file, run_hint } => InstallMethodJson::Run {
+                            file: file.map(|f| f.0),
+                            run_hint: run_hint.clone(),
+                        },
+                    },
+                })
+                .collect(),
+            targets: self
+                .targets
+                .iter()
+                .map(|(target, installers)| {
+                    (target.clone(), installers.iter().map(|i| i.0).collect())
+                })
+                .collect(),
+        }
+    }
+
+    /// Sends a HEAD request for every file to find the ones that have been deleted since the
+    /// Release was published. Those files are marked as unavailable, and excluded from
+    /// inference and installer selection.
+    ///
+    /// Only definitive "not found" responses count, so a flaky network won't hide anything.
+    pub async fn check_downloads(&mut self) {
+        let semaphore = DOWNLOAD_CHECKS.get_or_init(|| Semaphore::new(MAX_DOWNLOAD_CHECKS));
+        let client = reqwest::Client::new();
+        let checks = self.files.values().map(|file| {
+            let request = client.head(&file.download_url);
+            async move {
+                let _permit = semaphore.acquire().await.ok()?;
+                let response = request.send().await.ok()?;
+                Some(matches!(
+                    response.status(),
+                    reqwest::StatusCode::NOT_FOUND | reqwest::StatusCode::GONE
+                ))
+            }
+        });
+        let results = futures_util::future::join_all(checks).await;
+        for (file, missing) in self.files.values_mut().zip(results) {
+            if missing == Some(true) {
+                tracing::warn!(
+                    "{} is no longer available at {}, hiding it",
+                    file.name,
+                    file.download_url
+                );
+                file.unavailable = true;
+                file.infer = false;
+            }
+        }
+    }
+
     /// Add custom package manager values from the config
     pub fn add_package_managers(&mut self, config: &ArtifactsConfig) {
         // If we have a custom item for "npm" or "npx", then supress any entries
@@ -245,13 +371,29 @@ impl ReleaseArtifacts {
 
     /// Now that we've added all the data sources, select installers for each target
     pub fn select_installers(&mut self, artifacts_config: &ArtifactsConfig) {
-        // Hide anything that the user has asked for
+        // Hide anything that the user has asked for, or that needs a file that's gone
         for installer in &mut self.installers {
-            if artifacts_config.hidden.contains(&installer.label) {
+            let file = match installer.method {
+                InstallMethod::Download { file } => Some(file),
+                InstallMethod::Run { file, .. } => file,
+            };
+            let file_unavailable = file.is_some_and(|idx| {
+                self.files
+                    .get_index(idx.0)
+                    .is_some_and(|(_, file)| file.unavailable)
+            });
+            if artifacts_config.hidden.contains(&installer.label) || file_unavailable {
                 installer.display = DisplayPreference::Hidden;
             }
         }
-        for target in KNOWN_TARGET_TRIPLES.iter().copied().flatten().copied() {
+        // Every target something can be installed on, which can include targets we've never
+        // heard of if cargo-dist or the user's config told us about them
+        let all_targets = self
+            .installers
+            .iter()
+            .flat_map(|installer| installer.targets.keys().cloned())
+            .collect::<BTreeSet<_>>();
+        for target in &all_targets {
             // Gather up all the installers into an array
             let mut installers = vec![];
             for (idx, installer) in self.installers() {
@@ -0,0 +1,88 @@
+//! Software Bills of Materials attached to a Release
+//!
+//! These are recognized by their name, like `my-app.cdx.xml` (which cargo-dist writes with
+//! cargo-cyclonedx) or `my-app.spdx.json`.
+
+use serde::Serialize;
+
+use super::*;
+
+/// SPDX documents, by extension, along with how they're encoded
+const EXTS_FOR_SPDX: &[(&str, &str)] = &[
+    (".spdx.json", "JSON"),
+    (".spdx.xml", "XML"),
+    (".spdx.yaml", "YAML"),
+    (".spdx.yml", "YAML"),
+    (".spdx.rdf", "RDF"),
+    (".spdx", "tag-value"),
+];
+/// CycloneDX documents, by extension, along with how they're encoded
+const EXTS_FOR_CYCLONEDX: &[(&str, &str)] = &[
+    (".cdx.json", "JSON"),
+    (".cdx.xml", "XML"),
+    ("bom.json", "JSON"),
+    ("bom.xml", "XML"),
+];
+
+/// The SBOM standards we know about
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+pub enum SbomFormat {
+    Spdx,
+    CycloneDx,
+}
+
+impl SbomFormat {
+    pub fn name(self) -> &'static str {
+        match self {
+            SbomFormat::Spdx => "SPDX",
+            SbomFormat::CycloneDx => "CycloneDX",
+        }
+    }
+}
+
+/// An SBOM we found in the Release
+#[derive(Debug, Clone, Serialize)]
+pub struct Sbom {
+    /// The file containing it
+    pub file: FileIdx,
+    pub format: SbomFormat,
+    /// How the document is written, like "JSON"
+    pub encoding: String,
+    /// The format and encoding, like "CycloneDX (XML)"
+    pub label: String,
+}
+
+impl ReleaseArtifacts {
+    /// Find the SBOMs among the Release's files
+    pub fn add_sboms(&mut self) {
+        for idx in self.file_indices() {
+            let file = self.file(idx);
+            if file.unavailable {
+                continue;
+            }
+            let Some((format, encoding)) = sbom_format(&file.name) else {
+                continue;
+            };
+            // SBOMs are for reading, not installing
+            self.file_mut(idx).infer = false;
+            self.sboms.push(Sbom {
+                file: idx,
+                format,
+                encoding: encoding.to_owned(),
+                label: format!("{} ({encoding})", format.name()),
+            });
+        }
+    }
+}
+
+fn sbom_format(name: &str) -> Option<(SbomFormat, &'static str)> {
+    let formats = [
+        (EXTS_FOR_SPDX, SbomFormat::Spdx),
+        (EXTS_FOR_CYCLONEDX, SbomFormat::CycloneDx),
+    ];
+    formats.into_iter().find_map(|(exts, format)| {
+        exts.iter()
+            .find(|(ext, _)| name.ends_with(ext))
+            .map(|(_, encoding)| (format, *encoding))
+    })
+}
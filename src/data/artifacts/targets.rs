@@ -0,0 +1,78 @@
+//! The target triples we know how to describe
+//!
+//! Everything we know about a target lives in [`KNOWN_TARGETS`][], so supporting a new one is
+//! a matter of adding a row. Users can add their own (or relabel ours) with
+//! `components.artifacts.target_labels`.
+
+use crate::config::ArtifactsConfig;
+
+use super::{Targ, TargetTriple};
+
+/// A target triple and how we show it to people
+#[derive(Debug, Copy, Clone)]
+pub struct KnownTarget {
+    pub triple: &'static str,
+    pub label: &'static str,
+}
+
+const fn target(triple: &'static str, label: &'static str) -> KnownTarget {
+    KnownTarget { triple, label }
+}
+
+/// Every target we look for in file names, along with its label
+///
+/// No triple here may contain another one, since we find them in file names with `contains`.
+pub const KNOWN_TARGETS: &[KnownTarget] = &[
+    // Windows
+    target("i686-pc-windows-msvc", "Windows x86"),
+    target("x86_64-pc-windows-msvc", "Windows x64"),
+    target("aarch64-pc-windows-msvc", "Windows arm64"),
+    target("x86_64-pc-windows-gnu", "Windows x64 (MinGW)"),
+    // macOS
+    target("i686-apple-darwin", "macOS x86"),
+    target("x86_64-apple-darwin", "macOS Intel"),
+    target("aarch64-apple-darwin", "macOS Apple Silicon"),
+    target("universal2-apple-darwin", "macOS Universal"),
+    // Linux (glibc)
+    target("i686-unknown-linux-gnu", "Linux x86"),
+    target("x86_64-unknown-linux-gnu", "Linux x64"),
+    target("aarch64-unknown-linux-gnu", "Linux arm64"),
+    target("armv7-unknown-linux-gnueabihf", "Linux ARMv7"),
+    // Linux (musl)
+    target("i686-unknown-linux-musl", "Linux x86 (musl)"),
+    target("x86_64-unknown-linux-musl", "Linux x64 (musl)"),
+    target("aarch64-unknown-linux-musl", "Linux arm64 (musl)"),
+    target("armv7-unknown-linux-musleabihf", "Linux ARMv7 (musl)"),
+    // FreeBSD
+    target("x86_64-unknown-freebsd", "FreeBSD x64"),
+    target("aarch64-unknown-freebsd", "FreeBSD arm64"),
+];
+
+/// The label to show for a target, preferring the user's own
+///
+/// Targets we don't know about are shown as-is.
+pub fn target_label(triple: &Targ, config: Option<&ArtifactsConfig>) -> String {
+    let triple = triple.trim();
+    if let Some(label) = config.and_then(|config| config.target_labels.get(triple)) {
+        return label.clone();
+    }
+    KNOWN_TARGETS
+        .iter()
+        .find(|known| known.triple == triple)
+        .map(|known| known.label.to_owned())
+        .unwrap_or_else(|| triple.to_owned())
+}
+
+/// The targets whose triples appear in `name`, including any the user configured labels for
+pub fn targets_in_name(name: &str, config: Option<&ArtifactsConfig>) -> Vec<TargetTriple> {
+    let custom = config
+        .into_iter()
+        .flat_map(|config| config.target_labels.keys().map(String::as_str));
+    let mut targets: Vec<TargetTriple> = vec![];
+    for triple in KNOWN_TARGETS.iter().map(|known| known.triple).chain(custom) {
+        if name.contains(triple) && !targets.iter().any(|t| t == triple) {
+            targets.push(triple.to_owned());
+        }
+    }
+    targets
+}
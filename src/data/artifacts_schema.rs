@@ -0,0 +1,101 @@
+//! The format of the `artifacts.json` file we write next to the install page.
+//!
+//! Installers and third-party tools read this file, so unlike the template contexts it's built
+//! from, its shape is a stability guarantee. The rules are:
+//!
+//! * Adding a new field is not a breaking change, so readers should ignore fields they don't
+//!   know about.
+//! * Removing, renaming, or changing the meaning of a field is, and requires bumping
+//!   [`ARTIFACTS_SCHEMA_VERSION`][].
+//!
+//! All indices (`file`, `installers`, `checksum_file`, ...) point into
+//! `release.artifacts.files` and `release.artifacts.installers` respectively.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// The current version of the `artifacts.json` format. Bump this on breaking changes.
+pub const ARTIFACTS_SCHEMA_VERSION: u32 = 1;
+
+/// The contents of `artifacts.json`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactsJson {
+    /// The version of this format, see [`ARTIFACTS_SCHEMA_VERSION`][]
+    pub schema_version: u32,
+    /// The version of oranda that wrote this file. Predates `schema_version`, and is kept
+    /// around for older readers.
+    pub format_version: String,
+    /// The tag of the release the artifacts belong to
+    pub tag: String,
+    /// A human-readable release date
+    pub formatted_date: Option<String>,
+    /// Platforms we have a downloadable file or installer for
+    pub platforms_with_downloads: Vec<PlatformJson>,
+    /// Files that can be downloaded directly, as `(file index, file, platform display names)`
+    pub downloadable_files: Vec<(usize, FileJson, Vec<String>)>,
+    /// Everything we know about the release's artifacts
+    pub release: ReleaseJson,
+    /// Path to the script that detects the visitor's platform
+    pub os_script: String,
+    /// Whether any of the `downloadable_files` has a checksum file
+    pub has_checksum_files: bool,
+}
+
+/// A platform, and the installers that support it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PlatformJson {
+    /// Target triples this platform covers
+    pub target: Vec<String>,
+    /// A human-readable name for the platform, e.g. "Linux x64"
+    pub display_name: String,
+    /// Installer indices, most preferred first
+    pub installers: Vec<usize>,
+}
+
+/// A file attached to the release
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FileJson {
+    pub name: String,
+    pub download_url: String,
+    /// Path on this site where the source of the file (e.g. an install script) can be viewed
+    pub view_path: Option<String>,
+    /// Index of the file containing a checksum for this one
+    pub checksum_file: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseJson {
+    pub artifacts: ReleaseArtifactsJson,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseArtifactsJson {
+    pub files: Vec<FileJson>,
+    pub installers: Vec<InstallerJson>,
+    /// A map from target triples to installer indices, most preferred first
+    pub targets: BTreeMap<String, Vec<usize>>,
+}
+
+/// A way to install the release
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct InstallerJson {
+    pub label: String,
+    pub description: String,
+    /// The app this installer is for, if the release contains several
+    pub app_name: Option<String>,
+    pub method: InstallMethodJson,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type")]
+pub enum InstallMethodJson {
+    /// Download a file
+    Download { file: usize },
+    /// Run a command in a terminal
+    Run {
+        /// Index of the script the command runs, if any
+        file: Option<usize>,
+        run_hint: String,
+    },
+}
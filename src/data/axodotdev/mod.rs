@@ -1,3 +1,4 @@
+use crate::diagnostics;
 use crate::errors::*;
 
 use axoproject::GithubRepo;
@@ -70,7 +71,7 @@ impl AxoRelease {
             let warning = OrandaError::ReleasesCheckFailed {
                 repo: repo.to_string(),
             };
-            eprintln!("{:?}", miette::Report::new(warning));
+            diagnostics::report(warning);
             Ok(false)
         }
     }
@@ -84,6 +85,10 @@ impl ReleaseArtifacts {
                 download_url: asset.browser_download_url.clone(),
                 view_path: None,
                 checksum_file: None,
+                unavailable: false,
+                // axo Releases doesn't count downloads
+                download_count: None,
+                attestations: Default::default(),
                 infer: true,
             };
             self.add_file(file);
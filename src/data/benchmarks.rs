@@ -0,0 +1,195 @@
+//! Loading benchmark results written by criterion and hyperfine.
+//!
+//! Both tools have their own JSON formats with different units, so everything is normalized
+//! into [`BenchmarkResult`][]s measured in seconds.
+
+use std::fs;
+
+use axoasset::Asset;
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::Deserialize;
+
+use crate::errors::*;
+
+/// The file criterion writes its statistics for the latest run of a benchmark to
+const CRITERION_ESTIMATES: &str = "new/estimates.json";
+
+/// Timings for a single benchmark, in seconds
+#[derive(Debug, Clone)]
+pub struct BenchmarkResult {
+    pub name: String,
+    pub mean: f64,
+    pub stddev: Option<f64>,
+    pub median: Option<f64>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+/// All the results loaded from one entry in `components.benchmarks.files`
+#[derive(Debug, Clone)]
+pub struct BenchmarkGroup {
+    /// The file or directory name the results came from
+    pub name: String,
+    pub results: Vec<BenchmarkResult>,
+}
+
+/// The output of `hyperfine --export-json`
+#[derive(Deserialize)]
+struct HyperfineExport {
+    results: Vec<HyperfineResult>,
+}
+
+#[derive(Deserialize)]
+struct HyperfineResult {
+    command: String,
+    mean: f64,
+    stddev: Option<f64>,
+    median: Option<f64>,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+/// criterion's `estimates.json`, all in nanoseconds
+#[derive(Deserialize)]
+struct CriterionEstimates {
+    mean: CriterionEstimate,
+    median: Option<CriterionEstimate>,
+    std_dev: Option<CriterionEstimate>,
+}
+
+#[derive(Deserialize)]
+struct CriterionEstimate {
+    point_estimate: f64,
+}
+
+impl BenchmarkGroup {
+    /// Loads benchmark results from a path relative to `root`, or a URL. Directories are
+    /// searched for criterion results, and files can be either hyperfine or criterion JSON.
+    pub fn load(root: &Utf8Path, path: &str) -> Result<Self> {
+        let name = path
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or(path)
+            .to_owned();
+        let local = root.join(path);
+        let results = if local.is_dir() {
+            load_criterion_dir(&local)?
+        } else {
+            let load_failed = |details: String| OrandaError::BenchmarksLoadFailed {
+                path: path.to_owned(),
+                details,
+            };
+            let contents = tokio::runtime::Handle::current()
+                .block_on(Asset::load_string(if path.contains("://") {
+                    path
+                } else {
+                    local.as_str()
+                }))
+                .map_err(|e| load_failed(e.to_string()))?;
+            parse_results(&contents, &name).map_err(load_failed)?
+        };
+        Ok(Self { name, results })
+    }
+}
+
+/// Parses a single JSON file, figuring out which tool wrote it from its shape. `name` is
+/// used to name criterion results, which don't include the benchmark's name.
+pub fn parse_results(
+    contents: &str,
+    name: &str,
+) -> std::result::Result<Vec<BenchmarkResult>, String> {
+    if let Ok(export) = serde_json::from_str::<HyperfineExport>(contents) {
+        return Ok(export
+            .results
+            .into_iter()
+            .map(|r| BenchmarkResult {
+                name: r.command,
+                mean: r.mean,
+                stddev: r.stddev,
+                median: r.median,
+                min: r.min,
+                max: r.max,
+            })
+            .collect());
+    }
+    match serde_json::from_str::<CriterionEstimates>(contents) {
+        Ok(estimates) => Ok(vec![criterion_result(name.to_owned(), estimates)]),
+        Err(e) => Err(format!(
+            "this doesn't look like hyperfine or criterion output ({e})"
+        )),
+    }
+}
+
+fn criterion_result(name: String, estimates: CriterionEstimates) -> BenchmarkResult {
+    const NANOS: f64 = 1_000_000_000.0;
+    BenchmarkResult {
+        name,
+        mean: estimates.mean.point_estimate / NANOS,
+        stddev: estimates.std_dev.map(|e| e.point_estimate / NANOS),
+        median: estimates.median.map(|e| e.point_estimate / NANOS),
+        min: None,
+        max: None,
+    }
+}
+
+/// Finds every benchmark under a criterion output directory (usually `target/criterion`).
+/// Benchmarks are named after their path relative to `root`, which matches the
+/// `group/function/parameter` ids criterion prints.
+fn load_criterion_dir(root: &Utf8Path) -> Result<Vec<BenchmarkResult>> {
+    let mut estimate_files = Vec::new();
+    find_criterion_estimates(root.to_owned(), &mut estimate_files)?;
+    estimate_files.sort();
+
+    let mut results = Vec::new();
+    for file in estimate_files {
+        let contents = fs::read_to_string(&file)?;
+        let name = file
+            .strip_prefix(root)
+            .ok()
+            .and_then(|p| p.as_str().strip_suffix(CRITERION_ESTIMATES))
+            .unwrap_or(file.as_str())
+            .trim_end_matches('/')
+            .to_owned();
+        let estimates = serde_json::from_str::<CriterionEstimates>(&contents).map_err(|e| {
+            OrandaError::BenchmarksLoadFailed {
+                path: file.to_string(),
+                details: e.to_string(),
+            }
+        })?;
+        results.push(criterion_result(name, estimates));
+    }
+    Ok(results)
+}
+
+fn find_criterion_estimates(dir: Utf8PathBuf, found: &mut Vec<Utf8PathBuf>) -> Result<()> {
+    let estimates = dir.join(CRITERION_ESTIMATES);
+    if estimates.is_file() {
+        found.push(estimates);
+        return Ok(());
+    }
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        // criterion writes its HTML reports next to the benchmarks
+        if path.is_dir() && !path.ends_with("report") {
+            if let Ok(path) = Utf8PathBuf::from_path_buf(path) {
+                find_criterion_estimates(path, found)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Formats a duration in seconds with a unit that keeps the number readable, e.g. `1.23 ms`
+pub fn format_duration(seconds: f64) -> String {
+    let (value, unit) = if seconds >= 1.0 {
+        (seconds, "s")
+    } else if seconds >= 1e-3 {
+        (seconds * 1e3, "ms")
+    } else if seconds >= 1e-6 {
+        (seconds * 1e6, "µs")
+    } else {
+        (seconds * 1e9, "ns")
+    };
+    format!("{value:.2} {unit}")
+}
@@ -0,0 +1,204 @@
+//! Loading blog posts from a directory of markdown files.
+//!
+//! Posts can start with a YAML frontmatter block:
+//!
+//! ```text
+//! ---
+//! title: Announcing 1.0
+//! date: 2023-08-01
+//! author: Jane Doe
+//! tags: [release, announcement]
+//! ---
+//!
+//! The rest of the post...
+//! ```
+
+use std::collections::HashMap;
+
+use axoasset::LocalAsset;
+use camino::Utf8Path;
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use crate::errors::*;
+
+/// The metadata at the top of a post
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Frontmatter {
+    pub title: Option<String>,
+    /// `YYYY-MM-DD`, or a full RFC 3339 timestamp
+    pub date: Option<String>,
+    pub author: Option<String>,
+    /// For posts with several authors, used alongside `author`
+    pub authors: Vec<String>,
+    pub tags: Vec<String>,
+    /// A short summary, shown on the blog index and in the RSS feed
+    pub description: Option<String>,
+    /// Overrides the slug we derive from the file name
+    pub slug: Option<String>,
+    /// Drafts are skipped entirely
+    pub draft: bool,
+}
+
+/// A single blog post
+#[derive(Debug, Clone)]
+pub struct BlogPost {
+    /// The URL-safe name of this post, used as `blog/<slug>/`
+    pub slug: String,
+    pub title: String,
+    pub date: NaiveDate,
+    pub author: Option<String>,
+    /// Everyone else who worked on the post
+    pub authors: Vec<String>,
+    pub tags: Vec<String>,
+    pub description: Option<String>,
+    /// The post's markdown, without the frontmatter
+    pub body: String,
+}
+
+impl BlogPost {
+    /// Loads every markdown file in `dir` as a post, newest first. Drafts are skipped, as are
+    /// files we can't figure out a date or slug for (with a warning). If two posts have the same
+    /// slug, the file that sorts first by name wins, and the other is skipped with a warning.
+    pub fn load_all(dir: &Utf8Path) -> Result<Vec<Self>> {
+        let mut paths = Vec::new();
+        for entry in dir.read_dir_utf8()? {
+            let path = entry?.into_path();
+            if path.extension() == Some("md") {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        let mut posts = Vec::new();
+        let mut slugs = HashMap::new();
+        for path in paths {
+            let contents = LocalAsset::load_string(&path)?;
+            let file_stem = path.file_stem().unwrap_or_default();
+            match Self::parse(file_stem, &contents) {
+                Ok(Some(post)) => {
+                    if let Some(first) = slugs.get(&post.slug) {
+                        tracing::warn!(
+                            "Skipping blog post {}: {} already has the slug {}",
+                            path,
+                            first,
+                            post.slug
+                        );
+                        continue;
+                    }
+                    slugs.insert(post.slug.clone(), path.clone());
+                    posts.push(post);
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Skipping blog post {}: {}", path, e),
+            }
+        }
+        posts.sort_by(|a, b| b.date.cmp(&a.date).then_with(|| a.slug.cmp(&b.slug)));
+        Ok(posts)
+    }
+
+    /// Parses a post. `file_stem` is the file's name without `.md`, which we take the slug
+    /// (and if it starts with `YYYY-MM-DD-`, the date) from. Returns `Ok(None)` for drafts.
+    pub fn parse(file_stem: &str, contents: &str) -> std::result::Result<Option<Self>, String> {
+        let (frontmatter, body) = split_frontmatter(contents);
+        let frontmatter: Frontmatter = match frontmatter {
+            Some(yaml) => serde_yaml::from_str(yaml).map_err(|e| e.to_string())?,
+            None => Frontmatter::default(),
+        };
+        if frontmatter.draft {
+            return Ok(None);
+        }
+
+        let (file_date, file_slug) = split_date_prefix(file_stem);
+        let date = match frontmatter.date.as_deref() {
+            Some(date) => parse_date(date).ok_or_else(|| format!("couldn't parse date {date}"))?,
+            None => file_date.ok_or("it has no date in its frontmatter or file name")?,
+        };
+        let (title, body) = match frontmatter.title {
+            Some(title) => (title, body.to_owned()),
+            None => take_title(body).unwrap_or_else(|| (file_slug.to_owned(), body.to_owned())),
+        };
+
+        let slug = slugify(frontmatter.slug.as_deref().unwrap_or(file_slug));
+        if slug.is_empty() {
+            return Err("its slug is empty, so it has nowhere to go".to_owned());
+        }
+
+        Ok(Some(Self {
+            slug,
+            title,
+            date,
+            author: frontmatter.author,
+            authors: frontmatter.authors,
+            tags: frontmatter.tags,
+            description: frontmatter.description,
+            body,
+        }))
+    }
+}
+
+/// Splits a leading `---`-delimited YAML block off of a markdown file
+pub fn split_frontmatter(contents: &str) -> (Option<&str>, &str) {
+    let Some(rest) = contents
+        .strip_prefix("---\n")
+        .or_else(|| contents.strip_prefix("---\r\n"))
+    else {
+        return (None, contents);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            let yaml = &rest[..offset];
+            let body = &rest[offset + line.len()..];
+            return (Some(yaml), body.trim_start_matches(['\r', '\n']));
+        }
+        offset += line.len();
+    }
+    (None, contents)
+}
+
+/// If a post has no title in its frontmatter but starts with a `# Heading`, use that
+/// (and don't render it twice).
+fn take_title(body: &str) -> Option<(String, String)> {
+    let trimmed = body.trim_start();
+    let first_line = trimmed.lines().next()?;
+    let title = first_line.strip_prefix("# ")?.trim();
+    let rest = trimmed[first_line.len()..].trim_start();
+    Some((title.to_owned(), rest.to_owned()))
+}
+
+fn split_date_prefix(file_stem: &str) -> (Option<NaiveDate>, &str) {
+    if let (Some(date), Some(rest)) = (file_stem.get(..10), file_stem.get(10..)) {
+        if let Some(date) = parse_date(date) {
+            let rest = rest.trim_start_matches(['-', '_']);
+            if !rest.is_empty() {
+                return (Some(date), rest);
+            }
+        }
+    }
+    (None, file_stem)
+}
+
+pub(crate) fn parse_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .ok()
+        .or_else(|| {
+            chrono::DateTime::parse_from_rfc3339(date.trim())
+                .ok()
+                .map(|d| d.date_naive())
+        })
+}
+
+/// Lowercases and replaces anything that isn't alphanumeric with dashes
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_owned()
+}
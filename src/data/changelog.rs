@@ -0,0 +1,199 @@
+//! A forgiving parser for changelog files.
+//!
+//! axoproject's changelog support expects one fairly specific format. This is used when the user
+//! lists several changelog files (e.g. one per workspace member), and as a fallback when that
+//! parser can't make sense of a file. We accept both Keep-a-Changelog style headings
+//! (`## [1.2.3] - 2023-01-01`) and ad-hoc ones (`## v1.2.3`, `# Version 1.2.3 (beta)`,
+//! `## my-crate 1.2.3`).
+
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::SystemTime;
+
+use axoasset::LocalAsset;
+use camino::{Utf8Path, Utf8PathBuf};
+
+use crate::errors::*;
+
+/// A single version's section in a changelog file
+#[derive(Debug, Clone)]
+pub struct ChangelogSection {
+    /// The version this section is for, without any leading `v`
+    pub version: String,
+    /// The markdown contents of the section, without its heading
+    pub body: String,
+}
+
+/// A parsed changelog file
+#[derive(Debug, Clone)]
+pub struct ChangelogFile {
+    /// What to attribute this file's sections to when merging several changelogs. This is the
+    /// name of the directory the file lives in.
+    pub label: Option<String>,
+    pub sections: Vec<ChangelogSection>,
+}
+
+struct ChangelogItem {
+    modified: Option<SystemTime>,
+    file: Arc<ChangelogFile>,
+}
+
+/// Parsed changelog files, keyed by the root and path they were loaded with. Every release's
+/// page looks at the same files, so this saves parsing them once per release.
+static CHANGELOG_CACHE: OnceLock<RwLock<HashMap<(Utf8PathBuf, Utf8PathBuf), ChangelogItem>>> =
+    OnceLock::new();
+
+impl ChangelogFile {
+    /// Like [`ChangelogFile::load`][], but only parses the file again if it has been modified
+    /// since the last time, like between rebuilds in `oranda dev`.
+    pub fn load_cached(root: &Utf8Path, path: &Utf8Path) -> Result<Arc<Self>> {
+        let modified = std::fs::metadata(root.join(path))
+            .and_then(|m| m.modified())
+            .ok();
+        let key = (root.to_owned(), path.to_owned());
+        let cache = CHANGELOG_CACHE.get_or_init(Default::default);
+        if let Some(item) = cache.read().unwrap().get(&key) {
+            if modified.is_some() && item.modified == modified {
+                return Ok(item.file.clone());
+            }
+        }
+
+        let file = Arc::new(Self::load(root, path)?);
+        cache.write().unwrap().insert(
+            key,
+            ChangelogItem {
+                modified,
+                file: file.clone(),
+            },
+        );
+        Ok(file)
+    }
+
+    /// Loads and parses the changelog file at `path`, which is relative to `root`
+    pub fn load(root: &Utf8Path, path: &Utf8Path) -> Result<Self> {
+        let contents = LocalAsset::load_string(root.join(path))?;
+        let label = path
+            .parent()
+            .and_then(|p| p.file_name())
+            .map(|name| name.to_owned());
+        Ok(Self {
+            label,
+            sections: parse(&contents),
+        })
+    }
+
+    /// Finds the section for a version, ignoring any leading `v` on either side
+    pub fn section_for_version(&self, version: &str) -> Option<&ChangelogSection> {
+        let version = normalize_version(version);
+        self.sections.iter().find(|s| s.version == version)
+    }
+}
+
+/// Splits a changelog into per-version sections.
+///
+/// A section starts at any heading that contains something that looks like a version number,
+/// and runs until the next version heading, or the next heading of the same or a higher
+/// level. Headings without a version (like `## Unreleased` or `# Changelog`) are never treated
+/// as sections, but lower-level ones are kept as part of the section body.
+pub fn parse(contents: &str) -> Vec<ChangelogSection> {
+    let mut sections = Vec::new();
+    // (heading level, version, body lines)
+    let mut current: Option<(usize, String, Vec<&str>)> = None;
+    let mut in_code_block = false;
+
+    for line in contents.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+        }
+        let heading = if in_code_block {
+            None
+        } else {
+            parse_heading(line)
+        };
+        if let Some((level, text)) = heading {
+            let version = find_version(text);
+            // A version nested under another one is still its own release
+            let ends_current = current
+                .as_ref()
+                .is_some_and(|(current_level, _, _)| level <= *current_level || version.is_some());
+            if ends_current || (current.is_none() && version.is_some()) {
+                if let Some((_, version, body)) = current.take() {
+                    sections.push(finish_section(version, &body));
+                }
+                if let Some(version) = version {
+                    current = Some((level, version, Vec::new()));
+                }
+                continue;
+            }
+        }
+        if let Some((_, _, body)) = current.as_mut() {
+            body.push(line);
+        }
+    }
+    if let Some((_, version, body)) = current {
+        sections.push(finish_section(version, &body));
+    }
+
+    sections
+}
+
+fn finish_section(version: String, body: &[&str]) -> ChangelogSection {
+    // Keep-a-Changelog files end in a list of link reference definitions for the version
+    // headings, which would otherwise end up at the end of the oldest section.
+    let body = body
+        .iter()
+        .filter(|line| !is_link_definition(line))
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n");
+    ChangelogSection {
+        version,
+        body: body.trim().to_owned(),
+    }
+}
+
+/// Returns the level and text of an ATX markdown heading
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let text = &line[level..];
+    if !text.is_empty() && !text.starts_with(' ') {
+        return None;
+    }
+    Some((level, text.trim()))
+}
+
+fn is_link_definition(line: &str) -> bool {
+    let line = line.trim();
+    line.starts_with('[')
+        && line
+            .split_once("]:")
+            .is_some_and(|(_, rest)| rest.trim_start().starts_with("http"))
+}
+
+/// Finds the first version-looking token in a heading, e.g. `1.2.3` in
+/// `[v1.2.3](https://example.com) - 2023-01-01`.
+fn find_version(text: &str) -> Option<String> {
+    text.split(|c: char| c.is_whitespace() || "[]()/:,".contains(c))
+        .map(normalize_version)
+        .find(|token| looks_like_version(token))
+        .map(|token| token.to_owned())
+}
+
+fn looks_like_version(token: &str) -> bool {
+    let mut chars = token.chars();
+    chars.next().is_some_and(|c| c.is_ascii_digit())
+        && token.contains('.')
+        && chars.all(|c| c.is_ascii_alphanumeric() || ".-+".contains(c))
+        // Avoid mistaking dates like `2023.01.01` for versions
+        && !token.split('.').next().is_some_and(|major| major.len() == 4)
+}
+
+fn normalize_version(version: &str) -> &str {
+    version
+        .strip_prefix('v')
+        .or_else(|| version.strip_prefix('V'))
+        .unwrap_or(version)
+}
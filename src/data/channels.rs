@@ -0,0 +1,96 @@
+//! Release channels besides the latest stable release, see `components.artifacts.channels`
+
+use axoproject::GithubRepo;
+use camino::Utf8Path;
+
+use crate::config::{ArtifactsConfig, ChannelConfig};
+use crate::data::github::GithubRelease;
+use crate::data::{Context, Release, ReleaseSource};
+use crate::diagnostics;
+use crate::errors::*;
+
+/// A channel, along with the release we're showing for it
+#[derive(Debug, Clone)]
+pub struct Channel {
+    pub name: String,
+    pub description: Option<String>,
+    pub release: Release,
+}
+
+impl Context {
+    /// Find the release for each of the configured channels
+    ///
+    /// Channels whose release can't be found are skipped with a warning, since a missing
+    /// nightly build shouldn't stop the rest of the site from building. What we fetch for them
+    /// is cached for the project in `root_dir`.
+    pub fn add_channels(
+        &mut self,
+        root_dir: &Utf8Path,
+        artifacts_config: Option<&ArtifactsConfig>,
+    ) {
+        let Some(artifacts_config) = artifacts_config else {
+            return;
+        };
+        for (name, channel) in &artifacts_config.channels {
+            match self.channel_release(root_dir, channel, artifacts_config) {
+                Ok(Some(release)) => self.channels.push(Channel {
+                    name: name.clone(),
+                    description: channel.description.clone(),
+                    release,
+                }),
+                Ok(None) => tracing::warn!(
+                    "Couldn't find a release for the {} channel, so it won't be shown",
+                    name
+                ),
+                Err(e) => {
+                    tracing::warn!("Couldn't fetch the release for the {} channel", name);
+                    diagnostics::report(e);
+                }
+            }
+        }
+    }
+
+    fn channel_release(
+        &self,
+        root_dir: &Utf8Path,
+        channel: &ChannelConfig,
+        artifacts_config: &ArtifactsConfig,
+    ) -> Result<Option<Release>> {
+        // Releases from our own repo have already been fetched
+        let Some(repo_url) = &channel.repository else {
+            return Ok(self
+                .releases
+                .iter()
+                .find(|r| {
+                    matches_channel(channel, r.source.version_tag(), r.source.is_prerelease())
+                })
+                .cloned());
+        };
+
+        let repo = GithubRepo::from_url(repo_url)?;
+        let gh_releases = tokio::runtime::Handle::current()
+            .block_on(GithubRelease::fetch_all(root_dir, &repo))?;
+        let Some(gh_release) = gh_releases
+            .into_iter()
+            .find(|r| matches_channel(channel, &r.tag_name, r.prerelease))
+        else {
+            return Ok(None);
+        };
+        let release = tokio::runtime::Handle::current().block_on(Release::new(
+            root_dir,
+            ReleaseSource::Github(gh_release),
+            Some(&repo),
+            Some(artifacts_config),
+        ))?;
+        Ok(Some(release))
+    }
+}
+
+/// Whether a release is the one to show for `channel`, given that we go through them from
+/// newest to oldest
+pub fn matches_channel(channel: &ChannelConfig, tag: &str, prerelease: bool) -> bool {
+    match &channel.tag {
+        Some(channel_tag) => channel_tag == tag,
+        None => prerelease,
+    }
+}
@@ -0,0 +1,29 @@
+//! The JSON, TOML and YAML files that data pages are rendered from
+
+use axoasset::LocalAsset;
+use camino::Utf8Path;
+use serde_json::Value;
+
+use crate::errors::*;
+
+/// Loads a data file, picking how to parse it from its extension
+pub fn load(path: &str) -> Result<Value> {
+    let load_failed = |details: String| OrandaError::DataFileLoadFailed {
+        path: path.to_owned(),
+        details,
+    };
+    let contents = LocalAsset::load_string(path)?;
+    let extension = Utf8Path::new(path)
+        .extension()
+        .map(|ext| ext.to_ascii_lowercase());
+    match extension.as_deref() {
+        Some("json") => serde_json::from_str(&contents).map_err(|e| load_failed(e.to_string())),
+        Some("toml") => toml::from_str(&contents).map_err(|e| load_failed(e.to_string())),
+        Some("yml" | "yaml") => {
+            serde_yaml::from_str(&contents).map_err(|e| load_failed(e.to_string()))
+        }
+        _ => Err(load_failed(
+            "only .json, .toml, .yml and .yaml files are supported".to_owned(),
+        )),
+    }
+}
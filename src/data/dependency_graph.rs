@@ -0,0 +1,205 @@
+//! Which members of a Cargo workspace depend on which, for `workspace.dependency_graph`.
+//!
+//! We read the members' Cargo.toml files ourselves rather than asking `cargo metadata`, since
+//! all we need is their names, versions and dependency tables, and this way it works without
+//! a Rust toolchain (or a lockfile, or network access to resolve everything else).
+
+use axoasset::LocalAsset;
+use camino::Utf8Path;
+use serde::Serialize;
+use toml::Value;
+
+use crate::data::workspaces::WorkspaceData;
+use crate::errors::*;
+
+/// The tables dependencies can be listed in, besides the ones under `target.'cfg(..)'`
+const DEPENDENCY_TABLES: &[(&str, DependencyKind)] = &[
+    ("dependencies", DependencyKind::Normal),
+    ("build-dependencies", DependencyKind::Build),
+    ("dev-dependencies", DependencyKind::Dev),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyKind {
+    Normal,
+    Build,
+    Dev,
+}
+
+/// A workspace member that's a Cargo package
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GraphNode {
+    pub slug: String,
+    /// The package name, which is what other members' manifests refer to it by
+    pub name: String,
+    pub version: Option<String>,
+}
+
+/// One member depending on another
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GraphEdge {
+    /// The index of the dependent in `nodes`
+    pub from: usize,
+    /// The index of the dependency in `nodes`
+    pub to: usize,
+    /// The version requirement, like `^0.4.0`, if there is one
+    pub requirement: Option<String>,
+    pub kind: DependencyKind,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DependencyGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+impl DependencyGraph {
+    /// Reads the Cargo.toml of every member. Members that aren't Cargo packages are left out.
+    pub fn from_members(members: &[WorkspaceData]) -> Result<Self> {
+        let Some(first) = members.first() else {
+            return Ok(Self::default());
+        };
+        let root = load_manifest(&first.root_path.join("Cargo.toml"))?;
+        let mut manifests = vec![];
+        let mut graph = Self::default();
+        for member in members {
+            let Some(manifest) = load_manifest(&member.path.join("Cargo.toml"))? else {
+                continue;
+            };
+            let Some(package) = manifest.get("package") else {
+                continue;
+            };
+            let Some(name) = package.get("name").and_then(Value::as_str) else {
+                continue;
+            };
+            let version = package.get("version").and_then(|version| match version {
+                Value::String(version) => Some(version.clone()),
+                // `version.workspace = true`
+                _ => root
+                    .as_ref()
+                    .and_then(|root| root.get("workspace")?.get("package")?.get("version"))
+                    .and_then(Value::as_str)
+                    .map(str::to_owned),
+            });
+            graph.nodes.push(GraphNode {
+                slug: member.slug.clone(),
+                name: name.to_owned(),
+                version,
+            });
+            manifests.push(manifest);
+        }
+
+        for (from, manifest) in manifests.iter().enumerate() {
+            for (table, kind) in dependency_tables(manifest) {
+                for (key, spec) in table {
+                    // `foo = { package = "bar" }` depends on bar
+                    let package = spec.get("package").and_then(Value::as_str).unwrap_or(key);
+                    let Some(to) = graph.nodes.iter().position(|node| node.name == package) else {
+                        continue;
+                    };
+                    if to == from || graph.has_edge(from, to, kind) {
+                        continue;
+                    }
+                    let inherited = spec.get("workspace").and_then(Value::as_bool) == Some(true);
+                    let requirement = requirement(spec).or_else(|| {
+                        if !inherited {
+                            return None;
+                        }
+                        let root_spec = root
+                            .as_ref()?
+                            .get("workspace")?
+                            .get("dependencies")?
+                            .get(key)?;
+                        requirement(root_spec)
+                    });
+                    graph.edges.push(GraphEdge {
+                        from,
+                        to,
+                        requirement,
+                        kind,
+                    });
+                }
+            }
+        }
+        Ok(graph)
+    }
+
+    fn has_edge(&self, from: usize, to: usize, kind: DependencyKind) -> bool {
+        self.edges
+            .iter()
+            .any(|edge| edge.from == from && edge.to == to && edge.kind == kind)
+    }
+
+    /// The edges out of `node`
+    pub fn dependencies(&self, node: usize) -> impl Iterator<Item = &GraphEdge> {
+        self.edges.iter().filter(move |edge| edge.from == node)
+    }
+
+    /// Puts every member in a layer below the members that depend on it, numbered from the
+    /// bottom. Dev-dependencies are ignored, since they're allowed to go in circles.
+    pub fn layers(&self) -> Vec<usize> {
+        let mut layers = vec![None; self.nodes.len()];
+        for node in 0..self.nodes.len() {
+            self.layer(node, &mut layers, &mut vec![]);
+        }
+        layers.into_iter().map(Option::unwrap_or_default).collect()
+    }
+
+    fn layer(&self, node: usize, layers: &mut [Option<usize>], visiting: &mut Vec<usize>) -> usize {
+        if let Some(layer) = layers[node] {
+            return layer;
+        }
+        if visiting.contains(&node) {
+            return 0;
+        }
+        visiting.push(node);
+        let layer = self
+            .dependencies(node)
+            .filter(|edge| edge.kind != DependencyKind::Dev)
+            .map(|edge| edge.to)
+            .map(|dependency| self.layer(dependency, layers, visiting) + 1)
+            .max()
+            .unwrap_or(0);
+        visiting.pop();
+        layers[node] = Some(layer);
+        layer
+    }
+}
+
+fn load_manifest(path: &Utf8Path) -> Result<Option<Value>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let contents = LocalAsset::load_string(path)?;
+    Ok(Some(toml::from_str(&contents)?))
+}
+
+/// Every dependency table in a manifest, including platform-specific ones
+fn dependency_tables(manifest: &Value) -> Vec<(&toml::value::Table, DependencyKind)> {
+    let targets = manifest
+        .get("target")
+        .and_then(Value::as_table)
+        .into_iter()
+        .flat_map(|targets| targets.values());
+    std::iter::once(manifest)
+        .chain(targets)
+        .flat_map(|section| {
+            DEPENDENCY_TABLES.iter().filter_map(|(name, kind)| {
+                let table = section.get(name)?.as_table()?;
+                Some((table, *kind))
+            })
+        })
+        .collect()
+}
+
+/// The version requirement in `foo = "1.0"` or `foo = { version = "1.0" }`
+fn requirement(spec: &Value) -> Option<String> {
+    match spec {
+        Value::String(version) => Some(version.clone()),
+        _ => spec
+            .get("version")
+            .and_then(Value::as_str)
+            .map(str::to_owned),
+    }
+}
@@ -0,0 +1,120 @@
+//! Fetching the tags of a Docker image from the registry it's published to.
+
+use serde::Deserialize;
+
+use crate::config::{DockerConfig, DockerRegistry};
+use crate::diagnostics;
+use crate::errors::*;
+
+#[derive(Deserialize)]
+struct DockerHubTags {
+    results: Vec<NamedTag>,
+}
+
+#[derive(Deserialize)]
+struct QuayTags {
+    tags: Vec<NamedTag>,
+}
+
+#[derive(Deserialize)]
+struct NamedTag {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct GhcrToken {
+    token: String,
+}
+
+#[derive(Deserialize)]
+struct GhcrTags {
+    tags: Vec<String>,
+}
+
+/// Fetches up to `max_tags` of the image's tags from its first registry, most recent first
+/// where the registry tells us. Failing to do so is warned about, and gives no tags.
+pub fn fetch_tags(config: &DockerConfig) -> Vec<String> {
+    let Some(registry) = config.registries.first().copied() else {
+        return vec![];
+    };
+    let runtime = tokio::runtime::Handle::current();
+    runtime
+        .block_on(fetch_registry_tags(
+            registry,
+            &config.image,
+            config.max_tags,
+        ))
+        .map_err(diagnostics::report)
+        .unwrap_or_default()
+}
+
+async fn fetch_registry_tags(
+    registry: DockerRegistry,
+    image: &str,
+    max_tags: usize,
+) -> Result<Vec<String>> {
+    let mut tags: Vec<String> = match registry {
+        DockerRegistry::DockerHub => {
+            // Official images live under "library/"
+            let path = if image.contains('/') {
+                image.to_owned()
+            } else {
+                format!("library/{image}")
+            };
+            let url = format!(
+                "https://hub.docker.com/v2/repositories/{path}/tags?page_size={max_tags}&ordering=last_updated"
+            );
+            let response: DockerHubTags = get_json(registry, &url, None).await?;
+            response.results.into_iter().map(|tag| tag.name).collect()
+        }
+        DockerRegistry::Ghcr => {
+            // Public images still need an (anonymous) token
+            let url = format!("https://ghcr.io/token?scope=repository:{image}:pull");
+            let token: GhcrToken = get_json(registry, &url, None).await?;
+            let url = format!("https://ghcr.io/v2/{image}/tags/list");
+            let response: GhcrTags = get_json(registry, &url, Some(token.token)).await?;
+            // These come in alphabetical order, so the newest versions are usually last. Tags
+            // for signatures and attestations aren't something anyone would run.
+            response
+                .tags
+                .into_iter()
+                .rev()
+                .filter(|tag| !tag.starts_with("sha256-"))
+                .collect()
+        }
+        DockerRegistry::Quay => {
+            let url = format!(
+                "https://quay.io/api/v1/repository/{image}/tag/?limit={max_tags}&onlyActiveTags=true"
+            );
+            let response: QuayTags = get_json(registry, &url, None).await?;
+            response.tags.into_iter().map(|tag| tag.name).collect()
+        }
+    };
+    tags.truncate(max_tags);
+    Ok(tags)
+}
+
+async fn get_json<T: for<'de> Deserialize<'de>>(
+    registry: DockerRegistry,
+    url: &str,
+    token: Option<String>,
+) -> Result<T> {
+    let failed = |e: reqwest::Error| OrandaError::DockerTagsFetchFailed {
+        registry: registry.name().to_owned(),
+        details: e.to_string(),
+    };
+    let mut request = reqwest::Client::new()
+        .get(url)
+        .header(reqwest::header::USER_AGENT, "oranda");
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+    request
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(failed)?
+        .json::<T>()
+        .await
+        .map_err(failed)
+}
@@ -0,0 +1,277 @@
+//! A crate's feature flags, for the `components.features` page.
+//!
+//! Everything comes from the crate's own files: the `[features]` table of its Cargo.toml, the
+//! `## ` comments above each feature (the convention `document-features` uses), and failing
+//! that, the list item about the feature in the crate docs at the top of `src/lib.rs`.
+
+use std::collections::BTreeSet;
+use std::sync::OnceLock;
+
+use axoasset::{LocalAsset, SourceFile};
+use camino::Utf8Path;
+use indexmap::IndexMap;
+use regex::Regex;
+use serde::Serialize;
+use toml_edit::{Document, Item, Table};
+
+use crate::config::FeaturesConfig;
+use crate::errors::*;
+
+const DEPENDENCY_TABLES: &[&str] = &["dependencies", "build-dependencies", "dev-dependencies"];
+
+/// Something a feature turns on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FeatureEnables {
+    /// `"other-feature"`
+    Feature { name: String },
+    /// `"dep:serde"`, an optional dependency
+    Dependency { name: String },
+    /// `"serde/derive"`, or `"serde?/derive"` (weak) which only applies if something else
+    /// turns on the dependency
+    DependencyFeature {
+        dependency: String,
+        feature: String,
+        weak: bool,
+    },
+}
+
+impl FeatureEnables {
+    fn parse(value: &str) -> Self {
+        if let Some(name) = value.strip_prefix("dep:") {
+            return FeatureEnables::Dependency {
+                name: name.to_owned(),
+            };
+        }
+        match value.split_once('/') {
+            Some((dependency, feature)) => {
+                let weak = dependency.ends_with('?');
+                FeatureEnables::DependencyFeature {
+                    dependency: dependency.trim_end_matches('?').to_owned(),
+                    feature: feature.to_owned(),
+                    weak,
+                }
+            }
+            None => FeatureEnables::Feature {
+                name: value.to_owned(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Feature {
+    pub name: String,
+    /// Whether the feature is on unless you ask for `default-features = false`, because
+    /// `default` turns it on (or turns on something that does)
+    pub default: bool,
+    /// Markdown describing the feature, if the crate does
+    pub description: Option<String>,
+    pub enables: Vec<FeatureEnables>,
+    /// Whether this is the feature Cargo makes for an optional dependency that no feature
+    /// refers to with `dep:`
+    pub implicit: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CrateFeatures {
+    /// In the order they're listed in Cargo.toml, followed by the implicit ones
+    pub features: Vec<Feature>,
+    /// What the `default` feature turns on
+    pub default: Vec<FeatureEnables>,
+}
+
+/// Reads the features of the crate whose manifest is at `config.manifest_path`, relative to `root`
+pub fn load(config: &FeaturesConfig, root: &Utf8Path) -> Result<CrateFeatures> {
+    let manifest_path = root.join(&config.manifest_path);
+    let manifest = SourceFile::load_local(&manifest_path)?.deserialize_toml_edit()?;
+    let lib_path = manifest
+        .get("lib")
+        .and_then(|lib| lib.get("path"))
+        .and_then(Item::as_str)
+        .unwrap_or("src/lib.rs");
+    let lib_path = manifest_path
+        .parent()
+        .unwrap_or(Utf8Path::new(""))
+        .join(lib_path);
+    let lib_rs = if lib_path.is_file() {
+        Some(LocalAsset::load_string(&lib_path)?)
+    } else {
+        None
+    };
+    Ok(parse(&manifest, lib_rs.as_deref(), &config.hidden))
+}
+
+/// Reads the features from a parsed manifest, and the crate docs in `lib_rs`
+pub fn parse(manifest: &Document, lib_rs: Option<&str>, hidden: &[String]) -> CrateFeatures {
+    let empty = Table::new();
+    let table = manifest
+        .get("features")
+        .and_then(Item::as_table)
+        .unwrap_or(&empty);
+    let lib_docs = lib_rs.map(crate_doc_descriptions).unwrap_or_default();
+
+    let mut features = IndexMap::new();
+    let mut default = vec![];
+    for (name, value) in table.iter() {
+        let enables = value
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|value| value.as_str())
+            .map(FeatureEnables::parse)
+            .collect::<Vec<_>>();
+        if name == "default" {
+            default = enables;
+            continue;
+        }
+        let description = comment_description(table, name).or_else(|| lib_docs.get(name).cloned());
+        features.insert(
+            name.to_owned(),
+            Feature {
+                name: name.to_owned(),
+                default: false,
+                description,
+                enables,
+                implicit: false,
+            },
+        );
+    }
+
+    // Optional dependencies that aren't referred to with `dep:` get a feature of their own
+    let explicit_deps = features
+        .values()
+        .flat_map(|feature| &feature.enables)
+        .filter_map(|enables| match enables {
+            FeatureEnables::Dependency { name } => Some(name.clone()),
+            _ => None,
+        })
+        .collect::<BTreeSet<_>>();
+    for dependency in optional_dependencies(manifest) {
+        if explicit_deps.contains(&dependency) || features.contains_key(&dependency) {
+            continue;
+        }
+        features.insert(
+            dependency.clone(),
+            Feature {
+                name: dependency.clone(),
+                default: false,
+                description: lib_docs.get(&dependency).cloned(),
+                enables: vec![FeatureEnables::Dependency { name: dependency }],
+                implicit: true,
+            },
+        );
+    }
+
+    // Everything `default` turns on, directly or not
+    let mut queue = default.clone();
+    while let Some(enables) = queue.pop() {
+        let name = match enables {
+            FeatureEnables::Feature { name } => name,
+            // `serde/derive` turns on the `serde` dependency, and so its implicit feature
+            FeatureEnables::DependencyFeature {
+                dependency,
+                weak: false,
+                ..
+            } => dependency,
+            _ => continue,
+        };
+        if let Some(feature) = features.get_mut(&name) {
+            if !feature.default {
+                feature.default = true;
+                queue.extend(feature.enables.iter().cloned());
+            }
+        }
+    }
+
+    CrateFeatures {
+        features: features
+            .into_values()
+            .filter(|feature| !feature.name.starts_with('_') && !hidden.contains(&feature.name))
+            .collect(),
+        default,
+    }
+}
+
+/// The `## ` lines right above a feature in Cargo.toml
+fn comment_description(table: &Table, name: &str) -> Option<String> {
+    let prefix = table.key_decor(name)?.prefix()?.as_str()?;
+    let lines = prefix
+        .lines()
+        .map(str::trim)
+        .filter_map(|line| line.strip_prefix("##"))
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect::<Vec<_>>();
+    let description = lines.join("\n").trim().to_owned();
+    (!description.is_empty()).then_some(description)
+}
+
+/// The names of every optional dependency, including platform-specific ones
+fn optional_dependencies(manifest: &Document) -> Vec<String> {
+    let targets = manifest
+        .get("target")
+        .and_then(Item::as_table)
+        .into_iter()
+        .flat_map(|targets| targets.iter().map(|(_, target)| target));
+    std::iter::once(manifest.as_item())
+        .chain(targets)
+        .flat_map(|section| {
+            DEPENDENCY_TABLES
+                .iter()
+                .filter_map(|table| section.get(table)?.as_table_like())
+                .flat_map(|table| {
+                    table
+                        .iter()
+                        .filter(|(_, spec)| {
+                            spec.get("optional").and_then(Item::as_bool) == Some(true)
+                        })
+                        .map(|(name, _)| name.to_owned())
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Finds list items about features in the crate docs (`//!` comments), like
+/// ``* `serde`: Serialize and deserialize everything`` or `- **serde** — ...`, along with any
+/// lines that continue them
+fn crate_doc_descriptions(lib_rs: &str) -> IndexMap<String, String> {
+    static ITEM: OnceLock<Regex> = OnceLock::new();
+    let item = ITEM.get_or_init(|| {
+        Regex::new(
+            r"^[-*+]\s+(?:\*\*)?`([\w-]+)`(?:\*\*)?\s*(?:(?:[:\u{2013}\u{2014}]|-{1,2})\s*)?(.*)$",
+        )
+        .unwrap()
+    });
+    let docs = lib_rs
+        .lines()
+        .map(str::trim_start)
+        .map_while(|line| {
+            if line.is_empty() || line.starts_with("#!") {
+                return Some(None);
+            }
+            let doc = line.strip_prefix("//!")?;
+            Some(Some(doc.strip_prefix(' ').unwrap_or(doc)))
+        })
+        .flatten();
+
+    let mut descriptions = IndexMap::new();
+    let mut current: Option<(String, String)> = None;
+    for line in docs {
+        if let Some(captures) = item.captures(line) {
+            descriptions.extend(current.take());
+            current = Some((captures[1].to_owned(), captures[2].trim().to_owned()));
+        } else if line.starts_with([' ', '\t']) && !line.trim().is_empty() {
+            if let Some((_, description)) = &mut current {
+                description.push(' ');
+                description.push_str(line.trim());
+            }
+        } else {
+            descriptions.extend(current.take());
+        }
+    }
+    descriptions.extend(current);
+    descriptions.retain(|_, description| !description.is_empty());
+    descriptions
+}
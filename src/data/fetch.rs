@@ -0,0 +1,161 @@
+//! Fetching small bits of JSON for templates, see the `fetch_json` template function.
+//!
+//! Templates can only fetch from URLs the user has allowed in `build.fetch_json_allowlist`,
+//! and only follow redirects that stay within it. Responses are kept in memory for a few
+//! minutes, so that `oranda dev` doesn't hit the same endpoint on every rebuild.
+
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use reqwest::header::USER_AGENT;
+use reqwest::redirect;
+use url::Url;
+
+use crate::errors::*;
+
+/// How long we wait for a response before failing the build
+const TIMEOUT: Duration = Duration::from_secs(10);
+/// Templates are meant to fetch small bits of data, not whole datasets
+const MAX_BYTES: usize = 1024 * 1024;
+
+/// How long fetched responses are reused for
+const CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+/// How many responses we keep around at most, dropping the oldest first
+const MAX_CACHED: usize = 64;
+/// How many redirects we follow, as long as each one is allowed too
+const MAX_REDIRECTS: usize = 5;
+
+static FETCH_CACHE: RwLock<Vec<FetchItem>> = RwLock::new(Vec::new());
+
+struct FetchItem {
+    url: String,
+    fetched_at: Instant,
+    value: serde_json::Value,
+}
+
+/// Whether `url` is covered by an entry in `allowlist`. The URLs are parsed and compared
+/// piece by piece: the scheme, host and port have to match exactly, and the entry's path
+/// has to be a prefix of the URL's path in whole segments, after `..` segments are resolved.
+/// So `https://example.com` doesn't allow `https://example.com.evil.tld`, and
+/// `https://example.com/stats` doesn't allow `https://example.com/stats-private` or
+/// `https://example.com/stats/../admin`.
+pub fn is_allowed(url: &str, allowlist: &[String]) -> bool {
+    match Url::parse(url) {
+        Ok(url) => is_allowed_url(&url, allowlist),
+        Err(_) => false,
+    }
+}
+
+fn is_allowed_url(url: &Url, allowlist: &[String]) -> bool {
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+        return false;
+    }
+    allowlist.iter().any(|entry| {
+        let Ok(entry) = Url::parse(entry) else {
+            return false;
+        };
+        entry.scheme() == url.scheme()
+            && entry.host_str() == url.host_str()
+            && entry.port_or_known_default() == url.port_or_known_default()
+            && path_segments(&entry).len() <= path_segments(url).len()
+            && path_segments(&entry)
+                .iter()
+                .zip(path_segments(url))
+                .all(|(allowed, segment)| *allowed == segment)
+    })
+}
+
+/// The non-empty segments of a URL's path, so that a trailing slash doesn't matter
+fn path_segments(url: &Url) -> Vec<&str> {
+    url.path_segments()
+        .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default()
+}
+
+/// Fetches and parses the JSON at `url`, if it's allowed by the `allowlist` (see [`is_allowed`][])
+pub fn fetch_json(url: &str, allowlist: &[String]) -> Result<serde_json::Value> {
+    if !is_allowed(url, allowlist) {
+        return Err(OrandaError::FetchJsonNotAllowed {
+            url: url.to_owned(),
+        });
+    }
+    if let Some(item) = FETCH_CACHE
+        .read()
+        .expect("fetch cache lock poisoned")
+        .iter()
+        .find(|item| item.url == url && item.fetched_at.elapsed() < CACHE_TTL)
+    {
+        return Ok(item.value.clone());
+    }
+
+    let bytes = tokio::runtime::Handle::current()
+        .block_on(download(url, allowlist))
+        .map_err(|details| OrandaError::FetchJsonFailed {
+            url: url.to_owned(),
+            details,
+        })?;
+    let value: serde_json::Value =
+        serde_json::from_slice(&bytes).map_err(|e| OrandaError::FetchJsonFailed {
+            url: url.to_owned(),
+            details: e.to_string(),
+        })?;
+    let mut cache = FETCH_CACHE.write().expect("fetch cache lock poisoned");
+    cache.retain(|item| item.url != url && item.fetched_at.elapsed() < CACHE_TTL);
+    if cache.len() >= MAX_CACHED {
+        cache.remove(0);
+    }
+    cache.push(FetchItem {
+        url: url.to_owned(),
+        fetched_at: Instant::now(),
+        value: value.clone(),
+    });
+    Ok(value)
+}
+
+/// Only follows redirects to URLs that are allowed as well
+fn redirect_policy(allowlist: &[String]) -> redirect::Policy {
+    let allowlist = allowlist.to_vec();
+    redirect::Policy::custom(move |attempt| {
+        if attempt.previous().len() >= MAX_REDIRECTS {
+            attempt.error("too many redirects")
+        } else if is_allowed_url(attempt.url(), &allowlist) {
+            attempt.follow()
+        } else {
+            let error = format!(
+                "it redirected to {}, which isn't in build.fetch_json_allowlist",
+                attempt.url()
+            );
+            attempt.error(error)
+        }
+    })
+}
+
+async fn download(url: &str, allowlist: &[String]) -> std::result::Result<Vec<u8>, String> {
+    let too_large = || format!("the response is larger than {} KB", MAX_BYTES / 1024);
+    let client = reqwest::Client::builder()
+        .redirect(redirect_policy(allowlist))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut response = client
+        .get(url)
+        .header(USER_AGENT, "oranda")
+        .timeout(TIMEOUT)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| e.to_string())?;
+    if response
+        .content_length()
+        .is_some_and(|len| len > MAX_BYTES as u64)
+    {
+        return Err(too_large());
+    }
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+        bytes.extend_from_slice(&chunk);
+        if bytes.len() > MAX_BYTES {
+            return Err(too_large());
+        }
+    }
+    Ok(bytes)
+}
@@ -1,4 +1,6 @@
-use crate::config::{FundingConfig, StyleConfig};
+use crate::config::{Config, FundingConfig};
+use crate::data::sponsors::{self, SponsorsStats};
+use crate::diagnostics;
 use crate::errors::{OrandaError, Result};
 use crate::site::markdown::to_html;
 use axoasset::LocalAsset;
@@ -13,11 +15,17 @@ pub struct Funding {
     pub content: HashMap<FundingType, FundingContent>,
     /// Content read from the optional Markdown file
     pub docs_content: Option<String>,
+    /// Live GitHub Sponsors data, only fetched if a `monthly_goal` is configured
+    pub sponsors: Option<SponsorsStats>,
 }
 
 /// An enumeration of different supported funding providers. Represents the "key" portion of a
 /// funding.yml entry.
-#[derive(Serialize, Deserialize, Clone, Debug, PartialOrd, PartialEq, Eq, Hash, JsonSchema)]
+///
+/// Unless configured otherwise, funding methods are listed in the order of this enum.
+#[derive(
+    Serialize, Deserialize, Clone, Debug, PartialOrd, Ord, PartialEq, Eq, Hash, JsonSchema,
+)]
 #[serde(rename_all = "snake_case")]
 pub enum FundingType {
     Github,
@@ -28,6 +36,9 @@ pub enum FundingType {
     CommunityBridge,
     Issuehunt,
     Liberapay,
+    Polar,
+    BuyMeACoffee,
+    ThanksDev,
     Custom,
 }
 
@@ -42,6 +53,9 @@ impl std::fmt::Display for FundingType {
             FundingType::CommunityBridge => "community_bridge",
             FundingType::Issuehunt => "issue_hunt",
             FundingType::Liberapay => "liberapay",
+            FundingType::Polar => "polar",
+            FundingType::BuyMeACoffee => "buy_me_a_coffee",
+            FundingType::ThanksDev => "thanks_dev",
             FundingType::Custom => "custom",
         };
 
@@ -59,14 +73,14 @@ pub enum FundingContent {
 
 impl Funding {
     /// Creates a new Funding struct by attempting to read from the FUNDING.yml, and the docs file.
-    pub fn new(funding_cfg: &FundingConfig, style_cfg: &StyleConfig) -> Result<Self> {
+    pub fn new(funding_cfg: &FundingConfig, config: &Config) -> Result<Self> {
         let mut funding = if let Some(yml_path) = &funding_cfg.yml_path {
-            match LocalAsset::load_string(yml_path) {
+            match LocalAsset::load_string(config.path(yml_path)) {
                 Ok(res) => {
                     let parsed_response = parse_response(res)?;
                     Self {
                         content: parsed_response,
-                        docs_content: None,
+                        ..Self::default()
                     }
                 }
                 Err(e) => {
@@ -74,7 +88,7 @@ impl Funding {
                         path: yml_path.into(),
                         details: e,
                     };
-                    eprintln!("{:?}", miette::Report::new(warning));
+                    diagnostics::report(warning);
                     Self::default()
                 }
             }
@@ -83,11 +97,15 @@ impl Funding {
         };
 
         if let Some(md_path) = &funding_cfg.md_path {
-            let res = LocalAsset::load_string(md_path)?;
-            let html = to_html(&res, &style_cfg.syntax_theme)?;
+            let res = LocalAsset::load_string(config.path(md_path))?;
+            let html = to_html(&res, config)?;
             funding.docs_content = Some(html);
         }
 
+        if funding_cfg.monthly_goal.is_some() {
+            funding.sponsors = funding.fetch_sponsors();
+        }
+
         // Check that the user's preferred funding resolves properly
         if let Some(preferred) = funding_cfg.preferred_funding.as_ref() {
             if !funding.content.contains_key(preferred) {
@@ -117,7 +135,38 @@ impl Funding {
     }
 }
 
-fn parse_response(contents: String) -> Result<HashMap<FundingType, FundingContent>> {
+impl Funding {
+    /// Fetches stats for the first GitHub Sponsors profile in FUNDING.yml. Failures are
+    /// only warnings, since the rest of the funding page is still useful without them.
+    fn fetch_sponsors(&self) -> Option<SponsorsStats> {
+        let login = match self.content.get(&FundingType::Github) {
+            Some(FundingContent::One(login)) => login,
+            Some(FundingContent::Multiple(logins)) => logins.first()?,
+            None => {
+                tracing::warn!(
+                    "components.funding.monthly_goal is set, but your FUNDING.yml has no GitHub Sponsors entry"
+                );
+                return None;
+            }
+        };
+        match SponsorsStats::fetch(login) {
+            Ok(Some(stats)) => Some(stats),
+            Ok(None) => {
+                tracing::warn!(
+                    "Set {} to show your progress towards your funding goal",
+                    sponsors::TOKEN_VAR
+                );
+                None
+            }
+            Err(e) => {
+                diagnostics::report(e);
+                None
+            }
+        }
+    }
+}
+
+pub(crate) fn parse_response(contents: String) -> Result<HashMap<FundingType, FundingContent>> {
     let deserialized_map = serde_yaml::from_str(&contents);
     match deserialized_map {
         Ok(yaml) => Ok(yaml),
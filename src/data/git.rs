@@ -0,0 +1,260 @@
+//! Helpers for reading history out of the local git repository.
+//!
+//! We shell out to the `git` binary instead of linking a git implementation, since
+//! everyone building a site from a repository will already have it installed.
+
+use std::collections::HashMap;
+use std::process::Command;
+use std::sync::{OnceLock, RwLock};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use chrono::{DateTime, NaiveDate};
+use serde::Serialize;
+
+use crate::errors::*;
+
+/// Separates fields in our custom `git log` format. This is the ASCII "unit separator",
+/// which is vanishingly unlikely to show up in a commit message.
+const FIELD_SEPARATOR: char = '\u{1f}';
+
+/// A single commit, as reported by `git log`
+#[derive(Debug, Clone)]
+pub struct Commit {
+    /// The full commit hash
+    pub hash: String,
+    /// The first line of the commit message
+    pub summary: String,
+}
+
+/// A commit summary parsed according to the Conventional Commits spec
+/// (`type(scope)!: description`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConventionalCommit {
+    /// The commit type, e.g. `feat` or `fix`, lowercased
+    pub kind: String,
+    /// The optional scope in parentheses
+    pub scope: Option<String>,
+    /// Whether this commit was marked as a breaking change with `!`
+    pub breaking: bool,
+    /// The human-readable description following the colon
+    pub description: String,
+}
+
+impl ConventionalCommit {
+    /// Attempts to parse a commit summary. Returns `None` if the summary doesn't follow
+    /// the conventional commit format.
+    pub fn parse(summary: &str) -> Option<Self> {
+        let (prefix, description) = summary.split_once(':')?;
+        let description = description.trim();
+        if description.is_empty() {
+            return None;
+        }
+        let (prefix, breaking) = match prefix.strip_suffix('!') {
+            Some(prefix) => (prefix, true),
+            None => (prefix, false),
+        };
+        let (kind, scope) = match prefix.split_once('(') {
+            Some((kind, scope)) => (kind, Some(scope.strip_suffix(')')?.trim().to_owned())),
+            None => (prefix, None),
+        };
+        if kind.is_empty() || !kind.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return None;
+        }
+
+        Some(Self {
+            kind: kind.to_ascii_lowercase(),
+            scope: scope.filter(|s| !s.is_empty()),
+            breaking,
+            description: description.to_owned(),
+        })
+    }
+}
+
+/// Runs git with the given arguments in `dir`, returning its stdout.
+pub fn run(dir: &Utf8Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| OrandaError::GitCommandFailed {
+            command: args.join(" "),
+            details: e.to_string(),
+        })?;
+    if !output.status.success() {
+        return Err(OrandaError::GitCommandFailed {
+            command: args.join(" "),
+            details: String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Finds the tag that precedes `tag` in history, if there is one.
+pub fn previous_tag(dir: &Utf8Path, tag: &str) -> Result<Option<String>> {
+    // If `tag` is the first tag in the repository, git describe will fail as there's nothing
+    // to describe `tag^` with, which we treat as "no previous tag".
+    match run(
+        dir,
+        &["describe", "--tags", "--abbrev=0", &format!("{tag}^")],
+    ) {
+        Ok(output) => Ok(Some(output.trim().to_owned()).filter(|t| !t.is_empty())),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Lists the commits reachable from `to` but not from `from`, newest first. Merge commits
+/// are skipped, since they rarely say anything interesting about the changes themselves.
+pub fn commits_between(dir: &Utf8Path, from: Option<&str>, to: &str) -> Result<Vec<Commit>> {
+    let range = match from {
+        Some(from) => format!("{from}..{to}"),
+        None => to.to_owned(),
+    };
+    let format = format!("--format=%H{FIELD_SEPARATOR}%s");
+    let output = run(dir, &["log", "--no-merges", &format, &range])?;
+    Ok(output
+        .lines()
+        .filter_map(|line| {
+            let (hash, summary) = line.split_once(FIELD_SEPARATOR)?;
+            Some(Commit {
+                hash: hash.to_owned(),
+                summary: summary.to_owned(),
+            })
+        })
+        .collect())
+}
+
+/// Counts the commits reachable from `to` but not from `from`, including merge commits.
+pub fn count_commits_between(dir: &Utf8Path, from: &str, to: &str) -> Result<usize> {
+    let output = run(dir, &["rev-list", "--count", &format!("{from}..{to}")])?;
+    output
+        .trim()
+        .parse()
+        .map_err(|_| OrandaError::GitCommandFailed {
+            command: format!("rev-list --count {from}..{to}"),
+            details: format!("unexpected output: {}", output.trim()),
+        })
+}
+
+/// Somebody who has authored commits touching a file
+#[derive(Debug, Clone, Serialize)]
+pub struct Contributor {
+    pub name: String,
+    /// Up to two uppercase initials, used when we don't have an avatar
+    pub initials: String,
+    /// A GitHub avatar, if we could derive the username from a noreply email address
+    pub avatar_url: Option<String>,
+}
+
+/// Who has worked on a file, and when it was last changed
+#[derive(Debug, Clone, Serialize)]
+pub struct FileHistory {
+    pub last_author: String,
+    pub last_updated: String,
+    /// `last_updated`, unformatted
+    #[serde(skip)]
+    pub last_updated_date: Option<NaiveDate>,
+    /// Everyone who has committed to this file, most recent first
+    pub contributors: Vec<Contributor>,
+}
+
+#[derive(Default)]
+struct FileHistoryCache {
+    /// The HEAD commit of each directory we've read histories in, resolved once per build
+    heads: HashMap<Utf8PathBuf, String>,
+    /// Histories by the file's full path, along with the HEAD they were read at
+    histories: HashMap<Utf8PathBuf, (String, Option<FileHistory>)>,
+}
+
+/// File histories only change when new commits are made, so we key this on HEAD to avoid
+/// re-running `git log` for every page on every rebuild in `oranda dev`.
+static FILE_HISTORY_CACHE: OnceLock<RwLock<FileHistoryCache>> = OnceLock::new();
+
+/// Forgets the HEAD commit we found in `dir`, so that the next [`file_history`][] resolves it
+/// again. Builds call this once, so new commits are picked up by the next rebuild.
+pub fn start_build(dir: &Utf8Path) {
+    if let Some(cache) = FILE_HISTORY_CACHE.get() {
+        cache.write().unwrap().heads.remove(dir);
+    }
+}
+
+/// Reads the commit history of a single file. Returns `None` if the file has never been
+/// committed.
+pub fn file_history(dir: &Utf8Path, path: &str) -> Result<Option<FileHistory>> {
+    let cache = FILE_HISTORY_CACHE.get_or_init(Default::default);
+    let head = cache.read().unwrap().heads.get(dir).cloned();
+    let head = match head {
+        Some(head) => head,
+        None => {
+            let head = run(dir, &["rev-parse", "HEAD"])?.trim().to_owned();
+            let mut cache = cache.write().unwrap();
+            // Histories read at an older HEAD won't be asked for again
+            cache.histories.retain(|_, (h, _)| *h == head);
+            cache.heads.insert(dir.to_owned(), head.clone());
+            head
+        }
+    };
+    // Workspace members can have files with the same relative path
+    let key = dir.join(path);
+    if let Some((h, history)) = cache.read().unwrap().histories.get(&key) {
+        if *h == head {
+            return Ok(history.clone());
+        }
+    }
+
+    let format = format!("--format=%an{FIELD_SEPARATOR}%ae{FIELD_SEPARATOR}%aI");
+    let output = run(dir, &["log", "--follow", &format, "--", path])?;
+    let mut last = None;
+    let mut contributors: Vec<Contributor> = Vec::new();
+    for line in output.lines() {
+        let mut fields = line.split(FIELD_SEPARATOR);
+        let (Some(name), Some(email), Some(date)) = (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        if last.is_none() {
+            last = Some((name.to_owned(), date.to_owned()));
+        }
+        if !contributors.iter().any(|c| c.name == name) {
+            contributors.push(Contributor {
+                name: name.to_owned(),
+                initials: initials(name),
+                avatar_url: github_avatar(email),
+            });
+        }
+    }
+    let history = last.map(|(last_author, date)| {
+        let parsed = DateTime::parse_from_rfc3339(&date).ok();
+        FileHistory {
+            last_author,
+            last_updated: parsed
+                .map(|d| d.format("%b %e %Y").to_string())
+                .unwrap_or(date),
+            last_updated_date: parsed.map(|d| d.date_naive()),
+            contributors,
+        }
+    });
+
+    cache
+        .write()
+        .unwrap()
+        .histories
+        .insert(key, (head, history.clone()));
+    Ok(history)
+}
+
+fn initials(name: &str) -> String {
+    name.split_whitespace()
+        .filter_map(|word| word.chars().next())
+        .take(2)
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+/// GitHub's noreply addresses look like `1234+username@users.noreply.github.com` (or
+/// `username@users.noreply.github.com` for older accounts), which gives us a username
+/// we can fetch an avatar for without hitting the API.
+fn github_avatar(email: &str) -> Option<String> {
+    let local = email.strip_suffix("@users.noreply.github.com")?;
+    let username = local.split_once('+').map_or(local, |(_, name)| name);
+    Some(format!("https://github.com/{username}.png?size=48"))
+}
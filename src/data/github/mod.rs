@@ -1,7 +1,10 @@
+use crate::data::http_cache;
+use crate::diagnostics;
 use crate::errors::*;
 
 use axoasset::SourceFile;
 use axoproject::GithubRepo;
+use camino::Utf8Path;
 use chrono::DateTime;
 use serde::{Deserialize, Serialize};
 
@@ -26,6 +29,24 @@ pub struct GithubRelease {
     pub tarball_url: String,
     pub zipball_url: String,
     pub body: Option<String>,
+    /// Whoever published the release
+    #[serde(default)]
+    pub author: Option<GithubUser>,
+}
+
+/// The parts of a GitHub user we care about, as embedded in other API responses
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GithubUser {
+    pub login: String,
+    pub avatar_url: String,
+    pub html_url: String,
+}
+
+impl GithubUser {
+    /// Whether this is an app like `github-actions[bot]` rather than a person
+    pub fn is_bot(&self) -> bool {
+        self.login.ends_with("[bot]")
+    }
 }
 
 /// From the GitHub Rest API
@@ -47,14 +68,22 @@ pub struct GithubReleaseAsset {
 }
 
 impl GithubRelease {
-    pub async fn fetch_all(repo: &GithubRepo) -> Result<Vec<GithubRelease>> {
+    /// Fetches the releases of `repo`, caching them for the project in `root_dir`
+    pub async fn fetch_all(root_dir: &Utf8Path, repo: &GithubRepo) -> Result<Vec<GithubRelease>> {
         let request = octolotl::request::Releases::new(&repo.owner, &repo.name);
-        match octolotl::Request::send(&request, true).await {
-            Ok(r) => {
-                let res: serde_json::Value = serde_json::from_str(&r.text().await?)?;
-                let pretty_response = serde_json::to_string_pretty(&res)?;
-                let releases = SourceFile::new("", pretty_response)
-                    .deserialize_json::<Vec<GithubRelease>>()?;
+        match http_cache::get_github(root_dir, &request).await {
+            Ok(body) => {
+                // Repos with a lot of releases make for a big response, so parse it directly,
+                // and only pretty print it to point at what went wrong if that fails
+                let releases = match serde_json::from_slice::<Vec<GithubRelease>>(&body) {
+                    Ok(releases) => releases,
+                    Err(_) => {
+                        let res: serde_json::Value = serde_json::from_slice(&body)?;
+                        let pretty_response = serde_json::to_string_pretty(&res)?;
+                        SourceFile::new("", pretty_response)
+                            .deserialize_json::<Vec<GithubRelease>>()?
+                    }
+                };
 
                 // If DEBUG_DATA_CLAMP_DATE is set, discard entries that claim to be from after that date
                 let clamp = std::env::var("DEBUG_DATA_CLAMP_DATE").ok();
@@ -94,9 +123,9 @@ impl GithubRelease {
         None
     }
 
-    pub fn repo_has_releases(repo: &GithubRepo) -> Result<bool> {
+    pub fn repo_has_releases(root_dir: &Utf8Path, repo: &GithubRepo) -> Result<bool> {
         if let Ok(releases) =
-            tokio::runtime::Handle::current().block_on(GithubRelease::fetch_all(repo))
+            tokio::runtime::Handle::current().block_on(GithubRelease::fetch_all(root_dir, repo))
         {
             if releases.is_empty() {
                 Ok(false)
@@ -107,7 +136,7 @@ impl GithubRelease {
             let warning = OrandaError::ReleasesCheckFailed {
                 repo: repo.to_string(),
             };
-            eprintln!("{:?}", miette::Report::new(warning));
+            diagnostics::report(warning);
             Ok(false)
         }
     }
@@ -122,6 +151,9 @@ impl ReleaseArtifacts {
                 // The rest of these are filled in later
                 view_path: None,
                 checksum_file: None,
+                unavailable: false,
+                download_count: u64::try_from(asset.download_count).ok(),
+                attestations: Default::default(),
                 infer: true,
             };
             self.add_file(file);
@@ -0,0 +1,59 @@
+//! Reading the metadata file (`action.yml`) of a project that's a GitHub Action.
+
+use axoasset::LocalAsset;
+use indexmap::IndexMap;
+use serde::{Deserialize, Deserializer};
+
+use crate::errors::*;
+
+/// The parts of an action's metadata we show
+#[derive(Debug, Clone, Deserialize)]
+pub struct ActionManifest {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub inputs: IndexMap<String, ActionInput>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ActionInput {
+    pub description: Option<String>,
+    #[serde(default, deserialize_with = "scalar_bool")]
+    pub required: bool,
+    /// The default value, which is usually a string but can be written as a number or bool
+    #[serde(default, deserialize_with = "scalar_string")]
+    pub default: Option<String>,
+    #[serde(rename = "deprecationMessage")]
+    pub deprecation_message: Option<String>,
+}
+
+impl ActionManifest {
+    pub fn load(path: &str) -> Result<Self> {
+        let contents = LocalAsset::load_string(path)?;
+        Self::parse(&contents).map_err(|details| OrandaError::GithubActionParseError {
+            path: path.to_owned(),
+            details,
+        })
+    }
+
+    pub fn parse(contents: &str) -> std::result::Result<Self, String> {
+        serde_yaml::from_str(contents).map_err(|e| e.to_string())
+    }
+}
+
+/// YAML scalars like `true`, `"true"` and `3` as strings
+fn scalar_string<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<String>, D::Error> {
+    Ok(match serde_yaml::Value::deserialize(deserializer)? {
+        serde_yaml::Value::String(s) => Some(s),
+        serde_yaml::Value::Bool(b) => Some(b.to_string()),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    })
+}
+
+/// `required` is a bool, but is often written as a string
+fn scalar_bool<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<bool, D::Error> {
+    Ok(scalar_string(deserializer)?.is_some_and(|s| s == "true"))
+}
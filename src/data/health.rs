@@ -0,0 +1,274 @@
+//! Fetching CI status and code coverage for the project health strip.
+
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use axoproject::GithubRepo;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{CoverageService, HealthConfig};
+use crate::data::sponsors::TOKEN_VAR;
+use crate::diagnostics;
+use crate::errors::*;
+
+/// How long fetched results are reused for. This keeps `oranda dev` from hitting these APIs
+/// (and GitHub's rate limits) on every rebuild.
+const CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+static HEALTH_CACHE: RwLock<Vec<HealthItem>> = RwLock::new(Vec::new());
+
+struct HealthItem {
+    key: String,
+    fetched_at: Instant,
+    health: ProjectHealth,
+}
+
+/// Everything we could find out about the project's health. Either half can be missing if
+/// it isn't configured or couldn't be fetched.
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct ProjectHealth {
+    pub ci: Option<CiStatus>,
+    pub coverage: Option<Coverage>,
+}
+
+/// The outcome of the latest GitHub Actions run
+#[derive(Serialize, Clone, Debug)]
+pub struct CiStatus {
+    /// The workflow's name
+    pub name: String,
+    pub state: CiState,
+    pub url: String,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CiState {
+    Passing,
+    Failing,
+    Running,
+    Unknown,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Coverage {
+    pub service: CoverageService,
+    pub percent: f64,
+    pub url: String,
+}
+
+#[derive(Deserialize)]
+struct WorkflowRuns {
+    workflow_runs: Vec<WorkflowRun>,
+}
+
+#[derive(Deserialize)]
+struct WorkflowRun {
+    name: Option<String>,
+    status: Option<String>,
+    conclusion: Option<String>,
+    html_url: String,
+}
+
+/// Codecov's repository endpoint has `totals` at the top level, while its branch endpoint
+/// nests them under `head_commit`
+#[derive(Deserialize)]
+struct CodecovRepo {
+    totals: Option<CodecovTotals>,
+    head_commit: Option<CodecovCommit>,
+}
+
+#[derive(Deserialize)]
+struct CodecovCommit {
+    totals: Option<CodecovTotals>,
+}
+
+#[derive(Deserialize)]
+struct CodecovTotals {
+    coverage: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct CoverallsRepo {
+    covered_percent: Option<f64>,
+}
+
+impl ProjectHealth {
+    /// Fetches CI status and coverage for `repo`. Anything that fails is warned about and
+    /// left out, rather than failing the build.
+    pub fn fetch(repo: &GithubRepo, config: &HealthConfig) -> Self {
+        let key = format!(
+            "{}/{}:{:?}:{:?}:{:?}",
+            repo.owner, repo.name, config.workflow, config.branch, config.coverage
+        );
+        {
+            let cache = HEALTH_CACHE.read().unwrap();
+            if let Some(item) = cache
+                .iter()
+                .find(|i| i.key == key && i.fetched_at.elapsed() < CACHE_TTL)
+            {
+                return item.health.clone();
+            }
+        }
+
+        let runtime = tokio::runtime::Handle::current();
+        let ci = runtime
+            .block_on(fetch_ci_status(repo, config))
+            .map_err(diagnostics::report)
+            .ok()
+            .flatten();
+        let coverage = config.coverage.and_then(|service| {
+            runtime
+                .block_on(fetch_coverage(repo, service, config.branch.as_deref()))
+                .map_err(diagnostics::report)
+                .ok()
+                .flatten()
+        });
+        let health = Self { ci, coverage };
+
+        let mut cache = HEALTH_CACHE.write().unwrap();
+        cache.retain(|i| i.key != key);
+        cache.push(HealthItem {
+            key,
+            fetched_at: Instant::now(),
+            health: health.clone(),
+        });
+        health
+    }
+}
+
+async fn get(service: &str, url: &str, github_token: Option<String>) -> Result<String> {
+    let failed = |e: reqwest::Error| OrandaError::HealthFetchFailed {
+        service: service.to_owned(),
+        details: e.to_string(),
+    };
+    let mut request = reqwest::Client::new()
+        .get(url)
+        .header(reqwest::header::USER_AGENT, "oranda");
+    if let Some(token) = github_token {
+        request = request.bearer_auth(token);
+    }
+    request
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(failed)?
+        .text()
+        .await
+        .map_err(failed)
+}
+
+fn parse_json<T: for<'de> Deserialize<'de>>(service: &str, body: &str) -> Result<T> {
+    serde_json::from_str(body).map_err(|e| OrandaError::HealthFetchFailed {
+        service: service.to_owned(),
+        details: e.to_string(),
+    })
+}
+
+async fn fetch_ci_status(repo: &GithubRepo, config: &HealthConfig) -> Result<Option<CiStatus>> {
+    let runs_path = match &config.workflow {
+        Some(workflow) => format!("actions/workflows/{}/runs", encode(workflow)),
+        None => "actions/runs".to_owned(),
+    };
+    let mut url = format!(
+        "https://api.github.com/repos/{}/{}/{runs_path}?per_page=1&exclude_pull_requests=true",
+        repo.owner, repo.name
+    );
+    if let Some(branch) = &config.branch {
+        url.push_str(&format!("&branch={}", encode(branch)));
+    } else {
+        // Without a branch filter the latest run could be from anywhere, so default to
+        // runs triggered by pushes, which are overwhelmingly on the default branch.
+        url.push_str("&event=push");
+    }
+    // Unauthenticated requests work for public repositories, but a token helps with
+    // rate limits in CI.
+    let body = get("GitHub Actions", &url, std::env::var(TOKEN_VAR).ok()).await?;
+    CiStatus::parse(&body)
+}
+
+impl CiStatus {
+    /// Parses a response from GitHub's workflow runs API, taking the latest run
+    pub fn parse(body: &str) -> Result<Option<Self>> {
+        let runs: WorkflowRuns = parse_json("GitHub Actions", body)?;
+        Ok(runs.workflow_runs.into_iter().next().map(|run| {
+            let state = match (run.status.as_deref(), run.conclusion.as_deref()) {
+                (Some("completed"), Some("success")) => CiState::Passing,
+                (Some("completed"), Some("failure" | "timed_out" | "startup_failure")) => {
+                    CiState::Failing
+                }
+                (Some("queued" | "in_progress" | "waiting" | "pending" | "requested"), _) => {
+                    CiState::Running
+                }
+                _ => CiState::Unknown,
+            };
+            CiStatus {
+                name: run.name.unwrap_or_else(|| "CI".to_owned()),
+                state,
+                url: run.html_url,
+            }
+        }))
+    }
+}
+
+async fn fetch_coverage(
+    repo: &GithubRepo,
+    service: CoverageService,
+    branch: Option<&str>,
+) -> Result<Option<Coverage>> {
+    let (body, url) = match service {
+        CoverageService::Codecov => {
+            let mut api_url = format!(
+                "https://api.codecov.io/api/v2/github/{}/repos/{}/",
+                repo.owner, repo.name
+            );
+            if let Some(branch) = branch {
+                api_url = format!("{api_url}branches/{}/", encode(branch));
+            }
+            (
+                get("Codecov", &api_url, None).await?,
+                format!("https://app.codecov.io/gh/{}/{}", repo.owner, repo.name),
+            )
+        }
+        CoverageService::Coveralls => {
+            let mut page_url = format!("https://coveralls.io/github/{}/{}", repo.owner, repo.name);
+            let mut api_url = format!("{page_url}.json");
+            if let Some(branch) = branch {
+                let query = format!("?branch={}", encode(branch));
+                api_url.push_str(&query);
+                page_url.push_str(&query);
+            }
+            (get("Coveralls", &api_url, None).await?, page_url)
+        }
+    };
+    Coverage::parse(service, &body, url)
+}
+
+impl Coverage {
+    /// Parses a response from `service`'s API. `url` is where the coverage report can be seen.
+    pub fn parse(service: CoverageService, body: &str, url: String) -> Result<Option<Self>> {
+        let percent = match service {
+            CoverageService::Codecov => {
+                let codecov: CodecovRepo = parse_json("Codecov", body)?;
+                codecov
+                    .totals
+                    .or_else(|| codecov.head_commit.and_then(|c| c.totals))
+                    .and_then(|t| t.coverage)
+            }
+            CoverageService::Coveralls => {
+                let coveralls: CoverallsRepo = parse_json("Coveralls", body)?;
+                coveralls.covered_percent
+            }
+        };
+        Ok(percent.map(|percent| Coverage {
+            service,
+            percent,
+            url,
+        }))
+    }
+}
+
+fn encode(component: &str) -> String {
+    let mut encoded = String::new();
+    url_escape::encode_component_to_string(component, &mut encoded);
+    encoded
+}
@@ -0,0 +1,165 @@
+//! An on-disk cache for what we fetch over HTTP, under the project's `.oranda-cache/http/`.
+//!
+//! Every response that comes with an `ETag` or `Last-Modified` header is stored, and the next
+//! time we fetch the same URL we ask the server whether it changed since. If it didn't, we get
+//! an empty 304 back and use what we stored. GitHub doesn't count those against the rate limit,
+//! which matters when `oranda dev` rebuilds the site every time a file changes.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use octolotl::{OctolotlError, Requestable};
+use reqwest::header::{
+    HeaderMap, HeaderName, HeaderValue, ACCEPT, AUTHORIZATION, ETAG, IF_MODIFIED_SINCE,
+    IF_NONE_MATCH, LAST_MODIFIED, USER_AGENT,
+};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::data::sponsors;
+
+/// Where cached responses go, relative to the project's
+/// [`root_dir`][crate::config::Config::root_dir]
+pub const CACHE_DIR: &str = ".oranda-cache/http";
+
+/// Set this to anything to skip the cache, for when you think it's gone stale
+pub const DISABLE_VAR: &str = "ORANDA_NO_HTTP_CACHE";
+
+/// What we know about a cached response, stored on the first line of its file, before the body
+#[derive(Serialize, Deserialize, Debug)]
+struct Entry {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+/// Where the project in `root_dir` caches responses
+pub fn cache_dir(root_dir: &Utf8Path) -> Utf8PathBuf {
+    root_dir.join(CACHE_DIR)
+}
+
+/// Fetches `url`, using the cached response of the project in `root_dir` if the server says it
+/// hasn't changed
+pub async fn get(root_dir: &Utf8Path, url: &str) -> reqwest::Result<Vec<u8>> {
+    get_in(&cache_dir(root_dir), url).await
+}
+
+/// Like [`get`][], but caches in `cache_dir`
+pub async fn get_in(cache_dir: &Utf8Path, url: &str) -> reqwest::Result<Vec<u8>> {
+    fetch(cache_dir, url, HeaderMap::new()).await
+}
+
+/// Fetches from GitHub's REST API, asking for `accept` (like `application/vnd.github.raw+json`
+/// for a file's contents), and with the token in `GITHUB_TOKEN` if there is one, so that GitHub
+/// lets us make more requests
+pub async fn get_api(root_dir: &Utf8Path, url: &str, accept: &str) -> reqwest::Result<Vec<u8>> {
+    let mut headers = HeaderMap::new();
+    if let Ok(accept) = HeaderValue::from_str(accept) {
+        headers.insert(ACCEPT, accept);
+    }
+    if let Ok(token) = std::env::var(sponsors::TOKEN_VAR) {
+        if let Ok(token) = HeaderValue::from_str(&format!("Bearer {token}")) {
+            headers.insert(AUTHORIZATION, token);
+        }
+    }
+    fetch(&cache_dir(root_dir), url, headers).await
+}
+
+async fn fetch(cache_dir: &Utf8Path, url: &str, headers: HeaderMap) -> reqwest::Result<Vec<u8>> {
+    let path = entry_path(cache_dir, url);
+    let cached = if std::env::var(DISABLE_VAR).is_ok() {
+        None
+    } else {
+        read_entry(&path).filter(|(entry, _)| entry.url == url)
+    };
+
+    let mut request = reqwest::Client::new()
+        .get(url)
+        .header(USER_AGENT, "oranda")
+        .headers(headers);
+    if let Some((entry, _)) = &cached {
+        if let Some(etag) = &entry.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &entry.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+    let response = request.send().await?;
+    if response.status() == StatusCode::NOT_MODIFIED {
+        if let Some((_, body)) = cached {
+            tracing::debug!("Using the cached response for {url}");
+            return Ok(body);
+        }
+    }
+    let response = response.error_for_status()?;
+
+    let header = |name: HeaderName| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned)
+    };
+    let entry = Entry {
+        url: url.to_owned(),
+        etag: header(ETAG),
+        last_modified: header(LAST_MODIFIED),
+    };
+    let body = response.bytes().await?.to_vec();
+    if entry.etag.is_some() || entry.last_modified.is_some() {
+        if let Err(e) = write_entry(&path, &entry, &body) {
+            // The cache only saves time, so a build shouldn't fail over it
+            tracing::debug!("Couldn't cache the response for {url}: {e}");
+        }
+    }
+    Ok(body)
+}
+
+/// Fetches something from GitHub like [`octolotl::Request::send`][] does, through the proxy
+/// first and GitHub itself if that fails, but with the cache
+pub async fn get_github(
+    root_dir: &Utf8Path,
+    item: &impl Requestable,
+) -> Result<Vec<u8>, OctolotlError> {
+    match get(root_dir, &item.proxy_url()).await {
+        Ok(body) => Ok(body),
+        Err(e) => {
+            tracing::debug!("Fetching from the proxy failed, falling back to GitHub: {e}");
+            Ok(get(root_dir, &item.github_url()).await?)
+        }
+    }
+}
+
+/// Where the response for `url` is stored in `cache_dir`
+pub fn entry_path(cache_dir: &Utf8Path, url: &str) -> Utf8PathBuf {
+    cache_dir.join(format!("{:x}", Sha256::digest(url.as_bytes())))
+}
+
+fn read_entry(path: &Utf8Path) -> Option<(Entry, Vec<u8>)> {
+    let contents = std::fs::read(path).ok()?;
+    let split = contents.iter().position(|&byte| byte == b'\n')?;
+    let entry = serde_json::from_slice(&contents[..split]).ok()?;
+    Some((entry, contents[split + 1..].to_vec()))
+}
+
+fn write_entry(path: &Utf8Path, entry: &Entry, body: &[u8]) -> std::io::Result<()> {
+    static NEXT_TEMP: AtomicUsize = AtomicUsize::new(0);
+    let dir = path
+        .parent()
+        .expect("cache entries are always in a directory");
+    std::fs::create_dir_all(dir)?;
+    // Several sites of a workspace can fetch the same thing at once, so write the entry next to
+    // where it goes and move it into place, rather than have anyone read half an entry
+    let temp = path.with_extension(format!(
+        "{}-{}.tmp",
+        std::process::id(),
+        NEXT_TEMP.fetch_add(1, Ordering::Relaxed)
+    ));
+    let mut contents = serde_json::to_vec(entry)?;
+    contents.push(b'\n');
+    contents.extend_from_slice(body);
+    std::fs::write(&temp, contents)?;
+    std::fs::rename(&temp, path)
+}
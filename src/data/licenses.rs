@@ -0,0 +1,264 @@
+//! The licenses of a project's dependencies, for the `components.licenses` page.
+//!
+//! By default we ask `cargo metadata`, which knows each crate's SPDX license expression but not
+//! the text of the license. A report from `cargo about generate --format json` has the texts
+//! too, so we use that instead if there is one, or run cargo-about ourselves if we're asked to.
+//!
+//! The project's own license files go at the top of the page.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::process::Command;
+
+use axoasset::LocalAsset;
+use camino::Utf8Path;
+use serde::{Deserialize, Serialize};
+
+use crate::config::LicensesConfig;
+use crate::errors::*;
+
+/// A crate that uses a license
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct LicensedCrate {
+    pub name: String,
+    pub version: String,
+    pub repository: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct License {
+    /// The SPDX identifier, or for `cargo metadata`, the whole expression, like
+    /// "MIT OR Apache-2.0"
+    pub id: String,
+    /// The full name of the license, like "MIT License", if we know it
+    pub name: Option<String>,
+    /// The text of the license, if we know it
+    pub text: Option<String>,
+    pub crates: Vec<LicensedCrate>,
+}
+
+/// One of the project's own license files
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProjectLicense {
+    /// The name of the file, like "LICENSE-MIT"
+    pub file_name: String,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DependencyLicenses {
+    /// The licenses used by the most crates first
+    pub licenses: Vec<License>,
+}
+
+/// Loads the licenses of the dependencies, resolving the paths in `config` against `root`
+pub fn load(config: &LicensesConfig, root: &Utf8Path) -> Result<DependencyLicenses> {
+    if config.generate_report {
+        let output = Command::new("cargo")
+            .args(["about", "generate", "--format", "json", "--manifest-path"])
+            .arg(root.join(&config.manifest_path))
+            .output()
+            .map_err(|e| OrandaError::LicensesReportFailed {
+                details: e.to_string(),
+            })?;
+        if !output.status.success() {
+            return Err(OrandaError::LicensesReportFailed {
+                details: String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+            });
+        }
+        return from_cargo_about(&String::from_utf8_lossy(&output.stdout), &config.exclude);
+    }
+    if let Some(report) = &config.report {
+        let report = LocalAsset::load_string(root.join(report))?;
+        return from_cargo_about(&report, &config.exclude);
+    }
+    let output = Command::new("cargo")
+        .args(["metadata", "--format-version", "1", "--manifest-path"])
+        .arg(root.join(&config.manifest_path))
+        .output()
+        .map_err(|e| OrandaError::LicensesMetadataFailed {
+            details: e.to_string(),
+        })?;
+    if !output.status.success() {
+        return Err(OrandaError::LicensesMetadataFailed {
+            details: String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+        });
+    }
+    from_cargo_metadata(&String::from_utf8_lossy(&output.stdout), &config.exclude)
+}
+
+/// Reads the project's own license files, resolving their paths against `root`
+pub fn load_project_licenses(
+    config: &LicensesConfig,
+    root: &Utf8Path,
+) -> Result<Vec<ProjectLicense>> {
+    config
+        .project_licenses
+        .iter()
+        .flatten()
+        .map(|path| {
+            let path = root.join(path);
+            Ok(ProjectLicense {
+                file_name: path.file_name().unwrap_or(path.as_str()).to_owned(),
+                text: LocalAsset::load_string(&path)?,
+            })
+        })
+        .collect()
+}
+
+#[derive(Deserialize)]
+struct Metadata {
+    packages: Vec<MetadataPackage>,
+    workspace_members: Vec<String>,
+    resolve: Option<MetadataResolve>,
+}
+
+#[derive(Deserialize)]
+struct MetadataPackage {
+    id: String,
+    name: String,
+    version: String,
+    license: Option<String>,
+    license_file: Option<String>,
+    repository: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct MetadataResolve {
+    nodes: Vec<MetadataNode>,
+}
+
+#[derive(Deserialize)]
+struct MetadataNode {
+    id: String,
+    deps: Vec<MetadataDep>,
+}
+
+#[derive(Deserialize)]
+struct MetadataDep {
+    pkg: String,
+    dep_kinds: Vec<MetadataDepKind>,
+}
+
+#[derive(Deserialize)]
+struct MetadataDepKind {
+    kind: Option<String>,
+}
+
+/// Reads the licenses out of the output of `cargo metadata --format-version 1`, for everything
+/// the workspace's crates depend on apart from dev-dependencies
+pub fn from_cargo_metadata(json: &str, exclude: &[String]) -> Result<DependencyLicenses> {
+    let metadata: Metadata = serde_json::from_str(json)?;
+    let nodes = metadata
+        .resolve
+        .map(|resolve| resolve.nodes)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|node| (node.id, node.deps))
+        .collect::<HashMap<_, _>>();
+    let workspace_members = metadata.workspace_members.iter().collect::<HashSet<_>>();
+
+    // Walk the dependency graph from the workspace's crates, so that dependencies only the
+    // tests use don't show up
+    let mut used = HashSet::new();
+    let mut queue = metadata.workspace_members.clone();
+    while let Some(id) = queue.pop() {
+        for dep in nodes.get(&id).into_iter().flatten() {
+            let dev_only = dep
+                .dep_kinds
+                .iter()
+                .all(|kind| kind.kind.as_deref() == Some("dev"));
+            if !dev_only && used.insert(dep.pkg.clone()) {
+                queue.push(dep.pkg.clone());
+            }
+        }
+    }
+
+    let mut licenses = BTreeMap::<String, BTreeSet<LicensedCrate>>::new();
+    for package in metadata.packages {
+        if !used.contains(&package.id)
+            || workspace_members.contains(&package.id)
+            || exclude.contains(&package.name)
+        {
+            continue;
+        }
+        let id = match (package.license, package.license_file) {
+            // Cargo used to allow `MIT/Apache-2.0`
+            (Some(license), _) => license.replace('/', " OR "),
+            (None, Some(_)) => "Custom license".to_owned(),
+            (None, None) => "Unknown license".to_owned(),
+        };
+        licenses.entry(id).or_default().insert(LicensedCrate {
+            name: package.name,
+            version: package.version,
+            repository: package.repository,
+        });
+    }
+    Ok(sorted(licenses.into_iter().map(|(id, crates)| License {
+        id,
+        name: None,
+        text: None,
+        crates: crates.into_iter().collect(),
+    })))
+}
+
+#[derive(Deserialize)]
+struct AboutReport {
+    licenses: Vec<AboutLicense>,
+}
+
+#[derive(Deserialize)]
+struct AboutLicense {
+    id: String,
+    name: String,
+    text: String,
+    used_by: Vec<AboutUsedBy>,
+}
+
+#[derive(Deserialize)]
+struct AboutUsedBy {
+    #[serde(rename = "crate")]
+    krate: AboutCrate,
+}
+
+#[derive(Deserialize)]
+struct AboutCrate {
+    name: String,
+    version: String,
+    repository: Option<String>,
+}
+
+/// Reads the licenses out of a report made with `cargo about generate --format json`. A
+/// license can be in there more than once when crates ship different copies of its text.
+pub fn from_cargo_about(json: &str, exclude: &[String]) -> Result<DependencyLicenses> {
+    let report: AboutReport = serde_json::from_str(json)?;
+    Ok(sorted(report.licenses.into_iter().filter_map(|license| {
+        let crates = license
+            .used_by
+            .into_iter()
+            .map(|used_by| used_by.krate)
+            .filter(|krate| !exclude.contains(&krate.name))
+            .map(|krate| LicensedCrate {
+                name: krate.name,
+                version: krate.version,
+                repository: krate.repository,
+            })
+            .collect::<BTreeSet<_>>();
+        (!crates.is_empty()).then(|| License {
+            id: license.id,
+            name: Some(license.name),
+            text: Some(license.text),
+            crates: crates.into_iter().collect(),
+        })
+    })))
+}
+
+fn sorted(licenses: impl Iterator<Item = License>) -> DependencyLicenses {
+    let mut licenses = licenses.collect::<Vec<_>>();
+    licenses.sort_by(|a, b| {
+        b.crates
+            .len()
+            .cmp(&a.crates.len())
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    DependencyLicenses { licenses }
+}
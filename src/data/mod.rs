@@ -1,23 +1,50 @@
 use crate::config::{ArtifactsConfig, ProjectConfig};
 use crate::data::axodotdev::AxoRelease;
+use crate::data::channels::Channel;
 use crate::data::github::GithubRelease;
 use crate::data::release::CurrentStateRelease;
-use crate::data::release::ReleaseSource;
 use crate::errors::*;
 
+pub mod activity;
 pub mod artifacts;
+pub mod artifacts_schema;
 pub mod axodotdev;
+pub mod benchmarks;
+pub mod blog;
 pub mod cargo_dist;
+pub mod changelog;
+pub mod channels;
+pub mod data_file;
+pub mod dependency_graph;
+pub mod docker;
+pub mod features;
+pub mod fetch;
 pub mod funding;
+pub mod git;
 pub mod github;
+pub mod github_action;
+pub mod health;
+pub mod http_cache;
+pub mod licenses;
+pub mod org;
 mod release;
+pub mod roadmap;
+pub mod security;
+pub mod sponsors;
 pub mod workspaces;
 
-pub use release::Release;
+pub use release::{Release, ReleaseSource};
 
 use axoproject::GithubRepo;
+use camino::Utf8Path;
+use futures_util::{StreamExt, TryStreamExt};
 
-#[derive(Debug)]
+/// How many releases we process at once. Processing one can mean fetching its dist-manifest
+/// and checking its downloads, and repos with hundreds of releases shouldn't open hundreds of
+/// connections to GitHub at the same time.
+pub const MAX_CONCURRENT_FETCHES: usize = 8;
+
+#[derive(Debug, Clone)]
 pub struct Context {
     /// Info from Github
     pub repo: Option<GithubRepo>,
@@ -34,15 +61,20 @@ pub struct Context {
     /// Whether any of the `releases` have anything useful for
     /// the artifacts subsystem.
     pub has_artifacts: bool,
+    /// Releases to show for `components.artifacts.channels`, like nightly builds
+    pub channels: Vec<Channel>,
 }
 
 impl Context {
-    /// Make a Context with a faux-release for the current project state
+    /// Make a Context with a faux-release for the current project state. Everything a `Context`
+    /// fetches is cached for the project in `root_dir`.
     pub fn new_current(
+        root_dir: &Utf8Path,
         project_config: &ProjectConfig,
         artifacts_config: Option<&ArtifactsConfig>,
     ) -> Result<Self> {
         let releases = tokio::runtime::Handle::current().block_on(Self::make_current_release(
+            root_dir,
             None,
             project_config,
             artifacts_config,
@@ -56,14 +88,16 @@ impl Context {
     }
     /// Get releases using github
     pub fn new_github(
+        root_dir: &Utf8Path,
         repo_url: &str,
         project_config: &ProjectConfig,
         artifacts_config: Option<&ArtifactsConfig>,
     ) -> Result<Self> {
         let repo = GithubRepo::from_url(repo_url)?;
-        let mut releases = Self::fetch_all_github_releases(&repo, artifacts_config)?;
+        let mut releases = Self::fetch_all_github_releases(root_dir, &repo, artifacts_config)?;
         if releases.is_empty() {
             releases = tokio::runtime::Handle::current().block_on(Self::make_current_release(
+                root_dir,
                 Some(&repo),
                 project_config,
                 artifacts_config,
@@ -79,6 +113,7 @@ impl Context {
 
     /// Get releases using axo Releases
     pub fn new_axodotdev(
+        root_dir: &Utf8Path,
         package_name: &str,
         repo_url: &str,
         project_config: &ProjectConfig,
@@ -86,9 +121,10 @@ impl Context {
     ) -> Result<Self> {
         let repo = GithubRepo::from_url(repo_url)?;
         let mut releases =
-            Self::fetch_all_axodotdev_releases(package_name, &repo, artifacts_config)?;
+            Self::fetch_all_axodotdev_releases(root_dir, package_name, &repo, artifacts_config)?;
         if releases.is_empty() {
             releases = tokio::runtime::Handle::current().block_on(Self::make_current_release(
+                root_dir,
                 Some(&repo),
                 project_config,
                 artifacts_config,
@@ -115,41 +151,50 @@ impl Context {
 
     /// Fetch and process all the Github Releases to produce a final result
     pub fn fetch_all_github_releases(
+        root_dir: &Utf8Path,
         repo: &GithubRepo,
         artifacts_config: Option<&ArtifactsConfig>,
     ) -> Result<Vec<Release>> {
         let gh_releases =
-            tokio::runtime::Handle::current().block_on(GithubRelease::fetch_all(repo))?;
-        let all = tokio::runtime::Handle::current().block_on(
-            futures_util::future::try_join_all(gh_releases.into_iter().map(|gh_release| {
-                Release::new(
-                    ReleaseSource::Github(gh_release),
-                    Some(repo),
-                    artifacts_config,
-                )
-            })),
-        )?;
-        Ok(all)
+            tokio::runtime::Handle::current().block_on(GithubRelease::fetch_all(root_dir, repo))?;
+        tokio::runtime::Handle::current().block_on(Self::process_releases(
+            root_dir,
+            gh_releases.into_iter().map(ReleaseSource::Github),
+            repo,
+            artifacts_config,
+        ))
     }
 
     /// Fetch and process all the axo Releases to produce a final result
     pub fn fetch_all_axodotdev_releases(
+        root_dir: &Utf8Path,
         package_name: &str,
         repo: &GithubRepo,
         artifacts_config: Option<&ArtifactsConfig>,
     ) -> Result<Vec<Release>> {
         let axo_releases = tokio::runtime::Handle::current()
             .block_on(AxoRelease::fetch_all(package_name, repo))?;
-        let all = tokio::runtime::Handle::current().block_on(
-            futures_util::future::try_join_all(axo_releases.into_iter().map(|axo_release| {
-                Release::new(
-                    ReleaseSource::Axodotdev(axo_release),
-                    Some(repo),
-                    artifacts_config,
-                )
-            })),
-        )?;
-        Ok(all)
+        tokio::runtime::Handle::current().block_on(Self::process_releases(
+            root_dir,
+            axo_releases.into_iter().map(ReleaseSource::Axodotdev),
+            repo,
+            artifacts_config,
+        ))
+    }
+
+    /// Processes releases a few at a time, keeping them in the order they came in
+    pub async fn process_releases(
+        root_dir: &Utf8Path,
+        sources: impl Iterator<Item = ReleaseSource>,
+        repo: &GithubRepo,
+        artifacts_config: Option<&ArtifactsConfig>,
+    ) -> Result<Vec<Release>> {
+        futures_util::stream::iter(
+            sources.map(|source| Release::new(root_dir, source, Some(repo), artifacts_config)),
+        )
+        .buffered(MAX_CONCURRENT_FETCHES)
+        .try_collect()
+        .await
     }
 
     fn with_releases(
@@ -258,15 +303,18 @@ impl Context {
             has_prereleases,
             has_artifacts,
             latest_release,
+            channels: vec![],
         }
     }
 
     async fn make_current_release(
+        root_dir: &Utf8Path,
         repo: Option<&GithubRepo>,
         project_config: &ProjectConfig,
         artifacts_config: Option<&ArtifactsConfig>,
     ) -> Result<Vec<Release>> {
         let release = Release::new(
+            root_dir,
             ReleaseSource::CurrentState(CurrentStateRelease {
                 version: project_config.version.to_owned(),
                 date: None,
@@ -0,0 +1,175 @@
+//! What `oranda org` knows about an organization's projects, all of it from GitHub's API.
+
+use camino::Utf8Path;
+use comrak::nodes::{AstNode, NodeValue};
+use comrak::Arena;
+use futures_util::{StreamExt, TryStreamExt};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use crate::data::{http_cache, MAX_CONCURRENT_FETCHES};
+use crate::errors::*;
+use crate::site::markdown::initialize_comrak_options;
+
+const API: &str = "https://api.github.com";
+/// The most repos GitHub lists on one page
+const PER_PAGE: usize = 100;
+/// How long a README excerpt can get, in characters
+const EXCERPT_LENGTH: usize = 200;
+
+/// A repository, as GitHub lists it. See
+/// <https://docs.github.com/en/rest/repos/repos#list-organization-repositories>
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OrgRepo {
+    pub name: String,
+    pub description: Option<String>,
+    pub html_url: String,
+    pub homepage: Option<String>,
+    pub language: Option<String>,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    #[serde(default)]
+    pub stargazers_count: u64,
+    #[serde(default)]
+    pub fork: bool,
+    #[serde(default)]
+    pub archived: bool,
+}
+
+/// The latest release of a repository
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OrgRelease {
+    pub tag_name: String,
+    pub html_url: String,
+    pub published_at: Option<String>,
+}
+
+/// Everything a project's card on the portal shows
+#[derive(Clone, Debug)]
+pub struct OrgProject {
+    pub repo: OrgRepo,
+    /// The first paragraph of the README, as plain text
+    pub readme_excerpt: Option<String>,
+    pub latest_release: Option<OrgRelease>,
+}
+
+/// Fetches the public repos of `org` with any of `topics` (or all of them, if there are no
+/// topics), leaving out forks and archived repos, along with their READMEs and latest releases.
+/// Responses are cached under `root_dir`.
+pub fn fetch(root_dir: &Utf8Path, org: &str, topics: &[String]) -> Result<Vec<OrgProject>> {
+    let runtime = tokio::runtime::Handle::current();
+    let repos = runtime.block_on(fetch_repos(root_dir, org)).map_err(|e| {
+        OrandaError::OrgReposFetchFailed {
+            org: org.to_owned(),
+            details: e.to_string(),
+        }
+    })?;
+    let repos = repos
+        .into_iter()
+        .filter(|repo| !repo.fork && !repo.archived && has_topic(repo, topics));
+    runtime.block_on(
+        futures_util::stream::iter(repos.map(|repo| fetch_project(root_dir, org, repo)))
+            .buffered(MAX_CONCURRENT_FETCHES)
+            .try_collect(),
+    )
+}
+
+/// Whether `repo` has any of `topics`, which it always does if there are none
+pub fn has_topic(repo: &OrgRepo, topics: &[String]) -> bool {
+    topics.is_empty() || topics.iter().any(|topic| repo.topics.contains(topic))
+}
+
+async fn fetch_repos(root_dir: &Utf8Path, org: &str) -> Result<Vec<OrgRepo>> {
+    let mut repos = vec![];
+    for page in 1.. {
+        let url = format!("{API}/orgs/{org}/repos?type=public&per_page={PER_PAGE}&page={page}");
+        let body = http_cache::get_api(root_dir, &url, "application/vnd.github+json").await?;
+        let page: Vec<OrgRepo> = serde_json::from_slice(&body)?;
+        let done = page.len() < PER_PAGE;
+        repos.extend(page);
+        if done {
+            break;
+        }
+    }
+    Ok(repos)
+}
+
+async fn fetch_project(root_dir: &Utf8Path, org: &str, repo: OrgRepo) -> Result<OrgProject> {
+    let name = &repo.name;
+    // A repo without a README or releases still gets a card
+    let readme = optional(
+        http_cache::get_api(
+            root_dir,
+            &format!("{API}/repos/{org}/{name}/readme"),
+            "application/vnd.github.raw+json",
+        )
+        .await,
+    )?;
+    let release = optional(
+        http_cache::get_api(
+            root_dir,
+            &format!("{API}/repos/{org}/{name}/releases/latest"),
+            "application/vnd.github+json",
+        )
+        .await,
+    )?;
+    Ok(OrgProject {
+        readme_excerpt: readme.and_then(|readme| excerpt(&String::from_utf8_lossy(&readme))),
+        latest_release: release
+            .map(|release| serde_json::from_slice(&release))
+            .transpose()?,
+        repo,
+    })
+}
+
+/// Turns a 404 into nothing
+fn optional(response: reqwest::Result<Vec<u8>>) -> Result<Option<Vec<u8>>> {
+    match response {
+        Ok(body) => Ok(Some(body)),
+        Err(e) if e.status() == Some(StatusCode::NOT_FOUND) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// The first paragraph of `markdown` with any words in it, as plain text, cut off after about
+/// [`EXCERPT_LENGTH`][] characters. Badges and logos at the top of a README don't count.
+pub fn excerpt(markdown: &str) -> Option<String> {
+    let arena = Arena::new();
+    let root = comrak::parse_document(&arena, markdown, &initialize_comrak_options());
+    let text = root
+        .children()
+        .filter(|node| matches!(node.data.borrow().value, NodeValue::Paragraph))
+        .map(plain_text)
+        .map(|text| text.split_whitespace().collect::<Vec<_>>().join(" "))
+        .find(|text| text.chars().any(char::is_alphabetic))?;
+    if text.chars().count() <= EXCERPT_LENGTH {
+        return Some(text);
+    }
+    let mut cut = String::new();
+    for word in text.split(' ') {
+        if cut.chars().count() + word.chars().count() + 1 > EXCERPT_LENGTH {
+            break;
+        }
+        if !cut.is_empty() {
+            cut.push(' ');
+        }
+        cut.push_str(word);
+    }
+    Some(format!(
+        "{}…",
+        cut.trim_end_matches(|c: char| c.is_ascii_punctuation())
+    ))
+}
+
+/// The text of `node`, leaving out images and HTML
+fn plain_text<'a>(node: &'a AstNode<'a>) -> String {
+    node.children()
+        .map(|child| match &child.data.borrow().value {
+            NodeValue::Text(text) => text.clone(),
+            NodeValue::Code(code) => code.literal.clone(),
+            NodeValue::SoftBreak | NodeValue::LineBreak => " ".to_owned(),
+            NodeValue::Image(_) | NodeValue::HtmlInline(_) => String::new(),
+            _ => plain_text(child),
+        })
+        .collect()
+}
@@ -1,10 +1,12 @@
 use axoasset::SourceFile;
+use camino::Utf8Path;
 use cargo_dist_schema::DistManifest;
 use chrono::DateTime;
 use serde::Serialize;
 
 use crate::config::ArtifactsConfig;
-use crate::data::{cargo_dist, github::GithubRelease, GithubRepo};
+use crate::data::github::{GithubRelease, GithubUser};
+use crate::data::{cargo_dist, http_cache, GithubRepo};
 use crate::errors::*;
 
 use super::artifacts::ReleaseArtifacts;
@@ -57,13 +59,16 @@ impl ReleaseSource {
 
     /// Get a pretty formatted version of the date
     pub fn formatted_date(&self) -> Option<String> {
-        self.date().map(|date| {
-            if let Ok(parsed_date) = DateTime::parse_from_rfc3339(date) {
-                parsed_date.format("%b %e %Y at %R UTC").to_string()
-            } else {
-                date.to_owned()
-            }
-        })
+        self.date().map(Self::format_date)
+    }
+
+    /// Formats a release date the way we show them everywhere, if it's an RFC 3339 one
+    pub fn format_date(date: &str) -> String {
+        if let Ok(parsed_date) = DateTime::parse_from_rfc3339(date) {
+            parsed_date.format("%b %e %Y at %R UTC").to_string()
+        } else {
+            date.to_owned()
+        }
     }
 
     /// The display name of the release
@@ -84,6 +89,22 @@ impl ReleaseSource {
         }
     }
 
+    /// The GitHub user that published the release, if we know it
+    pub fn author(&self) -> Option<&GithubUser> {
+        match self {
+            ReleaseSource::Github(src) => src.author.as_ref(),
+            ReleaseSource::Axodotdev(_) | ReleaseSource::CurrentState(_) => None,
+        }
+    }
+
+    /// The release's page on the site it was published to, if there is one
+    pub fn html_url(&self) -> Option<&str> {
+        match self {
+            ReleaseSource::Github(src) => Some(src.html_url.as_str()),
+            ReleaseSource::Axodotdev(_) | ReleaseSource::CurrentState(_) => None,
+        }
+    }
+
     /// Find out if we're working with a current state release
     pub fn is_current_state(&self) -> bool {
         matches!(self, ReleaseSource::CurrentState(_))
@@ -100,7 +121,10 @@ pub struct Release {
 }
 
 impl Release {
+    /// Gathers what we know about a release. What we fetch for it is cached for the project in
+    /// `root_dir`.
     pub async fn new(
+        root_dir: &Utf8Path,
         source: ReleaseSource,
         repo: Option<&GithubRepo>,
         artifacts_config: Option<&ArtifactsConfig>,
@@ -116,13 +140,13 @@ impl Release {
 
         let manifest = if let (ReleaseSource::Github(gh_release), Some(repo)) = (&source, repo) {
             if artifacts_config.cargo_dist {
-                Self::fetch_manifest_github(gh_release, repo).await?
+                Self::fetch_manifest_github(root_dir, gh_release, repo).await?
             } else {
                 None
             }
         } else if let ReleaseSource::Axodotdev(axo_release) = &source {
             if artifacts_config.cargo_dist {
-                Self::fetch_manifest_axodotdev(axo_release).await?
+                Self::fetch_manifest_axodotdev(root_dir, axo_release).await?
             } else {
                 None
             }
@@ -148,7 +172,12 @@ impl Release {
             artifacts.add_cargo_dist(manifest);
         }
         artifacts.add_package_managers(artifacts_config);
-        artifacts.add_inference();
+        if artifacts_config.check_downloads {
+            artifacts.check_downloads().await;
+        }
+        artifacts.add_attestations(repo);
+        artifacts.add_sboms();
+        artifacts.add_inference(artifacts_config);
 
         // Compute the final result
         artifacts.select_installers(artifacts_config);
@@ -166,6 +195,7 @@ impl Release {
     }
 
     async fn fetch_manifest_github(
+        root_dir: &Utf8Path,
         gh_release: &GithubRelease,
         repo: &GithubRepo,
     ) -> Result<Option<DistManifest>> {
@@ -178,37 +208,40 @@ impl Release {
                 &encoded_tag,
                 cargo_dist::MANIFEST_FILENAME,
             );
-            let response = octolotl::Request::send(&request, true)
-                .await?
-                .error_for_status()?;
-
-            Ok(Self::parse_response(response, &gh_release.tag_name).await?)
+            let body = http_cache::get_github(root_dir, &request).await?;
+            Ok(Self::parse_manifest(&body, &gh_release.tag_name))
         } else {
             Ok(None)
         }
     }
 
-    async fn fetch_manifest_axodotdev(axo_release: &AxoRelease) -> Result<Option<DistManifest>> {
+    async fn fetch_manifest_axodotdev(
+        root_dir: &Utf8Path,
+        axo_release: &AxoRelease,
+    ) -> Result<Option<DistManifest>> {
         let mut encoded_tag = String::new();
         url_escape::encode_component_to_string(&axo_release.tag_name, &mut encoded_tag);
         if axo_release.has_dist_manifest() {
-            let response = reqwest::get(axo_release.asset_url("dist-manifest.json").unwrap())
-                .await?
-                .error_for_status()?;
-
-            Ok(Self::parse_response(response, &axo_release.tag_name).await?)
+            let url = axo_release.asset_url("dist-manifest.json").unwrap();
+            let body = http_cache::get(root_dir, url).await?;
+            Ok(Self::parse_manifest(&body, &axo_release.tag_name))
         } else {
             Ok(None)
         }
     }
 
-    async fn parse_response(
-        response: reqwest::Response,
-        tag: &str,
-    ) -> Result<Option<DistManifest>> {
-        let res = response.text().await?;
-        let src = SourceFile::new("dist-manifest.json", res);
-        Ok(match src.deserialize_json::<DistManifest>() {
+    /// Parses the dist-manifest.json of the release tagged `tag`, explaining why if we can't
+    pub fn parse_manifest(body: &[u8], tag: &str) -> Option<DistManifest> {
+        // Manifests of projects with a lot of targets get big, so parse them straight from the
+        // response, and only hold on to them as text to explain what went wrong
+        if let Ok(manifest) = serde_json::from_slice::<DistManifest>(body) {
+            return Some(manifest);
+        }
+        let src = SourceFile::new(
+            "dist-manifest.json",
+            String::from_utf8_lossy(body).into_owned(),
+        );
+        match src.deserialize_json::<DistManifest>() {
             Ok(manifest) => Some(manifest),
             Err(e) => {
                 // Try partially parsing the manifest to get schema version info
@@ -237,6 +270,6 @@ impl Release {
                 }
                 None
             }
-        })
+        }
     }
 }
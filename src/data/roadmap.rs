@@ -0,0 +1,169 @@
+//! Fetching a repository's open milestones from GitHub, for the `components.roadmap` page.
+
+use axoproject::GithubRepo;
+use camino::Utf8Path;
+use serde::{Deserialize, Serialize};
+
+use crate::config::RoadmapConfig;
+use crate::data::http_cache;
+use crate::diagnostics;
+use crate::errors::*;
+
+/// GitHub won't give us more than this many issues in one response
+const MAX_PER_PAGE: usize = 100;
+
+/// An open milestone and its issues
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct Milestone {
+    pub number: u64,
+    pub title: String,
+    pub description: Option<String>,
+    /// When the milestone is due, as YYYY-MM-DD
+    pub due_on: Option<String>,
+    pub open_issues: u64,
+    pub closed_issues: u64,
+    /// How many of the milestone's issues are closed, out of 100
+    pub percent_complete: u64,
+    pub url: String,
+    pub issues: Vec<MilestoneIssue>,
+}
+
+/// An issue or pull request in a milestone
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct MilestoneIssue {
+    pub number: u64,
+    pub title: String,
+    pub url: String,
+    pub closed: bool,
+    pub is_pull_request: bool,
+}
+
+#[derive(Deserialize)]
+struct GithubMilestone {
+    number: u64,
+    title: String,
+    description: Option<String>,
+    due_on: Option<String>,
+    open_issues: u64,
+    closed_issues: u64,
+    html_url: String,
+}
+
+#[derive(Deserialize)]
+struct GithubIssue {
+    number: u64,
+    title: String,
+    html_url: String,
+    state: String,
+    pull_request: Option<serde_json::Value>,
+}
+
+/// Fetches the repository's open milestones, soonest due first, with up to
+/// `config.max_issues` issues each. Failing to do so is warned about, and gives no milestones.
+/// `root_dir` is the project's, for the HTTP cache.
+pub fn fetch(
+    root_dir: &Utf8Path,
+    config: &RoadmapConfig,
+    repository: Option<&str>,
+) -> Vec<Milestone> {
+    let repo = match repository.map(GithubRepo::from_url) {
+        Some(Ok(repo)) => repo,
+        _ => {
+            tracing::warn!("Skipping the roadmap's milestones, your repository isn't on GitHub");
+            return vec![];
+        }
+    };
+    tokio::runtime::Handle::current()
+        .block_on(fetch_milestones(root_dir, &repo, config))
+        .map_err(diagnostics::report)
+        .unwrap_or_default()
+}
+
+async fn fetch_milestones(
+    root_dir: &Utf8Path,
+    repo: &GithubRepo,
+    config: &RoadmapConfig,
+) -> Result<Vec<Milestone>> {
+    let base = format!("https://api.github.com/repos/{}/{}", repo.owner, repo.name);
+    let url = format!("{base}/milestones?state=open&sort=due_on&direction=asc&per_page=100");
+    let mut milestones = parse_milestones(&get(root_dir, &url).await?)?;
+    if config.max_issues > 0 {
+        let state = if config.closed_issues { "all" } else { "open" };
+        for milestone in &mut milestones {
+            let url = format!(
+                "{base}/issues?milestone={}&state={state}&sort=created&direction=asc&per_page={}",
+                milestone.number,
+                config.max_issues.min(MAX_PER_PAGE)
+            );
+            let mut issues = parse_issues(&get(root_dir, &url).await?)?;
+            issues.truncate(config.max_issues);
+            milestone.issues = issues;
+        }
+    }
+    Ok(milestones)
+}
+
+async fn get(root_dir: &Utf8Path, url: &str) -> Result<String> {
+    let body = http_cache::get_api(root_dir, url, "application/vnd.github+json")
+        .await
+        .map_err(|e| OrandaError::RoadmapFetchFailed {
+            details: e.to_string(),
+        })?;
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+/// Reads GitHub's list of milestones, putting the ones without a due date last
+pub fn parse_milestones(json: &str) -> Result<Vec<Milestone>> {
+    let milestones: Vec<GithubMilestone> =
+        serde_json::from_str(json).map_err(|e| OrandaError::RoadmapFetchFailed {
+            details: e.to_string(),
+        })?;
+    let mut milestones = milestones
+        .into_iter()
+        .map(|milestone| {
+            let total = milestone.open_issues + milestone.closed_issues;
+            let percent_complete = (milestone.closed_issues * 100)
+                .checked_div(total)
+                .unwrap_or(0);
+            Milestone {
+                number: milestone.number,
+                title: milestone.title,
+                description: milestone.description.filter(|d| !d.trim().is_empty()),
+                due_on: milestone
+                    .due_on
+                    .as_deref()
+                    .map(|due_on| due_on.split('T').next().unwrap_or(due_on).to_owned()),
+                open_issues: milestone.open_issues,
+                closed_issues: milestone.closed_issues,
+                percent_complete,
+                url: milestone.html_url,
+                issues: vec![],
+            }
+        })
+        .collect::<Vec<_>>();
+    milestones.sort_by(|a, b| match (&a.due_on, &b.due_on) {
+        (Some(a), Some(b)) => a.cmp(b),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+    Ok(milestones)
+}
+
+/// Reads GitHub's list of issues in a milestone, which includes pull requests
+pub fn parse_issues(json: &str) -> Result<Vec<MilestoneIssue>> {
+    let issues: Vec<GithubIssue> =
+        serde_json::from_str(json).map_err(|e| OrandaError::RoadmapFetchFailed {
+            details: e.to_string(),
+        })?;
+    Ok(issues
+        .into_iter()
+        .map(|issue| MilestoneIssue {
+            number: issue.number,
+            title: issue.title,
+            url: issue.html_url,
+            closed: issue.state == "closed",
+            is_pull_request: issue.pull_request.is_some(),
+        })
+        .collect())
+}
@@ -0,0 +1,182 @@
+//! Fetching the security advisories published for a project, for the `components.security`
+//! page.
+
+use axoproject::GithubRepo;
+use camino::Utf8Path;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{AdvisorySource, SecurityConfig};
+use crate::data::http_cache;
+use crate::diagnostics;
+use crate::errors::*;
+
+const OSV_QUERY_URL: &str = "https://api.osv.dev/v1/query";
+
+/// A published security advisory
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Advisory {
+    /// Like "GHSA-xxxx-xxxx-xxxx" or "RUSTSEC-2023-0001"
+    pub id: String,
+    pub summary: String,
+    /// Other ids the advisory is known by, like its CVE
+    pub aliases: Vec<String>,
+    /// "low", "medium", "high" or "critical", if the database says
+    pub severity: Option<String>,
+    /// When the advisory was published, as YYYY-MM-DD
+    pub published: Option<String>,
+    pub url: String,
+}
+
+#[derive(Deserialize)]
+struct GithubAdvisory {
+    ghsa_id: String,
+    cve_id: Option<String>,
+    summary: String,
+    severity: Option<String>,
+    published_at: Option<String>,
+    html_url: String,
+}
+
+#[derive(Deserialize)]
+struct OsvResponse {
+    #[serde(default)]
+    vulns: Vec<OsvVuln>,
+}
+
+#[derive(Deserialize)]
+struct OsvVuln {
+    id: String,
+    summary: Option<String>,
+    details: Option<String>,
+    #[serde(default)]
+    aliases: Vec<String>,
+    published: Option<String>,
+    withdrawn: Option<String>,
+}
+
+/// Fetches the advisories from the database in `config`, newest first. Failing to do so is
+/// warned about, and gives no advisories. `root_dir` is the project's, for the HTTP cache.
+pub fn fetch(
+    root_dir: &Utf8Path,
+    config: &SecurityConfig,
+    repository: Option<&str>,
+    project_name: &str,
+) -> Vec<Advisory> {
+    let Some(source) = config.advisories else {
+        return vec![];
+    };
+    let runtime = tokio::runtime::Handle::current();
+    let advisories = match source {
+        AdvisorySource::GitHub => {
+            let repo = match repository.map(GithubRepo::from_url) {
+                Some(Ok(repo)) => repo,
+                _ => {
+                    tracing::warn!(
+                        "Skipping GitHub Security Advisories, your repository isn't on GitHub"
+                    );
+                    return vec![];
+                }
+            };
+            runtime.block_on(fetch_github(root_dir, &repo))
+        }
+        AdvisorySource::RustSec => {
+            let crate_name = config.crate_name.as_deref().unwrap_or(project_name);
+            runtime.block_on(fetch_rustsec(crate_name))
+        }
+    };
+    advisories.map_err(diagnostics::report).unwrap_or_default()
+}
+
+async fn fetch_github(root_dir: &Utf8Path, repo: &GithubRepo) -> Result<Vec<Advisory>> {
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/security-advisories?state=published&per_page=100",
+        repo.owner, repo.name
+    );
+    let body = http_cache::get_api(root_dir, &url, "application/vnd.github+json")
+        .await
+        .map_err(|e| failed(AdvisorySource::GitHub, e.to_string()))?;
+    parse_github(&String::from_utf8_lossy(&body))
+}
+
+async fn fetch_rustsec(crate_name: &str) -> Result<Vec<Advisory>> {
+    let query = serde_json::json!({
+        "package": { "name": crate_name, "ecosystem": "crates.io" }
+    });
+    let body = reqwest::Client::new()
+        .post(OSV_QUERY_URL)
+        .header(reqwest::header::USER_AGENT, "oranda")
+        .json(&query)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| failed(AdvisorySource::RustSec, e.to_string()))?
+        .text()
+        .await
+        .map_err(|e| failed(AdvisorySource::RustSec, e.to_string()))?;
+    parse_osv(&body)
+}
+
+/// Reads the response of GitHub's repository security advisories endpoint
+pub fn parse_github(json: &str) -> Result<Vec<Advisory>> {
+    let advisories: Vec<GithubAdvisory> =
+        serde_json::from_str(json).map_err(|e| failed(AdvisorySource::GitHub, e.to_string()))?;
+    Ok(newest_first(
+        advisories
+            .into_iter()
+            .map(|advisory| Advisory {
+                id: advisory.ghsa_id,
+                summary: advisory.summary,
+                aliases: advisory.cve_id.into_iter().collect(),
+                severity: advisory.severity,
+                published: advisory.published_at.as_deref().map(date),
+                url: advisory.html_url,
+            })
+            .collect(),
+    ))
+}
+
+/// Reads the response of an OSV query, keeping the RustSec advisories that haven't been
+/// withdrawn. OSV also has GitHub's advisories for crates, which would be listed twice.
+pub fn parse_osv(json: &str) -> Result<Vec<Advisory>> {
+    let response: OsvResponse =
+        serde_json::from_str(json).map_err(|e| failed(AdvisorySource::RustSec, e.to_string()))?;
+    Ok(newest_first(
+        response
+            .vulns
+            .into_iter()
+            .filter(|vuln| vuln.id.starts_with("RUSTSEC-") && vuln.withdrawn.is_none())
+            .map(|vuln| Advisory {
+                url: format!("https://rustsec.org/advisories/{}.html", vuln.id),
+                summary: vuln
+                    .summary
+                    .or(vuln.details)
+                    .unwrap_or_else(|| vuln.id.clone()),
+                id: vuln.id,
+                aliases: vuln.aliases,
+                severity: None,
+                published: vuln.published.as_deref().map(date),
+            })
+            .collect(),
+    ))
+}
+
+fn newest_first(mut advisories: Vec<Advisory>) -> Vec<Advisory> {
+    advisories.sort_by(|a, b| b.published.cmp(&a.published));
+    advisories
+}
+
+/// The date part of an RFC 3339 timestamp
+fn date(timestamp: &str) -> String {
+    timestamp.split('T').next().unwrap_or(timestamp).to_owned()
+}
+
+fn failed(source: AdvisorySource, details: String) -> OrandaError {
+    let source = match source {
+        AdvisorySource::GitHub => "GitHub",
+        AdvisorySource::RustSec => "RustSec",
+    };
+    OrandaError::AdvisoriesFetchFailed {
+        source_name: source.to_owned(),
+        details,
+    }
+}
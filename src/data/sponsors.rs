@@ -0,0 +1,218 @@
+//! Fetching GitHub Sponsors statistics from GitHub's GraphQL API.
+//!
+//! Unlike the REST endpoints we use for releases, the GraphQL API can't be used anonymously,
+//! so this only runs if a token is available in the `GITHUB_TOKEN` environment variable.
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::*;
+
+pub(crate) const GRAPHQL_URL: &str = "https://api.github.com/graphql";
+/// The environment variable we read the API token from. GitHub Actions provides this one.
+pub const TOKEN_VAR: &str = "GITHUB_TOKEN";
+
+const SPONSORS_QUERY: &str = r#"
+query($login: String!) {
+  repositoryOwner(login: $login) {
+    ... on Sponsorable {
+      sponsors { totalCount }
+      sponsorsListing {
+        activeGoal { kind targetValue percentComplete }
+        tiers(first: 20) {
+          nodes { name monthlyPriceInDollars isOneTime }
+        }
+      }
+    }
+  }
+}
+"#;
+
+/// A snapshot of somebody's GitHub Sponsors profile
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SponsorsStats {
+    pub login: String,
+    pub sponsor_count: u64,
+    /// The goal set on the Sponsors profile, if there's one. Unlike the profile's income,
+    /// which GitHub only reveals to the sponsored account, anyone can see how far along it is.
+    pub goal: Option<SponsorsGoal>,
+    /// Monthly (not one-time) sponsorship tiers, cheapest first
+    pub tiers: Vec<SponsorsTier>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SponsorsGoal {
+    pub kind: SponsorsGoalKind,
+    /// A number of sponsors, or US dollars per month
+    pub target: u64,
+    /// How far along the goal is, capped at 100
+    pub percent: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SponsorsGoalKind {
+    SponsorCount,
+    MonthlyAmount,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SponsorsTier {
+    pub name: String,
+    pub monthly_price: u64,
+}
+
+#[derive(Deserialize)]
+struct GraphqlResponse {
+    data: Option<ResponseData>,
+    #[serde(default)]
+    errors: Vec<GraphqlError>,
+}
+
+#[derive(Deserialize)]
+struct GraphqlError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ResponseData {
+    repository_owner: Option<SponsorableData>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SponsorableData {
+    sponsors: Option<TotalCount>,
+    sponsors_listing: Option<SponsorsListing>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TotalCount {
+    total_count: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SponsorsListing {
+    active_goal: Option<GoalNode>,
+    tiers: Option<TierConnection>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GoalNode {
+    kind: String,
+    target_value: u64,
+    percent_complete: u64,
+}
+
+#[derive(Deserialize)]
+struct TierConnection {
+    nodes: Vec<TierNode>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TierNode {
+    name: String,
+    monthly_price_in_dollars: u64,
+    is_one_time: bool,
+}
+
+impl SponsorsStats {
+    /// Fetches the sponsors profile of `login`. Returns `Ok(None)` if no token is available.
+    pub fn fetch(login: &str) -> Result<Option<Self>> {
+        let Ok(token) = std::env::var(TOKEN_VAR) else {
+            return Ok(None);
+        };
+        tokio::runtime::Handle::current()
+            .block_on(Self::fetch_with_token(login, &token))
+            .map(Some)
+    }
+
+    async fn fetch_with_token(login: &str, token: &str) -> Result<Self> {
+        let failed = |details: String| OrandaError::GithubSponsorsFetchFailed {
+            login: login.to_owned(),
+            details,
+        };
+        let body = serde_json::json!({
+            "query": SPONSORS_QUERY,
+            "variables": { "login": login },
+        });
+        let response = reqwest::Client::new()
+            .post(GRAPHQL_URL)
+            .header(reqwest::header::USER_AGENT, "oranda")
+            .bearer_auth(token)
+            .json(&body)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| failed(e.to_string()))?
+            .text()
+            .await
+            .map_err(|e| failed(e.to_string()))?;
+        Self::parse(login, &response)
+    }
+
+    /// Parses the GraphQL API's response to our query for the profile of `login`
+    pub fn parse(login: &str, response: &str) -> Result<Self> {
+        let failed = |details: String| OrandaError::GithubSponsorsFetchFailed {
+            login: login.to_owned(),
+            details,
+        };
+        let response: GraphqlResponse =
+            serde_json::from_str(response).map_err(|e| failed(e.to_string()))?;
+        let owner = response
+            .data
+            .and_then(|d| d.repository_owner)
+            .ok_or_else(|| {
+                let messages = response
+                    .errors
+                    .iter()
+                    .map(|e| e.message.as_str())
+                    .collect::<Vec<_>>();
+                if messages.is_empty() {
+                    failed("no such user or organization".to_owned())
+                } else {
+                    failed(messages.join(", "))
+                }
+            })?;
+
+        let listing = owner.sponsors_listing;
+        let goal = listing
+            .as_ref()
+            .and_then(|l| l.active_goal.as_ref())
+            .and_then(|goal| {
+                let kind = match goal.kind.as_str() {
+                    "TOTAL_SPONSORS_COUNT" => SponsorsGoalKind::SponsorCount,
+                    "MONTHLY_SPONSORSHIP_AMOUNT" => SponsorsGoalKind::MonthlyAmount,
+                    _ => return None,
+                };
+                Some(SponsorsGoal {
+                    kind,
+                    target: goal.target_value,
+                    percent: goal.percent_complete.min(100),
+                })
+            });
+        let mut tiers = listing
+            .and_then(|l| l.tiers)
+            .map(|t| t.nodes)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|t| !t.is_one_time)
+            .map(|t| SponsorsTier {
+                name: t.name,
+                monthly_price: t.monthly_price_in_dollars,
+            })
+            .collect::<Vec<_>>();
+        tiers.sort_by_key(|t| t.monthly_price);
+
+        Ok(Self {
+            login: login.to_owned(),
+            sponsor_count: owner.sponsors.map(|s| s.total_count).unwrap_or(0),
+            goal,
+            tiers,
+        })
+    }
+}
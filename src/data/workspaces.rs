@@ -1,4 +1,4 @@
-use crate::config::Config;
+use crate::config::{Config, ConfigOptions, SitesManifest, WorkspaceMember};
 use crate::errors::{OrandaError, Result};
 use camino::Utf8PathBuf;
 
@@ -14,53 +14,107 @@ pub fn from_config(
     workspace_config: &Config,
     root_path: &Utf8PathBuf,
     workspace_config_path: &Utf8PathBuf,
+    options: &ConfigOptions,
 ) -> Result<Vec<WorkspaceData>> {
     let mut vec = Vec::new();
-    for member in workspace_config.workspace.members.clone() {
-        if !member.path.exists() {
-            return Err(OrandaError::FileNotFound {
-                filedesc: "workspace member".to_string(),
-                path: member.path.display().to_string(),
-            });
-        }
-
-        // FIXME: I expect this to break at some point, because making paths absolute is an absolute
-        // hellhole, and should not be taken for granted.
-        let path = Utf8PathBuf::from(member.path.display().to_string()).canonicalize_utf8()?;
-        let mut config_path = path.clone();
-        config_path.push("oranda.json");
-        let mut config = Config::build_workspace_member(
-            &config_path,
-            workspace_config_path,
-            &path,
+    // A stable sort, so that members with the same weight stay in the order they're listed in
+    let mut members = workspace_config.workspace.members.clone();
+    members.sort_by_key(|member| member.weight());
+    for member in members {
+        let mut data = load_member(
             &member,
-            Some(member.slug.clone()),
+            root_path,
+            Some(workspace_config_path),
+            workspace_config.build.path_prefix.as_ref(),
+            &workspace_config.build.dist_dir,
+            options,
         )?;
 
-        // Set the correct path prefix. This should be:
-        // - If no root path prefix: `slug`
-        // - If root path prefix: `path_prefix/slug`
-        config.build.path_prefix =
-            if let Some(path_prefix) = workspace_config.build.path_prefix.as_ref() {
-                // FIXME: Doesn't account for trailing slashes right now
-                Some(format!("{}/{}", path_prefix, &member.slug))
-            } else {
-                Some(member.slug.to_string())
-            };
+        // Members can include files from anywhere in the workspace, unless told otherwise
+        if data.config.build.include_root.is_none() {
+            data.config.build.include_root = Some(root_path.to_string());
+        }
+
+        vec.push(data);
+    }
+
+    Ok(vec)
+}
 
-        // Set the correct dist_dir. This should be `cwd_from_root/workspace_dist_dir/slug`
-        config.build.dist_dir = format!(
-            "{}/{}/{}",
-            root_path, workspace_config.build.dist_dir, &member.slug
-        );
+/// Loads the sites listed in an `oranda build-many` manifest. Unlike workspace members, they
+/// don't inherit any config from the manifest, and their paths are relative to it.
+pub fn from_manifest(
+    manifest: &SitesManifest,
+    root_path: &Utf8PathBuf,
+    options: &ConfigOptions,
+) -> Result<Vec<WorkspaceData>> {
+    let mut sites = manifest.sites.clone();
+    sites.sort_by_key(|site| site.weight());
+    sites
+        .into_iter()
+        .map(|site| {
+            let site = WorkspaceMember {
+                path: root_path.as_std_path().join(&site.path),
+                ..site
+            };
+            load_member(
+                &site,
+                root_path,
+                None,
+                manifest.path_prefix.as_ref(),
+                &manifest.dist_dir,
+                options,
+            )
+        })
+        .collect()
+}
 
-        vec.push(WorkspaceData {
-            root_path: root_path.clone(),
-            slug: member.slug.clone(),
-            path,
-            config,
+fn load_member(
+    member: &WorkspaceMember,
+    root_path: &Utf8PathBuf,
+    root_config_path: Option<&Utf8PathBuf>,
+    root_path_prefix: Option<&String>,
+    root_dist_dir: &str,
+    options: &ConfigOptions,
+) -> Result<WorkspaceData> {
+    if !member.path.exists() {
+        return Err(OrandaError::FileNotFound {
+            filedesc: "workspace member".to_string(),
+            path: member.path.display().to_string(),
         });
     }
 
-    Ok(vec)
+    // FIXME: I expect this to break at some point, because making paths absolute is an absolute
+    // hellhole, and should not be taken for granted.
+    let path = Utf8PathBuf::from(member.path.display().to_string()).canonicalize_utf8()?;
+    let mut config_path = path.clone();
+    config_path.push("oranda.json");
+    let mut config = Config::build_workspace_member(
+        &config_path,
+        root_config_path,
+        &path,
+        member,
+        Some(member.slug.clone()),
+        options,
+    )?;
+
+    // Set the correct path prefix. This should be:
+    // - If no root path prefix: `slug`
+    // - If root path prefix: `path_prefix/slug`
+    config.build.path_prefix = if let Some(path_prefix) = root_path_prefix {
+        // FIXME: Doesn't account for trailing slashes right now
+        Some(format!("{}/{}", path_prefix, &member.slug))
+    } else {
+        Some(member.slug.to_string())
+    };
+
+    // Set the correct dist_dir. This should be `cwd_from_root/workspace_dist_dir/slug`
+    config.build.dist_dir = format!("{}/{}/{}", root_path, root_dist_dir, &member.slug);
+
+    Ok(WorkspaceData {
+        root_path: root_path.clone(),
+        slug: member.slug.clone(),
+        path,
+        config,
+    })
 }
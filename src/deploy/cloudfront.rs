@@ -0,0 +1,56 @@
+//! Invalidating a CloudFront distribution, so that it serves the files we just uploaded.
+
+use std::collections::BTreeMap;
+
+use chrono::Utc;
+use url::Url;
+
+use crate::deploy::sigv4::{Credentials, Signer};
+use crate::errors::*;
+
+/// Asks CloudFront to drop what distribution `id` has cached under `/{prefix}`
+pub async fn invalidate(credentials: &Credentials, id: &str, prefix: &str) -> Result<()> {
+    let failed = |details: String| OrandaError::CloudFrontInvalidationFailed {
+        distribution: id.to_owned(),
+        details,
+    };
+    let path = if prefix.is_empty() {
+        "/*".to_owned()
+    } else {
+        format!("/{prefix}/*")
+    };
+    let body = invalidation_batch(&path, &Utc::now().timestamp_millis().to_string());
+    let url = Url::parse(&format!(
+        "https://cloudfront.amazonaws.com/2020-05-31/distribution/{id}/invalidation"
+    ))
+    .map_err(|e| failed(e.to_string()))?;
+    // CloudFront is global, but its requests are signed for us-east-1
+    let signer = Signer {
+        credentials: credentials.clone(),
+        region: "us-east-1".to_owned(),
+        service: "cloudfront".to_owned(),
+    };
+    let mut headers = BTreeMap::from([("content-type".to_owned(), "text/xml".to_owned())]);
+    signer.sign("POST", &url, &mut headers, body.as_bytes(), Utc::now());
+
+    let mut request = reqwest::Client::new().post(url).body(body);
+    for (name, value) in headers.iter().filter(|(name, _)| *name != "host") {
+        request = request.header(name, value);
+    }
+    let response = request.send().await.map_err(|e| failed(e.to_string()))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(failed(format!("{status}: {body}")));
+    }
+    Ok(())
+}
+
+/// The request body for invalidating `path`. CloudFront uses `caller_reference` to tell retries
+/// of the same invalidation apart from new ones.
+pub fn invalidation_batch(path: &str, caller_reference: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<InvalidationBatch xmlns="http://cloudfront.amazonaws.com/doc/2020-05-31/"><Paths><Quantity>1</Quantity><Items><Path>{path}</Path></Items></Paths><CallerReference>oranda-{caller_reference}</CallerReference></InvalidationBatch>"#
+    )
+}
@@ -0,0 +1,63 @@
+//! Publishing a built site, see [`deploy`][].
+//!
+//! Uploads go through a [`SiteWriter`][crate::site::writer::SiteWriter], so anything that can
+//! write a site, like [`SiteBuilder::write_to`][crate::site::SiteBuilder::write_to], can write it
+//! straight to a bucket as well.
+
+use camino::Utf8Path;
+use url::Url;
+
+use crate::errors::*;
+use crate::site::writer::SiteWriter;
+
+pub mod cloudfront;
+pub mod s3;
+pub mod sigv4;
+
+use s3::{S3Target, S3Writer};
+use sigv4::{Credentials, Signer};
+
+/// Where and how to publish a site
+#[derive(Debug, Clone)]
+pub struct DeployOptions {
+    pub target: S3Target,
+    /// The bucket's region. Defaults to `AWS_REGION`, then `AWS_DEFAULT_REGION`, then
+    /// `us-east-1`.
+    pub region: Option<String>,
+    /// The S3-compatible storage to upload to instead of AWS
+    pub endpoint: Option<Url>,
+    /// The CloudFront distribution to invalidate once everything is uploaded
+    pub cloudfront_distribution: Option<String>,
+}
+
+impl DeployOptions {
+    pub fn region(&self) -> String {
+        self.region
+            .clone()
+            .or_else(|| std::env::var("AWS_REGION").ok())
+            .or_else(|| std::env::var("AWS_DEFAULT_REGION").ok())
+            .filter(|region| !region.is_empty())
+            .unwrap_or_else(|| "us-east-1".to_owned())
+    }
+}
+
+/// Uploads everything in `dist_dir`, returning how many files were uploaded
+pub fn deploy(dist_dir: &Utf8Path, options: &DeployOptions) -> Result<usize> {
+    let credentials = Credentials::from_env()?;
+    let signer = Signer {
+        credentials: credentials.clone(),
+        region: options.region(),
+        service: "s3".to_owned(),
+    };
+    let mut writer = S3Writer::new(options.target.clone(), signer, options.endpoint.clone());
+    writer.write_dir(Utf8Path::new(""), dist_dir)?;
+    if let Some(distribution) = &options.cloudfront_distribution {
+        tracing::info!("Invalidating CloudFront distribution {distribution}...");
+        tokio::runtime::Handle::current().block_on(cloudfront::invalidate(
+            &credentials,
+            distribution,
+            &options.target.prefix,
+        ))?;
+    }
+    Ok(writer.uploaded)
+}
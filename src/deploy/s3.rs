@@ -0,0 +1,174 @@
+//! Uploading a site to an S3 bucket, or any object storage that speaks the S3 API.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use camino::Utf8Path;
+use url::Url;
+
+use crate::deploy::sigv4::{self, Signer};
+use crate::errors::*;
+use crate::site::writer::SiteWriter;
+
+/// Where a site goes in object storage, written like `s3://bucket/prefix`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct S3Target {
+    pub bucket: String,
+    /// The key every file's key starts with, without slashes around it. Empty for the root of
+    /// the bucket.
+    pub prefix: String,
+}
+
+impl S3Target {
+    /// The key of the file at `path` in the site
+    pub fn key(&self, path: &Utf8Path) -> String {
+        if self.prefix.is_empty() {
+            path.as_str().to_owned()
+        } else {
+            format!("{}/{path}", self.prefix)
+        }
+    }
+}
+
+impl FromStr for S3Target {
+    type Err = OrandaError;
+
+    fn from_str(target: &str) -> Result<Self> {
+        let invalid = || OrandaError::InvalidDeployTarget {
+            target: target.to_owned(),
+        };
+        let rest = target.strip_prefix("s3://").ok_or_else(invalid)?;
+        let (bucket, prefix) = rest.split_once('/').unwrap_or((rest, ""));
+        if bucket.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self {
+            bucket: bucket.to_owned(),
+            prefix: prefix.trim_matches('/').to_owned(),
+        })
+    }
+}
+
+impl fmt::Display for S3Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "s3://{}/{}", self.bucket, self.prefix)
+    }
+}
+
+/// The `Content-Type` a file is served with
+pub fn content_type(path: &Utf8Path) -> String {
+    let mime = mime_guess::from_path(path).first_or_octet_stream();
+    let textual = mime.type_() == mime_guess::mime::TEXT
+        || matches!(
+            mime.essence_str(),
+            "application/javascript" | "application/json" | "application/xml"
+        );
+    if textual {
+        format!("{mime}; charset=utf-8")
+    } else {
+        mime.to_string()
+    }
+}
+
+/// The `Cache-Control` a file is served with. Pages, feeds and scripts change with every build,
+/// so browsers and CDNs check for a new version every time. oranda's own CSS is named after the
+/// version it's from and never changes, and everything else, like images, is cached for a day.
+pub fn cache_control(path: &Utf8Path) -> &'static str {
+    let name = path.file_name().unwrap_or_default();
+    match path.extension().unwrap_or_default() {
+        "css" if name.starts_with("oranda-v") => "public, max-age=31536000, immutable",
+        "html" | "xml" | "json" | "txt" | "js" | "css" | "webmanifest" | "" => {
+            "public, max-age=0, must-revalidate"
+        }
+        _ => "public, max-age=86400",
+    }
+}
+
+/// Uploads every file it's given to a bucket
+pub struct S3Writer {
+    target: S3Target,
+    signer: Signer,
+    endpoint: Option<Url>,
+    client: reqwest::Client,
+    /// How many files were uploaded so far
+    pub uploaded: usize,
+}
+
+impl S3Writer {
+    /// Uploads to `target` in AWS, or to the S3-compatible storage at `endpoint`, like
+    /// `https://<account>.r2.cloudflarestorage.com`
+    pub fn new(target: S3Target, signer: Signer, endpoint: Option<Url>) -> Self {
+        Self {
+            target,
+            signer,
+            endpoint,
+            client: reqwest::Client::new(),
+            uploaded: 0,
+        }
+    }
+
+    /// The URL of the object with `key`. Buckets in AWS are addressed by their own host, unless
+    /// their name has dots, which the wildcard certificate doesn't cover. Other storage is
+    /// addressed by path, which they all support.
+    pub fn object_url(&self, key: &str) -> Result<Url> {
+        let bucket = &self.target.bucket;
+        let key = sigv4::encode_key(key);
+        let url = match &self.endpoint {
+            Some(endpoint) => {
+                let base = endpoint.as_str().trim_end_matches('/');
+                format!("{base}/{bucket}/{key}")
+            }
+            None if bucket.contains('.') => {
+                format!(
+                    "https://s3.{}.amazonaws.com/{bucket}/{key}",
+                    self.signer.region
+                )
+            }
+            None => format!(
+                "https://{bucket}.s3.{}.amazonaws.com/{key}",
+                self.signer.region
+            ),
+        };
+        Url::parse(&url).map_err(|e| OrandaError::DeployFailed {
+            target: self.target.to_string(),
+            details: e.to_string(),
+        })
+    }
+}
+
+impl SiteWriter for S3Writer {
+    fn write(&mut self, path: &Utf8Path, contents: &[u8]) -> Result<()> {
+        let key = self.target.key(path);
+        let url = self.object_url(&key)?;
+        let mut headers = BTreeMap::from([
+            ("content-type".to_owned(), content_type(path)),
+            ("cache-control".to_owned(), cache_control(path).to_owned()),
+        ]);
+        self.signer
+            .sign("PUT", &url, &mut headers, contents, chrono::Utc::now());
+
+        let mut request = self.client.put(url).body(contents.to_vec());
+        // reqwest sets the host itself, from the URL we signed
+        for (name, value) in headers.iter().filter(|(name, _)| *name != "host") {
+            request = request.header(name, value);
+        }
+        let failed = |details: String| OrandaError::DeployFailed {
+            target: format!("s3://{}/{key}", self.target.bucket),
+            details,
+        };
+        let response = tokio::runtime::Handle::current()
+            .block_on(request.send())
+            .map_err(|e| failed(e.to_string()))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = tokio::runtime::Handle::current()
+                .block_on(response.text())
+                .unwrap_or_default();
+            return Err(failed(format!("{status}: {body}")));
+        }
+        tracing::debug!("Uploaded {key}");
+        self.uploaded += 1;
+        Ok(())
+    }
+}
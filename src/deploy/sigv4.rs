@@ -0,0 +1,151 @@
+//! Signing requests to AWS with [Signature Version 4][sigv4], which S3, CloudFront and most
+//! S3-compatible object storage accept.
+//!
+//! [sigv4]: https://docs.aws.amazon.com/IAM/latest/UserGuide/reference_aws-signing.html
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use url::Url;
+
+use crate::errors::*;
+
+/// The keys requests are signed with
+#[derive(Debug, Clone)]
+pub struct Credentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    /// Set for temporary credentials, like the ones CI gets from assuming a role
+    pub session_token: Option<String>,
+}
+
+impl Credentials {
+    /// Reads the credentials from `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and
+    /// `AWS_SESSION_TOKEN`, the same variables the AWS CLI reads
+    pub fn from_env() -> Result<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        match (var("AWS_ACCESS_KEY_ID"), var("AWS_SECRET_ACCESS_KEY")) {
+            (Some(access_key_id), Some(secret_access_key)) => Ok(Self {
+                access_key_id,
+                secret_access_key,
+                session_token: var("AWS_SESSION_TOKEN"),
+            }),
+            _ => Err(OrandaError::DeployCredentialsMissing),
+        }
+    }
+}
+
+/// Signs requests to one service in one region
+#[derive(Debug, Clone)]
+pub struct Signer {
+    pub credentials: Credentials,
+    pub region: String,
+    pub service: String,
+}
+
+impl Signer {
+    /// Adds the `host`, `x-amz-*` and `authorization` headers to a request for `url` with
+    /// `payload` as its body. Every header already in `headers` is signed, so their names need
+    /// to be lowercase.
+    pub fn sign(
+        &self,
+        method: &str,
+        url: &Url,
+        headers: &mut BTreeMap<String, String>,
+        payload: &[u8],
+        now: DateTime<Utc>,
+    ) {
+        let timestamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let host = match url.port() {
+            Some(port) => format!("{}:{port}", url.host_str().unwrap_or_default()),
+            None => url.host_str().unwrap_or_default().to_owned(),
+        };
+        headers.insert("host".to_owned(), host);
+        headers.insert("x-amz-date".to_owned(), timestamp.clone());
+        headers
+            .entry("x-amz-content-sha256".to_owned())
+            .or_insert_with(|| sha256_hex(payload));
+        if let Some(token) = &self.credentials.session_token {
+            headers.insert("x-amz-security-token".to_owned(), token.clone());
+        }
+
+        let signed_headers = headers.keys().cloned().collect::<Vec<_>>().join(";");
+        let mut canonical_headers = String::new();
+        for (name, value) in headers.iter() {
+            canonical_headers.push_str(&format!("{name}:{}\n", value.trim()));
+        }
+        let canonical_request = format!(
+            "{method}\n{path}\n{query}\n{canonical_headers}\n{signed_headers}\n{payload_hash}",
+            path = url.path(),
+            query = canonical_query(url),
+            payload_hash = headers["x-amz-content-sha256"],
+        );
+        let scope = format!("{date}/{}/{}/aws4_request", self.region, self.service);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{timestamp}\n{scope}\n{}",
+            sha256_hex(canonical_request.as_bytes())
+        );
+
+        let secret = format!("AWS4{}", self.credentials.secret_access_key);
+        let key = [
+            date.as_str(),
+            self.region.as_str(),
+            self.service.as_str(),
+            "aws4_request",
+        ]
+        .iter()
+        .fold(secret.into_bytes(), |key, part| hmac(&key, part.as_bytes()));
+        let mut mac = Hmac::<Sha256>::new_from_slice(&key).expect("HMAC takes keys of any length");
+        mac.update(string_to_sign.as_bytes());
+        let signature = format!("{:x}", mac.finalize().into_bytes());
+        headers.insert(
+            "authorization".to_owned(),
+            format!(
+                "AWS4-HMAC-SHA256 Credential={}/{scope},SignedHeaders={signed_headers},Signature={signature}",
+                self.credentials.access_key_id
+            ),
+        );
+    }
+}
+
+/// Percent-encodes `key` the way S3 expects object keys in a path, leaving the slashes alone
+pub fn encode_key(key: &str) -> String {
+    key.split('/').map(uri_encode).collect::<Vec<_>>().join("/")
+}
+
+fn uri_encode(s: &str) -> String {
+    s.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+fn canonical_query(url: &Url) -> String {
+    let mut pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(name, value)| (uri_encode(&name), uri_encode(&value)))
+        .collect();
+    pairs.sort();
+    pairs
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
@@ -0,0 +1,129 @@
+//! What `oranda dev` is up to, for editor integrations.
+//!
+//! The dev server serves this as JSON at [`STATUS_PATH`][], so that an editor plugin can poll it
+//! and show a failed rebuild next to the markdown or config that caused it, instead of in a
+//! terminal nobody's looking at.
+
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
+
+use camino::Utf8Path;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::errors::*;
+
+/// Where the dev server serves the status, regardless of `build.path_prefix`
+pub const STATUS_PATH: &str = "/__oranda/status";
+
+/// The status of the dev server's builds, shared between the thread building the site and the
+/// one serving it
+#[derive(Debug, Clone, Default)]
+pub struct DevStatusHandle(Arc<RwLock<DevStatus>>);
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct DevStatus {
+    pub state: BuildState,
+    /// How many builds have finished, successful or not
+    pub builds: u64,
+    pub last_build_started: Option<DateTime<Utc>>,
+    pub last_build_finished: Option<DateTime<Utc>>,
+    pub last_build_duration_ms: Option<u64>,
+    /// The files that changed to cause the current or last build
+    pub changed_paths: Vec<String>,
+    /// Why the last build failed, in miette's JSON format, which includes the error's code,
+    /// help text, and any labelled source spans
+    pub error: Option<serde_json::Value>,
+    /// The pages of the last successful build, as paths relative to the site's root
+    pub pages: Vec<String>,
+    #[serde(skip)]
+    started_at: Option<Instant>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BuildState {
+    /// Nothing has been built yet
+    #[default]
+    Idle,
+    Building,
+    Ok,
+    Failed,
+}
+
+impl DevStatusHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self) -> DevStatus {
+        self.0.read().unwrap().clone()
+    }
+
+    pub fn build_started(&self, changed_paths: Vec<String>) {
+        let mut status = self.0.write().unwrap();
+        status.state = BuildState::Building;
+        status.last_build_started = Some(Utc::now());
+        status.changed_paths = changed_paths;
+        status.started_at = Some(Instant::now());
+    }
+
+    /// Records the result of a build. The pages are read back from `dist_dir`, and are kept from
+    /// the last successful build if this one failed.
+    pub fn build_finished(&self, result: &Result<()>, dist_dir: &Utf8Path) {
+        let mut status = self.0.write().unwrap();
+        status.builds += 1;
+        status.last_build_finished = Some(Utc::now());
+        status.last_build_duration_ms = status
+            .started_at
+            .take()
+            .map(|started| started.elapsed().as_millis() as u64);
+        match result {
+            Ok(()) => {
+                status.state = BuildState::Ok;
+                status.error = None;
+                status.pages = list_pages(dist_dir);
+            }
+            Err(e) => {
+                status.state = BuildState::Failed;
+                status.error = Some(diagnostic_json(e));
+            }
+        }
+    }
+}
+
+/// Renders an error the way `--output-format=json` would
+pub fn diagnostic_json(error: &OrandaError) -> serde_json::Value {
+    let mut json = String::new();
+    if miette::JSONReportHandler::new()
+        .render_report(&mut json, error)
+        .is_err()
+    {
+        return serde_json::json!({ "message": error.to_string() });
+    }
+    serde_json::from_str(&json)
+        .unwrap_or_else(|_| serde_json::json!({ "message": error.to_string() }))
+}
+
+/// Every HTML file in `dist_dir`, like "index.html" or "docs/install/index.html"
+pub fn list_pages(dist_dir: &Utf8Path) -> Vec<String> {
+    fn walk(root: &Utf8Path, dir: &Utf8Path, pages: &mut Vec<String>) {
+        let Ok(entries) = dir.read_dir_utf8() else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                walk(root, path, pages);
+            } else if path.extension() == Some("html") {
+                if let Ok(relative) = path.strip_prefix(root) {
+                    pages.push(relative.as_str().replace('\\', "/"));
+                }
+            }
+        }
+    }
+    let mut pages = vec![];
+    walk(dist_dir, dist_dir, &mut pages);
+    pages.sort();
+    pages
+}
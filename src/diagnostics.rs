@@ -0,0 +1,139 @@
+//! Printing errors and warnings.
+//!
+//! By default we let miette draw them, with source snippets and all. With
+//! `--message-format=short`, every diagnostic is printed on one line instead, in the
+//! `file:line:col: severity: message` format that editors' problem matchers and CI annotations
+//! know how to jump to.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use clap::ValueEnum;
+use miette::{Diagnostic, Severity};
+
+use crate::errors::OrandaError;
+use crate::site::templates::override_path;
+
+static SHORT_MESSAGES: AtomicBool = AtomicBool::new(false);
+
+/// How errors and warnings are printed
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum MessageFormat {
+    /// miette's graphical output
+    #[default]
+    Human,
+    /// One line per message, like `oranda.json:3:5: error: unknown field`
+    Short,
+}
+
+pub fn set_message_format(format: MessageFormat) {
+    SHORT_MESSAGES.store(format == MessageFormat::Short, Ordering::Relaxed);
+}
+
+pub fn message_format() -> MessageFormat {
+    if SHORT_MESSAGES.load(Ordering::Relaxed) {
+        MessageFormat::Short
+    } else {
+        MessageFormat::Human
+    }
+}
+
+/// Prints an error (or, more often, a warning) that doesn't stop the build
+pub fn report(error: OrandaError) {
+    crate::report::record_warning(short_message(&error));
+    match message_format() {
+        MessageFormat::Human => eprintln!("{:?}", miette::Report::new(error)),
+        MessageFormat::Short => eprintln!("{}", short_message(&error)),
+    }
+}
+
+/// Where a diagnostic points, as precisely as we know
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub file: String,
+    /// 1-based
+    pub line: Option<usize>,
+    /// 1-based
+    pub column: Option<usize>,
+}
+
+/// Renders `error` as `file:line:col: severity: message`, leaving out whatever part of the
+/// location we don't know. Errors without a file are attributed to "oranda".
+pub fn short_message(error: &OrandaError) -> String {
+    let severity = match error.severity().unwrap_or(Severity::Error) {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Advice => "note",
+    };
+    let mut message = error.to_string();
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        let cause_message = cause.to_string();
+        // Transparent errors repeat their inner error's message
+        if !message.ends_with(&cause_message) {
+            message = format!("{message}: {cause_message}");
+        }
+        source = cause.source();
+    }
+    let message = message.replace('\n', " ");
+    let location = match location(error) {
+        Some(Location { file, line, column }) => {
+            let mut location = file;
+            if let Some(line) = line {
+                location = format!("{location}:{line}");
+                if let Some(column) = column {
+                    location = format!("{location}:{column}");
+                }
+            }
+            location
+        }
+        None => "oranda".to_owned(),
+    };
+    format!("{location}: {severity}: {message}")
+}
+
+pub fn location(error: &OrandaError) -> Option<Location> {
+    let at_file = |file: &str| {
+        Some(Location {
+            file: file.to_owned(),
+            line: None,
+            column: None,
+        })
+    };
+    match error {
+        OrandaError::Minijinja(e) => {
+            let name = e.name()?;
+            let file = override_path(name)
+                .map(|path| path.to_string())
+                .unwrap_or_else(|| name.to_owned());
+            Some(Location {
+                file,
+                line: e.line(),
+                column: None,
+            })
+        }
+        OrandaError::TemplateLoadFailed { path, line, .. } => Some(Location {
+            file: path.clone(),
+            line: *line,
+            column: None,
+        }),
+        OrandaError::PathDoesNotExist { path }
+        | OrandaError::GithubActionParseError { path, .. } => at_file(path),
+        OrandaError::FundingLoadFailed { path, .. } => at_file(path.as_str()),
+        _ => labelled_location(error),
+    }
+}
+
+/// The first label of the diagnostic, or of the diagnostics it wraps
+fn labelled_location(diagnostic: &dyn Diagnostic) -> Option<Location> {
+    if let (Some(source), Some(mut labels)) = (diagnostic.source_code(), diagnostic.labels()) {
+        if let Some(label) = labels.next() {
+            let contents = source.read_span(label.inner(), 0, 0).ok()?;
+            return Some(Location {
+                file: contents.name().unwrap_or("oranda").to_owned(),
+                line: Some(contents.line() + 1),
+                column: Some(contents.column() + 1),
+            });
+        }
+    }
+    labelled_location(diagnostic.diagnostic_source()?)
+}
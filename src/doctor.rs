@@ -0,0 +1,298 @@
+//! The checks `oranda doctor` runs.
+//!
+//! Every check turns into a [`Finding`][], good or bad, so the command can show what it looked
+//! at as well as what's wrong. The ones that need the network are kept apart from the rest, so
+//! that `--offline` can skip them.
+
+use axoasset::LocalAsset;
+use axoproject::GithubRepo;
+use camino::Utf8Path;
+use cargo_dist_schema::DistManifest;
+
+use crate::config::{Config, ConfigOptions};
+use crate::data::funding;
+use crate::data::github::GithubRelease;
+use crate::data::{cargo_dist, sponsors};
+use crate::errors::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    /// Something that will make the site worse, or slow down the build
+    Warning,
+    /// Something that will make the build fail
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// What was checked, like "readme"
+    pub check: &'static str,
+    pub status: Status,
+    pub message: String,
+    /// What to do about it
+    pub help: Option<String>,
+}
+
+impl Finding {
+    fn ok(check: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            check,
+            status: Status::Ok,
+            message: message.into(),
+            help: None,
+        }
+    }
+
+    fn warning(check: &'static str, message: impl Into<String>, help: impl Into<String>) -> Self {
+        Self {
+            check,
+            status: Status::Warning,
+            message: message.into(),
+            help: Some(help.into()),
+        }
+    }
+
+    fn error(check: &'static str, message: impl Into<String>, help: impl Into<String>) -> Self {
+        Self {
+            check,
+            status: Status::Error,
+            message: message.into(),
+            help: Some(help.into()),
+        }
+    }
+
+    /// The finding as a diagnostic to print, unless it's fine
+    pub fn to_error(&self) -> Option<OrandaError> {
+        let check = self.check.to_owned();
+        let message = self.message.clone();
+        let help = self.help.clone();
+        match self.status {
+            Status::Ok => None,
+            Status::Warning => Some(OrandaError::DoctorWarning {
+                check,
+                message,
+                help,
+            }),
+            Status::Error => Some(OrandaError::DoctorError {
+                check,
+                message,
+                help,
+            }),
+        }
+    }
+}
+
+/// Loads the config at `config_path`, and if that works, runs the checks that don't need the
+/// network on it
+pub fn check_config(
+    config_path: &Utf8Path,
+    options: &ConfigOptions,
+) -> (Option<Config>, Vec<Finding>) {
+    match Config::build(&config_path.to_owned(), options) {
+        Ok(config) => {
+            let mut findings = vec![if config_path.exists() {
+                Finding::ok("config", format!("{config_path} is valid"))
+            } else {
+                Finding::ok("config", format!("No {config_path}, using the defaults"))
+            }];
+            findings.extend(local_checks(&config));
+            (Some(config), findings)
+        }
+        Err(e) => {
+            let help = miette::Diagnostic::help(&e)
+                .map(|help| help.to_string())
+                .unwrap_or_else(|| "Fix the config and run oranda doctor again.".to_owned());
+            (None, vec![Finding::error("config", e.to_string(), help)])
+        }
+    }
+}
+
+/// The checks that only look at the config and the files it points to
+pub fn local_checks(config: &Config) -> Vec<Finding> {
+    let mut findings = vec![readme(config), repository(config)];
+    findings.extend(github_token(config));
+    findings.extend(funding_yml(config));
+    findings.extend(mdbook(config));
+    findings
+}
+
+/// The checks that talk to GitHub: whether the repository is there, and whether oranda can read
+/// the cargo-dist manifest of its latest release
+pub fn remote_checks(config: &Config) -> Vec<Finding> {
+    let Some(repo) = config
+        .project
+        .repository
+        .as_deref()
+        .and_then(|url| GithubRepo::from_url(url).ok())
+    else {
+        return vec![];
+    };
+    let repo_name = format!("{}/{}", repo.owner, repo.name);
+    let releases = match tokio::runtime::Handle::current()
+        .block_on(GithubRelease::fetch_all(&config.root_dir, &repo))
+    {
+        Ok(releases) => releases,
+        Err(e) => {
+            return vec![Finding::error(
+                "repository",
+                format!("Couldn't fetch the releases of {repo_name}: {e}"),
+                format!(
+                    "Make sure {} exists and is public. If GitHub is rate limiting you, set {}.",
+                    repo_name,
+                    sponsors::TOKEN_VAR
+                ),
+            )]
+        }
+    };
+    let mut findings = vec![Finding::ok(
+        "repository",
+        format!(
+            "{repo_name} is reachable, with {} release(s)",
+            releases.len()
+        ),
+    )];
+
+    let cargo_dist = config
+        .components
+        .artifacts
+        .as_ref()
+        .is_some_and(|artifacts| artifacts.cargo_dist);
+    if cargo_dist {
+        let latest = releases
+            .iter()
+            .filter(|release| !release.prerelease && release.has_dist_manifest())
+            .max_by(|a, b| a.published_at.cmp(&b.published_at));
+        findings.push(match latest {
+            Some(release) => {
+                let url = release
+                    .asset_url(cargo_dist::MANIFEST_FILENAME)
+                    .unwrap_or_default();
+                let contents = tokio::runtime::Handle::current()
+                    .block_on(async { reqwest::get(url).await?.error_for_status()?.text().await });
+                match contents {
+                    Ok(contents) => dist_manifest(&contents, &release.tag_name),
+                    Err(e) => Finding::error(
+                        "dist-manifest",
+                        format!(
+                            "Couldn't download the dist-manifest.json of {}: {e}",
+                            release.tag_name
+                        ),
+                        "Make sure the release's assets are public.",
+                    ),
+                }
+            }
+            None => Finding::warning(
+                "dist-manifest",
+                "None of your releases have a dist-manifest.json",
+                "If you don't use cargo-dist, set components.artifacts.cargo_dist to false.",
+            ),
+        });
+    }
+    findings
+}
+
+/// Checks that we can read a cargo-dist manifest, from the release tagged `tag`
+pub fn dist_manifest(contents: &str, tag: &str) -> Finding {
+    if let Err(e) = serde_json::from_str::<DistManifest>(contents) {
+        let help = match cargo_dist_schema::check_version(contents) {
+            Some(info) if info.format.unsupported() => format!(
+                "It was made by cargo-dist {}, which is too old for oranda to read. Releasing with a newer cargo-dist will fix this.",
+                info.version
+            ),
+            Some(info) => format!(
+                "It was made by cargo-dist {}, but this version of oranda understands manifests up to cargo-dist {}. Updating oranda should fix this.",
+                info.version,
+                cargo_dist_schema::SELF_VERSION
+            ),
+            None => "It doesn't look like a cargo-dist manifest at all.".to_owned(),
+        };
+        return Finding::error(
+            "dist-manifest",
+            format!("Couldn't read the dist-manifest.json of {tag}: {e}"),
+            help,
+        );
+    }
+    Finding::ok(
+        "dist-manifest",
+        format!("The dist-manifest.json of {tag} can be read"),
+    )
+}
+
+fn readme(config: &Config) -> Finding {
+    let path = &config.project.readme_path;
+    if Utf8Path::new(path).is_file() {
+        Finding::ok("readme", format!("Found {path}"))
+    } else {
+        Finding::error(
+            "readme",
+            format!("{path} doesn't exist"),
+            "oranda builds your front page from your README. Set project.readme_path if it's somewhere else.",
+        )
+    }
+}
+
+fn repository(config: &Config) -> Finding {
+    match &config.project.repository {
+        None => Finding::warning(
+            "repository",
+            "project.repository isn't set",
+            "Set it to your GitHub repository's URL to show your releases, artifacts and changelog.",
+        ),
+        Some(url) => match GithubRepo::from_url(url) {
+            Ok(_) => Finding::ok("repository", format!("{url} is a GitHub repository")),
+            Err(_) => Finding::warning(
+                "repository",
+                format!("{url} isn't a GitHub repository"),
+                "oranda can only fetch releases from GitHub, so the artifacts and changelog pages will be empty unless components.source is \"axodotdev\".",
+            ),
+        },
+    }
+}
+
+fn github_token(config: &Config) -> Option<Finding> {
+    config.project.repository.as_ref()?;
+    Some(if std::env::var(sponsors::TOKEN_VAR).is_ok() {
+        Finding::ok("github-token", format!("{} is set", sponsors::TOKEN_VAR))
+    } else {
+        Finding::warning(
+            "github-token",
+            format!("{} isn't set", sponsors::TOKEN_VAR),
+            "Without a token, GitHub only answers 60 requests an hour, which big changelogs or a few builds in a row can run into. Sponsor goals need one too.",
+        )
+    })
+}
+
+fn funding_yml(config: &Config) -> Option<Finding> {
+    let yml_path = config.components.funding.as_ref()?.yml_path.as_ref()?;
+    let finding = match LocalAsset::load_string(yml_path) {
+        Ok(contents) => match funding::parse_response(contents) {
+            Ok(_) => Finding::ok("funding", format!("{yml_path} is valid")),
+            Err(e) => Finding::error(
+                "funding",
+                format!("{yml_path} can't be read: {e}"),
+                "Check it against GitHub's FUNDING.yml format, or set components.funding.yml_path.",
+            ),
+        },
+        Err(e) => Finding::warning(
+            "funding",
+            format!("{yml_path} can't be loaded: {e}"),
+            "The funding page will only show what's in components.funding. Set components.funding.yml_path if your FUNDING.yml is somewhere else.",
+        ),
+    };
+    Some(finding)
+}
+
+fn mdbook(config: &Config) -> Option<Finding> {
+    let path = config.components.mdbook.as_ref()?.path.as_ref()?;
+    let book_toml = Utf8Path::new(path).join("book.toml");
+    Some(if book_toml.is_file() {
+        Finding::ok("mdbook", format!("Found {book_toml}"))
+    } else {
+        Finding::error(
+            "mdbook",
+            format!("There's no book.toml in {path}"),
+            "Set components.mdbook.path to the directory with your book.toml.",
+        )
+    })
+}
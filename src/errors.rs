@@ -146,6 +146,16 @@ pub enum OrandaError {
         details: mdbook::errors::Error,
     },
 
+    #[error("Couldn't apply `{key}` from components.mdbook.overrides to your mdbook")]
+    #[diagnostic(
+        help = "Make sure the key is a dotted path into book.toml, like `output.html.fold.enable`"
+    )]
+    MdBookOverride {
+        key: String,
+        #[source]
+        details: mdbook::errors::Error,
+    },
+
     #[error("Can't build mdbook because book output directory {dest_path} is under book source directory {src_path}")]
     #[diagnostic(help(
         "Make sure that your book source does not contain your book output directory, as that will lead to infinite recursion. Change either the `src` setting or the `build_dir` setting in your book.toml."
@@ -202,12 +212,69 @@ pub enum OrandaError {
         details: reqwest::Error,
     },
 
+    #[error("Failed to fetch GitHub Sponsors info for {login}: {details}")]
+    #[diagnostic(
+        severity = "warn",
+        help = "Make sure GITHUB_TOKEN is a valid token, and that {login} has a GitHub Sponsors profile."
+    )]
+    GithubSponsorsFetchFailed { login: String, details: String },
+
+    #[error("Failed to fetch project health from {service}: {details}")]
+    #[diagnostic(
+        severity = "warn",
+        help = "Check your components.health config. Setting GITHUB_TOKEN can help with GitHub's rate limits."
+    )]
+    HealthFetchFailed { service: String, details: String },
+
+    #[error("Couldn't fetch your project's activity from GitHub: {details}")]
+    #[diagnostic(
+        severity = "warn",
+        help = "The activity widget will be built without it. Setting GITHUB_TOKEN can help with GitHub's rate limits."
+    )]
+    ActivityFetchFailed { details: String },
+
+    #[error("Couldn't fetch security advisories from {source_name}: {details}")]
+    #[diagnostic(
+        severity = "warn",
+        help = "The security page will be built without them. Setting GITHUB_TOKEN can help with GitHub's rate limits."
+    )]
+    AdvisoriesFetchFailed {
+        source_name: String,
+        details: String,
+    },
+
+    #[error("Couldn't fetch your milestones from GitHub: {details}")]
+    #[diagnostic(
+        severity = "warn",
+        help = "The roadmap page will be built without them. Setting GITHUB_TOKEN can help with GitHub's rate limits."
+    )]
+    RoadmapFetchFailed { details: String },
+
+    #[error("Couldn't fetch your image's tags from {registry}: {details}")]
+    #[diagnostic(
+        severity = "warn",
+        help = "Only the tags in components.docker.tags will be shown. Check that the image is public."
+    )]
+    DockerTagsFetchFailed { registry: String, details: String },
+
+    #[error("Couldn't fetch the repositories of {org}: {details}")]
+    #[diagnostic(
+        help = "Check that {org} is a GitHub organization. Setting GITHUB_TOKEN can help with GitHub's rate limits."
+    )]
+    OrgReposFetchFailed { org: String, details: String },
+
     #[error("Couldn't find your FUNDING.yml or funding.md")]
     #[diagnostic(
         help = "You can manually specify md_path or yml_path in your components.funding config"
     )]
     FundingConfigInvalid,
 
+    #[error("The icon of your funding link \"{label}\" isn't one of ours or an image URL: {icon}")]
+    #[diagnostic(
+        help = "Local images aren't copied into your site. Put it in your static_dir and link it with an absolute path, like \"/static/icon.svg\"."
+    )]
+    FundingIconInvalid { label: String, icon: String },
+
     #[error("Error while parsing FUNDING.yml")]
     #[diagnostic(
         help = "Make sure your FUNDING.yml conforms to GitHub's format!",
@@ -215,6 +282,13 @@ pub enum OrandaError {
     )]
     GithubFundingParseError { details: String },
 
+    #[error("Failed to parse your GitHub Action's metadata file at {path}: {details}")]
+    #[diagnostic(
+        help = "Make sure your action.yml follows GitHub's metadata syntax, or disable this with `components.github_action: false`",
+        url = "https://docs.github.com/en/actions/creating-actions/metadata-syntax-for-github-actions"
+    )]
+    GithubActionParseError { path: String, details: String },
+
     #[error("Your preferred_funding '{preferred}' didn't match any of the sources we found")]
     #[diagnostic(help = "{help}")]
     PreferredFundingNotFound { preferred: String, help: String },
@@ -223,12 +297,239 @@ pub enum OrandaError {
     #[diagnostic(help = "You can manually specify path in your components.mdbook config")]
     MdBookConfigInvalid,
 
+    #[error("Couldn't apply `--set {key}`: {details}")]
+    #[diagnostic(
+        help = "--set takes config keys the way they're nested in oranda.json, like `--set build.path_prefix=preview`"
+    )]
+    ConfigOverrideInvalid { key: String, details: String },
+
+    #[error(
+        "The config for workspace member {slug} in oranda-workspace.json is invalid: {details}"
+    )]
+    #[diagnostic(help = "It takes the same keys as an oranda.json, except `workspace`.")]
+    WorkspaceMemberConfigInvalid { slug: String, details: String },
+
+    #[error("There's no profile called `{name}` in your config")]
+    #[diagnostic(
+        help = "Add it under `profiles` in your oranda.json. Profiles it has: {available}"
+    )]
+    UnknownProfile { name: String, available: String },
+
+    #[error("components.docs needs a `command` and an `output_dir`")]
+    #[diagnostic(
+        help = "Set `command` to whatever builds your docs, and `output_dir` to the directory it builds them to"
+    )]
+    DocsConfigInvalid,
+
+    #[error("components.demo is enabled, but doesn't say where the demo is")]
+    #[diagnostic(help = "Set `path` to the directory your demo is built to, like \"./www/dist\"")]
+    DemoConfigInvalid,
+
+    #[error("components.docker is enabled, but doesn't say which image to show")]
+    #[diagnostic(
+        help = "Set `image` to your image's name, like \"axodotdev/oranda\". `registries` and `tags` can't be empty either."
+    )]
+    DockerConfigInvalid,
+
+    #[error("Running `{command}` to build your docs failed: {details}")]
+    #[diagnostic(
+        help = "Make sure the command works when you run it yourself in components.docs.path"
+    )]
+    DocsBuildFailed { command: String, details: String },
+
+    #[error("Running `cargo metadata` to find your dependencies' licenses failed: {details}")]
+    #[diagnostic(
+        help = "Make sure `cargo metadata` works when you run it yourself, or point components.licenses.report at a cargo-about report"
+    )]
+    LicensesMetadataFailed { details: String },
+
+    #[error(
+        "Running `cargo about` to make a report of your dependencies' licenses failed: {details}"
+    )]
+    #[diagnostic(
+        help = "Install cargo-about with `cargo install cargo-about`, and make sure `cargo about generate --format json` works when you run it yourself"
+    )]
+    LicensesReportFailed { details: String },
+
+    #[error("Running `cargo doc` to build your API docs failed: {details}")]
+    #[diagnostic(
+        help = "Make sure `cargo doc --no-deps` works when you run it yourself in components.rustdoc.path"
+    )]
+    RustdocBuildFailed { details: String },
+
+    #[error("Couldn't find API docs for `{crate_name}` in `{doc_dir}`")]
+    #[diagnostic(
+        help = "Set components.rustdoc.crate_name to the crate whose docs you want to show"
+    )]
+    RustdocCrateNotFound { crate_name: String, doc_dir: String },
+
+    #[error("Couldn't make your site's icons from {path}: {details}")]
+    #[diagnostic(
+        help = "Point styles.web_manifest.icon at a local PNG, JPEG or GIF image, ideally a square one at least 512x512"
+    )]
+    WebManifestIconFailed { path: String, details: String },
+
+    #[error("You have private pages, but build.private_host isn't set, so nothing would keep them private")]
+    #[diagnostic(
+        help = "Set build.private_host to \"netlify\" to put them behind a password, or to \"none\" if you restrict access to `{private_path}` some other way"
+    )]
+    PrivateHostMissing { private_path: String },
+
+    #[error("You have private pages, but {var} isn't set, so they wouldn't be password protected")]
+    #[diagnostic(
+        help = "Set {var} to `user:password` when building, or set build.private_host to \"none\" if you restrict access some other way"
+    )]
+    PrivateCredentialsMissing { var: String },
+
     #[error("Specified path `{path}` was not found on your filesystem!")]
     #[diagnostic(
         help = "Make sure you specify your path relative to the oranda.json/manifest file/README file of your project!"
     )]
     PathDoesNotExist { path: String },
 
+    #[error("Running `git {command}` failed: {details}")]
+    #[diagnostic(help(
+        "Make sure git is installed and that you're running oranda inside a git repository."
+    ))]
+    GitCommandFailed { command: String, details: String },
+
+    #[error("Couldn't list the commits in {tag}, so its changelog entry falls back to the release notes: {details}")]
+    #[diagnostic(
+        severity = "warn",
+        help = "Make sure the tag exists in your checkout. In CI, you may need to fetch tags with `git fetch --tags`."
+    )]
+    ChangelogCommitsUnavailable { tag: String, details: String },
+
+    #[error("Couldn't read benchmark results from {path}: {details}")]
+    #[diagnostic(
+        severity = "warn",
+        help = "We support JSON exported by `hyperfine --export-json`, and criterion's output directory."
+    )]
+    BenchmarksLoadFailed { path: String, details: String },
+
+    #[error("Couldn't find a release for version {version}")]
+    #[diagnostic(
+        help = "Make sure the version matches a release tag, like `v1.2.3`, and that `project.repository` is set"
+    )]
+    ReleaseNotFound { version: String },
+
+    #[error("Couldn't find a browser to print PDFs with")]
+    #[diagnostic(help(
+        "Install Chrome, Chromium or Edge, or set ORANDA_BROWSER to the path of a Chromium-based browser."
+    ))]
+    BrowserNotFound,
+
+    #[error("Couldn't print {page} to a PDF: {details}")]
+    PdfExportFailed { page: String, details: String },
+
+    #[error("Couldn't make a self-signed certificate to serve your site over HTTPS: {details}")]
+    #[diagnostic(help = "Leave out --https to serve it over plain HTTP.")]
+    TlsCertificateFailed { details: String },
+
+    #[error("Couldn't mirror {name}: {details}")]
+    #[diagnostic(
+        severity = "warn",
+        help = "The download will link to where the file was published instead."
+    )]
+    MirrorFailed { name: String, details: String },
+
+    #[error("A template tried to fetch `{url}`, which isn't in build.fetch_json_allowlist")]
+    #[diagnostic(help = "Add the URL (or a prefix of it) to build.fetch_json_allowlist.")]
+    FetchJsonNotAllowed { url: String },
+
+    #[error("A template failed to fetch JSON from `{url}`: {details}")]
+    FetchJsonFailed { url: String, details: String },
+
+    #[error("Failed to load your template `{path}`: {details}")]
+    #[diagnostic(help = "Check the template's syntax, or remove it to use oranda's built-in one.")]
+    TemplateLoadFailed {
+        path: String,
+        line: Option<usize>,
+        details: String,
+    },
+
+    #[error("`{pattern}` in components.artifacts.matching isn't a valid regular expression")]
+    #[diagnostic(help = "{details}")]
+    InvalidArtifactPattern { pattern: String, details: String },
+
+    #[error("{count} theme snapshot(s) changed: {names}")]
+    #[diagnostic(
+        help = "The new renders are next to the old ones as `.new` files. If the changes are expected, run again with --accept."
+    )]
+    ThemeSnapshotsChanged { count: usize, names: String },
+
+    #[error("Failed to load the data in {path}: {details}")]
+    #[diagnostic(help = "This file is used by a page in components.data_pages.")]
+    DataFileLoadFailed { path: String, details: String },
+
+    #[error("Couldn't include `{path}` in a page, because it isn't inside {root}")]
+    #[diagnostic(
+        help = "Move the file into {root}, or set build.include_root to a directory that contains it."
+    )]
+    IncludeOutsideRoot { path: String, root: String },
+
+    #[error("Markdown files include each other in a loop: {chain}")]
+    #[diagnostic(help = "Remove one of these includes to break the loop.")]
+    IncludeCycle { chain: String },
+
+    #[error(
+        "`{versions}` in components.changelog.upgrade_guides isn't a valid version requirement"
+    )]
+    #[diagnostic(help = "{details}. Write it like \">=2.0.0, <2.1.0\" or \"^2\".")]
+    InvalidUpgradeGuideVersions { versions: String, details: String },
+
+    #[error("Don't know what kind of archive `{path}` is")]
+    #[diagnostic(help = "Give the archive a name ending in .tar.gz, .tgz or .zip.")]
+    UnknownArchiveFormat { path: String },
+
+    #[error("Failed to write the archive {path}: {details}")]
+    ArchiveFailed { path: String, details: String },
+
+    #[error("`{target}` isn't somewhere oranda can deploy to")]
+    #[diagnostic(help = "Write it like s3://my-bucket or s3://my-bucket/some/prefix.")]
+    InvalidDeployTarget { target: String },
+
+    #[error("Couldn't find credentials to deploy your site with")]
+    #[diagnostic(
+        help = "Set AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY, and AWS_SESSION_TOKEN if your credentials are temporary."
+    )]
+    DeployCredentialsMissing,
+
+    #[error("Failed to upload {target}: {details}")]
+    #[diagnostic(
+        help = "Check that your credentials can write to the bucket, and that --region or --endpoint point to where it lives."
+    )]
+    DeployFailed { target: String, details: String },
+
+    #[error("Failed to invalidate CloudFront distribution {distribution}: {details}")]
+    #[diagnostic(
+        help = "Your site was uploaded, but CloudFront may serve the old version until its cache expires."
+    )]
+    CloudFrontInvalidationFailed {
+        distribution: String,
+        details: String,
+    },
+
+    #[error("{check}: {message}")]
+    #[diagnostic(severity = "warn")]
+    DoctorWarning {
+        check: String,
+        message: String,
+        #[help]
+        help: Option<String>,
+    },
+
+    #[error("{check}: {message}")]
+    DoctorError {
+        check: String,
+        message: String,
+        #[help]
+        help: Option<String>,
+    },
+
+    #[error("oranda doctor found {count} problem(s) that will break your build")]
+    DoctorFailed { count: usize },
+
     #[error("{0}")]
     Other(String),
 }
@@ -0,0 +1,346 @@
+//! `oranda export announcement`, which turns a release's changelog entry into a post for a chat
+//! or social platform.
+//!
+//! Each platform has its own flavor of markdown (or none at all, in Mastodon's case) and its
+//! own length limit, so we walk the markdown ourselves and write out whatever the platform
+//! understands. Notes that don't fit get cut off at a line boundary, with a link to the full
+//! release notes at the end.
+
+use clap::ValueEnum;
+use comrak::nodes::{AstNode, ListType, NodeValue};
+use comrak::Arena;
+
+use crate::config::Config;
+use crate::data::Release;
+use crate::errors::*;
+use crate::site::markdown::initialize_comrak_options;
+
+use super::release_notes_markdown;
+
+/// Mastodon counts every link as this many characters, however long it is
+const MASTODON_URL_LENGTH: usize = 23;
+
+/// The platform to write an announcement for
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum AnnouncementFormat {
+    /// Discord's markdown, for a message of at most 2000 characters
+    Discord,
+    /// Slack's "mrkdwn", for a message of at most 4000 characters
+    Slack,
+    /// Plain text, for a post of at most 500 characters
+    Mastodon,
+}
+
+impl AnnouncementFormat {
+    /// The longest message the platform accepts, as counted by [`Self::length`][]
+    pub fn max_length(self) -> usize {
+        match self {
+            AnnouncementFormat::Discord => 2000,
+            // Slack technically allows longer messages, but starts truncating them at this point
+            AnnouncementFormat::Slack => 4000,
+            AnnouncementFormat::Mastodon => 500,
+        }
+    }
+
+    /// How long the platform considers `text` to be
+    pub fn length(self, text: &str) -> usize {
+        let length = text.chars().count();
+        if self != AnnouncementFormat::Mastodon {
+            return length;
+        }
+        let url_savings = text
+            .split_whitespace()
+            .filter(|token| is_absolute_url(token))
+            .map(|url| url.chars().count().saturating_sub(MASTODON_URL_LENGTH))
+            .sum::<usize>();
+        length - url_savings
+    }
+}
+
+/// Renders the announcement for `release`
+pub fn render(release: &Release, config: &Config, format: AnnouncementFormat) -> Result<String> {
+    let notes = release_notes_markdown(release, config)?;
+    let title = format!(
+        "{} {} is out!",
+        config.project.name,
+        release.source.version_tag()
+    );
+    let header = match format {
+        AnnouncementFormat::Discord => format!("## {}", escape(&title, format)),
+        AnnouncementFormat::Slack => format!("*{}*", escape(&title, format)),
+        AnnouncementFormat::Mastodon => title,
+    };
+    let url = release
+        .source
+        .html_url()
+        .or(config.project.homepage.as_deref());
+    let footer = url.map(|url| match format {
+        AnnouncementFormat::Discord => format!("Full release notes: <{url}>"),
+        AnnouncementFormat::Slack => format!("<{url}|Full release notes>"),
+        AnnouncementFormat::Mastodon => format!("Full release notes: {url}"),
+    });
+
+    let (announcement, truncated) = fit(&header, &convert(&notes, format), footer, format);
+    if truncated {
+        tracing::warn!(
+            "The release notes are too long for {:?}, so only the start of them is included.",
+            format
+        );
+    }
+    Ok(announcement)
+}
+
+/// Converts markdown to `format`. Returns the lines of the result, except that code blocks are
+/// kept together so they don't get cut in half.
+pub fn convert(markdown: &str, format: AnnouncementFormat) -> Vec<String> {
+    let arena = Arena::new();
+    let options = initialize_comrak_options();
+    let root = comrak::parse_document(&arena, markdown, &options);
+    let mut chunks = Vec::new();
+    for (i, block) in render_blocks(root, format).into_iter().enumerate() {
+        if i > 0 {
+            chunks.push(String::new());
+        }
+        chunks.extend(block);
+    }
+    chunks
+}
+
+/// Puts the announcement together, leaving out as much of the end of the notes as it takes to
+/// fit. Returns whether anything was left out.
+fn fit(
+    header: &str,
+    notes: &[String],
+    footer: Option<String>,
+    format: AnnouncementFormat,
+) -> (String, bool) {
+    let assemble = |body: &[String], truncated: bool| {
+        let mut parts = vec![header.to_owned()];
+        let mut body = body.join("\n").trim_end().to_owned();
+        if truncated {
+            body.push_str("\n…");
+        }
+        if !body.trim().is_empty() {
+            parts.push(body);
+        }
+        parts.extend(footer.clone());
+        parts.join("\n\n")
+    };
+
+    let full = assemble(notes, false);
+    if format.length(&full) <= format.max_length() {
+        return (full, false);
+    }
+    let mut kept = notes.len();
+    while kept > 0 {
+        kept -= 1;
+        let shortened = assemble(&notes[..kept], true);
+        if format.length(&shortened) <= format.max_length() {
+            return (shortened, true);
+        }
+    }
+    (assemble(&[], true), true)
+}
+
+/// Renders each block-level child of `node`, as a list of lines (or code blocks)
+fn render_blocks<'a>(node: &'a AstNode<'a>, format: AnnouncementFormat) -> Vec<Vec<String>> {
+    node.children()
+        .filter_map(|child| render_block(child, format))
+        .filter(|block| !block.is_empty())
+        .collect()
+}
+
+fn render_block<'a>(node: &'a AstNode<'a>, format: AnnouncementFormat) -> Option<Vec<String>> {
+    let lines = match &node.data.borrow().value {
+        NodeValue::Paragraph => {
+            let text = render_inlines(node, format);
+            text.lines()
+                .map(|line| line.trim_end().to_owned())
+                .collect()
+        }
+        NodeValue::Heading(heading) => {
+            let text = render_inlines(node, format);
+            vec![match format {
+                AnnouncementFormat::Discord => {
+                    format!("{} {text}", "#".repeat(heading.level.clamp(1, 3) as usize))
+                }
+                AnnouncementFormat::Slack => format!("*{text}*"),
+                AnnouncementFormat::Mastodon => text,
+            }]
+        }
+        NodeValue::List(list) => {
+            let mut number = list.start;
+            let mut lines = Vec::new();
+            for item in node.children() {
+                let marker = match (list.list_type, format) {
+                    (ListType::Ordered, _) => format!("{number}. "),
+                    (ListType::Bullet, AnnouncementFormat::Slack) => "• ".to_owned(),
+                    (ListType::Bullet, _) => "- ".to_owned(),
+                };
+                number += 1;
+                let indent = " ".repeat(marker.chars().count());
+                let item_lines = render_blocks(item, format).into_iter().flatten();
+                for (i, line) in item_lines.enumerate() {
+                    if i == 0 {
+                        lines.push(format!("{marker}{line}"));
+                    } else {
+                        lines.push(format!("{indent}{line}"));
+                    }
+                }
+            }
+            lines
+        }
+        NodeValue::BlockQuote => render_blocks(node, format)
+            .into_iter()
+            .flatten()
+            .map(|line| format!("> {line}"))
+            .collect(),
+        NodeValue::CodeBlock(code) => {
+            let code_text = code.literal.trim_end();
+            let block = match format {
+                AnnouncementFormat::Discord => {
+                    let lang = code.info.split_whitespace().next().unwrap_or("");
+                    format!("```{lang}\n{code_text}\n```")
+                }
+                AnnouncementFormat::Slack => format!("```\n{}\n```", escape(code_text, format)),
+                AnnouncementFormat::Mastodon => code_text.to_owned(),
+            };
+            vec![block]
+        }
+        NodeValue::Table(_) => node
+            .children()
+            .map(|row| {
+                row.children()
+                    .map(|cell| render_inlines(cell, format))
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            })
+            .collect(),
+        NodeValue::DescriptionList | NodeValue::DescriptionItem(_) => {
+            render_blocks(node, format).into_iter().flatten().collect()
+        }
+        NodeValue::DescriptionTerm => {
+            let text = render_blocks(node, format)
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(" ");
+            vec![match format {
+                AnnouncementFormat::Discord => format!("**{text}**"),
+                AnnouncementFormat::Slack => format!("*{text}*"),
+                AnnouncementFormat::Mastodon => text,
+            }]
+        }
+        NodeValue::DescriptionDetails => render_blocks(node, format)
+            .into_iter()
+            .flatten()
+            .map(|line| format!("  {line}"))
+            .collect(),
+        // Raw HTML (usually comments), horizontal rules and footnotes don't translate to any
+        // of the platforms
+        _ => return None,
+    };
+    Some(lines)
+}
+
+/// Renders the inline children of `node` as a single string
+fn render_inlines<'a>(node: &'a AstNode<'a>, format: AnnouncementFormat) -> String {
+    node.children()
+        .map(|child| render_inline(child, format))
+        .collect()
+}
+
+fn render_inline<'a>(node: &'a AstNode<'a>, format: AnnouncementFormat) -> String {
+    let (open, close) = match &node.data.borrow().value {
+        NodeValue::Text(text) => return escape(text, format),
+        NodeValue::Code(code) => {
+            return match format {
+                AnnouncementFormat::Mastodon => code.literal.clone(),
+                _ => format!("`{}`", escape_code(&code.literal, format)),
+            }
+        }
+        NodeValue::SoftBreak => return " ".to_owned(),
+        NodeValue::LineBreak => return "\n".to_owned(),
+        NodeValue::TaskItem { symbol } => {
+            return match symbol {
+                Some(_) => "[x] ".to_owned(),
+                None => "[ ] ".to_owned(),
+            }
+        }
+        NodeValue::HtmlInline(_) | NodeValue::FootnoteReference(_) => return String::new(),
+        NodeValue::Link(link) | NodeValue::Image(link) => {
+            let text = render_inlines(node, format);
+            return render_link(&link.url, &text, format);
+        }
+        NodeValue::Emph => match format {
+            AnnouncementFormat::Discord => ("*", "*"),
+            AnnouncementFormat::Slack => ("_", "_"),
+            AnnouncementFormat::Mastodon => ("", ""),
+        },
+        NodeValue::Strong => match format {
+            AnnouncementFormat::Discord => ("**", "**"),
+            AnnouncementFormat::Slack => ("*", "*"),
+            AnnouncementFormat::Mastodon => ("", ""),
+        },
+        NodeValue::Strikethrough => match format {
+            AnnouncementFormat::Discord => ("~~", "~~"),
+            AnnouncementFormat::Slack => ("~", "~"),
+            AnnouncementFormat::Mastodon => ("", ""),
+        },
+        _ => ("", ""),
+    };
+    format!("{open}{}{close}", render_inlines(node, format))
+}
+
+/// Links that aren't absolute (like `../foo.md`) won't work anywhere but the changelog they
+/// came from, so those just become their text.
+fn render_link(url: &str, text: &str, format: AnnouncementFormat) -> String {
+    if !is_absolute_url(url) && !url.starts_with("mailto:") {
+        return text.to_owned();
+    }
+    // Autolinks have the URL as their text, possibly escaped
+    let is_autolink = text.is_empty() || text == escape(url, format);
+    match format {
+        // The angle brackets stop Discord from embedding a preview of every link
+        AnnouncementFormat::Discord if is_autolink => format!("<{url}>"),
+        AnnouncementFormat::Discord => format!("[{text}](<{url}>)"),
+        AnnouncementFormat::Slack if is_autolink => format!("<{url}>"),
+        AnnouncementFormat::Slack => format!("<{url}|{text}>"),
+        AnnouncementFormat::Mastodon if is_autolink => url.to_owned(),
+        AnnouncementFormat::Mastodon => format!("{text} ({url})"),
+    }
+}
+
+fn is_absolute_url(url: &str) -> bool {
+    url.starts_with("https://") || url.starts_with("http://")
+}
+
+/// Escapes text so the platform doesn't treat any of it as formatting
+fn escape(text: &str, format: AnnouncementFormat) -> String {
+    match format {
+        AnnouncementFormat::Discord => {
+            let mut escaped = String::with_capacity(text.len());
+            for c in text.chars() {
+                if matches!(c, '\\' | '*' | '_' | '~' | '`' | '|') {
+                    escaped.push('\\');
+                }
+                escaped.push(c);
+            }
+            escaped
+        }
+        AnnouncementFormat::Slack => escape_code(text, format),
+        AnnouncementFormat::Mastodon => text.to_owned(),
+    }
+}
+
+/// Escapes the contents of a code span, where formatting characters are already ignored
+fn escape_code(text: &str, format: AnnouncementFormat) -> String {
+    match format {
+        // Slack wants these escaped everywhere, since it uses them for its own links
+        AnnouncementFormat::Slack => text
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;"),
+        _ => text.to_owned(),
+    }
+}
@@ -0,0 +1,179 @@
+//! `oranda export email`, which renders a release announcement that can be sent as a
+//! newsletter.
+//!
+//! Email clients ignore `<style>` tags and external stylesheets more often than not, so
+//! everything is styled with inline `style` attributes, including the release notes (see
+//! [`inline_styles`][]).
+
+use minijinja::Environment;
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::data::artifacts::{target_label, DisplayPreference, InstallMethod};
+use crate::data::Release;
+use crate::errors::*;
+use crate::site::link;
+
+use super::release_notes;
+
+const EMAIL_TEMPLATE: &str = include_str!("../../templates/export/email.html.j2");
+
+/// The styles we give each element of the release notes
+const STYLES: &[(&str, &str)] = &[
+    ("h1", "margin:24px 0 8px;font-size:22px;line-height:1.3;color:#111827;"),
+    ("h2", "margin:24px 0 8px;font-size:19px;line-height:1.3;color:#111827;"),
+    ("h3", "margin:20px 0 8px;font-size:17px;line-height:1.3;color:#111827;"),
+    ("h4", "margin:16px 0 8px;font-size:16px;line-height:1.3;color:#111827;"),
+    ("p", "margin:0 0 16px;font-size:16px;line-height:1.6;color:#374151;"),
+    ("ul", "margin:0 0 16px;padding-left:24px;"),
+    ("ol", "margin:0 0 16px;padding-left:24px;"),
+    ("li", "margin:0 0 4px;font-size:16px;line-height:1.6;color:#374151;"),
+    ("a", "color:#2563eb;text-decoration:underline;"),
+    ("code", "font-family:Menlo,Consolas,monospace;font-size:14px;background-color:#f3f4f6;padding:1px 4px;border-radius:3px;"),
+    ("pre", "margin:0 0 16px;padding:12px;overflow-x:auto;border-radius:4px;background-color:#1f2937;color:#f9fafb;font-family:Menlo,Consolas,monospace;font-size:14px;line-height:1.5;"),
+    ("blockquote", "margin:0 0 16px;padding-left:12px;border-left:3px solid #d1d5db;color:#6b7280;"),
+    ("table", "border-collapse:collapse;margin:0 0 16px;"),
+    ("th", "border:1px solid #e5e7eb;padding:6px 10px;text-align:left;"),
+    ("td", "border:1px solid #e5e7eb;padding:6px 10px;"),
+    ("img", "max-width:100%;height:auto;"),
+    ("hr", "border:none;border-top:1px solid #e5e7eb;margin:24px 0;"),
+];
+
+#[derive(Serialize, Debug)]
+struct EmailContext {
+    project_name: String,
+    version_tag: String,
+    name: Option<String>,
+    formatted_date: Option<String>,
+    /// The rendered release notes, with inlined styles
+    notes: String,
+    install_commands: Vec<EmailInstallCommand>,
+    downloads: Vec<EmailDownload>,
+    /// The release's page on GitHub
+    release_url: Option<String>,
+    homepage: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+struct EmailInstallCommand {
+    label: String,
+    command: String,
+}
+
+#[derive(Serialize, Debug)]
+struct EmailDownload {
+    name: String,
+    url: String,
+    platforms: Vec<String>,
+}
+
+/// Renders the announcement email for `release`
+pub fn render(release: &Release, config: &Config) -> Result<String> {
+    let notes = inline_styles(&absolute_links(&release_notes(release, config)?, config));
+
+    let mut install_commands = Vec::new();
+    let mut downloads = Vec::new();
+    for (_, installer) in release.artifacts.installers() {
+        if installer.display == DisplayPreference::Hidden {
+            continue;
+        }
+        match &installer.method {
+            InstallMethod::Run { run_hint, .. } => install_commands.push(EmailInstallCommand {
+                label: installer.label.clone(),
+                command: run_hint.clone(),
+            }),
+            InstallMethod::Download { file } => {
+                let file = release.artifacts.file(*file);
+                if file.unavailable {
+                    continue;
+                }
+                let mut platforms = installer
+                    .targets
+                    .keys()
+                    .map(|target| target_label(target, config.components.artifacts.as_ref()))
+                    .collect::<Vec<_>>();
+                platforms.sort();
+                downloads.push(EmailDownload {
+                    name: file.name.clone(),
+                    url: file.download_url.clone(),
+                    platforms,
+                });
+            }
+        }
+    }
+    downloads.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let context = EmailContext {
+        project_name: config.project.name.clone(),
+        version_tag: release.source.version_tag().to_owned(),
+        name: release.source.name().map(|s| s.to_owned()),
+        formatted_date: release.source.formatted_date(),
+        notes,
+        install_commands,
+        downloads,
+        release_url: release.source.html_url().map(|s| s.to_owned()),
+        homepage: config.project.homepage.clone(),
+    };
+
+    let mut env = Environment::new();
+    env.add_template("email.html", EMAIL_TEMPLATE)?;
+    Ok(env.get_template("email.html")?.render(context)?)
+}
+
+/// Resolves every `href` and `src` in `html` against the site's URL with
+/// [`link::make_absolute`][], since relative links mean nothing in someone's inbox
+pub fn absolute_links(html: &str, config: &Config) -> String {
+    let mut output = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = [" href=\"", " src=\""]
+        .iter()
+        .filter_map(|attr| rest.find(attr).map(|i| i + attr.len()))
+        .min()
+    {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest.find('"').unwrap_or(rest.len());
+        let url = &rest[..end];
+        // Leave full URLs (and `mailto:`s) exactly as they were written
+        let has_scheme = url.split('/').next().is_some_and(|part| part.contains(':'));
+        if has_scheme || url.starts_with('#') {
+            output.push_str(url);
+        } else {
+            output.push_str(&link::make_absolute(config, url));
+        }
+        rest = &rest[end..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Adds a `style` attribute to every tag in `html` that we have a style for in [`STYLES`][].
+/// Tags that are already styled (like syntax highlighted code blocks) are left alone.
+pub fn inline_styles(html: &str) -> String {
+    let mut output = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let name_end = rest[1..]
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .map_or(rest.len(), |i| i + 1);
+        let tag_end = rest.find('>').map_or(rest.len(), |i| i + 1);
+        let name = &rest[1..name_end];
+        let tag = &rest[..tag_end];
+        match STYLES
+            .iter()
+            .find(|(element, _)| element.eq_ignore_ascii_case(name))
+        {
+            Some((_, style)) if !tag.contains("style=") => {
+                output.push_str(&rest[..name_end]);
+                output.push_str(&format!(" style=\"{style}\""));
+                output.push_str(&rest[name_end..tag_end]);
+            }
+            _ => output.push_str(tag),
+        }
+        rest = &rest[tag_end..];
+    }
+    output.push_str(rest);
+    output
+}
@@ -0,0 +1,53 @@
+//! Exporting parts of a site for use elsewhere, like release announcements for a newsletter.
+//!
+//! Unlike the site itself, exports are rendered for a single release and are meant to be
+//! copy-pasted or sent as-is. Relative links wouldn't work there, so emails resolve them against
+//! the site's URL (when we know it) and announcements drop them.
+
+use axoproject::WorkspaceInfo;
+
+use crate::config::{AxoprojectLayer, Config};
+use crate::data::Release;
+use crate::errors::*;
+use crate::site::changelog;
+use crate::site::Site;
+
+pub mod announcement;
+pub mod email;
+pub mod pdf;
+
+/// Fetches the project's releases and finds the one tagged `version`, or the latest release
+/// if no version is given. `v1.2.3` and `1.2.3` are treated as the same version.
+pub fn find_release(config: &Config, version: Option<&str>) -> Result<Release> {
+    let context = Site::build_context(config)?;
+    let release = match version {
+        Some(version) => context.releases.iter().find(|release| {
+            let tag = release.source.version_tag();
+            tag == version || tag.trim_start_matches('v') == version.trim_start_matches('v')
+        }),
+        None => context.latest(),
+    };
+    release
+        .filter(|release| !release.source.is_current_state())
+        .cloned()
+        .ok_or_else(|| OrandaError::ReleaseNotFound {
+            version: version.unwrap_or("latest").to_owned(),
+        })
+}
+
+/// Renders a release's notes to HTML, the same way its changelog page does
+fn release_notes(release: &Release, config: &Config) -> Result<String> {
+    let project = project_in(config);
+    Ok(changelog::single_context(release, None, config, project.as_ref()).body)
+}
+
+/// A release's notes as markdown, from the same place its changelog page gets them
+fn release_notes_markdown(release: &Release, config: &Config) -> Result<String> {
+    let project = project_in(config);
+    changelog::release_markdown(project.as_ref(), release, config)
+}
+
+/// The project in the config's root directory, which the release notes come from
+fn project_in(config: &Config) -> Option<WorkspaceInfo> {
+    AxoprojectLayer::get_best_workspace(&config.root_dir)
+}
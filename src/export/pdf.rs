@@ -0,0 +1,187 @@
+//! Printing pages of the site to PDF, for teams that need release notes as documents.
+//!
+//! Laying out HTML is a browser's job, so we build the site, serve it on a random local port
+//! and have a headless Chrome (or anything else built on Chromium) print the page, using the
+//! site's print stylesheet.
+
+use std::net::{SocketAddr, TcpListener};
+use std::process::Command;
+use std::thread;
+
+use axum::http::StatusCode;
+use axum::routing::get_service;
+use axum::Router;
+use camino::{Utf8Path, Utf8PathBuf};
+use tokio::sync::oneshot;
+use tower_http::services::ServeDir;
+
+use crate::config::Config;
+use crate::errors::*;
+use crate::site::Site;
+
+/// Set this to the browser to print with, if it's not somewhere we look
+pub const BROWSER_ENV: &str = "ORANDA_BROWSER";
+
+/// Chromium-based browsers we look for in `PATH`
+const BROWSER_NAMES: &[&str] = &[
+    "chromium",
+    "chromium-browser",
+    "google-chrome",
+    "google-chrome-stable",
+    "chrome",
+    "microsoft-edge",
+    "msedge",
+];
+
+/// Where browsers are installed on systems that don't put them in `PATH`
+const BROWSER_PATHS: &[&str] = &[
+    "/Applications/Google Chrome.app/Contents/MacOS/Google Chrome",
+    "/Applications/Chromium.app/Contents/MacOS/Chromium",
+    "/Applications/Microsoft Edge.app/Contents/MacOS/Microsoft Edge",
+    r"C:\Program Files\Google\Chrome\Application\chrome.exe",
+    r"C:\Program Files (x86)\Google\Chrome\Application\chrome.exe",
+    r"C:\Program Files (x86)\Microsoft\Edge\Application\msedge.exe",
+];
+
+/// How long the browser gets to load fonts and run scripts before printing, in milliseconds
+const LOAD_BUDGET_MS: u32 = 10_000;
+
+/// Builds the site, then prints `page` to a PDF at `output`
+pub fn export(config: &Config, page: &str, output: &Utf8Path) -> Result<()> {
+    let browser = find_browser().ok_or(OrandaError::BrowserNotFound)?;
+    Site::build_single_for_dist(config, None, None)?.write(Some(config))?;
+
+    let dist_dir = Utf8PathBuf::from(&config.build.dist_dir);
+    let filename = page_filename(&dist_dir, page);
+    if !dist_dir.join(&filename).is_file() {
+        return Err(OrandaError::PathDoesNotExist {
+            path: dist_dir.join(&filename).to_string(),
+        });
+    }
+    // Browsers don't always resolve relative paths against the dir they were started in
+    let output = config.path(output.as_str());
+
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    let (shutdown, shutdown_rx) = oneshot::channel();
+    let server = {
+        let dist_dir = dist_dir.clone();
+        let path_prefix = config.build.path_prefix.clone();
+        thread::spawn(move || serve(listener, &dist_dir, &path_prefix, shutdown_rx))
+    };
+
+    let url = page_url(addr, &config.build.path_prefix, &filename);
+    tracing::info!("Printing {url}...");
+    let printed = print(&browser, &url, &output).map_err(|details| OrandaError::PdfExportFailed {
+        page: page.to_owned(),
+        details,
+    });
+    let _ = shutdown.send(());
+    server.join().expect("PDF server thread panicked")?;
+    printed
+}
+
+/// The browser to print with: `ORANDA_BROWSER`, or the first Chromium-based browser we find
+pub fn find_browser() -> Option<Utf8PathBuf> {
+    if let Ok(browser) = std::env::var(BROWSER_ENV) {
+        return Some(Utf8PathBuf::from(browser));
+    }
+    let exe_suffix = std::env::consts::EXE_SUFFIX;
+    let in_path = std::env::var_os("PATH").and_then(|paths| {
+        std::env::split_paths(&paths).find_map(|dir| {
+            BROWSER_NAMES.iter().find_map(|name| {
+                let path = dir.join(format!("{name}{exe_suffix}"));
+                path.is_file()
+                    .then(|| Utf8PathBuf::from_path_buf(path).ok())
+                    .flatten()
+            })
+        })
+    });
+    in_path.or_else(|| {
+        BROWSER_PATHS
+            .iter()
+            .map(Utf8PathBuf::from)
+            .find(|path| path.is_file())
+    })
+}
+
+/// The file in the dist dir that `page` refers to. Pages can be given like their links
+/// (`changelog`, `/changelog/`, `docs/setup.html`), and the empty page is the index.
+pub fn page_filename(dist_dir: &Utf8Path, page: &str) -> String {
+    let page = page.trim_matches('/');
+    if page.is_empty() {
+        "index.html".to_owned()
+    } else if page.ends_with(".html") {
+        page.to_owned()
+    } else if dist_dir.join(page).join("index.html").is_file() {
+        format!("{page}/index.html")
+    } else {
+        format!("{page}.html")
+    }
+}
+
+/// Where the local server has a page
+pub fn page_url(addr: SocketAddr, path_prefix: &Option<String>, filename: &str) -> String {
+    match path_prefix {
+        Some(prefix) => format!("http://{addr}/{}/{filename}", prefix.trim_matches('/')),
+        None => format!("http://{addr}/{filename}"),
+    }
+}
+
+/// What we tell the browser to print `url` with. Chrome changed the name of the flag to leave
+/// out the date and URL in the margins, so we pass both; it ignores the one it doesn't know.
+pub fn browser_args(url: &str, output: &Utf8Path) -> Vec<String> {
+    vec![
+        "--headless".to_owned(),
+        "--disable-gpu".to_owned(),
+        "--no-pdf-header-footer".to_owned(),
+        "--print-to-pdf-no-header".to_owned(),
+        format!("--virtual-time-budget={LOAD_BUDGET_MS}"),
+        format!("--print-to-pdf={output}"),
+        url.to_owned(),
+    ]
+}
+
+fn print(browser: &Utf8Path, url: &str, output: &Utf8Path) -> std::result::Result<(), String> {
+    let result = Command::new(browser)
+        .args(browser_args(url, output))
+        .output()
+        .map_err(|e| format!("couldn't run {browser}: {e}"))?;
+    if !result.status.success() {
+        return Err(String::from_utf8_lossy(&result.stderr).trim().to_owned());
+    }
+    if !output.is_file() {
+        return Err(format!("{browser} didn't write {output}"));
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn serve(
+    listener: TcpListener,
+    dist_dir: &Utf8Path,
+    path_prefix: &Option<String>,
+    shutdown: oneshot::Receiver<()>,
+) -> Result<()> {
+    let serve_dir =
+        get_service(ServeDir::new(dist_dir)).handle_error(|error: std::io::Error| async move {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Unhandled internal error: {}", error),
+            )
+        });
+    let prefix_route = match path_prefix {
+        Some(prefix) => format!("/{}", prefix.trim_matches('/')),
+        None => "/".to_owned(),
+    };
+    let app = Router::new().nest_service(&prefix_route, serve_dir);
+    axum::Server::from_tcp(listener)
+        .map_err(std::io::Error::other)?
+        .serve(app.into_make_service())
+        .with_graceful_shutdown(async {
+            let _ = shutdown.await;
+        })
+        .await
+        .map_err(std::io::Error::other)?;
+    Ok(())
+}
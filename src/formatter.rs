@@ -12,6 +12,8 @@ use tracing_subscriber::layer::Context;
 use tracing_subscriber::registry::LookupSpan;
 use tracing_subscriber::Layer;
 
+use crate::diagnostics::{self, MessageFormat};
+
 /// Our custom `FormatEvent` implementation.
 pub struct OrandaFormatter;
 
@@ -87,6 +89,10 @@ where
                     arrow_icon,
                     style.apply_to(message.message)
                 )
+            } else if matches!(metadata.level(), &Level::WARN)
+                && diagnostics::message_format() == MessageFormat::Short
+            {
+                format!("oranda: warning: {}", message.message)
             } else if matches!(metadata.level(), &Level::WARN) {
                 let style = Style::new().bold().fg(Yellow);
                 format!(
@@ -3,8 +3,15 @@
 
 pub mod config;
 pub mod data;
+pub mod deploy;
+pub mod dev_status;
+pub mod diagnostics;
+pub mod doctor;
 pub mod errors;
+pub mod export;
 pub mod formatter;
 pub mod generate;
 pub mod paths;
+pub mod report;
 pub mod site;
+pub mod tls;
@@ -2,12 +2,20 @@
 
 use clap::{Parser, Subcommand};
 use miette::Report;
+use oranda::config::overrides::{self, ConfigOverride};
+use oranda::config::ConfigOptions;
+use oranda::diagnostics::{self, MessageFormat};
+use oranda::errors::OrandaError;
 use tracing::subscriber::set_default;
 use tracing::Level;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
 use tracing_subscriber::layer::SubscriberExt;
 
 mod commands;
-use commands::{Build, ConfigSchema, Dev, GenerateCss, Serve};
+use commands::{
+    Build, BuildMany, ConfigSchema, Debug, Deploy, Dev, Doctor, Export, GenerateCss, Org, Serve,
+    Theme,
+};
 
 pub mod formatter;
 use crate::commands::Generate;
@@ -29,24 +37,79 @@ struct Cli {
     #[clap(default_value_t = OutputFormat::Human)]
     #[clap(help_heading = "GLOBAL OPTIONS", global = true)]
     pub output_format: OutputFormat,
+
+    /// How to print errors and warnings. `short` prints each on one line, as
+    /// `file:line:col: severity: message`, for editors and CI annotations
+    #[clap(long, value_enum)]
+    #[clap(default_value_t = MessageFormat::Human)]
+    #[clap(help_heading = "GLOBAL OPTIONS", global = true)]
+    pub message_format: MessageFormat,
+
+    /// Override a config value, like `--set build.path_prefix=preview/pr-42`. Values are read
+    /// as JSON when the key takes something other than a string. Can be given more than once,
+    /// and wins over `ORANDA_*` environment variables like `ORANDA_BUILD__PATH_PREFIX`.
+    #[clap(long = "set", value_name = "KEY=VALUE")]
+    #[clap(help_heading = "GLOBAL OPTIONS", global = true)]
+    pub overrides: Vec<ConfigOverride>,
+
+    /// Use the config values from this profile in the oranda.json's `profiles`, like
+    /// `--profile production`, on top of the rest of the file.
+    #[clap(long, value_name = "NAME")]
+    #[clap(help_heading = "GLOBAL OPTIONS", global = true)]
+    pub profile: Option<String>,
+
+    /// Build a preview of the site, like `--preview pr-42`, under its own path prefix, with a
+    /// banner, without analytics, and hidden from search engines. Same as
+    /// `--set build.preview=pr-42`.
+    #[clap(long, value_name = "NAME")]
+    #[clap(help_heading = "GLOBAL OPTIONS", global = true)]
+    pub preview: Option<String>,
 }
 
 #[derive(Subcommand, Debug)]
 enum Command {
     /// Build an oranda site.
     Build(Build),
+    /// Build several unrelated projects' sites into one directory, as listed in a manifest.
+    BuildMany(BuildMany),
+    /// Build a portal listing the public projects of a GitHub organization.
+    Org(Org),
     /// Start a local development server that recompiles your oranda site if a file changes.
     Dev(Dev),
     /// Start a file server to access your oranda site in a browser.
     Serve(Serve),
+    /// Upload a built site to an S3 bucket, or other S3-compatible object storage.
+    Deploy(Deploy),
     /// Generate infrastructure files for oranda sites.
     Generate(Generate),
+    /// Export parts of your site for use elsewhere, like release announcements.
+    Export(Export),
+    /// Work on themes and template overrides.
+    Theme(Theme),
+    /// Check your config and environment for problems before you build.
+    Doctor(Doctor),
+    /// Inspect what oranda sees when it builds your site.
+    Debug(Debug),
     #[clap(hide = true)]
     ConfigSchema(ConfigSchema),
     #[clap(hide = true)]
     GenerateCss(GenerateCss),
 }
 
+/// Logs go to stdout, unless the command prints something there that's meant for a machine
+fn log_writer(command: &Command) -> BoxMakeWriter {
+    let stdout_is_output = match command {
+        Command::Build(cmd) => cmd.reports_to_stdout(),
+        Command::Debug(_) => true,
+        _ => false,
+    };
+    if stdout_is_output {
+        BoxMakeWriter::new(std::io::stderr)
+    } else {
+        BoxMakeWriter::new(std::io::stdout)
+    }
+}
+
 fn main() {
     let cli = Cli::parse();
 
@@ -71,17 +134,57 @@ fn run(cli: &axocli::CliApp<Cli>) -> Result<(), Report> {
     let sub_filter = tracing_subscriber::filter::Targets::new().with_target("oranda", log_level);
     let sub = tracing_subscriber::registry()
         .with(formatter::CaptureFieldsLayer)
-        .with(tracing_subscriber::fmt::layer().event_format(formatter::OrandaFormatter))
+        .with(
+            tracing_subscriber::fmt::layer()
+                .event_format(formatter::OrandaFormatter)
+                .with_writer(log_writer(&cli.config.command)),
+        )
         .with(sub_filter);
     let _sub_guard = set_default(sub);
+    diagnostics::set_message_format(cli.config.message_format);
+    let preview = cli.config.preview.iter().map(|name| ConfigOverride {
+        key: vec!["build".to_owned(), "preview".to_owned()],
+        value: name.clone(),
+    });
+    // The environment comes first, so that flags win over it. It's only read here, so that
+    // the configs oranda builds as a library don't depend on it.
+    let env = overrides::from_env_vars(std::env::vars());
+    let options = ConfigOptions {
+        profile: cli.config.profile.clone(),
+        overrides: env
+            .into_iter()
+            .chain(preview)
+            .chain(cli.config.overrides.iter().cloned())
+            .collect(),
+    };
+
+    let result = run_command(&cli.config.command, &options);
+    if let (MessageFormat::Short, Err(report)) = (cli.config.message_format, &result) {
+        // axocli would draw the error with miette, so we print it ourselves and bail
+        match report.downcast_ref::<OrandaError>() {
+            Some(error) => eprintln!("{}", diagnostics::short_message(error)),
+            None => eprintln!("oranda: error: {report}"),
+        }
+        std::process::exit(-1);
+    }
+    result
+}
 
-    match &cli.config.command {
-        Command::Build(cmd) => cmd.run()?,
-        Command::Dev(cmd) => cmd.clone().run()?,
-        Command::Serve(cmd) => cmd.run()?,
+fn run_command(command: &Command, options: &ConfigOptions) -> Result<(), Report> {
+    match command {
+        Command::Build(cmd) => cmd.run(options)?,
+        Command::BuildMany(cmd) => cmd.run(options)?,
+        Command::Org(cmd) => cmd.run(options)?,
+        Command::Dev(cmd) => cmd.clone().run(options)?,
+        Command::Serve(cmd) => cmd.run(options)?,
+        Command::Deploy(cmd) => cmd.run(options)?,
         Command::ConfigSchema(cmd) => cmd.run()?,
         Command::GenerateCss(cmd) => cmd.run()?,
         Command::Generate(cmd) => cmd.run()?,
+        Command::Export(cmd) => cmd.run(options)?,
+        Command::Theme(cmd) => cmd.run()?,
+        Command::Doctor(cmd) => cmd.run(options)?,
+        Command::Debug(cmd) => cmd.run(options)?,
     };
     Ok(())
 }
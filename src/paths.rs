@@ -2,6 +2,16 @@ use crate::errors;
 use crate::errors::OrandaError;
 use camino::{Utf8Path, Utf8PathBuf};
 
+/// Resolves a path from the configuration that can also be a URL, like a logo or a stylesheet,
+/// against `root`. URLs are returned as they are.
+pub fn resolve_asset(root: &Utf8Path, path: &str) -> String {
+    if path.contains("://") {
+        path.to_owned()
+    } else {
+        root.join(path).into_string()
+    }
+}
+
 /// Creates a workspace-safe relative path. Takes the following arguments:
 /// - The root path of the workspace (or single project)
 /// - An optional workspace member path
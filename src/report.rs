@@ -0,0 +1,125 @@
+//! The summary `oranda build --report json` prints, for CI pipelines and dashboards.
+//!
+//! The parts of the build that know something worth reporting record it here as they go,
+//! rather than everything having to pass it back up to the command. Nothing is recorded unless
+//! the command [`start`][]s a report, and [`build_report`][] hands back what was recorded and
+//! stops, so builds that don't report, like the ones `oranda dev` and library users run, don't
+//! pile up warnings here.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use camino::Utf8Path;
+use serde::Serialize;
+
+use crate::errors::*;
+
+static RECORDED: Mutex<Option<Recorded>> = Mutex::new(None);
+
+#[derive(Default)]
+struct Recorded {
+    components: Vec<&'static str>,
+    releases: usize,
+    warnings: Vec<String>,
+}
+
+/// Formats the build report can be written in
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    Json,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct BuildReport {
+    /// The version of oranda that built the site
+    pub oranda_version: &'static str,
+    pub dist_dir: String,
+    pub duration_ms: u64,
+    /// The HTML pages written, as paths relative to `dist_dir`
+    pub pages: Vec<String>,
+    /// How many files were written, pages included
+    pub files_written: usize,
+    pub bytes_written: u64,
+    /// The components that were built, for every site in a workspace together
+    pub components: Vec<&'static str>,
+    /// How many releases were fetched, for every site in a workspace together
+    pub releases_fetched: usize,
+    /// The warnings printed during the build, one line each
+    pub warnings: Vec<String>,
+}
+
+/// Starts recording for the report of the build that's about to run, forgetting anything a
+/// build before it recorded
+pub fn start() {
+    *RECORDED.lock().unwrap() = Some(Recorded::default());
+}
+
+fn record(f: impl FnOnce(&mut Recorded)) {
+    if let Some(recorded) = RECORDED.lock().unwrap().as_mut() {
+        f(recorded);
+    }
+}
+
+pub fn record_components(components: &[&'static str]) {
+    record(|recorded| {
+        for component in components {
+            if !recorded.components.contains(component) {
+                recorded.components.push(component);
+            }
+        }
+    });
+}
+
+pub fn record_releases(count: usize) {
+    record(|recorded| recorded.releases += count);
+}
+
+pub fn record_warning(warning: String) {
+    record(|recorded| recorded.warnings.push(warning));
+}
+
+/// Puts together the report for a build that wrote to `dist_dir` and took `duration`, with what
+/// was recorded since [`start`][], and stops recording
+pub fn build_report(dist_dir: &Utf8Path, duration: Duration) -> Result<BuildReport> {
+    let mut pages = vec![];
+    let mut files_written = 0;
+    let mut bytes_written = 0;
+    if dist_dir.exists() {
+        let mut dirs = vec![dist_dir.to_owned()];
+        while let Some(dir) = dirs.pop() {
+            for entry in dir.read_dir_utf8()? {
+                let entry = entry?;
+                let metadata = entry.metadata()?;
+                if metadata.is_dir() {
+                    dirs.push(entry.path().to_owned());
+                    continue;
+                }
+                files_written += 1;
+                bytes_written += metadata.len();
+                if entry.path().extension() == Some("html") {
+                    let page = entry
+                        .path()
+                        .strip_prefix(dist_dir)
+                        .unwrap_or(entry.path())
+                        .as_str()
+                        .replace('\\', "/");
+                    pages.push(page);
+                }
+            }
+        }
+    }
+    pages.sort();
+
+    let recorded = RECORDED.lock().unwrap().take().unwrap_or_default();
+    Ok(BuildReport {
+        oranda_version: env!("CARGO_PKG_VERSION"),
+        dist_dir: dist_dir.to_string(),
+        duration_ms: duration.as_millis() as u64,
+        pages,
+        files_written,
+        bytes_written,
+        components: recorded.components,
+        releases_fetched: recorded.releases,
+        warnings: recorded.warnings,
+    })
+}
@@ -0,0 +1,75 @@
+use axoproject::GithubRepo;
+use serde::Serialize;
+
+use crate::config::{ActivityConfig, Config};
+use crate::data::activity::{Discussion, ProjectActivity};
+
+#[derive(Serialize, Debug)]
+pub struct ActivityContext {
+    pub open_issues: Option<u64>,
+    pub issues_url: String,
+    /// How long new issues wait for a response, like "5 hours"
+    pub response_time: Option<String>,
+    /// How many issues `response_time` is an average of
+    pub response_time_issues: usize,
+    pub discussions: Vec<Discussion>,
+    pub discussions_url: Option<String>,
+}
+
+/// Fetches the project's activity for the activity widget. Returns None if there's nothing to
+/// show.
+pub fn context(activity_config: &ActivityConfig, config: &Config) -> Option<ActivityContext> {
+    let repo_url = config.project.repository.as_ref()?;
+    let repo = match GithubRepo::from_url(repo_url) {
+        Ok(repo) => repo,
+        Err(e) => {
+            tracing::warn!("Skipping project activity, your repository isn't on GitHub: {e}");
+            return None;
+        }
+    };
+    let activity = ProjectActivity::fetch(&config.root_dir, &repo, activity_config);
+    ActivityContext::new(&repo, activity)
+}
+
+impl ActivityContext {
+    /// Returns None if none of the activity could be fetched
+    pub fn new(repo: &GithubRepo, activity: ProjectActivity) -> Option<Self> {
+        if activity.open_issues.is_none()
+            && activity.response_time.is_none()
+            && activity.discussions.is_empty()
+        {
+            return None;
+        }
+        Some(Self {
+            open_issues: activity.open_issues,
+            issues_url: format!("https://github.com/{}/{}/issues", repo.owner, repo.name),
+            response_time: activity
+                .response_time
+                .map(|response_time| format_hours(response_time.hours)),
+            response_time_issues: activity
+                .response_time
+                .map(|response_time| response_time.issues)
+                .unwrap_or(0),
+            discussions: activity.discussions,
+            discussions_url: activity.discussions_url,
+        })
+    }
+}
+
+/// Rounds a number of hours to something a person would say, like "5 hours" or "3 days"
+pub fn format_hours(hours: f64) -> String {
+    let plural = |count: f64, unit: &str| {
+        if count == 1.0 {
+            format!("1 {unit}")
+        } else {
+            format!("{count} {unit}s")
+        }
+    };
+    if hours < 1.0 {
+        "under an hour".to_owned()
+    } else if hours < 48.0 {
+        plural(hours.round(), "hour")
+    } else {
+        plural((hours / 24.0).round(), "day")
+    }
+}
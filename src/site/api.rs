@@ -0,0 +1,101 @@
+//! Machine-readable versions of the data we gather while building a site, written as
+//! `api/*.json` when `build.emit_json` is enabled. This lets other tools (status dashboards,
+//! update checkers built into apps, ...) reuse what oranda already knows about a project.
+
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::data::Context;
+use crate::errors::*;
+use crate::site::artifacts::ArtifactsContext;
+use crate::site::funding::FundingContext;
+use crate::site::link;
+use crate::site::markdown::ReadingTime;
+use crate::site::page::Page;
+use crate::site::private;
+use crate::site::templates::Templates;
+use crate::site::widgets;
+
+#[derive(Serialize, Debug)]
+struct ApiRelease {
+    version_tag: String,
+    name: Option<String>,
+    date: Option<String>,
+    is_prerelease: bool,
+    /// The release notes as provided by the release source, in Markdown
+    body: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+struct ApiPage {
+    path: String,
+    /// Only set for pages rendered from Markdown
+    reading_time: Option<ReadingTime>,
+}
+
+/// Builds the `api/*.json` files for a site. `pages` are the HTML pages we're about to write.
+pub fn build_api_pages(
+    pages: &[Page],
+    config: &Config,
+    templates: &Templates,
+    context: Option<&Context>,
+    artifacts: Option<&ArtifactsContext>,
+    funding: Option<&FundingContext>,
+) -> Result<Vec<Page>> {
+    let path_prefix = templates.layout.path_prefix();
+    let releases: Vec<ApiRelease> = context
+        .map(|context| {
+            context
+                .releases
+                .iter()
+                .filter(|release| !release.source.is_current_state())
+                .map(|release| ApiRelease {
+                    version_tag: release.source.version_tag().to_owned(),
+                    name: release.source.name().map(|s| s.to_owned()),
+                    date: release.source.date().map(|s| s.to_owned()),
+                    is_prerelease: release.source.is_prerelease(),
+                    body: release.source.body().map(|s| s.to_owned()),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let site_pages: Vec<ApiPage> = pages
+        .iter()
+        .filter(|page| page.filename.ends_with(".html") && !widgets::is_widget(&page.filename))
+        .filter(|page| !private::is_private_output(&page.filename, config))
+        .map(|page| {
+            let pretty_path = link::pretty_path(&page.filename);
+            let mut path = link::generate_relative(path_prefix, &pretty_path);
+            // The index page doesn't get a trailing slash from `generate_relative`
+            if pretty_path.is_empty() {
+                path.push('/');
+            }
+            ApiPage {
+                path,
+                reading_time: page.reading_time,
+            }
+        })
+        .collect();
+
+    let mut api_pages = vec![
+        json_page("releases", &releases)?,
+        json_page("pages", &site_pages)?,
+        json_page("nav", templates.layout.nav_links())?,
+    ];
+    if let Some(artifacts) = artifacts {
+        api_pages.push(json_page("artifacts", &artifacts.to_schema())?);
+    }
+    if let Some(funding) = funding {
+        api_pages.push(json_page("funding", funding)?);
+    }
+    Ok(api_pages)
+}
+
+fn json_page<T: Serialize + ?Sized>(name: &str, data: &T) -> Result<Page> {
+    Ok(Page {
+        contents: serde_json::to_string_pretty(data)?,
+        filename: format!("api/{name}.json"),
+        reading_time: None,
+        stale: None,
+    })
+}
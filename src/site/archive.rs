@@ -0,0 +1,159 @@
+//! Packing a built site into a single file, for `oranda build --archive`.
+//!
+//! Besides the site, every archive has an `oranda-manifest.json` at its root, listing each file
+//! with its size and SHA-256 hash, so whoever unpacks it can check that nothing went missing or
+//! got changed along the way.
+
+use std::io::Write;
+
+use axoasset::LocalAsset;
+use camino::{Utf8Path, Utf8PathBuf};
+use flate2::write::GzEncoder;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::errors::*;
+use crate::site::writer::{self, MemoryWriter, SiteWriter};
+
+/// The name of the manifest in the archive
+pub const MANIFEST_NAME: &str = "oranda-manifest.json";
+
+/// The kinds of archive oranda can make
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    TarGz,
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// The format an archive at `path` should be in, going by its extension
+    pub fn from_path(path: &Utf8Path) -> Result<Self> {
+        let name = path.file_name().unwrap_or_default();
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Ok(Self::TarGz)
+        } else if name.ends_with(".zip") {
+            Ok(Self::Zip)
+        } else {
+            Err(OrandaError::UnknownArchiveFormat {
+                path: path.to_string(),
+            })
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveManifest {
+    /// The version of oranda that made the archive
+    pub oranda_version: &'static str,
+    /// Every file in the archive except the manifest, sorted by path
+    pub files: Vec<ArchivedFile>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ArchivedFile {
+    /// The path of the file in the archive, with forward slashes
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// Collects a site's files, and packs them into an archive once they're all there
+#[derive(Debug, Clone, Default)]
+pub struct ArchiveWriter {
+    files: MemoryWriter,
+}
+
+impl ArchiveWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The manifest of the files collected so far
+    pub fn manifest(&self) -> ArchiveManifest {
+        let files = self
+            .files
+            .files
+            .iter()
+            .map(|(path, contents)| ArchivedFile {
+                path: path.as_str().replace('\\', "/"),
+                size: contents.len() as u64,
+                sha256: format!("{:x}", Sha256::digest(contents)),
+            })
+            .collect();
+        ArchiveManifest {
+            oranda_version: env!("CARGO_PKG_VERSION"),
+            files,
+        }
+    }
+
+    /// Writes the archive to `path`, in the format its extension asks for
+    pub fn finish(self, path: &Utf8Path) -> Result<ArchiveManifest> {
+        let format = ArchiveFormat::from_path(path)?;
+        let manifest = self.manifest();
+        let manifest_json = format!("{}\n", serde_json::to_string_pretty(&manifest)?);
+        let mut entries: Vec<(String, &[u8])> = manifest
+            .files
+            .iter()
+            .zip(self.files.files.values())
+            .map(|(file, contents)| (file.path.clone(), contents.as_slice()))
+            .collect();
+        entries.push((MANIFEST_NAME.to_owned(), manifest_json.as_bytes()));
+
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = std::fs::File::create(path)?;
+        let failed = |details: String| OrandaError::ArchiveFailed {
+            path: path.to_string(),
+            details,
+        };
+        match format {
+            ArchiveFormat::TarGz => {
+                let mut tar = tar::Builder::new(GzEncoder::new(file, Default::default()));
+                for (name, contents) in entries {
+                    let mut header = tar::Header::new_gnu();
+                    header.set_size(contents.len() as u64);
+                    header.set_mode(0o644);
+                    header.set_cksum();
+                    tar.append_data(&mut header, name, contents)
+                        .map_err(|e| failed(e.to_string()))?;
+                }
+                tar.into_inner()
+                    .and_then(|gz| gz.finish())
+                    .map_err(|e| failed(e.to_string()))?;
+            }
+            ArchiveFormat::Zip => {
+                let mut zip = zip::ZipWriter::new(file);
+                let options = zip::write::FileOptions::default()
+                    .compression_method(zip::CompressionMethod::Deflated);
+                for (name, contents) in entries {
+                    zip.start_file(name, options)
+                        .map_err(|e| failed(e.to_string()))?;
+                    zip.write_all(contents).map_err(|e| failed(e.to_string()))?;
+                }
+                zip.finish().map_err(|e| failed(e.to_string()))?;
+            }
+        }
+        Ok(manifest)
+    }
+}
+
+impl SiteWriter for ArchiveWriter {
+    fn write(&mut self, path: &Utf8Path, contents: &[u8]) -> Result<()> {
+        self.files.write(path, contents)
+    }
+}
+
+/// Packs everything in `dist_dir` into an archive at `path`. If the archive would end up in
+/// `dist_dir` itself, an older archive there isn't packed into the new one.
+pub fn archive_dir(dist_dir: &Utf8Path, path: &Utf8Path) -> Result<ArchiveManifest> {
+    let mut archive = ArchiveWriter::new();
+    let skip: Option<Utf8PathBuf> = path.strip_prefix(dist_dir).ok().map(|p| p.to_owned());
+    for file in writer::files_in(dist_dir)? {
+        if Some(&file) != skip.as_ref() {
+            let contents = LocalAsset::load_bytes(dist_dir.join(&file))?;
+            archive.write(&file, &contents)?;
+        }
+    }
+    archive.finish(path)
+}
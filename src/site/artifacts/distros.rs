@@ -0,0 +1,63 @@
+//! Packages in Linux distributions, from `components.artifacts.package_managers.distros`
+
+use serde::Serialize;
+
+use crate::config::{Distro, DistroPackage, PackageManagersConfig};
+
+#[derive(Serialize, Debug, Clone)]
+pub struct DistroContext {
+    /// Like "debian", for styling
+    id: String,
+    name: String,
+    logo: Option<String>,
+    /// The command(s) to install the package, one per line
+    command: String,
+}
+
+pub fn distros_context(config: &PackageManagersConfig) -> Vec<DistroContext> {
+    config
+        .distros
+        .iter()
+        .map(|(distro, package)| DistroContext {
+            id: distro.id().to_owned(),
+            name: distro.name().to_owned(),
+            logo: package.logo.clone(),
+            command: package
+                .command
+                .clone()
+                .unwrap_or_else(|| install_command(*distro, package)),
+        })
+        .collect()
+}
+
+/// The commands to install `package` on `distro`, including setting up its repository
+pub fn install_command(distro: Distro, package: &DistroPackage) -> String {
+    let name = &package.package;
+    let mut lines = vec![];
+    match distro {
+        Distro::Debian | Distro::Ubuntu => {
+            if let Some(repository) = &package.repository {
+                if let Some(key) = &package.key {
+                    lines.push(format!(
+                        "curl -fsSL {key} | sudo gpg --dearmor -o /usr/share/keyrings/{name}.gpg"
+                    ));
+                }
+                lines.push(format!(
+                    "echo \"{repository}\" | sudo tee /etc/apt/sources.list.d/{name}.list"
+                ));
+                lines.push("sudo apt update".to_owned());
+            }
+            lines.push(format!("sudo apt install {name}"));
+        }
+        Distro::Fedora => {
+            if let Some(copr) = &package.repository {
+                lines.push(format!("sudo dnf copr enable {copr}"));
+            }
+            lines.push(format!("sudo dnf install {name}"));
+        }
+        Distro::Arch => lines.push(format!("sudo pacman -S {name}")),
+        Distro::Aur => lines.push(format!("yay -S {name}")),
+        Distro::Nix => lines.push(format!("nix profile install nixpkgs#{name}")),
+    }
+    lines.join("\n")
+}
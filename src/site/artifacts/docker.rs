@@ -0,0 +1,57 @@
+//! The Docker section of the install page, from `components.docker`
+
+use serde::Serialize;
+
+use crate::config::DockerConfig;
+use crate::data::docker::fetch_tags;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct DockerContext {
+    /// The commands to pull and run the image, from the first registry
+    pull: String,
+    run: String,
+    registries: Vec<DockerRegistryContext>,
+    /// The configured tags, followed by any we fetched from the registry
+    tags: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct DockerRegistryContext {
+    name: String,
+    /// The image as it's pulled from this registry, like "ghcr.io/axodotdev/oranda"
+    reference: String,
+    url: String,
+}
+
+pub fn docker_context(config: &DockerConfig) -> DockerContext {
+    let mut tags = config.tags.clone();
+    if config.fetch_tags {
+        for tag in fetch_tags(config) {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+    }
+    let registries = config
+        .registries
+        .iter()
+        .map(|registry| DockerRegistryContext {
+            name: registry.name().to_owned(),
+            reference: registry.reference(&config.image),
+            url: registry.url(&config.image),
+        })
+        .collect::<Vec<_>>();
+    // Validation makes sure there's at least one registry and tag
+    let image = format!("{}:{}", registries[0].reference, config.tags[0]);
+    let run = if config.run_args.trim().is_empty() {
+        format!("docker run {image}")
+    } else {
+        format!("docker run {} {image}", config.run_args.trim())
+    };
+    DockerContext {
+        pull: format!("docker pull {image}"),
+        run,
+        registries,
+        tags,
+    }
+}
@@ -1,16 +1,28 @@
-use axoasset::LocalAsset;
-use axoproject::platforms::triple_to_display_name;
-use camino::Utf8PathBuf;
+use camino::Utf8Path;
 use std::collections::BTreeMap;
 
-use crate::config::Config;
-use crate::data::artifacts::{File, FileIdx, InstallMethod, InstallerIdx, TargetTriple};
+use crate::config::{ArtifactsConfig, Config};
+use crate::data::artifacts::{
+    target_label, File, FileIdx, InstallMethod, InstallerIdx, TargetTriple,
+};
+use crate::data::artifacts_schema::{
+    ArtifactsJson, PlatformJson, ReleaseJson, ARTIFACTS_SCHEMA_VERSION,
+};
 use crate::data::{Context, Release};
 use crate::errors::*;
 
-use crate::site::javascript;
+use crate::site::writer::SiteWriter;
+use crate::site::{javascript, link};
 use serde::Serialize;
 
+mod distros;
+mod docker;
+pub mod qr;
+mod script;
+pub use distros::{distros_context, install_command, DistroContext};
+pub use docker::{docker_context, DockerContext};
+pub use script::{script_pages, ScriptContext};
+
 /// A list of downloadable files.
 ///
 /// The inner Vec is a list of supported platforms (display name).
@@ -18,21 +30,49 @@ type DownloadableFiles = Vec<(FileIdx, File, Vec<String>)>;
 /// A map from TargetTriples to Installers that support that platform
 type Platforms = BTreeMap<TargetTriple, Vec<InstallerIdx>>;
 
-/// Current version of the JSON artifacts format
+/// The version of oranda writing `artifacts.json`, kept around as `format_version`
 const JSON_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// A transparent 1x1 GIF, served for `components.artifacts.install_counter`
+const COUNTER_PIXEL: &[u8] = &[
+    0x47, 0x49, 0x46, 0x38, 0x39, 0x61, 0x01, 0x00, 0x01, 0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0xff, 0xff, 0xff, 0x21, 0xf9, 0x04, 0x01, 0x00, 0x00, 0x00, 0x00, 0x2c, 0x00, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x01, 0x00, 0x00, 0x02, 0x02, 0x44, 0x01, 0x00, 0x3b,
+];
+
 #[derive(Serialize, Debug, Clone)]
 pub struct Platform {
     target: Vec<TargetTriple>,
     display_name: String,
     installers: Vec<InstallerIdx>,
+    /// Link to this platform's install counter image, if enabled
+    counter_pixel: Option<String>,
 }
 
-#[derive(Serialize, Debug)]
-pub struct ArtifactsJson {
-    pub format_version: String,
-    #[serde(flatten)]
-    pub inner: ArtifactsContext,
+/// The SBOMs of one release
+#[derive(Serialize, Debug, Clone)]
+pub struct SbomRelease {
+    tag: String,
+    sboms: Vec<SbomLink>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct SbomLink {
+    name: String,
+    url: String,
+    /// Something like "CycloneDX (XML)"
+    label: String,
+}
+
+/// The files of a release channel, like nightly builds, shown below the stable downloads
+#[derive(Serialize, Debug, Clone)]
+pub struct ChannelContext {
+    name: String,
+    description: Option<String>,
+    tag: String,
+    formatted_date: Option<String>,
+    downloadable_files: DownloadableFiles,
+    release: Release,
 }
 
 #[derive(Serialize, Debug, Clone)]
@@ -44,6 +84,57 @@ pub struct ArtifactsContext {
     release: Release,
     os_script: String,
     has_checksum_files: bool,
+    /// Whether any of the `downloadable_files` has a signature or provenance
+    has_attestations: bool,
+    has_download_counts: bool,
+    /// How many times the files of every release have been downloaded, if we know
+    total_downloads: Option<u64>,
+    /// `total_downloads`, shortened to something like "12.3k"
+    total_downloads_display: Option<String>,
+    /// Whether to show `total_downloads` on the index page
+    downloads_badge: bool,
+    /// The SBOMs of every release that has any, newest first
+    sbom_releases: Vec<SbomRelease>,
+    channels: Vec<ChannelContext>,
+    /// Packages in Linux distributions
+    distros: Vec<DistroContext>,
+    /// How to pull and run the project's Docker image
+    docker: Option<DockerContext>,
+    /// SVG QR codes for the files meant for phones, keyed by file name, if enabled
+    qr_codes: BTreeMap<String, String>,
+    /// An SVG QR code for the site, if enabled
+    site_qr_code: Option<String>,
+}
+
+impl ArtifactsContext {
+    /// Convert to the stable `artifacts.json` representation
+    pub fn to_schema(&self) -> ArtifactsJson {
+        ArtifactsJson {
+            schema_version: ARTIFACTS_SCHEMA_VERSION,
+            format_version: JSON_VERSION.to_string(),
+            tag: self.tag.clone(),
+            formatted_date: self.formatted_date.clone(),
+            platforms_with_downloads: self
+                .platforms_with_downloads
+                .iter()
+                .map(|platform| PlatformJson {
+                    target: platform.target.clone(),
+                    display_name: platform.display_name.clone(),
+                    installers: platform.installers.iter().map(|i| i.0).collect(),
+                })
+                .collect(),
+            downloadable_files: self
+                .downloadable_files
+                .iter()
+                .map(|(idx, file, platforms)| ((*idx).into(), file.to_schema(), platforms.clone()))
+                .collect(),
+            release: ReleaseJson {
+                artifacts: self.release.artifacts.to_schema(),
+            },
+            os_script: self.os_script.clone(),
+            has_checksum_files: self.has_checksum_files,
+        }
+    }
 }
 
 pub fn template_context(context: &Context, config: &Config) -> Result<Option<ArtifactsContext>> {
@@ -51,17 +142,90 @@ pub fn template_context(context: &Context, config: &Config) -> Result<Option<Art
         return Ok(None);
     };
     let os_script = javascript::build_os_script_path(&config.build.path_prefix);
+    let artifacts_config = config.components.artifacts.as_ref();
+    let install_counter = artifacts_config.is_some_and(|a| a.install_counter);
+    let downloads_badge = artifacts_config.is_some_and(|a| a.downloads_badge);
+    let qr_codes = artifacts_config.is_some_and(|a| a.qr_codes);
+    let version_tag = release.source.version_tag();
     let platforms_with_downloads = filter_platforms(release)
         .into_iter()
         .map(|(target, installers)| Platform {
-            display_name: triple_to_display_name(&target)
-                .map(|s| s.to_string())
-                .unwrap_or_else(|| target.clone()),
+            display_name: target_label(&target, artifacts_config),
+            counter_pixel: install_counter.then(|| {
+                link::generate_relative(
+                    &config.build.path_prefix,
+                    &counter_pixel_path(version_tag, &target),
+                )
+            }),
             target: vec![target],
             installers,
         })
         .collect::<Vec<_>>();
 
+    let downloadable_files = release_downloads(release, artifacts_config);
+
+    if downloadable_files.is_empty() {
+        tracing::warn!("You seem to have release automation set up, but we didn't detect any releases. The install page and associated widget will be empty. To disable this, set `artifacts: false`");
+    }
+    let has_checksum_files = downloadable_files
+        .iter()
+        .any(|(_, f, _)| f.checksum_file.is_some());
+    let has_attestations = downloadable_files
+        .iter()
+        .any(|(_, f, _)| !f.attestations.is_empty());
+    let has_download_counts = downloadable_files
+        .iter()
+        .any(|(_, f, _)| f.download_count.is_some());
+    let total_downloads = context
+        .releases
+        .iter()
+        .filter_map(|release| release.artifacts.download_count())
+        .reduce(|a, b| a + b);
+
+    Ok(Some(ArtifactsContext {
+        tag: release.source.version_tag().to_string(),
+        formatted_date: release.source.formatted_date(),
+        platforms_with_downloads,
+        release: release.to_owned(),
+        downloadable_files,
+        os_script,
+        has_checksum_files,
+        has_attestations,
+        has_download_counts,
+        total_downloads,
+        total_downloads_display: total_downloads.map(format_count),
+        downloads_badge,
+        sbom_releases: sbom_releases(context),
+        channels: context
+            .channels
+            .iter()
+            .map(|channel| ChannelContext {
+                name: channel.name.clone(),
+                description: channel.description.clone(),
+                tag: channel.release.source.version_tag().to_owned(),
+                formatted_date: channel.release.source.formatted_date(),
+                downloadable_files: release_downloads(&channel.release, artifacts_config),
+                release: channel.release.clone(),
+            })
+            .collect(),
+        distros: artifacts_config
+            .map(|a| distros_context(&a.package_managers))
+            .unwrap_or_default(),
+        docker: config.components.docker.as_ref().map(docker_context),
+        qr_codes: if qr_codes {
+            qr::file_codes(release, config)
+        } else {
+            BTreeMap::new()
+        },
+        site_qr_code: qr_codes.then(|| qr::site_code(config)).flatten(),
+    }))
+}
+
+/// The files of a release that can be downloaded, along with the platforms they're for
+fn release_downloads(
+    release: &Release,
+    artifacts_config: Option<&ArtifactsConfig>,
+) -> DownloadableFiles {
     let mut downloadable_files: Vec<_> = release
         .artifacts
         .installers()
@@ -69,52 +233,106 @@ pub fn template_context(context: &Context, config: &Config) -> Result<Option<Art
             let InstallMethod::Download { file } = installer.method else {
                 return None;
             };
+            if release.artifacts.file(file).unavailable {
+                return None;
+            }
             Some((
                 file,
                 release.artifacts.file(file).clone(),
                 installer
                     .targets
                     .keys()
-                    .map(|s| {
-                        triple_to_display_name(s)
-                            .map(|s| s.to_string())
-                            .unwrap_or_else(|| s.clone())
-                    })
+                    .map(|s| target_label(s, artifacts_config))
                     .collect::<Vec<_>>(),
             ))
         })
         .collect();
     downloadable_files.sort_by_key(|(_, f, _)| f.name.clone());
+    downloadable_files
+}
 
-    if downloadable_files.is_empty() {
-        tracing::warn!("You seem to have release automation set up, but we didn't detect any releases. The install page and associated widget will be empty. To disable this, set `artifacts: false`");
-    }
-    let has_checksum_files = downloadable_files
-        .iter()
-        .any(|(_, f, _)| f.checksum_file.is_some());
-
-    Ok(Some(ArtifactsContext {
-        tag: release.source.version_tag().to_string(),
-        formatted_date: release.source.formatted_date(),
-        platforms_with_downloads,
-        release: release.to_owned(),
-        downloadable_files,
-        os_script,
-        has_checksum_files,
-    }))
+/// Serves a release's files from the site, if `components.artifacts.mirror` is set
+pub fn mirror_release(
+    release: &mut Release,
+    config: &Config,
+    writer: &mut dyn SiteWriter,
+) -> Result<()> {
+    let Some(mirror) = config
+        .components
+        .artifacts
+        .as_ref()
+        .and_then(|a| a.mirror.as_ref())
+    else {
+        return Ok(());
+    };
+    let version = release.source.version_tag().to_owned();
+    release
+        .artifacts
+        .mirror_files(&version, mirror, config, writer)
 }
 
 /// Write a JSON file containing a representation of the template context, for external integration
-pub fn write_artifacts_json(config: &Config, context: &ArtifactsContext) -> Result<()> {
-    let cloned = (*context).clone();
-    let json_struct = ArtifactsJson {
-        format_version: JSON_VERSION.to_string(),
-        inner: cloned,
+pub fn write_artifacts_json(context: &ArtifactsContext, writer: &mut dyn SiteWriter) -> Result<()> {
+    let json_str = serde_json::to_string(&context.to_schema())?;
+    writer.write(Utf8Path::new("artifacts.json"), json_str.as_bytes())
+}
+
+fn sbom_releases(context: &Context) -> Vec<SbomRelease> {
+    context
+        .releases
+        .iter()
+        .filter(|release| !release.artifacts.sboms().is_empty())
+        .map(|release| SbomRelease {
+            tag: release.source.version_tag().to_owned(),
+            sboms: release
+                .artifacts
+                .sboms()
+                .iter()
+                .map(|sbom| {
+                    let file = release.artifacts.file(sbom.file);
+                    SbomLink {
+                        name: file.name.clone(),
+                        url: file.download_url.clone(),
+                        label: sbom.label.clone(),
+                    }
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Shortens a count to at most 3 significant digits, like "12.3k" or "4.5M"
+pub fn format_count(count: u64) -> String {
+    let (value, suffix) = match count {
+        0..=999 => return count.to_string(),
+        1_000..=999_999 => (count as f64 / 1_000.0, "k"),
+        1_000_000..=999_999_999 => (count as f64 / 1_000_000.0, "M"),
+        _ => (count as f64 / 1_000_000_000.0, "B"),
     };
-    let json_str = serde_json::to_string(&json_struct)?;
-    let mut path = Utf8PathBuf::from(config.build.dist_dir.clone());
-    path.push("artifacts.json");
-    LocalAsset::write_new_all(&json_str, path)?;
+    // Round down, so we never claim more downloads than there were
+    let formatted = if value < 100.0 {
+        format!("{:.1}", (value * 10.0).floor() / 10.0)
+    } else {
+        format!("{}", value.floor())
+    };
+    format!("{}{suffix}", formatted.trim_end_matches(".0"))
+}
+
+fn counter_pixel_path(version_tag: &str, target: &str) -> String {
+    format!("counter/{version_tag}/{target}.gif")
+}
+
+/// Write the install counter images for every platform in the install widget
+pub fn write_counter_pixels(context: &ArtifactsContext, writer: &mut dyn SiteWriter) -> Result<()> {
+    for platform in &context.platforms_with_downloads {
+        if platform.counter_pixel.is_none() {
+            continue;
+        }
+        for target in &platform.target {
+            let path = counter_pixel_path(&context.tag, target);
+            writer.write(Utf8Path::new(&path), COUNTER_PIXEL)?;
+        }
+    }
     Ok(())
 }
 
@@ -144,8 +362,12 @@ pub fn filter_platforms(release: &Release) -> Platforms {
         //
         // Eventually this should be replaced with a more robust notion of "nearby platforms"
         // as described in https://github.com/axodotdev/cargo-dist/issues/202
+        //
+        // A universal binary is better than that, and the install widget picks it on both.
         if let Some(entries) = platforms.get("x86_64-apple-darwin") {
-            if !platforms.contains_key("aarch64-apple-darwin") {
+            if !platforms.contains_key("aarch64-apple-darwin")
+                && !platforms.contains_key("universal2-apple-darwin")
+            {
                 let entries = entries.clone();
                 platforms.insert("aarch64-apple-darwin".to_owned(), entries);
             }
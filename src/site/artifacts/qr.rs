@@ -0,0 +1,97 @@
+//! QR codes for `components.artifacts.qr_codes`, so people can grab mobile builds with their
+//! phone's camera instead of typing out a link.
+
+use std::collections::BTreeMap;
+
+use qrcodegen::{QrCode, QrCodeEcc};
+use url::Url;
+
+use crate::config::Config;
+use crate::data::artifacts::InstallMethod;
+use crate::data::Release;
+
+/// Files that are installed on a phone or tablet, whatever they were built for
+const MOBILE_EXTS: &[&str] = &[".apk", ".xapk", ".ipa"];
+
+/// The blank space around a code, in modules. The spec asks for at least 4.
+const QUIET_ZONE: i32 = 4;
+
+/// Whether a file is meant for a phone or tablet, going by its name and the targets of the
+/// installers that download it
+pub fn is_mobile<'a>(name: &str, mut targets: impl Iterator<Item = &'a String>) -> bool {
+    MOBILE_EXTS.iter().any(|ext| name.ends_with(ext))
+        || targets.any(|target| target.contains("-android") || target.contains("-ios"))
+}
+
+/// QR codes for the mobile files of a release, keyed by file name
+pub fn file_codes(release: &Release, config: &Config) -> BTreeMap<String, String> {
+    let mut codes = BTreeMap::new();
+    for (_, installer) in release.artifacts.installers() {
+        let InstallMethod::Download { file } = installer.method else {
+            continue;
+        };
+        let file = release.artifacts.file(file);
+        if file.unavailable
+            || codes.contains_key(&file.name)
+            || !is_mobile(&file.name, installer.targets.keys())
+        {
+            continue;
+        }
+        let Some(url) = absolute_url(&file.download_url, config) else {
+            tracing::warn!(
+                "{} is served from your site, so it needs project.homepage set to get a QR code",
+                file.name
+            );
+            continue;
+        };
+        if let Some(code) = svg(&url, &format!("QR code for downloading {}", file.name)) {
+            codes.insert(file.name.clone(), code);
+        }
+    }
+    codes
+}
+
+/// A QR code for the site itself, if we know where it lives
+pub fn site_code(config: &Config) -> Option<String> {
+    let homepage = config.project.homepage.as_ref()?;
+    svg(homepage, &format!("QR code for {homepage}"))
+}
+
+/// Mirrored files have links relative to the site, which a phone can't do anything with
+fn absolute_url(url: &str, config: &Config) -> Option<String> {
+    if Url::parse(url).is_ok() {
+        return Some(url.to_owned());
+    }
+    let homepage = config.project.homepage.as_ref()?;
+    // Make sure the homepage is treated as a directory when joining
+    let base = Url::parse(&format!("{}/", homepage.trim_end_matches('/'))).ok()?;
+    let url = base.join(url.trim_start_matches('/')).ok()?;
+    Some(url.to_string())
+}
+
+/// Draws `text` as a QR code, as an SVG with one square per dark module
+pub fn svg(text: &str, label: &str) -> Option<String> {
+    let code = match QrCode::encode_text(text, QrCodeEcc::Medium) {
+        Ok(code) => code,
+        Err(e) => {
+            tracing::warn!("Couldn't make a QR code for {text}: {e}");
+            return None;
+        }
+    };
+    let size = code.size() + QUIET_ZONE * 2;
+    let mut path = String::new();
+    for y in 0..code.size() {
+        for x in 0..code.size() {
+            if code.get_module(x, y) {
+                path.push_str(&format!("M{},{}h1v1h-1z", x + QUIET_ZONE, y + QUIET_ZONE));
+            }
+        }
+    }
+    let label = label
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;");
+    Some(format!(
+        r##"<svg class="qr-code" xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {size} {size}" role="img" aria-label="{label}" shape-rendering="crispEdges"><rect width="100%" height="100%" fill="#fff"/><path d="{path}" fill="#000"/></svg>"##
+    ))
+}
@@ -0,0 +1,104 @@
+//! Pages showing the source of install scripts
+//!
+//! [`ReleaseArtifacts::make_scripts_viewable`][crate::data::artifacts::ReleaseArtifacts::make_scripts_viewable]
+//! copies the latest release's scripts onto the site as plain text. Here we also give each one a
+//! highlighted page with line anchors and its checksum, so people can read a script before piping
+//! it into their shell.
+
+use axoasset::LocalAsset;
+use camino::Utf8PathBuf;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::config::Config;
+use crate::data::artifacts::{FileIdx, InstallMethod};
+use crate::data::Release;
+use crate::errors::*;
+use crate::site::markdown::{syntax_highlight_lines, HighlightedLines};
+use crate::site::page::Page;
+use crate::site::templates::Templates;
+use crate::site::{javascript, link};
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ScriptContext {
+    name: String,
+    download_url: String,
+    /// Link to the plain text copy of the script on this site
+    raw_url: String,
+    /// The SHA-256 of the script we're showing
+    sha256: String,
+    /// Where the release's own checksum for the script can be downloaded, if it has one
+    checksum_url: Option<String>,
+    /// The command the install widget suggests for this script
+    run_hint: Option<String>,
+    /// The whole script, for the copy button
+    source: String,
+    code: HighlightedLines,
+    os_script: String,
+}
+
+/// Renders a page for each script that was made viewable, and points the script's `view_path`
+/// at it
+pub fn script_pages(
+    release: &mut Release,
+    templates: &Templates,
+    config: &Config,
+) -> Result<Vec<Page>> {
+    let mut pages = vec![];
+    for idx in release.artifacts.file_indices() {
+        let Some(raw_path) = release.artifacts.file(idx).view_path.clone() else {
+            continue;
+        };
+        let context = script_context(release, idx, &raw_path, config)?;
+        let filename = format!("{}.html", context.name);
+        pages.push(Page::new_from_template(
+            &filename,
+            templates,
+            "script.html",
+            &context,
+        )?);
+        release.artifacts.file_mut(idx).view_path = Some(filename);
+    }
+    Ok(pages)
+}
+
+fn script_context(
+    release: &Release,
+    idx: FileIdx,
+    raw_path: &str,
+    config: &Config,
+) -> Result<ScriptContext> {
+    let artifacts = &release.artifacts;
+    let file = artifacts.file(idx);
+    let source = LocalAsset::load_string(Utf8PathBuf::from(&config.build.dist_dir).join(raw_path))?;
+    // We don't have a PowerShell syntax, and would rather not warn about that for every build
+    let lang = if file.name.ends_with(".sh") {
+        "sh"
+    } else {
+        "text"
+    };
+    let code = syntax_highlight_lines(Some(lang), &source, &config.styles.syntax_theme)?;
+    let run_hint = artifacts
+        .installers()
+        .find_map(|(_, installer)| match &installer.method {
+            InstallMethod::Run {
+                file: Some(file),
+                run_hint,
+            } if *file == idx => Some(run_hint.clone()),
+            _ => None,
+        });
+
+    Ok(ScriptContext {
+        name: file.name.clone(),
+        download_url: file.download_url.clone(),
+        raw_url: link::generate_relative(&config.build.path_prefix, raw_path),
+        sha256: format!("{:x}", Sha256::digest(source.as_bytes())),
+        checksum_url: file
+            .checksum_file
+            .map(|checksum| artifacts.file(checksum).download_url.clone()),
+        run_hint,
+        source,
+        code,
+        os_script: javascript::build_os_script_path(&config.build.path_prefix),
+    })
+}
@@ -0,0 +1,224 @@
+//! Author bylines, and the `authors/` page listing everyone who has written a post or
+//! published a release.
+//!
+//! Names are resolved against the profiles in `components.authors.people`, so that a post can
+//! say `author: jdoe` (or `Jane Doe`, or their GitHub username) and still get the right name and
+//! avatar. Names without a profile are kept as-is.
+
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use crate::config::{AuthorProfile, Config};
+use crate::data::blog::slugify;
+use crate::data::github::GithubUser;
+use crate::data::Release;
+use crate::errors::*;
+use crate::site::link;
+use crate::site::page::Page;
+use crate::site::templates::Templates;
+
+/// How we credit somebody next to a post or release
+#[derive(Serialize, Debug, Clone)]
+pub struct Byline {
+    /// The anchor for this author on the authors page
+    pub id: String,
+    pub name: String,
+    pub avatar_url: Option<String>,
+    /// Where the byline links to, which is their entry on the authors page if it exists
+    pub link: Option<String>,
+}
+
+/// A post or release somebody is credited for
+#[derive(Serialize, Debug, Clone)]
+pub struct Credit {
+    pub title: String,
+    pub link: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct AuthorLink {
+    pub label: String,
+    pub url: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+struct AuthorEntry {
+    id: String,
+    name: String,
+    avatar_url: Option<String>,
+    bio: Option<String>,
+    links: Vec<AuthorLink>,
+    posts: Vec<Credit>,
+    releases: Vec<Credit>,
+}
+
+/// Resolves a name from a post's frontmatter to a byline
+pub fn byline(name: &str, config: &Config) -> Byline {
+    match find_profile(config, |id, profile| {
+        id.eq_ignore_ascii_case(name)
+            || profile.name.eq_ignore_ascii_case(name)
+            || profile
+                .github
+                .as_deref()
+                .is_some_and(|login| login.eq_ignore_ascii_case(name))
+    }) {
+        Some((id, profile)) => profile_byline(id, profile, config),
+        None => {
+            let id = slugify(name);
+            Byline {
+                link: authors_page_link(&id, config),
+                id,
+                name: name.to_owned(),
+                avatar_url: None,
+            }
+        }
+    }
+}
+
+/// The byline for whoever published a release on GitHub. Releases published by bots (like
+/// `github-actions[bot]`) aren't credited to anyone.
+///
+/// Only returns something if the authors component is enabled, since most projects don't
+/// care who pressed the button.
+pub fn release_byline(release: &Release, config: &Config) -> Option<Byline> {
+    config.components.authors.as_ref()?;
+    let user = release.source.author().filter(|user| !user.is_bot())?;
+    Some(github_byline(user, config))
+}
+
+fn github_byline(user: &GithubUser, config: &Config) -> Byline {
+    match find_profile(config, |_, profile| {
+        profile
+            .github
+            .as_deref()
+            .is_some_and(|login| login.eq_ignore_ascii_case(&user.login))
+    }) {
+        Some((id, profile)) => profile_byline(id, profile, config),
+        None => {
+            let id = slugify(&user.login);
+            Byline {
+                link: authors_page_link(&id, config),
+                id,
+                name: user.login.clone(),
+                avatar_url: Some(user.avatar_url.clone()),
+            }
+        }
+    }
+}
+
+fn find_profile(
+    config: &Config,
+    predicate: impl Fn(&str, &AuthorProfile) -> bool,
+) -> Option<(&str, &AuthorProfile)> {
+    let authors = config.components.authors.as_ref()?;
+    authors
+        .people
+        .iter()
+        .find(|(id, profile)| predicate(id, profile))
+        .map(|(id, profile)| (id.as_str(), profile))
+}
+
+fn profile_byline(id: &str, profile: &AuthorProfile, config: &Config) -> Byline {
+    let id = slugify(id);
+    Byline {
+        link: authors_page_link(&id, config),
+        id,
+        name: profile.name.clone(),
+        avatar_url: profile_avatar(profile),
+    }
+}
+
+fn profile_avatar(profile: &AuthorProfile) -> Option<String> {
+    profile.avatar.clone().or_else(|| {
+        profile
+            .github
+            .as_ref()
+            .map(|login| format!("https://github.com/{login}.png?size=96"))
+    })
+}
+
+fn authors_page_link(id: &str, config: &Config) -> Option<String> {
+    config.components.authors.as_ref()?;
+    let page = link::generate_relative(&config.build.path_prefix, "authors/");
+    Some(format!("{page}#{id}"))
+}
+
+/// Everyone who gets listed on the authors page, along with what they've worked on.
+/// Authors with a profile come first, in the order they're configured in.
+#[derive(Debug)]
+pub struct AuthorIndex {
+    entries: IndexMap<String, AuthorEntry>,
+}
+
+#[derive(Serialize, Debug)]
+struct AuthorsIndexContext {
+    authors: Vec<AuthorEntry>,
+}
+
+impl AuthorIndex {
+    pub fn new(config: &Config) -> Self {
+        let mut entries = IndexMap::new();
+        if let Some(authors) = &config.components.authors {
+            for (id, profile) in &authors.people {
+                let id = slugify(id);
+                let mut links = profile
+                    .links
+                    .iter()
+                    .map(|(label, url)| AuthorLink {
+                        label: label.clone(),
+                        url: url.clone(),
+                    })
+                    .collect::<Vec<_>>();
+                if let Some(login) = &profile.github {
+                    links.push(AuthorLink {
+                        label: "GitHub".to_owned(),
+                        url: format!("https://github.com/{login}"),
+                    });
+                }
+                entries.insert(
+                    id.clone(),
+                    AuthorEntry {
+                        id,
+                        name: profile.name.clone(),
+                        avatar_url: profile_avatar(profile),
+                        bio: profile.bio.clone(),
+                        links,
+                        posts: Vec::new(),
+                        releases: Vec::new(),
+                    },
+                );
+            }
+        }
+        Self { entries }
+    }
+
+    pub fn add_post(&mut self, byline: &Byline, credit: Credit) {
+        self.entry(byline).posts.push(credit);
+    }
+
+    pub fn add_release(&mut self, byline: &Byline, credit: Credit) {
+        self.entry(byline).releases.push(credit);
+    }
+
+    fn entry(&mut self, byline: &Byline) -> &mut AuthorEntry {
+        self.entries
+            .entry(byline.id.clone())
+            .or_insert_with(|| AuthorEntry {
+                id: byline.id.clone(),
+                name: byline.name.clone(),
+                avatar_url: byline.avatar_url.clone(),
+                bio: None,
+                links: Vec::new(),
+                posts: Vec::new(),
+                releases: Vec::new(),
+            })
+    }
+
+    /// Renders `authors/`
+    pub fn build_page(self, templates: &Templates) -> Result<Page> {
+        let context = AuthorsIndexContext {
+            authors: self.entries.into_values().collect(),
+        };
+        Page::new_from_template("authors.html", templates, "authors.html", &context)
+    }
+}
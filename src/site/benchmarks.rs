@@ -0,0 +1,67 @@
+use camino::Utf8Path;
+use serde::Serialize;
+
+use crate::config::BenchmarksConfig;
+use crate::data::benchmarks::{format_duration, BenchmarkGroup};
+use crate::diagnostics;
+
+#[derive(Serialize, Debug)]
+pub struct BenchmarksContext {
+    groups: Vec<BenchmarkGroupContext>,
+}
+
+#[derive(Serialize, Debug)]
+struct BenchmarkGroupContext {
+    name: String,
+    results: Vec<BenchmarkRow>,
+}
+
+#[derive(Serialize, Debug)]
+struct BenchmarkRow {
+    name: String,
+    mean: String,
+    stddev: Option<String>,
+    median: Option<String>,
+    /// Formatted as `min … max`, if we have both
+    range: Option<String>,
+    /// The mean as a percentage of the slowest mean in the group, for drawing bars
+    relative_width: f64,
+}
+
+/// Loads every configured results file. Files that fail to load are skipped with a warning,
+/// so a single stale path doesn't break the whole build.
+pub fn context(config: &BenchmarksConfig, root: &Utf8Path) -> BenchmarksContext {
+    let mut groups = Vec::new();
+    for file in &config.files {
+        match BenchmarkGroup::load(root, file) {
+            Ok(group) => groups.push(group_context(group)),
+            Err(e) => diagnostics::report(e),
+        }
+    }
+    BenchmarksContext { groups }
+}
+
+fn group_context(group: BenchmarkGroup) -> BenchmarkGroupContext {
+    let slowest = group.results.iter().map(|r| r.mean).fold(0.0, f64::max);
+    BenchmarkGroupContext {
+        name: group.name,
+        results: group
+            .results
+            .into_iter()
+            .map(|r| BenchmarkRow {
+                mean: format_duration(r.mean),
+                stddev: r.stddev.map(format_duration),
+                median: r.median.map(format_duration),
+                range: r.min.zip(r.max).map(|(min, max)| {
+                    format!("{} … {}", format_duration(min), format_duration(max))
+                }),
+                relative_width: if slowest > 0.0 {
+                    (r.mean / slowest * 100.0).max(1.0)
+                } else {
+                    0.0
+                },
+                name: r.name,
+            })
+            .collect(),
+    }
+}
@@ -0,0 +1,183 @@
+use serde::Serialize;
+
+use crate::config::{BlogConfig, Config};
+use crate::data::blog::BlogPost;
+use crate::errors::*;
+use crate::site::authors::{self, AuthorIndex, Byline, Credit};
+use crate::site::link;
+use crate::site::markdown::{to_html, ReadingTime};
+use crate::site::page::Page;
+use crate::site::rss;
+use crate::site::tags::{TagCloudEntry, TagLink, TaggedItem, TaggedItemKind, Taxonomy};
+use crate::site::templates::Templates;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct BlogPostContext {
+    pub slug: String,
+    pub title: String,
+    /// Relative link to the post's page
+    pub link: String,
+    /// A human-readable date, e.g. "Aug  1 2023"
+    pub formatted_date: String,
+    /// `YYYY-MM-DD`, for `<time datetime>`
+    pub date: String,
+    pub authors: Vec<Byline>,
+    pub tags: Vec<TagLink>,
+    pub description: Option<String>,
+    /// The rendered post
+    pub body: String,
+    pub reading_time: ReadingTime,
+}
+
+/// One page of the paginated blog index
+#[derive(Serialize, Debug)]
+pub struct BlogIndexContext {
+    posts: Vec<BlogPostContext>,
+    page_number: usize,
+    total_pages: usize,
+    prev_link: Option<String>,
+    next_link: Option<String>,
+    has_rss_feed: bool,
+    /// Every tag on the site, not just the ones on this page
+    tag_cloud: Vec<TagCloudEntry>,
+}
+
+/// Renders every post and the paginated index, plus the RSS feed if enabled. Posts are also
+/// added to `taxonomy` under their tags, and credited to their authors in `author_index`.
+///
+/// The index lives at `blog/`, further pages at `blog/page/<n>/`, and posts at `blog/<slug>/`.
+pub fn build_pages(
+    blog_config: &BlogConfig,
+    templates: &Templates,
+    config: &Config,
+    taxonomy: &mut Taxonomy,
+    author_index: &mut AuthorIndex,
+) -> Result<Vec<Page>> {
+    let Some(path) = &blog_config.path else {
+        return Ok(vec![]);
+    };
+    let posts = BlogPost::load_all(&config.path(path))?
+        .into_iter()
+        .map(|post| post_context(post, config))
+        .collect::<Result<Vec<_>>>()?;
+
+    for post in &posts {
+        let tags = post
+            .tags
+            .iter()
+            .map(|tag| tag.name.clone())
+            .collect::<Vec<_>>();
+        taxonomy.add(
+            &tags,
+            &TaggedItem {
+                kind: TaggedItemKind::Post,
+                title: post.title.clone(),
+                link: post.link.clone(),
+                date: Some(post.date.clone()),
+                formatted_date: Some(post.formatted_date.clone()),
+                description: post.description.clone(),
+            },
+        );
+        for byline in &post.authors {
+            author_index.add_post(
+                byline,
+                Credit {
+                    title: post.title.clone(),
+                    link: Some(post.link.clone()),
+                },
+            );
+        }
+    }
+    let tag_cloud = taxonomy.cloud(&config.build.path_prefix);
+
+    let mut pages = vec![];
+    for post in &posts {
+        let mut page = Page::new_from_template(
+            &format!("blog/{}.html", post.slug),
+            templates,
+            "blog_post.html",
+            &post,
+        )?;
+        page.reading_time = Some(post.reading_time);
+        pages.push(page);
+    }
+
+    let posts_per_page = blog_config.posts_per_page.max(1);
+    let total_pages = posts.len().div_ceil(posts_per_page).max(1);
+    for page_number in 1..=total_pages {
+        let page_posts = posts
+            .iter()
+            .skip((page_number - 1) * posts_per_page)
+            .take(posts_per_page)
+            .cloned()
+            .collect();
+        let index_context = BlogIndexContext {
+            posts: page_posts,
+            page_number,
+            total_pages,
+            prev_link: (page_number > 1).then(|| {
+                link::generate_relative(&config.build.path_prefix, &index_path(page_number - 1))
+            }),
+            next_link: (page_number < total_pages).then(|| {
+                link::generate_relative(&config.build.path_prefix, &index_path(page_number + 1))
+            }),
+            has_rss_feed: blog_config.rss_feed,
+            tag_cloud: tag_cloud.clone(),
+        };
+        let filename = if page_number == 1 {
+            "blog.html".to_owned()
+        } else {
+            format!("blog/page/{page_number}.html")
+        };
+        pages.push(Page::new_from_template(
+            &filename,
+            templates,
+            "blog_index.html",
+            &index_context,
+        )?);
+    }
+
+    if blog_config.rss_feed {
+        let feed = rss::generate_blog_rss_feed(&posts, config)?;
+        pages.push(Page {
+            contents: feed.to_string(),
+            filename: "blog.rss".to_string(),
+            reading_time: None,
+            stale: None,
+        });
+    }
+    Ok(pages)
+}
+
+fn index_path(page_number: usize) -> String {
+    if page_number == 1 {
+        "blog/".to_owned()
+    } else {
+        format!("blog/page/{page_number}/")
+    }
+}
+
+fn post_context(post: BlogPost, config: &Config) -> Result<BlogPostContext> {
+    let body = to_html(&post.body, config)?;
+    Ok(BlogPostContext {
+        reading_time: ReadingTime::from_html(&body),
+        body,
+        link: link::generate_relative(&config.build.path_prefix, &format!("blog/{}/", post.slug)),
+        formatted_date: post.date.format("%b %e %Y").to_string(),
+        date: post.date.format("%Y-%m-%d").to_string(),
+        slug: post.slug,
+        title: post.title,
+        authors: post
+            .author
+            .iter()
+            .chain(&post.authors)
+            .map(|name| authors::byline(name, config))
+            .collect(),
+        tags: post
+            .tags
+            .iter()
+            .map(|tag| TagLink::new(tag, &config.build.path_prefix))
+            .collect(),
+        description: post.description,
+    })
+}
@@ -0,0 +1,90 @@
+//! Building a site from another program, see [`SiteBuilder`][].
+
+use camino::Utf8PathBuf;
+
+use crate::config::Config;
+use crate::errors::*;
+use crate::site::writer::SiteWriter;
+use crate::site::Site;
+
+/// Builds the site of a single project, for tools that embed oranda rather than run it.
+///
+/// Everything the build reads is relative to the project's directory, so this never looks at,
+/// or changes, the current directory. It doesn't need a tokio runtime to be running either: the
+/// build always runs in one of its own, on a thread of its own if it's called from inside
+/// another runtime, since it blocks on its requests.
+#[derive(Debug, Clone)]
+pub struct SiteBuilder {
+    config: Config,
+    base_dir: Option<Utf8PathBuf>,
+}
+
+impl SiteBuilder {
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            base_dir: None,
+        }
+    }
+
+    /// Sets the directory the project lives in, which relative paths in the config, including
+    /// `build.dist_dir`, are relative to. This defaults to the config's
+    /// [`root_dir`][Config::root_dir].
+    pub fn base_dir(mut self, path: impl Into<Utf8PathBuf>) -> Self {
+        self.base_dir = Some(path.into());
+        self
+    }
+
+    /// The config the site is built with, with its paths resolved against the base directory
+    pub fn config(&self) -> Config {
+        let mut config = self.config.clone();
+        if let Some(base_dir) = &self.base_dir {
+            config.root_dir = base_dir.clone();
+        }
+        // Like a workspace member's, the dist dir stays put no matter where we run
+        config.build.dist_dir = config.path(&config.build.dist_dir).into_string();
+        config
+    }
+
+    /// Builds the site, returning its pages and files without writing them. Images the pages
+    /// link to are copied to `build.dist_dir` along the way, but nothing that's already there is
+    /// removed.
+    pub fn build(&self) -> Result<Site> {
+        let config = self.config();
+        with_runtime(|| Site::build_single(&config, None, None))
+    }
+
+    /// Writes a site from [`SiteBuilder::build`][] to `build.dist_dir`, along with the mdbook,
+    /// the static files and everything else that doesn't come from a page
+    pub fn write(&self, site: Site) -> Result<()> {
+        let config = self.config();
+        with_runtime(|| site.write(Some(&config)))
+    }
+
+    /// Like [`SiteBuilder::write`][], but hands the files to `writer`
+    pub fn write_to(&self, site: Site, writer: &mut (dyn SiteWriter + Send)) -> Result<()> {
+        let config = self.config();
+        with_runtime(|| site.write_to(Some(&config), writer))
+    }
+}
+
+/// Runs `f` in a tokio runtime of its own. The build blocks on futures with
+/// `Handle::block_on`, which panics when it's called from a thread that's running async code,
+/// so if there's a runtime already, `f` runs on a separate thread.
+fn with_runtime<T: Send>(f: impl FnOnce() -> Result<T> + Send) -> Result<T> {
+    if tokio::runtime::Handle::try_current().is_err() {
+        return in_own_runtime(f);
+    }
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| in_own_runtime(f))
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    })
+}
+
+fn in_own_runtime<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    let _guard = runtime.enter();
+    f()
+}
@@ -0,0 +1,60 @@
+//! Where the site lives: each page's canonical URL, and the `CNAME` file GitHub Pages reads to
+//! serve the site from a custom domain.
+
+use url::Url;
+
+use crate::config::Config;
+use crate::site::link;
+use crate::site::page::Page;
+use crate::site::{private, widgets};
+
+/// Adds a `<link rel="canonical">` and an `og:url` with each page's absolute URL to its `<head>`,
+/// if we know where the site is hosted
+pub fn add_links(pages: &mut [Page], config: &Config) {
+    for page in pages {
+        // Widgets are embedded elsewhere, and private and offline pages aren't for search
+        // engines or link previews
+        if !page.filename.ends_with(".html")
+            || page.filename == "offline.html"
+            || widgets::is_widget(&page.filename)
+            || private::is_private_output(&page.filename, config)
+        {
+            continue;
+        }
+        let Some(url) = link::page_url(config, &page.filename) else {
+            return;
+        };
+        let Some(head_end) = page.contents.find("</head>") else {
+            continue;
+        };
+        let url = url.replace('&', "&amp;").replace('"', "&quot;");
+        page.contents.insert_str(
+            head_end,
+            &format!(
+                "  <link rel=\"canonical\" href=\"{url}\" />\n    <meta property=\"og:url\" content=\"{url}\" />\n  "
+            ),
+        );
+    }
+}
+
+/// The `CNAME` file, with the domain the site is served at, if `build.cname` is on
+pub fn cname_page(config: &Config) -> Option<Page> {
+    if !config.build.cname {
+        return None;
+    }
+    let domain = link::base_url(config)
+        .and_then(|url| Url::parse(url).ok())
+        .and_then(|url| url.host_str().map(str::to_owned));
+    let Some(domain) = domain else {
+        tracing::warn!(
+            "Not writing a CNAME file, set build.base_url or project.homepage to the URL your site is served at"
+        );
+        return None;
+    };
+    Some(Page {
+        contents: format!("{domain}\n"),
+        filename: "CNAME".to_owned(),
+        reading_time: None,
+        stale: None,
+    })
+}
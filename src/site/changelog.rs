@@ -1,16 +1,23 @@
 use axoproject::{Version, WorkspaceInfo, WorkspaceKind};
+use camino::Utf8Path;
+use chrono::DateTime;
 use serde::Serialize;
 
-use crate::config::Config;
+use crate::config::{ChangelogSource, Config, UpgradeGuide};
+use crate::data::changelog::ChangelogFile;
+use crate::data::git::{self, Commit, ConventionalCommit};
 use crate::data::{Context, Release};
+use crate::diagnostics;
 use crate::errors::*;
-use crate::site::{javascript, markdown};
+use crate::site::authors::{self, Byline};
+use crate::site::{javascript, link, markdown};
 
 #[derive(Serialize, Debug)]
 pub struct ChangelogContext {
     pub releases: Vec<ChangelogRelease>,
     pub has_prereleases: bool,
     pub has_rss_feed: bool,
+    pub has_prerelease_rss_feed: bool,
     pub os_script: String,
 }
 
@@ -20,7 +27,76 @@ pub struct ChangelogRelease {
     pub version_tag: String,
     pub name: Option<String>,
     pub formatted_date: Option<String>,
+    /// Whoever published the release, if the authors component is enabled
+    pub author: Option<Byline>,
     pub body: String,
+    /// A link to the changes since the previous release, if we know where the repo is hosted
+    pub compare: Option<CompareLink>,
+    /// The release's files. Only filled in when `components.artifacts.check_downloads` is on,
+    /// so that we can point out the ones that have since been deleted.
+    pub downloads: Vec<ChangelogDownload>,
+    /// The guides for upgrading to this release, from `components.changelog.upgrade_guides`
+    pub upgrade_guides: Vec<UpgradeGuideLink>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct UpgradeGuideLink {
+    pub title: String,
+    pub url: String,
+}
+
+/// An upgrade guide's page. The guide itself is rendered into the template separately.
+#[derive(Serialize, Debug)]
+pub struct UpgradeGuideContext {
+    pub title: String,
+    pub versions: String,
+    /// The releases the guide is for, newest first
+    pub releases: Vec<UpgradeGuideRelease>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct UpgradeGuideRelease {
+    pub version_tag: String,
+    pub url: String,
+}
+
+/// The "full diff" between a release and the one before it
+#[derive(Serialize, Debug)]
+pub struct CompareLink {
+    pub previous_tag: String,
+    pub url: String,
+    /// How many commits the diff contains. Only known if the tags exist in the local checkout.
+    pub commit_count: Option<usize>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ChangelogDownload {
+    pub name: String,
+    pub url: String,
+    pub unavailable: bool,
+}
+
+/// A release in `changelog.json`
+#[derive(Serialize, Debug)]
+pub struct ChangelogJsonEntry {
+    pub version: String,
+    pub name: Option<String>,
+    /// In RFC 3339 format, if we could parse it
+    pub date: Option<String>,
+    pub prerelease: bool,
+    pub html: String,
+    pub markdown: String,
+    pub links: ChangelogJsonLinks,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ChangelogJsonLinks {
+    /// The release's page on this site
+    pub page: String,
+    /// The release's page on the host it was published to
+    pub release: Option<String>,
+    /// The changes since the previous release
+    pub compare: Option<String>,
 }
 
 pub fn index_context(
@@ -39,13 +115,18 @@ pub fn index_context(
                 .changelog
                 .clone()
                 .is_some_and(|c| c.rss_feed),
+            has_prerelease_rss_feed: false,
             os_script: javascript::build_os_script_path(&config.build.path_prefix),
         });
     }
     let releases = context
         .releases
         .iter()
-        .map(|release| single_context(release, config, project))
+        .enumerate()
+        .map(|(idx, release)| {
+            let previous = previous_release(&context.releases, idx);
+            single_context(release, previous, config, project)
+        })
         .collect();
     Ok(ChangelogContext {
         releases,
@@ -55,12 +136,67 @@ pub fn index_context(
             .changelog
             .clone()
             .is_some_and(|c| c.rss_feed),
+        has_prerelease_rss_feed: config
+            .components
+            .changelog
+            .as_ref()
+            .is_some_and(|c| c.prerelease_rss_feed),
         os_script: javascript::build_os_script_path(&config.build.path_prefix),
     })
 }
 
+/// Builds the entries of `changelog.json`, newest first. The placeholder release we make when
+/// there aren't any real ones is left out.
+pub fn json_entries(
+    context: &Context,
+    config: &Config,
+    project: Option<&WorkspaceInfo>,
+) -> Result<Vec<ChangelogJsonEntry>> {
+    let mut entries = vec![];
+    for (idx, release) in context.releases.iter().enumerate() {
+        if release.source.is_current_state() {
+            continue;
+        }
+        let version_tag = release.source.version_tag();
+        let markdown = release_markdown(project, release, config)?;
+        let html = markdown::release_to_html(&markdown, config)?;
+        let date = release.source.date().map(|date| {
+            DateTime::parse_from_rfc3339(date)
+                .map(|parsed| parsed.to_rfc3339())
+                .unwrap_or_else(|_| date.to_owned())
+        });
+        let compare = previous_release(&context.releases, idx).and_then(|previous| {
+            compare_url(
+                config.project.repository.as_deref()?,
+                previous.source.version_tag(),
+                version_tag,
+            )
+        });
+        entries.push(ChangelogJsonEntry {
+            version: version_tag.to_owned(),
+            name: release.source.name().map(str::to_owned),
+            date,
+            prerelease: release.source.is_prerelease(),
+            html,
+            markdown,
+            links: ChangelogJsonLinks {
+                page: link::generate_relative(
+                    &config.build.path_prefix,
+                    &format!("changelog/{version_tag}/"),
+                ),
+                release: release.source.html_url().map(str::to_owned),
+                compare,
+            },
+        });
+    }
+    Ok(entries)
+}
+
+/// Builds the context for a single release. `previous` is the release the changes are
+/// relative to, see [`previous_release`][].
 pub fn single_context(
     release: &Release,
+    previous: Option<&Release>,
     config: &Config,
     project: Option<&WorkspaceInfo>,
 ) -> ChangelogRelease {
@@ -69,43 +205,304 @@ pub fn single_context(
         version_tag: release.source.version_tag().to_string(),
         name: release.source.name().map(|s| s.to_string()),
         formatted_date: release.source.formatted_date(),
+        author: authors::release_byline(release, config),
         body: build_release_body(project, release, config).unwrap_or("".to_string()),
+        compare: previous.and_then(|previous| compare_link(release, previous, config)),
+        downloads: release_downloads(release, config),
+        upgrade_guides: upgrade_guides(release.source.version_tag(), config)
+            .map(|guide| UpgradeGuideLink {
+                title: upgrade_guide_title(guide),
+                url: upgrade_guide_link(guide, config),
+            })
+            .collect(),
+    }
+}
+
+/// The guides in `components.changelog.upgrade_guides` for the release tagged `version_tag`.
+/// Guides with invalid version requirements are skipped here, since building their own page
+/// will fail anyway.
+fn upgrade_guides<'a>(
+    version_tag: &str,
+    config: &'a Config,
+) -> impl Iterator<Item = &'a UpgradeGuide> {
+    let version = tag_version(version_tag);
+    config
+        .components
+        .changelog
+        .iter()
+        .flat_map(|changelog| &changelog.upgrade_guides)
+        .filter(move |guide| {
+            let Some(version) = &version else {
+                return false;
+            };
+            semver::VersionReq::parse(&guide.versions).is_ok_and(|req| req.matches(version))
+        })
+}
+
+/// Finds the version in a tag like `v1.2.0` or `my-crate-v1.2.0`
+pub fn tag_version(tag: &str) -> Option<semver::Version> {
+    tag.char_indices()
+        .filter(|(idx, c)| {
+            c.is_ascii_digit()
+                && tag[..*idx]
+                    .chars()
+                    .last()
+                    .map_or(true, |prev| matches!(prev, 'v' | 'V' | '-' | '@' | '/'))
+        })
+        .find_map(|(idx, _)| semver::Version::parse(&tag[idx..]).ok())
+}
+
+fn upgrade_guide_title(guide: &UpgradeGuide) -> String {
+    guide
+        .title
+        .clone()
+        .unwrap_or_else(|| "Upgrade guide".to_owned())
+}
+
+/// Where an upgrade guide's page goes, under `changelog/upgrading/`
+pub fn upgrade_guide_filename(guide: &UpgradeGuide) -> String {
+    let stem = Utf8Path::new(&guide.path).file_stem().unwrap_or("guide");
+    format!("changelog/upgrading/{}.html", slug::slugify(stem))
+}
+
+fn upgrade_guide_link(guide: &UpgradeGuide, config: &Config) -> String {
+    let filename = upgrade_guide_filename(guide);
+    let path = format!("{}/", filename.trim_end_matches(".html"));
+    link::generate_relative(&config.build.path_prefix, &path)
+}
+
+/// Builds the context for an upgrade guide's page, with links back to the releases it's for
+pub fn upgrade_guide_context(
+    guide: &UpgradeGuide,
+    context: &Context,
+    config: &Config,
+) -> Result<UpgradeGuideContext> {
+    let req = semver::VersionReq::parse(&guide.versions).map_err(|e| {
+        OrandaError::InvalidUpgradeGuideVersions {
+            versions: guide.versions.clone(),
+            details: e.to_string(),
+        }
+    })?;
+    let releases = context
+        .releases
+        .iter()
+        .filter(|release| !release.source.is_current_state())
+        .map(|release| release.source.version_tag())
+        .filter(|tag| tag_version(tag).is_some_and(|version| req.matches(&version)))
+        .map(|tag| UpgradeGuideRelease {
+            version_tag: tag.to_owned(),
+            url: link::generate_relative(&config.build.path_prefix, &format!("changelog/{tag}/")),
+        })
+        .collect();
+    Ok(UpgradeGuideContext {
+        title: upgrade_guide_title(guide),
+        versions: guide.versions.clone(),
+        releases,
+    })
+}
+
+/// Finds the release that came before `releases[idx]`. Stable releases are compared to the
+/// previous stable release, so that a changelog for 1.1.0 covers everything since 1.0.0 and
+/// not just what changed since 1.1.0-rc.2.
+pub fn previous_release(releases: &[Release], idx: usize) -> Option<&Release> {
+    let release = releases.get(idx)?;
+    let is_prerelease = release.source.is_prerelease();
+    releases[idx + 1..].iter().find(|previous| {
+        !previous.source.is_current_state() && (is_prerelease || !previous.source.is_prerelease())
+    })
+}
+
+fn compare_link(release: &Release, previous: &Release, config: &Config) -> Option<CompareLink> {
+    let repository = config.project.repository.as_deref()?;
+    let from = previous.source.version_tag();
+    let to = release.source.version_tag();
+    Some(CompareLink {
+        previous_tag: from.to_owned(),
+        url: compare_url(repository, from, to)?,
+        commit_count: git::count_commits_between(&config.root_dir, from, to).ok(),
+    })
+}
+
+/// The URL of the page showing the changes between two tags, for the hosts we know the URL
+/// scheme of
+pub fn compare_url(repository: &str, from: &str, to: &str) -> Option<String> {
+    let repository = repository.trim_end_matches('/').trim_end_matches(".git");
+    let host = repository
+        .split("://")
+        .nth(1)?
+        .split('/')
+        .next()?
+        .to_ascii_lowercase();
+    match host.as_str() {
+        "github.com" | "codeberg.org" => Some(format!("{repository}/compare/{from}...{to}")),
+        "gitlab.com" => Some(format!("{repository}/-/compare/{from}...{to}")),
+        _ => None,
     }
 }
 
-// Unwrap that we can't avoid without adding an extra if let block, since if let chains aren't stable
-#[allow(clippy::unnecessary_unwrap)]
+fn release_downloads(release: &Release, config: &Config) -> Vec<ChangelogDownload> {
+    let check_downloads = config
+        .components
+        .artifacts
+        .as_ref()
+        .is_some_and(|a| a.check_downloads);
+    if !check_downloads {
+        return Vec::new();
+    }
+    release
+        .artifacts
+        .files()
+        .map(|file| ChangelogDownload {
+            name: file.name.clone(),
+            url: file.download_url.clone(),
+            unavailable: file.unavailable,
+        })
+        .collect()
+}
+
 fn build_release_body(
     project: Option<&WorkspaceInfo>,
     release: &Release,
     config: &Config,
 ) -> Result<String> {
-    let contents = if config
-        .components
-        .changelog
-        .as_ref()
-        .is_some_and(|c| c.read_changelog_file)
-        && project.is_some()
+    let contents = release_markdown(project, release, config)?;
+    markdown::release_to_html(&contents, config)
+}
+
+/// Finds the markdown changelog entry for a release, wherever the changelog config says it
+/// should come from
+pub fn release_markdown(
+    project: Option<&WorkspaceInfo>,
+    release: &Release,
+    config: &Config,
+) -> Result<String> {
+    let changelog_config = config.components.changelog.as_ref();
+    let version = release.source.version_tag();
+    let contents = match (changelog_config, project) {
+        (Some(changelog_config), _)
+            if changelog_config.read_changelog_file && !changelog_config.files.is_empty() =>
+        {
+            body_from_changelog_files(&changelog_config.files, version, config)?
+        }
+        (Some(changelog_config), Some(project)) if changelog_config.read_changelog_file => {
+            match project.changelog_for_version(&parse_version(version, project)?) {
+                Ok(changelog) => changelog.map(|c| c.body).unwrap_or_default(),
+                // axoproject is fairly strict about the format it accepts, so give our more
+                // lenient parser a shot before giving up.
+                Err(e) => project
+                    .root_auto_includes
+                    .changelog
+                    .as_deref()
+                    .and_then(|path| ChangelogFile::load_cached(&config.root_dir, path).ok())
+                    .and_then(|file| file.section_for_version(version).map(|s| s.body.clone()))
+                    .ok_or_else(|| OrandaError::ChangelogParseFailed {
+                        name: config.project.name.clone(),
+                        version: version.to_owned(),
+                        details: e,
+                    })?,
+            }
+        }
+        _ => release.source.body().unwrap_or_default().to_owned(),
+    };
+    if contents.trim().is_empty()
+        && config
+            .components
+            .changelog
+            .as_ref()
+            .is_some_and(|c| c.source == ChangelogSource::Git)
     {
-        let project = project.unwrap();
-        let version = release.source.version_tag();
-        let changelog = project
-            .changelog_for_version(&parse_version(version, project)?)
-            .map_err(|e| OrandaError::ChangelogParseFailed {
-                name: config.project.name.clone(),
-                version: version.to_owned(),
-                details: e,
-            })?;
-        if let Some(changelog) = changelog {
-            changelog.body
-        } else {
-            "".to_owned()
+        let tag = release.source.version_tag();
+        match build_body_from_commits(tag, config) {
+            Ok(body) => Ok(body),
+            Err(e) => {
+                diagnostics::report(OrandaError::ChangelogCommitsUnavailable {
+                    tag: tag.to_owned(),
+                    details: e.to_string(),
+                });
+                Ok(release.source.body().unwrap_or_default().to_owned())
+            }
         }
     } else {
-        release.source.body().unwrap_or_default().to_owned()
-    };
+        Ok(contents)
+    }
+}
+
+/// Collects the entries for a version from several changelog files. If more than one file has
+/// an entry, each one gets a heading attributing it to the directory its file is in.
+fn body_from_changelog_files(files: &[String], version: &str, config: &Config) -> Result<String> {
+    let mut entries = Vec::new();
+    for path in files {
+        let file = ChangelogFile::load_cached(&config.root_dir, Utf8Path::new(path))?;
+        if let Some(section) = file.section_for_version(version) {
+            if !section.body.is_empty() {
+                entries.push((file.label.clone(), section.body.clone()));
+            }
+        }
+    }
+
+    if entries.len() == 1 {
+        return Ok(entries.remove(0).1);
+    }
+    Ok(entries
+        .into_iter()
+        .map(|(label, body)| match label {
+            Some(label) => format!("## {label}\n\n{body}"),
+            None => body,
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n"))
+}
 
-    markdown::to_html(&contents, &config.styles.syntax_theme)
+/// Generates a markdown changelog entry for a tag from the commits between it and the
+/// previous tag. Fails if the tag isn't in the local checkout.
+fn build_body_from_commits(tag: &str, config: &Config) -> Result<String> {
+    let previous = git::previous_tag(&config.root_dir, tag)?;
+    let commits = git::commits_between(&config.root_dir, previous.as_deref(), tag)?;
+    Ok(group_commits(&commits))
+}
+
+/// Groups conventional commits into markdown sections by type. Commits that don't follow
+/// the convention end up under "Other changes".
+pub fn group_commits(commits: &[Commit]) -> String {
+    let mut features = Vec::new();
+    let mut fixes = Vec::new();
+    let mut chores = Vec::new();
+    let mut other = Vec::new();
+    for commit in commits {
+        let short_hash = commit.hash.get(..7).unwrap_or(&commit.hash);
+        let Some(parsed) = ConventionalCommit::parse(&commit.summary) else {
+            other.push(format!("- {} ({short_hash})", commit.summary));
+            continue;
+        };
+        let mut line = String::from("- ");
+        if parsed.breaking {
+            line.push_str("**BREAKING:** ");
+        }
+        if let Some(scope) = &parsed.scope {
+            line.push_str(&format!("**{scope}:** "));
+        }
+        line.push_str(&format!("{} ({short_hash})", parsed.description));
+        match parsed.kind.as_str() {
+            "feat" => features.push(line),
+            "fix" => fixes.push(line),
+            "chore" => chores.push(line),
+            _ => other.push(line),
+        }
+    }
+
+    let mut body = String::new();
+    for (heading, entries) in [
+        ("Features", features),
+        ("Fixes", fixes),
+        ("Chores", chores),
+        ("Other changes", other),
+    ] {
+        if entries.is_empty() {
+            continue;
+        }
+        body.push_str(&format!("### {heading}\n\n{}\n\n", entries.join("\n")));
+    }
+    body
 }
 
 /// Parses a version string into an axoproject-compatible version.
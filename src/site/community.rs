@@ -0,0 +1,81 @@
+//! Pages for a project's code of conduct, contributing guide and governance docs, from
+//! `components.community`.
+
+use camino::Utf8Path;
+
+use crate::config::{CommunityConfig, Config};
+use crate::errors::*;
+use crate::site::layout::NavLink;
+use crate::site::link;
+use crate::site::page::{source, Page};
+use crate::site::templates::Templates;
+
+/// A community page: its title, its Markdown file and where it ends up on the site
+struct CommunityPage<'a> {
+    title: &'static str,
+    path: &'a str,
+    slug: &'static str,
+}
+
+fn community_pages(community: &CommunityConfig) -> Vec<CommunityPage<'_>> {
+    [
+        (
+            "Code of conduct",
+            &community.code_of_conduct,
+            "code-of-conduct",
+        ),
+        ("Contributing", &community.contributing, "contributing"),
+        ("Governance", &community.governance, "governance"),
+    ]
+    .into_iter()
+    .filter_map(|(title, path, slug)| {
+        path.as_deref()
+            .map(|path| CommunityPage { title, path, slug })
+    })
+    .collect()
+}
+
+/// Renders a page for each of the files we found. They're placed at fixed paths, like
+/// "/contributing/", rather than wherever the file is, since it's often in `.github/`.
+pub fn build_pages(
+    community: &CommunityConfig,
+    templates: &Templates,
+    config: &Config,
+) -> Result<Vec<Page>> {
+    let mut pages = vec![];
+    for page in community_pages(community) {
+        let (mut rendered, _) = Page::new_from_markdown(page.path, templates, config, None, true)?;
+        rendered.filename = format!("{}.html", page.slug);
+        pages.push(rendered);
+    }
+    Ok(pages)
+}
+
+/// The links to the community pages, for the footer
+pub fn links(config: &Config) -> Vec<NavLink> {
+    let Some(community) = &config.components.community else {
+        return vec![];
+    };
+    community_pages(community)
+        .into_iter()
+        .map(|page| NavLink {
+            name: page.title.to_owned(),
+            path: link::generate_relative(&config.build.path_prefix, &format!("{}/", page.slug)),
+        })
+        .collect()
+}
+
+/// The link to the community page built from `path`, which is relative to the project's
+/// directory, if there is one, so that links between these files keep working
+pub fn link_to(path: &Utf8Path, config: &Config) -> Option<String> {
+    let community = config.components.community.as_ref()?;
+    community_pages(community)
+        .into_iter()
+        .find(|page| {
+            // Detected paths include the project's directory
+            let page_path = pathdiff::diff_utf8_paths(page.path, &config.root_dir)
+                .unwrap_or_else(|| page.path.into());
+            source::normalize(&page_path) == path
+        })
+        .map(|page| link::generate_relative(&config.build.path_prefix, &format!("{}/", page.slug)))
+}
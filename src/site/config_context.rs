@@ -0,0 +1,90 @@
+//! The resolved config, as every template sees it under `config`.
+//!
+//! This is a copy of the parts of [`Config`][] that say something about the site, so that
+//! template overrides can branch on them without the user having to repeat them in a data file.
+//! Paths on the build machine, credentials and analytics setup are left out on purpose.
+
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::site::markdown::SyntaxTheme;
+use crate::site::oranda_theme::OrandaTheme;
+use crate::site::Site;
+
+#[derive(Serialize, Debug)]
+pub struct ConfigContext {
+    pub project: ProjectContext,
+    pub build: BuildContext,
+    pub styles: StylesContext,
+    /// The components the site is built with, like "artifacts" or "changelog"
+    pub components: Vec<&'static str>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ProjectContext {
+    pub name: String,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub homepage: Option<String>,
+    pub repository: Option<String>,
+    pub license: Option<String>,
+    pub requirements: IndexMap<String, String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct BuildContext {
+    pub path_prefix: Option<String>,
+    pub preview: Option<String>,
+    pub page_history: bool,
+    pub emit_json: bool,
+    pub offline_support: bool,
+    pub compat: bool,
+    pub single_file: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct StylesContext {
+    pub theme: OrandaTheme,
+    pub syntax_theme: SyntaxTheme,
+    pub has_logo: bool,
+}
+
+impl ConfigContext {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            project: ProjectContext {
+                name: config.project.name.clone(),
+                version: config.project.version.clone(),
+                description: config.project.description.clone(),
+                homepage: config.project.homepage.clone(),
+                repository: config.project.repository.clone(),
+                license: config.project.license.clone(),
+                requirements: config.project.requirements.clone(),
+            },
+            build: BuildContext {
+                path_prefix: config.build.path_prefix.clone(),
+                preview: config.build.preview.clone(),
+                page_history: config.build.page_history,
+                emit_json: config.build.emit_json,
+                offline_support: config.build.offline_support,
+                compat: config.build.compat,
+                single_file: config.build.single_file,
+            },
+            styles: StylesContext {
+                theme: config.styles.theme,
+                syntax_theme: config.styles.syntax_theme,
+                has_logo: config.styles.logo.is_some(),
+            },
+            components: Site::planned_components(config),
+        }
+    }
+
+    /// The config of a workspace's index page, which has no components of its own
+    pub fn new_for_workspace_index(workspace_config: &Config) -> Self {
+        Self {
+            components: vec![],
+            ..Self::new(workspace_config)
+        }
+    }
+}
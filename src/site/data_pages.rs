@@ -0,0 +1,80 @@
+//! Pages rendered from the user's own data files, see [`DataPagesConfig`][].
+
+use camino::Utf8Path;
+use indexmap::IndexSet;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::config::{DataPage, DataPagesConfig};
+use crate::data::data_file;
+use crate::errors::*;
+use crate::site::page::Page;
+use crate::site::templates::Templates;
+
+#[derive(Serialize, Debug)]
+pub struct DataPageContext {
+    pub title: String,
+    /// The contents of the data file, as-is
+    pub data: Value,
+    /// If the data is a list of objects, every key they have, in the order we first saw them
+    pub columns: Vec<String>,
+    /// If the data is a list of objects, their values for each of `columns`
+    pub rows: Vec<Vec<String>>,
+    /// The data as pretty-printed JSON, for when it isn't a list of objects
+    pub json: String,
+}
+
+/// The context for the page at `path`, reading its data file relative to `root`
+pub fn context(path: &str, page: &DataPage, root: &Utf8Path) -> Result<DataPageContext> {
+    let data = data_file::load(root.join(&page.data).as_str())?;
+    let (columns, rows) = table(&data).unwrap_or_default();
+    Ok(DataPageContext {
+        title: page.title.clone().unwrap_or_else(|| path.to_owned()),
+        json: serde_json::to_string_pretty(&data)?,
+        data,
+        columns,
+        rows,
+    })
+}
+
+/// Turns a list of objects into a table, if that's what we've got
+fn table(data: &Value) -> Option<(Vec<String>, Vec<Vec<String>>)> {
+    let items = data.as_array().filter(|items| !items.is_empty())?;
+    let mut columns = IndexSet::new();
+    for item in items {
+        columns.extend(item.as_object()?.keys().cloned());
+    }
+    let rows = items
+        .iter()
+        .map(|item| {
+            columns
+                .iter()
+                .map(|column| match item.get(column) {
+                    None | Some(Value::Null) => String::new(),
+                    Some(Value::String(s)) => s.clone(),
+                    Some(other) => other.to_string(),
+                })
+                .collect()
+        })
+        .collect();
+    Some((columns.into_iter().collect(), rows))
+}
+
+pub fn build_pages(
+    templates: &Templates,
+    data_cfg: &DataPagesConfig,
+    root: &Utf8Path,
+) -> Result<Vec<Page>> {
+    let mut pages = vec![];
+    for (path, page) in &data_cfg.pages {
+        let context = context(path, page, root)?;
+        let filename = format!("{}.html", path.trim_matches('/'));
+        pages.push(Page::new_from_template(
+            &filename,
+            templates,
+            page.template(),
+            &context,
+        )?);
+    }
+    Ok(pages)
+}
@@ -0,0 +1,135 @@
+//! The template contexts `oranda debug context` prints, for people writing template overrides
+//! and themes who want to know what they have to work with.
+//!
+//! These are built the same way a real build builds them, minus everything a build writes to
+//! the dist dir along the way.
+
+use indexmap::IndexMap;
+use minijinja::context;
+use serde_json::Value;
+
+use crate::config::{AxoprojectLayer, Config, ConfigOptions};
+use crate::data::{funding::Funding, workspaces};
+use crate::errors::*;
+use crate::site::config_context::ConfigContext;
+use crate::site::layout::LayoutContext;
+use crate::site::workspace_index::WorkspaceIndexContext;
+use crate::site::{activity, artifacts, changelog, demo, funding, github_action, health, Site};
+
+/// Every page `contexts` and `workspace_contexts` can return, in the order they return them
+pub const PAGES: &[&str] = &[
+    "layout",
+    "config",
+    "index",
+    "artifacts",
+    "changelog",
+    "funding",
+    "workspace_index",
+];
+
+/// The contexts for a single site's pages. `layout` and `config` are what every template gets
+/// under those names, and the rest are what each page's template gets as `page`. Pages the site
+/// doesn't have are `null`.
+pub fn contexts(config: &Config) -> Result<IndexMap<&'static str, Value>> {
+    let context = if Site::needs_context(config)? {
+        Some(Site::build_context(config)?)
+    } else {
+        None
+    };
+    let mut contexts = IndexMap::new();
+    contexts.insert(
+        "layout",
+        serde_json::to_value(LayoutContext::new(config, context.as_ref())?)?,
+    );
+    contexts.insert("config", serde_json::to_value(ConfigContext::new(config))?);
+
+    let artifacts_context = match &context {
+        Some(context) if config.components.artifacts_enabled() => {
+            artifacts::template_context(context, config)?
+        }
+        _ => None,
+    };
+    let health_context = config
+        .components
+        .health
+        .as_ref()
+        .and_then(|health_cfg| health::context(health_cfg, config));
+    let activity_context = config
+        .components
+        .activity
+        .as_ref()
+        .and_then(|activity_cfg| activity::context(activity_cfg, config));
+    let github_action_context = match &config.components.github_action {
+        Some(action_cfg) => github_action::context(action_cfg, config, context.as_ref())?,
+        None => None,
+    };
+    let demo_context = config
+        .components
+        .demo
+        .as_ref()
+        .map(|demo_cfg| demo::context(demo_cfg, config));
+    contexts.insert(
+        "index",
+        serde_json::to_value(context!(
+            artifacts => artifacts_context,
+            health => health_context,
+            activity => activity_context,
+            github_action => github_action_context,
+            demo => demo_context,
+        ))?,
+    );
+    contexts.insert("artifacts", serde_json::to_value(&artifacts_context)?);
+
+    let changelog_context = match &context {
+        Some(context) if config.components.changelog.is_some() => {
+            let project = AxoprojectLayer::get_best_workspace(&config.root_dir);
+            Some(changelog::index_context(context, config, project.as_ref())?)
+        }
+        _ => None,
+    };
+    contexts.insert("changelog", serde_json::to_value(changelog_context)?);
+
+    let funding_context = match &config.components.funding {
+        Some(funding_cfg) => {
+            let funding = Funding::new(funding_cfg, config)?;
+            Some(funding::context(funding_cfg, &funding)?)
+        }
+        None => None,
+    };
+    contexts.insert("funding", serde_json::to_value(funding_context)?);
+    contexts.insert("workspace_index", Value::Null);
+    Ok(contexts)
+}
+
+/// The contexts for a workspace's index page. The members' own pages are left out, since
+/// they're each a site of their own: run this in a member's directory to see those.
+pub fn workspace_contexts(
+    workspace_config: &Config,
+    options: &ConfigOptions,
+) -> Result<IndexMap<&'static str, Value>> {
+    let root_path = workspace_config.root_dir.clone();
+    let workspace_config_path = root_path.join("oranda-workspace.json");
+    let members = workspaces::from_config(
+        workspace_config,
+        &root_path,
+        &workspace_config_path,
+        options,
+    )?;
+    let mut contexts = IndexMap::new();
+    contexts.insert(
+        "layout",
+        serde_json::to_value(LayoutContext::new_for_workspace_index(workspace_config)?)?,
+    );
+    contexts.insert(
+        "config",
+        serde_json::to_value(ConfigContext::new_for_workspace_index(workspace_config))?,
+    );
+    for page in ["index", "artifacts", "changelog", "funding"] {
+        contexts.insert(page, Value::Null);
+    }
+    contexts.insert(
+        "workspace_index",
+        serde_json::to_value(WorkspaceIndexContext::new(&members, workspace_config)?)?,
+    );
+    Ok(contexts)
+}
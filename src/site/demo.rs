@@ -0,0 +1,58 @@
+//! A web demo of the project, see [`DemoConfig`][crate::config::DemoConfig].
+
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::Serialize;
+
+use crate::config::{Config, DemoConfig};
+use crate::errors::*;
+use crate::paths::determine_path;
+use crate::site::link;
+use crate::site::writer::SiteWriter;
+
+/// Where on the site the demo goes
+pub const DEMO_DIR: &str = "demo";
+
+#[derive(Serialize, Debug, Clone)]
+pub struct DemoContext {
+    pub title: String,
+    /// Link to the embedded page
+    pub url: String,
+    pub height: u32,
+}
+
+pub fn context(demo_cfg: &DemoConfig, config: &Config) -> DemoContext {
+    DemoContext {
+        title: demo_cfg.title.clone(),
+        url: link::generate_relative(
+            &config.build.path_prefix,
+            &format!("{DEMO_DIR}/{}", demo_cfg.entry.trim_start_matches("./")),
+        ),
+        height: demo_cfg.height,
+    }
+}
+
+/// Get an absolute path to the demo's directory, for the project in `root_dir`
+pub fn demo_dir(root_dir: &Utf8Path, demo_cfg: &DemoConfig) -> Result<Utf8PathBuf> {
+    match determine_path(root_dir, &None::<&Utf8Path>, &demo_cfg.path)? {
+        Some(path) => Ok(root_dir.join(path)),
+        None => Err(OrandaError::PathDoesNotExist {
+            path: demo_cfg.path.clone(),
+        }),
+    }
+}
+
+/// Copy the demo to "demo/" in the site
+pub fn copy_demo(
+    root_dir: &Utf8Path,
+    demo_cfg: &DemoConfig,
+    writer: &mut dyn SiteWriter,
+) -> Result<()> {
+    let demo_dir = demo_dir(root_dir, demo_cfg)?;
+    let entry = demo_dir.join(&demo_cfg.entry);
+    if !demo_dir.is_dir() || !entry.is_file() {
+        return Err(OrandaError::PathDoesNotExist {
+            path: entry.to_string(),
+        });
+    }
+    writer.write_dir(Utf8Path::new(DEMO_DIR), &demo_dir)
+}
@@ -0,0 +1,208 @@
+//! Draws a workspace's [`DependencyGraph`] for its index page.
+//!
+//! Members are drawn in rows, with everything a member depends on in the rows below it, so the
+//! graph reads from the applications at the top down to the shared libraries at the bottom.
+
+use std::cmp::Ordering;
+
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::data::dependency_graph::{DependencyGraph, DependencyKind};
+use crate::site::link;
+
+const NODE_HEIGHT: usize = 48;
+const MIN_NODE_WIDTH: usize = 120;
+/// Roughly how wide a character of the labels is, to size the boxes without measuring text
+const CHAR_WIDTH: usize = 8;
+const GAP_X: usize = 32;
+const GAP_Y: usize = 64;
+const MARGIN: usize = 8;
+
+#[derive(Debug, Serialize)]
+pub struct DependencyGraphContext {
+    pub svg: String,
+    /// The same thing as a list, for people who can't see the graph
+    pub members: Vec<MemberDependencies>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MemberDependencies {
+    pub name: String,
+    pub slug: String,
+    pub dependencies: Vec<DependencyLink>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DependencyLink {
+    pub name: String,
+    pub slug: String,
+    pub requirement: Option<String>,
+    pub kind: DependencyKind,
+}
+
+impl DependencyGraphContext {
+    /// Returns nothing if no member depends on another, as there'd be nothing to draw
+    pub fn new(graph: &DependencyGraph, config: &Config) -> Option<Self> {
+        if graph.edges.is_empty() {
+            return None;
+        }
+        let members = graph
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| MemberDependencies {
+                name: node.name.clone(),
+                slug: node.slug.clone(),
+                dependencies: graph
+                    .dependencies(index)
+                    .map(|edge| DependencyLink {
+                        name: graph.nodes[edge.to].name.clone(),
+                        slug: graph.nodes[edge.to].slug.clone(),
+                        requirement: edge.requirement.clone(),
+                        kind: edge.kind,
+                    })
+                    .collect(),
+            })
+            .collect();
+        Some(Self {
+            svg: svg(graph, config),
+            members,
+        })
+    }
+}
+
+/// Where each node's box goes: its top left corner
+fn positions(graph: &DependencyGraph, node_width: usize) -> (Vec<(usize, usize)>, usize, usize) {
+    let layers = graph.layers();
+    let rows = layers.iter().max().map_or(0, |max| max + 1);
+    // Top row first
+    let mut row_members = vec![vec![]; rows];
+    for (node, layer) in layers.iter().enumerate() {
+        row_members[rows - 1 - layer].push(node);
+    }
+    let widest = row_members.iter().map(Vec::len).max().unwrap_or(0);
+    let width = widest * node_width + widest.saturating_sub(1) * GAP_X + MARGIN * 2;
+    let height = rows * NODE_HEIGHT + rows.saturating_sub(1) * GAP_Y + MARGIN * 2;
+    let mut positions = vec![(0, 0); graph.nodes.len()];
+    for (row, members) in row_members.iter().enumerate() {
+        let row_width = members.len() * node_width + members.len().saturating_sub(1) * GAP_X;
+        let start = (width - row_width) / 2;
+        for (column, node) in members.iter().enumerate() {
+            positions[*node] = (
+                start + column * (node_width + GAP_X),
+                MARGIN + row * (NODE_HEIGHT + GAP_Y),
+            );
+        }
+    }
+    (positions, width, height)
+}
+
+/// The graph as an SVG, with an arrow from each member to each of its dependencies. Arrows for
+/// dev-dependencies are dashed, and every member links to its site.
+pub fn svg(graph: &DependencyGraph, config: &Config) -> String {
+    let labels = graph
+        .nodes
+        .iter()
+        .map(|node| match &node.version {
+            Some(version) => (node.name.clone(), format!("v{version}")),
+            None => (node.name.clone(), String::new()),
+        })
+        .collect::<Vec<_>>();
+    let longest = labels
+        .iter()
+        .map(|(name, version)| name.len().max(version.len()))
+        .max()
+        .unwrap_or(0);
+    let node_width = (longest * CHAR_WIDTH + 32).max(MIN_NODE_WIDTH);
+    let (positions, width, mut height) = positions(graph, node_width);
+    // Dev-dependencies can point across a row, and loop below it
+    if graph
+        .edges
+        .iter()
+        .any(|edge| positions[edge.from].1 == positions[edge.to].1)
+    {
+        height += GAP_Y / 2;
+    }
+
+    let mut svg = format!(
+        r#"<svg class="dependency-graph" xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {width} {height}" width="{width}" height="{height}" role="img" aria-label="Which workspace members depend on which">"#
+    );
+    svg.push_str(r#"<defs><marker id="dependency-graph-arrow" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="8" markerHeight="8" orient="auto-start-reverse"><path d="M0,0L10,5L0,10z" fill="currentColor"/></marker></defs>"#);
+
+    // Edges first, so the boxes are drawn over their ends
+    for edge in &graph.edges {
+        let (from_x, from_y) = positions[edge.from];
+        let (to_x, to_y) = positions[edge.to];
+        let x1 = from_x + node_width / 2;
+        let x2 = to_x + node_width / 2;
+        let (y1, y2, bend) = match to_y.cmp(&from_y) {
+            Ordering::Greater => (
+                from_y + NODE_HEIGHT,
+                to_y,
+                (from_y + NODE_HEIGHT + to_y) / 2,
+            ),
+            Ordering::Less => (
+                from_y,
+                to_y + NODE_HEIGHT,
+                (from_y + to_y + NODE_HEIGHT) / 2,
+            ),
+            Ordering::Equal => {
+                let bottom = from_y + NODE_HEIGHT;
+                (bottom, bottom, bottom + GAP_Y / 2)
+            }
+        };
+        let dashes = if edge.kind == DependencyKind::Dev {
+            r#" stroke-dasharray="4 4""#
+        } else {
+            ""
+        };
+        let mut title = format!(
+            "{} depends on {}",
+            graph.nodes[edge.from].name, graph.nodes[edge.to].name
+        );
+        if let Some(requirement) = &edge.requirement {
+            title.push_str(&format!(" {requirement}"));
+        }
+        match edge.kind {
+            DependencyKind::Normal => {}
+            DependencyKind::Build => title.push_str(" (build)"),
+            DependencyKind::Dev => title.push_str(" (dev)"),
+        }
+        svg.push_str(&format!(
+            r#"<path class="dependency-graph-edge" d="M{x1},{y1}C{x1},{bend} {x2},{bend} {x2},{y2}" fill="none" stroke="currentColor"{dashes} marker-end="url(#dependency-graph-arrow)"><title>{}</title></path>"#,
+            escape(&title)
+        ));
+    }
+
+    for ((node, (name, version)), (x, y)) in graph.nodes.iter().zip(&labels).zip(positions) {
+        let center = x + node_width / 2;
+        let href = link::generate_relative(&config.build.path_prefix, &node.slug);
+        svg.push_str(&format!(
+            r#"<a class="dependency-graph-node" href="{}"><rect x="{x}" y="{y}" width="{node_width}" height="{NODE_HEIGHT}" rx="6" fill="none" stroke="currentColor"/>"#,
+            escape(&href)
+        ));
+        let name_y = if version.is_empty() { y + 29 } else { y + 21 };
+        svg.push_str(&format!(
+            r#"<text x="{center}" y="{name_y}" text-anchor="middle" fill="currentColor" font-weight="bold">{}</text>"#,
+            escape(name)
+        ));
+        if !version.is_empty() {
+            svg.push_str(&format!(
+                r#"<text x="{center}" y="{}" text-anchor="middle" fill="currentColor" font-size="12">{}</text>"#,
+                y + 38,
+                escape(version)
+            ));
+        }
+        svg.push_str("</a>");
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,73 @@
+//! Docs built by an external tool, like Docusaurus, mkdocs or Sphinx, see
+//! [`DocsConfig`][crate::config::DocsConfig].
+
+use std::process::Command;
+
+use camino::{Utf8Path, Utf8PathBuf};
+
+use crate::config::DocsConfig;
+use crate::errors::*;
+use crate::paths::determine_path;
+use crate::site::writer::SiteWriter;
+
+/// Get an absolute path to the directory we run the docs command in, for the project in
+/// `root_dir`
+pub fn docs_dir(root_dir: &Utf8Path, docs_cfg: &DocsConfig) -> Result<Utf8PathBuf> {
+    match determine_path(root_dir, &None::<&Utf8Path>, &docs_cfg.path)? {
+        // This comes back relative to the root (and empty for the root itself)
+        Some(path) => Ok(root_dir.join(path)),
+        None => Err(OrandaError::PathDoesNotExist {
+            path: docs_cfg.path.clone(),
+        }),
+    }
+}
+
+/// Run the docs command, and copy what it built to "docs/" in the site
+pub fn build_docs(
+    root_dir: &Utf8Path,
+    docs_cfg: &DocsConfig,
+    writer: &mut dyn SiteWriter,
+) -> Result<()> {
+    let docs_dir = docs_dir(root_dir, docs_cfg)?;
+    tracing::info!("Building docs with `{}`...", docs_cfg.command);
+    let mut command = if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.arg("/C");
+        command
+    } else {
+        let mut command = Command::new("sh");
+        command.arg("-c");
+        command
+    };
+    let output = command
+        .arg(&docs_cfg.command)
+        .current_dir(&docs_dir)
+        .output()
+        .map_err(|e| OrandaError::DocsBuildFailed {
+            command: docs_cfg.command.clone(),
+            details: e.to_string(),
+        })?;
+    if !output.status.success() {
+        // The end of the output is usually where the actual error is
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stderr = stderr.trim();
+        let details = if stderr.is_empty() {
+            output.status.to_string()
+        } else {
+            let lines = stderr.lines().collect::<Vec<_>>();
+            lines[lines.len().saturating_sub(20)..].join("\n")
+        };
+        return Err(OrandaError::DocsBuildFailed {
+            command: docs_cfg.command.clone(),
+            details,
+        });
+    }
+
+    let output_dir = docs_dir.join(&docs_cfg.output_dir);
+    if !output_dir.is_dir() {
+        return Err(OrandaError::PathDoesNotExist {
+            path: output_dir.to_string(),
+        });
+    }
+    writer.write_dir(Utf8Path::new("docs"), &output_dir)
+}
@@ -0,0 +1,65 @@
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::data::features::{CrateFeatures, FeatureEnables};
+use crate::errors::*;
+use crate::site::markdown::to_html;
+
+#[derive(Serialize, Debug)]
+pub struct FeaturesContext {
+    pub features: Vec<FeatureRow>,
+    /// What the `default` feature turns on, as written in Cargo.toml
+    pub default: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct FeatureRow {
+    pub name: String,
+    pub default: bool,
+    pub description_html: Option<String>,
+    /// What the feature turns on, as written in Cargo.toml
+    pub enables: Vec<String>,
+    pub implicit: bool,
+}
+
+impl FeaturesContext {
+    pub fn new(crate_features: &CrateFeatures, config: &Config) -> Result<Self> {
+        let features = crate_features
+            .features
+            .iter()
+            .map(|feature| {
+                Ok(FeatureRow {
+                    name: feature.name.clone(),
+                    default: feature.default,
+                    description_html: feature
+                        .description
+                        .as_deref()
+                        .map(|description| to_html(description, config))
+                        .transpose()?,
+                    enables: feature.enables.iter().map(display).collect(),
+                    implicit: feature.implicit,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            features,
+            default: crate_features.default.iter().map(display).collect(),
+        })
+    }
+}
+
+/// Writes an entry of a feature's list back the way it looks in Cargo.toml
+pub fn display(enables: &FeatureEnables) -> String {
+    match enables {
+        FeatureEnables::Feature { name } => name.clone(),
+        FeatureEnables::Dependency { name } => format!("dep:{name}"),
+        FeatureEnables::DependencyFeature {
+            dependency,
+            feature,
+            weak,
+        } => {
+            let weak = if *weak { "?" } else { "" };
+            format!("{dependency}{weak}/{feature}")
+        }
+    }
+}
@@ -1,5 +1,6 @@
-use crate::config::FundingConfig;
+use crate::config::{FundingConfig, FundingLink, BUILTIN_FUNDING_ICONS};
 use crate::data::funding::{Funding, FundingContent, FundingType};
+use crate::data::sponsors::{SponsorsGoal, SponsorsTier};
 use crate::errors::Result;
 use serde::Serialize;
 
@@ -8,13 +9,45 @@ pub struct FundingContext {
     preferred_funding: Option<Vec<FundingMethod>>,
     funding: Vec<FundingMethod>,
     docs_content: Option<String>,
+    goal: Option<FundingGoal>,
+}
+
+/// The user's `monthly_goal`, with what we know from GitHub Sponsors
+#[derive(Serialize, Debug)]
+pub struct FundingGoal {
+    link: String,
+    monthly_goal: u64,
+    /// The goal on the Sponsors profile, which is what a progress bar can be shown for
+    progress: Option<SponsorsGoal>,
+    sponsor_count: u64,
+    tiers: Vec<SponsorsTier>,
 }
 
 #[derive(Serialize, Debug)]
 pub struct FundingMethod {
     title: String,
     link: String,
+    /// One of the icons in `templates/site/icons`
     icon: Option<String>,
+    /// An image to use as the icon instead
+    icon_url: Option<String>,
+}
+
+impl FundingContext {
+    /// Every way to fund the project, the preferred ones first
+    pub fn methods(&self) -> impl Iterator<Item = &FundingMethod> {
+        self.preferred_funding.iter().flatten().chain(&self.funding)
+    }
+}
+
+impl FundingMethod {
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn link(&self) -> &str {
+        &self.link
+    }
 }
 
 pub fn context(config: &FundingConfig, funding: &Funding) -> Result<FundingContext> {
@@ -28,19 +61,70 @@ pub fn context(config: &FundingConfig, funding: &Funding) -> Result<FundingConte
         .preferred_funding
         .as_ref()
         .and_then(|preferred| Some((preferred, funding_base.remove(preferred)?)))
-        .map(|(preferred, content)| to_funding_methods(preferred, &content));
+        .map(|(preferred, content)| to_funding_methods(config, preferred, &content));
+
+    let mut funding_types = funding_base.keys().cloned().collect::<Vec<_>>();
+    if !config.links.is_empty() && !funding_types.contains(&FundingType::Custom) {
+        funding_types.push(FundingType::Custom);
+    }
+    // Anything the user listed in `order` comes first, followed by everything else in the
+    // order of `FundingType`
+    funding_types.sort_by_key(|ftype| {
+        (
+            config
+                .order
+                .iter()
+                .position(|t| t == ftype)
+                .unwrap_or(usize::MAX),
+            ftype.clone(),
+        )
+    });
+    let mut methods = Vec::new();
+    for ftype in funding_types {
+        if let Some(content) = funding_base.get(&ftype) {
+            methods.extend(to_funding_methods(config, &ftype, content));
+        }
+        if ftype == FundingType::Custom {
+            methods.extend(config.links.iter().map(custom_link));
+        }
+    }
 
     Ok(FundingContext {
         preferred_funding,
-        funding: funding_base
-            .iter()
-            .flat_map(|(k, v)| to_funding_methods(k, v))
-            .collect(),
+        funding: methods,
         docs_content: funding.docs_content.clone(),
+        goal: config
+            .monthly_goal
+            .zip(funding.sponsors.as_ref())
+            .map(|(monthly_goal, sponsors)| FundingGoal {
+                link: format!("https://github.com/sponsors/{}", sponsors.login),
+                monthly_goal,
+                progress: sponsors.goal.clone(),
+                sponsor_count: sponsors.sponsor_count,
+                tiers: sponsors.tiers.clone(),
+            }),
     })
 }
 
-fn to_funding_methods(ftype: &FundingType, content: &FundingContent) -> Vec<FundingMethod> {
+fn custom_link(link: &FundingLink) -> FundingMethod {
+    let (icon, icon_url) = match &link.icon {
+        Some(icon) if BUILTIN_FUNDING_ICONS.contains(&icon.as_str()) => (Some(icon.clone()), None),
+        Some(url) if FundingLink::icon_is_url(url) => (None, Some(url.clone())),
+        _ => (None, None),
+    };
+    FundingMethod {
+        title: link.label.clone(),
+        link: link.url.clone(),
+        icon,
+        icon_url,
+    }
+}
+
+fn to_funding_methods(
+    config: &FundingConfig,
+    ftype: &FundingType,
+    content: &FundingContent,
+) -> Vec<FundingMethod> {
     let mut return_vec = Vec::new();
     match ftype {
         FundingType::Github => {
@@ -50,6 +134,7 @@ fn to_funding_methods(ftype: &FundingType, content: &FundingContent) -> Vec<Fund
                     title: "GitHub".to_string(),
                     link: format!("https://github.com/sponsors/{item}"),
                     icon: Some("github".to_string()),
+                    icon_url: None,
                 })
             }
         }
@@ -59,6 +144,7 @@ fn to_funding_methods(ftype: &FundingType, content: &FundingContent) -> Vec<Fund
                     title: "Patreon".to_string(),
                     link: format!("https://patreon.com/{item}"),
                     icon: Some("patreon".to_string()),
+                    icon_url: None,
                 })
             }
         }
@@ -68,6 +154,7 @@ fn to_funding_methods(ftype: &FundingType, content: &FundingContent) -> Vec<Fund
                     title: "OpenCollective".to_string(),
                     link: format!("https://opencollective.com/{item}"),
                     icon: Some("opencollective".to_string()),
+                    icon_url: None,
                 })
             }
         }
@@ -77,6 +164,7 @@ fn to_funding_methods(ftype: &FundingType, content: &FundingContent) -> Vec<Fund
                     title: "Ko-fi".to_string(),
                     link: format!("https://ko-fi.com/{item}"),
                     icon: Some("kofi".to_string()),
+                    icon_url: None,
                 })
             }
         }
@@ -85,7 +173,8 @@ fn to_funding_methods(ftype: &FundingType, content: &FundingContent) -> Vec<Fund
                 return_vec.push(FundingMethod {
                     title: "Tidelift".to_string(),
                     link: format!("https://tidelift.com/subscription/pkg/{item}"),
-                    icon: Some("patreon".to_string()),
+                    icon: Some("tidelift".to_string()),
+                    icon_url: None,
                 })
             }
         }
@@ -95,6 +184,7 @@ fn to_funding_methods(ftype: &FundingType, content: &FundingContent) -> Vec<Fund
                     title: "CommunityBridge".to_string(),
                     link: format!("https://crowdfunding.lfx.linuxfoundation.org/projects/{item}"),
                     icon: None,
+                    icon_url: None,
                 })
             }
         }
@@ -104,6 +194,7 @@ fn to_funding_methods(ftype: &FundingType, content: &FundingContent) -> Vec<Fund
                     title: "IssueHunt".to_string(),
                     link: format!("https://issuehunt.com/r/{item}"),
                     icon: None,
+                    icon_url: None,
                 })
             }
         }
@@ -113,6 +204,38 @@ fn to_funding_methods(ftype: &FundingType, content: &FundingContent) -> Vec<Fund
                     title: "Liberapay".to_string(),
                     link: format!("https://liberapay.com/{item}"),
                     icon: Some("liberapay".to_string()),
+                    icon_url: None,
+                })
+            }
+        }
+        FundingType::Polar => {
+            if let FundingContent::One(item) = content {
+                return_vec.push(FundingMethod {
+                    title: "Polar".to_string(),
+                    link: format!("https://polar.sh/{item}"),
+                    icon: Some("polar".to_string()),
+                    icon_url: None,
+                })
+            }
+        }
+        FundingType::BuyMeACoffee => {
+            if let FundingContent::One(item) = content {
+                return_vec.push(FundingMethod {
+                    title: "Buy Me a Coffee".to_string(),
+                    link: format!("https://buymeacoffee.com/{item}"),
+                    icon: Some("buymeacoffee".to_string()),
+                    icon_url: None,
+                })
+            }
+        }
+        FundingType::ThanksDev => {
+            if let FundingContent::One(item) = content {
+                // FUNDING.yml entries look like `u/gh/username`
+                return_vec.push(FundingMethod {
+                    title: "thanks.dev".to_string(),
+                    link: format!("https://thanks.dev/{item}"),
+                    icon: Some("thanksdev".to_string()),
+                    icon_url: None,
                 })
             }
         }
@@ -123,11 +246,17 @@ fn to_funding_methods(ftype: &FundingType, content: &FundingContent) -> Vec<Fund
                     title: item.clone(),
                     link: item,
                     icon: None,
+                    icon_url: None,
                 })
             }
         }
     }
 
+    if let Some(name) = config.names.get(ftype) {
+        for method in &mut return_vec {
+            method.title.clone_from(name);
+        }
+    }
     return_vec
 }
 
@@ -0,0 +1,109 @@
+//! The "use this action" section of the front page, from `components.github_action`
+
+use axoproject::GithubRepo;
+use serde::Serialize;
+
+use crate::config::{Config, GithubActionConfig};
+use crate::data::github_action::ActionManifest;
+use crate::data::Context;
+use crate::errors::*;
+
+#[derive(Serialize, Debug)]
+pub struct GithubActionContext {
+    name: String,
+    description: Option<String>,
+    /// The workflow step to copy, as YAML
+    snippet: String,
+    inputs: Vec<GithubActionInput>,
+}
+
+#[derive(Serialize, Debug)]
+struct GithubActionInput {
+    name: String,
+    description: Option<String>,
+    required: bool,
+    default: Option<String>,
+    deprecation_message: Option<String>,
+}
+
+/// Reads the action's metadata and builds the section. Returns None if we can't tell what the
+/// action is called on GitHub.
+pub fn context(
+    action_config: &GithubActionConfig,
+    config: &Config,
+    context: Option<&Context>,
+) -> Result<Option<GithubActionContext>> {
+    let Some(path) = &action_config.path else {
+        return Ok(None);
+    };
+    let manifest = ActionManifest::load(config.path(path).as_str())?;
+    let uses = match &action_config.uses {
+        Some(uses) => uses.clone(),
+        None => match config
+            .project
+            .repository
+            .as_deref()
+            .map(GithubRepo::from_url)
+        {
+            Some(Ok(repo)) => format!("{}/{}", repo.owner, repo.name),
+            _ => {
+                tracing::warn!("Skipping the GitHub Action snippet, because we don't know your action's repository. Set project.repository, or components.github_action.uses.");
+                return Ok(None);
+            }
+        },
+    };
+    // Without any releases, the best we can do is point at the default branch
+    let version = context
+        .and_then(|context| context.latest())
+        .filter(|release| !release.source.is_current_state())
+        .map(|release| release.source.version_tag().to_owned())
+        .unwrap_or_else(|| "main".to_owned());
+
+    Ok(Some(GithubActionContext {
+        snippet: snippet(&manifest, &uses, &version, action_config),
+        name: manifest.name,
+        description: manifest.description,
+        inputs: manifest
+            .inputs
+            .into_iter()
+            .map(|(name, input)| GithubActionInput {
+                name,
+                description: input.description,
+                required: input.required,
+                default: input.default,
+                deprecation_message: input.deprecation_message,
+            })
+            .collect(),
+    }))
+}
+
+/// A workflow step using the action at `version`. Required inputs are always included, along
+/// with any the user gave a value for.
+pub fn snippet(
+    manifest: &ActionManifest,
+    uses: &str,
+    version: &str,
+    action_config: &GithubActionConfig,
+) -> String {
+    let mut with = serde_yaml::Mapping::new();
+    for (name, input) in &manifest.inputs {
+        let value = match action_config.with.get(name) {
+            Some(value) => value.clone(),
+            None if input.required => input.default.clone().unwrap_or_default(),
+            None => continue,
+        };
+        with.insert(name.clone().into(), value.into());
+    }
+    for (name, value) in &action_config.with {
+        if !manifest.inputs.contains_key(name) {
+            with.insert(name.clone().into(), value.clone().into());
+        }
+    }
+    let mut step = serde_yaml::Mapping::new();
+    step.insert("name".into(), manifest.name.clone().into());
+    step.insert("uses".into(), format!("{uses}@{version}").into());
+    if !with.is_empty() {
+        step.insert("with".into(), with.into());
+    }
+    serde_yaml::to_string(&vec![step]).unwrap_or_default()
+}
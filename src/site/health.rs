@@ -0,0 +1,74 @@
+use axoproject::GithubRepo;
+use serde::Serialize;
+
+use crate::config::{Config, CoverageService, HealthConfig};
+use crate::data::health::{CiState, ProjectHealth};
+
+#[derive(Serialize, Debug)]
+pub struct HealthContext {
+    badges: Vec<HealthBadge>,
+}
+
+#[derive(Serialize, Debug)]
+struct HealthBadge {
+    label: String,
+    value: String,
+    /// "good", "bad", or "neutral", used to color the badge
+    state: &'static str,
+    url: String,
+}
+
+/// Fetches the project's health and turns it into badges for the health strip. Returns None if
+/// there's nothing to show.
+pub fn context(health_config: &HealthConfig, config: &Config) -> Option<HealthContext> {
+    let repo_url = config.project.repository.as_ref()?;
+    let repo = match GithubRepo::from_url(repo_url) {
+        Ok(repo) => repo,
+        Err(e) => {
+            tracing::warn!("Skipping project health, your repository isn't on GitHub: {e}");
+            return None;
+        }
+    };
+    let health = ProjectHealth::fetch(&repo, health_config);
+
+    let mut badges = Vec::new();
+    if let Some(ci) = health.ci {
+        let (value, state) = match ci.state {
+            CiState::Passing => ("passing", "good"),
+            CiState::Failing => ("failing", "bad"),
+            CiState::Running => ("running", "neutral"),
+            CiState::Unknown => ("unknown", "neutral"),
+        };
+        badges.push(HealthBadge {
+            label: ci.name,
+            value: value.to_owned(),
+            state,
+            url: ci.url,
+        });
+    }
+    if let Some(coverage) = health.coverage {
+        let state = if coverage.percent >= 80.0 {
+            "good"
+        } else if coverage.percent < 50.0 {
+            "bad"
+        } else {
+            "neutral"
+        };
+        let service = match coverage.service {
+            CoverageService::Codecov => "Codecov",
+            CoverageService::Coveralls => "Coveralls",
+        };
+        badges.push(HealthBadge {
+            label: format!("Coverage ({service})"),
+            value: format!("{:.1}%", coverage.percent),
+            state,
+            url: coverage.url,
+        });
+    }
+
+    if badges.is_empty() {
+        None
+    } else {
+        Some(HealthContext { badges })
+    }
+}
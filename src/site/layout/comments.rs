@@ -0,0 +1,92 @@
+//! The comment thread on release pages and blog posts, from `components.comments`.
+
+use axoproject::GithubRepo;
+use serde::Serialize;
+
+use crate::config::{CommentsConfig, CommentsProvider};
+
+#[derive(Serialize, Debug)]
+pub struct CommentsContext {
+    /// The provider's script, which replaces itself with the thread
+    pub script_src: &'static str,
+    /// The attributes the script is configured with, from their names to their values
+    pub attributes: Vec<(String, String)>,
+}
+
+impl CommentsContext {
+    /// Returns None if no repository is set and the project's isn't on GitHub
+    pub fn new(config: &CommentsConfig, repository: Option<&str>) -> Option<Self> {
+        let configured_repo = match &config.provider {
+            CommentsProvider::Giscus(giscus) => giscus.repo.clone(),
+            CommentsProvider::Utterances(utterances) => utterances.repo.clone(),
+        };
+        let repo = match configured_repo.or_else(|| project_repo(repository)) {
+            Some(repo) => repo,
+            None => {
+                tracing::warn!(
+                    "Skipping comments, your repository isn't on GitHub. Set the repo in components.comments to use another one."
+                );
+                return None;
+            }
+        };
+        let attr = |name: &str, value: &str| (name.to_owned(), value.to_owned());
+        let context = match &config.provider {
+            CommentsProvider::Giscus(giscus) => Self {
+                script_src: "https://giscus.app/client.js",
+                attributes: vec![
+                    attr("data-repo", &repo),
+                    attr("data-repo-id", &giscus.repo_id),
+                    attr("data-category", &giscus.category),
+                    attr("data-category-id", &giscus.category_id),
+                    attr(
+                        "data-mapping",
+                        giscus.mapping.as_deref().unwrap_or("pathname"),
+                    ),
+                    attr("data-strict", "1"),
+                    attr(
+                        "data-reactions-enabled",
+                        if giscus.reactions.unwrap_or(true) {
+                            "1"
+                        } else {
+                            "0"
+                        },
+                    ),
+                    attr("data-emit-metadata", "0"),
+                    attr("data-input-position", "bottom"),
+                    attr(
+                        "data-theme",
+                        config.theme.as_deref().unwrap_or("preferred_color_scheme"),
+                    ),
+                    attr("data-loading", "lazy"),
+                ],
+            },
+            CommentsProvider::Utterances(utterances) => {
+                let mut attributes = vec![
+                    attr("repo", &repo),
+                    attr(
+                        "issue-term",
+                        utterances.issue_term.as_deref().unwrap_or("pathname"),
+                    ),
+                ];
+                if let Some(label) = &utterances.label {
+                    attributes.push(attr("label", label));
+                }
+                attributes.push(attr(
+                    "theme",
+                    config.theme.as_deref().unwrap_or("preferred-color-scheme"),
+                ));
+                Self {
+                    script_src: "https://utteranc.es/client.js",
+                    attributes,
+                }
+            }
+        };
+        Some(context)
+    }
+}
+
+/// The project's repository as "owner/name", if it's on GitHub
+fn project_repo(repository: Option<&str>) -> Option<String> {
+    let repo = GithubRepo::from_url(repository?).ok()?;
+    Some(format!("{}/{}", repo.owner, repo.name))
+}
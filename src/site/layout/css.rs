@@ -4,6 +4,10 @@ use std::sync::RwLock;
 use crate::errors::*;
 
 use crate::config::style::ORANDA_CSS_TAG;
+use crate::data::http_cache;
+use crate::paths::resolve_asset;
+use crate::site::link;
+use crate::site::writer::SiteWriter;
 use axoasset::{Asset, LocalAsset};
 use camino::Utf8Path;
 use minifier::css;
@@ -15,10 +19,12 @@ struct CssItem {
     contents: String,
 }
 
-fn concat_minify(css_files: &[String]) -> Result<String> {
+/// Minifies and concatenates stylesheets, which are either URLs or paths relative to `root`
+pub fn concat_minify(css_files: &[String], root: &Utf8Path) -> Result<String> {
     let mut css = String::new();
     for file in css_files {
-        let future = Asset::load_string(file);
+        let origin_path = resolve_asset(root, file);
+        let future = Asset::load_string(&origin_path);
         let unminified = tokio::runtime::Handle::current().block_on(future)?;
         let minified = match css::minify(&unminified) {
             Ok(css) => Ok(css),
@@ -35,12 +41,19 @@ pub fn get_css_link(path_prefix: &Option<String>, release_tag: &str) -> Result<S
     Ok(crate::site::link::generate_relative(path_prefix, &filename))
 }
 
-/// Places CSS in the destination directory. Depending on the results of the build script, the
-/// output of this will differ.
-pub fn place_css(dist_dir: &str, release_tag: &str) -> Result<()> {
+/// Writes oranda's CSS. Depending on the results of the build script, the output of this will
+/// differ. A CSS file we fetch is cached for the project in `root_dir`.
+#[cfg_attr(not(css = "tailwind"), allow(unused_variables))]
+pub fn write_css(
+    root_dir: &Utf8Path,
+    dist_dir: &str,
+    release_tag: &str,
+    writer: &mut dyn SiteWriter,
+) -> Result<()> {
     // Above all, we respect specifying `style.oranda_css_version`.
     if release_tag == ORANDA_CSS_TAG {
-        // If the user has set `ORANDA_USE_TAILWIND_BINARY`, build using the Tailwind binary.
+        // If the user has set `ORANDA_USE_TAILWIND_BINARY`, build using the Tailwind binary. It
+        // writes to the dist dir itself, which the site is written from.
         #[cfg(css = "tailwind")]
         {
             oranda_generate_css::build_css(Utf8Path::new(dist_dir))?;
@@ -50,33 +63,33 @@ pub fn place_css(dist_dir: &str, release_tag: &str) -> Result<()> {
         #[cfg(css = "file")]
         {
             let css = include_str!("../../../oranda-css/dist/oranda.css");
-            LocalAsset::write_new_all(css, format!("{dist_dir}/oranda-{release_tag}.css"))?;
+            let filename = get_css_filename(release_tag);
+            writer.write(Utf8Path::new(&filename), css.as_bytes())?;
         }
 
         // Otherwise, fall back to fetching from GitHub releases.
         #[cfg(css = "fetch")]
         {
-            fetch_css(dist_dir, release_tag)?;
+            fetch_css(root_dir, release_tag, writer)?;
         }
         Ok(())
     } else {
         // If we specified a custom oranda version, or someone compiled oranda without Cargo (how?),
         // fall back to fetching that version off GitHub.
-        fetch_css(dist_dir, release_tag)
+        fetch_css(root_dir, release_tag, writer)
     }
 }
 
-fn fetch_css(dist_dir: &str, release_tag: &str) -> Result<()> {
+fn fetch_css(root_dir: &Utf8Path, release_tag: &str, writer: &mut dyn SiteWriter) -> Result<()> {
     match env::var("ORANDA_CSS") {
         Ok(path) => {
             let msg = format!("Overriding oranda_css path with {}", &path);
             tracing::warn!("{}", &msg);
-            LocalAsset::copy(&path, dist_dir)?;
-            Ok(())
+            let contents = LocalAsset::load_bytes(&path)?;
+            writer.write(Utf8Path::new(&get_css_filename(release_tag)), &contents)
         }
         Err(_) => {
-            let filename = format!("oranda-{release_tag}.css");
-            let dest_path = Utf8Path::new(dist_dir).join(filename);
+            let filename = get_css_filename(release_tag);
 
             // Do we already have this value cached?
             let cache_val = {
@@ -92,8 +105,8 @@ fn fetch_css(dist_dir: &str, release_tag: &str) -> Result<()> {
                 c
             } else {
                 // Nope, sure don't. Get it, and if we are successful, store it for next time.
-                let fresh =
-                    tokio::runtime::Handle::current().block_on(fetch_oranda(release_tag))?;
+                let fresh = tokio::runtime::Handle::current()
+                    .block_on(fetch_oranda(root_dir, release_tag))?;
 
                 let mut cache = CSS_CACHE.write().expect("CSS Cache should not be poisoned");
                 cache.push(CssItem {
@@ -103,19 +116,16 @@ fn fetch_css(dist_dir: &str, release_tag: &str) -> Result<()> {
                 fresh
             };
 
-            LocalAsset::write_new_all(&oranda_css_response, dest_path)?;
-            Ok(())
+            writer.write(Utf8Path::new(&filename), oranda_css_response.as_bytes())
         }
     }
 }
 
-async fn fetch_oranda(release_tag: &str) -> Result<String> {
+async fn fetch_oranda(root_dir: &Utf8Path, release_tag: &str) -> Result<String> {
     let oranda_css_request =
         octolotl::request::ReleaseAsset::new("axodotdev", "oranda", release_tag, "oranda.css");
-    Ok(octolotl::Request::send(&oranda_css_request, true)
-        .await?
-        .text()
-        .await?)
+    let body = http_cache::get_github(root_dir, &oranda_css_request).await?;
+    Ok(String::from_utf8_lossy(&body).into_owned())
 }
 
 fn get_css_filename(release_tag: &str) -> String {
@@ -126,9 +136,23 @@ fn get_css_filename(release_tag: &str) -> String {
     }
 }
 
-pub fn write_additional_css(additional_css: &[String], dist_dir: &Utf8Path) -> Result<()> {
-    let minified_css = concat_minify(additional_css)?;
+pub fn write_additional_css(
+    additional_css: &[String],
+    root: &Utf8Path,
+    writer: &mut dyn SiteWriter,
+) -> Result<()> {
+    let minified_css = concat_minify(additional_css, root)?;
 
-    LocalAsset::write_new(&minified_css, dist_dir.join("custom.css"))?;
-    Ok(())
+    writer.write(Utf8Path::new("custom.css"), minified_css.as_bytes())
+}
+
+const COMPAT_CSS_SOURCE: &str = include_str!("./compat.css");
+
+pub fn get_compat_css_link(path_prefix: &Option<String>) -> String {
+    link::generate_relative(path_prefix, "compat.css")
+}
+
+/// Writes the fallback styles for old browsers that `build.compat` adds to every page
+pub fn write_compat_css(writer: &mut dyn SiteWriter) -> Result<()> {
+    writer.write(Utf8Path::new("compat.css"), COMPAT_CSS_SOURCE.as_bytes())
 }
@@ -0,0 +1,92 @@
+//! The links, copyright notice and icons in the footer at the bottom of every page, from
+//! `marketing.footer`.
+
+use chrono::Datelike;
+use serde::Serialize;
+
+use crate::config::{Config, SocialPlatform};
+use crate::site::link;
+
+#[derive(Serialize, Debug, Default)]
+pub struct FooterContext {
+    columns: Vec<FooterColumnContext>,
+    copyright: Option<String>,
+    social: Vec<SocialLinkContext>,
+    built_with_oranda: bool,
+}
+
+#[derive(Serialize, Debug)]
+struct FooterColumnContext {
+    title: String,
+    links: Vec<FooterLinkContext>,
+}
+
+#[derive(Serialize, Debug)]
+struct FooterLinkContext {
+    text: String,
+    href: String,
+}
+
+#[derive(Serialize, Debug)]
+struct SocialLinkContext {
+    /// The name of the platform's icon, in `icons/`
+    icon: &'static str,
+    name: &'static str,
+    url: String,
+    /// Mastodon checks for `rel="me"` links back to a profile to verify it
+    rel_me: bool,
+}
+
+impl FooterContext {
+    pub fn new(config: &Config) -> Self {
+        let footer = &config.marketing.footer;
+        let columns = footer
+            .columns
+            .iter()
+            .map(|column| FooterColumnContext {
+                title: column.title.clone(),
+                links: column
+                    .links
+                    .iter()
+                    .map(|(text, href)| FooterLinkContext {
+                        text: text.clone(),
+                        href: footer_href(&config.build.path_prefix, href),
+                    })
+                    .collect(),
+            })
+            .collect();
+        let social = footer
+            .social
+            .iter()
+            .map(|(platform, url)| SocialLinkContext {
+                icon: platform.id(),
+                name: platform.name(),
+                url: url.clone(),
+                rel_me: *platform == SocialPlatform::Mastodon,
+            })
+            .collect();
+        Self {
+            columns,
+            copyright: footer
+                .copyright
+                .as_deref()
+                .map(|copyright| expand_copyright(copyright, chrono::Utc::now().year())),
+            social,
+            built_with_oranda: footer.built_with_oranda,
+        }
+    }
+}
+
+/// Replaces `{year}` in a copyright notice
+pub fn expand_copyright(copyright: &str, year: i32) -> String {
+    copyright.replace("{year}", &year.to_string())
+}
+
+/// Links to other sites are left alone, and the rest are relative to the root of the site
+fn footer_href(path_prefix: &Option<String>, href: &str) -> String {
+    if href.contains("://") || href.starts_with("mailto:") || href.starts_with('#') {
+        href.to_owned()
+    } else {
+        link::generate_relative(path_prefix, href.trim_start_matches('/'))
+    }
+}
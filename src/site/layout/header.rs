@@ -1,21 +1,27 @@
 use crate::config::Config;
 use crate::errors::*;
+use crate::paths::resolve_asset;
 use crate::site::link;
+use crate::site::writer::SiteWriter;
 
-use axoasset::{Asset, LocalAsset};
+use axoasset::Asset;
+use camino::Utf8Path;
 
 const DEFAULT_FAVICON: &[u8] = include_bytes!("../../../assets/favicon.ico");
 
-/// Copies the favicon into the dist dir
-pub fn place_default_favicon(config: &Config) -> Result<()> {
-    let asset = LocalAsset::new("favicon.ico", DEFAULT_FAVICON.to_vec())?;
-    asset.write(&config.build.dist_dir)?;
-    Ok(())
+/// Writes oranda's own favicon, for sites that don't set one
+pub fn write_default_favicon(writer: &mut dyn SiteWriter) -> Result<()> {
+    writer.write(Utf8Path::new("favicon.ico"), DEFAULT_FAVICON)
 }
 
 /// Fetches the logo and adds it to the dist_dir, then returns the path to link it with
 pub fn get_logo(logo: &str, config: &Config) -> Result<String> {
-    let fetched_logo = fetch_logo(&config.build.path_prefix, &config.build.dist_dir, logo);
+    let origin_path = resolve_asset(&config.root_dir, logo);
+    let fetched_logo = fetch_logo(
+        &config.build.path_prefix,
+        &config.build.dist_dir,
+        &origin_path,
+    );
 
     tokio::runtime::Handle::current().block_on(fetched_logo)
 }
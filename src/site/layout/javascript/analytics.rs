@@ -7,27 +7,34 @@ use crate::config::AnalyticsConfig;
 pub struct Analytics {
     pub snippet: Option<String>,
     pub google_script: Option<String>,
+    /// Set if the analytics set cookies and there's a consent banner, so that they're only
+    /// loaded once the visitor agrees to it
+    pub needs_consent: bool,
 }
 
 impl Analytics {
-    pub fn new(config: &Option<AnalyticsConfig>) -> Self {
+    pub fn new(config: &Option<AnalyticsConfig>, consent_banner: bool) -> Self {
         if let Some(analytics) = config {
-            match analytics {
+            let mut this = match analytics {
                 AnalyticsConfig::Google(provider) => {
                     let google_script = Some(provider.get_script());
                     Self {
                         snippet: Some(provider.snippet()),
                         google_script,
+                        needs_consent: false,
                     }
                 }
                 AnalyticsConfig::Plausible(provider) => Self::build(provider),
                 AnalyticsConfig::Fathom(provider) => Self::build(provider),
                 AnalyticsConfig::Umami(provider) => Self::build(provider),
-            }
+            };
+            this.needs_consent = consent_banner && analytics.sets_cookies();
+            this
         } else {
             Self {
                 snippet: None,
                 google_script: None,
+                needs_consent: false,
             }
         }
     }
@@ -36,6 +43,7 @@ impl Analytics {
         Self {
             snippet: Some(provider.snippet()),
             google_script: None,
+            needs_consent: false,
         }
     }
 }
@@ -10,14 +10,24 @@ let options = {
     mac64: "x86_64-apple",
     mac32: "i686-apple",
     macSilicon: "aarch64-apple",
+    macUniversal: "universal2-apple",
 
     linux64: "x86_64-unknown-linux",
     linux32: "i686-unknown-linux",
     linuxArm: "aarch64-unknown-linux",
 
+    freebsd64: "x86_64-unknown-freebsd",
+
     // ios: "ios",
     // android: "linux-android",
-    // freebsd: "freebsd",
+};
+
+// What to offer when there's nothing for the detected platform, in order
+let fallbacks = {
+    // Windows on ARM can run x64 binaries
+    [options.windowsArm]: [options.windows64],
+    [options.mac64]: [options.macUniversal],
+    [options.macSilicon]: [options.macUniversal],
 };
 
 function isAppleSilicon() {
@@ -44,7 +54,9 @@ function getOS() {
     var platform = navigator.platform;
 
     if (navigator.appVersion.includes("Win")) {
-        if (
+        if (userAgent.includes("ARM64") || userAgent.includes("aarch64")) {
+            OS = options.windowsArm;
+        } else if (
             !userAgent.includes("Windows NT 5.0") &&
             !userAgent.includes("Windows NT 5.1") &&
             (userAgent.indexOf("Win64") > -1 ||
@@ -108,9 +120,9 @@ function getOS() {
     // ) {
     //     OS = options.ios;
     // }
-    // if (platform.toLocaleLowerCase().includes("freebsd")) {
-    //     OS = options.freebsd;
-    // }
+    if (platform.toLocaleLowerCase().includes("freebsd")) {
+        OS = options.freebsd64;
+    }
 
     return OS;
 }
@@ -131,6 +143,13 @@ if (archSelect) {
 // Hydrate tab buttons with events
 Array.from(document.querySelectorAll(".install-tab[data-id]")).forEach((tab) => {
     tab.addEventListener("click", onTabClick);
+    // Tabs can be focused, so let them be picked with the keyboard too
+    tab.addEventListener("keydown", (evt) => {
+        if (evt.key === "Enter" || evt.key === " ") {
+            evt.preventDefault();
+            onTabClick(evt);
+        }
+    });
 });
 
 function onArchChange(evt) {
@@ -152,6 +171,7 @@ function onArchChange(evt) {
         firstContentChild.classList.remove("hidden");
         if (firstTabChild) {
             firstTabChild.classList.add("selected");
+            firstTabChild.setAttribute("aria-selected", "true");
         }
     }
     // Hide "no OS detected" message
@@ -174,12 +194,14 @@ function onTabClick(evt) {
         if (oldContentEl && oldTabEl) {
             oldContentEl.classList.add("hidden");
             oldTabEl.classList.remove("selected");
+            oldTabEl.setAttribute("aria-selected", "false");
         }
 
         // Unhide new content element
         newContentEl.classList.remove("hidden");
         // Select new tab element
         evt.currentTarget.classList.add("selected");
+        evt.currentTarget.setAttribute("aria-selected", "true");
     }
 }
 
@@ -190,7 +212,8 @@ let hit = allPlatforms.find(
         if (
             a.attributes["data-arch"].value.includes(options.mac64) &&
             os.includes(options.macSilicon) &&
-            !allPlatforms.find(p => p.attributes["data-arch"].value.includes(options.macSilicon))) {
+            !allPlatforms.find(p => p.attributes["data-arch"].value.includes(options.macSilicon)) &&
+            !allPlatforms.find(p => p.attributes["data-arch"].value.includes(options.macUniversal))) {
             // Unhide hint
             document.querySelector(".mac-switch").classList.remove("hidden");
             return true;
@@ -198,6 +221,14 @@ let hit = allPlatforms.find(
         return a.attributes["data-arch"].value.includes(os);
     }
 );
+if (!hit && os) {
+    for (const fallback of fallbacks[os] || []) {
+        hit = allPlatforms.find((a) => a.attributes["data-arch"].value.includes(fallback));
+        if (hit) {
+            break;
+        }
+    }
+}
 
 if (hit) {
     hit.classList.remove("hidden");
@@ -208,6 +239,7 @@ if (hit) {
     firstContentChild.classList.remove("hidden");
     if (firstTabChild) {
         firstTabChild.classList.add("selected");
+        firstTabChild.setAttribute("aria-selected", "true");
     }
 } else {
     const noDetectEl = document.querySelector(".no-autodetect");
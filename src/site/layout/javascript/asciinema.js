@@ -0,0 +1,191 @@
+// Plays asciinema recordings (asciicast v2) in the `.asciinema-player` elements on the page.
+//
+// This is a deliberately small player: it understands the cursor movement and erasing that
+// shells and progress bars use, and drops colors and other styling.
+(function () {
+  const ESCAPE = /\x1b(?:\[([0-9;?]*)([A-Za-z])|\][^\x07\x1b]*(?:\x07|\x1b\\)|[()][0-9A-B]|.)/y;
+
+  // A screen that output is written to, one line at a time
+  class Screen {
+    constructor() {
+      this.lines = [""];
+      this.row = 0;
+      this.col = 0;
+    }
+
+    write(data) {
+      let i = 0;
+      while (i < data.length) {
+        const c = data[i];
+        if (c === "\x1b") {
+          ESCAPE.lastIndex = i;
+          const match = ESCAPE.exec(data);
+          if (!match) {
+            i += 1;
+            continue;
+          }
+          this.control(match[1] || "", match[2]);
+          i = ESCAPE.lastIndex;
+          continue;
+        }
+        if (c === "\n") {
+          this.row += 1;
+          this.col = 0;
+        } else if (c === "\r") {
+          this.col = 0;
+        } else if (c === "\b") {
+          this.col = Math.max(0, this.col - 1);
+        } else if (c >= " " || c === "\t") {
+          this.put(c);
+        }
+        i += 1;
+      }
+    }
+
+    put(c) {
+      while (this.lines.length <= this.row) this.lines.push("");
+      const line = this.lines[this.row].padEnd(this.col, " ");
+      this.lines[this.row] = line.slice(0, this.col) + c + line.slice(this.col + 1);
+      this.col += 1;
+    }
+
+    control(params, command) {
+      const args = params.replace("?", "").split(";").map((n) => parseInt(n, 10));
+      const n = args[0] || 1;
+      while (this.lines.length <= this.row) this.lines.push("");
+      switch (command) {
+        case "A":
+          this.row = Math.max(0, this.row - n);
+          break;
+        case "B":
+          this.row += n;
+          break;
+        case "C":
+          this.col += n;
+          break;
+        case "D":
+          this.col = Math.max(0, this.col - n);
+          break;
+        case "G":
+          this.col = n - 1;
+          break;
+        case "H":
+        case "f":
+          this.row = (args[0] || 1) - 1;
+          this.col = (args[1] || 1) - 1;
+          break;
+        case "J":
+          if (args[0] === 2 || args[0] === 3) {
+            this.lines = [""];
+            this.row = 0;
+            this.col = 0;
+          } else {
+            this.lines[this.row] = this.lines[this.row].slice(0, this.col);
+            this.lines.length = this.row + 1;
+          }
+          break;
+        case "K":
+          if (!args[0]) {
+            this.lines[this.row] = this.lines[this.row].slice(0, this.col);
+          } else if (args[0] === 2) {
+            this.lines[this.row] = "";
+          }
+          break;
+        default:
+          // Colors and everything else are ignored
+          break;
+      }
+    }
+
+    text(rows) {
+      const lines = rows ? this.lines.slice(-rows) : this.lines;
+      return lines.join("\n");
+    }
+  }
+
+  async function load(src) {
+    const response = await fetch(src);
+    const lines = (await response.text()).split("\n").filter((line) => line.trim());
+    const header = JSON.parse(lines[0]);
+    const events = lines
+      .slice(1)
+      .map((line) => JSON.parse(line))
+      .filter((event) => event[1] === "o");
+    return { header, events };
+  }
+
+  function setUp(player) {
+    if (player.dataset.ready) return;
+    player.dataset.ready = "true";
+
+    const screen = player.querySelector(".asciinema-screen");
+    const toggle = player.querySelector(".asciinema-toggle");
+    let recording = null;
+    let timer = null;
+    let next = 0;
+    let state = new Screen();
+    let rows = null;
+
+    function render() {
+      screen.textContent = state.text(rows);
+    }
+
+    function pause() {
+      clearTimeout(timer);
+      timer = null;
+      toggle.textContent = "▶";
+      toggle.setAttribute("aria-label", "Play");
+    }
+
+    function step() {
+      const events = recording.events;
+      if (next >= events.length) {
+        if (player.dataset.loop !== undefined) {
+          state = new Screen();
+          next = 0;
+          timer = setTimeout(step, 1000);
+        } else {
+          next = 0;
+          pause();
+        }
+        return;
+      }
+      const previous = next === 0 ? 0 : events[next - 1][0];
+      state.write(events[next][2]);
+      render();
+      next += 1;
+      const wait = next < events.length ? events[next][0] - previous : 0;
+      // Long pauses in recordings are usually someone thinking, not something to watch
+      timer = setTimeout(step, Math.min(wait, 2) * 1000);
+    }
+
+    async function play() {
+      if (!recording) {
+        recording = await load(player.dataset.src);
+        const cols = player.dataset.cols || recording.header.width;
+        rows = player.dataset.rows || recording.header.height;
+        if (cols) screen.style.width = `${cols}ch`;
+        if (rows) screen.style.height = `${rows * 1.2}em`;
+      }
+      if (next === 0) state = new Screen();
+      toggle.textContent = "❚❚";
+      toggle.setAttribute("aria-label", "Pause");
+      step();
+    }
+
+    toggle.addEventListener("click", () => {
+      if (timer) {
+        pause();
+      } else {
+        play().catch(() => {
+          screen.textContent = "Couldn't load this recording.";
+        });
+      }
+    });
+    if (player.dataset.autoplay !== undefined) {
+      toggle.click();
+    }
+  }
+
+  document.querySelectorAll(".asciinema-player").forEach(setUp);
+})();
@@ -1,18 +1,116 @@
-use axoasset::LocalAsset;
 use camino::Utf8Path;
+use sha2::{Digest, Sha256};
 
+use crate::config::Config;
 use crate::errors::*;
+use crate::site::layout::css;
 use crate::site::link;
+use crate::site::page::Page;
+use crate::site::writer::SiteWriter;
 
 pub mod analytics;
 
 const ARTIFACTS_SCRIPT_SOURCE: &str = include_str!("./artifacts.js");
+const SERVICE_WORKER_SOURCE: &str = include_str!("./service_worker.js");
+const PLAYER_SCRIPT_SOURCE: &str = include_str!("./asciinema.js");
 
 pub fn build_os_script_path(path_prefix: &Option<String>) -> String {
     link::generate_relative(path_prefix, "artifacts.js")
 }
 
-pub fn write_os_script(dist_dir: &Utf8Path) -> Result<()> {
-    LocalAsset::write_new(ARTIFACTS_SCRIPT_SOURCE, dist_dir.join("artifacts.js"))?;
-    Ok(())
+pub fn write_os_script(writer: &mut dyn SiteWriter) -> Result<()> {
+    writer.write(
+        Utf8Path::new("artifacts.js"),
+        ARTIFACTS_SCRIPT_SOURCE.as_bytes(),
+    )
+}
+
+pub fn build_service_worker_path(path_prefix: &Option<String>) -> String {
+    link::generate_relative(path_prefix, "sw.js")
+}
+
+/// How many of the newest release pages the service worker keeps for reading offline
+pub const OFFLINE_RELEASES: usize = 5;
+
+/// The URLs the service worker caches as soon as it's installed: the site's shell (the home
+/// page, its styles and scripts), the changelog and its [`OFFLINE_RELEASES`][] newest release
+/// pages. Everything else a visitor reads is cached as they read it.
+pub fn offline_urls(config: &Config, pages: &[Page]) -> Result<Vec<String>> {
+    let prefix = &config.build.path_prefix;
+    let mut urls = vec![match prefix {
+        Some(prefix) => format!("/{prefix}/"),
+        None => "/".to_owned(),
+    }];
+    urls.push(css::get_css_link(
+        prefix,
+        &config.styles.oranda_css_version,
+    )?);
+    if config.build.compat {
+        urls.push(css::get_compat_css_link(prefix));
+    }
+    if !config.styles.additional_css.is_empty() {
+        urls.push(link::generate_relative(prefix, "custom.css"));
+    }
+    if config.components.artifacts_enabled() {
+        urls.push(build_os_script_path(prefix));
+    }
+    urls.push(link::generate_relative(prefix, "favicon.ico"));
+    let page_link =
+        |page: &Page| link::generate_relative(prefix, &link::pretty_path(&page.filename));
+    urls.extend(
+        pages
+            .iter()
+            .filter(|page| page.filename == "changelog.html")
+            .map(page_link),
+    );
+    // Release pages are built newest first
+    urls.extend(
+        pages
+            .iter()
+            .filter(|page| {
+                let filename = Utf8Path::new(&page.filename);
+                filename.parent() == Some(Utf8Path::new("changelog"))
+                    && filename.extension() == Some("html")
+            })
+            .take(OFFLINE_RELEASES)
+            .map(page_link),
+    );
+    Ok(urls)
+}
+
+/// Builds the service worker that caches `urls` and serves the offline fallback page, with the
+/// page's URL filled in.
+pub fn build_service_worker(path_prefix: &Option<String>, urls: &[String]) -> String {
+    let offline_url = link::generate_relative(path_prefix, "offline/");
+    let precache_urls = serde_json::to_string(urls).unwrap_or_else(|_| "[]".to_owned());
+    // A new list of pages gets a new cache, and the old one is cleared out when the worker
+    // that made it is replaced
+    let version = format!("{:x}", Sha256::digest(precache_urls.as_bytes()));
+    SERVICE_WORKER_SOURCE
+        .replace("__OFFLINE_URL__", &offline_url)
+        .replace("__PRECACHE_URLS__", &precache_urls)
+        .replace("__CACHE_VERSION__", &version[..8])
+}
+
+pub fn write_service_worker(
+    writer: &mut dyn SiteWriter,
+    path_prefix: &Option<String>,
+    urls: &[String],
+) -> Result<()> {
+    writer.write(
+        Utf8Path::new("sw.js"),
+        build_service_worker(path_prefix, urls).as_bytes(),
+    )
+}
+
+pub fn build_player_path(path_prefix: &Option<String>) -> String {
+    link::generate_relative(path_prefix, "asciinema.js")
+}
+
+/// Writes the player for asciinema recordings, see [`asciinema`][crate::site::markdown::asciinema]
+pub fn write_player(writer: &mut dyn SiteWriter) -> Result<()> {
+    writer.write(
+        Utf8Path::new("asciinema.js"),
+        PLAYER_SCRIPT_SOURCE.as_bytes(),
+    )
 }
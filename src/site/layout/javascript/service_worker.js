@@ -0,0 +1,83 @@
+// Keeps the site's shell and its newest release notes around for reading offline, and serves
+// a fallback page when the visitor is offline and the page they're navigating to isn't cached.
+const CACHE_NAME = "oranda-offline-__CACHE_VERSION__";
+const OFFLINE_URL = "__OFFLINE_URL__";
+const PRECACHE_URLS = __PRECACHE_URLS__;
+
+self.addEventListener("install", (event) => {
+  event.waitUntil(
+    caches
+      .open(CACHE_NAME)
+      .then((cache) =>
+        cache.add(new Request(OFFLINE_URL, { cache: "reload" })).then(() =>
+          // One page going missing shouldn't keep the rest from being cached
+          Promise.all(
+            PRECACHE_URLS.map((url) =>
+              cache.add(new Request(url, { cache: "reload" })).catch(() => {})
+            )
+          )
+        )
+      )
+      .then(() => self.skipWaiting())
+  );
+});
+
+self.addEventListener("activate", (event) => {
+  event.waitUntil(
+    caches
+      .keys()
+      .then((keys) =>
+        Promise.all(
+          keys
+            .filter((key) => key.startsWith("oranda-") && key !== CACHE_NAME)
+            .map((key) => caches.delete(key))
+        )
+      )
+      .then(() => self.clients.claim())
+  );
+});
+
+self.addEventListener("fetch", (event) => {
+  const request = event.request;
+  if (request.method !== "GET" || new URL(request.url).origin !== self.location.origin) {
+    return;
+  }
+  if (request.mode === "navigate") {
+    // Pages come from the network while we're online, and we keep a copy of each one that's
+    // read so it can be read again offline
+    event.respondWith(
+      fetch(request)
+        .then((response) => {
+          if (response.ok) {
+            const copy = response.clone();
+            caches.open(CACHE_NAME).then((cache) => cache.put(request, copy));
+          }
+          return response;
+        })
+        .catch(() =>
+          caches
+            .match(request)
+            .then((response) => response || caches.match(OFFLINE_URL))
+            .then((response) => response || Response.error())
+        )
+    );
+    return;
+  }
+  // Styles and the like come from the cache if they're in it, and get refreshed in the
+  // background for next time
+  event.respondWith(
+    caches.open(CACHE_NAME).then((cache) =>
+      cache.match(request).then((cached) => {
+        const fetched = fetch(request)
+          .then((response) => {
+            if (response.ok && cached) {
+              cache.put(request, response.clone());
+            }
+            return response;
+          })
+          .catch(() => cached || Response.error());
+        return cached || fetched;
+      })
+    )
+  );
+});
@@ -1,15 +1,26 @@
-use crate::config::{Config, SocialConfig};
+use crate::config::{Config, ConsentBannerConfig, SocialConfig};
 use crate::errors::*;
 use crate::site::oranda_theme::OrandaTheme;
+use indexmap::IndexMap;
 use serde::Serialize;
 
+pub mod comments;
 pub mod css;
+pub mod footer;
 pub mod header;
 pub mod javascript;
+pub mod newsletter;
+pub mod preview;
+pub mod web_manifest;
 use crate::data::Context;
+use crate::site::layout::comments::CommentsContext;
+use crate::site::layout::footer::FooterContext;
 use crate::site::layout::header::get_logo;
-use crate::site::{link, page};
+use crate::site::layout::newsletter::NewsletterContext;
+use crate::site::{community, link, page, private};
 use javascript::analytics::Analytics;
+use preview::PreviewContext;
+use web_manifest::WebManifestContext;
 
 #[derive(Serialize, Debug, Default)]
 pub struct LayoutContext {
@@ -21,19 +32,66 @@ pub struct LayoutContext {
     favicon_url: String,
     description: Option<String>,
     oranda_css_path: String,
+    /// Result of [`OrandaTheme::font_stylesheets`][], emitted as preload hints
+    font_stylesheets: Vec<&'static str>,
     has_additional_css: bool,
     logo: Option<String>,
     license: Option<String>,
+    /// What the project needs, from `project.requirements`
+    requirements: IndexMap<String, String>,
     additional_pages: Option<Vec<AdditionalPageContext>>,
     artifacts_link: Option<String>,
     mdbook_link: Option<String>,
+    docs_link: Option<String>,
+    rustdoc_link: Option<String>,
     funding_link: Option<String>,
+    support_matrix_link: Option<String>,
+    features_link: Option<String>,
+    roadmap_link: Option<String>,
+    /// Linked from the footer rather than the nav
+    licenses_link: Option<String>,
+    /// Set if the licenses page has the project's own licenses, not just its dependencies'
+    has_project_licenses: bool,
+    /// Also linked from the footer
+    security_link: Option<String>,
+    /// The code of conduct, contributing and governance pages, linked from the footer
+    community_links: Vec<NavLink>,
+    benchmarks_link: Option<String>,
+    blog_link: Option<String>,
+    blog_rss_link: Option<String>,
     changelog_link: Option<String>,
-    has_nav: bool,
+    changelog_rss_link: Option<String>,
+    changelog_prerelease_rss_link: Option<String>,
+    /// Pages a visitor is likely to navigate to next, emitted as prefetch hints
+    prefetch_links: Vec<String>,
+    /// Where to register the service worker from, if `build.offline_support` is on
+    service_worker_path: Option<String>,
+    /// The fallback styles for old browsers, set if `build.compat` is on
+    compat_css_path: Option<String>,
+    /// The links in the top level nav, from [`Self::collect_nav_links`][]
+    nav_links: Vec<NavLink>,
     home_link: String,
     path_prefix: Option<String>,
     analytics: Analytics,
+    /// Shown until the visitor accepts or declines analytics that set cookies
+    consent_banner: Option<ConsentBannerConfig>,
     social: SocialConfig,
+    footer: FooterContext,
+    /// Shown on the index and blog pages
+    newsletter: Option<NewsletterContext>,
+    /// The comment thread, which only release pages and blog posts include
+    comments: Option<CommentsContext>,
+    /// Set on preview builds, to show a banner and keep search engines away
+    preview: Option<PreviewContext>,
+    /// The manifest, icons and theme color for pinning or installing the site
+    web_manifest: Option<WebManifestContext>,
+}
+
+/// A single entry in the site's top level nav
+#[derive(Serialize, Debug)]
+pub struct NavLink {
+    pub name: String,
+    pub path: String,
 }
 
 #[derive(Serialize, Debug, Default)]
@@ -46,27 +104,55 @@ impl LayoutContext {
     pub fn new(config: &Config, context: Option<&Context>) -> Result<Self> {
         let css_path =
             css::get_css_link(&config.build.path_prefix, &config.styles.oranda_css_version)?;
-        let additional_pages = if config.build.additional_pages.is_empty() {
-            None
-        } else {
-            let mut ret = Vec::new();
-            for (name, path) in config.build.additional_pages.iter() {
-                if page::source::is_markdown(path) {
-                    let file_path = page::source::get_filename_with_dir(path)?;
-                    if let Some(path) = file_path {
-                        let href = link::generate_relative(
-                            &config.build.path_prefix,
-                            &format!("{}/", path),
-                        );
-                        ret.push(AdditionalPageContext {
-                            name: name.clone(),
-                            path: href,
-                        });
+        let data_pages = config
+            .components
+            .data_pages
+            .iter()
+            .flat_map(|data_cfg| &data_cfg.pages)
+            .filter(|(_, page)| page.in_nav());
+        let additional_pages =
+            if config.build.additional_pages.is_empty() && data_pages.clone().next().is_none() {
+                None
+            } else {
+                let mut ret = Vec::new();
+                for (name, path) in config.build.additional_pages.iter() {
+                    if page::source::is_markdown(path) {
+                        let file_path = private::page_path(path, config)?;
+                        if let Some(href) = file_path {
+                            let href = link::generate_relative(
+                                &config.build.path_prefix,
+                                &format!("{}/", href),
+                            );
+                            let weight = page::PageFrontmatter::load(config.path(path)).weight;
+                            ret.push((
+                                weight,
+                                AdditionalPageContext {
+                                    name: name.clone(),
+                                    path: href,
+                                },
+                            ));
+                        }
                     }
                 }
-            }
-            Some(ret)
-        };
+                // Data pages go after the markdown ones, unless their weights say otherwise
+                for (path, page) in data_pages {
+                    ret.push((
+                        page.weight(),
+                        AdditionalPageContext {
+                            name: page.title.clone().unwrap_or_else(|| path.clone()),
+                            path: link::generate_relative(
+                                &config.build.path_prefix,
+                                &format!("{}/", path.trim_matches('/')),
+                            ),
+                        },
+                    ));
+                }
+                // A stable sort, so that pages with the same weight stay in the order they're
+                // configured in
+                ret.sort_by_key(|(weight, _)| *weight);
+                let ret: Vec<_> = ret.into_iter().map(|(_, page)| page).collect();
+                Some(ret)
+            };
 
         let favicon_url = link::generate_relative(&config.build.path_prefix, "favicon.ico");
         let logo = if let Some(logo) = config.styles.logo.as_deref() {
@@ -86,11 +172,68 @@ impl LayoutContext {
             .mdbook
             .as_ref()
             .map(|_| link::generate_relative(&config.build.path_prefix, "book/"));
+        let docs_link = config
+            .components
+            .docs
+            .as_ref()
+            .map(|_| link::generate_relative(&config.build.path_prefix, "docs/"));
+        let rustdoc_link = config
+            .components
+            .rustdoc
+            .as_ref()
+            .map(|_| link::generate_relative(&config.build.path_prefix, "rustdoc/"));
         let funding_link = &config
             .components
             .funding
             .as_ref()
             .map(|_| link::generate_relative(&config.build.path_prefix, "funding/"));
+        let support_matrix_link = config
+            .components
+            .support_matrix
+            .as_ref()
+            .map(|_| link::generate_relative(&config.build.path_prefix, "platforms/"));
+        let features_link = config
+            .components
+            .features
+            .as_ref()
+            .map(|_| link::generate_relative(&config.build.path_prefix, "features/"));
+        let roadmap_link = config
+            .components
+            .roadmap
+            .as_ref()
+            .map(|_| link::generate_relative(&config.build.path_prefix, "roadmap/"));
+        let licenses_link = config
+            .components
+            .licenses
+            .as_ref()
+            .map(|_| link::generate_relative(&config.build.path_prefix, "licenses/"));
+        let has_project_licenses = config
+            .components
+            .licenses
+            .as_ref()
+            .and_then(|licenses| licenses.project_licenses.as_ref())
+            .is_some_and(|paths| !paths.is_empty());
+        let security_link = config
+            .components
+            .security
+            .as_ref()
+            .map(|_| link::generate_relative(&config.build.path_prefix, "security/"));
+        let benchmarks_link = config
+            .components
+            .benchmarks
+            .as_ref()
+            .map(|_| link::generate_relative(&config.build.path_prefix, "benchmarks/"));
+        let blog_link = config
+            .components
+            .blog
+            .as_ref()
+            .map(|_| link::generate_relative(&config.build.path_prefix, "blog/"));
+        let blog_rss_link = config
+            .components
+            .blog
+            .as_ref()
+            .filter(|blog| blog.rss_feed)
+            .map(|_| link::generate_relative(&config.build.path_prefix, "blog.rss"));
         let changelog_link = if context.is_some() {
             config
                 .components
@@ -100,19 +243,64 @@ impl LayoutContext {
         } else {
             None
         };
-        let has_nav = additional_pages.is_some()
-            || artifacts_link.is_some()
-            || mdbook_link.is_some()
-            || funding_link.is_some()
-            || changelog_link.is_some();
+        let changelog_rss_link = if changelog_link.is_some()
+            && config
+                .components
+                .changelog
+                .as_ref()
+                .is_some_and(|c| c.rss_feed)
+        {
+            Some(link::generate_relative(
+                &config.build.path_prefix,
+                "changelog.rss",
+            ))
+        } else {
+            None
+        };
+        let changelog_prerelease_rss_link = if changelog_link.is_some()
+            && config
+                .components
+                .changelog
+                .as_ref()
+                .is_some_and(|c| c.prerelease_rss_feed)
+        {
+            Some(link::generate_relative(
+                &config.build.path_prefix,
+                "changelog-prerelease.rss",
+            ))
+        } else {
+            None
+        };
+        let prefetch_links = artifacts_link
+            .iter()
+            .chain(changelog_link.iter())
+            .cloned()
+            .collect();
         let home_link = if let Some(path_prefix) = config.build.path_prefix.as_ref() {
             format!("/{}/", path_prefix)
         } else {
             "/".to_string()
         };
-        let analytics = Analytics::new(&config.marketing.analytics);
+        let analytics = Analytics::new(
+            &config.marketing.analytics,
+            config.marketing.consent_banner.is_some(),
+        );
+        // The banner is only worth showing if there's something to consent to
+        let consent_banner = if analytics.needs_consent {
+            config.marketing.consent_banner.clone()
+        } else {
+            None
+        };
+        let service_worker_path = config
+            .build
+            .offline_support
+            .then(|| javascript::build_service_worker_path(&config.build.path_prefix));
+        let compat_css_path = config
+            .build
+            .compat
+            .then(|| css::get_compat_css_link(&config.build.path_prefix));
 
-        Ok(Self {
+        let mut layout = Self {
             theme: config.styles.theme.as_css_classes(),
             project_name: config.project.name.clone(),
             homepage: config.project.homepage.clone(),
@@ -121,19 +309,97 @@ impl LayoutContext {
             description: config.project.description.clone(),
             logo,
             license: config.project.license.clone(),
+            requirements: config.project.requirements.clone(),
             oranda_css_path: css_path,
+            font_stylesheets: config.styles.theme.font_stylesheets(),
             has_additional_css: !config.styles.additional_css.is_empty(),
             additional_pages,
             artifacts_link,
             mdbook_link: mdbook_link.clone(),
+            docs_link,
+            rustdoc_link,
             funding_link: funding_link.clone(),
+            support_matrix_link,
+            features_link,
+            roadmap_link,
+            licenses_link,
+            has_project_licenses,
+            security_link,
+            community_links: community::links(config),
+            benchmarks_link,
+            blog_link,
+            blog_rss_link,
             changelog_link: changelog_link.clone(),
-            has_nav,
+            changelog_rss_link,
+            changelog_prerelease_rss_link,
+            prefetch_links,
+            service_worker_path,
+            compat_css_path,
+            nav_links: Vec::new(),
             home_link,
             path_prefix: config.build.path_prefix.clone(),
             analytics,
-            social: config.marketing.social.clone(),
-        })
+            consent_banner,
+            social: SocialConfig {
+                // Link previews need a full URL to fetch the image from
+                image: config
+                    .marketing
+                    .social
+                    .image
+                    .as_deref()
+                    .map(|image| link::make_absolute(config, image)),
+                ..config.marketing.social.clone()
+            },
+            footer: FooterContext::new(config),
+            newsletter: config
+                .marketing
+                .newsletter
+                .as_ref()
+                .map(NewsletterContext::new),
+            comments: config.components.comments.as_ref().and_then(|comments| {
+                CommentsContext::new(comments, config.project.repository.as_deref())
+            }),
+            preview: preview::context(config),
+            web_manifest: WebManifestContext::new(config),
+        };
+        layout.nav_links = layout.collect_nav_links();
+        Ok(layout)
+    }
+
+    pub fn path_prefix(&self) -> &Option<String> {
+        &self.path_prefix
+    }
+
+    /// The links in the top level nav, in the order `nav.html` renders them
+    pub fn nav_links(&self) -> &[NavLink] {
+        &self.nav_links
+    }
+
+    fn collect_nav_links(&self) -> Vec<NavLink> {
+        let link = |name: &str, path: &String| NavLink {
+            name: name.to_owned(),
+            path: path.clone(),
+        };
+        let mut links = vec![link("Home", &self.home_link)];
+        for page in self.additional_pages.iter().flatten() {
+            links.push(link(&page.name, &page.path));
+        }
+        links.extend(self.artifacts_link.iter().map(|l| link("Install", l)));
+        links.extend(self.mdbook_link.iter().map(|l| link("Docs", l)));
+        links.extend(self.docs_link.iter().map(|l| link("Docs", l)));
+        links.extend(self.rustdoc_link.iter().map(|l| link("API", l)));
+        links.extend(self.funding_link.iter().map(|l| link("Funding", l)));
+        links.extend(
+            self.support_matrix_link
+                .iter()
+                .map(|l| link("Platforms", l)),
+        );
+        links.extend(self.features_link.iter().map(|l| link("Features", l)));
+        links.extend(self.roadmap_link.iter().map(|l| link("Roadmap", l)));
+        links.extend(self.benchmarks_link.iter().map(|l| link("Benchmarks", l)));
+        links.extend(self.blog_link.iter().map(|l| link("Blog", l)));
+        links.extend(self.changelog_link.iter().map(|l| link("Changelog", l)));
+        links
     }
 
     /// Generates a new layout context to use for the workspace index page.
@@ -146,8 +412,14 @@ impl LayoutContext {
             project_name: workspace_config.workspace.name.clone().unwrap_or_default(),
             theme: workspace_config.styles.theme.as_css_classes(),
             oranda_css_path: css_path,
+            font_stylesheets: workspace_config.styles.theme.font_stylesheets(),
             has_additional_css: !workspace_config.styles.additional_css.is_empty(),
             path_prefix: workspace_config.build.path_prefix.clone(),
+            compat_css_path: workspace_config
+                .build
+                .compat
+                .then(|| css::get_compat_css_link(&workspace_config.build.path_prefix)),
+            preview: preview::context(workspace_config),
             ..Default::default()
         })
     }
@@ -165,4 +437,38 @@ impl OrandaTheme {
             OrandaTheme::Cupcake => "cupcake",
         }
     }
+
+    /// Gets the color of this theme's page background, for places outside of our CSS that
+    /// need it, like the web manifest.
+    ///
+    /// These need to be kept in sync with the variables in `oranda-css`.
+    pub fn background_color(&self) -> &'static str {
+        match self {
+            OrandaTheme::Light | OrandaTheme::AxoLight => "#ffffff",
+            OrandaTheme::Dark | OrandaTheme::AxoDark | OrandaTheme::Hacker => "#141414",
+            OrandaTheme::Cupcake => "#faf7f5",
+        }
+    }
+
+    /// Gets the webfont stylesheets this theme's CSS imports, so we can hint
+    /// the browser to start fetching them before oranda.css has been parsed.
+    ///
+    /// These need to be kept in sync with the `@import`s in `oranda-css`.
+    pub fn font_stylesheets(&self) -> Vec<&'static str> {
+        const FIRA_SANS: &str =
+            "https://fonts.googleapis.com/css2?family=Fira+Sans:wght@400;700;900&display=swap";
+        let theme_font = match self {
+            OrandaTheme::Light | OrandaTheme::Dark => None,
+            OrandaTheme::AxoLight | OrandaTheme::AxoDark => Some(
+                "https://fonts.googleapis.com/css2?family=Comfortaa:wght@400;700&display=swap",
+            ),
+            OrandaTheme::Hacker => Some(
+                "https://fonts.googleapis.com/css2?family=IBM+Plex+Mono:wght@400;600;700&display=swap",
+            ),
+            OrandaTheme::Cupcake => Some(
+                "https://fonts.googleapis.com/css2?family=Inter:wght@400;500;600&display=swap",
+            ),
+        };
+        std::iter::once(FIRA_SANS).chain(theme_font).collect()
+    }
 }
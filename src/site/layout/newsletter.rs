@@ -0,0 +1,75 @@
+//! The newsletter signup form on the index and blog pages, from `marketing.newsletter`.
+
+use serde::Serialize;
+use url::Url;
+
+use crate::config::{NewsletterConfig, NewsletterProvider};
+
+#[derive(Serialize, Debug)]
+pub struct NewsletterContext {
+    pub action: String,
+    pub email_field: String,
+    /// Extra fields the provider wants sent along, from their names to their values
+    pub hidden_fields: Vec<(String, String)>,
+    /// The name of the field Mailchimp uses to catch bots, which people must leave empty
+    pub honeypot_field: Option<String>,
+    pub title: String,
+    pub description: Option<String>,
+    pub button_text: String,
+}
+
+impl NewsletterContext {
+    pub fn new(config: &NewsletterConfig) -> Self {
+        let (action, email_field, hidden_fields, honeypot_field) = match &config.provider {
+            NewsletterProvider::Buttondown(buttondown) => (
+                format!(
+                    "https://buttondown.com/api/emails/embed-subscribe/{}",
+                    buttondown.username
+                ),
+                "email".to_owned(),
+                vec![("embed".to_owned(), "1".to_owned())],
+                None,
+            ),
+            NewsletterProvider::Mailchimp(mailchimp) => (
+                mailchimp.action.clone(),
+                "EMAIL".to_owned(),
+                vec![],
+                mailchimp_honeypot(&mailchimp.action),
+            ),
+            NewsletterProvider::Form(form) => (
+                form.action.clone(),
+                form.email_field
+                    .clone()
+                    .unwrap_or_else(|| "email".to_owned()),
+                vec![],
+                None,
+            ),
+        };
+        Self {
+            action,
+            email_field,
+            hidden_fields,
+            honeypot_field,
+            title: config
+                .title
+                .clone()
+                .unwrap_or_else(|| "Get release announcements".to_owned()),
+            description: config.description.clone(),
+            button_text: config
+                .button_text
+                .clone()
+                .unwrap_or_else(|| "Subscribe".to_owned()),
+        }
+    }
+}
+
+/// Mailchimp's embed code names its bot trap after the `u` and `id` in the form's action
+fn mailchimp_honeypot(action: &str) -> Option<String> {
+    let url = Url::parse(action).ok()?;
+    let query_param = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+    Some(format!("b_{}_{}", query_param("u")?, query_param("id")?))
+}
@@ -0,0 +1,34 @@
+//! The banner on preview builds, see `build.preview`
+
+use axoproject::GithubRepo;
+use serde::Serialize;
+
+use crate::config::Config;
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct PreviewContext {
+    /// Like "pr-42"
+    pub name: String,
+    /// The pull request this is a preview of, if we could tell
+    pub link: Option<String>,
+}
+
+pub fn context(config: &Config) -> Option<PreviewContext> {
+    let name = config.build.preview.as_ref()?.trim_matches('/').to_owned();
+    let link = pull_request_link(&name, config.project.repository.as_deref());
+    Some(PreviewContext { name, link })
+}
+
+/// Links previews named like "pr-42" (or "pr42", or "pull-42") to the pull request on GitHub
+pub fn pull_request_link(name: &str, repository: Option<&str>) -> Option<String> {
+    let lower = name.to_ascii_lowercase();
+    let number = ["pull-", "pull", "pr-", "pr"]
+        .iter()
+        .find_map(|prefix| lower.strip_prefix(prefix))?;
+    let number: u64 = number.parse().ok()?;
+    let repo = GithubRepo::from_url(repository?).ok()?;
+    Some(format!(
+        "https://github.com/{}/{}/pull/{number}",
+        repo.owner, repo.name
+    ))
+}
@@ -0,0 +1,235 @@
+//! The `site.webmanifest`, app icons and theme color from `styles.web_manifest`.
+
+use std::io::Cursor;
+
+use camino::Utf8Path;
+use image::codecs::ico::{IcoEncoder, IcoFrame};
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageOutputFormat, Rgba, RgbaImage};
+use serde::Serialize;
+use serde_json::json;
+
+use crate::config::{Config, WebManifestConfig};
+use crate::errors::*;
+use crate::paths::resolve_asset;
+use crate::site::link;
+use crate::site::writer::SiteWriter;
+
+pub const MANIFEST_FILENAME: &str = "site.webmanifest";
+
+/// Where an icon gets linked from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IconUse {
+    /// A `<link rel="icon">` in every page
+    Favicon,
+    /// A `<link rel="apple-touch-icon">` in every page, which iOS uses for home screens
+    AppleTouch,
+    /// Only listed in the manifest
+    Manifest,
+}
+
+const ICONS: &[(&str, u32, IconUse)] = &[
+    ("favicon-16x16.png", 16, IconUse::Favicon),
+    ("favicon-32x32.png", 32, IconUse::Favicon),
+    ("apple-touch-icon.png", 180, IconUse::AppleTouch),
+    ("android-chrome-192x192.png", 192, IconUse::Manifest),
+    ("android-chrome-512x512.png", 512, IconUse::Manifest),
+];
+
+/// The sizes packed into `favicon.ico`, for browsers that ask for it without looking at our links
+const FAVICON_SIZES: &[u32] = &[16, 32, 48];
+
+#[derive(Serialize, Debug)]
+pub struct IconLink {
+    pub rel: &'static str,
+    pub sizes: String,
+    pub href: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct WebManifestContext {
+    pub manifest_path: String,
+    pub theme_color: String,
+    /// The `<link>`s to the icons, empty if there's no image to make them from
+    pub icons: Vec<IconLink>,
+}
+
+impl WebManifestContext {
+    pub fn new(config: &Config) -> Option<Self> {
+        let manifest = config.styles.web_manifest.as_ref()?;
+        let prefix = &config.build.path_prefix;
+        let icons = if source_icon(config).is_some() {
+            ICONS
+                .iter()
+                .filter_map(|(filename, size, icon_use)| {
+                    let rel = match icon_use {
+                        IconUse::Favicon => "icon",
+                        IconUse::AppleTouch => "apple-touch-icon",
+                        IconUse::Manifest => return None,
+                    };
+                    Some(IconLink {
+                        rel,
+                        sizes: format!("{size}x{size}"),
+                        href: link::generate_relative(prefix, filename),
+                    })
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+        Some(Self {
+            manifest_path: link::generate_relative(prefix, MANIFEST_FILENAME),
+            theme_color: theme_color(manifest, config).to_owned(),
+            icons,
+        })
+    }
+}
+
+/// The image the icons are made from: `icon` if it's set, otherwise the logo if it's a local
+/// image we can resize
+pub fn source_icon(config: &Config) -> Option<&str> {
+    let manifest = config.styles.web_manifest.as_ref()?;
+    if let Some(icon) = manifest.icon.as_deref() {
+        return Some(icon);
+    }
+    let logo = config.styles.logo.as_deref()?;
+    let extension = logo.rsplit('.').next()?.to_lowercase();
+    let resizable = matches!(extension.as_str(), "png" | "jpg" | "jpeg" | "gif");
+    (resizable && !logo.contains("://")).then_some(logo)
+}
+
+fn theme_color<'a>(manifest: &'a WebManifestConfig, config: &Config) -> &'a str {
+    manifest
+        .theme_color
+        .as_deref()
+        .unwrap_or_else(|| config.styles.theme.background_color())
+}
+
+fn background_color<'a>(manifest: &'a WebManifestConfig, config: &Config) -> &'a str {
+    manifest
+        .background_color
+        .as_deref()
+        .unwrap_or_else(|| config.styles.theme.background_color())
+}
+
+/// The contents of `site.webmanifest`
+pub fn manifest(config: &Config) -> serde_json::Value {
+    let default = WebManifestConfig::default();
+    let manifest = config.styles.web_manifest.as_ref().unwrap_or(&default);
+    let name = manifest.name.as_deref().unwrap_or(&config.project.name);
+    // With a trailing slash, so that the scope covers every page under the prefix
+    let home = link::generate_relative(&config.build.path_prefix, "");
+    let start_url = format!("{}/", home.trim_end_matches('/'));
+    let icons: Vec<_> = if source_icon(config).is_some() {
+        ICONS
+            .iter()
+            .filter(|(_, size, _)| *size >= 192)
+            .map(|(filename, size, _)| {
+                json!({
+                    "src": link::generate_relative(&config.build.path_prefix, filename),
+                    "sizes": format!("{size}x{size}"),
+                    "type": "image/png",
+                })
+            })
+            .collect()
+    } else {
+        vec![]
+    };
+    let mut value = json!({
+        "name": name,
+        "short_name": manifest.short_name.as_deref().unwrap_or(name),
+        "start_url": start_url,
+        "scope": start_url,
+        "display": manifest.display,
+        "theme_color": theme_color(manifest, config),
+        "background_color": background_color(manifest, config),
+        "icons": icons,
+    });
+    if let Some(description) = &config.project.description {
+        value["description"] = json!(description);
+    }
+    value
+}
+
+/// Writes `site.webmanifest`, and the icons made from [`source_icon`][] if there is one
+pub fn write_web_manifest(config: &Config, writer: &mut dyn SiteWriter) -> Result<()> {
+    let Some(manifest_config) = config.styles.web_manifest.as_ref() else {
+        return Ok(());
+    };
+    if let Some(icon) = source_icon(config) {
+        let path = resolve_asset(&config.root_dir, icon);
+        let failed = |details: String| OrandaError::WebManifestIconFailed {
+            path: path.clone(),
+            details,
+        };
+        let bytes = std::fs::read(&path).map_err(|e| failed(e.to_string()))?;
+        let source = image::load_from_memory(&bytes).map_err(|e| failed(e.to_string()))?;
+        let background = parse_hex_color(background_color(manifest_config, config));
+        for (filename, size, icon_use) in ICONS {
+            // iOS fills in transparency with black, so give it the page's background instead
+            let fill = (*icon_use == IconUse::AppleTouch).then_some(background);
+            let resized = square(&source, *size, fill);
+            writer.write(
+                Utf8Path::new(filename),
+                &encode_png(&resized).map_err(failed)?,
+            )?;
+        }
+        if config.styles.favicon.is_none() {
+            let ico = encode_ico(&source).map_err(failed)?;
+            writer.write(Utf8Path::new("favicon.ico"), &ico)?;
+        }
+    }
+    let contents = serde_json::to_string_pretty(&manifest(config))?;
+    writer.write(Utf8Path::new(MANIFEST_FILENAME), contents.as_bytes())
+}
+
+/// Scales `source` to fit a `size` by `size` square, centered on a transparent background, or
+/// on `fill` if it's set
+fn square(source: &DynamicImage, size: u32, fill: Option<Rgba<u8>>) -> RgbaImage {
+    let scaled = source.resize(size, size, FilterType::Lanczos3).to_rgba8();
+    let mut canvas = RgbaImage::from_pixel(size, size, fill.unwrap_or(Rgba([0, 0, 0, 0])));
+    let x = (size - scaled.width()) / 2;
+    let y = (size - scaled.height()) / 2;
+    image::imageops::overlay(&mut canvas, &scaled, x.into(), y.into());
+    canvas
+}
+
+fn encode_png(image: &RgbaImage) -> std::result::Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    DynamicImage::ImageRgba8(image.clone())
+        .write_to(&mut Cursor::new(&mut bytes), ImageOutputFormat::Png)
+        .map_err(|e| e.to_string())?;
+    Ok(bytes)
+}
+
+fn encode_ico(source: &DynamicImage) -> std::result::Result<Vec<u8>, String> {
+    let mut frames = Vec::new();
+    for size in FAVICON_SIZES {
+        let resized = square(source, *size, None);
+        let frame = IcoFrame::as_png(resized.as_raw(), *size, *size, image::ColorType::Rgba8)
+            .map_err(|e| e.to_string())?;
+        frames.push(frame);
+    }
+    let mut bytes = Vec::new();
+    IcoEncoder::new(&mut bytes)
+        .encode_images(&frames)
+        .map_err(|e| e.to_string())?;
+    Ok(bytes)
+}
+
+/// Parses a `#rgb` or `#rrggbb` color, falling back to white for anything else CSS allows
+fn parse_hex_color(color: &str) -> Rgba<u8> {
+    let hex = color.trim().trim_start_matches('#');
+    let hex = if hex.len() == 3 {
+        hex.chars().flat_map(|c| [c, c]).collect()
+    } else {
+        hex.to_owned()
+    };
+    match u32::from_str_radix(&hex, 16) {
+        Ok(rgb) if hex.len() == 6 => {
+            let [_, r, g, b] = rgb.to_be_bytes();
+            Rgba([r, g, b, 255])
+        }
+        _ => Rgba([255, 255, 255, 255]),
+    }
+}
@@ -0,0 +1,61 @@
+use serde::Serialize;
+
+use crate::data::licenses::{DependencyLicenses, LicensedCrate, ProjectLicense};
+
+#[derive(Serialize, Debug)]
+pub struct LicensesContext {
+    /// The project's own license files, shown above its dependencies' licenses
+    pub project_licenses: Vec<ProjectLicense>,
+    pub licenses: Vec<LicenseRow>,
+    /// How many different crates are listed, for the summary at the top
+    pub crate_count: usize,
+}
+
+#[derive(Serialize, Debug)]
+pub struct LicenseRow {
+    pub id: String,
+    pub name: Option<String>,
+    pub text: Option<String>,
+    pub crates: Vec<LicensedCrate>,
+    /// The id of the license's section on the page
+    pub anchor: String,
+}
+
+impl LicensesContext {
+    pub fn new(dependency_licenses: &DependencyLicenses) -> Self {
+        let mut crates = dependency_licenses
+            .licenses
+            .iter()
+            .flat_map(|license| &license.crates)
+            .map(|krate| (&krate.name, &krate.version))
+            .collect::<Vec<_>>();
+        crates.sort();
+        crates.dedup();
+        let mut licenses: Vec<LicenseRow> = vec![];
+        for license in &dependency_licenses.licenses {
+            // cargo-about lists a license once per distinct copy of its text
+            let mut anchor = format!("license-{}", slug::slugify(&license.id));
+            let copies = licenses.iter().filter(|row| row.id == license.id).count();
+            if copies > 0 {
+                anchor.push_str(&format!("-{}", copies + 1));
+            }
+            licenses.push(LicenseRow {
+                id: license.id.clone(),
+                name: license.name.clone(),
+                text: license.text.clone(),
+                crates: license.crates.clone(),
+                anchor,
+            });
+        }
+        Self {
+            project_licenses: vec![],
+            licenses,
+            crate_count: crates.len(),
+        }
+    }
+
+    pub fn with_project_licenses(mut self, project_licenses: Vec<ProjectLicense>) -> Self {
+        self.project_licenses = project_licenses;
+        self
+    }
+}
@@ -1,5 +1,7 @@
 use crate::config::Config;
-use camino::Utf8PathBuf;
+use crate::site::widgets;
+use camino::{Utf8Path, Utf8PathBuf};
+use url::Url;
 
 pub fn generate_relative(path_prefix: &Option<String>, file_name: &str) -> String {
     // NOTE: intentionally no leading `/` here because it makes camino add a phantom `/` or `\`
@@ -14,12 +16,36 @@ pub fn generate_relative(path_prefix: &Option<String>, file_name: &str) -> Strin
     sanitize_path(&path, file_name)
 }
 
-/// Generates an absolute path to the end-user hosted version of a file. Returns an option, in case
-/// the `url` configuration option wasn't set.
+/// The URL the site is served at: `build.base_url`, or else `project.homepage`, unless that's
+/// just the repository. Never ends with a `/`.
+pub fn base_url(config: &Config) -> Option<&str> {
+    let trim = |url: &str| url.trim().trim_end_matches('/').to_owned();
+    let homepage =
+        config.project.homepage.as_deref().filter(|homepage| {
+            config.project.repository.as_deref().map(trim) != Some(trim(homepage))
+        });
+    config
+        .build
+        .base_url
+        .as_deref()
+        .or(homepage)
+        .map(|url| url.trim().trim_end_matches('/'))
+        .filter(|url| !url.is_empty())
+}
+
+/// Generates an absolute URL to the end-user hosted version of a file. Returns None if we don't
+/// know where the site is hosted, because neither `build.base_url` nor `project.homepage` is set.
 pub fn generate_absolute(config: &Config, file_name: &str) -> Option<String> {
-    let url = "http://127.0.0.1:7979"; // FIXME: wip
-    let url = url.trim_end_matches('/');
-    let path = if let Some(prefix) = &config.build.path_prefix {
+    let url = base_url(config)?;
+    // The base URL of a site hosted under a path, like "https://axodotdev.github.io/oranda",
+    // usually ends with the prefix already
+    let prefix = config
+        .build
+        .path_prefix
+        .as_deref()
+        .map(|prefix| prefix.trim_matches('/'))
+        .filter(|prefix| !prefix.is_empty() && !url.ends_with(&format!("/{prefix}")));
+    let path = if let Some(prefix) = prefix {
         format!("{}/{}", prefix, file_name)
     } else {
         file_name.to_owned()
@@ -29,6 +55,53 @@ pub fn generate_absolute(config: &Config, file_name: &str) -> Option<String> {
     Some(format!("{}{}", url, sanitized_path))
 }
 
+/// The absolute URL a page we're building will be served at, from its filename, like
+/// "https://oranda.axo.dev/changelog/" for "changelog.html"
+pub fn page_url(config: &Config, filename: &str) -> Option<String> {
+    let path = pretty_path(filename);
+    let mut url = generate_absolute(config, &path)?;
+    if (path.is_empty() || path.ends_with('/')) && !url.ends_with('/') {
+        url.push('/');
+    }
+    Some(url)
+}
+
+/// Like [`generate_absolute`][], but falls back to a link relative to the site's root, for
+/// places where an absolute URL is better but not required
+pub fn generate_absolute_or_relative(config: &Config, file_name: &str) -> String {
+    generate_absolute(config, file_name)
+        .unwrap_or_else(|| generate_relative(&config.build.path_prefix, file_name))
+}
+
+/// Resolves a link against the site's base URL, so that "static/og.png" and "/static/og.png"
+/// become full URLs. Full URLs, and everything if we don't know where the site is hosted, are
+/// left alone.
+pub fn make_absolute(config: &Config, link: &str) -> String {
+    base_url(config)
+        .and_then(|url| Url::parse(&format!("{url}/")).ok())
+        .and_then(|url| url.join(link).ok())
+        .map(String::from)
+        .unwrap_or_else(|| link.to_owned())
+}
+
+/// Turns the filename of a page we're building into the path it'll be served at, mirroring
+/// the "pretty link" rewriting we do when writing pages (`page.html` -> `page/`). Widgets keep
+/// their filename.
+pub fn pretty_path(filename: &str) -> String {
+    let path = Utf8Path::new(filename);
+    if path.extension() != Some("html") || widgets::is_widget(filename) {
+        return filename.to_owned();
+    }
+    if path.file_name() == Some("index.html") {
+        path.parent()
+            .filter(|p| !p.as_str().is_empty())
+            .map(|p| format!("{p}/"))
+            .unwrap_or_default()
+    } else {
+        format!("{}/", path.with_extension(""))
+    }
+}
+
 fn sanitize_path(path: &str, file_name: &str) -> String {
     // Break the url up into its segments, and precent-encode each part,
     // prepending a `/` before each part to make the resulting URL absolute
@@ -0,0 +1,153 @@
+//! Terminal recordings, embedded with `{{asciinema src="demo.cast"}}`
+//!
+//! Recordings in asciinema's `.cast` format are played by a small player we write to the site
+//! ourselves (see [`write_player`][crate::site::javascript::write_player]), so nothing is
+//! loaded from a CDN. Recordings rendered to SVG, like the ones svg-term makes, are shown as
+//! images. Either way, if `src` is a file in the project, it's copied to "/recordings/".
+//!
+//! Like other [shortcodes][crate::site::markdown::shortcodes], it's swapped for a placeholder
+//! before the markdown is rendered, and for the player after it's sanitized, since the sanitizer
+//! would otherwise strip the player's attributes and script.
+
+use axoasset::LocalAsset;
+use camino::Utf8PathBuf;
+
+use crate::config::Config;
+use crate::errors::*;
+use crate::site::markdown::shortcodes;
+use crate::site::{javascript, link};
+
+/// The class of the element the player script looks for
+pub const PLAYER_CLASS: &str = "asciinema-player";
+/// Where recordings that live in the project are copied to in the dist dir
+pub const RECORDINGS_DIR: &str = "recordings";
+
+const PLACEHOLDER: &str = "ORANDAASCIINEMARECORDING";
+
+/// A recording from an `{{asciinema ...}}` shortcode
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recording {
+    pub src: String,
+    pub title: Option<String>,
+    pub cols: Option<u32>,
+    pub rows: Option<u32>,
+    pub autoplay: bool,
+    pub looped: bool,
+}
+
+impl Recording {
+    /// Reads the attributes of a shortcode, like `src="demo.cast" cols=80 autoplay`
+    pub fn parse(attributes: &str) -> Option<Self> {
+        let mut recording = Recording::default();
+        for (key, value) in shortcodes::parse_attributes(attributes) {
+            match key.as_str() {
+                "src" => recording.src = value.unwrap_or_default(),
+                "title" => recording.title = value,
+                "cols" => recording.cols = value.and_then(|v| v.parse().ok()),
+                "rows" => recording.rows = value.and_then(|v| v.parse().ok()),
+                "autoplay" => recording.autoplay = value.map_or(true, |v| v != "false"),
+                "loop" => recording.looped = value.map_or(true, |v| v != "false"),
+                other => tracing::warn!("Ignoring unknown asciinema attribute `{other}`"),
+            }
+        }
+        (!recording.src.is_empty()).then_some(recording)
+    }
+
+    fn is_svg(&self) -> bool {
+        self.src
+            .split(['?', '#'])
+            .next()
+            .is_some_and(|path| path.to_ascii_lowercase().ends_with(".svg"))
+    }
+}
+
+/// Swaps every shortcode outside of code for a placeholder, returning the recordings in the
+/// order their placeholders are numbered
+pub fn extract(markdown: &str) -> (String, Vec<Recording>) {
+    if !markdown.contains("asciinema") {
+        return (markdown.to_owned(), vec![]);
+    }
+    let mut recordings = vec![];
+    let output = shortcodes::replace_outside_code(markdown, |name, attributes, original| {
+        if name != "asciinema" {
+            return original.to_owned();
+        }
+        match Recording::parse(attributes) {
+            Some(recording) => {
+                recordings.push(recording);
+                format!("{PLACEHOLDER}{}", recordings.len() - 1)
+            }
+            None => {
+                tracing::warn!("Skipping an asciinema recording without a `src`");
+                original.to_owned()
+            }
+        }
+    });
+    (output, recordings)
+}
+
+/// Swaps the placeholders in rendered HTML for the recordings, copying any that live in the
+/// project to the dist dir
+pub fn embed(html: &str, recordings: &[Recording], config: &Config) -> Result<String> {
+    let players = recordings
+        .iter()
+        .map(|recording| render(recording, config))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(shortcodes::swap_placeholders(html, PLACEHOLDER, &players))
+}
+
+fn render(recording: &Recording, config: &Config) -> Result<String> {
+    let src = escape(&recording_url(&recording.src, config)?);
+    let title = recording.title.as_deref().map(escape);
+    let caption = title
+        .as_ref()
+        .map(|title| format!("<figcaption>{title}</figcaption>"))
+        .unwrap_or_default();
+    if recording.is_svg() {
+        let alt = title.as_deref().unwrap_or("Terminal recording");
+        return Ok(format!(
+            r#"<figure class="asciinema"><img src="{src}" alt="{alt}" loading="lazy" />{caption}</figure>"#
+        ));
+    }
+
+    let mut attributes = format!(r#"class="{PLAYER_CLASS}" data-src="{src}""#);
+    if let Some(cols) = recording.cols {
+        attributes.push_str(&format!(r#" data-cols="{cols}""#));
+    }
+    if let Some(rows) = recording.rows {
+        attributes.push_str(&format!(r#" data-rows="{rows}""#));
+    }
+    if recording.autoplay {
+        attributes.push_str(" data-autoplay");
+    }
+    if recording.looped {
+        attributes.push_str(" data-loop");
+    }
+    let script = javascript::build_player_path(&config.build.path_prefix);
+    Ok(format!(
+        r#"<figure class="asciinema"><div {attributes}><pre class="asciinema-screen"></pre><button class="asciinema-toggle" type="button" aria-label="Play">▶</button></div>{caption}<noscript><a href="{src}">Download the recording</a></noscript></figure><script src="{script}" defer></script>"#
+    ))
+}
+
+/// Links to a recording, copying it over first if it's a file in the project
+fn recording_url(src: &str, config: &Config) -> Result<String> {
+    let path = config.path(src);
+    let is_local = !src.contains("://") && !src.starts_with('/') && path.is_file();
+    let Some(file_name) = path.file_name().filter(|_| is_local) else {
+        return Ok(src.to_owned());
+    };
+    let dest_dir = Utf8PathBuf::from(&config.build.dist_dir).join(RECORDINGS_DIR);
+    LocalAsset::create_dir_all(&dest_dir)?;
+    LocalAsset::copy(&path, &dest_dir)?;
+    Ok(link::generate_relative(
+        &config.build.path_prefix,
+        &format!("{RECORDINGS_DIR}/{file_name}"),
+    ))
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,26 @@
+//! Emoji shortcodes, like `:rocket:`, which are swapped for the emoji they stand for
+//!
+//! We use the same shortcodes as GitHub, so READMEs and release notes written for GitHub look
+//! the same on the site. Shortcodes we don't know, and the handful of GitHub's that aren't
+//! Unicode emoji (like `:octocat:`), are left as they are.
+
+use std::sync::OnceLock;
+
+use regex::{Captures, Regex};
+
+use crate::site::markdown::shortcodes;
+
+/// Swaps every emoji shortcode outside of code for its emoji
+pub fn replace(markdown: &str) -> String {
+    static EMOJI: OnceLock<Regex> = OnceLock::new();
+    let pattern = EMOJI.get_or_init(|| Regex::new(r":([a-z0-9_+-]+):").unwrap());
+    if !markdown.contains(':') {
+        return markdown.to_owned();
+    }
+    shortcodes::replace_matches_outside_code(markdown, pattern, |captures: &Captures| {
+        match emojis::get_by_shortcode(&captures[1]) {
+            Some(emoji) => emoji.as_str().to_owned(),
+            None => captures[0].to_owned(),
+        }
+    })
+}
@@ -0,0 +1,113 @@
+//! Sharing markdown between pages, and between workspace members, with
+//! `<!-- include: ../shared/install.md -->` or `{{ include file="../shared/install.md" }}`
+//!
+//! Includes are resolved before anything else happens to a page, so included files can use
+//! shortcodes and include other files themselves. Paths are relative to the project, or to the
+//! file doing the including for nested includes, and have to stay inside `build.include_root`.
+
+use std::sync::OnceLock;
+
+use axoasset::LocalAsset;
+use camino::{Utf8Path, Utf8PathBuf};
+use regex::{Captures, Regex};
+
+use crate::config::Config;
+use crate::errors::*;
+use crate::site::markdown::shortcodes;
+
+/// Swaps every include outside of code for the contents of the file it points at
+pub fn resolve(markdown: &str, config: &Config) -> Result<String> {
+    if !markdown.contains("include") {
+        return Ok(markdown.to_owned());
+    }
+    let dir = &config.root_dir;
+    let root = match &config.build.include_root {
+        Some(root) => dir.join(root),
+        None => dir.clone(),
+    };
+    let root = root
+        .canonicalize_utf8()
+        .map_err(|_| OrandaError::PathDoesNotExist {
+            path: root.to_string(),
+        })?;
+    resolve_in(markdown, dir, &root, &mut vec![])
+}
+
+/// Resolves the includes in `markdown`, which lives in `dir`. `stack` is the files we're in the
+/// middle of including, so that we can tell when they include each other.
+fn resolve_in(
+    markdown: &str,
+    dir: &Utf8Path,
+    root: &Utf8Path,
+    stack: &mut Vec<Utf8PathBuf>,
+) -> Result<String> {
+    static INCLUDE: OnceLock<Regex> = OnceLock::new();
+    let pattern = INCLUDE.get_or_init(|| {
+        Regex::new(r"<!--\s*include:\s*(.+?)\s*-->|\{\{\s*include\s+([^}]*?)\s*\}\}").unwrap()
+    });
+    let mut error = None;
+    let output = shortcodes::replace_matches_outside_code(markdown, pattern, |captures| {
+        if error.is_some() {
+            return String::new();
+        }
+        let Some(path) = include_path(captures) else {
+            tracing::warn!("Skipping an include without a `file`");
+            return captures[0].to_owned();
+        };
+        include(&path, dir, root, stack).unwrap_or_else(|e| {
+            error = Some(e);
+            String::new()
+        })
+    });
+    match error {
+        Some(e) => Err(e),
+        None => Ok(output),
+    }
+}
+
+fn include_path(captures: &Captures) -> Option<String> {
+    if let Some(path) = captures.get(1) {
+        return Some(path.as_str().trim_matches(['"', '\'']).to_owned());
+    }
+    shortcodes::parse_attributes(captures.get(2)?.as_str())
+        .into_iter()
+        .find(|(key, _)| key == "file")
+        .and_then(|(_, value)| value)
+}
+
+fn include(
+    path: &str,
+    dir: &Utf8Path,
+    root: &Utf8Path,
+    stack: &mut Vec<Utf8PathBuf>,
+) -> Result<String> {
+    let joined = dir.join(path);
+    let full = joined
+        .canonicalize_utf8()
+        .map_err(|_| OrandaError::FileNotFound {
+            filedesc: "included file".to_owned(),
+            path: joined.to_string(),
+        })?;
+    if !full.starts_with(root) {
+        return Err(OrandaError::IncludeOutsideRoot {
+            path: path.to_owned(),
+            root: root.to_string(),
+        });
+    }
+    if stack.contains(&full) {
+        let chain = stack
+            .iter()
+            .chain([&full])
+            .map(|p| p.strip_prefix(root).unwrap_or(p).to_string())
+            .collect::<Vec<_>>()
+            .join(" -> ");
+        return Err(OrandaError::IncludeCycle { chain });
+    }
+
+    let contents = LocalAsset::load_string(&full)?;
+    let parent = full.parent().unwrap_or(root).to_owned();
+    stack.push(full);
+    let resolved = resolve_in(&contents, &parent, root, stack);
+    stack.pop();
+    Ok(resolved?.trim_end().to_owned())
+}
@@ -1,26 +1,47 @@
+use std::cell::RefCell;
 use std::collections::HashMap;
 
+pub mod asciinema;
+pub mod emoji;
+pub mod include;
+pub mod relative_links;
+pub mod shortcodes;
+pub mod sponsors;
 mod syntax_highlight;
 pub use syntax_highlight::syntax_themes::SyntaxTheme;
-pub use syntax_highlight::{dump_syntax_themes, syntax_highlight};
+pub use syntax_highlight::{
+    dump_syntax_themes, syntax_highlight, syntax_highlight_lines, HighlightedLines,
+};
 
+use crate::config::{Config, MarkdownSanitize};
 use crate::errors::*;
 
 use ammonia::Builder;
 use comrak::adapters::SyntaxHighlighterAdapter;
 use comrak::{self, ComrakOptions, ComrakPlugins};
+use serde::Serialize;
 
 pub struct Adapters<'a> {
     syntax_theme: &'a SyntaxTheme,
+    /// Set for untrusted markdown: highlighted code is kept here and swapped for a
+    /// placeholder, so that its inline styles don't have to get past the sanitizer
+    highlighted: Option<RefCell<Vec<String>>>,
 }
+
+const HIGHLIGHTED_CODE_PLACEHOLDER: &str = "ORANDAHIGHLIGHTEDCODE";
 impl SyntaxHighlighterAdapter for Adapters<'_> {
     fn highlight(&self, lang: Option<&str>, code: &str) -> String {
         let highlighted_code = syntax_highlight(lang, code, self.syntax_theme);
 
         // requires a string to be returned
-        match highlighted_code {
-            Ok(code) => code,
-            Err(_) => String::new(),
+        let code = highlighted_code.unwrap_or_default();
+        match &self.highlighted {
+            Some(highlighted) => {
+                let mut highlighted = highlighted.borrow_mut();
+                highlighted.push(code);
+                format!("{HIGHLIGHTED_CODE_PLACEHOLDER}{}", highlighted.len() - 1)
+            }
+            None => code,
         }
     }
 
@@ -33,7 +54,7 @@ impl SyntaxHighlighterAdapter for Adapters<'_> {
     }
 }
 
-fn initialize_comrak_options() -> ComrakOptions {
+pub(crate) fn initialize_comrak_options() -> ComrakOptions {
     let mut options = ComrakOptions::default();
 
     options.extension.strikethrough = true;
@@ -47,17 +68,154 @@ fn initialize_comrak_options() -> ComrakOptions {
     options
 }
 
-pub fn to_html(markdown: &str, syntax_theme: &SyntaxTheme) -> Result<String> {
-    let options = initialize_comrak_options();
+/// Renders markdown for the public site, with sponsor-only sections swapped for teasers
+pub fn to_html(markdown: &str, config: &Config) -> Result<String> {
+    let untrusted = config.markdown.sanitize == MarkdownSanitize::All;
+    render(markdown, config, false, untrusted)
+}
+
+/// Renders markdown including its sponsor-only sections
+pub fn to_html_for_sponsors(markdown: &str, config: &Config) -> Result<String> {
+    let untrusted = config.markdown.sanitize == MarkdownSanitize::All;
+    render(markdown, config, true, untrusted)
+}
+
+/// Renders the notes of a release (or other markdown fetched from GitHub), which
+/// `markdown.sanitize` may say not to trust
+pub fn release_to_html(markdown: &str, config: &Config) -> Result<String> {
+    let untrusted = config.markdown.sanitize != MarkdownSanitize::Basic;
+    render(markdown, config, false, untrusted)
+}
+
+/// Where the ids in untrusted markdown are moved to, so they can't clash with ours
+const UNTRUSTED_ID_PREFIX: &str = "user-content-";
+
+fn render(markdown: &str, config: &Config, for_sponsors: bool, untrusted: bool) -> Result<String> {
+    let mut options = initialize_comrak_options();
+    let extensions = &config.markdown.extensions;
+    options.extension.footnotes = extensions.footnotes;
+    options.extension.tasklist = extensions.tasklists;
+    options.extension.description_lists = extensions.definition_lists;
 
     let mut plugins = ComrakPlugins::default();
-    let adapter = Adapters { syntax_theme };
+    let adapter = Adapters {
+        syntax_theme: &config.styles.syntax_theme,
+        highlighted: untrusted.then(RefCell::default),
+    };
     plugins.render.codefence_syntax_highlighter = Some(&adapter);
 
-    let unsafe_html = comrak::markdown_to_html_with_plugins(markdown, &options, &plugins);
-    let safe_html = Builder::new()
-        .add_generic_attributes(&["style", "class", "id"])
-        .clean(&unsafe_html)
-        .to_string();
-    Ok(safe_html)
+    // Including files, shortcodes and recordings all put things on the page that the sanitizer
+    // never sees, so untrusted markdown doesn't get them
+    let markdown = if untrusted {
+        markdown.to_owned()
+    } else {
+        include::resolve(markdown, config)?
+    };
+    let (markdown, sponsor_sections) = if for_sponsors {
+        (markdown, vec![])
+    } else {
+        sponsors::extract(&markdown)
+    };
+    let (markdown, recordings) = if untrusted {
+        (markdown, vec![])
+    } else {
+        asciinema::extract(&markdown)
+    };
+    let shortcodes = if markdown.contains("{{") && !untrusted {
+        Some(shortcodes::Shortcodes::new(config)?)
+    } else {
+        None
+    };
+    let (markdown, found) = match &shortcodes {
+        Some(shortcodes) => shortcodes.extract(&markdown),
+        None => (markdown, vec![]),
+    };
+    let markdown = if extensions.emoji {
+        emoji::replace(&markdown)
+    } else {
+        markdown
+    };
+    let unsafe_html = comrak::markdown_to_html_with_plugins(&markdown, &options, &plugins);
+    let mut sanitizer = Builder::new();
+    let unsafe_html = if untrusted {
+        // Nothing may restyle the page. Syntax highlighting is done with inline styles, but
+        // highlighted code is only put back in after sanitizing (see `Adapters::highlighted`)
+        sanitizer
+            .add_generic_attributes(&["class", "id"])
+            .id_prefix(Some(UNTRUSTED_ID_PREFIX))
+            .url_schemes(["http", "https", "mailto"].into_iter().collect())
+            .link_rel(Some("noopener noreferrer nofollow"));
+        // Keep links within the page, like footnotes, pointing at the prefixed ids
+        unsafe_html.replace("href=\"#", &format!("href=\"#{UNTRUSTED_ID_PREFIX}"))
+    } else {
+        sanitizer.add_generic_attributes(&["style", "class", "id"]);
+        unsafe_html
+    };
+    if extensions.footnotes {
+        // Footnotes are listed in a section at the end of the page
+        sanitizer.add_tags(&["section"]);
+    }
+    if extensions.tasklists {
+        // The checkboxes of task lists, which the sanitizer would otherwise drop
+        sanitizer
+            .add_tags(&["input"])
+            .add_tag_attributes("input", &["checked", "disabled"])
+            .set_tag_attribute_value("input", "type", "checkbox");
+    }
+    let safe_html = sanitizer.clean(&unsafe_html).to_string();
+    let safe_html = match adapter.highlighted {
+        Some(highlighted) => shortcodes::swap_placeholders(
+            &safe_html,
+            HIGHLIGHTED_CODE_PLACEHOLDER,
+            &highlighted.into_inner(),
+        ),
+        None => safe_html,
+    };
+    let html = match &shortcodes {
+        Some(shortcodes) => shortcodes.embed(&safe_html, &found, config)?,
+        None => safe_html,
+    };
+    let html = sponsors::embed(&html, &sponsor_sections, config)?;
+    asciinema::embed(&html, &recordings, config)
+}
+
+/// How many words a typical reader gets through in a minute
+const WORDS_PER_MINUTE: usize = 200;
+
+/// How long a rendered page is, so themes can show "5 min read"
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadingTime {
+    pub words: usize,
+    /// Rounded up, so never less than 1
+    pub minutes: usize,
+}
+
+impl ReadingTime {
+    /// Counts the words in rendered HTML, skipping over tags
+    pub fn from_html(html: &str) -> Self {
+        let mut text = String::with_capacity(html.len());
+        let mut in_tag = false;
+        for c in html.chars() {
+            match c {
+                '<' => in_tag = true,
+                // Tags separate words (think `<td>a</td><td>b</td>`)
+                '>' if in_tag => {
+                    in_tag = false;
+                    text.push(' ');
+                }
+                _ if !in_tag => text.push(c),
+                _ => {}
+            }
+        }
+        let words = text
+            .split_whitespace()
+            // Skip punctuation, including escaped punctuation like `&amp;`
+            .filter(|word| !(word.starts_with('&') && word.ends_with(';')))
+            .filter(|word| word.chars().any(char::is_alphanumeric))
+            .count();
+        Self {
+            words,
+            minutes: words.div_ceil(WORDS_PER_MINUTE).max(1),
+        }
+    }
 }
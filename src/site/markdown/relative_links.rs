@@ -0,0 +1,185 @@
+//! Relative links and images, like `./docs/setup.md` or `assets/logo.png`.
+//!
+//! READMEs are usually written to be read on GitHub, where these resolve to files in the repo.
+//! On our site they'd be broken, so after a page is rendered we point them somewhere that
+//! exists, per `markdown.relative_links`: the file on GitHub, or a copy of it in the dist dir.
+//! Links to Markdown files we build as additional pages, or as community pages, go to those pages
+//! instead.
+
+use std::sync::OnceLock;
+
+use axoasset::LocalAsset;
+use axoproject::GithubRepo;
+use camino::{Utf8Path, Utf8PathBuf};
+use regex::{Captures, Regex};
+
+use crate::config::{Config, RelativeLinkTarget};
+use crate::data::git;
+use crate::errors::*;
+use crate::site::page::source;
+use crate::site::{community, link, private};
+
+/// Rewrites the relative links and images in `html`, which was rendered from the Markdown file
+/// at `source_path`
+pub fn rewrite(html: &str, source_path: &str, config: &Config) -> Result<String> {
+    static TAG: OnceLock<Regex> = OnceLock::new();
+    let tag = TAG.get_or_init(|| {
+        Regex::new(r#"<(?:(a)\s[^>]*?\bhref|(img)\s[^>]*?\bsrc)="([^"]*)""#).unwrap()
+    });
+    // Everything below works with paths relative to the project's dir
+    let source_path =
+        source::get_filename_with_dir(source_path, &config.root_dir)?.unwrap_or_default();
+    let base_dir = source_path.parent().unwrap_or(Utf8Path::new(""));
+    let mut rewriter = Rewriter {
+        config,
+        base_dir,
+        repo_prefix: None,
+    };
+    let mut error = None;
+    let html = tag.replace_all(html, |captures: &Captures| {
+        let url = &captures[3];
+        let is_image = captures.get(2).is_some();
+        match rewriter.rewrite_url(url, is_image) {
+            Ok(Some(new_url)) => {
+                let whole = &captures[0];
+                // The URL is always at the end of what we matched
+                format!("{}{new_url}\"", &whole[..whole.len() - url.len() - 1])
+            }
+            Ok(None) => captures[0].to_owned(),
+            Err(e) => {
+                error.get_or_insert(e);
+                captures[0].to_owned()
+            }
+        }
+    });
+    match error {
+        Some(e) => Err(e),
+        None => Ok(html.into_owned()),
+    }
+}
+
+/// Whether a link is relative to the page it's on, rather than a full URL, an absolute path
+/// or an anchor
+pub fn is_relative(url: &str) -> bool {
+    static SCHEME: OnceLock<Regex> = OnceLock::new();
+    let scheme = SCHEME.get_or_init(|| Regex::new(r"^[A-Za-z][A-Za-z0-9+.-]*:").unwrap());
+    !(url.is_empty()
+        || url.starts_with('/')
+        || url.starts_with('#')
+        || url.starts_with('?')
+        || scheme.is_match(url))
+}
+
+struct Rewriter<'a> {
+    config: &'a Config,
+    base_dir: &'a Utf8Path,
+    /// Where the project's dir is in the git repo, which we only ask git for once we need it
+    repo_prefix: Option<Utf8PathBuf>,
+}
+
+impl Rewriter<'_> {
+    fn rewrite_url(&mut self, url: &str, is_image: bool) -> Result<Option<String>> {
+        if !is_relative(url) {
+            return Ok(None);
+        }
+        let (path, suffix) = url.split_at(url.find(['#', '?']).unwrap_or(url.len()));
+        let path = source::normalize(&self.base_dir.join(path));
+        if !is_image {
+            if let Some(page) = self.additional_page(&path)? {
+                return Ok(Some(format!("{page}{suffix}")));
+            }
+        }
+        let target = if is_image {
+            self.config.markdown.relative_links.images
+        } else {
+            self.config.markdown.relative_links.links
+        };
+        match target {
+            RelativeLinkTarget::Keep => Ok(None),
+            RelativeLinkTarget::Bundle => match self.bundle(&path)? {
+                Some(bundled) => Ok(Some(format!("{bundled}{suffix}"))),
+                // Directories and files outside the project can't be copied over, so fall back
+                // to linking to them on GitHub
+                None => Ok(self
+                    .repo_url(&path, is_image)
+                    .map(|url| format!("{url}{suffix}"))),
+            },
+            RelativeLinkTarget::Repo => Ok(self
+                .repo_url(&path, is_image)
+                .map(|url| format!("{url}{suffix}"))),
+        }
+    }
+
+    /// The link to the page built from the Markdown file at `path`, if it's an additional page or
+    /// a community page
+    fn additional_page(&self, path: &Utf8Path) -> Result<Option<String>> {
+        if !source::is_markdown(path.as_str()) {
+            return Ok(None);
+        }
+        if let Some(link) = community::link_to(path, self.config) {
+            return Ok(Some(link));
+        }
+        let is_page = self
+            .config
+            .build
+            .additional_pages
+            .values()
+            .any(|page| source::normalize(Utf8Path::new(page)) == path);
+        if !is_page {
+            return Ok(None);
+        }
+        let page_path = private::page_path(path.as_str(), self.config)?;
+        Ok(page_path.map(|page_path| {
+            link::generate_relative(&self.config.build.path_prefix, &format!("{page_path}/"))
+        }))
+    }
+
+    /// Copies the file at `path` to the same place in the dist dir, returning its new link
+    fn bundle(&self, path: &Utf8Path) -> Result<Option<String>> {
+        let source = self.config.path(path);
+        if path.is_absolute() || path.starts_with("..") || !source.is_file() {
+            return Ok(None);
+        }
+        let dest_dir = Utf8PathBuf::from(&self.config.build.dist_dir)
+            .join(path.parent().unwrap_or(Utf8Path::new("")));
+        LocalAsset::create_dir_all(&dest_dir)?;
+        LocalAsset::copy(&source, &dest_dir)?;
+        Ok(Some(link::generate_relative(
+            &self.config.build.path_prefix,
+            path.as_str(),
+        )))
+    }
+
+    /// The file at `path` on GitHub, if the project's repository is there
+    fn repo_url(&mut self, path: &Utf8Path, is_image: bool) -> Option<String> {
+        let repo_url = self.config.project.repository.as_deref()?;
+        let repo = GithubRepo::from_url(repo_url).ok()?;
+        let prefix = self.repo_prefix.get_or_insert_with(|| {
+            // If we're not in a git repo, assume we're at the root of it
+            git::run(&self.config.root_dir, &["rev-parse", "--show-prefix"])
+                .map(|prefix| Utf8PathBuf::from(prefix.trim()))
+                .unwrap_or_default()
+        });
+        let repo_path = source::normalize(&prefix.join(path));
+        if repo_path.is_absolute() || repo_path.starts_with("..") {
+            return None;
+        }
+        let owner = &repo.owner;
+        let name = &repo.name;
+        if is_image {
+            Some(format!(
+                "https://raw.githubusercontent.com/{owner}/{name}/HEAD/{repo_path}"
+            ))
+        } else {
+            // GitHub redirects between the two, but linking to the right one saves a hop
+            let kind = if self.config.path(path).is_dir() {
+                "tree"
+            } else {
+                "blob"
+            };
+            Some(format!(
+                "https://github.com/{owner}/{name}/{kind}/HEAD/{repo_path}"
+            ))
+        }
+    }
+}
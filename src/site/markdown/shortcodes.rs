@@ -0,0 +1,199 @@
+//! Shortcodes, like `{{ button href="/docs/" text="Read the docs" }}`
+//!
+//! Each shortcode is rendered with the minijinja partial called `shortcodes/<name>.html`, with
+//! its attributes as variables. oranda comes with a few of these, and more can be added (or ours
+//! replaced) by putting them in a "shortcodes" directory in `build.template_dir`. Anything that
+//! looks like a shortcode but doesn't have a partial is left alone, so that READMEs talking
+//! about Jinja and friends aren't mangled.
+//!
+//! Like recordings, shortcodes are swapped for placeholders before the markdown is rendered, and
+//! for their HTML after it's sanitized.
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use minijinja::value::Value;
+use minijinja::Environment;
+use regex::{Captures, Regex};
+
+use crate::config::Config;
+use crate::errors::*;
+use crate::site::templates::Templates;
+
+/// Where shortcode partials live, both in our templates and in `build.template_dir`
+pub const SHORTCODES_DIR: &str = "shortcodes";
+
+const PLACEHOLDER: &str = "ORANDASHORTCODE";
+
+/// A shortcode found in a page
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Shortcode {
+    pub name: String,
+    /// The attributes, in order, with `None` for bare flags like `autoplay`
+    pub attributes: Vec<(String, Option<String>)>,
+}
+
+impl Shortcode {
+    fn context(&self, config: &Config) -> Value {
+        let mut context = BTreeMap::new();
+        for (key, value) in &self.attributes {
+            let value = value.clone().map_or(Value::from(true), Value::from);
+            context.insert(key.clone(), value);
+        }
+        context
+            .entry("path_prefix".to_owned())
+            .or_insert_with(|| Value::from_serializable(&config.build.path_prefix));
+        Value::from(context)
+    }
+}
+
+/// Reads the attributes of a shortcode, like `src="demo.cast" cols=80 autoplay`
+pub fn parse_attributes(attributes: &str) -> Vec<(String, Option<String>)> {
+    static ATTRIBUTE: OnceLock<Regex> = OnceLock::new();
+    let attribute = ATTRIBUTE.get_or_init(|| {
+        Regex::new(r#"([\w-]+)(?:\s*=\s*(?:"([^"]*)"|'([^']*)'|(\S+)))?"#).unwrap()
+    });
+    attribute
+        .captures_iter(attributes)
+        .map(|captures| {
+            let value = captures
+                .get(2)
+                .or(captures.get(3))
+                .or(captures.get(4))
+                .map(|m| m.as_str().to_owned());
+            (captures[1].to_owned(), value)
+        })
+        .collect()
+}
+
+/// Calls `replace` with the name and attributes of every `{{ name ... }}` outside of code
+/// blocks and inline code, swapping the shortcode for what it returns
+pub(crate) fn replace_outside_code(
+    markdown: &str,
+    mut replace: impl FnMut(&str, &str, &str) -> String,
+) -> String {
+    static SHORTCODE: OnceLock<Regex> = OnceLock::new();
+    let shortcode = SHORTCODE
+        .get_or_init(|| Regex::new(r"\{\{\s*([A-Za-z][\w-]*)(?:\s+([^}]*?))?\s*\}\}").unwrap());
+    if !markdown.contains("{{") {
+        return markdown.to_owned();
+    }
+    replace_matches_outside_code(markdown, shortcode, |captures| {
+        let attributes = captures.get(2).map_or("", |m| m.as_str());
+        replace(&captures[1], attributes, &captures[0])
+    })
+}
+
+/// Swaps every match of `pattern` outside of code blocks and inline code for what `replace`
+/// returns
+pub(crate) fn replace_matches_outside_code(
+    markdown: &str,
+    pattern: &Regex,
+    mut replace: impl FnMut(&Captures) -> String,
+) -> String {
+    let mut output = String::with_capacity(markdown.len());
+    let mut fence: Option<&str> = None;
+    for line in markdown.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        match fence {
+            Some(marker) => {
+                if trimmed.starts_with(marker) {
+                    fence = None;
+                }
+                output.push_str(line);
+                continue;
+            }
+            None if trimmed.starts_with("```") || trimmed.starts_with("~~~") => {
+                fence = Some(&trimmed[..3]);
+                output.push_str(line);
+                continue;
+            }
+            None => {}
+        }
+        // Every other piece between backticks is inline code
+        for (index, piece) in line.split('`').enumerate() {
+            if index > 0 {
+                output.push('`');
+            }
+            if index % 2 == 1 {
+                output.push_str(piece);
+                continue;
+            }
+            let replaced = pattern.replace_all(piece, |captures: &Captures| replace(captures));
+            output.push_str(&replaced);
+        }
+    }
+    output
+}
+
+/// Swaps numbered placeholders in rendered HTML for what they stand for
+pub(crate) fn swap_placeholders(html: &str, placeholder: &str, replacements: &[String]) -> String {
+    let mut html = html.to_owned();
+    // Go backwards so that placeholder 1 doesn't match the start of placeholder 10
+    for (index, replacement) in replacements.iter().enumerate().rev() {
+        let placeholder = format!("{placeholder}{index}");
+        // A shortcode on its own line would otherwise leave its HTML inside a paragraph
+        let paragraph = format!("<p>{placeholder}</p>");
+        html = if html.contains(&paragraph) {
+            html.replace(&paragraph, replacement)
+        } else {
+            html.replace(&placeholder, replacement)
+        };
+    }
+    html
+}
+
+/// The shortcode partials available to a site
+pub struct Shortcodes {
+    env: Environment<'static>,
+}
+
+impl Shortcodes {
+    pub fn new(config: &Config) -> Result<Self> {
+        let env = Templates::new_for_shortcodes(config)?;
+        Ok(Self { env })
+    }
+
+    fn template_name(name: &str) -> String {
+        format!("{SHORTCODES_DIR}/{name}.html")
+    }
+
+    /// Whether there's a partial for the shortcode called `name`
+    pub fn has(&self, name: &str) -> bool {
+        self.env.get_template(&Self::template_name(name)).is_ok()
+    }
+
+    /// Swaps every shortcode we have a partial for with a placeholder, returning the shortcodes
+    /// in the order their placeholders are numbered
+    pub fn extract(&self, markdown: &str) -> (String, Vec<Shortcode>) {
+        let mut shortcodes = vec![];
+        let output = replace_outside_code(markdown, |name, attributes, original| {
+            if !self.has(name) {
+                return original.to_owned();
+            }
+            shortcodes.push(Shortcode {
+                name: name.to_owned(),
+                attributes: parse_attributes(attributes),
+            });
+            format!("{PLACEHOLDER}{}", shortcodes.len() - 1)
+        });
+        (output, shortcodes)
+    }
+
+    pub fn render(&self, shortcode: &Shortcode, config: &Config) -> Result<String> {
+        let template = self
+            .env
+            .get_template(&Self::template_name(&shortcode.name))?;
+        let html = template.render(shortcode.context(config))?;
+        Ok(html.trim().to_owned())
+    }
+
+    /// Swaps the placeholders in rendered HTML for the shortcodes' partials
+    pub fn embed(&self, html: &str, shortcodes: &[Shortcode], config: &Config) -> Result<String> {
+        let rendered = shortcodes
+            .iter()
+            .map(|shortcode| self.render(shortcode, config))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(swap_placeholders(html, PLACEHOLDER, &rendered))
+    }
+}
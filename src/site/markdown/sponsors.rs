@@ -0,0 +1,125 @@
+//! Sections only sponsors get to read, between `<!-- sponsors-only -->` and
+//! `<!-- /sponsors-only -->` markers.
+//!
+//! On the public site each section is swapped for a teaser that links to the funding page,
+//! rendered with the `shortcodes/sponsors-only.html` partial so it can be replaced like any other
+//! shortcode. With `markdown.sponsor_copies` on, additional pages with these sections are also
+//! written in full inside `build.private_path`, for the host to put behind a password.
+
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::config::Config;
+use crate::errors::*;
+use crate::site::link;
+use crate::site::markdown::shortcodes::{swap_placeholders, Shortcode, Shortcodes};
+
+const PLACEHOLDER: &str = "ORANDASPONSORSONLY";
+
+/// The name of the partial (and shortcode) the teaser is rendered with
+pub const TEASER_SHORTCODE: &str = "sponsors-only";
+
+/// A section that was left out of the public version of a page
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SponsorSection {
+    /// From `<!-- sponsors-only: Advanced tuning -->`
+    pub title: Option<String>,
+}
+
+fn start_marker() -> &'static Regex {
+    static START: OnceLock<Regex> = OnceLock::new();
+    START.get_or_init(|| Regex::new(r"^<!--\s*sponsors-only\s*(?::\s*(.*?))?\s*-->$").unwrap())
+}
+
+fn end_marker() -> &'static Regex {
+    static END: OnceLock<Regex> = OnceLock::new();
+    END.get_or_init(|| Regex::new(r"^<!--\s*/sponsors-only\s*-->$").unwrap())
+}
+
+/// Whether the page has any sponsor-only sections (outside of code blocks)
+pub fn has_sections(markdown: &str) -> bool {
+    markdown.contains("sponsors-only") && !extract(markdown).1.is_empty()
+}
+
+/// Swaps every sponsor-only section for a placeholder, returning the sections in the order
+/// their placeholders are numbered. A section that's never closed runs to the end of the page.
+pub fn extract(markdown: &str) -> (String, Vec<SponsorSection>) {
+    if !markdown.contains("sponsors-only") {
+        return (markdown.to_owned(), vec![]);
+    }
+    let mut output = String::with_capacity(markdown.len());
+    let mut sections = vec![];
+    let mut fence: Option<&str> = None;
+    let mut in_section = false;
+    for line in markdown.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if in_section {
+            if fence.is_none() && end_marker().is_match(trimmed) {
+                in_section = false;
+            }
+        } else if fence.is_none() {
+            if let Some(captures) = start_marker().captures(trimmed) {
+                sections.push(SponsorSection {
+                    title: captures
+                        .get(1)
+                        .map(|m| m.as_str().to_owned())
+                        .filter(|title| !title.is_empty()),
+                });
+                // Blank lines around it, so it's a paragraph of its own
+                output.push_str(&format!("\n{PLACEHOLDER}{}\n\n", sections.len() - 1));
+                in_section = true;
+                continue;
+            }
+        }
+        match fence {
+            Some(marker) if trimmed.starts_with(marker) => fence = None,
+            None if trimmed.starts_with("```") || trimmed.starts_with("~~~") => {
+                fence = Some(&trimmed[..3]);
+            }
+            _ => {}
+        }
+        if !in_section {
+            output.push_str(line);
+        }
+    }
+    if in_section {
+        tracing::warn!("A sponsors-only section was never closed with <!-- /sponsors-only -->, so it runs to the end of the page");
+    }
+    (output, sections)
+}
+
+/// Where the teaser sends readers: the funding page if we build one
+fn funding_link(config: &Config) -> Option<String> {
+    config
+        .components
+        .funding
+        .as_ref()
+        .map(|_| link::generate_relative(&config.build.path_prefix, "funding/"))
+}
+
+/// Swaps the placeholders in rendered HTML for the teasers
+pub fn embed(html: &str, sections: &[SponsorSection], config: &Config) -> Result<String> {
+    if sections.is_empty() {
+        return Ok(html.to_owned());
+    }
+    let shortcodes = Shortcodes::new(config)?;
+    let teasers = sections
+        .iter()
+        .map(|section| {
+            let mut attributes = vec![];
+            if let Some(title) = &section.title {
+                attributes.push(("title".to_owned(), Some(title.clone())));
+            }
+            if let Some(link) = funding_link(config) {
+                attributes.push(("href".to_owned(), Some(link)));
+            }
+            let teaser = Shortcode {
+                name: TEASER_SHORTCODE.to_owned(),
+                attributes,
+            };
+            shortcodes.render(&teaser, config)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(swap_placeholders(html, PLACEHOLDER, &teasers))
+}
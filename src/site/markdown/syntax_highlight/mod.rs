@@ -5,9 +5,14 @@ use std::time::Instant;
 
 use crate::errors::*;
 use crate::site::markdown::syntax_highlight::syntax_themes::SyntaxTheme;
+use serde::Serialize;
+use syntect::easy::HighlightLines;
 use syntect::highlighting::{Theme, ThemeSet};
-use syntect::html::highlighted_html_for_string;
+use syntect::html::{
+    highlighted_html_for_string, styled_line_to_highlighted_html, IncludeBackground,
+};
 use syntect::parsing::{SyntaxDefinition, SyntaxReference, SyntaxSet};
+use syntect::util::LinesWithEndings;
 
 // The reason for this function is that find_syntax_by_extension will work when your
 // snippet uses rs but not when it uses rust as the language.
@@ -77,11 +82,8 @@ pub fn dump_syntax_themes() -> Result<()> {
     Ok(())
 }
 
-pub fn syntax_highlight(
-    lang: Option<&str>,
-    code: &str,
-    syntax_theme: &SyntaxTheme,
-) -> Result<String> {
+/// Loads our syntaxes, along with the given theme
+fn load_syntaxes(syntax_theme: &SyntaxTheme) -> Result<(SyntaxSet, Theme)> {
     let ps = syntect::dumps::from_uncompressed_data(include_bytes!("./syntax_themes.themedump"))
         .expect("failed to load syntax_themes.themedump from the binary");
     let themes = THEMES
@@ -94,19 +96,63 @@ pub fn syntax_highlight(
             Ok((name.to_string(), theme))
         })
         .collect::<Result<BTreeMap<String, Theme>>>()?;
-    let theme_set = ThemeSet { themes };
-    let language = match lang {
+    let mut theme_set = ThemeSet { themes };
+    let theme = theme_set
+        .themes
+        .remove(&syntax_theme.as_str())
+        .expect("syntax theme was missing from the binary?");
+    Ok((ps, theme))
+}
+
+fn language_for(lang: Option<&str>) -> &str {
+    match lang {
         None => "",
         Some("text") => "txt",
         Some("shell") => "sh",
         Some(l) => l,
-    };
-    let syntax = find_syntax(&ps, language)?;
+    }
+}
 
-    Ok(highlighted_html_for_string(
-        code,
-        &ps,
-        syntax,
-        &theme_set.themes[&syntax_theme.as_str()],
-    )?)
+pub fn syntax_highlight(
+    lang: Option<&str>,
+    code: &str,
+    syntax_theme: &SyntaxTheme,
+) -> Result<String> {
+    let (ps, theme) = load_syntaxes(syntax_theme)?;
+    let syntax = find_syntax(&ps, language_for(lang))?;
+
+    Ok(highlighted_html_for_string(code, &ps, syntax, &theme)?)
+}
+
+/// Code highlighted one line at a time, see [`syntax_highlight_lines`][]
+#[derive(Debug, Clone, Serialize)]
+pub struct HighlightedLines {
+    /// The theme's background color, like `#263238`
+    pub background: Option<String>,
+    /// The HTML for each line, without the trailing newline
+    pub lines: Vec<String>,
+}
+
+/// Like [`syntax_highlight`][], but keeps every line separate, so that each one can get its
+/// own anchor
+pub fn syntax_highlight_lines(
+    lang: Option<&str>,
+    code: &str,
+    syntax_theme: &SyntaxTheme,
+) -> Result<HighlightedLines> {
+    let (ps, theme) = load_syntaxes(syntax_theme)?;
+    let syntax = find_syntax(&ps, language_for(lang))?;
+
+    let mut highlighter = HighlightLines::new(syntax, &theme);
+    let mut lines = vec![];
+    for line in LinesWithEndings::from(code) {
+        let regions = highlighter.highlight_line(line, &ps)?;
+        let html = styled_line_to_highlighted_html(&regions, IncludeBackground::No)?;
+        lines.push(html.trim_end_matches(['\n', '\r']).to_owned());
+    }
+    let background = theme
+        .settings
+        .background
+        .map(|c| format!("#{:02x}{:02x}{:02x}", c.r, c.g, c.b));
+    Ok(HighlightedLines { background, lines })
 }
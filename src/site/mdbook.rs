@@ -3,10 +3,11 @@ use camino::{Utf8Path, Utf8PathBuf};
 use mdbook::MDBook;
 use std::path::PathBuf;
 
-use crate::config::MdBookConfig;
-use crate::data::workspaces::WorkspaceData;
+use crate::config::{MdBookConfig, StyleConfig};
 use crate::errors::*;
-use crate::site::{oranda_theme::OrandaTheme, Site};
+use crate::site::layout::css;
+use crate::site::oranda_theme::OrandaTheme;
+use crate::site::writer::SiteWriter;
 
 use super::markdown::SyntaxTheme;
 use crate::paths::determine_path;
@@ -29,6 +30,15 @@ const THEME_INDEX_HBS: &str = include_str!("../../oranda-css/mdbook-theme/index.
 const KEY_ORANDA_VARS: &str = "/*ORANDA-THEME-VARS*/";
 /// index.hbs needs us to substitute this with at least one copy of THEME_BUTTON_HTML_TEMPLATE
 const KEY_ORANDA_BUTTONS: &str = "<!--ORANDA-THEME-BUTTONS-->";
+/// index.hbs needs us to substitute this with links to the theme's webfonts and the user's
+/// additional css (if any)
+const KEY_ORANDA_STYLESHEETS: &str = "<!--ORANDA-STYLESHEETS-->";
+/// Template for the HTML for a stylesheet link in index.hbs
+const STYLESHEET_HTML_TEMPLATE: &str = r#"        <link rel="stylesheet" href="{{HREF}}">"#;
+/// STYLESHEET_HTML_TEMPLATE needs us to substitute this with the stylesheet's URL
+const KEY_STYLESHEET_HREF: &str = "{{HREF}}";
+/// Where we write the user's additional css in the book, concatenated like custom.css is
+const THEME_CUSTOM_CSS_PATH: &str = "oranda-custom.css";
 /// THEME_BUTTON_HTML_TEMPLATE needs us to substitute this with CLASS_ORANDA_DARK or CLASS_ORANDA_LIGHT
 ///
 /// (yes mdbook has some magic in it where you use a css class as the id of a button in its dropdowns)
@@ -153,25 +163,18 @@ impl AxomdbookTheme {
     }
 }
 
-/// Get a proper absolute path to the mdbook's dir (the one containing book.toml)
+/// Get a proper absolute path to the mdbook's dir (the one containing book.toml), for the
+/// project in `root_dir`
 ///
 /// This needs to be absolute because some mdbook renderers flip out with relative paths.
-///
-/// FIXME: this is broken if pwd is not the same dir as oranda.json. Our config code
-/// should do this mapping for us, when it still knows where oranda.json is!
-pub fn mdbook_dir(
-    workspace: Option<&WorkspaceData>,
-    book_cfg: &MdBookConfig,
-) -> Result<Utf8PathBuf> {
-    let root_path = Utf8PathBuf::from_path_buf(std::env::current_dir()?).unwrap_or_default();
-    let member_path = workspace.map(|w| &w.path);
+pub fn mdbook_dir(root_dir: &Utf8Path, book_cfg: &MdBookConfig) -> Result<Utf8PathBuf> {
     let book_path = book_cfg
         .path
         .as_ref()
         .expect("Had no mdbook.path, but config code didn't disable mdbook?");
-    let path = determine_path(root_path, &member_path, book_path)?;
+    let path = determine_path(root_dir, &None::<&Utf8Path>, book_path)?;
     if let Some(path) = path {
-        Ok(path)
+        Ok(root_dir.join(path))
     } else {
         Err(OrandaError::PathDoesNotExist {
             path: book_path.clone(),
@@ -189,21 +192,20 @@ pub fn custom_theme(book_cfg: &MdBookConfig, oranda_theme: &OrandaTheme) -> Opti
 }
 
 /// Gets the dir where we should write custom theme files
-pub fn custom_theme_dir(_book_cfg: &MdBookConfig, dist: &Utf8Path) -> Result<Utf8PathBuf> {
-    let pwd = axoasset::LocalAsset::current_dir()?;
-    Ok(pwd.join(dist).join("mdbook_theme"))
+pub fn custom_theme_dir(root_dir: &Utf8Path, dist: &Utf8Path) -> Utf8PathBuf {
+    root_dir.join(dist).join("mdbook_theme")
 }
 
-/// Build and write the mdbook to the dist dir
+/// Build the mdbook of the project in `root_dir` and write it to the site
 pub fn build_mdbook(
-    workspace: Option<&WorkspaceData>,
+    root_dir: &Utf8Path,
     dist: &Utf8Path,
     book_cfg: &MdBookConfig,
-    oranda_theme: &OrandaTheme,
-    syntax_theme: &SyntaxTheme,
+    styles: &StyleConfig,
+    writer: &mut dyn SiteWriter,
 ) -> Result<()> {
     // Read mdbook's config to inherit the user's setup
-    let book_dir = mdbook_dir(workspace, book_cfg)?;
+    let book_dir = mdbook_dir(root_dir, book_cfg)?;
     let mut md = load_mdbook(&book_dir)?;
 
     // Check if we'd be infinitely recursing due to mdbook's build output directory being
@@ -218,11 +220,11 @@ pub fn build_mdbook(
     }
 
     // If custom theme is enabled, set that up
-    let custom_theme = custom_theme(book_cfg, oranda_theme);
-    let theme_dir = custom_theme_dir(book_cfg, dist)?;
+    let custom_theme = custom_theme(book_cfg, &styles.theme);
+    let theme_dir = custom_theme_dir(root_dir, dist);
     if let Some(theme) = custom_theme {
         // Create all the files for our custom theme
-        init_theme_dir(&theme_dir, theme)?;
+        init_theme_dir(&theme_dir, theme, styles)?;
 
         // Tell mdbook to default to our theme, forcing both the light and dark modes
         //
@@ -244,6 +246,16 @@ pub fn build_mdbook(
             .expect("failed to convert theme_dir to a TOML String");
     }
 
+    // Apply the user's overrides last, so they can change anything we set above
+    for (key, value) in &book_cfg.overrides {
+        md.config
+            .set(key, value)
+            .map_err(|e| OrandaError::MdBookOverride {
+                key: key.clone(),
+                details: e,
+            })?;
+    }
+
     // Build the mdbook
     let build_dir =
         Utf8PathBuf::from_path_buf(md.build_dir_for("html")).expect("mdbook path wasn't utf8");
@@ -254,17 +266,19 @@ pub fn build_mdbook(
 
     if custom_theme.is_some() {
         // If custom theme is enabled, add the axo syntax highlighting theme to the output
-        add_custom_syntax_theme_to_output(syntax_theme, &build_dir)?;
+        add_custom_syntax_theme_to_output(&styles.syntax_theme, &build_dir)?;
+        // ...and the user's additional css, which index.hbs links to
+        if !styles.additional_css.is_empty() {
+            let custom_css = css::concat_minify(&styles.additional_css, root_dir)?;
+            LocalAsset::write_new_all(&custom_css, build_dir.join(THEME_CUSTOM_CSS_PATH))?;
+        }
         // See docs of this function for why we delete this dir
         delete_theme_dir(&theme_dir)?;
     }
 
-    // Copy the contents to "public/book/"
+    // Copy the contents to "book/" in the site
     // FIXME: make this something they can set in the MdBookConfig
-    let book_dist = dist.join("book");
-    Site::copy_static(&book_dist, build_dir.as_str())?;
-
-    Ok(())
+    writer.write_dir(Utf8Path::new("book"), &build_dir)
 }
 
 /// Load the mdbook config (book.toml) in a given directory
@@ -295,8 +309,9 @@ pub fn homogenize_path(path: &PathBuf) -> PathBuf {
 /// Initialize a directory with our custom theme files
 ///
 /// Note that these files assume you will also call [`add_custom_syntax_theme_to_output`][]
-/// to add axo-highlight.css to the build dir.
-fn init_theme_dir(theme_dir: &Utf8Path, theme: AxomdbookTheme) -> Result<()> {
+/// to add axo-highlight.css to the build dir, and write the user's additional css to
+/// oranda-custom.css if they have any.
+fn init_theme_dir(theme_dir: &Utf8Path, theme: AxomdbookTheme, styles: &StyleConfig) -> Result<()> {
     // Just to be safe, clear out the theme dir in case it still exists
     delete_theme_dir(theme_dir)?;
 
@@ -321,7 +336,21 @@ fn init_theme_dir(theme_dir: &Utf8Path, theme: AxomdbookTheme) -> Result<()> {
     if let Some(twin) = theme.twin_theme() {
         add_theme_button(&mut buttons, twin);
     }
-    let index = THEME_INDEX_HBS.replace(KEY_ORANDA_BUTTONS, &buttons);
+    // Load the same webfonts the rest of the site does, followed by the user's css so it
+    // can override any of the theme's variables
+    let mut stylesheets = String::new();
+    for href in styles.theme.font_stylesheets() {
+        add_stylesheet(&mut stylesheets, href);
+    }
+    if !styles.additional_css.is_empty() {
+        add_stylesheet(
+            &mut stylesheets,
+            &format!("{{{{ path_to_root }}}}{THEME_CUSTOM_CSS_PATH}"),
+        );
+    }
+    let index = THEME_INDEX_HBS
+        .replace(KEY_ORANDA_BUTTONS, &buttons)
+        .replace(KEY_ORANDA_STYLESHEETS, &stylesheets);
 
     // Now write all the files
     let files = vec![
@@ -389,3 +418,9 @@ fn add_theme_button(output: &mut String, theme: AxomdbookTheme) {
     output.push_str(&button);
     output.push('\n');
 }
+
+fn add_stylesheet(output: &mut String, href: &str) {
+    let link = STYLESHEET_HTML_TEMPLATE.replace(KEY_STYLESHEET_HREF, href);
+    output.push_str(&link);
+    output.push('\n');
+}
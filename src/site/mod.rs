@@ -1,4 +1,6 @@
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
 
 use axoasset::{Asset, LocalAsset};
 use axoproject::GithubRepo;
@@ -7,62 +9,178 @@ use indexmap::IndexMap;
 use minijinja::context;
 use tracing::instrument;
 
-use crate::config::{AxoprojectLayer, Config, ReleasesSource};
+use crate::config::{AxoprojectLayer, Config, ConfigOptions, ReleasesSource};
 use crate::data::github::GithubRelease;
-use crate::data::{funding::Funding, workspaces, Context};
+use crate::data::{funding::Funding, git, workspaces, Context};
+use crate::diagnostics;
 use crate::errors::*;
+use crate::paths::resolve_asset;
+use crate::report;
 
+use crate::data::org::OrgProject;
 use crate::data::workspaces::WorkspaceData;
+use crate::site::authors::{AuthorIndex, Credit};
+use crate::site::markdown::asciinema;
+use crate::site::org_index::OrgIndexContext;
+use crate::site::rebuild::{Incremental, Output, Rebuild};
+use crate::site::sidebar::Sidebar;
+use crate::site::tags::{TaggedItem, TaggedItemKind, Taxonomy};
 use crate::site::templates::Templates;
 use crate::site::workspace_index::WorkspaceIndexContext;
+use crate::site::writer::{DirWriter, MemoryWriter, SiteWriter};
+pub use builder::SiteBuilder;
 use layout::css;
 pub use layout::javascript;
 use page::Page;
 
+pub mod activity;
+pub mod api;
+pub mod archive;
 pub mod artifacts;
+pub mod authors;
+mod benchmarks;
+pub mod blog;
+mod builder;
+pub mod canonical;
 pub mod changelog;
+pub mod community;
+pub mod config_context;
+pub mod data_pages;
+pub mod debug;
+pub mod demo;
+pub mod dependency_graph;
+pub mod docs;
+pub mod features;
 pub mod funding;
+pub mod github_action;
+mod health;
 pub mod layout;
+pub mod licenses;
 pub mod link;
 pub mod markdown;
 pub mod mdbook;
 pub mod oranda_theme;
+pub mod org_index;
 pub mod page;
+pub mod private;
+pub mod rebuild;
+pub mod roadmap;
 pub mod rss;
+pub mod rustdoc;
+pub mod security;
+pub mod sidebar;
+pub mod single_file;
+pub mod sitemap;
+pub mod support_matrix;
+pub mod tags;
 pub mod templates;
+pub mod theme_test;
+pub mod well_known;
+pub mod widgets;
 mod workspace_index;
+pub mod writer;
 
 #[derive(Debug)]
 pub struct Site {
     pub workspace_data: Option<WorkspaceData>,
     pub pages: Vec<Page>,
+    /// Files the build made besides the pages, like the CSS and `artifacts.json`, which are
+    /// written along with them
+    pub files: MemoryWriter,
+    /// What `oranda dev` asked this build to make, if it's not making the whole site
+    pub plan: Option<Rebuild>,
 }
 
 impl Site {
-    pub fn build_multi(workspace_config: &Config, json_only: bool) -> Result<Vec<Site>> {
+    pub fn build_multi(
+        workspace_config: &Config,
+        json_only: bool,
+        options: &ConfigOptions,
+    ) -> Result<Vec<Site>> {
+        let members = Self::workspace_members(workspace_config, options)?;
+        Self::build_members(&members, json_only, None)
+    }
+
+    /// Loads the members of the workspace, for [`Site::build_members`][] to build
+    pub fn workspace_members(
+        workspace_config: &Config,
+        options: &ConfigOptions,
+    ) -> Result<Vec<WorkspaceData>> {
         tracing::info!("Workspace detected, gathering info...");
-        // We assume the root path is wherever oranda-workspace.json is located (current dir)
-        let root_path = Utf8PathBuf::from_path_buf(std::env::current_dir()?).unwrap_or_default();
+        // We assume the root path is wherever oranda-workspace.json is located
+        let root_path = workspace_config.root_dir.clone();
 
         let mut workspace_config_path = root_path.clone();
         workspace_config_path.push("oranda-workspace.json");
-        let mut results = Vec::new();
-        let members =
-            workspaces::from_config(workspace_config, &root_path, &workspace_config_path)?;
+        let members = workspaces::from_config(
+            workspace_config,
+            &root_path,
+            &workspace_config_path,
+            options,
+        )?;
         tracing::info!("Building {} workspace member(s)...", members.len());
-        for member in &members {
-            std::env::set_current_dir(&member.path)?;
-            let mut site = if json_only {
-                Self::build_single_json_only(&member.config, Some(member.slug.to_string()))?
-            } else {
-                Self::build_single(&member.config, Some(member.slug.to_string()))?
-            };
-            site.workspace_data = Some(member.clone());
-            results.push(site);
-            std::env::set_current_dir(&root_path)?;
-        }
+        Ok(members)
+    }
+
+    /// Builds the sites of several workspace members, or of the projects an `oranda build-many`
+    /// manifest lists, returning them in the same order.
+    pub fn build_members(
+        members: &[WorkspaceData],
+        json_only: bool,
+        incremental: Option<&Incremental>,
+    ) -> Result<Vec<Site>> {
+        // Members don't depend on each other, so we build as many at once as we have cores for.
+        // Each one gets its own slot, to keep the results in the order the members are in.
+        let jobs = std::thread::available_parallelism()
+            .map_or(1, |n| n.get())
+            .min(members.len());
+        let next = AtomicUsize::new(0);
+        let results = members
+            .iter()
+            .map(|_| Mutex::new(None))
+            .collect::<Vec<Mutex<Option<Result<Site>>>>>();
+        let runtime = tokio::runtime::Handle::current();
+        std::thread::scope(|scope| {
+            for _ in 0..jobs {
+                scope.spawn(|| {
+                    let _guard = runtime.enter();
+                    loop {
+                        let idx = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(member) = members.get(idx) else {
+                            break;
+                        };
+                        *results[idx].lock().unwrap() =
+                            Some(Self::build_member(member, json_only, incremental));
+                    }
+                });
+            }
+        });
+
+        results
+            .into_iter()
+            .map(|site| {
+                site.into_inner()
+                    .unwrap()
+                    .expect("every workspace member should have been built")
+            })
+            .collect()
+    }
 
-        Ok(results)
+    /// Builds a workspace member's site. Everything it reads is relative to the member's
+    /// [`Config::root_dir`][], so this doesn't care what the current directory is.
+    fn build_member(
+        member: &WorkspaceData,
+        json_only: bool,
+        incremental: Option<&Incremental>,
+    ) -> Result<Site> {
+        let prefix = Some(member.slug.to_string());
+        let mut site = if json_only {
+            Self::build_single_json_only(&member.config, prefix)?
+        } else {
+            Self::build_single_for_dist(&member.config, prefix, incremental)?
+        };
+        site.workspace_data = Some(member.clone());
+        Ok(site)
     }
 
     pub fn build_and_write_workspace_index(
@@ -70,13 +188,6 @@ impl Site {
         member_data: &Vec<WorkspaceData>,
     ) -> Result<()> {
         let templates = Templates::new_for_workspace_index(workspace_config)?;
-        if workspace_config.styles.favicon.is_none() {
-            layout::header::place_default_favicon(workspace_config)?;
-        }
-        css::place_css(
-            &workspace_config.build.dist_dir,
-            &workspace_config.styles.oranda_css_version,
-        )?;
         let context = WorkspaceIndexContext::new(member_data, workspace_config)?;
         let page = Page::new_from_template(
             "index.html",
@@ -84,57 +195,184 @@ impl Site {
             "workspace_index/index.html",
             &context,
         )?;
-        let mut dist = Utf8PathBuf::from(&workspace_config.build.dist_dir);
+        Self::write_index_page(workspace_config, page)
+    }
+
+    /// Builds the portal `oranda org` makes for an organization's `projects`
+    pub fn build_and_write_org_index(
+        config: &Config,
+        org: &str,
+        projects: &[OrgProject],
+    ) -> Result<()> {
+        Self::clean_dist_dir(&config.build.dist_dir)?;
+        let templates = Templates::new_for_workspace_index(config)?;
+        let context = OrgIndexContext::new(org, projects);
+        let page = Page::new_from_template(
+            "index.html",
+            &templates,
+            "workspace_index/org.html",
+            &context,
+        )?;
+        Self::write_index_page(config, page)
+    }
+
+    /// Writes a page that links to other sites, like a workspace's index, with everything it
+    /// needs next to it
+    fn write_index_page(workspace_config: &Config, mut page: Page) -> Result<()> {
+        let dist = Utf8PathBuf::from(&workspace_config.build.dist_dir);
+        let mut writer = DirWriter::new(&dist);
+        if workspace_config.styles.favicon.is_none() {
+            layout::header::write_default_favicon(&mut writer)?;
+        }
+        css::write_css(
+            &workspace_config.root_dir,
+            &workspace_config.build.dist_dir,
+            &workspace_config.styles.oranda_css_version,
+            &mut writer,
+        )?;
         let additional_css = &workspace_config.styles.additional_css;
         if !additional_css.is_empty() {
-            css::write_additional_css(additional_css, &dist)?;
+            css::write_additional_css(additional_css, &workspace_config.root_dir, &mut writer)?;
+        }
+        if workspace_config.build.compat {
+            css::write_compat_css(&mut writer)?;
+        }
+        if let Some(cname) = canonical::cname_page(workspace_config) {
+            LocalAsset::write_new_all(&cname.contents, dist.join(&cname.filename))?;
+        }
+        canonical::add_links(std::slice::from_mut(&mut page), workspace_config);
+        let index = dist.join("index.html");
+        LocalAsset::write_new_all(&page.contents, &index)?;
+        if workspace_config.build.single_file {
+            single_file::inline_page(&index, &dist, &workspace_config.build.path_prefix)?;
         }
-        dist.push("index.html");
-        LocalAsset::write_new_all(&page.contents, dist)?;
         Ok(())
     }
 
+    /// Clears out the dist dir, then builds the site with [`Site::build_single`][], for a site
+    /// that's going to be written there with [`Site::write`][]. A partial rebuild in
+    /// `oranda dev` leaves whatever it doesn't make again where it is.
+    pub fn build_single_for_dist(
+        config: &Config,
+        prefix: Option<String>,
+        incremental: Option<&Incremental>,
+    ) -> Result<Site> {
+        if rebuild::is_full_build(incremental.map(Incremental::plan)) {
+            Self::clean_dist_dir(&config.build.dist_dir)?;
+        }
+        Self::build_single(config, prefix, incremental)
+    }
+
+    /// Builds the site of a single project. `oranda dev` passes what it's kept from the builds
+    /// before, and the plan for this one; other builds make everything.
+    ///
+    /// This doesn't clear out the dist dir, since the site may be written somewhere else;
+    /// see [`Site::build_single_for_dist`][].
     #[instrument("workspace_page", fields(prefix = prefix))]
-    pub fn build_single(config: &Config, prefix: Option<String>) -> Result<Site> {
-        Self::clean_dist_dir(&config.build.dist_dir)?;
+    pub fn build_single(
+        config: &Config,
+        prefix: Option<String>,
+        incremental: Option<&Incremental>,
+    ) -> Result<Site> {
+        let plan = incremental.map(Incremental::plan);
+        let mut files = MemoryWriter::new();
         if config.styles.favicon.is_none() {
-            layout::header::place_default_favicon(config)?;
+            layout::header::write_default_favicon(&mut files)?;
         }
-        css::place_css(&config.build.dist_dir, &config.styles.oranda_css_version)?;
-        let needs_context = Self::needs_context(config)?;
-        let context = if needs_context {
-            Some(Self::build_context(config)?)
-        } else {
-            None
+        css::write_css(
+            &config.root_dir,
+            &config.build.dist_dir,
+            &config.styles.oranda_css_version,
+            &mut files,
+        )?;
+        if !rebuild::should_build(plan, Output::Pages) {
+            return Ok(Site {
+                pages: vec![],
+                workspace_data: None,
+                files,
+                plan: plan.cloned(),
+            });
+        }
+        git::start_build(&config.root_dir);
+        let kept = incremental.and_then(|incremental| incremental.kept(&config.build.dist_dir));
+        let reused = kept.is_some();
+        let (mut context, funding) = match kept {
+            Some(kept) => kept,
+            None => Self::fetch_context_and_funding(config)?,
         };
+        if let Some(context) = &context {
+            Self::record_releases(context);
+        }
 
         let templates = Templates::new(config, context.as_ref())?;
 
         let mut pages = vec![];
+        let mut taxonomy = Taxonomy::default();
+        let mut author_index = AuthorIndex::new(config);
 
-        if !config.build.additional_pages.is_empty() {
-            let mut additional_pages =
-                Self::build_additional_pages(&config.build.additional_pages, &templates, config)?;
+        if !config.build.additional_pages.is_empty() || config.build.sidebar.is_some() {
+            let mut additional_pages = Self::build_additional_pages(
+                &config.build.additional_pages,
+                &templates,
+                config,
+                &mut taxonomy,
+            )?;
             pages.append(&mut additional_pages);
         }
 
         let mut index = None;
+        let mut artifacts_context = None;
+        let mut funding_context = None;
         Self::print_plan(config);
 
-        if let Some(mut context) = context {
+        let health_context = config
+            .components
+            .health
+            .as_ref()
+            .and_then(|health_cfg| health::context(health_cfg, config));
+        let activity_context = config
+            .components
+            .activity
+            .as_ref()
+            .and_then(|activity_cfg| activity::context(activity_cfg, config));
+        let github_action_context = match &config.components.github_action {
+            Some(action_cfg) => github_action::context(action_cfg, config, context.as_ref())?,
+            None => None,
+        };
+        let demo_context = config
+            .components
+            .demo
+            .as_ref()
+            .map(|demo_cfg| demo::context(demo_cfg, config));
+
+        if let Some(context) = context.as_mut() {
             if config.components.artifacts_enabled() {
                 if let Some(latest) = context.latest_mut() {
                     // Give especially nice treatment to the latest release and make
-                    // its scripts easy to view (others get hotlinked and will just download)
-                    latest.artifacts.make_scripts_viewable(config)?;
+                    // its scripts easy to view (others get hotlinked and will just download).
+                    // A reused context had this done by the build that fetched it, and the
+                    // files it wrote are still there.
+                    if !reused {
+                        latest.artifacts.make_scripts_viewable(config)?;
+                    }
+                    pages.extend(artifacts::script_pages(latest, &templates, config)?);
+                    if !reused {
+                        artifacts::mirror_release(latest, config, &mut files)?;
+                    }
 
-                    let template_context = artifacts::template_context(&context, config)?;
+                    let template_context = artifacts::template_context(context, config)?;
                     index = Some(Page::new_from_both(
                         &config.project.readme_path,
                         "index.html",
                         &templates,
                         "index.html",
-                        context!(artifacts => template_context),
+                        context!(
+                            artifacts => template_context,
+                            health => health_context,
+                            activity => activity_context,
+                            github_action => github_action_context,
+                            demo => demo_context,
+                        ),
                         config,
                     )?);
                     let artifacts_page = Page::new_from_template(
@@ -144,25 +382,169 @@ impl Site {
                         &template_context,
                     )?;
                     pages.push(artifacts_page);
-                    if let Some(template_context) = template_context {
-                        artifacts::write_artifacts_json(config, &template_context)?;
+                    if let Some(template_context) = &template_context {
+                        artifacts::write_artifacts_json(template_context, &mut files)?;
+                        artifacts::write_counter_pixels(template_context, &mut files)?;
                     }
+                    artifacts_context = template_context;
                 }
             }
             if config.components.changelog.is_some() {
-                let mut changelog_pages =
-                    Self::build_changelog_pages(&context, &templates, config)?;
+                let mut changelog_pages = Self::build_changelog_pages(context, &templates, config)?;
                 pages.append(&mut changelog_pages);
             }
-            if let Some(funding_cfg) = &config.components.funding {
-                let funding = Funding::new(funding_cfg, &config.styles)?;
-                let context = funding::context(funding_cfg, &funding)?;
+            if config.components.authors.is_some() {
+                for release in &context.releases {
+                    if let Some(byline) = authors::release_byline(release, config) {
+                        let version_tag = release.source.version_tag();
+                        let link = config.components.changelog.as_ref().map(|_| {
+                            link::generate_relative(
+                                &config.build.path_prefix,
+                                &format!("changelog/{version_tag}/"),
+                            )
+                        });
+                        author_index.add_release(
+                            &byline,
+                            Credit {
+                                title: version_tag.to_owned(),
+                                link,
+                            },
+                        );
+                    }
+                }
+            }
+            if let (Some(funding_cfg), Some(funding)) = (&config.components.funding, &funding) {
+                let context = funding::context(funding_cfg, funding)?;
                 let page =
                     Page::new_from_template("funding.html", &templates, "funding.html", &context)?;
                 pages.push(page);
+                funding_context = Some(context);
             }
         }
 
+        if let Some(support_matrix_cfg) = &config.components.support_matrix {
+            let support_matrix_context = support_matrix::context(
+                support_matrix_cfg,
+                config.components.artifacts.as_ref(),
+                context.as_ref(),
+            );
+            let page = Page::new_from_template(
+                "platforms.html",
+                &templates,
+                "support_matrix.html",
+                &support_matrix_context,
+            )?;
+            pages.push(page);
+        }
+
+        if let Some(features_cfg) = &config.components.features {
+            let crate_features = crate::data::features::load(features_cfg, &config.root_dir)?;
+            let features_context = features::FeaturesContext::new(&crate_features, config)?;
+            let page = Page::new_from_template(
+                "features.html",
+                &templates,
+                "features.html",
+                &features_context,
+            )?;
+            pages.push(page);
+        }
+
+        if let Some(licenses_cfg) = &config.components.licenses {
+            let dependency_licenses = crate::data::licenses::load(licenses_cfg, &config.root_dir)?;
+            let project_licenses =
+                crate::data::licenses::load_project_licenses(licenses_cfg, &config.root_dir)?;
+            let licenses_context = licenses::LicensesContext::new(&dependency_licenses)
+                .with_project_licenses(project_licenses);
+            let page = Page::new_from_template(
+                "licenses.html",
+                &templates,
+                "licenses.html",
+                &licenses_context,
+            )?;
+            pages.push(page);
+        }
+
+        if let Some(community_cfg) = &config.components.community {
+            let mut community_pages = community::build_pages(community_cfg, &templates, config)?;
+            pages.append(&mut community_pages);
+        }
+
+        if let Some(security_cfg) = &config.components.security {
+            let security_context = security::SecurityContext::new(security_cfg, config)?;
+            let page = Page::new_from_template(
+                "security.html",
+                &templates,
+                "security.html",
+                &security_context,
+            )?;
+            pages.push(page);
+        }
+
+        if let Some(roadmap_cfg) = &config.components.roadmap {
+            let roadmap_context = roadmap::RoadmapContext::new(roadmap_cfg, config)?;
+            let page = Page::new_from_template(
+                "roadmap.html",
+                &templates,
+                "roadmap.html",
+                &roadmap_context,
+            )?;
+            pages.push(page);
+        }
+
+        if let Some(rustdoc_cfg) = &config.components.rustdoc {
+            let rustdoc_context = rustdoc::context(rustdoc_cfg, config);
+            let page = Page::new_from_template(
+                "rustdoc.html",
+                &templates,
+                "rustdoc.html",
+                &rustdoc_context,
+            )?;
+            pages.push(page);
+        }
+
+        if let Some(blog_cfg) = &config.components.blog {
+            let mut blog_pages = blog::build_pages(
+                blog_cfg,
+                &templates,
+                config,
+                &mut taxonomy,
+                &mut author_index,
+            )?;
+            pages.append(&mut blog_pages);
+        }
+
+        if config.components.authors.is_some() {
+            pages.push(author_index.build_page(&templates)?);
+        }
+
+        if !taxonomy.is_empty() {
+            let mut tag_pages =
+                tags::build_pages(&taxonomy, &templates, &config.build.path_prefix)?;
+            pages.append(&mut tag_pages);
+        }
+
+        if let Some(benchmarks_cfg) = &config.components.benchmarks {
+            let benchmarks_context = benchmarks::context(benchmarks_cfg, &config.root_dir);
+            let page = Page::new_from_template(
+                "benchmarks.html",
+                &templates,
+                "benchmarks.html",
+                &benchmarks_context,
+            )?;
+            pages.push(page);
+        }
+
+        if let Some(data_cfg) = &config.components.data_pages {
+            let mut data_pages = data_pages::build_pages(&templates, data_cfg, &config.root_dir)?;
+            pages.append(&mut data_pages);
+        }
+
+        if config.build.offline_support {
+            let page =
+                Page::new_from_template("offline.html", &templates, "offline.html", &context!())?;
+            pages.push(page);
+        }
+
         let index = if let Some(index) = index {
             index
         } else {
@@ -171,14 +553,55 @@ impl Site {
                 "index.html",
                 &templates,
                 "index.html",
-                context!(),
+                context!(
+                    health => health_context,
+                    activity => activity_context,
+                    github_action => github_action_context,
+                    demo => demo_context,
+                ),
                 config,
             )?
         };
         pages.push(index);
+        if config.build.emit_json {
+            let mut api_pages = api::build_api_pages(
+                &pages,
+                config,
+                &templates,
+                context.as_ref(),
+                artifacts_context.as_ref(),
+                funding_context.as_ref(),
+            )?;
+            pages.append(&mut api_pages);
+        }
+        pages.extend(well_known::build_pages(config, funding_context.as_ref())?);
+        canonical::add_links(&mut pages, config);
+        // A sitemap can only have absolute URLs in it
+        if link::base_url(config).is_some() {
+            let sitemap = sitemap::generate_sitemap(&pages, config);
+            pages.push(Page {
+                contents: sitemap,
+                filename: "sitemap.xml".to_string(),
+                reading_time: None,
+                stale: None,
+            });
+        } else if Self::has_rss_feeds(config) {
+            tracing::warn!(
+                "Your RSS feeds will link to your pages without a domain, set build.base_url or project.homepage to the URL your site is served at"
+            );
+        }
+        // A workspace member's dist dir isn't the root of the site
+        if prefix.is_none() {
+            pages.extend(canonical::cname_page(config));
+        }
+        if let Some(incremental) = incremental {
+            incremental.keep(&config.build.dist_dir, context.as_ref(), funding.as_ref());
+        }
         Ok(Site {
             pages,
             workspace_data: None,
+            files,
+            plan: plan.cloned(),
         })
     }
 
@@ -192,12 +615,14 @@ impl Site {
         };
 
         if let Some(mut context) = context {
+            Self::record_releases(&context);
             if config.components.artifacts_enabled() {
                 if let Some(latest) = context.latest_mut() {
                     latest.artifacts.make_scripts_viewable(config)?;
                     let template_context = artifacts::template_context(&context, config)?;
                     if let Some(template_context) = template_context {
-                        artifacts::write_artifacts_json(config, &template_context)?;
+                        let mut writer = DirWriter::new(&config.build.dist_dir);
+                        artifacts::write_artifacts_json(&template_context, &mut writer)?;
                     }
                 }
             }
@@ -206,36 +631,80 @@ impl Site {
         Ok(Site {
             pages: vec![],
             workspace_data: None,
+            files: MemoryWriter::new(),
+            plan: None,
         })
     }
 
-    pub fn get_workspace_config() -> Result<Option<Config>> {
+    pub fn get_workspace_config(options: &ConfigOptions) -> Result<Option<Config>> {
         let path = Utf8PathBuf::from("./oranda-workspace.json");
         if path.exists() {
-            let workspace_config = Config::build_workspace_root(&path)?;
+            let workspace_config = Config::build_workspace_root(&path, options)?;
             Ok(Some(workspace_config))
         } else {
             Ok(None)
         }
     }
 
-    fn needs_context(config: &Config) -> Result<bool> {
+    pub(crate) fn needs_context(config: &Config) -> Result<bool> {
         Ok(config.project.repository.is_some()
             && (config.components.artifacts_enabled()
                 || config.components.changelog.is_some()
                 || config.components.funding.is_some()
-                || Self::has_repo_and_releases(&config.project.repository)?))
+                || Self::has_repo_and_releases(config)?))
+    }
+
+    fn has_rss_feeds(config: &Config) -> bool {
+        config
+            .components
+            .changelog
+            .as_ref()
+            .is_some_and(|changelog| changelog.rss_feed || changelog.prerelease_rss_feed)
+            || config
+                .components
+                .blog
+                .as_ref()
+                .is_some_and(|blog| blog.rss_feed)
     }
 
-    fn has_repo_and_releases(repo_config: &Option<String>) -> Result<bool> {
-        if let Some(repo) = repo_config {
-            GithubRelease::repo_has_releases(&GithubRepo::from_url(repo)?)
+    fn has_repo_and_releases(config: &Config) -> Result<bool> {
+        if let Some(repo) = &config.project.repository {
+            GithubRelease::repo_has_releases(&config.root_dir, &GithubRepo::from_url(repo)?)
         } else {
             Ok(false)
         }
     }
 
     fn print_plan(config: &Config) {
+        let planned_components = Self::planned_components(config);
+        report::record_components(&planned_components);
+        let joined = planned_components
+            .iter()
+            .fold(String::new(), |acc, component| {
+                if acc.is_empty() {
+                    component.to_string()
+                } else {
+                    format!("{}, {}", acc, component)
+                }
+            });
+        if !joined.is_empty() {
+            tracing::info!("Building components: {}", joined);
+        }
+    }
+
+    /// Counts the releases we fetched for the build report, leaving out the placeholder we make
+    /// when there aren't any
+    fn record_releases(context: &Context) {
+        let fetched = context
+            .releases
+            .iter()
+            .filter(|release| !release.source.is_current_state())
+            .count();
+        report::record_releases(fetched);
+    }
+
+    /// The components a build with this config will build, in the order we announce them
+    pub fn planned_components(config: &Config) -> Vec<&'static str> {
         let mut planned_components = Vec::new();
         if config.components.artifacts_enabled() {
             planned_components.push("artifacts");
@@ -249,32 +718,97 @@ impl Site {
         if config.components.mdbook.is_some() {
             planned_components.push("mdbook");
         }
+        if config.components.docs.is_some() {
+            planned_components.push("docs");
+        }
+        if config.components.rustdoc.is_some() {
+            planned_components.push("rustdoc");
+        }
+        if config.components.support_matrix.is_some() {
+            planned_components.push("support_matrix");
+        }
+        if config.components.features.is_some() {
+            planned_components.push("features");
+        }
+        if config.components.licenses.is_some() {
+            planned_components.push("licenses");
+        }
+        if config.components.roadmap.is_some() {
+            planned_components.push("roadmap");
+        }
+        if config.components.security.is_some() {
+            planned_components.push("security");
+        }
+        if config.components.community.is_some() {
+            planned_components.push("community");
+        }
+        if config.components.benchmarks.is_some() {
+            planned_components.push("benchmarks");
+        }
+        if config.components.health.is_some() {
+            planned_components.push("health");
+        }
+        if config.components.activity.is_some() {
+            planned_components.push("activity");
+        }
+        if config.components.comments.is_some() {
+            planned_components.push("comments");
+        }
+        if config.components.blog.is_some() {
+            planned_components.push("blog");
+        }
+        if config.components.authors.is_some() {
+            planned_components.push("authors");
+        }
+        if config.components.demo.is_some() {
+            planned_components.push("demo");
+        }
+        if config.components.data_pages.is_some() {
+            planned_components.push("data_pages");
+        }
+        planned_components
+    }
 
-        let joined = planned_components
-            .iter()
-            .fold(String::new(), |acc, component| {
-                if acc.is_empty() {
-                    component.to_string()
-                } else {
-                    format!("{}, {}", acc, component)
-                }
-            });
-        if !joined.is_empty() {
-            tracing::info!("Building components: {}", joined);
+    /// Gathers the project's releases and its funding data, if the site needs them. Both mostly
+    /// wait on GitHub, so they're fetched at the same time.
+    fn fetch_context_and_funding(config: &Config) -> Result<(Option<Context>, Option<Funding>)> {
+        if !Self::needs_context(config)? {
+            return Ok((None, None));
         }
+        let runtime = tokio::runtime::Handle::current();
+        std::thread::scope(|scope| {
+            let funding = config.components.funding.as_ref().map(|funding_cfg| {
+                scope.spawn(|| {
+                    let _guard = runtime.enter();
+                    Funding::new(funding_cfg, config)
+                })
+            });
+            let context = Self::build_context(config)?;
+            let funding = funding
+                .map(|handle| handle.join().expect("fetching funding data panicked"))
+                .transpose()?;
+            Ok((Some(context), funding))
+        })
     }
 
-    fn build_context(config: &Config) -> Result<Context> {
+    /// Gathers everything we know about the project's releases
+    pub fn build_context(config: &Config) -> Result<Context> {
         let Some(repo_url) = config.project.repository.as_ref() else {
-            return Context::new_current(&config.project, config.components.artifacts.as_ref());
+            return Context::new_current(
+                &config.root_dir,
+                &config.project,
+                config.components.artifacts.as_ref(),
+            );
         };
         let maybe_ctx = match config.components.source {
             Some(ReleasesSource::GitHub) | None => Context::new_github(
+                &config.root_dir,
                 repo_url,
                 &config.project,
                 config.components.artifacts.as_ref(),
             ),
             Some(ReleasesSource::Axodotdev) => Context::new_axodotdev(
+                &config.root_dir,
                 &config.project.name,
                 repo_url,
                 &config.project,
@@ -283,12 +817,16 @@ impl Site {
         };
 
         match maybe_ctx {
-            Ok(c) => Ok(c),
+            Ok(mut c) => {
+                c.add_channels(&config.root_dir, config.components.artifacts.as_ref());
+                Ok(c)
+            }
             Err(e) => {
                 // We don't want to hard error here, as we can most likely keep on going even
                 // without a well-formed context.
-                eprintln!("{:?}", miette::Report::new(e));
+                diagnostics::report(e);
                 Ok(Context::new_current(
+                    &config.root_dir,
                     &config.project,
                     config.components.artifacts.as_ref(),
                 )?)
@@ -300,11 +838,66 @@ impl Site {
         files: &IndexMap<String, String>,
         templates: &Templates,
         config: &Config,
+        taxonomy: &mut Taxonomy,
     ) -> Result<Vec<Page>> {
+        let sidebar = config
+            .build
+            .sidebar
+            .as_deref()
+            .map(|path| Sidebar::load(path, config))
+            .transpose()?;
+        // Pages that are only linked from the sidebar get built too
+        let mut files = files
+            .iter()
+            .map(|(name, path)| (name.clone(), path.clone()))
+            .collect::<Vec<_>>();
+        if let Some(sidebar) = &sidebar {
+            for (title, source) in sidebar.sources() {
+                let already_built = files
+                    .iter()
+                    .any(|(_, path)| page::source::normalize(Utf8Path::new(path)) == source);
+                if !already_built {
+                    files.push((title, source.to_string()));
+                }
+            }
+        }
+
         let mut pages = vec![];
-        for file_path in files.values() {
+        let mut stale_pages = vec![];
+        for (name, file_path) in &files {
             if page::source::is_markdown(file_path) {
-                let additional_page = Page::new_from_markdown(file_path, templates, config, true)?;
+                let (additional_page, frontmatter) =
+                    Page::new_from_markdown(file_path, templates, config, sidebar.as_ref(), true)?;
+                if let Some(stale) = &additional_page.stale {
+                    stale_pages.push(format!("{file_path}: {}", stale.reason));
+                }
+                if let Some(copy) =
+                    Page::new_sponsor_copy(file_path, templates, config, sidebar.as_ref())?
+                {
+                    pages.push(copy);
+                }
+                if frontmatter.private {
+                    pages.push(additional_page);
+                    continue;
+                }
+                if let Some(path) =
+                    page::source::get_filename_with_dir(file_path, &config.root_dir)?
+                {
+                    taxonomy.add(
+                        &frontmatter.tags,
+                        &TaggedItem {
+                            kind: TaggedItemKind::Page,
+                            title: frontmatter.title.unwrap_or_else(|| name.clone()),
+                            link: link::generate_relative(
+                                &config.build.path_prefix,
+                                &format!("{}/", path),
+                            ),
+                            date: None,
+                            formatted_date: None,
+                            description: frontmatter.description,
+                        },
+                    );
+                }
                 pages.push(additional_page)
             } else {
                 let msg = format!(
@@ -314,6 +907,13 @@ impl Site {
                 tracing::warn!("{}", &msg);
             }
         }
+        if !stale_pages.is_empty() {
+            tracing::warn!(
+                "{} page(s) may be outdated:\n  {}",
+                stale_pages.len(),
+                stale_pages.join("\n  ")
+            );
+        }
         Ok(pages)
     }
 
@@ -324,10 +924,7 @@ impl Site {
     ) -> Result<Vec<Page>> {
         let mut pages = vec![];
         // Recompute the axoproject layer here (unfortunately we don't pass it around)
-        let cur_dir = std::env::current_dir()?;
-        let project = AxoprojectLayer::get_best_workspace(
-            &Utf8PathBuf::from_path_buf(cur_dir).expect("Current directory isn't UTF-8?"),
-        );
+        let project = AxoprojectLayer::get_best_workspace(&config.root_dir);
         let index_context = changelog::index_context(context, config, project.as_ref())?;
         let changelog_page = Page::new_from_template(
             "changelog.html",
@@ -346,11 +943,52 @@ impl Site {
             pages.push(Page {
                 contents: changelog_rss.to_string(),
                 filename: "changelog.rss".to_string(),
+                reading_time: None,
+                stale: None,
+            });
+        }
+        if config
+            .components
+            .changelog
+            .as_ref()
+            .is_some_and(|c| c.prerelease_rss_feed)
+        {
+            let prerelease_rss = rss::generate_prerelease_rss_feed(&index_context, config)?;
+            pages.push(Page {
+                contents: prerelease_rss.to_string(),
+                filename: "changelog-prerelease.rss".to_string(),
+                reading_time: None,
+                stale: None,
             });
         }
+        if config.components.changelog.as_ref().is_some_and(|c| c.json) {
+            let entries = changelog::json_entries(context, config, project.as_ref())?;
+            pages.push(Page {
+                contents: serde_json::to_string_pretty(&entries)?,
+                filename: "changelog.json".to_string(),
+                reading_time: None,
+                stale: None,
+            });
+        }
+        if config
+            .components
+            .changelog
+            .as_ref()
+            .is_some_and(|c| c.widget)
+        {
+            let whats_new = widgets::whats_new_context(context, config, project.as_ref());
+            pages.push(Page::new_from_template(
+                widgets::WHATS_NEW,
+                templates,
+                "widgets/whats_new.html",
+                &whats_new,
+            )?);
+        }
         if !(context.releases.len() == 1 && context.releases[0].source.is_current_state()) {
-            for release in context.releases.iter() {
-                let single_context = changelog::single_context(release, config, project.as_ref());
+            for (idx, release) in context.releases.iter().enumerate() {
+                let previous = changelog::previous_release(&context.releases, idx);
+                let single_context =
+                    changelog::single_context(release, previous, config, project.as_ref());
                 let page = Page::new_from_template(
                     &format!("changelog/{}.html", single_context.version_tag),
                     templates,
@@ -360,6 +998,22 @@ impl Site {
                 pages.push(page);
             }
         }
+        for guide in config
+            .components
+            .changelog
+            .iter()
+            .flat_map(|changelog| &changelog.upgrade_guides)
+        {
+            let guide_context = changelog::upgrade_guide_context(guide, context, config)?;
+            pages.push(Page::new_from_both(
+                &guide.path,
+                &changelog::upgrade_guide_filename(guide),
+                templates,
+                "upgrade_guide.html",
+                guide_context,
+                config,
+            )?);
+        }
         Ok(pages)
     }
 
@@ -379,58 +1033,160 @@ impl Site {
     /// page instead (its config is stored in the `Site` struct itself). If none of these apply,
     /// that's a bug (for now).
     pub fn write(self, config: Option<&Config>) -> Result<()> {
-        // Differentiate between workspace page write or single page write by checking if there's a
-        // workspace config set in the struct, or if the (single) page config is manually passed to
-        // the function.
-        let config = if let Some(config) = config {
-            config
+        let dist = Utf8PathBuf::from(&Self::config_of(&self.workspace_data, config).build.dist_dir);
+        self.write_to(config, &mut DirWriter::new(dist))
+    }
+
+    /// Like [`Site::write`][], but hands the site's files to `writer` instead of writing them to
+    /// the dist dir. Whatever the build itself placed in the dist dir, like images the pages
+    /// link to, goes to the writer too.
+    pub fn write_to(self, config: Option<&Config>, writer: &mut dyn SiteWriter) -> Result<()> {
+        let config = Self::config_of(&self.workspace_data, config);
+        let dist = Utf8PathBuf::from(&config.build.dist_dir);
+        if writer.local_dir() != Some(dist.as_path()) && dist.is_dir() {
+            writer.write_dir(Utf8Path::new(""), &dist)?;
+        }
+        for (path, contents) in &self.files.files {
+            writer.write(path, contents)?;
+        }
+        let has_private_pages = self
+            .pages
+            .iter()
+            .any(|page| private::is_private_output(&page.filename, config));
+        // Fail before anything's written, rather than publish pages that should've been private
+        let private_credentials = if has_private_pages {
+            let from_env = std::env::var(private::CREDENTIALS_ENV_VAR).ok();
+            private::access_credentials(config, from_env)?
         } else {
-            &self.workspace_data.as_ref().expect("Attempted to build workspace page without workspace config. This is an oranda bug!").config
+            None
         };
-        let dist = Utf8PathBuf::from(&config.build.dist_dir);
+        let has_recordings = self
+            .pages
+            .iter()
+            .any(|page| page.contents.contains(asciinema::PLAYER_CLASS));
+        let offline_urls = if config.build.offline_support {
+            javascript::offline_urls(config, &self.pages)?
+        } else {
+            vec![]
+        };
+        let mut written_pages = vec![];
         for page in self.pages {
             let filename_path = Utf8PathBuf::from(&page.filename);
             // Prepare to write a "pretty link" for pages that aren't index.html already.
             // This essentially means that we rewrite the page from "page.html" to
             // "page/index.html", so that it can be loaded as "mysite.com/page" in the browser.
-            let full_path: Utf8PathBuf = if !filename_path.ends_with("index.html")
+            let path: Utf8PathBuf = if !filename_path.ends_with("index.html")
                 && filename_path.extension() == Some("html")
+                && !widgets::is_widget(&page.filename)
             {
                 // Surely we can't we do anything BUT unwrap here? A file without a name is a mess.
                 let file_stem = filename_path.file_stem().expect("missing file_stem???");
                 let parent = filename_path.parent().unwrap_or("".into());
-                dist.join(parent).join(file_stem).join("index.html")
+                parent.join(file_stem).join("index.html")
             } else {
-                dist.join(filename_path)
+                filename_path
             };
-            LocalAsset::write_new_all(&page.contents, full_path)?;
-        }
-        if let Some(book_cfg) = &config.components.mdbook {
-            mdbook::build_mdbook(
-                self.workspace_data.as_ref(),
-                &dist,
-                book_cfg,
-                &config.styles.theme,
-                &config.styles.syntax_theme,
-            )?;
+            writer.write(&path, page.contents.as_bytes())?;
+            if path.extension() == Some("html") {
+                written_pages.push(path);
+            }
+        }
+        if let Some(book_cfg) = config
+            .components
+            .mdbook
+            .as_ref()
+            .filter(|_| rebuild::should_build(self.plan.as_ref(), Output::Mdbook))
+        {
+            mdbook::build_mdbook(&config.root_dir, &dist, book_cfg, &config.styles, writer)?;
+        }
+        if let Some(docs_cfg) = config
+            .components
+            .docs
+            .as_ref()
+            .filter(|_| rebuild::should_build(self.plan.as_ref(), Output::Docs))
+        {
+            docs::build_docs(&config.root_dir, docs_cfg, writer)?;
+        }
+        if let Some(demo_cfg) = config
+            .components
+            .demo
+            .as_ref()
+            .filter(|_| rebuild::should_build(self.plan.as_ref(), Output::Demo))
+        {
+            demo::copy_demo(&config.root_dir, demo_cfg, writer)?;
+        }
+        if let Some(rustdoc_cfg) = config
+            .components
+            .rustdoc
+            .as_ref()
+            .filter(|_| rebuild::should_build(self.plan.as_ref(), Output::Rustdoc))
+        {
+            rustdoc::build_rustdoc(rustdoc_cfg, config, writer)?;
+        }
+        if let Some(favicon) = config.styles.favicon.as_ref() {
+            let origin_path = resolve_asset(&config.root_dir, favicon);
+            let load_future = Asset::load_bytes(&origin_path);
+            let contents = tokio::runtime::Handle::current().block_on(load_future)?;
+            let filename = origin_path.rsplit('/').next().unwrap_or("favicon.ico");
+            writer.write(Utf8Path::new(filename), &contents)?;
         }
-        if let Some(origin_path) = config.styles.favicon.as_ref() {
-            let copy_result_future = Asset::copy(origin_path, &config.build.dist_dir[..]);
-            tokio::runtime::Handle::current().block_on(copy_result_future)?;
+        layout::web_manifest::write_web_manifest(config, writer)?;
+        let static_dir = config.path(&config.build.static_dir);
+        if let (true, Some(name)) = (static_dir.is_dir(), static_dir.file_name()) {
+            writer.write_dir(Utf8Path::new(name), &static_dir)?;
         }
-        if Path::new(&config.build.static_dir).exists() {
-            Self::copy_static(&dist, &config.build.static_dir)?;
+        if let Some(credentials) = &private_credentials {
+            private::write_host_config(&dist, config, credentials, writer)?;
+        }
+        if config.build.compat {
+            css::write_compat_css(writer)?;
+        }
+        javascript::write_os_script(writer)?;
+        if has_recordings {
+            javascript::write_player(writer)?;
+        }
+        if config.build.offline_support {
+            javascript::write_service_worker(writer, &config.build.path_prefix, &offline_urls)?;
         }
-        javascript::write_os_script(&dist)?;
 
         let additional_css = &config.styles.additional_css;
         if !additional_css.is_empty() {
-            css::write_additional_css(additional_css, &dist)?;
+            css::write_additional_css(additional_css, &config.root_dir, writer)?;
+        }
+
+        // This goes last, so that everything the pages link to has been written
+        if config.build.single_file {
+            match writer.local_dir() {
+                Some(dir) => {
+                    for page in &written_pages {
+                        single_file::inline_page(&dir.join(page), dir, &config.build.path_prefix)?;
+                    }
+                }
+                None => tracing::warn!(
+                    "build.single_file only works when writing the site to a directory, so the pages still link to what they use."
+                ),
+            }
         }
 
         Ok(())
     }
 
+    /// The config to write a site with: `config` for a single site, or the member's own for a
+    /// workspace member (see [`Site::write`][])
+    fn config_of<'a>(
+        workspace_data: &'a Option<WorkspaceData>,
+        config: Option<&'a Config>,
+    ) -> &'a Config {
+        // Differentiate between workspace page write or single page write by checking if there's a
+        // workspace config set in the struct, or if the (single) page config is manually passed to
+        // the function.
+        if let Some(config) = config {
+            config
+        } else {
+            &workspace_data.as_ref().expect("Attempted to build workspace page without workspace config. This is an oranda bug!").config
+        }
+    }
+
     pub fn clean_dist_dir(dist_path: &str) -> Result<()> {
         if Path::new(dist_path).exists() {
             std::fs::remove_dir_all(dist_path)?;
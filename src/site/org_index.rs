@@ -0,0 +1,72 @@
+use serde::Serialize;
+
+use crate::data::org::{OrgProject, OrgRelease};
+use crate::data::ReleaseSource;
+
+#[derive(Serialize, Debug)]
+pub struct OrgIndexContext {
+    /// The organization's name on GitHub
+    pub org: String,
+    /// The projects, most starred first
+    pub projects: Vec<OrgIndexProject>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct OrgIndexProject {
+    pub name: String,
+    pub description: Option<String>,
+    pub readme_excerpt: Option<String>,
+    pub language: Option<String>,
+    pub stars: u64,
+    /// The project's own website, if it has one
+    pub homepage: Option<String>,
+    pub repository: String,
+    pub latest_release: Option<OrgIndexRelease>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct OrgIndexRelease {
+    pub tag_name: String,
+    pub url: String,
+    pub formatted_date: Option<String>,
+}
+
+impl OrgIndexContext {
+    pub fn new(org: &str, projects: &[OrgProject]) -> Self {
+        let mut projects = projects
+            .iter()
+            .map(|project| OrgIndexProject {
+                name: project.repo.name.clone(),
+                description: project.repo.description.clone(),
+                readme_excerpt: project.readme_excerpt.clone(),
+                language: project.repo.language.clone(),
+                stars: project.repo.stargazers_count,
+                homepage: project
+                    .repo
+                    .homepage
+                    .clone()
+                    .filter(|homepage| !homepage.is_empty()),
+                repository: project.repo.html_url.clone(),
+                latest_release: project.latest_release.as_ref().map(OrgIndexRelease::new),
+            })
+            .collect::<Vec<_>>();
+        projects.sort_by(|a, b| b.stars.cmp(&a.stars));
+        Self {
+            org: org.to_owned(),
+            projects,
+        }
+    }
+}
+
+impl OrgIndexRelease {
+    fn new(release: &OrgRelease) -> Self {
+        Self {
+            tag_name: release.tag_name.clone(),
+            url: release.html_url.clone(),
+            formatted_date: release
+                .published_at
+                .as_deref()
+                .map(ReleaseSource::format_date),
+        }
+    }
+}
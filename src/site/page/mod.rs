@@ -1,23 +1,75 @@
 use std::path::Path;
 
 use crate::config::Config;
+use crate::data::blog::split_frontmatter;
+use crate::data::git::{self, FileHistory};
 use crate::errors::*;
-use crate::site::markdown::{self, SyntaxTheme};
+use crate::site::markdown::{self, relative_links, ReadingTime};
+use crate::site::page::stale::Staleness;
+use crate::site::sidebar::Sidebar;
+use crate::site::tags::TagLink;
 
 use crate::paths::determine_path;
 use crate::site::templates::Templates;
-use axoasset::SourceFile;
-use camino::Utf8PathBuf;
+use axoasset::{LocalAsset, SourceFile};
+use camino::{Utf8Path, Utf8PathBuf};
+use chrono::Local;
 use minijinja::context;
 use minijinja::value::Value;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 pub mod source;
+pub mod stale;
 
 #[derive(Debug)]
 pub struct Page {
     pub contents: String,
     pub filename: String,
+    /// Set for pages rendered from Markdown, like blog posts and additional pages
+    pub reading_time: Option<ReadingTime>,
+    /// Set for additional pages that may be outdated
+    pub stale: Option<Staleness>,
+}
+
+/// The metadata an additional page can declare in a leading YAML frontmatter block
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct PageFrontmatter {
+    /// Used for the page's `<title>`
+    pub title: Option<String>,
+    pub tags: Vec<String>,
+    pub description: Option<String>,
+    /// A date (YYYY-MM-DD) after which the page should be reviewed again
+    pub review_by: Option<String>,
+    /// Whether the page goes in `build.private_path`, for the host to restrict access to
+    pub private: bool,
+    /// Where the page goes in the nav. Lower weights come first, and pages with the same
+    /// weight keep the order they're configured in.
+    pub weight: i32,
+}
+
+impl PageFrontmatter {
+    /// Splits the frontmatter off a page's source, returning it and the rest of the page
+    pub fn parse(source: &str) -> (Self, &str) {
+        match split_frontmatter(source) {
+            // A page that happens to start with a horizontal rule isn't frontmatter, so
+            // if it doesn't parse, render the whole thing.
+            (Some(yaml), body) => match serde_yaml::from_str(yaml) {
+                Ok(parsed) => (parsed, body),
+                Err(_) => (Self::default(), source),
+            },
+            (None, body) => (Self::default(), body),
+        }
+    }
+
+    /// Reads just the frontmatter of the page at `path`, if there is one
+    pub fn load(path: impl AsRef<Utf8Path>) -> Self {
+        let path = path.as_ref();
+        match LocalAsset::load_string(path) {
+            Ok(source) => Self::parse(&source).0,
+            Err(_) => Self::default(),
+        }
+    }
 }
 
 impl Page {
@@ -34,21 +86,96 @@ impl Page {
         Ok(Self {
             contents,
             filename: filename.to_string(),
+            reading_time: None,
+            stale: None,
         })
     }
 
     /// Creates a new page by rendering a Markdown file into the "markdown page" template. Automatically
     /// determines the output path based on the path to the input Markdown file, diffing it with the
     /// basepath of the project.
+    ///
+    /// The file can start with a frontmatter block, which is returned alongside the page.
     pub fn new_from_markdown(
         path: &str,
         templates: &Templates,
         config: &Config,
+        sidebar: Option<&Sidebar>,
         fail_fast: bool,
-    ) -> Result<Self> {
-        let body = Self::load_and_render_contents(path, &config.styles.syntax_theme)?;
-        let contents = if let Some(body) = body {
-            templates.render_to_string("markdown_page.html", context!(body))?
+    ) -> Result<(Self, PageFrontmatter)> {
+        Self::render_markdown_page(path, templates, config, sidebar, fail_fast, false)
+    }
+
+    /// Creates the full version of a Markdown page with sponsor-only sections, which is written
+    /// to `build.private_path`. Returns `None` if `markdown.sponsor_copies` is off, or the page
+    /// has no such sections (or is private already, and so rendered in full anyway).
+    pub fn new_sponsor_copy(
+        path: &str,
+        templates: &Templates,
+        config: &Config,
+        sidebar: Option<&Sidebar>,
+    ) -> Result<Option<Self>> {
+        if !config.markdown.sponsor_copies {
+            return Ok(None);
+        }
+        let Some(source) = Self::load_contents(path, config)? else {
+            return Ok(None);
+        };
+        let (frontmatter, body) = PageFrontmatter::parse(&source);
+        if frontmatter.private || !markdown::sponsors::has_sections(body) {
+            return Ok(None);
+        }
+        let (page, _) = Self::render_markdown_page(path, templates, config, sidebar, true, true)?;
+        Ok(Some(page))
+    }
+
+    fn render_markdown_page(
+        path: &str,
+        templates: &Templates,
+        config: &Config,
+        sidebar: Option<&Sidebar>,
+        fail_fast: bool,
+        for_sponsors: bool,
+    ) -> Result<(Self, PageFrontmatter)> {
+        let source = Self::load_contents(path, config)?;
+        let mut frontmatter = PageFrontmatter::default();
+        let mut reading_time = None;
+        let mut stale = None;
+        let contents = if let Some(source) = source {
+            let (parsed, body) = PageFrontmatter::parse(&source);
+            frontmatter = parsed;
+            // Private pages are already behind a password, so they get sponsor-only sections too
+            let body = if for_sponsors || frontmatter.private {
+                markdown::to_html_for_sponsors(body, config)?
+            } else {
+                markdown::to_html(body, config)?
+            };
+            let body = relative_links::rewrite(&body, path, config)?;
+            reading_time = Some(ReadingTime::from_html(&body));
+            let history = if config.build.page_history || config.build.stale_after_days.is_some() {
+                Self::load_history(path, config)
+            } else {
+                None
+            };
+            stale = stale::staleness(
+                frontmatter.review_by.as_deref(),
+                history.as_ref(),
+                config.build.stale_after_days,
+                Local::now().date_naive(),
+            );
+            // We only loaded the history to check how old the page is
+            let history = history.filter(|_| config.build.page_history);
+            let title = frontmatter.title.clone();
+            let tags = frontmatter
+                .tags
+                .iter()
+                .map(|tag| TagLink::new(tag, &config.build.path_prefix))
+                .collect::<Vec<_>>();
+            let sidebar = sidebar.map(|sidebar| sidebar.context(path));
+            templates.render_to_string(
+                "markdown_page.html",
+                context!(body, history, title, tags, reading_time, sidebar, stale),
+            )?
         } else {
             if fail_fast {
                 return Err(OrandaError::PathDoesNotExist {
@@ -57,17 +184,26 @@ impl Page {
             }
             templates.render_to_string("markdown_page.html", context!())?
         };
-        // Try diffing with the execution directory in case the user has provided an absolute-ish
+        // Try diffing with the project's directory in case the user has provided an absolute-ish
         // path, in order to obtain the relative-to-dir path segment
-        let relpath = if let Some(path) = pathdiff::diff_paths(path, std::env::current_dir()?) {
+        let relpath = if let Some(path) = pathdiff::diff_paths(path, &config.root_dir) {
             path
         } else {
             path.into()
         };
-        Ok(Self {
+        // Written as "page.html", which `Site::write` turns into "page/index.html"
+        let mut filename =
+            source::normalize(Utf8Path::new(&relpath.display().to_string())).with_extension("html");
+        if for_sponsors || frontmatter.private {
+            filename = Utf8Path::new(&config.build.private_path).join(filename);
+        }
+        let page = Self {
             contents,
-            filename: relpath.display().to_string(),
-        })
+            filename: filename.to_string(),
+            reading_time,
+            stale,
+        };
+        Ok((page, frontmatter))
     }
 
     /// Combines both above functions by rendering a Markdown file into an arbitrary template. The markdown
@@ -83,31 +219,52 @@ impl Page {
         context: T,
         config: &Config,
     ) -> Result<Self> {
-        let body = Self::load_and_render_contents(path, &config.styles.syntax_theme)?;
+        let body = Self::load_and_render_contents(path, config)?;
         if body.is_none() {
             tracing::warn!("{} could not be found on disk!", path);
         }
         let template = templates.get(template_name)?;
-        let context =
-            context!(layout => templates.layout, page => context, markdown_content => body);
+        let context = context!(
+            layout => templates.layout,
+            config => templates.config,
+            page => context,
+            markdown_content => body,
+        );
         let contents = template.render(context)?;
         Ok(Self {
             contents,
             filename: filename.to_string(),
+            reading_time: None,
+            stale: None,
         })
     }
 
-    fn load_and_render_contents(
-        source: &str,
-        syntax_theme: &SyntaxTheme,
-    ) -> Result<Option<String>> {
-        let src_path = Utf8PathBuf::from_path_buf(std::env::current_dir()?)
-            .expect("Current directory is not UTF-8");
-        let path = determine_path(src_path, &None::<Utf8PathBuf>, source)?;
+    /// Reads the git history for a page's source file. Failing to do so shouldn't break
+    /// the build, so we only warn about it.
+    fn load_history(path: &str, config: &Config) -> Option<FileHistory> {
+        match git::file_history(&config.root_dir, path) {
+            Ok(history) => history,
+            Err(e) => {
+                tracing::warn!("Couldn't read git history for {}: {}", path, e);
+                None
+            }
+        }
+    }
+
+    fn load_and_render_contents(source: &str, config: &Config) -> Result<Option<String>> {
+        Self::load_contents(source, config)?
+            .map(|contents| {
+                let html = markdown::to_html(&contents, config)?;
+                relative_links::rewrite(&html, source, config)
+            })
+            .transpose()
+    }
+
+    fn load_contents(source: &str, config: &Config) -> Result<Option<String>> {
+        let path = determine_path(&config.root_dir, &None::<Utf8PathBuf>, source)?;
         if let Some(path) = path {
-            let source = SourceFile::load_local(path)?;
-            let contents = source.contents();
-            Ok(Some(markdown::to_html(contents, syntax_theme)?))
+            let source = SourceFile::load_local(config.path(path))?;
+            Ok(Some(source.contents().to_owned()))
         } else {
             Ok(None)
         }
@@ -1,5 +1,5 @@
-use crate::errors::{OrandaError, Result};
-use camino::Utf8PathBuf;
+use crate::errors::Result;
+use camino::{Utf8Component, Utf8Path, Utf8PathBuf};
 use std::path::Path;
 
 pub fn is_markdown(file: &str) -> bool {
@@ -10,12 +10,10 @@ pub fn is_markdown(file: &str) -> bool {
     }
 }
 
-pub fn get_filename_with_dir(file: &str) -> Result<Option<Utf8PathBuf>> {
-    // Try diffing with the execution directory in case the user has provided an absolute-ish
+pub fn get_filename_with_dir(file: &str, root: &Utf8Path) -> Result<Option<Utf8PathBuf>> {
+    // Try diffing with the project's directory in case the user has provided an absolute-ish
     // path, in order to obtain the relative-to-dir path segment
-    let cur_dir = Utf8PathBuf::from_path_buf(std::env::current_dir()?)
-        .map_err(|_| OrandaError::Other("Unable to read your current working directory.".into()))?;
-    let path = if let Some(path) = pathdiff::diff_utf8_paths(file, cur_dir) {
+    let path = if let Some(path) = pathdiff::diff_utf8_paths(file, root) {
         path
     } else {
         Utf8PathBuf::from(file)
@@ -23,3 +21,20 @@ pub fn get_filename_with_dir(file: &str) -> Result<Option<Utf8PathBuf>> {
 
     Ok(Some(path.with_extension("")))
 }
+
+/// Removes `.` and resolves `..` in a relative path, without touching the filesystem
+pub fn normalize(path: &Utf8Path) -> Utf8PathBuf {
+    let mut normalized = Utf8PathBuf::new();
+    for component in path.components() {
+        match component {
+            Utf8Component::CurDir => {}
+            Utf8Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push("..");
+                }
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
@@ -0,0 +1,49 @@
+//! Working out whether an additional page may be outdated, either because the `review_by` date
+//! in its frontmatter has passed, or because it hasn't been changed in `build.stale_after_days`.
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::data::blog::parse_date;
+use crate::data::git::FileHistory;
+
+/// Why a page may be outdated
+#[derive(Serialize, Debug, Clone)]
+pub struct Staleness {
+    /// Something like "it was due for review on Jan  1 2024"
+    pub reason: String,
+}
+
+/// Checks `review_by` first, since that's what the author asked for, and falls back to how
+/// long it's been since the page was last changed.
+pub fn staleness(
+    review_by: Option<&str>,
+    history: Option<&FileHistory>,
+    stale_after_days: Option<u32>,
+    today: NaiveDate,
+) -> Option<Staleness> {
+    if let Some(review_by) = review_by {
+        match parse_date(review_by) {
+            Some(date) if date < today => {
+                return Some(Staleness {
+                    reason: format!("it was due for review on {}", date.format("%b %e %Y")),
+                });
+            }
+            // A review date in the future overrides the git age, so authors can sign off on a
+            // page that's old but still accurate
+            Some(_) => return None,
+            None => tracing::warn!("Couldn't parse review_by date {}", review_by),
+        }
+    }
+
+    let stale_after_days = stale_after_days?;
+    let history = history?;
+    let last_updated = history.last_updated_date?;
+    let age = (today - last_updated).num_days();
+    (age > i64::from(stale_after_days)).then(|| Staleness {
+        reason: format!(
+            "it was last updated on {}, more than {} days ago",
+            history.last_updated, stale_after_days
+        ),
+    })
+}
@@ -0,0 +1,93 @@
+//! Pages marked `private: true` in their frontmatter.
+//!
+//! oranda can't keep anyone out of a static site by itself, so private pages are all written
+//! to `build.private_path`, left out of everything that lists pages publicly, and we write the
+//! config for the host to put that directory behind a password.
+
+use axoasset::LocalAsset;
+use camino::{Utf8Path, Utf8PathBuf};
+
+use crate::config::{Config, PrivateHost};
+use crate::errors::*;
+use crate::site::link;
+use crate::site::page::{source, PageFrontmatter};
+use crate::site::writer::SiteWriter;
+
+/// Where we read basic auth credentials for private pages from, as `user:password` (or several
+/// of those, separated by spaces)
+pub const CREDENTIALS_ENV_VAR: &str = "ORANDA_PRIVATE_AUTH";
+
+/// The path (without extension) the markdown page at `path` is written to, which is inside
+/// `build.private_path` for private pages
+pub fn page_path(path: &str, config: &Config) -> Result<Option<Utf8PathBuf>> {
+    let Some(page_path) = source::get_filename_with_dir(path, &config.root_dir)? else {
+        return Ok(None);
+    };
+    if PageFrontmatter::load(config.path(path)).private {
+        Ok(Some(
+            Utf8Path::new(&config.build.private_path).join(page_path),
+        ))
+    } else {
+        Ok(Some(page_path))
+    }
+}
+
+/// Whether a page we're writing (see [`Page::filename`][crate::site::page::Page]) is private.
+/// This compares whole path segments, so "internal-notes.html" isn't in "internal".
+pub fn is_private_output(filename: &str, config: &Config) -> bool {
+    let segments = |path: &str| {
+        path.split('/')
+            .filter(|segment| !segment.is_empty() && *segment != ".")
+            .map(str::to_owned)
+            .collect::<Vec<_>>()
+    };
+    let private_path = segments(&config.build.private_path);
+    !private_path.is_empty() && segments(filename).starts_with(&private_path)
+}
+
+/// Checks that private pages will actually be kept private, before any of them are written:
+/// `build.private_host` has to be chosen, and Netlify needs credentials, which are passed in
+/// from [`CREDENTIALS_ENV_VAR`][]. Returns the credentials to write, if the host takes any.
+pub fn access_credentials(config: &Config, credentials: Option<String>) -> Result<Option<String>> {
+    match config.build.private_host {
+        None => Err(OrandaError::PrivateHostMissing {
+            private_path: config.build.private_path.clone(),
+        }),
+        Some(PrivateHost::None) => Ok(None),
+        Some(PrivateHost::Netlify) => credentials
+            .map(|credentials| credentials.trim().to_owned())
+            .filter(|credentials| !credentials.is_empty())
+            .map(Some)
+            .ok_or_else(|| OrandaError::PrivateCredentialsMissing {
+                var: CREDENTIALS_ENV_VAR.to_owned(),
+            }),
+    }
+}
+
+/// Writes the host's access rules for the private pages, next to any the user already has in
+/// `dist`. Credentials are only ever written for a host that's been chosen explicitly.
+pub fn write_host_config(
+    dist: &Utf8Path,
+    config: &Config,
+    credentials: &str,
+    writer: &mut dyn SiteWriter,
+) -> Result<()> {
+    if config.build.private_host == Some(PrivateHost::Netlify) {
+        let rules = netlify_headers(config, credentials);
+        // The user's own rules were copied over from the static dir already
+        let existing = LocalAsset::load_string(dist.join("_headers")).unwrap_or_default();
+        let headers = if existing.trim().is_empty() {
+            rules
+        } else {
+            format!("{}\n\n{rules}", existing.trim_end())
+        };
+        writer.write(Utf8Path::new("_headers"), headers.as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Netlify's `_headers` rules for the private pages
+pub fn netlify_headers(config: &Config, credentials: &str) -> String {
+    let path = link::generate_relative(&config.build.path_prefix, &config.build.private_path);
+    format!("{path}/*\n  X-Robots-Tag: noindex\n  Basic-Auth: {credentials}\n")
+}
@@ -0,0 +1,269 @@
+//! What `oranda dev` rebuilds when a file changes.
+//!
+//! Every path dev watches is an [`Input`][] to some of the things a build makes, its
+//! [`Output`][]s. When files change, only the outputs that depend on them are made again: editing
+//! an additional page rerenders the pages with the releases we fetched last time and leaves the
+//! mdbook alone, while editing `oranda.json` still rebuilds everything from scratch.
+//!
+//! `oranda dev` keeps an [`Incremental`][] from one build to the next, which it passes to every
+//! build with the plan for it. Outside of `oranda dev` there is no plan, and every build is a
+//! full one.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::sync::Mutex;
+
+use camino::{Utf8Path, Utf8PathBuf};
+
+use crate::data::funding::Funding;
+use crate::data::Context;
+
+/// Something a build reads, by what it's used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Input {
+    /// `oranda.json`, `oranda-workspace.json`, or a package manifest like `Cargo.toml`
+    Config,
+    Readme,
+    AdditionalPage,
+    /// `FUNDING.yml` and `funding.md`
+    Funding,
+    GithubAction,
+    Demo,
+    /// `book.toml` and everything in the book's source and theme directories
+    Mdbook,
+    /// Whatever `components.docs.watch` lists
+    Docs,
+    /// Anything else, like the paths given to `--include-paths`
+    Other,
+}
+
+/// Something a build makes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Output {
+    /// What we fetch about the project, like its releases and sponsors
+    Releases,
+    /// Every page oranda renders itself, and the files that go with them
+    Pages,
+    Mdbook,
+    Docs,
+    Demo,
+    Rustdoc,
+}
+
+const ALL: &[Output] = &[
+    Output::Releases,
+    Output::Pages,
+    Output::Mdbook,
+    Output::Docs,
+    Output::Demo,
+    Output::Rustdoc,
+];
+
+impl Input {
+    /// What has to be made again when this input changes
+    pub fn outputs(self) -> &'static [Output] {
+        match self {
+            Input::Readme | Input::AdditionalPage | Input::GithubAction => &[Output::Pages],
+            // The index page shows the demo, and the demo itself gets copied next to it
+            Input::Demo => &[Output::Pages, Output::Demo],
+            // Sponsors are fetched along with the releases
+            Input::Funding => &[Output::Releases, Output::Pages],
+            Input::Mdbook => &[Output::Mdbook],
+            Input::Docs => &[Output::Docs],
+            Input::Config | Input::Other => ALL,
+        }
+    }
+}
+
+/// The outputs a build should make
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Rebuild {
+    outputs: BTreeSet<Output>,
+}
+
+impl Rebuild {
+    pub fn everything() -> Self {
+        Self::from(ALL)
+    }
+
+    pub fn includes(&self, output: Output) -> bool {
+        self.outputs.contains(&output)
+    }
+
+    pub fn is_everything(&self) -> bool {
+        ALL.iter().all(|output| self.includes(*output))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.outputs.is_empty()
+    }
+
+    /// Adds the outputs of `other`, like those of a rebuild that failed and still has to happen
+    pub fn merge(&mut self, other: &Rebuild) {
+        self.outputs.extend(&other.outputs);
+    }
+}
+
+impl From<&[Output]> for Rebuild {
+    fn from(outputs: &[Output]) -> Self {
+        Self {
+            outputs: outputs.iter().copied().collect(),
+        }
+    }
+}
+
+impl fmt::Display for Rebuild {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_everything() {
+            return write!(f, "everything");
+        }
+        let outputs = self
+            .outputs
+            .iter()
+            .map(|output| format!("{output:?}").to_lowercase())
+            .collect::<Vec<_>>();
+        write!(f, "{}", outputs.join(", "))
+    }
+}
+
+/// The paths `oranda dev` watches, and what each of them is an input to
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    inputs: Vec<(Utf8PathBuf, Input)>,
+}
+
+impl DependencyGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file, or a directory and everything in it
+    pub fn add(&mut self, path: Utf8PathBuf, input: Input) {
+        self.inputs.push((path, input));
+    }
+
+    pub fn extend(&mut self, other: DependencyGraph) {
+        self.inputs.extend(other.inputs);
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = &Utf8PathBuf> {
+        self.inputs.iter().map(|(path, _)| path)
+    }
+
+    /// What to rebuild now that the files at `changed` have changed. A path that isn't an input
+    /// to anything we know of rebuilds everything, to be safe.
+    pub fn affected(&self, changed: &[Utf8PathBuf]) -> Rebuild {
+        let inputs = self
+            .inputs
+            .iter()
+            .filter_map(|(path, input)| Some((absolute(path)?, *input)))
+            .collect::<Vec<_>>();
+        let mut rebuild = Rebuild::default();
+        for path in changed {
+            let path = absolute(path).unwrap_or_else(|| path.clone());
+            let mut matched = false;
+            for (input_path, input) in &inputs {
+                if path.starts_with(input_path) {
+                    rebuild.merge(&Rebuild::from(input.outputs()));
+                    matched = true;
+                }
+            }
+            if !matched {
+                return Rebuild::everything();
+            }
+        }
+        rebuild
+    }
+}
+
+/// Where `path` is, with any symlinks resolved, so that paths from the config can be compared
+/// to the ones the watcher reports. A file that was just deleted can't be resolved, but the
+/// directory it was in still can.
+fn absolute(path: &Utf8Path) -> Option<Utf8PathBuf> {
+    if let Ok(path) = path.canonicalize_utf8() {
+        return Some(path);
+    }
+    let parent = path.parent()?;
+    let parent = if parent.as_str().is_empty() {
+        Utf8Path::new(".")
+    } else {
+        parent
+    };
+    Some(parent.canonicalize_utf8().ok()?.join(path.file_name()?))
+}
+
+/// Whether a build with this plan should make `output`. Without a plan, it makes everything.
+pub fn should_build(plan: Option<&Rebuild>, output: Output) -> bool {
+    plan.map_or(true, |plan| plan.includes(output))
+}
+
+/// Whether a build with this plan makes everything, and so starts from an empty `dist_dir`
+pub fn is_full_build(plan: Option<&Rebuild>) -> bool {
+    plan.map_or(true, Rebuild::is_everything)
+}
+
+/// What `oranda dev` carries from one build to the next: the plan for the build that's about to
+/// run, and what the builds before it fetched, for it to reuse
+#[derive(Debug)]
+pub struct Incremental {
+    plan: Rebuild,
+    kept: Mutex<Vec<Kept>>,
+}
+
+/// What a build of the site in `dist_dir` fetched
+#[derive(Debug)]
+struct Kept {
+    dist_dir: String,
+    context: Option<Context>,
+    funding: Option<Funding>,
+}
+
+impl Incremental {
+    /// The state before the first build, which makes everything
+    pub fn new() -> Self {
+        Self {
+            plan: Rebuild::everything(),
+            kept: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Sets what the next build makes
+    pub fn set_plan(&mut self, plan: Rebuild) {
+        self.plan = plan;
+    }
+
+    pub fn plan(&self) -> &Rebuild {
+        &self.plan
+    }
+
+    /// Keeps what the build of the site in `dist_dir` fetched, for a later build to reuse
+    pub fn keep(&self, dist_dir: &str, context: Option<&Context>, funding: Option<&Funding>) {
+        let mut kept = self.kept.lock().unwrap();
+        kept.retain(|kept| kept.dist_dir != dist_dir);
+        kept.push(Kept {
+            dist_dir: dist_dir.to_owned(),
+            context: context.cloned(),
+            funding: funding.cloned(),
+        });
+    }
+
+    /// What the last build of the site in `dist_dir` fetched, if this build doesn't have to
+    /// fetch it again
+    pub fn kept(&self, dist_dir: &str) -> Option<(Option<Context>, Option<Funding>)> {
+        if self.plan.includes(Output::Releases) {
+            return None;
+        }
+        self.kept
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|kept| kept.dist_dir == dist_dir)
+            .map(|kept| (kept.context.clone(), kept.funding.clone()))
+    }
+}
+
+impl Default for Incremental {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,50 @@
+use serde::Serialize;
+
+use crate::config::{Config, RoadmapConfig};
+use crate::data::roadmap::{self, Milestone};
+use crate::errors::*;
+use crate::site::markdown;
+
+#[derive(Serialize, Debug)]
+pub struct RoadmapContext {
+    pub milestones: Vec<MilestoneContext>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct MilestoneContext {
+    #[serde(flatten)]
+    pub milestone: Milestone,
+    /// The milestone's description, rendered to HTML like release notes since anyone with
+    /// triage access can write it
+    pub description_html: Option<String>,
+}
+
+impl RoadmapContext {
+    /// Fetches the repository's open milestones
+    pub fn new(roadmap_config: &RoadmapConfig, config: &Config) -> Result<Self> {
+        let milestones = roadmap::fetch(
+            &config.root_dir,
+            roadmap_config,
+            config.project.repository.as_deref(),
+        );
+        Self::from_milestones(milestones, config)
+    }
+
+    pub fn from_milestones(milestones: Vec<Milestone>, config: &Config) -> Result<Self> {
+        let milestones = milestones
+            .into_iter()
+            .map(|milestone| {
+                let description_html = milestone
+                    .description
+                    .as_deref()
+                    .map(|description| markdown::release_to_html(description, config))
+                    .transpose()?;
+                Ok(MilestoneContext {
+                    milestone,
+                    description_html,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { milestones })
+    }
+}
@@ -1,20 +1,51 @@
 use crate::config::Config;
 use crate::errors::Result;
-use crate::site::changelog::ChangelogContext;
-use crate::site::link::generate_absolute;
+use crate::site::blog::BlogPostContext;
+use crate::site::changelog::{ChangelogContext, ChangelogRelease};
+use crate::site::link::generate_absolute_or_relative;
 use rss::extension::atom;
 use rss::{CategoryBuilder, Channel, ChannelBuilder, GuidBuilder, Item, ItemBuilder};
 
 pub fn generate_rss_feed(context: &ChangelogContext, config: &Config) -> Result<Channel> {
+    changelog_channel(
+        context.releases.iter(),
+        format!("{} Changelog", &config.project.name),
+        format!("Changelog information for {}", &config.project.name),
+        "changelog.rss",
+        config,
+    )
+}
+
+/// Like [`generate_rss_feed`][], but only with prereleases
+pub fn generate_prerelease_rss_feed(
+    context: &ChangelogContext,
+    config: &Config,
+) -> Result<Channel> {
+    changelog_channel(
+        context.releases.iter().filter(|r| r.is_prerelease),
+        format!("{} Prereleases", &config.project.name),
+        format!("Prerelease information for {}", &config.project.name),
+        "changelog-prerelease.rss",
+        config,
+    )
+}
+
+fn changelog_channel<'a>(
+    releases: impl Iterator<Item = &'a ChangelogRelease>,
+    title: String,
+    description: String,
+    filename: &str,
+    config: &Config,
+) -> Result<Channel> {
     let category = CategoryBuilder::default()
         .name(format!("{} Changelog", &config.project.name))
         .domain(config.project.repository.clone())
         .build();
 
     let mut items: Vec<Item> = Vec::new();
-    for release in &context.releases {
+    for release in releases {
         let link =
-            generate_absolute(config, &format!("changelog/{}", release.version_tag)).unwrap();
+            generate_absolute_or_relative(config, &format!("changelog/{}", release.version_tag));
         let guid = GuidBuilder::default().permalink(true).value(&link).build();
         let item = ItemBuilder::default()
             .title(release.name.clone().unwrap_or(release.version_tag.clone()))
@@ -28,21 +59,74 @@ pub fn generate_rss_feed(context: &ChangelogContext, config: &Config) -> Result<
 
     let self_link = atom::Link {
         rel: "self".to_string(),
-        href: generate_absolute(config, "changelog.rss").unwrap(),
+        href: generate_absolute_or_relative(config, filename),
         ..Default::default()
     };
     let atom_link = atom::AtomExtensionBuilder::default()
         .links(vec![self_link])
         .build();
     let channel = ChannelBuilder::default()
-        .title(format!("{} Changelog", &config.project.name))
+        .title(title)
+        .description(description)
+        .categories(vec![category])
+        .items(items)
+        .link(generate_absolute_or_relative(config, "changelog"))
+        .atom_ext(atom_link)
+        .build();
+    Ok(channel)
+}
+
+pub fn generate_blog_rss_feed(posts: &[BlogPostContext], config: &Config) -> Result<Channel> {
+    let mut items: Vec<Item> = Vec::new();
+    for post in posts {
+        let link = generate_absolute_or_relative(config, &format!("blog/{}", post.slug));
+        let guid = GuidBuilder::default().permalink(true).value(&link).build();
+        let pub_date = chrono::NaiveDate::parse_from_str(&post.date, "%Y-%m-%d")
+            .ok()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|date| date.and_utc().to_rfc2822());
+        let categories = post
+            .tags
+            .iter()
+            .map(|tag| CategoryBuilder::default().name(tag.name.clone()).build())
+            .collect::<Vec<_>>();
+        let author = Some(
+            post.authors
+                .iter()
+                .map(|byline| byline.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+        )
+        .filter(|names| !names.is_empty());
+        let item = ItemBuilder::default()
+            .title(post.title.clone())
+            .description(post.description.clone())
+            .content(Some(post.body.clone()))
+            .author(author)
+            .categories(categories)
+            .pub_date(pub_date)
+            .link(link)
+            .guid(guid)
+            .build();
+        items.push(item);
+    }
+
+    let self_link = atom::Link {
+        rel: "self".to_string(),
+        href: generate_absolute_or_relative(config, "blog.rss"),
+        ..Default::default()
+    };
+    let atom_link = atom::AtomExtensionBuilder::default()
+        .links(vec![self_link])
+        .build();
+    let channel = ChannelBuilder::default()
+        .title(format!("{} Blog", &config.project.name))
         .description(format!(
-            "Changelog information for {}",
+            "News and announcements from {}",
             &config.project.name
         ))
-        .categories(vec![category])
         .items(items)
-        .link(generate_absolute(config, "changelog").unwrap())
+        .link(generate_absolute_or_relative(config, "blog"))
         .atom_ext(atom_link)
         .build();
     Ok(channel)
@@ -0,0 +1,109 @@
+//! API docs built with rustdoc, see [`RustdocConfig`][crate::config::RustdocConfig].
+//!
+//! The docs themselves are copied to `rustdoc/` as-is, and the "API" page (`rustdoc/` too, as
+//! rustdoc doesn't write an index there) shows the main crate's docs in a frame, so that they
+//! still have the site's header and nav. `api/` is left for the JSON from `build.emit_json`.
+
+use std::process::Command;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::Serialize;
+
+use crate::config::{AxoprojectLayer, Config, RustdocConfig};
+use crate::errors::*;
+use crate::paths::determine_path;
+use crate::site::link;
+use crate::site::oranda_theme::OrandaTheme;
+use crate::site::writer::SiteWriter;
+
+#[derive(Serialize, Debug)]
+pub struct RustdocContext {
+    crate_name: String,
+    /// The main crate's docs, which the page embeds
+    docs_link: String,
+}
+
+pub fn context(rustdoc_cfg: &RustdocConfig, config: &Config) -> RustdocContext {
+    let crate_dir = rustdoc_cfg.crate_dir_name(&config.project.name);
+    RustdocContext {
+        crate_name: rustdoc_cfg
+            .crate_name
+            .clone()
+            .unwrap_or_else(|| config.project.name.clone()),
+        docs_link: link::generate_relative(
+            &config.build.path_prefix,
+            &format!("rustdoc/{crate_dir}/index.html"),
+        ),
+    }
+}
+
+/// Get an absolute path to the crate we're documenting
+fn crate_dir(root_dir: &Utf8Path, rustdoc_cfg: &RustdocConfig) -> Result<Utf8PathBuf> {
+    match determine_path(root_dir, &None::<&Utf8Path>, &rustdoc_cfg.path)? {
+        // This comes back relative to the root (and empty for the root itself)
+        Some(path) => Ok(root_dir.join(path)),
+        None => Err(OrandaError::PathDoesNotExist {
+            path: rustdoc_cfg.path.clone(),
+        }),
+    }
+}
+
+/// Build the docs if we're asked to, and copy them to "rustdoc/" in the site
+pub fn build_rustdoc(
+    rustdoc_cfg: &RustdocConfig,
+    config: &Config,
+    writer: &mut dyn SiteWriter,
+) -> Result<()> {
+    let crate_dir = crate_dir(&config.root_dir, rustdoc_cfg)?;
+    if rustdoc_cfg.build {
+        tracing::info!("Building API docs with `cargo doc`...");
+        let mut command = Command::new("cargo");
+        command.args(["doc", "--no-deps"]).current_dir(&crate_dir);
+        if rustdoc_cfg.theme {
+            // Keep any flags the user has set up for themselves
+            let mut flags = std::env::var("RUSTDOCFLAGS").unwrap_or_default();
+            flags.push_str(&format!(
+                " --default-theme {}",
+                rustdoc_theme(&config.styles.theme)
+            ));
+            command.env("RUSTDOCFLAGS", flags.trim());
+        }
+        let output = command
+            .output()
+            .map_err(|e| OrandaError::RustdocBuildFailed {
+                details: e.to_string(),
+            })?;
+        if !output.status.success() {
+            // The end of the output is usually where the actual error is
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let lines = stderr.trim().lines().collect::<Vec<_>>();
+            return Err(OrandaError::RustdocBuildFailed {
+                details: lines[lines.len().saturating_sub(20)..].join("\n"),
+            });
+        }
+    }
+
+    let doc_dir = match &rustdoc_cfg.doc_dir {
+        Some(doc_dir) => crate_dir.join(doc_dir),
+        None => AxoprojectLayer::get_best_workspace(&crate_dir)
+            .map(|project| project.target_dir)
+            .unwrap_or_else(|| crate_dir.join("target"))
+            .join("doc"),
+    };
+    let crate_dir_name = rustdoc_cfg.crate_dir_name(&config.project.name);
+    if !doc_dir.join(&crate_dir_name).join("index.html").exists() {
+        return Err(OrandaError::RustdocCrateNotFound {
+            crate_name: crate_dir_name,
+            doc_dir: doc_dir.to_string(),
+        });
+    }
+    writer.write_dir(Utf8Path::new("rustdoc"), &doc_dir)
+}
+
+/// rustdoc only has a light and a dark theme (and ayu), so pick whichever is closest
+fn rustdoc_theme(theme: &OrandaTheme) -> &'static str {
+    match theme {
+        OrandaTheme::Dark | OrandaTheme::AxoDark | OrandaTheme::Hacker => "dark",
+        OrandaTheme::Light | OrandaTheme::AxoLight | OrandaTheme::Cupcake => "light",
+    }
+}
@@ -0,0 +1,55 @@
+use serde::Serialize;
+
+use crate::config::{AdvisorySource, Config, SecurityConfig};
+use crate::data::security::{self, Advisory};
+use crate::errors::*;
+use crate::site::markdown;
+
+#[derive(Serialize, Debug)]
+pub struct SecurityContext {
+    /// The security policy, rendered to HTML
+    pub policy: Option<String>,
+    /// The name of the database advisories were fetched from, if they were asked for
+    pub advisories_source: Option<&'static str>,
+    pub advisories: Vec<Advisory>,
+}
+
+impl SecurityContext {
+    /// Renders the security policy, and fetches the advisories if they were asked for
+    pub fn new(security_config: &SecurityConfig, config: &Config) -> Result<Self> {
+        let policy_path = config.path(&security_config.policy);
+        let policy = if policy_path.exists() {
+            let source = axoasset::SourceFile::load_local(&policy_path)?;
+            Some(markdown::to_html(source.contents(), config)?)
+        } else {
+            tracing::warn!(
+                "Couldn't find your security policy at {}, the security page won't have one",
+                security_config.policy
+            );
+            None
+        };
+        let advisories = security::fetch(
+            &config.root_dir,
+            security_config,
+            config.project.repository.as_deref(),
+            &config.project.name,
+        );
+        Ok(Self::from_parts(policy, security_config, advisories))
+    }
+
+    pub fn from_parts(
+        policy: Option<String>,
+        security_config: &SecurityConfig,
+        advisories: Vec<Advisory>,
+    ) -> Self {
+        let advisories_source = security_config.advisories.map(|source| match source {
+            AdvisorySource::GitHub => "GitHub Security Advisories",
+            AdvisorySource::RustSec => "RustSec",
+        });
+        Self {
+            policy,
+            advisories_source,
+            advisories,
+        }
+    }
+}
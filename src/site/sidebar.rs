@@ -0,0 +1,214 @@
+//! The sidebar for additional pages, defined by a markdown file like mdbook's `SUMMARY.md`.
+//!
+//! The file is a (nested) list of links to markdown files, relative to the sidebar file
+//! itself. Headings start a new section, and list items without a link become labels for the
+//! items nested under them:
+//!
+//! ```markdown
+//! - [Introduction](./docs/intro.md)
+//!
+//! # Guides
+//!
+//! - [Installing](./docs/install.md)
+//! - Configuration
+//!   - [Basics](./docs/config.md)
+//! ```
+
+use axoasset::LocalAsset;
+use camino::{Utf8Path, Utf8PathBuf};
+use comrak::nodes::{AstNode, NodeValue};
+use comrak::Arena;
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::errors::*;
+use crate::site::link;
+use crate::site::markdown::initialize_comrak_options;
+use crate::site::page::source::{is_markdown, normalize};
+use crate::site::page::PageFrontmatter;
+
+#[derive(Debug, Clone)]
+pub struct Sidebar {
+    entries: Vec<SidebarEntry>,
+}
+
+#[derive(Debug, Clone)]
+struct SidebarEntry {
+    title: String,
+    /// The markdown file this entry links to, relative to the current directory
+    source: Option<Utf8PathBuf>,
+    /// Where the entry links to, which is either one of our pages or an external URL
+    link: Option<String>,
+    is_section: bool,
+    children: Vec<SidebarEntry>,
+}
+
+/// A sidebar entry, as seen from one particular page
+#[derive(Serialize, Debug, Clone)]
+pub struct SidebarItem {
+    pub title: String,
+    pub link: Option<String>,
+    /// Whether this is the page we're on
+    pub active: bool,
+    /// Whether the page we're on is somewhere below this entry, so that it starts out expanded
+    pub expanded: bool,
+    /// Whether this is a heading rather than a list item
+    pub is_section: bool,
+    pub children: Vec<SidebarItem>,
+}
+
+impl Sidebar {
+    pub fn load(path: &str, config: &Config) -> Result<Self> {
+        let contents = LocalAsset::load_string(config.path(path))?;
+        let base_dir = Utf8Path::new(path).parent().unwrap_or(Utf8Path::new(""));
+        Ok(Self::parse(&contents, base_dir, config))
+    }
+
+    /// Parses a sidebar file, resolving links against `base_dir`
+    pub fn parse(markdown: &str, base_dir: &Utf8Path, config: &Config) -> Self {
+        let arena = Arena::new();
+        let options = initialize_comrak_options();
+        let root = comrak::parse_document(&arena, markdown, &options);
+
+        let mut entries: Vec<SidebarEntry> = Vec::new();
+        for node in root.children() {
+            let value = node.data.borrow().value.clone();
+            match value {
+                NodeValue::Heading(_) => entries.push(SidebarEntry {
+                    title: text_of(node),
+                    source: None,
+                    link: None,
+                    is_section: true,
+                    children: Vec::new(),
+                }),
+                NodeValue::List(_) => {
+                    let items = list_entries(node, base_dir, config);
+                    // Lists after a heading belong to that section
+                    match entries.last_mut() {
+                        Some(section) if section.is_section => section.children.extend(items),
+                        _ => entries.extend(items),
+                    }
+                }
+                _ => {}
+            }
+        }
+        Self { entries }
+    }
+
+    /// Every markdown file the sidebar links to, in order, along with its title
+    pub fn sources(&self) -> Vec<(String, Utf8PathBuf)> {
+        fn collect(entries: &[SidebarEntry], sources: &mut Vec<(String, Utf8PathBuf)>) {
+            for entry in entries {
+                if let Some(source) = &entry.source {
+                    sources.push((entry.title.clone(), source.clone()));
+                }
+                collect(&entry.children, sources);
+            }
+        }
+        let mut sources = Vec::new();
+        collect(&self.entries, &mut sources);
+        sources
+    }
+
+    /// The sidebar as it should be rendered on the page built from `current_source`
+    pub fn context(&self, current_source: &str) -> Vec<SidebarItem> {
+        let current = normalize(Utf8Path::new(current_source));
+        self.entries
+            .iter()
+            .map(|entry| entry.context(&current))
+            .collect()
+    }
+}
+
+impl SidebarEntry {
+    fn context(&self, current: &Utf8Path) -> SidebarItem {
+        let children = self
+            .children
+            .iter()
+            .map(|child| child.context(current))
+            .collect::<Vec<_>>();
+        let active = self.source.as_deref() == Some(current);
+        SidebarItem {
+            title: self.title.clone(),
+            link: self.link.clone(),
+            active,
+            expanded: active || children.iter().any(|child| child.expanded),
+            is_section: self.is_section,
+            children,
+        }
+    }
+}
+
+fn list_entries<'a>(
+    list: &'a AstNode<'a>,
+    base_dir: &Utf8Path,
+    config: &Config,
+) -> Vec<SidebarEntry> {
+    let mut entries = Vec::new();
+    for item in list.children() {
+        let mut entry = SidebarEntry {
+            title: String::new(),
+            source: None,
+            link: None,
+            is_section: false,
+            children: Vec::new(),
+        };
+        for child in item.children() {
+            let value = child.data.borrow().value.clone();
+            match value {
+                NodeValue::Paragraph if entry.title.is_empty() => {
+                    entry.title = text_of(child);
+                    if let Some(url) = first_link(child) {
+                        let (source, link) = resolve(&url, base_dir, config);
+                        entry.source = source;
+                        entry.link = Some(link);
+                    }
+                }
+                NodeValue::List(_) => {
+                    entry.children.extend(list_entries(child, base_dir, config));
+                }
+                _ => {}
+            }
+        }
+        if !entry.title.is_empty() {
+            entries.push(entry);
+        }
+    }
+    entries
+}
+
+/// Links to markdown files point at the page we build from them. Anything else is left as-is.
+fn resolve(url: &str, base_dir: &Utf8Path, config: &Config) -> (Option<Utf8PathBuf>, String) {
+    let is_external = url.contains("://") || url.starts_with('/') || url.starts_with('#');
+    if is_external || !is_markdown(url) {
+        return (None, url.to_owned());
+    }
+    let source = normalize(&base_dir.join(url));
+    let mut page_path = source.with_extension("");
+    if PageFrontmatter::load(config.path(&source)).private {
+        page_path = Utf8Path::new(&config.build.private_path).join(page_path);
+    }
+    let link = link::generate_relative(&config.build.path_prefix, &format!("{page_path}/"));
+    (Some(source), link)
+}
+
+fn first_link<'a>(node: &'a AstNode<'a>) -> Option<String> {
+    node.descendants()
+        .find_map(|child| match &child.data.borrow().value {
+            NodeValue::Link(link) => Some(link.url.clone()),
+            _ => None,
+        })
+}
+
+fn text_of<'a>(node: &'a AstNode<'a>) -> String {
+    let mut text = String::new();
+    for child in node.descendants() {
+        match &child.data.borrow().value {
+            NodeValue::Text(t) => text.push_str(t),
+            NodeValue::Code(code) => text.push_str(&code.literal),
+            NodeValue::SoftBreak | NodeValue::LineBreak => text.push(' '),
+            _ => {}
+        }
+    }
+    text.trim().to_owned()
+}
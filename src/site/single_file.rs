@@ -0,0 +1,241 @@
+//! Turns written pages into single self-contained HTML files, for `build.single_file`.
+//!
+//! This runs over the pages after everything else has been written to the dist dir, so it can
+//! read the stylesheets, scripts and images they link to back from where they ended up, rather
+//! than every part of the build having to know how to inline what it adds.
+
+use std::sync::OnceLock;
+
+use axoasset::LocalAsset;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use camino::{Utf8Path, Utf8PathBuf};
+use regex::{Captures, Regex};
+
+use crate::errors::*;
+
+/// Images bigger than this stay links, since base64 makes them a third bigger again, and
+/// nobody wants a 20MB changelog
+pub const MAX_INLINE_IMAGE_SIZE: u64 = 100 * 1024;
+
+/// Inlines everything the page at `page_path` uses from `dist_dir` into it
+pub fn inline_page(
+    page_path: &Utf8Path,
+    dist_dir: &Utf8Path,
+    path_prefix: &Option<String>,
+) -> Result<()> {
+    let html = LocalAsset::load_string(page_path)?;
+    let resolver = Resolver {
+        dist_dir,
+        page_dir: page_path.parent().unwrap_or(dist_dir),
+        path_prefix: path_prefix.as_deref(),
+    };
+    let html = inline_html(&html, &resolver)?;
+    LocalAsset::write_new(&html, page_path)?;
+    Ok(())
+}
+
+/// Works out which file in the dist dir a link in a page points to
+pub struct Resolver<'a> {
+    pub dist_dir: &'a Utf8Path,
+    /// The directory the page is in, for relative links
+    pub page_dir: &'a Utf8Path,
+    pub path_prefix: Option<&'a str>,
+}
+
+impl Resolver<'_> {
+    /// Returns nothing for links to other sites and for files that don't exist
+    pub fn resolve(&self, link: &str, relative_to: &Utf8Path) -> Option<Utf8PathBuf> {
+        if link.is_empty()
+            || link.starts_with('#')
+            || link.starts_with("//")
+            || link.starts_with("data:")
+            || link.contains("://")
+        {
+            return None;
+        }
+        let link = link.split(['?', '#']).next().unwrap_or(link);
+        let path = match link.strip_prefix('/') {
+            Some(absolute) => {
+                let absolute = match self.path_prefix {
+                    Some(prefix) => absolute
+                        .strip_prefix(prefix.trim_matches('/'))
+                        .map(|rest| rest.trim_start_matches('/'))
+                        .unwrap_or(absolute),
+                    None => absolute,
+                };
+                self.dist_dir.join(absolute)
+            }
+            None => {
+                // Resolve `..` ourselves, since the OS only does if every dir on the way exists
+                let mut path = relative_to.to_owned();
+                for part in link.split('/') {
+                    match part {
+                        "" | "." => {}
+                        ".." => {
+                            path.pop();
+                        }
+                        part => path.push(part),
+                    }
+                }
+                path
+            }
+        };
+        path.is_file().then_some(path)
+    }
+}
+
+fn tag_regex() -> &'static Regex {
+    static TAG: OnceLock<Regex> = OnceLock::new();
+    TAG.get_or_init(|| {
+        Regex::new(
+            r#"(?s)<link\b[^>]*>|<script\b[^>]*\bsrc="[^"]*"[^>]*>\s*</script>|<img\b[^>]*>"#,
+        )
+        .unwrap()
+    })
+}
+
+fn attribute_regex() -> &'static Regex {
+    static ATTRIBUTE: OnceLock<Regex> = OnceLock::new();
+    ATTRIBUTE.get_or_init(|| Regex::new(r#"([\w-]+)\s*=\s*"([^"]*)""#).unwrap())
+}
+
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    attribute_regex()
+        .captures_iter(tag)
+        .find(|captures| captures[1].eq_ignore_ascii_case(name))
+        .and_then(|captures| captures.get(2))
+        .map(|value| value.as_str())
+}
+
+/// Inlines the stylesheets, scripts, icons and small images an HTML page links to
+pub fn inline_html(html: &str, resolver: &Resolver) -> Result<String> {
+    let mut error = None;
+    let inlined = tag_regex().replace_all(html, |captures: &Captures| {
+        let tag = &captures[0];
+        match inline_tag(tag, resolver) {
+            Ok(Some(replacement)) => replacement,
+            Ok(None) => tag.to_owned(),
+            Err(e) => {
+                error.get_or_insert(e);
+                tag.to_owned()
+            }
+        }
+    });
+    match error {
+        Some(e) => Err(e),
+        None => Ok(inlined.into_owned()),
+    }
+}
+
+fn inline_tag(tag: &str, resolver: &Resolver) -> Result<Option<String>> {
+    if tag.starts_with("<script") {
+        let Some(path) =
+            attribute(tag, "src").and_then(|src| resolver.resolve(src, resolver.page_dir))
+        else {
+            return Ok(None);
+        };
+        let script = LocalAsset::load_string(path)?;
+        // `defer` and `async` don't mean anything to inline scripts, and nothing else would
+        // make sense without the `src`
+        let kind = attribute(tag, "type")
+            .map(|kind| format!(r#" type="{kind}""#))
+            .unwrap_or_default();
+        return Ok(Some(format!(
+            "<script{kind}>{}</script>",
+            script.replace("</script", r"<\/script")
+        )));
+    }
+
+    if tag.starts_with("<img") {
+        let Some(path) =
+            attribute(tag, "src").and_then(|src| resolver.resolve(src, resolver.page_dir))
+        else {
+            return Ok(None);
+        };
+        let Some(data_uri) = image_data_uri(&path)? else {
+            return Ok(None);
+        };
+        return Ok(Some(replace_attribute(tag, "src", &data_uri)));
+    }
+
+    let rel = attribute(tag, "rel")
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let Some(path) =
+        attribute(tag, "href").and_then(|href| resolver.resolve(href, resolver.page_dir))
+    else {
+        return Ok(None);
+    };
+    let rels = rel.split_whitespace().collect::<Vec<_>>();
+    if rels.contains(&"stylesheet") {
+        let css = inline_css(&LocalAsset::load_string(&path)?, &path, resolver)?;
+        let media = attribute(tag, "media")
+            .map(|media| format!(r#" media="{media}""#))
+            .unwrap_or_default();
+        Ok(Some(format!(
+            "<style{media}>{}</style>",
+            css.replace("</style", r"<\/style")
+        )))
+    } else if rels.contains(&"icon") {
+        let Some(data_uri) = image_data_uri(&path)? else {
+            return Ok(None);
+        };
+        Ok(Some(replace_attribute(tag, "href", &data_uri)))
+    } else if rels.contains(&"preload") {
+        // Whatever it was preloading is in the page now
+        Ok(Some(String::new()))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Inlines the small images a stylesheet refers to with `url(...)`
+fn inline_css(css: &str, css_path: &Utf8Path, resolver: &Resolver) -> Result<String> {
+    static URL: OnceLock<Regex> = OnceLock::new();
+    let url = URL.get_or_init(|| Regex::new(r#"url\(\s*['"]?([^'")]+?)['"]?\s*\)"#).unwrap());
+    let css_dir = css_path.parent().unwrap_or(resolver.dist_dir);
+    let mut error = None;
+    let inlined = url.replace_all(css, |captures: &Captures| {
+        let Some(path) = resolver.resolve(&captures[1], css_dir) else {
+            return captures[0].to_owned();
+        };
+        match image_data_uri(&path) {
+            Ok(Some(data_uri)) => format!(r#"url("{data_uri}")"#),
+            Ok(None) => captures[0].to_owned(),
+            Err(e) => {
+                error.get_or_insert(e);
+                captures[0].to_owned()
+            }
+        }
+    });
+    match error {
+        Some(e) => Err(e),
+        None => Ok(inlined.into_owned()),
+    }
+}
+
+/// The image as a `data:` URI, unless it's too big to be worth inlining
+fn image_data_uri(path: &Utf8Path) -> Result<Option<String>> {
+    if std::fs::metadata(path)?.len() > MAX_INLINE_IMAGE_SIZE {
+        return Ok(None);
+    }
+    let mime = mime_guess::from_path(path).first_or_octet_stream();
+    let bytes = LocalAsset::load_bytes(path)?;
+    Ok(Some(format!(
+        "data:{mime};base64,{}",
+        STANDARD.encode(bytes)
+    )))
+}
+
+fn replace_attribute(tag: &str, name: &str, value: &str) -> String {
+    attribute_regex()
+        .replace_all(tag, |captures: &Captures| {
+            if captures[1].eq_ignore_ascii_case(name) {
+                format!(r#"{}="{value}""#, &captures[1])
+            } else {
+                captures[0].to_owned()
+            }
+        })
+        .into_owned()
+}
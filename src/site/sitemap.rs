@@ -0,0 +1,47 @@
+use camino::Utf8Path;
+
+use crate::config::Config;
+use crate::site::link::page_url;
+use crate::site::page::Page;
+use crate::site::{private, widgets};
+
+/// Generates a `sitemap.xml` listing every HTML page we're about to write. Pages are only
+/// listed if we know the URL the site is served at, see [`page_url`][].
+pub fn generate_sitemap(pages: &[Page], config: &Config) -> String {
+    let mut locs = Vec::new();
+    for page in pages {
+        let path = Utf8Path::new(&page.filename);
+        // The offline fallback page is only ever shown by the service worker
+        if path.extension() != Some("html") || page.filename == "offline.html" {
+            continue;
+        }
+        // Widgets are only meant to be seen embedded somewhere else
+        if widgets::is_widget(&page.filename) || private::is_private_output(&page.filename, config)
+        {
+            continue;
+        }
+        locs.extend(page_url(config, &page.filename));
+    }
+
+    let mut xml = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+"#,
+    );
+    for loc in locs {
+        xml.push_str(&format!(
+            "  <url>\n    <loc>{}</loc>\n  </url>\n",
+            escape(&loc)
+        ));
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
@@ -0,0 +1,72 @@
+use serde::Serialize;
+
+use crate::config::{ArtifactsConfig, SupportMatrixConfig, SupportStatus};
+use crate::data::artifacts::target_label;
+use crate::data::Context;
+
+#[derive(Serialize, Debug)]
+pub struct SupportMatrixContext {
+    statuses: Vec<StatusColumn>,
+    rows: Vec<SupportMatrixRow>,
+}
+
+#[derive(Serialize, Debug)]
+struct StatusColumn {
+    status: SupportStatus,
+    label: &'static str,
+}
+
+#[derive(Serialize, Debug)]
+struct SupportMatrixRow {
+    target: String,
+    display_name: String,
+    status: SupportStatus,
+}
+
+/// Builds the rows of the support matrix from the user's configured targets, followed by any
+/// other targets the latest cargo-dist release was built for.
+pub fn context(
+    config: &SupportMatrixConfig,
+    artifacts_config: Option<&ArtifactsConfig>,
+    context: Option<&Context>,
+) -> SupportMatrixContext {
+    let mut targets: Vec<(String, SupportStatus)> = config
+        .targets
+        .iter()
+        .map(|(target, status)| (target.clone(), *status))
+        .collect();
+    if config.cargo_dist_targets {
+        let manifest = context
+            .and_then(|context| context.latest())
+            .and_then(|release| release.manifest.as_ref());
+        if let Some(manifest) = manifest {
+            for target in manifest
+                .artifacts
+                .values()
+                .flat_map(|artifact| &artifact.target_triples)
+            {
+                if !targets.iter().any(|(t, _)| t == target) {
+                    targets.push((target.clone(), config.default_status));
+                }
+            }
+        }
+    }
+
+    SupportMatrixContext {
+        statuses: SupportStatus::ALL
+            .iter()
+            .map(|status| StatusColumn {
+                status: *status,
+                label: status.label(),
+            })
+            .collect(),
+        rows: targets
+            .into_iter()
+            .map(|(target, status)| SupportMatrixRow {
+                display_name: target_label(&target, artifacts_config),
+                target,
+                status,
+            })
+            .collect(),
+    }
+}
@@ -0,0 +1,168 @@
+//! Tag pages for blog posts and additional pages.
+//!
+//! Anything with `tags` in its frontmatter gets listed on `tags/<tag>/`, and `tags/` shows a
+//! "tag cloud" of every tag, sized by how often it's used. Tags are matched by their slug, so
+//! `Release Notes` and `release-notes` end up on the same page.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::data::blog::slugify;
+use crate::errors::*;
+use crate::site::link;
+use crate::site::page::Page;
+use crate::site::templates::Templates;
+
+/// How many different sizes tags can have in the tag cloud
+const MAX_WEIGHT: usize = 5;
+
+/// A tag, and where its page lives
+#[derive(Serialize, Debug, Clone)]
+pub struct TagLink {
+    pub name: String,
+    pub link: String,
+}
+
+impl TagLink {
+    pub fn new(name: &str, path_prefix: &Option<String>) -> Self {
+        Self {
+            name: name.to_owned(),
+            link: link::generate_relative(path_prefix, &format!("tags/{}/", slugify(name))),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TaggedItemKind {
+    Post,
+    Page,
+}
+
+/// A blog post or page that's listed on a tag's page
+#[derive(Serialize, Debug, Clone)]
+pub struct TaggedItem {
+    pub kind: TaggedItemKind,
+    pub title: String,
+    pub link: String,
+    /// `YYYY-MM-DD`, only set for blog posts
+    pub date: Option<String>,
+    pub formatted_date: Option<String>,
+    pub description: Option<String>,
+}
+
+/// A single entry in the tag cloud
+#[derive(Serialize, Debug, Clone)]
+pub struct TagCloudEntry {
+    pub name: String,
+    pub link: String,
+    pub count: usize,
+    /// From 1 to 5, depending on how `count` compares to the other tags
+    pub weight: usize,
+}
+
+#[derive(Debug)]
+struct Tag {
+    /// The first spelling of the tag we saw
+    name: String,
+    items: Vec<TaggedItem>,
+}
+
+/// Every tag on the site, keyed by slug
+#[derive(Debug, Default)]
+pub struct Taxonomy {
+    tags: BTreeMap<String, Tag>,
+}
+
+impl Taxonomy {
+    /// Lists `item` under each of `tags`
+    pub fn add(&mut self, tags: &[String], item: &TaggedItem) {
+        for name in tags {
+            let slug = slugify(name);
+            if slug.is_empty() {
+                continue;
+            }
+            let tag = self.tags.entry(slug).or_insert_with(|| Tag {
+                name: name.clone(),
+                items: Vec::new(),
+            });
+            tag.items.push(item.clone());
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// Every tag, alphabetically, with a weight scaled between the least and most used tags
+    pub fn cloud(&self, path_prefix: &Option<String>) -> Vec<TagCloudEntry> {
+        let counts = self.tags.values().map(|tag| tag.items.len());
+        let min = counts.clone().min().unwrap_or(0);
+        let max = counts.max().unwrap_or(0);
+        self.tags
+            .values()
+            .map(|tag| {
+                let count = tag.items.len();
+                let weight = if max == min {
+                    1
+                } else {
+                    1 + (count - min) * (MAX_WEIGHT - 1) / (max - min)
+                };
+                let TagLink { name, link } = TagLink::new(&tag.name, path_prefix);
+                TagCloudEntry {
+                    name,
+                    link,
+                    count,
+                    weight,
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct TagsIndexContext {
+    tag_cloud: Vec<TagCloudEntry>,
+}
+
+#[derive(Serialize, Debug)]
+struct TagContext {
+    name: String,
+    /// Blog posts first, newest first, followed by pages
+    items: Vec<TaggedItem>,
+    tags_link: String,
+}
+
+/// Renders `tags/`, and a `tags/<slug>/` page for every tag
+pub fn build_pages(
+    taxonomy: &Taxonomy,
+    templates: &Templates,
+    path_prefix: &Option<String>,
+) -> Result<Vec<Page>> {
+    let mut pages = vec![Page::new_from_template(
+        "tags.html",
+        templates,
+        "tags_index.html",
+        &TagsIndexContext {
+            tag_cloud: taxonomy.cloud(path_prefix),
+        },
+    )?];
+    for (slug, tag) in &taxonomy.tags {
+        let mut items = tag.items.clone();
+        // `None` sorts before any date, so this puts pages after posts
+        items.sort_by(|a, b| b.date.cmp(&a.date));
+        let context = TagContext {
+            name: tag.name.clone(),
+            items,
+            tags_link: link::generate_relative(path_prefix, "tags/"),
+        };
+        pages.push(Page::new_from_template(
+            &format!("tags/{slug}.html"),
+            templates,
+            "tag.html",
+            &context,
+        )?);
+    }
+    Ok(pages)
+}
@@ -6,24 +6,44 @@
 //! can also use features such as imports, inheritance, extends, and so on.
 
 use crate::config::Config;
-use crate::data::Context;
-use crate::errors::Result;
+use crate::data::{fetch, Context};
+use crate::errors::{OrandaError, Result};
+use crate::site::config_context::ConfigContext;
 use crate::site::layout::LayoutContext;
+use crate::site::markdown::shortcodes::SHORTCODES_DIR;
 use crate::site::markdown::SyntaxTheme;
 use crate::site::{link, markdown};
+use axoasset::LocalAsset;
+use camino::{Utf8Path, Utf8PathBuf};
 use include_dir::{include_dir, Dir};
 use minijinja::value::Value;
-use minijinja::{context, AutoEscape, Environment, Template};
+use minijinja::{context, AutoEscape, Environment, Error, ErrorKind, Template};
 use std::collections::HashMap;
+use std::sync::RwLock;
 
 const TEMPLATE_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/templates/site");
 
+/// The files the user's templates were loaded from, by template name, so that errors can point
+/// at them
+static OVERRIDE_PATHS: RwLock<Vec<(String, Utf8PathBuf)>> = RwLock::new(Vec::new());
+
+/// The file the template called `name` was loaded from, if it's one of the user's
+pub fn override_path(name: &str) -> Option<Utf8PathBuf> {
+    let paths = OVERRIDE_PATHS.read().unwrap();
+    paths
+        .iter()
+        .find(|(template, _)| template == name)
+        .map(|(_, path)| path.clone())
+}
+
 /// Main templates struct that gets passed around in the application.
 pub struct Templates<'a> {
     /// Minijinja environment that contains all loaded templates
     pub env: Environment<'a>,
     /// Layout context used for every render
     pub layout: LayoutContext,
+    /// The resolved config, also available to every render
+    pub config: ConfigContext,
 }
 
 impl<'a> Templates<'a> {
@@ -40,12 +60,25 @@ impl<'a> Templates<'a> {
             env.add_template_owned(path, contents)
                 .expect("failed to add jinja2 template");
         }
+        if let Some(template_dir) = &config.build.template_dir {
+            Self::load_overrides(&mut env, &config.path(template_dir))?;
+        }
         env.add_filter("generate_link", Self::generate_link);
         env.add_filter("syntax_highlight", Self::syntax_highlight);
+        let allowlist = config.build.fetch_json_allowlist.clone();
+        env.add_function("fetch_json", move |url: String| {
+            fetch::fetch_json(&url, &allowlist)
+                .map(|json| Value::from_serializable(&json))
+                .map_err(|e| Error::new(ErrorKind::InvalidOperation, e.to_string()))
+        });
         // Use opt-in autoescape
         env.set_auto_escape_callback(|_| AutoEscape::None);
         let layout = LayoutContext::new(config, context)?;
-        Ok(Self { env, layout })
+        Ok(Self {
+            env,
+            layout,
+            config: ConfigContext::new(config),
+        })
     }
 
     pub fn new_for_workspace_index(workspace_config: &Config) -> Result<Self> {
@@ -59,9 +92,42 @@ impl<'a> Templates<'a> {
             env.add_template_owned(path, contents)
                 .expect("failed to add jinja2 template");
         }
+        // Overrides for the index live in `workspace_index/`, just like ours
+        if let Some(template_dir) = &workspace_config.build.template_dir {
+            Self::load_overrides(&mut env, &workspace_config.path(template_dir))?;
+        }
         env.add_filter("generate_link", Self::generate_link);
         let layout = LayoutContext::new_for_workspace_index(workspace_config)?;
-        Ok(Self { env, layout })
+        Ok(Self {
+            env,
+            layout,
+            config: ConfigContext::new_for_workspace_index(workspace_config),
+        })
+    }
+
+    /// An environment with just the shortcode partials, ours and the user's
+    pub fn new_for_shortcodes(config: &Config) -> Result<Environment<'static>> {
+        let mut env = Environment::new();
+        let mut files = HashMap::new();
+        if let Some(dir) = TEMPLATE_DIR.get_dir(SHORTCODES_DIR) {
+            Self::load_files(dir, &mut files).expect("failed to load jinja2 templates from binary");
+        }
+        for (path, contents) in files {
+            env.add_template_owned(path, contents)
+                .expect("failed to add jinja2 template");
+        }
+        if let Some(template_dir) = &config.build.template_dir {
+            let template_dir = config.path(template_dir);
+            let dir = template_dir.join(SHORTCODES_DIR);
+            if dir.is_dir() {
+                let mut files = HashMap::new();
+                Self::load_override_files(&template_dir, &dir, &mut files)?;
+                Self::add_overrides(&mut env, files)?;
+            }
+        }
+        env.add_filter("generate_link", Self::generate_link);
+        env.set_auto_escape_callback(|_| AutoEscape::None);
+        Ok(env)
     }
 
     pub fn get(&self, name: &str) -> Result<Template> {
@@ -69,7 +135,8 @@ impl<'a> Templates<'a> {
     }
 
     pub fn render_to_string(&self, name: &str, context: Value) -> Result<String> {
-        let context_with_layout = context!(layout => self.layout, page => context);
+        let context_with_layout =
+            context!(layout => self.layout, config => self.config, page => context);
         let template = self.env.get_template(name)?;
         Ok(template.render(context_with_layout)?)
     }
@@ -95,6 +162,65 @@ impl<'a> Templates<'a> {
         Ok(())
     }
 
+    /// Adds the user's templates from `dir`, replacing built-in templates with the same name
+    fn load_overrides(env: &mut Environment<'a>, dir: &Utf8Path) -> Result<()> {
+        if !dir.exists() {
+            return Err(OrandaError::PathDoesNotExist {
+                path: dir.to_string(),
+            });
+        }
+        let mut files = HashMap::new();
+        Self::load_override_files(dir, dir, &mut files)?;
+        Self::add_overrides(env, files)
+    }
+
+    fn add_overrides(
+        env: &mut Environment<'_>,
+        files: HashMap<String, (Utf8PathBuf, String)>,
+    ) -> Result<()> {
+        for (name, (path, contents)) in files {
+            {
+                let mut paths = OVERRIDE_PATHS.write().unwrap();
+                paths.retain(|(template, _)| *template != name);
+                paths.push((name.clone(), path.clone()));
+            }
+            env.add_template_owned(name, contents).map_err(|e| {
+                OrandaError::TemplateLoadFailed {
+                    path: path.to_string(),
+                    line: e.line(),
+                    details: e.to_string(),
+                }
+            })?;
+        }
+        Ok(())
+    }
+
+    fn load_override_files(
+        root: &Utf8Path,
+        dir: &Utf8Path,
+        files: &mut HashMap<String, (Utf8PathBuf, String)>,
+    ) -> Result<()> {
+        for entry in dir.read_dir_utf8()? {
+            let path = entry?.path().to_owned();
+            if path.is_dir() {
+                Self::load_override_files(root, &path, files)?;
+            } else if path.extension() == Some("j2") {
+                // Named like the built-in templates: relative to the dir, without the .j2
+                let name = path
+                    .strip_prefix(root)
+                    .unwrap_or(&path)
+                    .with_extension("")
+                    .components()
+                    .map(|c| c.as_str())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                let contents = LocalAsset::load_string(&path)?;
+                files.insert(name, (path, contents));
+            }
+        }
+        Ok(())
+    }
+
     fn generate_link(base: String, path_prefix: String) -> String {
         // Weird Jinja serialization handling
         let path_prefix = if path_prefix == "none" {
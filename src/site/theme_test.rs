@@ -0,0 +1,315 @@
+//! Renders oranda's templates for a few made-up sites, for `oranda theme test`.
+//!
+//! Theme authors keep the results as snapshots next to their templates, so that when a new
+//! version of oranda changes what the templates are given (or the built-in templates their
+//! overrides include), they can see exactly which pages came out differently.
+
+use axoproject::GithubRepo;
+use camino::{Utf8Path, Utf8PathBuf};
+use minijinja::context;
+
+use crate::config::{ArtifactsConfig, ChangelogConfig, Config};
+use crate::data::artifacts::ReleaseArtifacts;
+use crate::data::github::{GithubRelease, GithubReleaseAsset};
+use crate::data::org::{OrgProject, OrgRelease, OrgRepo};
+use crate::data::{Context, Release, ReleaseSource};
+use crate::errors::*;
+use crate::site::markdown::to_html;
+use crate::site::org_index::OrgIndexContext;
+use crate::site::page::Page;
+use crate::site::templates::Templates;
+use crate::site::workspace_index::{WorkspaceIndexContext, WorkspaceIndexMember};
+use crate::site::{artifacts, changelog};
+
+/// A rendered page, named like `release-rich/artifacts.html`
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub name: String,
+    pub contents: String,
+}
+
+const README: &str = "# axolotlsay\n\n> 💬 a CLI for learning to distribute CLIs in rust\n\n## Usage\n\n```sh\n$ axolotlsay \"hello world\"\n```\n";
+
+/// Renders every fixture with the templates in `template_dir` taking precedence over ours
+pub fn render_fixtures(template_dir: &Utf8Path) -> Result<Vec<Snapshot>> {
+    let mut snapshots = minimal(template_dir)?;
+    snapshots.extend(release_rich(template_dir)?);
+    snapshots.extend(workspace(template_dir)?);
+    snapshots.extend(org(template_dir)?);
+    Ok(snapshots)
+}
+
+fn base_config(template_dir: &Utf8Path) -> Config {
+    let mut config = Config::default();
+    "axolotlsay".clone_into(&mut config.project.name);
+    config.project.description = Some("a CLI for learning to distribute CLIs in rust".to_owned());
+    config.build.template_dir = Some(template_dir.to_string());
+    config
+}
+
+/// A site with nothing but a README and a page
+fn minimal(template_dir: &Utf8Path) -> Result<Vec<Snapshot>> {
+    let config = base_config(template_dir);
+    let templates = Templates::new(&config, None)?;
+    let readme = to_html(README, &config)?;
+    let page = to_html(
+        "# Guide\n\nSome *docs*, with a [link](https://example.com).\n",
+        &config,
+    )?;
+    Ok(vec![
+        render(
+            &templates,
+            "minimal/index.html",
+            "index.html",
+            context!(),
+            Some(readme),
+        )?,
+        Snapshot {
+            name: "minimal/markdown_page.html".to_owned(),
+            contents: templates.render_to_string(
+                "markdown_page.html",
+                context!(body => page, title => "Guide"),
+            )?,
+        },
+    ])
+}
+
+/// A site for a project with a couple of releases full of files
+fn release_rich(template_dir: &Utf8Path) -> Result<Vec<Snapshot>> {
+    let mut config = base_config(template_dir);
+    config.project.repository = Some("https://github.com/axodotdev/axolotlsay".to_owned());
+    let mut artifacts_config = ArtifactsConfig {
+        auto: true,
+        ..ArtifactsConfig::default()
+    };
+    artifacts_config
+        .package_managers
+        .preferred
+        .insert("cargo".to_owned(), "cargo install axolotlsay".to_owned());
+    config.components.artifacts = Some(artifacts_config.clone());
+    config.components.changelog = Some(ChangelogConfig {
+        read_changelog_file: false,
+        ..ChangelogConfig::default()
+    });
+
+    let releases = vec![
+        fixture_release("v0.2.0", "2023-10-10T12:00:00Z", &artifacts_config),
+        fixture_release("v0.1.0", "2023-09-01T12:00:00Z", &artifacts_config),
+    ];
+    let context = Context {
+        repo: Some(GithubRepo::from_url(
+            "https://github.com/axodotdev/axolotlsay",
+        )?),
+        releases,
+        has_prereleases: false,
+        latest_release: Some(0),
+        has_artifacts: true,
+        channels: vec![],
+    };
+    let templates = Templates::new(&config, Some(&context))?;
+    let readme = to_html(README, &config)?;
+    let artifacts_context = artifacts::template_context(&context, &config)?;
+    let changelog_context = changelog::index_context(&context, &config, None)?;
+    let single = changelog::single_context(
+        &context.releases[0],
+        changelog::previous_release(&context.releases, 0),
+        &config,
+        None,
+    );
+    Ok(vec![
+        render(
+            &templates,
+            "release-rich/index.html",
+            "index.html",
+            context!(artifacts => artifacts_context),
+            Some(readme),
+        )?,
+        snapshot(
+            &templates,
+            "release-rich/artifacts.html",
+            "artifacts.html",
+            &artifacts_context,
+        )?,
+        snapshot(
+            &templates,
+            "release-rich/changelog_index.html",
+            "changelog_index.html",
+            &changelog_context,
+        )?,
+        snapshot(
+            &templates,
+            "release-rich/changelog_single.html",
+            "changelog_single.html",
+            &context!(release => single, os_script => ""),
+        )?,
+    ])
+}
+
+/// The index of a workspace with two members
+fn workspace(template_dir: &Utf8Path) -> Result<Vec<Snapshot>> {
+    let mut config = base_config(template_dir);
+    "axodotdev".clone_into(&mut config.project.name);
+    let templates = Templates::new_for_workspace_index(&config)?;
+    let member = |name: &str, description: &str| WorkspaceIndexMember {
+        name: name.to_owned(),
+        slug: name.to_owned(),
+        description: Some(description.to_owned()),
+        repository: Some(format!("https://github.com/axodotdev/{name}")),
+        logo: None,
+    };
+    let context = WorkspaceIndexContext {
+        members: vec![member("oranda", "generate beautiful landing pages")],
+        docs_content: Some(to_html("Tools for shipping software.", &config)?),
+        preferred_members: vec![member(
+            "axolotlsay",
+            "a CLI for learning to distribute CLIs",
+        )],
+        dependency_graph: None,
+    };
+    Ok(vec![snapshot(
+        &templates,
+        "workspace/index.html",
+        "workspace_index/index.html",
+        &context,
+    )?])
+}
+
+/// The portal of an organization with a released project and one that never made a release
+fn org(template_dir: &Utf8Path) -> Result<Vec<Snapshot>> {
+    let mut config = base_config(template_dir);
+    "axodotdev".clone_into(&mut config.project.name);
+    let templates = Templates::new_for_workspace_index(&config)?;
+    let project = |name: &str, stars: u64, latest_release: Option<OrgRelease>| OrgProject {
+        repo: OrgRepo {
+            name: name.to_owned(),
+            description: Some(format!("the {name} project")),
+            html_url: format!("https://github.com/axodotdev/{name}"),
+            homepage: None,
+            language: Some("Rust".to_owned()),
+            topics: vec![],
+            stargazers_count: stars,
+            fork: false,
+            archived: false,
+        },
+        readme_excerpt: Some(format!("{name} does one thing well.")),
+        latest_release,
+    };
+    let context = OrgIndexContext::new(
+        "axodotdev",
+        &[
+            project("axolotlsay", 12, None),
+            project(
+                "oranda",
+                400,
+                Some(OrgRelease {
+                    tag_name: "v0.6.0".to_owned(),
+                    html_url: "https://github.com/axodotdev/oranda/releases/tag/v0.6.0".to_owned(),
+                    published_at: Some("2023-12-04T18:01:36Z".to_owned()),
+                }),
+            ),
+        ],
+    );
+    Ok(vec![snapshot(
+        &templates,
+        "org/index.html",
+        "workspace_index/org.html",
+        &context,
+    )?])
+}
+
+fn snapshot<T: serde::Serialize>(
+    templates: &Templates,
+    name: &str,
+    template_name: &str,
+    context: &T,
+) -> Result<Snapshot> {
+    let page = Page::new_from_template(name, templates, template_name, context)?;
+    Ok(Snapshot {
+        name: name.to_owned(),
+        contents: page.contents,
+    })
+}
+
+/// Renders a template that also takes the README, like the index page
+fn render(
+    templates: &Templates,
+    name: &str,
+    template_name: &str,
+    page: minijinja::Value,
+    markdown_content: Option<String>,
+) -> Result<Snapshot> {
+    let contents = templates.get(template_name)?.render(context!(
+        layout => templates.layout,
+        config => templates.config,
+        page => page,
+        markdown_content => markdown_content,
+    ))?;
+    Ok(Snapshot {
+        name: name.to_owned(),
+        contents,
+    })
+}
+
+fn fixture_release(tag: &str, date: &str, artifacts_config: &ArtifactsConfig) -> Release {
+    let assets = [
+        "axolotlsay-installer.sh",
+        "axolotlsay-installer.ps1",
+        "axolotlsay-x86_64-unknown-linux-gnu.tar.gz",
+        "axolotlsay-aarch64-apple-darwin.tar.gz",
+        "axolotlsay-x86_64-apple-darwin.tar.gz",
+        "axolotlsay-x86_64-pc-windows-msvc.zip",
+        "axolotlsay-x86_64-pc-windows-msvc.msi",
+    ];
+    let url = format!("https://github.com/axodotdev/axolotlsay/releases/tag/{tag}");
+    let gh_release = GithubRelease {
+        url: url.clone(),
+        assets_url: String::new(),
+        html_url: url,
+        id: 0,
+        tag_name: tag.to_owned(),
+        target_commitish: "main".to_owned(),
+        name: Some(tag.to_owned()),
+        draft: false,
+        prerelease: false,
+        created_at: date.to_owned(),
+        published_at: date.to_owned(),
+        assets: assets
+            .iter()
+            .map(|name| GithubReleaseAsset {
+                url: String::new(),
+                id: 0,
+                node_id: String::new(),
+                name: (*name).to_owned(),
+                label: None,
+                content_type: "application/octet-stream".to_owned(),
+                state: "uploaded".to_owned(),
+                size: 0,
+                download_count: 100,
+                created_at: date.to_owned(),
+                updated_at: date.to_owned(),
+                browser_download_url: format!(
+                    "https://github.com/axodotdev/axolotlsay/releases/download/{tag}/{name}"
+                ),
+            })
+            .collect(),
+        tarball_url: String::new(),
+        zipball_url: String::new(),
+        body: Some(format!("## {tag}\n\n- Said hello to more axolotls\n")),
+        author: None,
+    };
+    let mut artifacts = ReleaseArtifacts::new(None);
+    artifacts.add_github(&gh_release);
+    artifacts.add_package_managers(artifacts_config);
+    artifacts.add_inference(artifacts_config);
+    artifacts.select_installers(artifacts_config);
+    Release {
+        manifest: None,
+        source: ReleaseSource::Github(gh_release),
+        artifacts,
+    }
+}
+
+/// Where the snapshot for `snapshot` is kept in `snapshot_dir`
+pub fn snapshot_path(snapshot_dir: &Utf8Path, snapshot: &Snapshot) -> Utf8PathBuf {
+    snapshot_dir.join(&snapshot.name)
+}
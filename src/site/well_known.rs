@@ -0,0 +1,185 @@
+//! `humans.txt` and files in `/.well-known/`, see `marketing.well_known`.
+//!
+//! These are all written from things we already know about the project, so that keeping them
+//! up to date is one less chore.
+
+use axoasset::LocalAsset;
+use serde_json::json;
+
+use crate::config::{Config, FundingManifestConfig};
+use crate::errors::*;
+use crate::site::funding::FundingContext;
+use crate::site::page::Page;
+
+pub const HUMANS_TXT: &str = "humans.txt";
+pub const FUNDING_MANIFEST: &str = "funding.json";
+/// Lists the funding manifests that belong to this domain, see
+/// <https://fundingjson.org/>
+pub const FUNDING_MANIFEST_URLS: &str = ".well-known/funding-manifest-urls";
+const WELL_KNOWN_DIR: &str = ".well-known";
+
+/// The files `marketing.well_known` asks for
+pub fn build_pages(config: &Config, funding: Option<&FundingContext>) -> Result<Vec<Page>> {
+    let well_known = &config.marketing.well_known;
+    let mut pages = vec![];
+    if well_known.humans_txt {
+        pages.push(text_page(HUMANS_TXT, humans_txt(config)));
+    }
+    if let Some(manifest_cfg) = &well_known.funding_manifest {
+        match funding {
+            Some(funding) => {
+                let manifest = funding_manifest(config, manifest_cfg, funding)?;
+                pages.push(text_page(FUNDING_MANIFEST, manifest));
+                match &config.project.homepage {
+                    Some(homepage) => {
+                        let url = format!("{}/{FUNDING_MANIFEST}\n", homepage.trim_end_matches('/'));
+                        pages.push(text_page(FUNDING_MANIFEST_URLS, url));
+                    }
+                    None => tracing::warn!(
+                        "Your funding manifest can't be listed in {} without a homepage.",
+                        FUNDING_MANIFEST_URLS
+                    ),
+                }
+            }
+            None => tracing::warn!(
+                "marketing.well_known.funding_manifest is set, but there's no funding page to build it from, so it won't be written."
+            ),
+        }
+    }
+    for (name, path) in &well_known.files {
+        let contents = LocalAsset::load_string(config.path(path))?;
+        pages.push(text_page(&format!("{WELL_KNOWN_DIR}/{name}"), contents));
+    }
+    Ok(pages)
+}
+
+fn text_page(filename: &str, contents: String) -> Page {
+    Page {
+        contents,
+        filename: filename.to_owned(),
+        reading_time: None,
+        stale: None,
+    }
+}
+
+/// Credits the people in `components.authors`, and describes the project, following
+/// <https://humanstxt.org/>
+pub fn humans_txt(config: &Config) -> String {
+    let mut txt = String::new();
+    let people = config
+        .components
+        .authors
+        .iter()
+        .flat_map(|authors| authors.people.values())
+        .collect::<Vec<_>>();
+    if !people.is_empty() {
+        txt.push_str("/* TEAM */\n");
+        for person in people {
+            txt.push_str(&format!("Name: {}\n", person.name));
+            if let Some(github) = &person.github {
+                txt.push_str(&format!("GitHub: {github}\n"));
+            }
+            for (label, url) in &person.links {
+                txt.push_str(&format!("{label}: {url}\n"));
+            }
+            txt.push('\n');
+        }
+    }
+    let project = &config.project;
+    txt.push_str("/* SITE */\n");
+    txt.push_str(&format!("Project: {}\n", project.name));
+    let fields = [
+        ("Description", &project.description),
+        ("Homepage", &project.homepage),
+        ("Repository", &project.repository),
+        ("License", &project.license),
+    ];
+    for (label, value) in fields {
+        if let Some(value) = value {
+            txt.push_str(&format!("{label}: {value}\n"));
+        }
+    }
+    txt.push_str("Software: oranda\n");
+    txt
+}
+
+/// A FLOSS/fund funding manifest, with a channel for each way to fund the project and a
+/// single plan that takes any amount through any of them
+pub fn funding_manifest(
+    config: &Config,
+    manifest_cfg: &FundingManifestConfig,
+    funding: &FundingContext,
+) -> Result<String> {
+    let project = &config.project;
+    let mut guids: Vec<String> = vec![];
+    let channels = funding
+        .methods()
+        .map(|method| {
+            let mut guid = slug(method.title());
+            if guids.contains(&guid) {
+                guid = format!("{guid}-{}", guids.len() + 1);
+            }
+            guids.push(guid.clone());
+            json!({
+                "guid": guid,
+                "type": "payment-provider",
+                "address": method.link(),
+                "description": method.title(),
+            })
+        })
+        .collect::<Vec<_>>();
+    let webpage = project.homepage.as_ref().or(project.repository.as_ref());
+    let description = manifest_cfg
+        .description
+        .clone()
+        .or_else(|| project.description.clone())
+        .unwrap_or_default();
+    let manifest = json!({
+        "version": "v1.0.0",
+        "entity": {
+            "type": manifest_cfg.entity_type.as_deref().unwrap_or("individual"),
+            "role": "owner",
+            "name": manifest_cfg.name.as_ref().unwrap_or(&project.name),
+            "email": manifest_cfg.email,
+            "description": description,
+            "webpageUrl": { "url": webpage },
+        },
+        "projects": [{
+            "guid": slug(&project.name),
+            "name": project.name,
+            "description": project.description.clone().unwrap_or_default(),
+            "webpageUrl": { "url": webpage },
+            "repositoryUrl": { "url": project.repository },
+            "licenses": project.license.iter().map(|license| format!("spdx:{license}")).collect::<Vec<_>>(),
+            "tags": [],
+        }],
+        "funding": {
+            "channels": channels,
+            "plans": [{
+                "guid": "support",
+                "status": "active",
+                "name": format!("Support {}", project.name),
+                "description": "Any amount helps.",
+                "amount": 0,
+                "currency": "USD",
+                "frequency": "monthly",
+                "channels": guids,
+            }],
+            "history": [],
+        },
+    });
+    Ok(serde_json::to_string_pretty(&manifest)?)
+}
+
+/// Lowercase letters, digits and dashes, which is what the manifest's ids may contain
+fn slug(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_owned()
+}
@@ -0,0 +1,55 @@
+//! Small pages for other sites and apps to embed, like the "what's new" widget.
+//!
+//! Unlike the rest of the site, these are written at exactly their filename (so
+//! "widgets/whats-new.html" and not "widgets/whats-new/index.html"), since whoever embeds them
+//! points straight at that file.
+
+use axoproject::WorkspaceInfo;
+use camino::Utf8Path;
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::data::Context;
+use crate::site::changelog::{self, ChangelogRelease};
+use crate::site::link;
+
+/// Where widgets live on the site
+pub const WIDGETS_DIR: &str = "widgets";
+/// The filename of the "what's new" widget
+pub const WHATS_NEW: &str = "widgets/whats-new.html";
+
+/// Whether the page with this filename is a widget
+pub fn is_widget(filename: &str) -> bool {
+    Utf8Path::new(filename).starts_with(WIDGETS_DIR)
+}
+
+#[derive(Serialize, Debug)]
+pub struct WhatsNewContext {
+    /// The latest stable release, or the latest prerelease if there's nothing stable yet
+    pub release: Option<ChangelogRelease>,
+    pub changelog_link: String,
+}
+
+pub fn whats_new_context(
+    context: &Context,
+    config: &Config,
+    project: Option<&WorkspaceInfo>,
+) -> WhatsNewContext {
+    let releases = context
+        .releases
+        .iter()
+        .enumerate()
+        .filter(|(_, release)| !release.source.is_current_state());
+    let latest = releases
+        .clone()
+        .find(|(_, release)| !release.source.is_prerelease())
+        .or_else(|| releases.clone().next());
+    let release = latest.map(|(idx, release)| {
+        let previous = changelog::previous_release(&context.releases, idx);
+        changelog::single_context(release, previous, config, project)
+    });
+    WhatsNewContext {
+        release,
+        changelog_link: link::generate_relative(&config.build.path_prefix, "changelog/"),
+    }
+}
@@ -1,7 +1,9 @@
 use crate::config::Config;
+use crate::data::dependency_graph::DependencyGraph;
 use crate::data::workspaces::WorkspaceData;
 use crate::errors::{OrandaError, Result};
 use crate::paths::determine_path;
+use crate::site::dependency_graph::DependencyGraphContext;
 use crate::site::markdown::to_html;
 use axoasset::LocalAsset;
 use camino::Utf8PathBuf;
@@ -12,6 +14,7 @@ pub struct WorkspaceIndexContext {
     pub members: Vec<WorkspaceIndexMember>,
     pub docs_content: Option<String>,
     pub preferred_members: Vec<WorkspaceIndexMember>,
+    pub dependency_graph: Option<DependencyGraphContext>,
 }
 
 #[derive(Serialize, Debug)]
@@ -56,14 +59,23 @@ impl WorkspaceIndexContext {
             docs_content: None,
             members: index_members,
             preferred_members: index_preferred_members,
+            dependency_graph: None,
         };
 
         if let Some(docs_path) = &workspace_config.workspace.docs_path {
-            let res = LocalAsset::load_string(docs_path)?;
-            let html = to_html(&res, &workspace_config.styles.syntax_theme)?;
+            let res = LocalAsset::load_string(workspace_config.path(docs_path))?;
+            let html = to_html(&res, workspace_config)?;
             workspace.docs_content = Some(html);
         }
 
+        if workspace_config.workspace.dependency_graph {
+            let graph = DependencyGraph::from_members(members)?;
+            workspace.dependency_graph = DependencyGraphContext::new(&graph, workspace_config);
+            if workspace.dependency_graph.is_none() {
+                tracing::warn!("workspace.dependency_graph is set, but none of your members depend on each other, so there's no graph to show.");
+            }
+        }
+
         Ok(workspace)
     }
 
@@ -72,7 +84,7 @@ impl WorkspaceIndexContext {
         member: &WorkspaceData,
         workspace_config: &Config,
     ) -> Result<Utf8PathBuf> {
-        let root_path = Utf8PathBuf::from_path_buf(std::env::current_dir()?).unwrap_or_default();
+        let root_path = &member.root_path;
         if logo_url.starts_with("http") {
             // Lifted from axoasset. Expose it there?
             let mut filename = url::Url::parse(logo_url)?
@@ -0,0 +1,107 @@
+//! Where a built site goes, see [`SiteWriter`][].
+//!
+//! [`Site::write`][super::Site::write] writes to the dist dir with a [`DirWriter`][], but the
+//! pages, scripts and everything else can just as well go to memory, an archive or a bucket.
+//! Parts of the build that run other tools, like mdbook, still put what those tools make on disk
+//! first, and hand the writer the directory they ended up in.
+
+use std::collections::BTreeMap;
+
+use axoasset::LocalAsset;
+use camino::{Utf8Path, Utf8PathBuf};
+
+use crate::errors::*;
+
+/// Something that takes the files of a built site
+pub trait SiteWriter {
+    /// Writes `contents` to `path`, which is relative to the root of the site
+    fn write(&mut self, path: &Utf8Path, contents: &[u8]) -> Result<()>;
+
+    /// Writes every file in the local directory `dir` under `path`, keeping the directory
+    /// structure they're in
+    fn write_dir(&mut self, path: &Utf8Path, dir: &Utf8Path) -> Result<()> {
+        for file in files_in(dir)? {
+            let contents = LocalAsset::load_bytes(dir.join(&file))?;
+            self.write(&path.join(&file), &contents)?;
+        }
+        Ok(())
+    }
+
+    /// The local directory this writes to, if it writes to one. Some things, like
+    /// `build.single_file`, can only be done to files on disk.
+    fn local_dir(&self) -> Option<&Utf8Path> {
+        None
+    }
+}
+
+/// Writes a site to a local directory, like the dist dir
+#[derive(Debug, Clone)]
+pub struct DirWriter {
+    dir: Utf8PathBuf,
+}
+
+impl DirWriter {
+    pub fn new(dir: impl Into<Utf8PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+impl SiteWriter for DirWriter {
+    fn write(&mut self, path: &Utf8Path, contents: &[u8]) -> Result<()> {
+        let path = self.dir.join(path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    fn local_dir(&self) -> Option<&Utf8Path> {
+        Some(&self.dir)
+    }
+}
+
+/// Keeps a site in memory, for tools that do something with the files themselves
+#[derive(Debug, Clone, Default)]
+pub struct MemoryWriter {
+    pub files: BTreeMap<Utf8PathBuf, Vec<u8>>,
+}
+
+impl MemoryWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The file at `path`, as text
+    pub fn get(&self, path: impl AsRef<Utf8Path>) -> Option<String> {
+        self.files
+            .get(path.as_ref())
+            .map(|contents| String::from_utf8_lossy(contents).into_owned())
+    }
+}
+
+impl SiteWriter for MemoryWriter {
+    fn write(&mut self, path: &Utf8Path, contents: &[u8]) -> Result<()> {
+        self.files.insert(path.to_owned(), contents.to_vec());
+        Ok(())
+    }
+}
+
+/// Every file under `dir`, relative to it, sorted so that writers see them in the same order
+/// every time
+pub fn files_in(dir: &Utf8Path) -> Result<Vec<Utf8PathBuf>> {
+    let mut files = vec![];
+    let mut dirs = vec![dir.to_owned()];
+    while let Some(current) = dirs.pop() {
+        for entry in current.read_dir_utf8()? {
+            let path = entry?.into_path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else if let Ok(relative) = path.strip_prefix(dir) {
+                files.push(relative.to_owned());
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
@@ -0,0 +1,95 @@
+//! Self-signed certificates for `oranda serve --https` and `oranda dev --https`.
+//!
+//! Browsers only let pages use some APIs, like the clipboard, in a secure context, which a site
+//! previewed from another device over plain HTTP isn't. We make a certificate for the addresses
+//! the site can be reached at and keep it under the project's `.oranda-cache/tls/`, so that a
+//! browser you told to trust it once keeps doing so until those addresses change.
+
+use std::net::{IpAddr, UdpSocket};
+
+use axoasset::LocalAsset;
+use camino::{Utf8Path, Utf8PathBuf};
+
+use crate::errors::*;
+
+/// Where the certificate goes, relative to the project's
+/// [`root_dir`][crate::config::Config::root_dir]
+pub const CERT_DIR: &str = ".oranda-cache/tls";
+
+/// A certificate and its private key, both PEM-encoded
+#[derive(Debug, Clone)]
+pub struct SelfSigned {
+    pub cert_pem: String,
+    pub key_pem: String,
+}
+
+/// Where the project in `root_dir` keeps its certificate
+pub fn cert_dir(root_dir: &Utf8Path) -> Utf8PathBuf {
+    root_dir.join(CERT_DIR)
+}
+
+/// A certificate for `names`, reusing the one the project in `root_dir` made last time if it
+/// was for the same names
+pub fn self_signed(root_dir: &Utf8Path, names: &[String]) -> Result<SelfSigned> {
+    self_signed_in(&cert_dir(root_dir), names)
+}
+
+/// Like [`self_signed`][], but keeps the certificate in `dir`
+pub fn self_signed_in(dir: &Utf8Path, names: &[String]) -> Result<SelfSigned> {
+    let names_path = dir.join("names.txt");
+    let cert_path = dir.join("cert.pem");
+    let key_path = dir.join("key.pem");
+    let stored = (
+        std::fs::read_to_string(&names_path),
+        std::fs::read_to_string(&cert_path),
+        std::fs::read_to_string(&key_path),
+    );
+    if let (Ok(stored_names), Ok(cert_pem), Ok(key_pem)) = stored {
+        if stored_names == names.join("\n") {
+            return Ok(SelfSigned { cert_pem, key_pem });
+        }
+    }
+
+    let cert = rcgen::generate_simple_self_signed(names).map_err(|e| {
+        OrandaError::TlsCertificateFailed {
+            details: e.to_string(),
+        }
+    })?;
+    let cert_pem = cert
+        .serialize_pem()
+        .map_err(|e| OrandaError::TlsCertificateFailed {
+            details: e.to_string(),
+        })?;
+    let key_pem = cert.serialize_private_key_pem();
+    LocalAsset::write_new_all(&cert_pem, &cert_path)?;
+    LocalAsset::write_new_all(&key_pem, &key_path)?;
+    LocalAsset::write_new_all(&names.join("\n"), &names_path)?;
+    Ok(SelfSigned { cert_pem, key_pem })
+}
+
+/// The addresses a server listening on `host` can be reached at. Listening on every interface
+/// (`0.0.0.0`) adds this machine's address on the local network.
+pub fn names_for(host: IpAddr) -> Vec<String> {
+    let mut names = vec![
+        "localhost".to_owned(),
+        "127.0.0.1".to_owned(),
+        "::1".to_owned(),
+    ];
+    let extra = if host.is_unspecified() {
+        lan_address()
+    } else {
+        Some(host).filter(|host| !host.is_loopback())
+    };
+    names.extend(extra.map(|ip| ip.to_string()));
+    names
+}
+
+/// This machine's address on the local network, the one other devices would use to reach it
+pub fn lan_address() -> Option<IpAddr> {
+    // Connecting a UDP socket doesn't send anything, but it does pick the interface we'd route
+    // traffic through, and so the address we have on that network
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("8.8.8.8:80").ok()?;
+    let ip = socket.local_addr().ok()?.ip();
+    Some(ip).filter(|ip| !ip.is_loopback() && !ip.is_unspecified())
+}
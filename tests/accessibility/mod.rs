@@ -0,0 +1,144 @@
+use std::collections::{HashMap, HashSet};
+
+use minijinja::context;
+use minijinja::value::Value;
+use oranda::config::{Config, FundingConfig};
+use oranda::data::funding::{Funding, FundingContent, FundingType};
+use oranda::site::funding;
+use oranda::site::templates::Templates;
+use scraper::{ElementRef, Html, Selector};
+
+fn select<'a>(html: &'a Html, selector: &str) -> Vec<ElementRef<'a>> {
+    html.select(&Selector::parse(selector).unwrap()).collect()
+}
+
+fn text(element: &ElementRef) -> String {
+    element.text().collect::<String>().trim().to_owned()
+}
+
+/// Whether something a screen reader announces has a name to announce
+fn has_name(element: &ElementRef) -> bool {
+    let attr = |name| {
+        element
+            .value()
+            .attr(name)
+            .is_some_and(|v| !v.trim().is_empty())
+    };
+    if attr("aria-label") || attr("title") || !text(element).is_empty() {
+        return true;
+    }
+    element
+        .select(&Selector::parse("img[alt]").unwrap())
+        .any(|img| !img.value().attr("alt").unwrap().trim().is_empty())
+}
+
+/// A small subset of what axe checks, covering the mistakes templates tend to make
+fn audit(source: &str) -> Vec<String> {
+    let html = Html::parse_document(source);
+    let mut problems = vec![];
+    if select(&html, "html[lang]").is_empty() {
+        problems.push("<html> has no lang".to_owned());
+    }
+    if select(&html, "main").len() != 1 {
+        problems.push("there should be exactly one <main>".to_owned());
+    }
+    let mut ids = HashMap::new();
+    for element in select(&html, "[id]") {
+        *ids.entry(element.value().id().unwrap().to_owned())
+            .or_insert(0) += 1;
+    }
+    for (id, count) in &ids {
+        if *count > 1 {
+            problems.push(format!("id {id} is used {count} times"));
+        }
+    }
+    match select(&html, "a.skip-link").first() {
+        Some(skip) => {
+            let target = skip.value().attr("href").unwrap_or_default();
+            if !ids.contains_key(target.trim_start_matches('#')) {
+                problems.push(format!(
+                    "the skip link points at {target}, which doesn't exist"
+                ));
+            }
+        }
+        None => problems.push("there's no skip link".to_owned()),
+    }
+    for img in select(&html, "img:not([alt])") {
+        problems.push(format!("image without alt: {}", img.html()));
+    }
+    for element in select(&html, "a[href], button, [role=tab]") {
+        if !has_name(&element) {
+            problems.push(format!("no accessible name: {}", element.html()));
+        }
+    }
+    let labels = select(&html, "label[for]")
+        .iter()
+        .filter_map(|label| label.value().attr("for"))
+        .collect::<HashSet<_>>();
+    for control in select(&html, "input:not([type=hidden]), select, textarea") {
+        let labelled = control.value().attr("aria-label").is_some()
+            || control.value().id().is_some_and(|id| labels.contains(id));
+        if !labelled {
+            problems.push(format!("form control without a label: {}", control.html()));
+        }
+    }
+    for iframe in select(&html, "iframe:not([title])") {
+        problems.push(format!("iframe without a title: {}", iframe.html()));
+    }
+    for nested in select(&html, "a a, a button, button a, button button") {
+        problems.push(format!("nested interactive element: {}", nested.html()));
+    }
+    problems
+}
+
+fn templates(config: &Config) -> Templates {
+    Templates::new(config, None).unwrap()
+}
+
+#[test]
+fn it_builds_accessible_layouts() {
+    let mut config = Config::default();
+    config.project.repository = Some("https://github.com/axodotdev/axolotlsay".to_owned());
+    config.project.license = Some("MIT".to_owned());
+    let templates = templates(&config);
+
+    let page = templates
+        .render_to_string("offline.html", context!())
+        .unwrap();
+    assert_eq!(audit(&page), Vec::<String>::new());
+
+    let body = "<h2>Hello</h2><p><img src=\"logo.png\" alt=\"Logo\"></p>";
+    let page = templates
+        .render_to_string("markdown_page.html", context!(body, title => "Docs"))
+        .unwrap();
+    assert_eq!(audit(&page), Vec::<String>::new());
+}
+
+#[test]
+fn it_builds_an_accessible_funding_page() {
+    let config = Config::default();
+    let funding = Funding {
+        content: HashMap::from([
+            (
+                FundingType::Github,
+                FundingContent::One("axodotdev".to_owned()),
+            ),
+            (FundingType::KoFi, FundingContent::One("axo".to_owned())),
+        ]),
+        ..Default::default()
+    };
+    let context = funding::context(&FundingConfig::default(), &funding).unwrap();
+    let page = templates(&config)
+        .render_to_string("funding.html", Value::from_serializable(&context))
+        .unwrap();
+    assert!(page.contains("https://github.com/sponsors/axodotdev"));
+    assert_eq!(audit(&page), Vec::<String>::new());
+}
+
+#[test]
+fn it_catches_inaccessible_markup() {
+    let problems = audit(
+        r#"<html><body><main><a href="/"><button>Go</button></a><img src="x.png"><select></select></main></body></html>"#,
+    );
+    assert_eq!(problems.len(), 5, "{problems:#?}");
+}
@@ -0,0 +1,174 @@
+use axoproject::GithubRepo;
+use minijinja::{context, Value};
+use oranda::config::Config;
+use oranda::data::activity::{parse_discussions, parse_issue_count, response_time};
+use oranda::data::activity::{ProjectActivity, ResponseTime};
+use oranda::site::activity::{format_hours, ActivityContext};
+use oranda::site::templates::Templates;
+
+const ISSUES: &str = r#"[
+  {
+    "url": "https://api.github.com/repos/axodotdev/axolotlsay/issues/3",
+    "user": { "login": "newcomer", "type": "User" },
+    "created_at": "2024-05-03T00:00:00Z"
+  },
+  {
+    "url": "https://api.github.com/repos/axodotdev/axolotlsay/issues/2",
+    "user": { "login": "maintainer", "type": "User" },
+    "created_at": "2024-05-02T00:00:00Z",
+    "pull_request": {}
+  },
+  {
+    "url": "https://api.github.com/repos/axodotdev/axolotlsay/issues/1",
+    "user": { "login": "reporter", "type": "User" },
+    "created_at": "2024-05-01T00:00:00Z"
+  }
+]"#;
+
+const COMMENTS: &str = r#"[
+  {
+    "issue_url": "https://api.github.com/repos/axodotdev/axolotlsay/issues/3",
+    "user": { "login": "maintainer", "type": "User" },
+    "created_at": "2024-05-03T06:00:00Z"
+  },
+  {
+    "issue_url": "https://api.github.com/repos/axodotdev/axolotlsay/issues/2",
+    "user": { "login": "reviewer", "type": "User" },
+    "created_at": "2024-05-02T01:00:00Z"
+  },
+  {
+    "issue_url": "https://api.github.com/repos/axodotdev/axolotlsay/issues/1",
+    "user": { "login": "maintainer", "type": "User" },
+    "created_at": "2024-05-01T10:00:00Z"
+  },
+  {
+    "issue_url": "https://api.github.com/repos/axodotdev/axolotlsay/issues/1",
+    "user": { "login": "reporter", "type": "User" },
+    "created_at": "2024-05-01T01:00:00Z"
+  },
+  {
+    "issue_url": "https://api.github.com/repos/axodotdev/axolotlsay/issues/1",
+    "user": { "login": "triage-bot", "type": "Bot" },
+    "created_at": "2024-05-01T00:01:00Z"
+  }
+]"#;
+
+const DISCUSSIONS: &str = r#"{
+  "data": {
+    "repository": {
+      "url": "https://github.com/axodotdev/axolotlsay",
+      "hasDiscussionsEnabled": true,
+      "discussions": {
+        "nodes": [
+          {
+            "title": "Show and tell: axolotlsay in my shell prompt",
+            "url": "https://github.com/axodotdev/axolotlsay/discussions/7",
+            "createdAt": "2024-05-04T12:00:00Z",
+            "comments": { "totalCount": 1 }
+          }
+        ]
+      }
+    }
+  }
+}"#;
+
+fn repo() -> GithubRepo {
+    GithubRepo::from_url("https://github.com/axodotdev/axolotlsay").unwrap()
+}
+
+#[test]
+fn it_reads_the_open_issue_count() {
+    assert_eq!(
+        parse_issue_count(r#"{ "total_count": 42, "items": [] }"#).unwrap(),
+        42
+    );
+}
+
+#[test]
+fn it_averages_the_first_responses_from_other_people() {
+    let average = response_time(ISSUES, COMMENTS).unwrap().unwrap();
+    // Issue 1 waited 10 hours (the bot and the reporter don't count), issue 3 waited 6, and
+    // issue 2 is a pull request
+    assert_eq!(average.issues, 2);
+    assert_eq!(average.hours, 8.0);
+    assert_eq!(response_time("[]", COMMENTS).unwrap(), None);
+}
+
+#[test]
+fn it_reads_discussions() {
+    let (discussions, url) = parse_discussions(DISCUSSIONS).unwrap();
+    assert_eq!(discussions.len(), 1);
+    assert_eq!(discussions[0].created_at, "2024-05-04");
+    assert_eq!(discussions[0].comments, 1);
+    assert_eq!(
+        url.as_deref(),
+        Some("https://github.com/axodotdev/axolotlsay/discussions")
+    );
+
+    let disabled = DISCUSSIONS.replace(
+        r#""hasDiscussionsEnabled": true"#,
+        r#""hasDiscussionsEnabled": false"#,
+    );
+    assert_eq!(parse_discussions(&disabled).unwrap(), (vec![], None));
+    assert!(parse_discussions(r#"{ "errors": [{ "message": "Bad credentials" }] }"#).is_err());
+}
+
+#[test]
+fn it_formats_response_times() {
+    assert_eq!(format_hours(0.2), "under an hour");
+    assert_eq!(format_hours(1.2), "1 hour");
+    assert_eq!(format_hours(30.0), "30 hours");
+    assert_eq!(format_hours(80.0), "3 days");
+}
+
+#[test]
+fn it_renders_the_activity_widget() {
+    let activity = ProjectActivity {
+        open_issues: Some(12),
+        response_time: Some(ResponseTime {
+            hours: 8.0,
+            issues: 2,
+        }),
+        discussions: parse_discussions(DISCUSSIONS).unwrap().0,
+        discussions_url: Some("https://github.com/axodotdev/axolotlsay/discussions".to_owned()),
+    };
+    let context = ActivityContext::new(&repo(), activity).unwrap();
+    let templates = Templates::new(&Config::default(), None).unwrap();
+    let page = templates
+        .render_to_string(
+            "index.html",
+            context!(activity => Value::from_serializable(&context)),
+        )
+        .unwrap();
+    assert!(page.contains(r#"<span class="activity-value">12</span>"#));
+    assert!(page.contains(r#"<span class="activity-value">8 hours</span>"#));
+    assert!(page.contains("Show and tell: axolotlsay in my shell prompt"));
+    assert!(page.contains("All discussions"));
+}
+
+#[test]
+fn it_escapes_discussion_titles() {
+    let discussions = DISCUSSIONS.replace(
+        "Show and tell: axolotlsay in my shell prompt",
+        "<script>alert(1)</script>",
+    );
+    let activity = ProjectActivity {
+        discussions: parse_discussions(&discussions).unwrap().0,
+        ..Default::default()
+    };
+    let context = ActivityContext::new(&repo(), activity).unwrap();
+    let templates = Templates::new(&Config::default(), None).unwrap();
+    let page = templates
+        .render_to_string(
+            "index.html",
+            context!(activity => Value::from_serializable(&context)),
+        )
+        .unwrap();
+    assert!(page.contains("&lt;script&gt;alert(1)&lt;&#x2f;script&gt;"));
+    assert!(!page.contains("<script>alert(1)"));
+}
+
+#[test]
+fn it_skips_the_widget_when_nothing_was_fetched() {
+    assert!(ActivityContext::new(&repo(), ProjectActivity::default()).is_none());
+}
@@ -0,0 +1,138 @@
+use oranda::config::{ArtifactsConfig, Config};
+use oranda::data::artifacts::ReleaseArtifacts;
+use oranda::data::{Context, Release, ReleaseSource};
+use oranda::site::api::build_api_pages;
+use oranda::site::page::Page;
+use oranda::site::templates::Templates;
+use serde_json::{json, Value};
+
+use crate::utils::github::release_with_assets;
+
+fn page(filename: &str) -> Page {
+    Page {
+        contents: String::new(),
+        filename: filename.to_owned(),
+        reading_time: None,
+        stale: None,
+    }
+}
+
+#[test]
+fn it_emits_the_site_data_as_json() {
+    let mut config = Config::default();
+    config.build.path_prefix = Some(String::from("axolotlsay"));
+    config.build.private_path = String::from("sponsors");
+    config.components.artifacts = Some(ArtifactsConfig {
+        cargo_dist: true,
+        ..Default::default()
+    });
+    let mut gh_release = release_with_assets("v1.0.0", &[]);
+    gh_release.body = Some(String::from("Fixed the **thing**"));
+    let context = Context {
+        repo: None,
+        releases: vec![Release {
+            manifest: None,
+            source: ReleaseSource::Github(gh_release),
+            artifacts: ReleaseArtifacts::new(None),
+        }],
+        has_prereleases: false,
+        latest_release: Some(0),
+        has_artifacts: false,
+        channels: vec![],
+    };
+    let templates = Templates::new(&config, Some(&context)).unwrap();
+    let pages = [
+        page("index.html"),
+        page("artifacts.html"),
+        page("changelog/v1.0.0.html"),
+        page("sponsors/roadmap.html"),
+        page("404.html.json"),
+    ];
+
+    let api_pages =
+        build_api_pages(&pages, &config, &templates, Some(&context), None, None).unwrap();
+    let json = |name: &str| -> Value {
+        let page = api_pages
+            .iter()
+            .find(|page| page.filename == format!("api/{name}.json"))
+            .unwrap();
+        serde_json::from_str(&page.contents).unwrap()
+    };
+    assert_eq!(
+        api_pages
+            .iter()
+            .map(|p| p.filename.as_str())
+            .collect::<Vec<_>>(),
+        ["api/releases.json", "api/pages.json", "api/nav.json"]
+    );
+
+    let releases = json("releases");
+    assert_eq!(releases[0]["version_tag"], "v1.0.0");
+    assert_eq!(releases[0]["is_prerelease"], false);
+    assert_eq!(releases[0]["body"], "Fixed the **thing**");
+
+    // Private pages and anything that isn't HTML are left out
+    let paths = json("pages")
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|page| page["path"].as_str().unwrap().to_owned())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        [
+            "/axolotlsay/",
+            "/axolotlsay/artifacts/",
+            "/axolotlsay/changelog/v1.0.0/"
+        ]
+    );
+
+    assert_eq!(
+        json("nav"),
+        json!([
+            { "name": "Home", "path": "/axolotlsay/" },
+            { "name": "Install", "path": "/axolotlsay/artifacts/" },
+            { "name": "Docs", "path": "/axolotlsay/book/" },
+            { "name": "Funding", "path": "/axolotlsay/funding/" },
+            { "name": "Blog", "path": "/axolotlsay/blog/" },
+            { "name": "Changelog", "path": "/axolotlsay/changelog/" },
+        ])
+    );
+}
+
+#[test]
+fn it_renders_the_nav_from_the_same_links() {
+    let config = Config::default();
+    let templates = Templates::new(&config, None).unwrap();
+    let nav = templates
+        .render_to_string(
+            "includes/nav.html",
+            minijinja::context!(layout => &templates.layout),
+        )
+        .unwrap();
+    let links = templates
+        .layout
+        .nav_links()
+        .iter()
+        .map(|link| format!(r#"<li><a href="{}">{}</a></li>"#, link.path, link.name))
+        .collect::<Vec<_>>();
+    assert_eq!(links.len(), 4);
+    for link in &links {
+        assert!(nav.contains(link.as_str()), "{link} missing from {nav}");
+    }
+
+    // With nowhere to go but home, there's no nav at all
+    let mut config = Config::default();
+    config.components.mdbook = None;
+    config.components.funding = None;
+    config.components.blog = None;
+    let templates = Templates::new(&config, None).unwrap();
+    assert_eq!(templates.layout.nav_links().len(), 1);
+    let nav = templates
+        .render_to_string(
+            "includes/nav.html",
+            minijinja::context!(layout => &templates.layout),
+        )
+        .unwrap();
+    assert!(!nav.contains("<nav"));
+}
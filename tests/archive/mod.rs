@@ -0,0 +1,83 @@
+use std::io::Read;
+
+use assert_fs::prelude::*;
+use camino::{Utf8Path, Utf8PathBuf};
+use flate2::read::GzDecoder;
+use oranda::site::archive::{self, ArchiveFormat, MANIFEST_NAME};
+
+fn dist() -> assert_fs::TempDir {
+    let dist = assert_fs::TempDir::new().unwrap();
+    dist.child("index.html").write_str("<h1>hi</h1>").unwrap();
+    dist.child("changelog/index.html")
+        .write_str("<h1>changes</h1>")
+        .unwrap();
+    dist
+}
+
+fn utf8(dir: &assert_fs::TempDir) -> Utf8PathBuf {
+    Utf8PathBuf::from_path_buf(dir.path().to_owned()).unwrap()
+}
+
+#[test]
+fn it_packs_a_tarball_with_a_manifest() {
+    let dist = dist();
+    let out = assert_fs::TempDir::new().unwrap();
+    let path = utf8(&out).join("site.tar.gz");
+    let manifest = archive::archive_dir(&utf8(&dist), &path).unwrap();
+    let paths: Vec<_> = manifest
+        .files
+        .iter()
+        .map(|file| file.path.as_str())
+        .collect();
+    assert_eq!(paths, ["changelog/index.html", "index.html"]);
+    assert_eq!(manifest.files[1].size, 11);
+    assert_eq!(
+        manifest.files[1].sha256,
+        "e7fbb6fbbf4ce294913eb62b53ff03a7546649cfdc0d824d9e3a2b4541502f7f"
+    );
+
+    let file = std::fs::File::open(&path).unwrap();
+    let mut tar = tar::Archive::new(GzDecoder::new(file));
+    let mut entries = vec![];
+    for entry in tar.entries().unwrap() {
+        let mut entry = entry.unwrap();
+        let mut contents = String::new();
+        entry.read_to_string(&mut contents).unwrap();
+        entries.push((entry.path().unwrap().display().to_string(), contents));
+    }
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0].0, "changelog/index.html");
+    assert_eq!(entries[0].1, "<h1>changes</h1>");
+    assert_eq!(entries[2].0, MANIFEST_NAME);
+    assert!(entries[2].1.contains(&manifest.files[1].sha256));
+}
+
+#[test]
+fn it_packs_a_zip_without_packing_itself() {
+    let dist = dist();
+    dist.child("site.zip")
+        .write_str("an older archive")
+        .unwrap();
+    let path = utf8(&dist).join("site.zip");
+    archive::archive_dir(&utf8(&dist), &path).unwrap();
+
+    let mut zip = zip::ZipArchive::new(std::fs::File::open(&path).unwrap()).unwrap();
+    let mut names: Vec<_> = zip.file_names().map(str::to_owned).collect();
+    names.sort();
+    assert_eq!(names, ["changelog/index.html", "index.html", MANIFEST_NAME]);
+    let mut index = String::new();
+    zip.by_name("index.html")
+        .unwrap()
+        .read_to_string(&mut index)
+        .unwrap();
+    assert_eq!(index, "<h1>hi</h1>");
+}
+
+#[test]
+fn it_picks_the_format_by_extension() {
+    let format = |path: &str| ArchiveFormat::from_path(Utf8Path::new(path)).ok();
+    assert_eq!(format("site.tar.gz"), Some(ArchiveFormat::TarGz));
+    assert_eq!(format("out/site.tgz"), Some(ArchiveFormat::TarGz));
+    assert_eq!(format("site.zip"), Some(ArchiveFormat::Zip));
+    assert_eq!(format("site.rar"), None);
+}
@@ -0,0 +1,205 @@
+use std::collections::BTreeMap;
+
+use oranda::config::{ArtifactsConfig, Config, MatchingConfig};
+use oranda::data::artifacts::ReleaseArtifacts;
+use oranda::data::artifacts_schema::{
+    ArtifactsJson, FileJson, InstallMethodJson, InstallerJson, PlatformJson, ReleaseArtifactsJson,
+    ReleaseJson, ARTIFACTS_SCHEMA_VERSION,
+};
+use oranda::data::{Context, Release, ReleaseSource};
+use oranda::site::artifacts::template_context;
+use serde_json::json;
+
+use crate::utils::github::release_with_assets;
+
+fn example() -> ArtifactsJson {
+    let tarball = FileJson {
+        name: "axolotlsay-x86_64-unknown-linux-gnu.tar.gz".to_string(),
+        download_url: "https://github.com/axodotdev/axolotlsay/releases/download/v0.1.0/axolotlsay-x86_64-unknown-linux-gnu.tar.gz".to_string(),
+        view_path: None,
+        checksum_file: Some(1),
+    };
+    let checksum = FileJson {
+        name: "axolotlsay-x86_64-unknown-linux-gnu.tar.gz.sha256".to_string(),
+        download_url: "https://github.com/axodotdev/axolotlsay/releases/download/v0.1.0/axolotlsay-x86_64-unknown-linux-gnu.tar.gz.sha256".to_string(),
+        view_path: None,
+        checksum_file: None,
+    };
+    let script = FileJson {
+        name: "axolotlsay-installer.sh".to_string(),
+        download_url: "https://github.com/axodotdev/axolotlsay/releases/download/v0.1.0/axolotlsay-installer.sh".to_string(),
+        view_path: Some("/axolotlsay-installer.sh.txt".to_string()),
+        checksum_file: None,
+    };
+    ArtifactsJson {
+        schema_version: ARTIFACTS_SCHEMA_VERSION,
+        format_version: "0.7.0".to_string(),
+        tag: "v0.1.0".to_string(),
+        formatted_date: Some("Oct 10 2023 at 12:00 UTC".to_string()),
+        platforms_with_downloads: vec![PlatformJson {
+            target: vec!["x86_64-unknown-linux-gnu".to_string()],
+            display_name: "Linux x64".to_string(),
+            installers: vec![1, 0],
+        }],
+        downloadable_files: vec![(0, tarball.clone(), vec!["Linux x64".to_string()])],
+        release: ReleaseJson {
+            artifacts: ReleaseArtifactsJson {
+                files: vec![tarball, checksum, script],
+                installers: vec![
+                    InstallerJson {
+                        label: "tarball".to_string(),
+                        description: "download an archive".to_string(),
+                        app_name: None,
+                        method: InstallMethodJson::Download { file: 0 },
+                    },
+                    InstallerJson {
+                        label: "shell".to_string(),
+                        description: "run a shell script".to_string(),
+                        app_name: Some("axolotlsay".to_string()),
+                        method: InstallMethodJson::Run {
+                            file: Some(2),
+                            run_hint: "curl --proto '=https' --tlsv1.2 -LsSf https://github.com/axodotdev/axolotlsay/releases/download/v0.1.0/axolotlsay-installer.sh | sh".to_string(),
+                        },
+                    },
+                ],
+                targets: BTreeMap::from([("x86_64-unknown-linux-gnu".to_string(), vec![1, 0])]),
+            },
+        },
+        os_script: "/artifacts.js".to_string(),
+        has_checksum_files: true,
+    }
+}
+
+#[test]
+fn it_round_trips_artifacts_json() {
+    let artifacts = example();
+    let serialized = serde_json::to_string(&artifacts).unwrap();
+    let deserialized: ArtifactsJson = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(artifacts, deserialized);
+}
+
+#[test]
+fn it_keeps_the_artifacts_json_field_names_stable() {
+    let value = serde_json::to_value(example()).unwrap();
+    assert_eq!(value["schema_version"], json!(ARTIFACTS_SCHEMA_VERSION));
+    assert_eq!(
+        value["platforms_with_downloads"][0]["installers"],
+        json!([1, 0])
+    );
+    assert_eq!(value["downloadable_files"][0][0], json!(0));
+    assert_eq!(value["downloadable_files"][0][1]["checksum_file"], json!(1));
+    assert_eq!(
+        value["release"]["artifacts"]["installers"][0]["method"],
+        json!({ "type": "Download", "file": 0 })
+    );
+    assert_eq!(
+        value["release"]["artifacts"]["installers"][1]["method"]["type"],
+        json!("Run")
+    );
+    assert_eq!(
+        value["release"]["artifacts"]["targets"]["x86_64-unknown-linux-gnu"],
+        json!([1, 0])
+    );
+}
+
+#[test]
+fn it_ignores_unknown_artifacts_json_fields() {
+    let mut value = serde_json::to_value(example()).unwrap();
+    value["some_future_field"] = json!("hello");
+    value["release"]["artifacts"]["files"][0]["size"] = json!(1234);
+    let deserialized: ArtifactsJson = serde_json::from_value(value).unwrap();
+    assert_eq!(deserialized, example());
+}
+
+/// The context `artifacts.json` is made from, for a release with a tarball, its checksum and an
+/// installer script
+fn release_context(config: &Config) -> Context {
+    let gh_release = release_with_assets(
+        "v0.1.0",
+        &[
+            ("axolotlsay-x86_64-unknown-linux-gnu.tar.gz", 3),
+            ("axolotlsay-x86_64-unknown-linux-gnu.tar.gz.sha256", 0),
+            ("axolotlsay-installer.sh", 5),
+        ],
+    );
+    let artifacts_config = config.components.artifacts.as_ref().unwrap();
+    let mut artifacts = ReleaseArtifacts::new(None);
+    artifacts.add_github(&gh_release);
+    artifacts.add_inference(artifacts_config);
+    artifacts.select_installers(artifacts_config);
+    Context {
+        repo: None,
+        releases: vec![Release {
+            manifest: None,
+            source: ReleaseSource::Github(gh_release),
+            artifacts,
+        }],
+        has_prereleases: false,
+        latest_release: Some(0),
+        has_artifacts: true,
+        channels: vec![],
+    }
+}
+
+#[test]
+fn it_writes_a_real_release_in_the_schema() {
+    let mut config = Config::default();
+    config.components.artifacts = Some(ArtifactsConfig {
+        matching: MatchingConfig {
+            checksums: vec![r"\.sha256$".to_owned()],
+            ..MatchingConfig::default()
+        },
+        ..ArtifactsConfig::default()
+    });
+    let context = release_context(&config);
+    let schema = template_context(&context, &config)
+        .unwrap()
+        .unwrap()
+        .to_schema();
+
+    // What's written is exactly what readers of the schema get back
+    let value = serde_json::to_value(&schema).unwrap();
+    let deserialized: ArtifactsJson = serde_json::from_value(value.clone()).unwrap();
+    assert_eq!(deserialized, schema);
+    assert_eq!(value["schema_version"], json!(ARTIFACTS_SCHEMA_VERSION));
+    assert_eq!(value["tag"], json!("v0.1.0"));
+
+    let release = &context.releases[0];
+    assert_eq!(schema.release.artifacts, release.artifacts.to_schema());
+    let files = &schema.release.artifacts.files;
+    let names = files.iter().map(|f| f.name.as_str()).collect::<Vec<_>>();
+    assert!(names.contains(&"axolotlsay-x86_64-unknown-linux-gnu.tar.gz"));
+    assert!(names.contains(&"axolotlsay-installer.sh"));
+    let tarball = files
+        .iter()
+        .find(|f| f.name == "axolotlsay-x86_64-unknown-linux-gnu.tar.gz")
+        .unwrap();
+    assert_eq!(
+        tarball.download_url,
+        "https://example.com/axolotlsay-x86_64-unknown-linux-gnu.tar.gz"
+    );
+    let checksum = tarball.checksum_file.unwrap();
+    assert_eq!(
+        files[checksum].name,
+        "axolotlsay-x86_64-unknown-linux-gnu.tar.gz.sha256"
+    );
+
+    // Every index in the schema points at something that's there
+    let installers = &schema.release.artifacts.installers;
+    for installer in installers {
+        match &installer.method {
+            InstallMethodJson::Download { file } => assert!(*file < files.len()),
+            InstallMethodJson::Run { file, .. } => {
+                assert!(file.map_or(true, |file| file < files.len()))
+            }
+        }
+    }
+    assert!(!schema.platforms_with_downloads.is_empty());
+    for platform in &schema.platforms_with_downloads {
+        assert!(platform.installers.iter().all(|i| *i < installers.len()));
+    }
+    for (installer, file, _) in &schema.downloadable_files {
+        assert!(*installer < installers.len());
+        assert!(files.contains(file));
+    }
+}
@@ -0,0 +1,55 @@
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use oranda::config::Config;
+use oranda::site::markdown::asciinema::{extract, Recording};
+use oranda::site::markdown::to_html;
+
+#[test]
+fn it_reads_shortcode_attributes() {
+    let recording =
+        Recording::parse(r#"src="demo.cast" title='Hi there' cols=80 autoplay"#).unwrap();
+    assert_eq!(
+        recording,
+        Recording {
+            src: "demo.cast".to_owned(),
+            title: Some("Hi there".to_owned()),
+            cols: Some(80),
+            rows: None,
+            autoplay: true,
+            looped: false,
+        }
+    );
+    assert!(Recording::parse("autoplay").is_none());
+}
+
+#[test]
+fn it_leaves_code_blocks_alone() {
+    let markdown = "{{asciinema src=\"a.cast\"}}\n\n```\n{{asciinema src=\"b.cast\"}}\n```\n";
+    let (replaced, recordings) = extract(markdown);
+    assert_eq!(recordings.len(), 1);
+    assert_eq!(recordings[0].src, "a.cast");
+    assert!(replaced.contains("{{asciinema src=\"b.cast\"}}"));
+}
+
+#[test]
+fn it_embeds_players_and_images() {
+    let dist = TempDir::new().unwrap();
+    let mut config = Config::default();
+    config.build.dist_dir = dist.path().display().to_string();
+    config.build.path_prefix = Some("axolotlsay".to_owned());
+
+    let html = to_html(
+        "{{asciinema src=\"https://example.com/demo.cast\" loop}}\n\nSee {{asciinema src=\"https://example.com/demo.svg\" title=\"<Demo>\"}}\n",
+        &config,
+    )
+    .unwrap();
+    assert!(html.starts_with(
+        r#"<figure class="asciinema"><div class="asciinema-player" data-src="https://example.com/demo.cast" data-loop>"#
+    ));
+    assert!(html.contains(r#"<script src="/axolotlsay/asciinema.js" defer></script>"#));
+    assert!(html.contains(
+        r#"<p>See <figure class="asciinema"><img src="https://example.com/demo.svg" alt="&lt;Demo&gt;""#
+    ));
+    // Nothing to copy for remote recordings
+    assert!(!dist.child("recordings").exists());
+}
@@ -0,0 +1,64 @@
+use axoproject::GithubRepo;
+use oranda::data::artifacts::ReleaseArtifacts;
+
+use crate::utils::github::release_with_assets;
+
+const LINUX: &str = "app-x86_64-unknown-linux-gnu.tar.gz";
+const WINDOWS: &str = "app-x86_64-pc-windows-msvc.zip";
+
+fn artifacts(assets: &[&str]) -> ReleaseArtifacts {
+    let assets = assets.iter().map(|name| (*name, 0)).collect::<Vec<_>>();
+    let mut artifacts = ReleaseArtifacts::new(None);
+    artifacts.add_github(&release_with_assets("v1.0.0", &assets));
+    artifacts
+}
+
+fn repo() -> GithubRepo {
+    GithubRepo {
+        owner: "axodotdev".to_owned(),
+        name: "app".to_owned(),
+    }
+}
+
+#[test]
+fn it_groups_signatures_with_their_files() {
+    let mut artifacts = artifacts(&[
+        LINUX,
+        &format!("{LINUX}.sig"),
+        &format!("{LINUX}.pem"),
+        WINDOWS,
+        &format!("{WINDOWS}.sigstore.json"),
+        "multiple.intoto.jsonl",
+    ]);
+    artifacts.add_attestations(Some(&repo()));
+
+    let linux = artifacts.file(artifacts.file_idx(&LINUX.to_owned()).unwrap());
+    let signature = artifacts.file(linux.attestations.signature.unwrap());
+    assert_eq!(signature.name, format!("{LINUX}.sig"));
+    assert!(linux.attestations.certificate.is_some());
+    assert_eq!(
+        linux.attestations.verify_hints,
+        vec![
+            format!("cosign verify-blob {LINUX} --signature {LINUX}.sig --certificate {LINUX}.pem --certificate-identity-regexp '^https://github.com/axodotdev/app/' --certificate-oidc-issuer https://token.actions.githubusercontent.com"),
+            format!("slsa-verifier verify-artifact {LINUX} --provenance-path multiple.intoto.jsonl --source-uri github.com/axodotdev/app"),
+        ]
+    );
+
+    let windows = artifacts.file(artifacts.file_idx(&WINDOWS.to_owned()).unwrap());
+    assert!(windows.attestations.sigstore_bundle.is_some());
+    assert!(windows.attestations.verify_hints[0]
+        .contains("--bundle app-x86_64-pc-windows-msvc.zip.sigstore.json"));
+
+    // The signatures themselves aren't verified with anything
+    let signature_idx = linux.attestations.signature.unwrap();
+    assert!(artifacts.file(signature_idx).attestations.is_empty());
+}
+
+#[test]
+fn it_only_suggests_keyless_verification_for_github_repos() {
+    let mut artifacts = artifacts(&[LINUX, &format!("{LINUX}.sigstore.json")]);
+    artifacts.add_attestations(None);
+    let linux = artifacts.file(artifacts.file_idx(&LINUX.to_owned()).unwrap());
+    assert!(linux.attestations.sigstore_bundle.is_some());
+    assert!(linux.attestations.verify_hints.is_empty());
+}
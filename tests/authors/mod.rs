@@ -0,0 +1,45 @@
+use oranda::config::{AuthorProfile, AuthorsConfig, Config};
+use oranda::site::authors::byline;
+
+fn config() -> Config {
+    let mut config = Config::default();
+    let mut authors = AuthorsConfig::default();
+    authors.people.insert(
+        "jdoe".to_owned(),
+        AuthorProfile {
+            name: "Jane Doe".to_owned(),
+            avatar: None,
+            github: Some("janedoe".to_owned()),
+            bio: None,
+            links: Default::default(),
+        },
+    );
+    config.components.authors = Some(authors);
+    config
+}
+
+#[test]
+fn it_resolves_authors_by_id_name_or_github_login() {
+    let config = config();
+    for name in ["jdoe", "Jane Doe", "JaneDoe"] {
+        let resolved = byline(name, &config);
+        assert_eq!(resolved.id, "jdoe");
+        assert_eq!(resolved.name, "Jane Doe");
+        assert_eq!(
+            resolved.avatar_url.as_deref(),
+            Some("https://github.com/janedoe.png?size=96")
+        );
+        assert_eq!(resolved.link.as_deref(), Some("/authors/#jdoe"));
+    }
+}
+
+#[test]
+fn it_keeps_unknown_authors_as_is() {
+    let resolved = byline("Someone Else", &config());
+    assert_eq!(resolved.id, "someone-else");
+    assert_eq!(resolved.name, "Someone Else");
+    assert_eq!(resolved.avatar_url, None);
+
+    let resolved = byline("Someone Else", &Config::default());
+    assert_eq!(resolved.link, None);
+}
@@ -32,7 +32,7 @@ fn it_loads_a_js_project_config() {
         .write_str(project_config::package_json())
         .expect("failed to write package_json");
 
-    let config = AxoprojectLayer::load(Some(tempdir.path().to_path_buf()))
+    let config = AxoprojectLayer::load(Utf8Path::from_path(tempdir.path()).unwrap())
         .expect("failed to load package.json")
         .unwrap();
     let project = config.project.unwrap();
@@ -74,7 +74,7 @@ fn it_loads_a_rust_project_config() {
     let main = tempdir.child("src/main.rs");
     main.write_str(project_config::main_rs())
         .expect("failed to write main.rs");
-    let config = AxoprojectLayer::load(Some(tempdir.path().to_path_buf()))
+    let config = AxoprojectLayer::load(Utf8Path::from_path(tempdir.path()).unwrap())
         .expect("failed to load Cargo.toml")
         .unwrap();
     let project = config.project.unwrap();
@@ -0,0 +1,40 @@
+use oranda::data::benchmarks::{format_duration, parse_results};
+
+#[test]
+fn it_parses_hyperfine_exports() {
+    let results = parse_results(
+        r#"{"results": [
+            {"command": "oranda build", "mean": 0.25, "stddev": 0.01, "median": 0.24,
+             "user": 0.2, "system": 0.05, "min": 0.23, "max": 0.3, "times": [0.23, 0.3]}
+        ]}"#,
+        "build.json",
+    )
+    .unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].name, "oranda build");
+    assert_eq!(results[0].mean, 0.25);
+    assert_eq!(results[0].max, Some(0.3));
+}
+
+#[test]
+fn it_parses_criterion_estimates_in_seconds() {
+    let results = parse_results(
+        r#"{
+            "mean": {"confidence_interval": {}, "point_estimate": 1500.0, "standard_error": 3.0},
+            "median": {"confidence_interval": {}, "point_estimate": 1400.0, "standard_error": 2.0},
+            "median_abs_dev": {"confidence_interval": {}, "point_estimate": 20.0, "standard_error": 1.0},
+            "slope": null,
+            "std_dev": {"confidence_interval": {}, "point_estimate": 100.0, "standard_error": 1.0}
+        }"#,
+        "parse",
+    )
+    .unwrap();
+    assert_eq!(results[0].name, "parse");
+    assert_eq!(format_duration(results[0].mean), "1.50 µs");
+    assert_eq!(results[0].min, None);
+}
+
+#[test]
+fn it_rejects_unknown_json() {
+    assert!(parse_results(r#"{"hello": "world"}"#, "nope.json").is_err());
+}
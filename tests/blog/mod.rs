@@ -0,0 +1,92 @@
+use assert_fs::prelude::*;
+use camino::Utf8Path;
+use oranda::data::blog::{slugify, split_frontmatter, BlogPost};
+use oranda::site::markdown::ReadingTime;
+
+#[test]
+fn it_reads_frontmatter() {
+    let post = BlogPost::parse(
+        "announcing-1.0",
+        "---\ntitle: Announcing 1.0\ndate: 2023-08-01\nauthor: Jane Doe\ntags: [release]\n---\n\nWe did it!\n",
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(post.title, "Announcing 1.0");
+    assert_eq!(post.slug, "announcing-1-0");
+    assert_eq!(post.date.to_string(), "2023-08-01");
+    assert_eq!(post.author.as_deref(), Some("Jane Doe"));
+    assert_eq!(post.tags, vec!["release"]);
+    assert_eq!(post.body, "We did it!\n");
+}
+
+#[test]
+fn it_falls_back_to_the_file_name_and_heading() {
+    let post = BlogPost::parse("2023-09-02-second-post", "# Our second post\n\nHello\n")
+        .unwrap()
+        .unwrap();
+    assert_eq!(post.title, "Our second post");
+    assert_eq!(post.slug, "second-post");
+    assert_eq!(post.date.to_string(), "2023-09-02");
+    assert_eq!(post.body, "Hello\n");
+}
+
+#[test]
+fn it_skips_drafts_and_rejects_undated_posts() {
+    assert!(
+        BlogPost::parse("wip", "---\ndate: 2023-01-01\ndraft: true\n---\nWIP")
+            .unwrap()
+            .is_none()
+    );
+    assert!(BlogPost::parse("undated", "Hello").is_err());
+}
+
+#[test]
+fn it_rejects_posts_without_a_slug() {
+    assert!(BlogPost::parse("2023-01-01-hello", "---\nslug: \"!!!\"\n---\nHello").is_err());
+}
+
+#[test]
+fn it_skips_posts_whose_slug_is_taken() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    dir.child("2023-01-01-hello.md")
+        .write_str("# First\n\nHello")
+        .unwrap();
+    dir.child("2023-02-01-again.md")
+        .write_str("---\nslug: hello\n---\n# Second\n\nHello again")
+        .unwrap();
+    dir.child("2023-03-01-nothing.md")
+        .write_str("---\nslug: \"...\"\n---\nNothing")
+        .unwrap();
+    let posts = BlogPost::load_all(Utf8Path::from_path(dir.path()).unwrap()).unwrap();
+    let titles = posts
+        .iter()
+        .map(|post| (post.slug.as_str(), post.title.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(titles, vec![("hello", "First")]);
+}
+
+#[test]
+fn it_only_splits_leading_frontmatter() {
+    assert_eq!(
+        split_frontmatter("Hello\n---\nworld"),
+        (None, "Hello\n---\nworld")
+    );
+    assert_eq!(
+        split_frontmatter("---\ntitle: x\n---\nbody"),
+        (Some("title: x\n"), "body")
+    );
+    assert_eq!(slugify("Hello, World!"), "hello-world");
+}
+
+#[test]
+fn it_estimates_reading_time() {
+    let short = ReadingTime::from_html(
+        "<p>Hello <em>there</em>,</p><ul><li>one</li><li>two</li></ul> &mdash;",
+    );
+    assert_eq!(short.words, 4);
+    assert_eq!(short.minutes, 1);
+
+    let long = ReadingTime::from_html(&"<p>word</p>".repeat(401));
+    assert_eq!(long.words, 401);
+    assert_eq!(long.minutes, 3);
+}
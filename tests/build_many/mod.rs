@@ -0,0 +1,72 @@
+use assert_fs::prelude::*;
+use camino::Utf8PathBuf;
+use oranda::config::{ConfigOptions, SitesManifest};
+use oranda::data::workspaces;
+use oranda::errors::OrandaError;
+
+use super::utils::tokio_utils::TEST_RUNTIME;
+
+fn path(dir: &assert_fs::TempDir) -> Utf8PathBuf {
+    Utf8PathBuf::from_path_buf(dir.path().canonicalize().unwrap()).unwrap()
+}
+
+#[test]
+fn it_builds_each_site_from_its_own_directory() {
+    let _guard = TEST_RUNTIME.enter();
+    let root = assert_fs::TempDir::new().unwrap();
+    for name in ["axolotlsay", "oranda"] {
+        root.child(format!("projects/{name}/README.md"))
+            .write_str(&format!("# {name}\n"))
+            .unwrap();
+    }
+    root.child("projects/oranda/oranda.json")
+        .write_str(r#"{ "project": { "name": "oranda" } }"#)
+        .unwrap();
+    root.child("portal/sites.json")
+        .write_str(
+            r#"{
+                "dist_dir": "site",
+                "path_prefix": "tools",
+                "sites": [
+                    { "slug": "axolotlsay", "path": "../projects/axolotlsay" },
+                    { "slug": "oranda", "path": "../projects/oranda", "weight": -1 }
+                ]
+            }"#,
+        )
+        .unwrap();
+    let manifest_path = path(&root).join("portal/sites.json");
+
+    let manifest = SitesManifest::load(&manifest_path).unwrap();
+    let root_path = SitesManifest::root_dir(&manifest_path).unwrap();
+    assert_eq!(root_path, path(&root).join("portal"));
+    let sites =
+        workspaces::from_manifest(&manifest, &root_path, &ConfigOptions::default()).unwrap();
+
+    let slugs: Vec<_> = sites.iter().map(|site| site.slug.as_str()).collect();
+    assert_eq!(slugs, ["oranda", "axolotlsay"]);
+    for site in &sites {
+        let slug = &site.slug;
+        assert_eq!(
+            site.config.root_dir,
+            path(&root).join("projects").join(slug)
+        );
+        assert_eq!(
+            site.config.build.path_prefix.as_deref(),
+            Some(format!("tools/{slug}").as_str())
+        );
+        assert_eq!(
+            site.config.build.dist_dir,
+            format!("{root_path}/site/{slug}")
+        );
+        // Unrelated projects can't reach into each other's files
+        assert_eq!(site.config.build.include_root, None);
+    }
+    assert_eq!(sites[0].config.project.name, "oranda");
+}
+
+#[test]
+fn it_complains_about_a_missing_manifest() {
+    let root = assert_fs::TempDir::new().unwrap();
+    let err = SitesManifest::load(&path(&root).join("sites.json")).unwrap_err();
+    assert!(matches!(err, OrandaError::FileNotFound { .. }));
+}
@@ -0,0 +1,126 @@
+use oranda::config::Config;
+use oranda::site::canonical::{add_links, cname_page};
+use oranda::site::link::{
+    base_url, generate_absolute, generate_absolute_or_relative, make_absolute, page_url,
+};
+use oranda::site::page::Page;
+
+fn config(base_url: &str, path_prefix: Option<&str>) -> Config {
+    let mut config = Config::default();
+    config.build.base_url = Some(base_url.to_owned());
+    config.build.path_prefix = path_prefix.map(str::to_owned);
+    config
+}
+
+fn page(filename: &str) -> Page {
+    Page {
+        contents: "<html><head><title>Hi</title></head><body></body></html>".to_owned(),
+        filename: filename.to_owned(),
+        reading_time: None,
+        stale: None,
+    }
+}
+
+#[test]
+fn it_falls_back_to_the_homepage_unless_its_the_repository() {
+    let mut config = Config::default();
+    assert_eq!(base_url(&config), None);
+    config.project.homepage = Some("https://oranda.axo.dev/".to_owned());
+    assert_eq!(base_url(&config), Some("https://oranda.axo.dev"));
+    config.project.homepage = Some("https://github.com/axodotdev/oranda".to_owned());
+    config.project.repository = Some("https://github.com/axodotdev/oranda/".to_owned());
+    assert_eq!(base_url(&config), None);
+    config.build.base_url = Some("https://axodotdev.github.io/oranda/".to_owned());
+    assert_eq!(
+        base_url(&config),
+        Some("https://axodotdev.github.io/oranda")
+    );
+}
+
+#[test]
+fn it_generates_absolute_urls_against_the_base_url() {
+    let config = config("https://axodotdev.github.io", Some("oranda"));
+    assert_eq!(
+        generate_absolute(&config, "blog.rss").as_deref(),
+        Some("https://axodotdev.github.io/oranda/blog.rss")
+    );
+    // The prefix isn't added twice
+    let config = self::config("https://axodotdev.github.io/oranda", Some("oranda"));
+    assert_eq!(
+        generate_absolute(&config, "blog.rss").as_deref(),
+        Some("https://axodotdev.github.io/oranda/blog.rss")
+    );
+    assert_eq!(
+        generate_absolute_or_relative(&Config::default(), "blog.rss"),
+        "/blog.rss"
+    );
+}
+
+#[test]
+fn it_generates_page_urls() {
+    let config = config("https://oranda.axo.dev", None);
+    assert_eq!(
+        page_url(&config, "index.html").as_deref(),
+        Some("https://oranda.axo.dev/")
+    );
+    assert_eq!(
+        page_url(&config, "changelog/v0.6.0.html").as_deref(),
+        Some("https://oranda.axo.dev/changelog/v0.6.0/")
+    );
+    assert_eq!(
+        page_url(&config, "widgets/whats-new.html").as_deref(),
+        Some("https://oranda.axo.dev/widgets/whats-new.html")
+    );
+}
+
+#[test]
+fn it_adds_canonical_links_to_public_pages() {
+    let config = config("https://oranda.axo.dev", None);
+    let mut pages = vec![
+        page("changelog.html"),
+        page("offline.html"),
+        page("internal/roadmap.html"),
+    ];
+    add_links(&mut pages, &config);
+    assert!(pages[0]
+        .contents
+        .contains(r#"<link rel="canonical" href="https://oranda.axo.dev/changelog/" />"#));
+    assert!(pages[0]
+        .contents
+        .contains(r#"<meta property="og:url" content="https://oranda.axo.dev/changelog/" />"#));
+    assert!(!pages[1].contents.contains("canonical"));
+    assert!(!pages[2].contents.contains("canonical"));
+
+    let mut pages = vec![page("index.html")];
+    add_links(&mut pages, &Config::default());
+    assert!(!pages[0].contents.contains("canonical"));
+}
+
+#[test]
+fn it_makes_links_absolute() {
+    let config = config("https://axodotdev.github.io/oranda", None);
+    assert_eq!(
+        make_absolute(&config, "static/og.png"),
+        "https://axodotdev.github.io/oranda/static/og.png"
+    );
+    assert_eq!(
+        make_absolute(&config, "https://example.com/og.png"),
+        "https://example.com/og.png"
+    );
+    assert_eq!(
+        make_absolute(&Config::default(), "static/og.png"),
+        "static/og.png"
+    );
+}
+
+#[test]
+fn it_writes_a_cname_file_when_asked() {
+    let mut config = config("https://oranda.axo.dev/", None);
+    assert!(cname_page(&config).is_none());
+    config.build.cname = true;
+    let cname = cname_page(&config).unwrap();
+    assert_eq!(cname.filename, "CNAME");
+    assert_eq!(cname.contents, "oranda.axo.dev\n");
+    config.build.base_url = None;
+    assert!(cname_page(&config).is_none());
+}
@@ -0,0 +1,417 @@
+use assert_fs::TempDir;
+use camino::Utf8Path;
+use minijinja::Value;
+use oranda::config::{ChangelogConfig, ChangelogSource, Config, UpgradeGuide};
+use oranda::data::artifacts::ReleaseArtifacts;
+use oranda::data::changelog::{parse, ChangelogFile};
+use oranda::data::git::{Commit, ConventionalCommit};
+use oranda::data::{Context, Release, ReleaseSource};
+use oranda::site::changelog::{
+    compare_url, group_commits, json_entries, release_markdown, single_context, tag_version,
+    upgrade_guide_context, upgrade_guide_filename, ChangelogContext, ChangelogRelease,
+};
+use oranda::site::link::pretty_path;
+use oranda::site::rss::generate_prerelease_rss_feed;
+use oranda::site::templates::Templates;
+use oranda::site::widgets::{whats_new_context, WHATS_NEW};
+
+use crate::utils::github::release_with_assets;
+
+#[test]
+fn it_parses_keep_a_changelog_headings() {
+    let sections = parse(
+        r#"# Changelog
+
+## [Unreleased]
+
+- Something in progress
+
+## [1.1.0] - 2023-05-01
+
+### Added
+
+- A new thing
+
+## [1.0.0] - 2023-04-01
+
+- Initial release
+
+[Unreleased]: https://github.com/axodotdev/oranda/compare/v1.1.0...HEAD
+[1.1.0]: https://github.com/axodotdev/oranda/compare/v1.0.0...v1.1.0
+"#,
+    );
+    assert_eq!(sections.len(), 2);
+    assert_eq!(sections[0].version, "1.1.0");
+    assert_eq!(sections[0].body, "### Added\n\n- A new thing");
+    assert_eq!(sections[1].version, "1.0.0");
+    assert_eq!(sections[1].body, "- Initial release");
+}
+
+#[test]
+fn it_parses_ad_hoc_headings() {
+    let sections = parse(
+        r#"# Version 0.3.0-prerelease.1 (beta)
+
+Beep
+
+## v0.2.0 - 2023.01.02
+
+```md
+## v0.1.5
+```
+
+# my-crate v0.1.0
+
+Boop
+"#,
+    );
+    let versions: Vec<_> = sections.iter().map(|s| s.version.as_str()).collect();
+    assert_eq!(versions, ["0.3.0-prerelease.1", "0.2.0", "0.1.0"]);
+    assert_eq!(sections[0].body, "Beep");
+    assert!(sections[1].body.contains("## v0.1.5"));
+    assert_eq!(sections[2].body, "Boop");
+}
+
+#[test]
+fn it_parses_changelog_files_once_until_they_change() {
+    let project = TempDir::new().unwrap();
+    let root = Utf8Path::from_path(project.path()).unwrap();
+    let path = project.path().join("CHANGELOG.md");
+    std::fs::write(&path, "## 0.1.0\n\n- First!\n").unwrap();
+
+    let first = ChangelogFile::load_cached(root, Utf8Path::new("CHANGELOG.md")).unwrap();
+    let second = ChangelogFile::load_cached(root, Utf8Path::new("CHANGELOG.md")).unwrap();
+    assert!(std::sync::Arc::ptr_eq(&first, &second));
+
+    std::fs::write(&path, "## 0.2.0\n\n- Second!\n\n## 0.1.0\n\n- First!\n").unwrap();
+    let later = std::time::SystemTime::now() + std::time::Duration::from_secs(10);
+    std::fs::File::options()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_modified(later)
+        .unwrap();
+    let changed = ChangelogFile::load_cached(root, Utf8Path::new("CHANGELOG.md")).unwrap();
+    assert_eq!(changed.sections.len(), 2);
+}
+
+fn release(version_tag: &str, is_prerelease: bool) -> ChangelogRelease {
+    ChangelogRelease {
+        is_prerelease,
+        version_tag: version_tag.to_owned(),
+        name: None,
+        formatted_date: None,
+        author: None,
+        body: String::new(),
+        compare: None,
+        downloads: Vec::new(),
+        upgrade_guides: Vec::new(),
+    }
+}
+
+#[test]
+fn it_only_puts_prereleases_in_the_prerelease_feed() {
+    let context = ChangelogContext {
+        releases: vec![
+            release("v1.1.0-beta.1", true),
+            release("v1.0.0", false),
+            release("v1.0.0-rc.1", true),
+        ],
+        has_prereleases: true,
+        has_rss_feed: true,
+        has_prerelease_rss_feed: true,
+        os_script: String::new(),
+    };
+    let feed = generate_prerelease_rss_feed(&context, &Config::default()).unwrap();
+    let titles = feed
+        .items()
+        .iter()
+        .filter_map(|item| item.title())
+        .collect::<Vec<_>>();
+    assert_eq!(titles, vec!["v1.1.0-beta.1", "v1.0.0-rc.1"]);
+}
+
+#[test]
+fn it_builds_compare_urls_for_known_hosts() {
+    assert_eq!(
+        compare_url("https://github.com/axodotdev/oranda/", "v0.5.0", "v0.6.0").as_deref(),
+        Some("https://github.com/axodotdev/oranda/compare/v0.5.0...v0.6.0")
+    );
+    assert_eq!(
+        compare_url("https://gitlab.com/group/project.git", "1.0.0", "1.1.0").as_deref(),
+        Some("https://gitlab.com/group/project/-/compare/1.0.0...1.1.0")
+    );
+    assert_eq!(
+        compare_url("https://git.example.com/project", "1.0.0", "1.1.0"),
+        None
+    );
+}
+
+fn github_release(tag: &str, date: &str, body: &str) -> Release {
+    let mut release = release_with_assets(tag, &[]);
+    date.clone_into(&mut release.published_at);
+    release.html_url = format!("https://github.com/axodotdev/axolotlsay/releases/tag/{tag}");
+    release.body = Some(body.to_owned());
+    Release {
+        manifest: None,
+        source: ReleaseSource::Github(release),
+        artifacts: ReleaseArtifacts::new(None),
+    }
+}
+
+#[test]
+fn it_exports_releases_as_json() {
+    let mut config = Config::default();
+    config.project.repository = Some("https://github.com/axodotdev/axolotlsay".to_owned());
+    config.build.path_prefix = Some("axolotlsay".to_owned());
+    config.components.changelog = Some(ChangelogConfig {
+        read_changelog_file: false,
+        ..ChangelogConfig::default()
+    });
+    let context = Context {
+        repo: None,
+        releases: vec![
+            github_release("v0.2.0", "2023-10-10T12:00:00Z", "- Said *hello*"),
+            github_release("v0.1.0", "2023-09-01T12:00:00Z", "- First!"),
+        ],
+        has_prereleases: false,
+        latest_release: Some(0),
+        has_artifacts: false,
+        channels: vec![],
+    };
+
+    let entries = json_entries(&context, &config, None).unwrap();
+    let json = serde_json::to_value(entries).unwrap();
+    assert_eq!(json[0]["version"], "v0.2.0");
+    assert_eq!(json[0]["date"], "2023-10-10T12:00:00+00:00");
+    assert_eq!(json[0]["markdown"], "- Said *hello*");
+    assert!(json[0]["html"]
+        .as_str()
+        .unwrap()
+        .contains("<li>Said <em>hello</em></li>"));
+    assert_eq!(json[0]["links"]["page"], "/axolotlsay/changelog/v0.2.0/");
+    assert_eq!(
+        json[0]["links"]["release"],
+        "https://github.com/axodotdev/axolotlsay/releases/tag/v0.2.0"
+    );
+    assert_eq!(
+        json[0]["links"]["compare"],
+        "https://github.com/axodotdev/axolotlsay/compare/v0.1.0...v0.2.0"
+    );
+    assert_eq!(json[1]["links"]["compare"], serde_json::Value::Null);
+}
+
+#[test]
+fn it_shows_the_latest_stable_release_in_the_whats_new_widget() {
+    let mut config = Config::default();
+    config.components.changelog = Some(ChangelogConfig {
+        read_changelog_file: false,
+        ..ChangelogConfig::default()
+    });
+    let mut beta = github_release("v0.3.0-beta.1", "2023-11-01T12:00:00Z", "- Testing");
+    if let ReleaseSource::Github(release) = &mut beta.source {
+        release.prerelease = true;
+    }
+    let context = Context {
+        repo: None,
+        releases: vec![
+            beta,
+            github_release("v0.2.0", "2023-10-10T12:00:00Z", "- Said *hello*"),
+        ],
+        has_prereleases: true,
+        latest_release: Some(1),
+        has_artifacts: false,
+        channels: vec![],
+    };
+
+    let whats_new = whats_new_context(&context, &config, None);
+    let release = whats_new.release.unwrap();
+    assert_eq!(release.version_tag, "v0.2.0");
+    assert!(release.body.contains("<li>Said <em>hello</em></li>"));
+    assert_eq!(whats_new.changelog_link, "/changelog/");
+    // Embedders point straight at the file, so it's not turned into a pretty link
+    assert_eq!(pretty_path(WHATS_NEW), "widgets/whats-new.html");
+}
+
+#[test]
+fn it_finds_versions_in_tags() {
+    let version = |tag| tag_version(tag).map(|version| version.to_string());
+    assert_eq!(version("v1.2.0").as_deref(), Some("1.2.0"));
+    assert_eq!(version("2.0.0-rc.1").as_deref(), Some("2.0.0-rc.1"));
+    assert_eq!(version("crate2-v1.0.0").as_deref(), Some("1.0.0"));
+    assert_eq!(version("my-crate-0.3.1").as_deref(), Some("0.3.1"));
+    assert_eq!(version("nightly"), None);
+}
+
+#[test]
+fn it_links_releases_and_upgrade_guides_both_ways() {
+    let guide = UpgradeGuide {
+        path: "docs/Upgrading to 2.md".to_owned(),
+        versions: ">=2.0.0, <2.1.0".to_owned(),
+        title: Some("Upgrading to 2.0".to_owned()),
+    };
+    let mut config = Config::default();
+    config.build.path_prefix = Some("axolotlsay".to_owned());
+    config.components.changelog = Some(ChangelogConfig {
+        read_changelog_file: false,
+        upgrade_guides: vec![guide.clone()],
+        ..ChangelogConfig::default()
+    });
+    let context = Context {
+        repo: None,
+        releases: vec![
+            github_release("v2.1.0", "2023-12-01T12:00:00Z", ""),
+            github_release("v2.0.1", "2023-11-10T12:00:00Z", ""),
+            github_release("v2.0.0", "2023-11-01T12:00:00Z", ""),
+            github_release("v1.0.0", "2023-10-10T12:00:00Z", ""),
+        ],
+        has_prereleases: false,
+        latest_release: Some(0),
+        has_artifacts: false,
+        channels: vec![],
+    };
+
+    assert_eq!(
+        upgrade_guide_filename(&guide),
+        "changelog/upgrading/upgrading-to-2.html"
+    );
+    let guides = |idx: usize| {
+        single_context(&context.releases[idx], None, &config, None)
+            .upgrade_guides
+            .into_iter()
+            .map(|link| (link.title, link.url))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(guides(0), vec![]);
+    assert_eq!(
+        guides(2),
+        vec![(
+            "Upgrading to 2.0".to_owned(),
+            "/axolotlsay/changelog/upgrading/upgrading-to-2/".to_owned()
+        )]
+    );
+    assert_eq!(guides(3), vec![]);
+
+    let guide_context = upgrade_guide_context(&guide, &context, &config).unwrap();
+    let releases = guide_context
+        .releases
+        .iter()
+        .map(|release| (release.version_tag.as_str(), release.url.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        releases,
+        vec![
+            ("v2.0.1", "/axolotlsay/changelog/v2.0.1/"),
+            ("v2.0.0", "/axolotlsay/changelog/v2.0.0/"),
+        ]
+    );
+
+    let page = Templates::new(&config, None)
+        .unwrap()
+        .render_to_string(
+            "upgrade_guide.html",
+            Value::from_serializable(&guide_context),
+        )
+        .unwrap();
+    assert!(page.contains(r#"<a href="/axolotlsay/changelog/v2.0.0/">v2.0.0</a>"#));
+
+    let broken = UpgradeGuide {
+        versions: "two point oh".to_owned(),
+        ..guide
+    };
+    assert!(upgrade_guide_context(&broken, &context, &config).is_err());
+}
+
+#[test]
+fn it_parses_conventional_commits() {
+    assert_eq!(
+        ConventionalCommit::parse("feat(cli): add a --quiet flag"),
+        Some(ConventionalCommit {
+            kind: "feat".to_owned(),
+            scope: Some("cli".to_owned()),
+            breaking: false,
+            description: "add a --quiet flag".to_owned(),
+        })
+    );
+    assert_eq!(
+        ConventionalCommit::parse("Fix!: drop the old config format"),
+        Some(ConventionalCommit {
+            kind: "fix".to_owned(),
+            scope: None,
+            breaking: true,
+            description: "drop the old config format".to_owned(),
+        })
+    );
+    let breaking = ConventionalCommit::parse("refactor(build)!: rename outputs").unwrap();
+    assert_eq!(breaking.scope.as_deref(), Some("build"));
+    assert!(breaking.breaking);
+
+    assert_eq!(ConventionalCommit::parse("Update README"), None);
+    assert_eq!(ConventionalCommit::parse("feat: "), None);
+    assert_eq!(ConventionalCommit::parse("feat(cli: unclosed scope"), None);
+    assert_eq!(
+        ConventionalCommit::parse("Merge branch 'main': whatever"),
+        None
+    );
+}
+
+fn commit(hash: &str, summary: &str) -> Commit {
+    Commit {
+        hash: hash.to_owned(),
+        summary: summary.to_owned(),
+    }
+}
+
+#[test]
+fn it_groups_commits_by_type() {
+    let body = group_commits(&[
+        commit("1111111aaaa", "fix(site): escape titles"),
+        commit("2222222bbbb", "Bump the version"),
+        commit("3333333cccc", "feat!: new config format"),
+        commit("4444444dddd", "chore: update dependencies"),
+        commit("5555555eeee", "docs: explain themes"),
+        commit("6666666ffff", "feat(cli): add a --quiet flag"),
+    ]);
+    assert_eq!(
+        body,
+        "### Features\n\n\
+         - **BREAKING:** new config format (3333333)\n\
+         - **cli:** add a --quiet flag (6666666)\n\n\
+         ### Fixes\n\n\
+         - **site:** escape titles (1111111)\n\n\
+         ### Chores\n\n\
+         - update dependencies (4444444)\n\n\
+         ### Other changes\n\n\
+         - Bump the version (2222222)\n\
+         - explain themes (5555555)\n\n"
+    );
+    assert_eq!(group_commits(&[]), "");
+}
+
+#[test]
+fn it_falls_back_to_release_notes_when_a_tag_is_missing() {
+    // Not a git repository, so there are no tags to list commits between
+    let project = TempDir::new().unwrap();
+    std::fs::write(
+        project.path().join("CHANGELOG.md"),
+        "# Changelog\n\n## 0.1.0\n\n- First!\n",
+    )
+    .unwrap();
+    let config = Config {
+        root_dir: Utf8Path::from_path(project.path()).unwrap().to_owned(),
+        components: oranda::config::ComponentConfig {
+            changelog: Some(ChangelogConfig {
+                files: vec!["CHANGELOG.md".to_owned()],
+                source: ChangelogSource::Git,
+                ..ChangelogConfig::default()
+            }),
+            ..Default::default()
+        },
+        ..Config::default()
+    };
+    let release = github_release("v0.2.0", "2023-10-10T12:00:00Z", "- Said hello");
+    assert_eq!(
+        release_markdown(None, &release, &config).unwrap(),
+        "- Said hello"
+    );
+}
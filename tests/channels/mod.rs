@@ -0,0 +1,108 @@
+use camino::Utf8Path;
+use oranda::config::{ArtifactsConfig, ChannelConfig, Config};
+use oranda::data::artifacts::ReleaseArtifacts;
+use oranda::data::{Context, Release, ReleaseSource};
+use oranda::site::artifacts::template_context;
+use oranda::site::page::Page;
+use oranda::site::templates::Templates;
+
+use crate::utils::github::release_with_assets;
+
+fn release(tag: &str, prerelease: bool, config: &ArtifactsConfig) -> Release {
+    let mut gh_release = release_with_assets(
+        tag,
+        &[(&format!("app-{tag}-x86_64-unknown-linux-gnu.tar.gz"), 0)],
+    );
+    gh_release.prerelease = prerelease;
+    let mut artifacts = ReleaseArtifacts::new(None);
+    artifacts.add_github(&gh_release);
+    artifacts.add_inference(config);
+    artifacts.select_installers(config);
+    Release {
+        manifest: None,
+        source: ReleaseSource::Github(gh_release),
+        artifacts,
+    }
+}
+
+fn context(config: &ArtifactsConfig) -> Context {
+    Context {
+        repo: None,
+        releases: vec![
+            release("nightly", true, config),
+            release("v1.1.0-beta.1", true, config),
+            release("v1.0.0", false, config),
+        ],
+        has_prereleases: true,
+        latest_release: Some(2),
+        has_artifacts: true,
+        channels: vec![],
+    }
+}
+
+#[test]
+fn it_finds_channel_releases_by_tag_or_prerelease() {
+    let mut config = ArtifactsConfig::default();
+    config.channels.insert(
+        "Nightly".to_owned(),
+        ChannelConfig {
+            tag: Some("nightly".to_owned()),
+            ..ChannelConfig::default()
+        },
+    );
+    config
+        .channels
+        .insert("Beta".to_owned(), ChannelConfig::default());
+    config.channels.insert(
+        "Canary".to_owned(),
+        ChannelConfig {
+            tag: Some("canary".to_owned()),
+            ..ChannelConfig::default()
+        },
+    );
+    let mut context = context(&config);
+    context.add_channels(Utf8Path::new(""), Some(&config));
+
+    let channels = context
+        .channels
+        .iter()
+        .map(|channel| (channel.name.as_str(), channel.release.source.version_tag()))
+        .collect::<Vec<_>>();
+    // There's no canary release, so that channel is skipped
+    assert_eq!(channels, vec![("Nightly", "nightly"), ("Beta", "nightly")]);
+}
+
+#[test]
+fn it_shows_channels_on_the_install_page() {
+    let mut config = Config::default();
+    let mut artifacts_config = ArtifactsConfig::default();
+    artifacts_config.channels.insert(
+        "Nightly".to_owned(),
+        ChannelConfig {
+            tag: Some("nightly".to_owned()),
+            description: Some("Built from main every night".to_owned()),
+            ..ChannelConfig::default()
+        },
+    );
+    let mut context = context(&artifacts_config);
+    context.add_channels(&config.root_dir, Some(&artifacts_config));
+    config.components.artifacts = Some(artifacts_config);
+
+    let templates = Templates::new(&config, Some(&context)).unwrap();
+    let template_context = template_context(&context, &config).unwrap();
+    let page = Page::new_from_template(
+        "artifacts.html",
+        &templates,
+        "artifacts.html",
+        &template_context,
+    )
+    .unwrap();
+    assert!(page.contents.contains("<h3>Stable</h3>"));
+    assert!(page.contents.contains("Built from main every night"));
+    assert!(page
+        .contents
+        .contains("app-nightly-x86_64-unknown-linux-gnu.tar.gz"));
+    assert!(page
+        .contents
+        .contains("app-v1.0.0-x86_64-unknown-linux-gnu.tar.gz"));
+}
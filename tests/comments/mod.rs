@@ -0,0 +1,92 @@
+use minijinja::context;
+use oranda::config::{CommentsConfig, CommentsProvider, Config, Giscus, Utterances};
+use oranda::site::layout::comments::CommentsContext;
+use oranda::site::templates::Templates;
+
+const REPOSITORY: &str = "https://github.com/axodotdev/axolotlsay";
+
+fn render(config: &Config, template: &str) -> String {
+    let templates = Templates::new(config, None).unwrap();
+    let page = context! {
+        posts => Vec::<()>::new(),
+        total_pages => 1,
+        title => "Hello",
+        body => "<p>Hi</p>",
+        reading_time => context! { minutes => 1 },
+    };
+    templates.render_to_string(template, page).unwrap()
+}
+
+fn giscus() -> CommentsConfig {
+    CommentsConfig {
+        provider: CommentsProvider::Giscus(Giscus {
+            repo: None,
+            repo_id: "R_kgDOabc".to_owned(),
+            category: "Announcements".to_owned(),
+            category_id: "DIC_kwDOabc".to_owned(),
+            mapping: Some("title".to_owned()),
+            reactions: Some(false),
+        }),
+        theme: Some("dark".to_owned()),
+    }
+}
+
+#[test]
+fn it_configures_giscus() {
+    let context = CommentsContext::new(&giscus(), Some(REPOSITORY)).unwrap();
+    assert_eq!(context.script_src, "https://giscus.app/client.js");
+    let attribute = |name: &str| {
+        context
+            .attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    };
+    assert_eq!(attribute("data-repo"), Some("axodotdev/axolotlsay"));
+    assert_eq!(attribute("data-category-id"), Some("DIC_kwDOabc"));
+    assert_eq!(attribute("data-mapping"), Some("title"));
+    assert_eq!(attribute("data-reactions-enabled"), Some("0"));
+    assert_eq!(attribute("data-theme"), Some("dark"));
+}
+
+#[test]
+fn it_defaults_to_utterances_with_the_project_repository() {
+    let context = CommentsContext::new(&CommentsConfig::default(), Some(REPOSITORY)).unwrap();
+    assert_eq!(context.script_src, "https://utteranc.es/client.js");
+    assert_eq!(
+        context.attributes,
+        vec![
+            ("repo".to_owned(), "axodotdev/axolotlsay".to_owned()),
+            ("issue-term".to_owned(), "pathname".to_owned()),
+            ("theme".to_owned(), "preferred-color-scheme".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn it_needs_a_github_repository() {
+    assert!(CommentsContext::new(&CommentsConfig::default(), None).is_none());
+    let config = CommentsConfig {
+        provider: CommentsProvider::Utterances(Utterances {
+            repo: Some("axodotdev/comments".to_owned()),
+            ..Utterances::default()
+        }),
+        theme: None,
+    };
+    assert!(
+        CommentsContext::new(&config, Some("https://gitlab.com/axodotdev/axolotlsay")).is_some()
+    );
+}
+
+#[test]
+fn it_only_renders_comments_on_blog_posts_and_releases() {
+    let mut config = Config::default();
+    config.project.repository = Some(REPOSITORY.to_owned());
+    config.components.comments = Some(CommentsConfig::default());
+    let post = render(&config, "blog_post.html");
+    assert!(post.contains(
+        r#"<script src="https://utteranc.es/client.js" repo="axodotdev&#x2f;axolotlsay""#
+    ));
+    let index = render(&config, "index.html");
+    assert!(!index.contains("utteranc.es"));
+}
@@ -0,0 +1,94 @@
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use camino::Utf8Path;
+use minijinja::context;
+use oranda::config::{CommunityConfig, Config};
+use oranda::site::community;
+use oranda::site::templates::Templates;
+
+fn project() -> TempDir {
+    let project = TempDir::new().unwrap();
+    project
+        .child("CODE_OF_CONDUCT.md")
+        .write_str("# Code of conduct\n\nBe kind.")
+        .unwrap();
+    project
+        .child(".github/CONTRIBUTING.md")
+        .write_str("# Contributing\n\nRead the [code of conduct](../CODE_OF_CONDUCT.md) first.")
+        .unwrap();
+    project
+}
+
+fn config(project: &TempDir) -> Config {
+    let root_dir = Utf8Path::from_path(project.path()).unwrap();
+    let mut community = Some(CommunityConfig::default());
+    CommunityConfig::find_paths(&mut community, project.path()).unwrap();
+    let mut config = Config {
+        root_dir: root_dir.to_owned(),
+        ..Config::default()
+    };
+    config.components.community = community;
+    config.build.path_prefix = Some("axolotlsay".to_owned());
+    config
+}
+
+#[test]
+fn it_finds_community_files_in_the_root_and_dot_github() {
+    let project = project();
+    let config = config(&project);
+    let community = config.components.community.unwrap();
+    assert!(community
+        .code_of_conduct
+        .unwrap()
+        .ends_with("/CODE_OF_CONDUCT.md"));
+    assert!(community
+        .contributing
+        .unwrap()
+        .ends_with("/.github/CONTRIBUTING.md"));
+    assert!(community.governance.is_none());
+}
+
+#[test]
+fn it_turns_itself_off_without_any_files() {
+    let project = TempDir::new().unwrap();
+    let mut community = Some(CommunityConfig::default());
+    CommunityConfig::find_paths(&mut community, project.path()).unwrap();
+    assert!(community.is_none());
+}
+
+#[test]
+fn it_builds_pages_at_fixed_paths() {
+    let project = project();
+    let config = config(&project);
+    let templates = Templates::new(&config, None).unwrap();
+    let pages = community::build_pages(
+        config.components.community.as_ref().unwrap(),
+        &templates,
+        &config,
+    )
+    .unwrap();
+    let filenames = pages
+        .iter()
+        .map(|page| page.filename.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(filenames, vec!["code-of-conduct.html", "contributing.html"]);
+    // Links between the files go to their pages
+    assert!(pages[1].contents.contains(
+        r#"<a href="/axolotlsay/code-of-conduct/" rel="noopener noreferrer">code of conduct</a>"#
+    ));
+    // And every page links to them from the footer
+    assert!(pages[0]
+        .contents
+        .contains(r#"<a href="/axolotlsay/contributing/">Contributing</a>"#));
+}
+
+#[test]
+fn it_leaves_the_footer_alone_without_community_files() {
+    let mut config = Config::default();
+    config.components.community = None;
+    let html = Templates::new(&config, None)
+        .unwrap()
+        .render_to_string("offline.html", context!())
+        .unwrap();
+    assert!(!html.contains("Code of conduct"));
+}
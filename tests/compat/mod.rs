@@ -0,0 +1,28 @@
+use minijinja::context;
+use oranda::config::Config;
+use oranda::site::templates::Templates;
+
+fn render_offline_page(config: &Config) -> String {
+    let templates = Templates::new(config, None).unwrap();
+    templates
+        .render_to_string("offline.html", context!())
+        .unwrap()
+}
+
+#[test]
+fn it_adds_fallback_styles_in_compat_mode() {
+    let mut config = Config::default();
+    config.build.path_prefix = Some("axolotlsay".to_owned());
+    let html = render_offline_page(&config);
+    assert!(!html.contains("compat.css"));
+    assert!(html.contains("fonts.googleapis.com"));
+
+    config.build.compat = true;
+    let html = render_offline_page(&config);
+    let compat = html.find(r#"href="/axolotlsay/compat.css""#).unwrap();
+    let theme = html
+        .find(r#"rel="stylesheet" href="/axolotlsay/oranda"#)
+        .unwrap();
+    assert!(compat < theme);
+    assert!(!html.contains("fonts.googleapis.com"));
+}
@@ -0,0 +1,113 @@
+use assert_fs::prelude::*;
+use camino::Utf8Path;
+use oranda::config::overrides::{from_env_vars, to_layer, ConfigOverride};
+use oranda::config::{Config, ConfigOptions};
+use oranda::errors::OrandaError;
+use oranda::site::oranda_theme::OrandaTheme;
+
+fn set(overrides: &[&str]) -> Vec<ConfigOverride> {
+    overrides.iter().map(|o| o.parse().unwrap()).collect()
+}
+
+#[test]
+fn it_layers_nested_keys() {
+    let layer = to_layer(&set(&[
+        "styles.theme=hacker",
+        "build.path_prefix=preview/pr-42",
+        "components.docker.max_tags=3",
+        "project.version=1.0",
+    ]))
+    .unwrap()
+    .unwrap();
+    assert_eq!(layer.styles.unwrap().theme, Some(OrandaTheme::Hacker));
+    assert_eq!(
+        layer.build.unwrap().path_prefix.as_deref(),
+        Some("preview/pr-42")
+    );
+    // Numbers are only numbers where the config wants one
+    assert_eq!(layer.project.unwrap().version.as_deref(), Some("1.0"));
+    let docker = serde_json::to_value(layer.components.unwrap().docker.unwrap()).unwrap();
+    assert_eq!(docker["max_tags"], 3);
+}
+
+#[test]
+fn later_overrides_win() {
+    let layer = to_layer(&set(&["build.path_prefix=one", "build.path_prefix=two"]))
+        .unwrap()
+        .unwrap();
+    assert_eq!(layer.build.unwrap().path_prefix.as_deref(), Some("two"));
+}
+
+#[test]
+fn overrides_only_apply_to_the_config_theyre_given_to() {
+    let project = assert_fs::TempDir::new().unwrap();
+    project.child("oranda.json").write_str("{}").unwrap();
+    let root = Utf8Path::from_path(project.path()).unwrap();
+    let options = ConfigOptions {
+        overrides: set(&["build.path_prefix=preview/pr-42"]),
+        ..Default::default()
+    };
+    let config = Config::build_in_with(root, Utf8Path::new("oranda.json"), &options).unwrap();
+    assert_eq!(config.build.path_prefix.as_deref(), Some("preview/pr-42"));
+
+    let config = Config::build_in(root, Utf8Path::new("oranda.json")).unwrap();
+    assert_eq!(config.build.path_prefix, None);
+}
+
+#[test]
+fn the_environment_only_applies_when_its_passed_in() {
+    let project = assert_fs::TempDir::new().unwrap();
+    project.child("oranda.json").write_str("{}").unwrap();
+    let root = Utf8Path::from_path(project.path()).unwrap();
+    std::env::set_var("ORANDA_BUILD__DIST_DIR", "from-the-environment");
+
+    let config = Config::build_in(root, Utf8Path::new("oranda.json")).unwrap();
+    assert_eq!(config.build.dist_dir, "public");
+
+    let options = ConfigOptions {
+        overrides: from_env_vars(std::env::vars()),
+        ..Default::default()
+    };
+    let config = Config::build_in_with(root, Utf8Path::new("oranda.json"), &options).unwrap();
+    assert_eq!(config.build.dist_dir, "from-the-environment");
+    std::env::remove_var("ORANDA_BUILD__DIST_DIR");
+}
+
+#[test]
+fn it_rejects_unknown_keys() {
+    let error = to_layer(&set(&["styles.themee=dark"])).unwrap_err();
+    assert!(matches!(
+        error,
+        OrandaError::ConfigOverrideInvalid { ref key, .. } if key == "styles.themee"
+    ));
+    assert!("nokey".parse::<ConfigOverride>().is_err());
+    assert!("build..path_prefix=x".parse::<ConfigOverride>().is_err());
+}
+
+#[test]
+fn it_reads_overrides_from_the_environment() {
+    let vars = [
+        ("ORANDA_STYLES__THEME", "hacker"),
+        ("ORANDA_BUILD__PATH_PREFIX", "preview/pr-42"),
+        // Not config, so they're left alone
+        ("ORANDA_CSS", "./oranda.css"),
+        ("ORANDA_BUILD__", "x"),
+        ("PATH", "/usr/bin"),
+    ]
+    .map(|(name, value)| (name.to_owned(), value.to_owned()));
+    let overrides = from_env_vars(vars);
+    assert_eq!(
+        overrides,
+        set(&["build.path_prefix=preview/pr-42", "styles.theme=hacker"])
+    );
+
+    // Flags come after the environment, so they win
+    let mut layered = overrides;
+    layered.extend(set(&["build.path_prefix=from-a-flag"]));
+    let layer = to_layer(&layered).unwrap().unwrap();
+    assert_eq!(
+        layer.build.unwrap().path_prefix.as_deref(),
+        Some("from-a-flag")
+    );
+    assert_eq!(layer.styles.unwrap().theme, Some(OrandaTheme::Hacker));
+}
@@ -0,0 +1,60 @@
+use minijinja::context;
+use oranda::config::{AnalyticsConfig, Config, ConsentBannerConfig};
+use oranda::site::layout::javascript::analytics::{Google, Plausible};
+use oranda::site::templates::Templates;
+
+fn render_offline_page(config: &Config) -> String {
+    let templates = Templates::new(config, None).unwrap();
+    templates
+        .render_to_string("offline.html", context!())
+        .unwrap()
+}
+
+fn config(analytics: AnalyticsConfig) -> Config {
+    let mut config = Config::default();
+    config.marketing.analytics = Some(analytics);
+    config.marketing.consent_banner = Some(ConsentBannerConfig {
+        privacy_policy: Some("/privacy/".to_owned()),
+        ..ConsentBannerConfig::default()
+    });
+    config
+}
+
+fn google() -> AnalyticsConfig {
+    AnalyticsConfig::Google(Google {
+        tracking_id: "G-123".to_owned(),
+    })
+}
+
+#[test]
+fn it_waits_for_consent_before_loading_google_analytics() {
+    let html = render_offline_page(&config(google()));
+    let template = html.find(r#"<template id="consent-analytics">"#).unwrap();
+    let script = html.find("googletagmanager.com/gtag/js?id=G-123").unwrap();
+    let end = html.find("</template>").unwrap();
+    assert!(template < script && script < end);
+    assert!(html.contains(r#"<div class="consent-banner""#));
+    assert!(html.contains(r#"<a href="/privacy/">Privacy policy</a>"#));
+    assert!(html.contains(r#"class="consent-settings""#));
+}
+
+#[test]
+fn it_loads_cookieless_analytics_right_away() {
+    let html = render_offline_page(&config(AnalyticsConfig::Plausible(Plausible {
+        domain: "example.com".to_owned(),
+        script_url: None,
+    })));
+    assert!(html.contains(r#"data-domain="example.com""#));
+    assert!(!html.contains("consent-analytics"));
+    assert!(!html.contains("consent-banner"));
+}
+
+#[test]
+fn it_loads_analytics_right_away_without_a_banner() {
+    let mut config = config(google());
+    config.marketing.consent_banner = None;
+    let html = render_offline_page(&config);
+    assert!(html.contains("googletagmanager.com/gtag/js?id=G-123"));
+    assert!(!html.contains("consent-analytics"));
+    assert!(!html.contains("consent-banner"));
+}
@@ -0,0 +1,81 @@
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use camino::Utf8Path;
+use oranda::config::DataPage;
+use oranda::errors::OrandaError;
+use oranda::site::data_pages::context;
+
+fn page(data: &str) -> DataPage {
+    DataPage {
+        data: data.to_owned(),
+        template: None,
+        title: None,
+        nav: None,
+        weight: None,
+    }
+}
+
+#[test]
+fn it_turns_a_list_of_objects_into_a_table() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.child("plugins.toml");
+    file.write_str(
+        "[[plugins]]\nname = \"oranda-rss\"\nstars = 12\n\n[[plugins]]\nname = \"oranda-qr\"\nurl = \"https://example.com\"\n",
+    )
+    .unwrap();
+    // TOML can't have a list at the top, so point at a JSON file for the table
+    let json = dir.child("plugins.json");
+    json.write_str(r#"[{"name": "oranda-rss", "stars": 12}, {"name": "oranda-qr", "url": "https://example.com"}]"#)
+        .unwrap();
+
+    let ctx = context(
+        "plugins",
+        &page(json.path().to_str().unwrap()),
+        Utf8Path::new(""),
+    )
+    .unwrap();
+    assert_eq!(ctx.title, "plugins");
+    assert_eq!(ctx.columns, vec!["name", "stars", "url"]);
+    assert_eq!(
+        ctx.rows,
+        vec![
+            vec!["oranda-rss", "12", ""],
+            vec!["oranda-qr", "", "https://example.com"],
+        ]
+    );
+
+    let ctx = context(
+        "plugins",
+        &page(file.path().to_str().unwrap()),
+        Utf8Path::new(""),
+    )
+    .unwrap();
+    assert!(ctx.columns.is_empty());
+    assert_eq!(ctx.data["plugins"][1]["name"], "oranda-qr");
+}
+
+#[test]
+fn it_reads_yaml_and_rejects_unknown_formats() {
+    let dir = TempDir::new().unwrap();
+    let yaml = dir.child("team.yml");
+    yaml.write_str("- name: Ada\n  role: Maintainer\n").unwrap();
+    let ctx = context(
+        "team",
+        &page(yaml.path().to_str().unwrap()),
+        Utf8Path::new(""),
+    )
+    .unwrap();
+    assert_eq!(ctx.rows, vec![vec!["Ada", "Maintainer"]]);
+
+    let csv = dir.child("team.csv");
+    csv.write_str("name,role\n").unwrap();
+    let result = context(
+        "team",
+        &page(csv.path().to_str().unwrap()),
+        Utf8Path::new(""),
+    );
+    assert!(matches!(
+        result,
+        Err(OrandaError::DataFileLoadFailed { .. })
+    ));
+}
@@ -0,0 +1,24 @@
+use oranda::config::Config;
+use oranda::site::debug::{contexts, PAGES};
+
+#[test]
+fn it_builds_contexts_for_every_page() {
+    let mut config = Config::default();
+    config.project.name = String::from("axolotlsay");
+    config.build.path_prefix = Some("axolotlsay".to_owned());
+    let contexts = contexts(&config).unwrap();
+    assert_eq!(contexts.keys().copied().collect::<Vec<_>>(), PAGES);
+
+    let layout = &contexts["layout"];
+    assert_eq!(layout["project_name"], "axolotlsay");
+    assert_eq!(layout["path_prefix"], "axolotlsay");
+    assert_eq!(layout["home_link"], "/axolotlsay/");
+
+    // Without a repository there's nothing to build artifacts or a changelog from
+    let index = contexts["index"].as_object().unwrap();
+    assert!(index.contains_key("artifacts"));
+    assert!(index["artifacts"].is_null());
+    assert!(contexts["artifacts"].is_null());
+    assert!(contexts["changelog"].is_null());
+    assert!(contexts["workspace_index"].is_null());
+}
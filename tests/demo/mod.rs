@@ -0,0 +1,73 @@
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use camino::Utf8Path;
+use oranda::config::{Config, DemoConfig};
+use oranda::errors::OrandaError;
+use oranda::site::demo::{context, copy_demo};
+use oranda::site::writer::MemoryWriter;
+
+#[test]
+fn it_links_to_the_entry_under_the_prefix() {
+    let mut config = Config::default();
+    config.build.path_prefix = Some("axolotlsay".to_owned());
+    let demo = DemoConfig {
+        path: "./www/dist".to_owned(),
+        entry: "./play.html".to_owned(),
+        ..DemoConfig::default()
+    };
+    let context = context(&demo, &config);
+    assert_eq!(context.url, "/axolotlsay/demo/play.html");
+    assert_eq!(context.title, "Try it out");
+    assert_eq!(context.height, 480);
+}
+
+#[test]
+fn it_copies_the_whole_demo() {
+    let project = TempDir::new().unwrap();
+    project
+        .child("www/index.html")
+        .write_str("<p>hi</p>")
+        .unwrap();
+    project
+        .child("www/pkg/demo_bg.wasm")
+        .write_binary(b"\0asm")
+        .unwrap();
+    let demo = DemoConfig {
+        path: "www".to_owned(),
+        ..DemoConfig::default()
+    };
+
+    let mut writer = MemoryWriter::new();
+    copy_demo(
+        Utf8Path::from_path(project.path()).unwrap(),
+        &demo,
+        &mut writer,
+    )
+    .unwrap();
+    assert_eq!(writer.get("demo/index.html").unwrap(), "<p>hi</p>");
+    assert_eq!(
+        writer.files[Utf8Path::new("demo/pkg/demo_bg.wasm")],
+        b"\0asm"
+    );
+}
+
+#[test]
+fn it_needs_a_path_and_an_entry() {
+    assert!(matches!(
+        DemoConfig::validate(&Some(DemoConfig::default())),
+        Err(OrandaError::DemoConfigInvalid)
+    ));
+    let demo_dir = TempDir::new().unwrap();
+    let demo = DemoConfig {
+        path: demo_dir.path().display().to_string(),
+        ..DemoConfig::default()
+    };
+    assert!(matches!(
+        copy_demo(
+            Utf8Path::from_path(demo_dir.path()).unwrap(),
+            &demo,
+            &mut MemoryWriter::new()
+        ),
+        Err(OrandaError::PathDoesNotExist { .. })
+    ));
+}
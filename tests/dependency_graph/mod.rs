@@ -0,0 +1,172 @@
+use assert_fs::prelude::*;
+use camino::Utf8PathBuf;
+use oranda::config::Config;
+use oranda::data::dependency_graph::{DependencyGraph, DependencyKind};
+use oranda::data::workspaces::WorkspaceData;
+use oranda::site::dependency_graph::{svg, DependencyGraphContext};
+
+/// A workspace with a library, a CLI that uses it, a test helper, and some docs that aren't
+/// a Cargo package at all
+fn workspace(root: &assert_fs::TempDir) -> Vec<WorkspaceData> {
+    root.child("Cargo.toml")
+        .write_str(
+            r#"
+[workspace]
+members = ["core", "cli", "testutil"]
+
+[workspace.package]
+version = "0.3.0"
+
+[workspace.dependencies]
+app-core = { path = "core", version = "0.3.0" }
+"#,
+        )
+        .unwrap();
+    root.child("core/Cargo.toml")
+        .write_str(
+            r#"
+[package]
+name = "app-core"
+version.workspace = true
+
+[dev-dependencies]
+app-testutil = { path = "../testutil" }
+"#,
+        )
+        .unwrap();
+    root.child("cli/Cargo.toml")
+        .write_str(
+            r#"
+[package]
+name = "app"
+version = "1.2.0"
+
+[dependencies]
+app-core.workspace = true
+serde = "1"
+
+[target.'cfg(windows)'.build-dependencies]
+helper = { package = "app-testutil", path = "../testutil", version = "0.1" }
+"#,
+        )
+        .unwrap();
+    root.child("testutil/Cargo.toml")
+        .write_str(
+            r#"
+[package]
+name = "app-testutil"
+version = "0.1.0"
+publish = false
+
+[dependencies]
+app-core = { path = "../core" }
+"#,
+        )
+        .unwrap();
+    root.child("docs/README.md").write_str("# Docs").unwrap();
+
+    let root_path = Utf8PathBuf::from_path_buf(root.path().to_owned()).unwrap();
+    ["core", "cli", "testutil", "docs"]
+        .into_iter()
+        .map(|slug| WorkspaceData {
+            root_path: root_path.clone(),
+            slug: slug.to_owned(),
+            path: root_path.join(slug),
+            config: Config::default(),
+        })
+        .collect()
+}
+
+#[test]
+fn it_reads_dependencies_between_members() {
+    let root = assert_fs::TempDir::new().unwrap();
+    let graph = DependencyGraph::from_members(&workspace(&root)).unwrap();
+
+    let nodes = graph
+        .nodes
+        .iter()
+        .map(|node| {
+            (
+                node.slug.as_str(),
+                node.name.as_str(),
+                node.version.as_deref(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        nodes,
+        vec![
+            ("core", "app-core", Some("0.3.0")),
+            ("cli", "app", Some("1.2.0")),
+            ("testutil", "app-testutil", Some("0.1.0")),
+        ]
+    );
+
+    let edges = graph
+        .edges
+        .iter()
+        .map(|edge| {
+            (
+                graph.nodes[edge.from].name.as_str(),
+                graph.nodes[edge.to].name.as_str(),
+                edge.requirement.as_deref(),
+                edge.kind,
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        edges,
+        vec![
+            ("app-core", "app-testutil", None, DependencyKind::Dev),
+            ("app", "app-core", Some("0.3.0"), DependencyKind::Normal),
+            ("app", "app-testutil", Some("0.1"), DependencyKind::Build),
+            ("app-testutil", "app-core", None, DependencyKind::Normal),
+        ]
+    );
+}
+
+#[test]
+fn it_puts_dependencies_below_their_dependents() {
+    let root = assert_fs::TempDir::new().unwrap();
+    let graph = DependencyGraph::from_members(&workspace(&root)).unwrap();
+    // core's dev-dependency on testutil doesn't count, or they'd go in circles
+    assert_eq!(graph.layers(), vec![0, 2, 1]);
+}
+
+#[test]
+fn it_draws_the_graph() {
+    let root = assert_fs::TempDir::new().unwrap();
+    let graph = DependencyGraph::from_members(&workspace(&root)).unwrap();
+    let mut config = Config::default();
+    config.build.path_prefix = Some("tools".to_owned());
+    let svg = svg(&graph, &config);
+    assert!(svg.starts_with("<svg class=\"dependency-graph\""));
+    assert!(svg.contains("role=\"img\""));
+    assert_eq!(svg.matches("class=\"dependency-graph-edge\"").count(), 4);
+    assert_eq!(svg.matches("class=\"dependency-graph-node\"").count(), 3);
+    assert!(svg.contains("<a class=\"dependency-graph-node\" href=\"/tools/cli\">"));
+    assert!(svg.contains("<title>app depends on app-core 0.3.0</title>"));
+    assert!(svg.contains("<title>app-core depends on app-testutil (dev)</title>"));
+    assert!(svg.contains(">v0.3.0</text>"));
+    assert_eq!(svg.matches("stroke-dasharray").count(), 1);
+}
+
+#[test]
+fn it_skips_workspaces_without_dependencies_between_members() {
+    let root = assert_fs::TempDir::new().unwrap();
+    let members = workspace(&root)
+        .into_iter()
+        .filter(|member| member.slug != "core")
+        .collect::<Vec<_>>();
+    let graph = DependencyGraph::from_members(&members).unwrap();
+    // testutil is still a build-dependency of the CLI
+    assert!(DependencyGraphContext::new(&graph, &Config::default()).is_some());
+
+    let members = workspace(&root)
+        .into_iter()
+        .filter(|member| member.slug == "core" || member.slug == "docs")
+        .collect::<Vec<_>>();
+    let graph = DependencyGraph::from_members(&members).unwrap();
+    assert_eq!(graph.nodes.len(), 1);
+    assert!(DependencyGraphContext::new(&graph, &Config::default()).is_none());
+}
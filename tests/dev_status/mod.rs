@@ -0,0 +1,40 @@
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use camino::Utf8Path;
+use oranda::dev_status::{BuildState, DevStatusHandle};
+use oranda::errors::OrandaError;
+
+#[test]
+fn it_reports_failed_builds_and_keeps_the_last_pages() {
+    let dist = TempDir::new().unwrap();
+    dist.child("index.html").write_str("").unwrap();
+    dist.child("docs/install/index.html").write_str("").unwrap();
+    dist.child("oranda.css").write_str("").unwrap();
+    let dist_dir = Utf8Path::from_path(dist.path()).unwrap();
+
+    let status = DevStatusHandle::new();
+    assert_eq!(status.get().state, BuildState::Idle);
+    status.build_started(vec![]);
+    assert_eq!(status.get().state, BuildState::Building);
+    status.build_finished(&Ok(()), dist_dir);
+    let ok = status.get();
+    assert_eq!(ok.state, BuildState::Ok);
+    assert_eq!(ok.pages, ["docs/install/index.html", "index.html"]);
+
+    status.build_started(vec!["oranda.json".to_owned()]);
+    status.build_finished(
+        &Err(OrandaError::DocsConfigInvalid),
+        Utf8Path::new("does-not-exist"),
+    );
+    let failed = status.get();
+    assert_eq!(failed.state, BuildState::Failed);
+    assert_eq!(failed.builds, 2);
+    assert_eq!(failed.changed_paths, ["oranda.json"]);
+    assert_eq!(failed.pages, ok.pages);
+    let error = failed.error.unwrap();
+    assert_eq!(
+        error["message"],
+        "components.docs needs a `command` and an `output_dir`"
+    );
+    assert!(error["help"].as_str().unwrap().contains("output_dir"));
+}
@@ -0,0 +1,46 @@
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use camino::Utf8PathBuf;
+use oranda::config::{Config, ConfigOptions};
+use oranda::diagnostics::short_message;
+use oranda::errors::OrandaError;
+
+#[test]
+fn it_points_at_the_config_field_that_failed_to_parse() {
+    let dir = TempDir::new().unwrap();
+    let config = dir.child("oranda.json");
+    config
+        .write_str("{\n  \"build\": { \"dist_dir\": 5 }\n}\n")
+        .unwrap();
+    let config_path = Utf8PathBuf::from_path_buf(config.path().to_owned()).unwrap();
+
+    let error = Config::build(&config_path, &ConfigOptions::default()).unwrap_err();
+    let message = short_message(&error);
+    assert!(
+        message.starts_with(&format!("{config_path}:2:26: error: ")),
+        "{message}"
+    );
+    assert!(!message.contains('\n'));
+}
+
+#[test]
+fn it_points_at_the_template_line() {
+    let error = OrandaError::TemplateLoadFailed {
+        path: "templates/index.html.j2".to_owned(),
+        line: Some(3),
+        details: "unexpected end of input".to_owned(),
+    };
+    assert_eq!(
+        short_message(&error),
+        "templates/index.html.j2:3: error: Failed to load your template `templates/index.html.j2`: unexpected end of input"
+    );
+}
+
+#[test]
+fn it_attributes_errors_without_a_file_to_oranda() {
+    let message = short_message(&OrandaError::DocsConfigInvalid);
+    assert!(
+        message.starts_with("oranda: error: components.docs needs"),
+        "{message}"
+    );
+}
@@ -0,0 +1,59 @@
+use oranda::config::{Distro, DistroPackage};
+use oranda::site::artifacts::install_command;
+
+fn package(name: &str) -> DistroPackage {
+    DistroPackage {
+        package: name.to_owned(),
+        repository: None,
+        key: None,
+        command: None,
+        logo: None,
+    }
+}
+
+#[test]
+fn it_sets_up_apt_repositories_before_installing() {
+    let package = DistroPackage {
+        repository: Some(
+            "deb [signed-by=/usr/share/keyrings/app.gpg] https://apt.example.com stable main"
+                .to_owned(),
+        ),
+        key: Some("https://apt.example.com/key.gpg".to_owned()),
+        ..package("app")
+    };
+    assert_eq!(
+        install_command(Distro::Debian, &package),
+        "curl -fsSL https://apt.example.com/key.gpg | sudo gpg --dearmor -o /usr/share/keyrings/app.gpg
+echo \"deb [signed-by=/usr/share/keyrings/app.gpg] https://apt.example.com stable main\" | sudo tee /etc/apt/sources.list.d/app.list
+sudo apt update
+sudo apt install app"
+    );
+}
+
+#[test]
+fn it_installs_from_each_distro() {
+    let copr = DistroPackage {
+        repository: Some("me/app".to_owned()),
+        ..package("app")
+    };
+    assert_eq!(
+        install_command(Distro::Fedora, &copr),
+        "sudo dnf copr enable me/app\nsudo dnf install app"
+    );
+    assert_eq!(
+        install_command(Distro::Ubuntu, &package("app")),
+        "sudo apt install app"
+    );
+    assert_eq!(
+        install_command(Distro::Arch, &package("app")),
+        "sudo pacman -S app"
+    );
+    assert_eq!(
+        install_command(Distro::Aur, &package("app-bin")),
+        "yay -S app-bin"
+    );
+    assert_eq!(
+        install_command(Distro::Nix, &package("app")),
+        "nix profile install nixpkgs#app"
+    );
+}
@@ -0,0 +1,47 @@
+use oranda::config::{DockerConfig, DockerRegistry};
+use oranda::errors::OrandaError;
+use oranda::site::artifacts::docker_context;
+
+#[test]
+fn it_uses_the_first_registry_and_tag_for_the_commands() {
+    let config = DockerConfig {
+        image: "axodotdev/oranda".to_owned(),
+        registries: vec![DockerRegistry::Ghcr, DockerRegistry::DockerHub],
+        tags: vec!["0.6".to_owned(), "latest".to_owned()],
+        run_args: "--rm -v $(pwd):/site".to_owned(),
+        ..DockerConfig::default()
+    };
+    let context = serde_json::to_value(docker_context(&config)).unwrap();
+    assert_eq!(context["pull"], "docker pull ghcr.io/axodotdev/oranda:0.6");
+    assert_eq!(
+        context["run"],
+        "docker run --rm -v $(pwd):/site ghcr.io/axodotdev/oranda:0.6"
+    );
+    assert_eq!(context["tags"], serde_json::json!(["0.6", "latest"]));
+    assert_eq!(
+        context["registries"][1]["url"],
+        "https://hub.docker.com/r/axodotdev/oranda"
+    );
+    assert_eq!(context["registries"][1]["reference"], "axodotdev/oranda");
+}
+
+#[test]
+fn it_links_to_official_images() {
+    assert_eq!(
+        DockerRegistry::DockerHub.url("nginx"),
+        "https://hub.docker.com/_/nginx"
+    );
+    assert_eq!(
+        DockerRegistry::Quay.reference("coreos/etcd"),
+        "quay.io/coreos/etcd"
+    );
+}
+
+#[test]
+fn it_needs_an_image() {
+    let config = Some(DockerConfig::default());
+    assert!(matches!(
+        DockerConfig::validate(&config),
+        Err(OrandaError::DockerConfigInvalid)
+    ));
+}
@@ -0,0 +1,69 @@
+use assert_fs::prelude::*;
+use camino::{Utf8Path, Utf8PathBuf};
+use oranda::config::{Config, DocsConfig};
+use oranda::errors::OrandaError;
+use oranda::site::docs::build_docs;
+use oranda::site::templates::Templates;
+use oranda::site::writer::MemoryWriter;
+
+// These commands stick to what both `sh` and `cmd` understand
+fn docs_config(command: &str) -> DocsConfig {
+    DocsConfig {
+        command: command.to_owned(),
+        output_dir: String::from("site"),
+        path: String::from("docs"),
+        ..Default::default()
+    }
+}
+
+fn project() -> (assert_fs::TempDir, Utf8PathBuf) {
+    let project = assert_fs::TempDir::new().unwrap();
+    project.child("docs/README.md").write_str("# Docs").unwrap();
+    let root = Utf8PathBuf::from_path_buf(project.path().to_owned()).unwrap();
+    (project, root)
+}
+
+#[test]
+fn it_copies_the_built_docs_and_links_them() {
+    let (_project, root) = project();
+    let docs_cfg = docs_config("mkdir site && cd site && echo built> index.html");
+    let mut writer = MemoryWriter::new();
+    build_docs(&root, &docs_cfg, &mut writer).unwrap();
+    let index = writer.files.get(Utf8Path::new("docs/index.html")).unwrap();
+    assert_eq!(String::from_utf8_lossy(index).trim(), "built");
+    assert_eq!(writer.files.len(), 1);
+
+    let mut config = Config::default();
+    config.build.path_prefix = Some(String::from("axolotlsay"));
+    config.components.mdbook = None;
+    config.components.docs = Some(docs_cfg);
+    let templates = Templates::new(&config, None).unwrap();
+    let nav = templates
+        .render_to_string(
+            "includes/nav.html",
+            minijinja::context!(layout => &templates.layout),
+        )
+        .unwrap();
+    assert!(nav.contains(r#"<li><a href="/axolotlsay/docs/">Docs</a></li>"#));
+    assert_eq!(nav.matches(">Docs<").count(), 1);
+}
+
+#[test]
+fn it_reports_failed_docs_builds() {
+    let (_project, root) = project();
+    let mut writer = MemoryWriter::new();
+
+    let err = build_docs(&root, &docs_config("echo boom 1>&2 && exit 3"), &mut writer);
+    match err {
+        Err(OrandaError::DocsBuildFailed { command, details }) => {
+            assert_eq!(command, "echo boom 1>&2 && exit 3");
+            assert_eq!(details, "boom");
+        }
+        other => panic!("expected DocsBuildFailed, got {other:?}"),
+    }
+
+    // The command succeeding isn't enough, it has to build something
+    let err = build_docs(&root, &docs_config("echo done"), &mut writer);
+    assert!(matches!(err, Err(OrandaError::PathDoesNotExist { .. })));
+    assert!(writer.files.is_empty());
+}
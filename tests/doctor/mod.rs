@@ -0,0 +1,89 @@
+use assert_fs::prelude::*;
+use camino::Utf8PathBuf;
+use oranda::config::{Config, ConfigOptions, FundingConfig, MdBookConfig};
+use oranda::doctor::{check_config, dist_manifest, local_checks, Finding, Status};
+
+fn path(dir: &assert_fs::TempDir, name: &str) -> String {
+    Utf8PathBuf::from_path_buf(dir.path().join(name))
+        .unwrap()
+        .into_string()
+}
+
+fn status(findings: &[Finding], check: &str) -> Option<Status> {
+    findings
+        .iter()
+        .find(|finding| finding.check == check)
+        .map(|finding| finding.status)
+}
+
+fn config(dir: &assert_fs::TempDir) -> Config {
+    let mut config = Config::default();
+    config.project.readme_path = path(dir, "README.md");
+    config.components.funding = Some(FundingConfig {
+        yml_path: Some(path(dir, "FUNDING.yml")),
+        ..FundingConfig::default()
+    });
+    config.components.mdbook = Some(MdBookConfig {
+        path: Some(path(dir, "docs")),
+        ..MdBookConfig::default()
+    });
+    config
+}
+
+#[test]
+fn it_finds_problems_with_files() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    dir.child("FUNDING.yml").write_str("github: [").unwrap();
+    dir.child("docs/SUMMARY.md").write_str("# Summary").unwrap();
+    let findings = local_checks(&config(&dir));
+    assert_eq!(status(&findings, "readme"), Some(Status::Error));
+    assert_eq!(status(&findings, "funding"), Some(Status::Error));
+    assert_eq!(status(&findings, "mdbook"), Some(Status::Error));
+    assert_eq!(status(&findings, "repository"), Some(Status::Warning));
+    // There's nothing to fetch from GitHub without a repository
+    assert_eq!(status(&findings, "github-token"), None);
+    assert!(findings
+        .iter()
+        .filter(|finding| finding.status != Status::Ok)
+        .all(|finding| finding.help.is_some() && finding.to_error().is_some()));
+}
+
+#[test]
+fn it_passes_a_healthy_project() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    dir.child("README.md").write_str("# axolotlsay").unwrap();
+    dir.child("FUNDING.yml")
+        .write_str("github: axodotdev")
+        .unwrap();
+    dir.child("docs/book.toml").write_str("[book]").unwrap();
+    let mut config = config(&dir);
+    config.project.repository = Some("https://github.com/axodotdev/axolotlsay".to_owned());
+    let findings = local_checks(&config);
+    for check in ["readme", "funding", "mdbook", "repository"] {
+        assert_eq!(status(&findings, check), Some(Status::Ok), "{check}");
+    }
+    assert!(status(&findings, "github-token").is_some());
+}
+
+#[test]
+fn it_reports_broken_config() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    dir.child("oranda.json")
+        .write_str(r#"{ "build": { "dist_dir": 5 } }"#)
+        .unwrap();
+    let (config, findings) = check_config(
+        &Utf8PathBuf::from(path(&dir, "oranda.json")),
+        &ConfigOptions::default(),
+    );
+    assert!(config.is_none());
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].check, "config");
+    assert_eq!(findings[0].status, Status::Error);
+}
+
+#[test]
+fn it_reports_unreadable_dist_manifests() {
+    let finding = dist_manifest(r#"{ "releases": "all of them" }"#, "v1.0.0");
+    assert_eq!(finding.status, Status::Error);
+    assert!(finding.message.contains("v1.0.0"));
+}
@@ -0,0 +1,90 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+
+use oranda::data::artifacts::{File, ReleaseArtifacts};
+
+use crate::utils::tokio_utils::TEST_RUNTIME;
+
+/// Answers every request with the status listed for its path
+fn serve(statuses: Vec<(&'static str, &'static str)>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut lines = BufReader::new(&stream).lines().map(|l| l.unwrap());
+            let request = lines.next().unwrap_or_default();
+            for line in lines {
+                if line.is_empty() {
+                    break;
+                }
+            }
+            let path = request.split(' ').nth(1).unwrap_or_default();
+            let status = statuses
+                .iter()
+                .find(|(p, _)| *p == path)
+                .map_or("200 OK", |(_, status)| status);
+            let response =
+                format!("HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    url
+}
+
+fn file(name: &str, download_url: String) -> File {
+    File {
+        name: name.to_owned(),
+        download_url,
+        view_path: None,
+        checksum_file: None,
+        unavailable: false,
+        download_count: None,
+        attestations: Default::default(),
+        infer: true,
+    }
+}
+
+#[test]
+fn it_only_hides_files_that_are_definitely_gone() {
+    let url = serve(vec![
+        ("/missing.tar.gz", "404 Not Found"),
+        ("/gone.tar.gz", "410 Gone"),
+        ("/broken.tar.gz", "500 Internal Server Error"),
+        ("/private.tar.gz", "403 Forbidden"),
+    ]);
+    // Nothing's listening here anymore, so the request fails outright
+    let closed = {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    };
+    let mut artifacts = ReleaseArtifacts::new(None);
+    for name in [
+        "app.tar.gz",
+        "missing.tar.gz",
+        "gone.tar.gz",
+        "broken.tar.gz",
+        "private.tar.gz",
+    ] {
+        artifacts.add_file(file(name, format!("{url}/{name}")));
+    }
+    artifacts.add_file(file("offline.tar.gz", format!("{closed}/offline.tar.gz")));
+
+    TEST_RUNTIME.block_on(artifacts.check_downloads());
+
+    let files = artifacts
+        .files()
+        .map(|f| (f.name.as_str(), f.unavailable, f.infer))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        files,
+        [
+            ("app.tar.gz", false, true),
+            ("missing.tar.gz", true, false),
+            ("gone.tar.gz", true, false),
+            ("broken.tar.gz", false, true),
+            ("private.tar.gz", false, true),
+            ("offline.tar.gz", false, true),
+        ]
+    );
+}
@@ -0,0 +1,35 @@
+use oranda::data::artifacts::ReleaseArtifacts;
+use oranda::site::artifacts::format_count;
+
+use crate::utils::github::release_with_assets;
+
+#[test]
+fn it_adds_up_github_download_counts() {
+    let release = release_with_assets(
+        "v1.0.0",
+        &[
+            ("app-x86_64-unknown-linux-gnu.tar.gz", 1200),
+            ("app-x86_64-pc-windows-msvc.zip", 34),
+        ],
+    );
+    let mut artifacts = ReleaseArtifacts::new(None);
+    artifacts.add_github(&release);
+
+    let counts = artifacts
+        .files()
+        .map(|file| file.download_count)
+        .collect::<Vec<_>>();
+    assert_eq!(counts, vec![Some(1200), Some(34)]);
+    assert_eq!(artifacts.download_count(), Some(1234));
+    assert_eq!(ReleaseArtifacts::new(None).download_count(), None);
+}
+
+#[test]
+fn it_shortens_counts_without_rounding_up() {
+    assert_eq!(format_count(999), "999");
+    assert_eq!(format_count(1_000), "1k");
+    assert_eq!(format_count(12_345), "12.3k");
+    assert_eq!(format_count(99_999), "99.9k");
+    assert_eq!(format_count(123_456), "123k");
+    assert_eq!(format_count(4_560_000), "4.5M");
+}
@@ -0,0 +1,87 @@
+use assert_fs::prelude::*;
+use camino::Utf8Path;
+use oranda::config::Config;
+use oranda::export::announcement::{convert, AnnouncementFormat};
+use oranda::export::email::{absolute_links, inline_styles};
+use oranda::export::pdf::{browser_args, page_filename, page_url};
+
+#[test]
+fn it_inlines_styles_into_release_notes() {
+    let html = "<h2>Fixes</h2>\n<p>See <a href=\"https://example.com\">#12</a> &lt;3</p>\n<pre style=\"background-color:#263238;\"><span>fn</span></pre>";
+    let inlined = inline_styles(html);
+    assert!(inlined.starts_with("<h2 style=\"margin:24px 0 8px;"));
+    assert!(inlined.contains("<a style=\"color:#2563eb;text-decoration:underline;\" href=\"https://example.com\">#12</a> &lt;3</p>"));
+    // Syntax highlighted code is already styled
+    assert!(inlined.contains("<pre style=\"background-color:#263238;\"><span>fn</span></pre>"));
+    assert_eq!(inlined.matches("</p>").count(), 1);
+}
+
+#[test]
+fn it_makes_links_in_emails_absolute() {
+    let html = "<p>See <a href=\"../docs/setup/\">setup</a> and <a href=\"https://example.com\">this</a></p>\n<img src=\"/static/demo.png\" alt=\"demo\">";
+    let mut config = Config::default();
+    config.build.base_url = Some(String::from("https://oranda.example/"));
+    assert_eq!(
+        absolute_links(html, &config),
+        "<p>See <a href=\"https://oranda.example/docs/setup/\">setup</a> and <a href=\"https://example.com\">this</a></p>\n<img src=\"https://oranda.example/static/demo.png\" alt=\"demo\">"
+    );
+    // Without a URL for the site, there's nothing to resolve them against
+    assert_eq!(absolute_links(html, &Config::default()), html);
+}
+
+#[test]
+fn it_converts_release_notes_for_each_platform() {
+    let notes = "## Fixes\n\n- Handle **empty** files ([#12](https://github.com/x/y/pull/12))\n- Don't crash on `a<b`\n\n<!-- generated -->\n";
+
+    let discord = convert(notes, AnnouncementFormat::Discord).join("\n");
+    assert_eq!(discord, "## Fixes\n\n- Handle **empty** files ([#12](<https://github.com/x/y/pull/12>))\n- Don't crash on `a<b`");
+
+    let slack = convert(notes, AnnouncementFormat::Slack).join("\n");
+    assert_eq!(slack, "*Fixes*\n\n• Handle *empty* files (<https://github.com/x/y/pull/12|#12>)\n• Don't crash on `a&lt;b`");
+
+    let mastodon = convert(notes, AnnouncementFormat::Mastodon).join("\n");
+    assert_eq!(mastodon, "Fixes\n\n- Handle empty files (#12 (https://github.com/x/y/pull/12))\n- Don't crash on a<b");
+}
+
+#[test]
+fn it_counts_links_like_mastodon_does() {
+    let url = format!("https://example.com/{}", "a".repeat(100));
+    let text = format!("New release: {url}");
+    assert_eq!(AnnouncementFormat::Mastodon.length(&text), 13 + 23);
+    assert_eq!(AnnouncementFormat::Discord.length(&text), text.len());
+}
+
+#[test]
+fn it_finds_pages_to_print_like_links() {
+    let dist = assert_fs::TempDir::new().unwrap();
+    dist.child("docs/index.html").touch().unwrap();
+    let dist_dir = Utf8Path::from_path(dist.path()).unwrap();
+    assert_eq!(page_filename(dist_dir, "changelog"), "changelog.html");
+    assert_eq!(page_filename(dist_dir, "/changelog/"), "changelog.html");
+    assert_eq!(
+        page_filename(dist_dir, "docs/setup.html"),
+        "docs/setup.html"
+    );
+    assert_eq!(page_filename(dist_dir, "docs"), "docs/index.html");
+    assert_eq!(page_filename(dist_dir, "/"), "index.html");
+}
+
+#[test]
+fn it_prints_pages_from_the_local_server() {
+    let addr = "127.0.0.1:4321".parse().unwrap();
+    assert_eq!(
+        page_url(addr, &None, "changelog.html"),
+        "http://127.0.0.1:4321/changelog.html"
+    );
+    assert_eq!(
+        page_url(addr, &Some("/oranda/".to_owned()), "changelog.html"),
+        "http://127.0.0.1:4321/oranda/changelog.html"
+    );
+    let args = browser_args(
+        "http://127.0.0.1:4321/changelog.html",
+        Utf8Path::new("/tmp/changelog.pdf"),
+    );
+    assert!(args.contains(&"--headless".to_owned()));
+    assert!(args.contains(&"--print-to-pdf=/tmp/changelog.pdf".to_owned()));
+    assert_eq!(args.last().unwrap(), "http://127.0.0.1:4321/changelog.html");
+}
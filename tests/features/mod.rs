@@ -0,0 +1,159 @@
+use assert_fs::prelude::*;
+use camino::Utf8Path;
+use oranda::config::{Config, FeaturesConfig};
+use oranda::data::features::{self, CrateFeatures, FeatureEnables};
+use oranda::site::features::FeaturesContext;
+use oranda::site::page::Page;
+use oranda::site::templates::Templates;
+
+const MANIFEST: &str = r#"
+[package]
+name = "app"
+version = "1.0.0"
+
+[features]
+default = ["std", "serde?/derive"]
+#! ### Everyday features
+
+## Use the standard library.
+##
+## Turn this off for `no_std` targets.
+std = ["alloc"]
+alloc = []
+json = ["dep:serde_json", "serde/derive"]
+_internal = []
+test-helpers = []
+
+[dependencies]
+serde = { version = "1", optional = true }
+serde_json = { version = "1", optional = true }
+log = "0.4"
+
+[target.'cfg(unix)'.dependencies]
+nix = { version = "0.27", optional = true }
+"#;
+
+const LIB_RS: &str = r#"//! An app.
+//!
+//! ## Features
+//!
+//! * `alloc`: Use the `alloc` crate,
+//!   without the rest of `std`
+//! - **`json`** — Read and write JSON
+//! - `std`: This isn't used, Cargo.toml says it better
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// - `nix`: Not crate docs
+pub fn app() {}
+"#;
+
+fn crate_features(hidden: &[&str]) -> CrateFeatures {
+    let root = assert_fs::TempDir::new().unwrap();
+    root.child("Cargo.toml").write_str(MANIFEST).unwrap();
+    root.child("src/lib.rs").write_str(LIB_RS).unwrap();
+    let config = FeaturesConfig {
+        manifest_path: root.child("Cargo.toml").to_string_lossy().into_owned(),
+        hidden: hidden.iter().map(|name| name.to_string()).collect(),
+    };
+    features::load(&config, Utf8Path::new("")).unwrap()
+}
+
+#[test]
+fn it_reads_features_in_order() {
+    let features = crate_features(&[]);
+    let names = features
+        .features
+        .iter()
+        .map(|feature| (feature.name.as_str(), feature.implicit))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        vec![
+            ("std", false),
+            ("alloc", false),
+            ("json", false),
+            ("test-helpers", false),
+            ("serde", true),
+            ("nix", true),
+        ]
+    );
+    assert_eq!(
+        features.features[2].enables,
+        vec![
+            FeatureEnables::Dependency {
+                name: "serde_json".to_owned()
+            },
+            FeatureEnables::DependencyFeature {
+                dependency: "serde".to_owned(),
+                feature: "derive".to_owned(),
+                weak: false,
+            },
+        ]
+    );
+}
+
+#[test]
+fn it_follows_what_default_turns_on() {
+    let features = crate_features(&[]);
+    let defaults = features
+        .features
+        .iter()
+        .filter(|feature| feature.default)
+        .map(|feature| feature.name.as_str())
+        .collect::<Vec<_>>();
+    // `serde?/derive` is weak, so it doesn't turn serde on by itself
+    assert_eq!(defaults, vec!["std", "alloc"]);
+}
+
+#[test]
+fn it_reads_descriptions() {
+    let features = crate_features(&[]);
+    let description = |name: &str| {
+        features
+            .features
+            .iter()
+            .find(|feature| feature.name == name)
+            .unwrap()
+            .description
+            .as_deref()
+    };
+    assert_eq!(
+        description("std"),
+        Some("Use the standard library.\n\nTurn this off for `no_std` targets.")
+    );
+    assert_eq!(
+        description("alloc"),
+        Some("Use the `alloc` crate, without the rest of `std`")
+    );
+    assert_eq!(description("json"), Some("Read and write JSON"));
+    assert_eq!(description("test-helpers"), None);
+    assert_eq!(description("nix"), None);
+}
+
+#[test]
+fn it_hides_features() {
+    let features = crate_features(&["test-helpers", "nix"]);
+    let names = features
+        .features
+        .iter()
+        .map(|feature| feature.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["std", "alloc", "json", "serde"]);
+}
+
+#[test]
+fn it_renders_the_page() {
+    let config = Config::default();
+    let context = FeaturesContext::new(&crate_features(&[]), &config).unwrap();
+    assert_eq!(context.default, vec!["std", "serde?/derive"]);
+    let templates = Templates::new(&config, None).unwrap();
+    let page = Page::new_from_template("features.html", &templates, "features.html", &context)
+        .unwrap()
+        .contents;
+    assert!(page.contains("<h1>Feature flags</h1>"));
+    assert!(page.contains(r#"<tr id="feature-json">"#));
+    assert!(page.contains("<code>dep:serde_json</code>"));
+    assert!(page.contains("<code>no_std</code>"));
+    assert_eq!(page.matches(r#"title="Enabled by default""#).count(), 2);
+    assert!(page.contains("Enables the optional dependency of the same name."));
+}
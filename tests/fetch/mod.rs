@@ -0,0 +1,107 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+
+use oranda::data::fetch::{fetch_json, is_allowed};
+use oranda::errors::OrandaError;
+
+use super::utils::tokio_utils::TEST_RUNTIME;
+
+/// Answers each request with the next of `responses`, and returns the server's base URL
+fn serve(responses: Vec<String>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for (stream, response) in listener.incoming().zip(responses) {
+            let mut stream = stream.unwrap();
+            for line in BufReader::new(&stream).lines() {
+                if line.unwrap().is_empty() {
+                    break;
+                }
+            }
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    url
+}
+
+fn redirect(location: &str) -> String {
+    format!("HTTP/1.1 302 Found\r\nLocation: {location}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+}
+
+fn json(body: &str) -> String {
+    format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+#[test]
+fn it_compares_hosts_exactly() {
+    let allowlist = vec!["https://api.example.com".to_owned()];
+    assert!(is_allowed("https://api.example.com/stats.json", &allowlist));
+    assert!(!is_allowed(
+        "https://api.example.com.evil.tld/stats.json",
+        &allowlist
+    ));
+    assert!(!is_allowed(
+        "https://api.example.com@evil.tld/stats.json",
+        &allowlist
+    ));
+    assert!(!is_allowed("http://api.example.com/stats.json", &allowlist));
+    assert!(!is_allowed(
+        "https://api.example.com:8443/stats.json",
+        &allowlist
+    ));
+    assert!(is_allowed(
+        "https://api.example.com:443/stats.json",
+        &allowlist
+    ));
+}
+
+#[test]
+fn it_compares_whole_path_segments() {
+    let allowlist = vec!["https://api.example.com/stats".to_owned()];
+    assert!(is_allowed("https://api.example.com/stats", &allowlist));
+    assert!(is_allowed("https://api.example.com/stats/", &allowlist));
+    assert!(is_allowed(
+        "https://api.example.com/stats/downloads.json",
+        &allowlist
+    ));
+    assert!(!is_allowed(
+        "https://api.example.com/stats-private.json",
+        &allowlist
+    ));
+    assert!(!is_allowed(
+        "https://api.example.com/stats/../admin.json",
+        &allowlist
+    ));
+    assert!(!is_allowed(
+        "https://api.example.com/stats/%2e%2e/admin.json",
+        &allowlist
+    ));
+    assert!(!is_allowed("https://api.example.com/", &allowlist));
+    assert!(!is_allowed("not a url", &allowlist));
+    assert!(!is_allowed("file:///etc/passwd", &["file:///".to_owned()]));
+}
+
+#[test]
+fn it_follows_redirects_within_the_allowlist() {
+    let base = serve(vec![
+        redirect("/stats/v2.json"),
+        json(r#"{"downloads": 42}"#),
+    ]);
+    let allowlist = vec![format!("{base}/stats/")];
+    let _guard = TEST_RUNTIME.enter();
+    let value = fetch_json(&format!("{base}/stats/v1.json"), &allowlist).unwrap();
+    assert_eq!(value["downloads"], 42);
+}
+
+#[test]
+fn it_refuses_redirects_out_of_the_allowlist() {
+    let elsewhere = serve(vec![json(r#"{"secret": true}"#)]);
+    let base = serve(vec![redirect(&format!("{elsewhere}/secret.json"))]);
+    let allowlist = vec![format!("{base}/stats/")];
+    let _guard = TEST_RUNTIME.enter();
+    let result = fetch_json(&format!("{base}/stats/moved.json"), &allowlist);
+    assert!(matches!(result, Err(OrandaError::FetchJsonFailed { .. })));
+}
@@ -0,0 +1,96 @@
+use std::process::Command;
+
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use camino::Utf8Path;
+use oranda::data::git::{file_history, start_build};
+
+fn git(dir: &TempDir, args: &[&str]) {
+    let status = Command::new("git")
+        .args(args)
+        .current_dir(dir.path())
+        .env("GIT_AUTHOR_DATE", "2023-05-01T12:00:00Z")
+        .env("GIT_COMMITTER_DATE", "2023-05-01T12:00:00Z")
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {args:?} failed");
+}
+
+fn commit(dir: &TempDir, name: &str, email: &str, message: &str) {
+    git(
+        dir,
+        &[
+            "-c",
+            &format!("user.name={name}"),
+            "-c",
+            &format!("user.email={email}"),
+            "commit",
+            "--quiet",
+            "--all",
+            "--message",
+            message,
+        ],
+    );
+}
+
+fn repo() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    git(&dir, &["init", "--quiet"]);
+    dir.child("README.md").write_str("# Hello\n").unwrap();
+    dir.child("docs/guide.md").write_str("# Guide\n").unwrap();
+    git(&dir, &["add", "."]);
+    commit(&dir, "Ada Lovelace", "ada@example.com", "Add pages");
+    dir
+}
+
+#[test]
+fn it_reads_the_history_of_a_page() {
+    let dir = repo();
+    let root = Utf8Path::from_path(dir.path()).unwrap();
+    dir.child("docs/guide.md")
+        .write_str("# Guide\n\nMore.\n")
+        .unwrap();
+    commit(
+        &dir,
+        "Grace Hopper",
+        "12345+grace@users.noreply.github.com",
+        "Expand the guide",
+    );
+
+    let history = file_history(root, "docs/guide.md").unwrap().unwrap();
+    assert_eq!(history.last_author, "Grace Hopper");
+    assert_eq!(history.last_updated, "May  1 2023");
+    let contributors = history
+        .contributors
+        .iter()
+        .map(|c| (c.name.as_str(), c.initials.as_str(), c.avatar_url.is_some()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        contributors,
+        vec![("Grace Hopper", "GH", true), ("Ada Lovelace", "AL", false)]
+    );
+
+    let readme = file_history(root, "README.md").unwrap().unwrap();
+    assert_eq!(readme.last_author, "Ada Lovelace");
+    assert!(file_history(root, "never-committed.md").unwrap().is_none());
+}
+
+#[test]
+fn it_picks_up_new_commits_in_the_next_build() {
+    let dir = repo();
+    let root = Utf8Path::from_path(dir.path()).unwrap();
+    start_build(root);
+    let history = file_history(root, "README.md").unwrap().unwrap();
+    assert_eq!(history.last_author, "Ada Lovelace");
+
+    dir.child("README.md").write_str("# Hello!\n").unwrap();
+    commit(&dir, "Grace Hopper", "grace@example.com", "Excitement");
+    // Within a build, HEAD is only resolved once
+    let history = file_history(root, "README.md").unwrap().unwrap();
+    assert_eq!(history.last_author, "Ada Lovelace");
+
+    start_build(root);
+    let history = file_history(root, "README.md").unwrap().unwrap();
+    assert_eq!(history.last_author, "Grace Hopper");
+    assert_eq!(history.contributors.len(), 2);
+}
@@ -0,0 +1,66 @@
+use minijinja::context;
+use oranda::config::{Config, FooterColumn, SocialPlatform};
+use oranda::site::layout::footer::expand_copyright;
+use oranda::site::templates::Templates;
+
+fn render_offline_page(config: &Config) -> String {
+    let templates = Templates::new(config, None).unwrap();
+    templates
+        .render_to_string("offline.html", context!())
+        .unwrap()
+}
+
+#[test]
+fn it_fills_in_the_year() {
+    assert_eq!(
+        expand_copyright("© 2023-{year} Axo Developer Co.", 2026),
+        "© 2023-2026 Axo Developer Co."
+    );
+    assert_eq!(expand_copyright("© Axo", 2026), "© Axo");
+}
+
+#[test]
+fn it_renders_columns_and_social_links() {
+    let mut config = Config::default();
+    config.build.path_prefix = Some("axolotlsay".to_owned());
+    config.marketing.footer.columns = vec![FooterColumn {
+        title: "Docs".to_owned(),
+        links: [
+            ("Install".to_owned(), "/install".to_owned()),
+            (
+                "Crate".to_owned(),
+                "https://crates.io/crates/axolotlsay".to_owned(),
+            ),
+        ]
+        .into_iter()
+        .collect(),
+    }];
+    config.marketing.footer.copyright = Some("© Axo".to_owned());
+    config.marketing.footer.social.insert(
+        SocialPlatform::Mastodon,
+        "https://mastodon.social/@axodotdev".to_owned(),
+    );
+    config
+        .marketing
+        .footer
+        .social
+        .insert(SocialPlatform::Discord, "https://discord.gg/axo".to_owned());
+    let html = render_offline_page(&config);
+    assert!(html.contains("<h2>Docs</h2>"));
+    assert!(html.contains(r#"<a href="/axolotlsay/install">Install</a>"#));
+    assert!(html.contains(r#"<a href="https://crates.io/crates/axolotlsay">Crate</a>"#));
+    assert!(html.contains("&middot; © Axo"));
+    assert!(html.contains(r#"aria-label="Mastodon" class="social-icon" rel="me""#));
+    assert!(html.contains(r#"aria-label="Discord" class="social-icon">"#));
+    assert!(html.find("Mastodon").unwrap() < html.find("Discord").unwrap());
+    assert!(html.contains("Built with <a"));
+}
+
+#[test]
+fn it_can_leave_out_oranda() {
+    let mut config = Config::default();
+    config.marketing.footer.built_with_oranda = false;
+    let html = render_offline_page(&config);
+    assert!(!html.contains("Built with"));
+    assert!(!html.contains("footer-columns"));
+}
@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+
+use oranda::config::{FundingConfig, FundingLink};
+use oranda::data::funding::{Funding, FundingContent, FundingType};
+use oranda::errors::OrandaError;
+use oranda::site::funding;
+
+fn link(label: &str, icon: Option<&str>) -> FundingLink {
+    FundingLink {
+        label: label.to_owned(),
+        url: format!("https://example.com/{}", label.to_lowercase()),
+        icon: icon.map(|icon| icon.to_owned()),
+    }
+}
+
+fn funding() -> Funding {
+    Funding {
+        content: HashMap::from([
+            (
+                FundingType::Github,
+                FundingContent::One("axodotdev".to_owned()),
+            ),
+            (FundingType::Patreon, FundingContent::One("axo".to_owned())),
+            (FundingType::KoFi, FundingContent::One("axo".to_owned())),
+        ]),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn it_orders_funding_links() {
+    let config = FundingConfig {
+        preferred_funding: Some(FundingType::Patreon),
+        order: vec![FundingType::KoFi, FundingType::Custom],
+        links: vec![link("Stripe", Some("stripe")), link("Shop", None)],
+        ..FundingConfig::default()
+    };
+    let context = funding::context(&config, &funding()).unwrap();
+    let titles = context.methods().map(|m| m.title()).collect::<Vec<_>>();
+    // The preferred method first, then `order`, then everything else
+    assert_eq!(titles, ["Patreon", "Ko-fi", "Stripe", "Shop", "GitHub"]);
+
+    // Without `order`, custom links come last
+    let config = FundingConfig {
+        links: vec![link("Stripe", Some("stripe"))],
+        ..FundingConfig::default()
+    };
+    let context = funding::context(&config, &funding()).unwrap();
+    let titles = context.methods().map(|m| m.title()).collect::<Vec<_>>();
+    assert_eq!(titles, ["GitHub", "Patreon", "Ko-fi", "Stripe"]);
+}
+
+#[test]
+fn it_picks_icons_for_custom_links() {
+    let config = FundingConfig {
+        links: vec![
+            link("Stripe", Some("stripe")),
+            link("Shop", Some("https://example.com/shop.png")),
+            link("Static", Some("/static/coin.svg")),
+            link("Plain", None),
+        ],
+        ..FundingConfig::default()
+    };
+    let context = funding::context(&config, &Funding::default()).unwrap();
+    let json = serde_json::to_value(context).unwrap();
+    let icons = json["funding"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| (m["icon"].as_str(), m["icon_url"].as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        icons,
+        [
+            (Some("stripe"), None),
+            (None, Some("https://example.com/shop.png")),
+            (None, Some("/static/coin.svg")),
+            (None, None),
+        ]
+    );
+}
+
+#[test]
+fn it_rejects_icons_that_wont_be_on_the_site() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let mut config = Some(FundingConfig {
+        links: vec![link("Shop", Some("assets/shop.png"))],
+        ..FundingConfig::default()
+    });
+    let error = FundingConfig::find_paths(&mut config, dir.path()).unwrap_err();
+    assert!(matches!(
+        error,
+        OrandaError::FundingIconInvalid { ref icon, .. } if icon == "assets/shop.png"
+    ));
+}
@@ -0,0 +1,60 @@
+use oranda::config::GithubActionConfig;
+use oranda::data::github_action::ActionManifest;
+use oranda::site::github_action::snippet;
+
+const ACTION_YML: &str = r#"
+name: Setup axolotlsay
+description: Installs axolotlsay
+inputs:
+  version:
+    description: The version to install
+    required: "true"
+    default: latest
+  token:
+    description: A GitHub token, for rate limits
+    required: false
+  verbose:
+    required: false
+    default: false
+runs:
+  using: node20
+  main: dist/index.js
+"#;
+
+#[test]
+fn it_reads_loosely_typed_inputs() {
+    let manifest = ActionManifest::parse(ACTION_YML).unwrap();
+    assert_eq!(manifest.name, "Setup axolotlsay");
+    let names = manifest.inputs.keys().collect::<Vec<_>>();
+    assert_eq!(names, ["version", "token", "verbose"]);
+    assert!(manifest.inputs["version"].required);
+    assert!(!manifest.inputs["token"].required);
+    assert_eq!(manifest.inputs["verbose"].default.as_deref(), Some("false"));
+}
+
+#[test]
+fn it_uses_the_release_and_required_inputs() {
+    let manifest = ActionManifest::parse(ACTION_YML).unwrap();
+    let config = GithubActionConfig::default();
+    assert_eq!(
+        snippet(&manifest, "axodotdev/axolotlsay", "v0.2.0", &config),
+        "- name: Setup axolotlsay
+  uses: axodotdev/axolotlsay@v0.2.0
+  with:
+    version: latest
+"
+    );
+}
+
+#[test]
+fn it_includes_configured_inputs() {
+    let manifest = ActionManifest::parse(ACTION_YML).unwrap();
+    let mut config = GithubActionConfig::default();
+    config
+        .with
+        .insert("token".to_owned(), "${{ secrets.GITHUB_TOKEN }}".to_owned());
+    let snippet = snippet(&manifest, "axodotdev/axolotlsay", "main", &config);
+    assert!(snippet.contains("uses: axodotdev/axolotlsay@main"));
+    assert!(snippet.contains("version: latest\n    token: ${{ secrets.GITHUB_TOKEN }}\n"));
+    assert!(!snippet.contains("verbose"));
+}
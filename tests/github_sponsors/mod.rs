@@ -0,0 +1,52 @@
+use oranda::data::sponsors::{SponsorsGoal, SponsorsGoalKind, SponsorsStats};
+
+const RESPONSE: &str = include_str!("response.json");
+
+#[test]
+fn it_parses_a_sponsors_profile() {
+    let stats = SponsorsStats::parse("axodotdev", RESPONSE).unwrap();
+    assert_eq!(stats.login, "axodotdev");
+    assert_eq!(stats.sponsor_count, 42);
+    assert_eq!(
+        stats.goal,
+        Some(SponsorsGoal {
+            kind: SponsorsGoalKind::MonthlyAmount,
+            target: 500,
+            percent: 64,
+        })
+    );
+    let tiers = stats
+        .tiers
+        .iter()
+        .map(|t| (t.name.as_str(), t.monthly_price))
+        .collect::<Vec<_>>();
+    assert_eq!(tiers, vec![("Fan", 5), ("Supporter", 25)]);
+}
+
+#[test]
+fn it_parses_a_profile_without_a_listing() {
+    let response =
+        r#"{"data":{"repositoryOwner":{"sponsors":{"totalCount":0},"sponsorsListing":null}}}"#;
+    let stats = SponsorsStats::parse("someone", response).unwrap();
+    assert_eq!(stats.sponsor_count, 0);
+    assert_eq!(stats.goal, None);
+    assert!(stats.tiers.is_empty());
+}
+
+#[test]
+fn it_caps_goal_progress_and_reports_errors() {
+    let response = RESPONSE
+        .replace("MONTHLY_SPONSORSHIP_AMOUNT", "TOTAL_SPONSORS_COUNT")
+        .replace("64", "130");
+    let goal = SponsorsStats::parse("axodotdev", &response)
+        .unwrap()
+        .goal
+        .unwrap();
+    assert_eq!(goal.kind, SponsorsGoalKind::SponsorCount);
+    assert_eq!(goal.percent, 100);
+
+    let response =
+        r#"{"data":{"repositoryOwner":null},"errors":[{"message":"Could not resolve to a User"}]}"#;
+    let err = SponsorsStats::parse("nobody", response).unwrap_err();
+    assert!(format!("{err:?}").contains("Could not resolve to a User"));
+}
@@ -0,0 +1,96 @@
+use oranda::config::CoverageService;
+use oranda::data::health::{CiState, CiStatus, Coverage};
+
+fn run(status: &str, conclusion: Option<&str>) -> String {
+    let conclusion = conclusion
+        .map(|c| format!("\"{c}\""))
+        .unwrap_or_else(|| "null".to_owned());
+    format!(
+        r#"{{
+            "total_count": 2,
+            "workflow_runs": [
+                {{
+                    "id": 2,
+                    "name": "Web",
+                    "status": "{status}",
+                    "conclusion": {conclusion},
+                    "html_url": "https://github.com/axodotdev/oranda/actions/runs/2"
+                }},
+                {{
+                    "id": 1,
+                    "name": "Web",
+                    "status": "completed",
+                    "conclusion": "success",
+                    "html_url": "https://github.com/axodotdev/oranda/actions/runs/1"
+                }}
+            ]
+        }}"#
+    )
+}
+
+fn state(status: &str, conclusion: Option<&str>) -> CiState {
+    CiStatus::parse(&run(status, conclusion))
+        .unwrap()
+        .unwrap()
+        .state
+}
+
+#[test]
+fn it_parses_the_latest_workflow_run() {
+    let ci = CiStatus::parse(&run("completed", Some("success")))
+        .unwrap()
+        .unwrap();
+    assert_eq!(ci.name, "Web");
+    assert_eq!(ci.state, CiState::Passing);
+    assert_eq!(ci.url, "https://github.com/axodotdev/oranda/actions/runs/2");
+
+    assert_eq!(state("completed", Some("failure")), CiState::Failing);
+    assert_eq!(state("completed", Some("timed_out")), CiState::Failing);
+    assert_eq!(state("in_progress", None), CiState::Running);
+    assert_eq!(state("queued", None), CiState::Running);
+    assert_eq!(state("completed", Some("cancelled")), CiState::Unknown);
+
+    let unnamed = r#"{"workflow_runs": [{"status": "completed", "conclusion": "success", "html_url": "https://example.com"}]}"#;
+    assert_eq!(CiStatus::parse(unnamed).unwrap().unwrap().name, "CI");
+    let none = r#"{"total_count": 0, "workflow_runs": []}"#;
+    assert!(CiStatus::parse(none).unwrap().is_none());
+    assert!(CiStatus::parse("<html>rate limited</html>").is_err());
+}
+
+#[test]
+fn it_parses_codecov_totals() {
+    let url = || "https://app.codecov.io/gh/axodotdev/oranda".to_owned();
+    let repo = r#"{"name": "oranda", "totals": {"files": 80, "coverage": 71.25}}"#;
+    let coverage = Coverage::parse(CoverageService::Codecov, repo, url())
+        .unwrap()
+        .unwrap();
+    assert_eq!(coverage.percent, 71.25);
+    assert_eq!(coverage.url, url());
+
+    let branch =
+        r#"{"name": "main", "head_commit": {"commitid": "abc", "totals": {"coverage": 64.0}}}"#;
+    let coverage = Coverage::parse(CoverageService::Codecov, branch, url())
+        .unwrap()
+        .unwrap();
+    assert_eq!(coverage.percent, 64.0);
+
+    let empty = r#"{"name": "oranda", "totals": null}"#;
+    assert!(Coverage::parse(CoverageService::Codecov, empty, url())
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn it_parses_coveralls_totals() {
+    let url = || "https://coveralls.io/github/axodotdev/oranda".to_owned();
+    let body = r#"{"repo_name": "axodotdev/oranda", "badge_url": "https://example.com/badge.svg", "covered_percent": 88.4}"#;
+    let coverage = Coverage::parse(CoverageService::Coveralls, body, url())
+        .unwrap()
+        .unwrap();
+    assert_eq!(coverage.percent, 88.4);
+
+    let pending = r#"{"repo_name": "axodotdev/oranda", "covered_percent": null}"#;
+    assert!(Coverage::parse(CoverageService::Coveralls, pending, url())
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,67 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+
+use camino::Utf8PathBuf;
+use oranda::data::http_cache;
+
+use super::utils::tokio_utils::TEST_RUNTIME;
+
+/// Serves `body` with an ETag `count` times, answering requests that already have it with a 304,
+/// and sends back the `If-None-Match` header of every request
+fn serve(body: &'static str, count: usize) -> (String, mpsc::Receiver<Option<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/releases", listener.local_addr().unwrap());
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        for stream in listener.incoming().take(count) {
+            let mut stream = stream.unwrap();
+            let mut if_none_match = None;
+            for line in BufReader::new(&stream).lines() {
+                let line = line.unwrap();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(": ") {
+                    if name.eq_ignore_ascii_case("if-none-match") {
+                        if_none_match = Some(value.to_owned());
+                    }
+                }
+            }
+            let response = if if_none_match.as_deref() == Some("\"v1\"") {
+                "HTTP/1.1 304 Not Modified\r\nETag: \"v1\"\r\nConnection: close\r\n\r\n".to_owned()
+            } else {
+                format!(
+                    "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+            };
+            stream.write_all(response.as_bytes()).unwrap();
+            sender.send(if_none_match).unwrap();
+        }
+    });
+    (url, receiver)
+}
+
+#[test]
+fn it_revalidates_cached_responses() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let root_dir = Utf8PathBuf::from_path_buf(dir.path().to_owned()).unwrap();
+    let (url, requests) = serve("[]", 2);
+
+    let first = TEST_RUNTIME
+        .block_on(http_cache::get(&root_dir, &url))
+        .unwrap();
+    assert_eq!(first, b"[]");
+    assert_eq!(requests.recv().unwrap(), None);
+    // The cache is the project's, wherever oranda runs from
+    let cache_dir = root_dir.join(".oranda-cache/http");
+    assert_eq!(http_cache::cache_dir(&root_dir), cache_dir);
+    assert!(http_cache::entry_path(&cache_dir, &url).exists());
+
+    let second = TEST_RUNTIME
+        .block_on(http_cache::get_in(&cache_dir, &url))
+        .unwrap();
+    assert_eq!(second, b"[]");
+    assert_eq!(requests.recv().unwrap().as_deref(), Some("\"v1\""));
+}
@@ -0,0 +1,73 @@
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use oranda::config::Config;
+use oranda::errors::OrandaError;
+use oranda::site::markdown::include::resolve;
+
+fn config_for(root: &TempDir) -> Config {
+    let mut config = Config::default();
+    config.build.include_root = Some(root.path().display().to_string());
+    config
+}
+
+#[test]
+fn it_includes_files_and_the_files_they_include() {
+    let root = TempDir::new().unwrap();
+    root.child("shared/install.md")
+        .write_str("Install it with:\n\n<!-- include: ./command.md -->\n")
+        .unwrap();
+    root.child("shared/command.md")
+        .write_str("```sh\ncargo install axolotlsay\n```\n")
+        .unwrap();
+    let install = root.child("shared/install.md");
+
+    let markdown = format!(
+        "# axolotlsay\n\n{{{{ include file=\"{}\" }}}}\n\n`<!-- include: nope.md -->`\n",
+        install.path().display()
+    );
+    let resolved = resolve(&markdown, &config_for(&root)).unwrap();
+    assert_eq!(
+        resolved,
+        "# axolotlsay\n\nInstall it with:\n\n```sh\ncargo install axolotlsay\n```\n\n`<!-- include: nope.md -->`\n"
+    );
+}
+
+#[test]
+fn it_refuses_to_include_files_in_a_loop() {
+    let root = TempDir::new().unwrap();
+    root.child("a.md")
+        .write_str("<!-- include: b.md -->\n")
+        .unwrap();
+    root.child("b.md")
+        .write_str("<!-- include: a.md -->\n")
+        .unwrap();
+
+    let markdown = format!(
+        "<!-- include: {} -->\n",
+        root.child("a.md").path().display()
+    );
+    let result = resolve(&markdown, &config_for(&root));
+    match result {
+        Err(OrandaError::IncludeCycle { chain }) => assert_eq!(chain, "a.md -> b.md -> a.md"),
+        other => panic!("expected an include cycle, got {other:?}"),
+    }
+}
+
+#[test]
+fn it_refuses_to_include_files_outside_the_root() {
+    let dir = TempDir::new().unwrap();
+    dir.child("project/README.md").write_str("hi").unwrap();
+    dir.child("secrets.md").write_str("hunter2").unwrap();
+    let mut config = Config::default();
+    config.build.include_root = Some(dir.child("project").path().display().to_string());
+
+    let markdown = format!(
+        "<!-- include: {} -->\n",
+        dir.child("secrets.md").path().display()
+    );
+    let result = resolve(&markdown, &config);
+    assert!(matches!(
+        result,
+        Err(OrandaError::IncludeOutsideRoot { .. })
+    ));
+}
@@ -0,0 +1,56 @@
+use minijinja::context;
+use oranda::config::{ArtifactsConfig, Config};
+use oranda::data::artifacts::ReleaseArtifacts;
+use oranda::data::{Context, Release, ReleaseSource};
+use oranda::site::artifacts::template_context;
+use oranda::site::templates::Templates;
+
+use crate::utils::github::release_with_assets;
+
+fn render_widget(install_counter: bool) -> String {
+    let github = release_with_assets("v1.2.0", &[("app-x86_64-unknown-linux-gnu.tar.gz", 0)]);
+    let artifacts_config = ArtifactsConfig {
+        install_counter,
+        ..ArtifactsConfig::default()
+    };
+    let mut artifacts = ReleaseArtifacts::new(None);
+    artifacts.add_github(&github);
+    artifacts.add_inference(&artifacts_config);
+    artifacts.select_installers(&artifacts_config);
+    let context = Context {
+        repo: None,
+        releases: vec![Release {
+            manifest: None,
+            source: ReleaseSource::Github(github),
+            artifacts,
+        }],
+        has_prereleases: false,
+        latest_release: Some(0),
+        has_artifacts: true,
+        channels: vec![],
+    };
+
+    let mut config = Config::default();
+    config.build.path_prefix = Some("app".to_owned());
+    config.components.artifacts = Some(artifacts_config);
+    let artifacts = template_context(&context, &config).unwrap();
+    Templates::new(&config, None)
+        .unwrap()
+        .render_to_string(
+            "includes/install_widget.html",
+            context!(artifacts => artifacts),
+        )
+        .unwrap()
+}
+
+#[test]
+fn it_adds_a_lazy_counter_image_to_each_platform() {
+    let html = render_widget(true);
+    assert!(html.contains(
+        r#"<img class="install-counter" src="/app/counter/v1.2.0/x86_64-unknown-linux-gnu.gif" alt="" width="1" height="1" loading="lazy" />"#
+    ));
+    // The image does the counting, so there's nothing for scripts to hook into
+    assert!(!html.contains("data-counter"));
+
+    assert!(!render_widget(false).contains("install-counter"));
+}
@@ -50,7 +50,7 @@ fn it_adds_additional_css() {
         }),
         &mut t,
     );
-    let site = Site::build_single(&config, None).unwrap();
+    let site = Site::build_single(&config, None, None).unwrap();
     let page = find_page(&site.pages, "index.html");
     assert_selector_exists(&page.contents, "link[rel='stylesheet'][href='/custom.css']");
 }
@@ -67,7 +67,7 @@ fn it_can_point_to_custom_repository() {
         }),
         &mut t,
     );
-    let site = Site::build_single(&config, None).unwrap();
+    let site = Site::build_single(&config, None, None).unwrap();
     let page = find_page(&site.pages, "index.html");
     assert_selector_exists(
         &page.contents,
@@ -87,7 +87,7 @@ fn it_renders_changelog_with_release_content() {
         }),
         &mut t,
     );
-    let site = Site::build_single(&config, None).unwrap();
+    let site = Site::build_single(&config, None, None).unwrap();
     let page = find_page(&site.pages, "changelog.html");
     assert_selector_exists(&page.contents, "h2[id='tag-v0.2.0']~.release-body p>strong");
 }
@@ -97,7 +97,7 @@ fn it_adds_oranda_css() {
     let _guard = TEST_RUNTIME.enter();
     let mut t = temp_build_dir();
     let config = oranda_config::from_json(json!({}), &mut t);
-    let site = Site::build_single(&config, None).unwrap();
+    let site = Site::build_single(&config, None, None).unwrap();
     let css_name = format!("oranda-{}.css", ORANDA_CSS_TAG);
     assert!(t.child(&css_name).exists());
     let page = find_page(&site.pages, "index.html");
@@ -116,7 +116,7 @@ fn it_adds_oranda_css_with_pinned_version() {
         }),
         &mut t,
     );
-    let site = Site::build_single(&config, None).unwrap();
+    let site = Site::build_single(&config, None, None).unwrap();
     assert!(t.child("oranda-v0.1.0.css").exists());
     let page = find_page(&site.pages, "index.html");
     assert_selector_exists(&page.contents, "link[href='/oranda-v0.1.0.css']");
@@ -134,7 +134,7 @@ fn reads_description() {
         }),
         &mut t,
     );
-    let site = Site::build_single(&config, None).unwrap();
+    let site = Site::build_single(&config, None, None).unwrap();
     let page = find_page(&site.pages, "index.html");
     assert_selector_exists(&page.contents, "meta[content='you axolotl questions']");
 }
@@ -151,7 +151,7 @@ fn reads_theme() {
         }),
         &mut t,
     );
-    let site = Site::build_single(&config, None).unwrap();
+    let site = Site::build_single(&config, None, None).unwrap();
     let page = find_page(&site.pages, "index.html");
     assert_selector_exists(&page.contents, "html.cupcake");
 }
@@ -161,11 +161,11 @@ fn creates_footer() {
     let _guard = TEST_RUNTIME.enter();
     let mut t = temp_build_dir();
     let config = oranda_config::from_json(json!({}), &mut t);
-    let site = Site::build_single(&config, None).unwrap();
+    let site = Site::build_single(&config, None, None).unwrap();
     let page = find_page(&site.pages, "index.html");
     assert_selector_exists(
         &page.contents,
-        "footer>a[href='https://github.com/oranda-gallery/oranda']",
+        "footer .footer-icons>a[href='https://github.com/oranda-gallery/oranda']",
     );
     assert!(selector_get_inner(&page.contents, "footer span").contains("MIT OR Apache-2.0"));
 }
@@ -184,7 +184,7 @@ fn creates_downloads_page() {
         }),
         &mut t,
     );
-    let site = Site::build_single(&config, None).unwrap();
+    let site = Site::build_single(&config, None, None).unwrap();
     let page = find_page(&site.pages, "artifacts.html");
     assert_selector_exists(&page.contents, ".artifacts-table");
 }
@@ -201,7 +201,7 @@ fn adds_prefix() {
         }),
         &mut t,
     );
-    let site = Site::build_single(&config, None).unwrap();
+    let site = Site::build_single(&config, None, None).unwrap();
     let page = find_page(&site.pages, "index.html");
     assert_selector_exists(&page.contents, "script[src='/axo/artifacts.js']");
     assert_selector_exists(&page.contents, "nav.nav a[href='/axo/']");
@@ -223,7 +223,7 @@ fn it_inserts_plausible_tag() {
         }),
         &mut t,
     );
-    let site = Site::build_single(&config, None).unwrap();
+    let site = Site::build_single(&config, None, None).unwrap();
     let page = find_page(&site.pages, "index.html");
     assert_selector_exists(&page.contents, "script[defer='true'][data-domain='opensource.axo.dev'][src='https://plausible.io/js/script.js']");
 }
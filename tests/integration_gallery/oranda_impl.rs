@@ -139,6 +139,7 @@ impl Tools {
             build: None,
             marketing: None,
             styles: None,
+            markdown: None,
             components: None,
             workspace: Some(WorkspaceLayer {
                 name: Some("oranda gallery".to_owned()),
@@ -147,7 +148,9 @@ impl Tools {
                 preferred_members: None,
                 auto: Some(false),
                 docs_path: None,
+                dependency_graph: None,
             }),
+            profiles: None,
             _schema: None,
         };
         self.save_oranda_workspace_json(&json)
@@ -221,6 +224,8 @@ impl<'a> TestContext<'a, Tools> {
                 .push(WorkspaceMember {
                     slug: test_name.to_owned(),
                     path: self.working_dir.as_std_path().to_owned(),
+                    weight: None,
+                    config: None,
                 });
             self.tools.save_oranda_workspace_json(&json)?;
         }
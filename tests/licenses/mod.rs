@@ -0,0 +1,158 @@
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use camino::Utf8Path;
+use minijinja::Value;
+use oranda::config::{Config, LicensesConfig};
+use oranda::data::licenses::{
+    from_cargo_about, from_cargo_metadata, load_project_licenses, DependencyLicenses,
+};
+use oranda::site::licenses::LicensesContext;
+use oranda::site::templates::Templates;
+
+const METADATA: &str = r#"{
+  "packages": [
+    { "id": "app 0.1.0", "name": "app", "version": "0.1.0", "license": "MIT", "license_file": null, "repository": null },
+    { "id": "serde 1.0.0", "name": "serde", "version": "1.0.0", "license": "MIT OR Apache-2.0", "license_file": null, "repository": "https://github.com/serde-rs/serde" },
+    { "id": "libc 0.2.0", "name": "libc", "version": "0.2.0", "license": "MIT/Apache-2.0", "license_file": null, "repository": null },
+    { "id": "ring 0.17.0", "name": "ring", "version": "0.17.0", "license": null, "license_file": "LICENSE", "repository": null },
+    { "id": "cc 1.0.0", "name": "cc", "version": "1.0.0", "license": "MIT OR Apache-2.0", "license_file": null, "repository": null },
+    { "id": "insta 1.0.0", "name": "insta", "version": "1.0.0", "license": "Apache-2.0", "license_file": null, "repository": null }
+  ],
+  "workspace_members": ["app 0.1.0"],
+  "resolve": {
+    "nodes": [
+      { "id": "app 0.1.0", "deps": [
+        { "pkg": "serde 1.0.0", "dep_kinds": [{ "kind": null }] },
+        { "pkg": "ring 0.17.0", "dep_kinds": [{ "kind": null }] },
+        { "pkg": "insta 1.0.0", "dep_kinds": [{ "kind": "dev" }] }
+      ] },
+      { "id": "ring 0.17.0", "deps": [
+        { "pkg": "cc 1.0.0", "dep_kinds": [{ "kind": "build" }] },
+        { "pkg": "libc 0.2.0", "dep_kinds": [{ "kind": null }] }
+      ] },
+      { "id": "serde 1.0.0", "deps": [] },
+      { "id": "cc 1.0.0", "deps": [] },
+      { "id": "libc 0.2.0", "deps": [] },
+      { "id": "insta 1.0.0", "deps": [] }
+    ]
+  }
+}"#;
+
+#[test]
+fn it_reads_licenses_from_cargo_metadata() {
+    let licenses = from_cargo_metadata(METADATA, &["cc".to_owned()]).unwrap();
+    let summary = licenses
+        .licenses
+        .iter()
+        .map(|license| {
+            let crates = license
+                .crates
+                .iter()
+                .map(|krate| krate.name.as_str())
+                .collect::<Vec<_>>();
+            (license.id.as_str(), crates)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![
+            ("MIT OR Apache-2.0", vec!["libc", "serde"]),
+            ("Custom license", vec!["ring"]),
+        ]
+    );
+}
+
+#[test]
+fn it_reads_licenses_from_cargo_about() {
+    let report = r#"{
+      "overview": [],
+      "crates": [],
+      "licenses": [
+        { "name": "MIT License", "id": "MIT", "text": "Copyright <you>", "source_path": null, "used_by": [
+          { "crate": { "name": "serde", "version": "1.0.0", "repository": "https://github.com/serde-rs/serde" }, "path": null },
+          { "crate": { "name": "app", "version": "0.1.0", "repository": null }, "path": null }
+        ] },
+        { "name": "Apache License 2.0", "id": "Apache-2.0", "text": "Apache", "source_path": null, "used_by": [
+          { "crate": { "name": "app", "version": "0.1.0", "repository": null }, "path": null }
+        ] }
+      ]
+    }"#;
+    let licenses = from_cargo_about(report, &["app".to_owned()]).unwrap();
+    assert_eq!(licenses.licenses.len(), 1);
+    let mit = &licenses.licenses[0];
+    assert_eq!(mit.name.as_deref(), Some("MIT License"));
+    assert_eq!(mit.crates[0].name, "serde");
+
+    let mut config = Config::default();
+    config.project.name = String::from("axolotlsay");
+    let page = Templates::new(&config, None)
+        .unwrap()
+        .render_to_string(
+            "licenses.html",
+            Value::from_serializable(&LicensesContext::new(&licenses)),
+        )
+        .unwrap();
+    assert!(page.contains("axolotlsay uses 1 third-party"));
+    assert!(page.contains(r##"<a href="#license-mit">MIT License</a>"##));
+    assert!(page.contains("Copyright &lt;you&gt;"));
+}
+
+#[test]
+fn it_finds_the_projects_own_licenses() {
+    let project = TempDir::new().unwrap();
+    project.child("LICENSE-MIT").write_str("MIT text").unwrap();
+    project
+        .child("LICENSE-APACHE")
+        .write_str("Apache text")
+        .unwrap();
+    project.child("README.md").write_str("# app").unwrap();
+    let root = Utf8Path::from_path(project.path()).unwrap();
+
+    let mut licenses_config = Some(LicensesConfig::default());
+    LicensesConfig::find_paths(&mut licenses_config, project.path()).unwrap();
+    let licenses_config = licenses_config.unwrap();
+    let project_licenses = load_project_licenses(&licenses_config, root).unwrap();
+    let file_names = project_licenses
+        .iter()
+        .map(|license| license.file_name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(file_names, vec!["LICENSE-APACHE", "LICENSE-MIT"]);
+    assert_eq!(project_licenses[1].text, "MIT text");
+
+    // Listing them yourself skips the detection
+    let mut licenses_config = Some(LicensesConfig {
+        project_licenses: Some(vec![]),
+        ..LicensesConfig::default()
+    });
+    LicensesConfig::find_paths(&mut licenses_config, project.path()).unwrap();
+    assert!(load_project_licenses(&licenses_config.unwrap(), root)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn it_shows_the_projects_own_licenses_first() {
+    let project = TempDir::new().unwrap();
+    project.child("LICENSE").write_str("MIT <text>").unwrap();
+    let root = Utf8Path::from_path(project.path()).unwrap();
+    let licenses_config = LicensesConfig {
+        project_licenses: Some(vec!["LICENSE".to_owned()]),
+        ..LicensesConfig::default()
+    };
+
+    let mut config = Config::default();
+    config.project.name = String::from("axolotlsay");
+    config.project.license = Some(String::from("MIT"));
+    config.components.licenses = Some(licenses_config.clone());
+    let context = LicensesContext::new(&DependencyLicenses::default())
+        .with_project_licenses(load_project_licenses(&licenses_config, root).unwrap());
+    let page = Templates::new(&config, None)
+        .unwrap()
+        .render_to_string("licenses.html", Value::from_serializable(&context))
+        .unwrap();
+    assert!(page.contains("<h1>Licenses</h1>"));
+    assert!(page.contains("axolotlsay is licensed under <code>MIT</code>."));
+    assert!(page.contains("<summary>LICENSE</summary>"));
+    assert!(page.contains("MIT &lt;text&gt;"));
+    assert!(page.contains(">Licenses</a>"));
+}
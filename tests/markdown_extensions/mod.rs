@@ -0,0 +1,45 @@
+use oranda::config::Config;
+use oranda::site::markdown::to_html;
+
+const MARKDOWN: &str =
+    "Hello[^1]\n\n- [x] Done\n- [ ] Not yet\n\nTerm\n\n: Definition\n\n[^1]: A footnote\n";
+
+#[test]
+fn it_renders_footnotes_tasklists_and_definition_lists() {
+    let html = to_html(MARKDOWN, &Config::default()).unwrap();
+    assert!(html.contains(r##"<a href="#fn-1" id="fnref-1""##));
+    assert!(html.contains(r#"<section class="footnotes">"#));
+    assert!(html.contains(r##"<a href="#fnref-1" class="footnote-backref""##));
+    assert!(html.contains(r#"<li><input disabled="" checked="" type="checkbox"> Done</li>"#));
+    assert!(html.contains("<dl><dt>Term</dt>"));
+}
+
+#[test]
+fn it_leaves_disabled_extensions_as_text() {
+    let mut config = Config::default();
+    config.markdown.extensions.footnotes = false;
+    config.markdown.extensions.tasklists = false;
+    config.markdown.extensions.definition_lists = false;
+
+    let html = to_html(MARKDOWN, &config).unwrap();
+    assert!(!html.contains("footnote-ref"));
+    assert!(html.contains("<li>[x] Done</li>"));
+    assert!(!html.contains("<dl>"));
+    // Only task lists get to keep their checkboxes
+    let html = to_html("<input type=\"text\" value=\"hi\">", &config).unwrap();
+    assert!(!html.contains("<input"));
+}
+
+#[test]
+fn it_renders_emoji_shortcodes_outside_of_code() {
+    let markdown = "Ship it :rocket: :+1: at 12:30:45, not :nope: or :octocat:\n\n`:rocket:`\n\n```\n:tada:\n```\n";
+    let html = to_html(markdown, &Config::default()).unwrap();
+    assert!(html.contains("<p>Ship it 🚀 👍 at 12:30:45, not :nope: or :octocat:</p>"));
+    assert!(html.contains("<code>:rocket:</code>"));
+    assert!(!html.contains('🎉'));
+
+    let mut config = Config::default();
+    config.markdown.extensions.emoji = false;
+    let html = to_html(":rocket:", &config).unwrap();
+    assert_eq!(html.trim(), "<p>:rocket:</p>");
+}
@@ -0,0 +1,97 @@
+use oranda::config::{ArtifactsConfig, MatchingConfig};
+use oranda::data::artifacts::{InstallMethod, ReleaseArtifacts};
+
+use crate::utils::github::release_with_assets;
+
+fn artifacts(assets: &[&str], matching: &MatchingConfig) -> ReleaseArtifacts {
+    let assets = assets.iter().map(|name| (*name, 0)).collect::<Vec<_>>();
+    let mut artifacts = ReleaseArtifacts::new(None);
+    artifacts.add_github(&release_with_assets("v1.0.0", &assets));
+    artifacts.add_inference(&ArtifactsConfig {
+        matching: matching.clone(),
+        ..ArtifactsConfig::default()
+    });
+    artifacts
+}
+
+fn installer_files(artifacts: &ReleaseArtifacts) -> Vec<(&str, &str)> {
+    artifacts
+        .installers()
+        .filter_map(|(_, installer)| match installer.method {
+            InstallMethod::Download { file } => {
+                Some((artifacts.file(file).name.as_str(), installer.label.as_str()))
+            }
+            InstallMethod::Run { .. } => None,
+        })
+        .collect()
+}
+
+#[test]
+fn it_uses_custom_installer_and_archive_rules() {
+    let matching = MatchingConfig {
+        installers: vec![r"^app-setup-.*\.exe$".to_owned()],
+        archives: vec![r"\.cpio$".to_owned()],
+        ..MatchingConfig::default()
+    };
+    let artifacts = artifacts(
+        &[
+            "app-setup-x86_64-pc-windows-msvc.exe",
+            "app-x86_64-unknown-linux-gnu.cpio",
+            "app-source.cpio",
+            "app-x86_64-apple-darwin.tar.gz",
+        ],
+        &matching,
+    );
+    assert_eq!(
+        installer_files(&artifacts),
+        vec![
+            ("app-setup-x86_64-pc-windows-msvc.exe", "exe"),
+            ("app-x86_64-unknown-linux-gnu.cpio", "tarball"),
+            ("app-x86_64-apple-darwin.tar.gz", "tarball"),
+        ]
+    );
+}
+
+#[test]
+fn it_ignores_matching_files() {
+    let matching = MatchingConfig {
+        ignored: vec!["-debuginfo".to_owned()],
+        ..MatchingConfig::default()
+    };
+    let artifacts = artifacts(
+        &[
+            "app-x86_64-unknown-linux-gnu.tar.gz",
+            "app-debuginfo-x86_64-unknown-linux-gnu.tar.gz",
+        ],
+        &matching,
+    );
+    assert_eq!(
+        installer_files(&artifacts),
+        vec![("app-x86_64-unknown-linux-gnu.tar.gz", "tarball")]
+    );
+}
+
+#[test]
+fn it_attaches_matched_checksums() {
+    let matching = MatchingConfig {
+        checksums: vec![r"\.b3$".to_owned()],
+        ..MatchingConfig::default()
+    };
+    let artifacts = artifacts(
+        &[
+            "app-x86_64-unknown-linux-gnu.tar.gz",
+            "app-x86_64-unknown-linux-gnu.tar.gz.b3",
+            "other-x86_64-unknown-linux-gnu.tar.gz.b3",
+        ],
+        &matching,
+    );
+    let archive = artifacts
+        .file_idx(&"app-x86_64-unknown-linux-gnu.tar.gz".to_owned())
+        .unwrap();
+    let checksum = artifacts
+        .file(archive)
+        .checksum_file
+        .map(|idx| artifacts.file(idx).name.as_str());
+    assert_eq!(checksum, Some("app-x86_64-unknown-linux-gnu.tar.gz.b3"));
+    assert_eq!(installer_files(&artifacts).len(), 1);
+}
@@ -0,0 +1,80 @@
+use assert_fs::prelude::*;
+use camino::{Utf8Path, Utf8PathBuf};
+use indexmap::IndexMap;
+use oranda::config::{MdBookConfig, StyleConfig};
+use oranda::site::mdbook::build_mdbook;
+use oranda::site::oranda_theme::OrandaTheme;
+use oranda::site::writer::MemoryWriter;
+use serde_json::json;
+
+use crate::utils::tokio_utils::TEST_RUNTIME;
+
+fn contents<'a>(writer: &'a MemoryWriter, path: &str) -> &'a str {
+    let bytes = writer
+        .files
+        .get(Utf8Path::new(path))
+        .unwrap_or_else(|| panic!("{path} wasn't written"));
+    std::str::from_utf8(bytes).unwrap()
+}
+
+#[test]
+fn it_passes_the_site_styles_and_overrides_to_the_book() {
+    let _guard = TEST_RUNTIME.enter();
+    let project = assert_fs::TempDir::new().unwrap();
+    project
+        .child("docs/book.toml")
+        .write_str("[book]\ntitle = \"Original Title\"\n")
+        .unwrap();
+    project
+        .child("docs/src/SUMMARY.md")
+        .write_str("- [Intro](intro.md)\n")
+        .unwrap();
+    project
+        .child("docs/src/intro.md")
+        .write_str("# Intro\n")
+        .unwrap();
+    project
+        .child("custom.css")
+        .write_str(".banner { color: red; }\n")
+        .unwrap();
+    let root = Utf8PathBuf::from_path_buf(project.path().to_owned()).unwrap();
+
+    let book_cfg = MdBookConfig {
+        path: Some(String::from("docs")),
+        overrides: IndexMap::from([
+            (String::from("book.title"), json!("Overridden Title")),
+            (String::from("output.html.no-section-label"), json!(true)),
+        ]),
+        ..Default::default()
+    };
+    let styles = StyleConfig {
+        theme: OrandaTheme::Hacker,
+        additional_css: vec![String::from("custom.css")],
+        ..Default::default()
+    };
+    let mut writer = MemoryWriter::new();
+    build_mdbook(
+        &root,
+        Utf8Path::new("public"),
+        &book_cfg,
+        &styles,
+        &mut writer,
+    )
+    .unwrap();
+
+    let page = contents(&writer, "book/intro.html");
+    assert!(page.contains("<title>Intro - Overridden Title</title>"));
+    // The theme's webfonts, then the user's css so it wins over the theme
+    let fonts = page.find("family=IBM+Plex+Mono").unwrap();
+    let custom = page.find(r#"href="oranda-custom.css""#).unwrap();
+    assert!(fonts < custom);
+    assert!(page.contains(r#"class="theme" id="oranda-dark">"#));
+
+    assert_eq!(
+        contents(&writer, "book/oranda-custom.css"),
+        "/* custom.css */.banner{color:red;}"
+    );
+    assert!(contents(&writer, "book/css/variables.css").contains("--color-hacker-green"));
+    // The theme only needs to exist while mdbook builds
+    assert!(!root.join("public/mdbook_theme").exists());
+}
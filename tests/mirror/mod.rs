@@ -0,0 +1,136 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+
+use assert_fs::TempDir;
+use oranda::config::{ArtifactsConfig, Config, MirrorConfig};
+use oranda::data::artifacts::mirror::expected_checksum;
+use oranda::data::artifacts::{File, ReleaseArtifacts};
+use oranda::data::{Release, ReleaseSource};
+use oranda::site::artifacts::mirror_release;
+use oranda::site::writer::MemoryWriter;
+
+use crate::utils::github::release_with_assets;
+use crate::utils::tokio_utils::TEST_RUNTIME;
+
+const HASH: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+#[test]
+fn it_reads_bare_checksum_files() {
+    assert_eq!(
+        expected_checksum(&format!("{HASH}\n"), "app.tar.gz").as_deref(),
+        Some(HASH)
+    );
+}
+
+#[test]
+fn it_finds_the_right_line_in_sha256sums() {
+    let other = "a".repeat(64);
+    let contents = format!("{other}  other.zip\n{}  *app.tar.gz\n", HASH.to_uppercase());
+    assert_eq!(
+        expected_checksum(&contents, "app.tar.gz").as_deref(),
+        Some(HASH)
+    );
+    assert_eq!(expected_checksum(&contents, "missing.zip"), None);
+    assert_eq!(expected_checksum("not a checksum", "app.tar.gz"), None);
+}
+
+/// Serves `files` by path until the test ends, and returns the server's base URL
+fn serve(files: Vec<(&'static str, String)>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut lines = BufReader::new(&stream).lines().map(|l| l.unwrap());
+            let request = lines.next().unwrap_or_default();
+            for line in lines {
+                if line.is_empty() {
+                    break;
+                }
+            }
+            let path = request.split(' ').nth(1).unwrap_or_default();
+            let response = match files.iter().find(|(p, _)| *p == path) {
+                Some((_, body)) => format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                ),
+                None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    .to_owned(),
+            };
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    url
+}
+
+fn file(name: &str, download_url: String) -> File {
+    File {
+        name: name.to_owned(),
+        download_url,
+        view_path: None,
+        checksum_file: None,
+        unavailable: false,
+        download_count: None,
+        attestations: Default::default(),
+        infer: true,
+    }
+}
+
+#[test]
+fn it_mirrors_a_release() {
+    let _guard = TEST_RUNTIME.enter();
+    // HASH is the SHA-256 of "test"
+    let url = serve(vec![
+        ("/app.tar.gz", "test".to_owned()),
+        ("/app.tar.gz.sha256", format!("{HASH}  app.tar.gz\n")),
+        ("/bad.zip", "tampered".to_owned()),
+        ("/bad.zip.sha256", format!("{HASH}  bad.zip\n")),
+    ]);
+    let mut artifacts = ReleaseArtifacts::new(None);
+    for name in ["app.tar.gz", "bad.zip"] {
+        let checksum = artifacts.add_file(file(
+            &format!("{name}.sha256"),
+            format!("{url}/{name}.sha256"),
+        ));
+        let mut file = file(name, format!("{url}/{name}"));
+        file.checksum_file = Some(checksum);
+        artifacts.add_file(file);
+    }
+    let mut release = Release {
+        manifest: None,
+        source: ReleaseSource::Github(release_with_assets("v1.0.0", &[])),
+        artifacts,
+    };
+
+    let dist = TempDir::new().unwrap();
+    let mut config = Config::default();
+    config.build.dist_dir = String::from(dist.path().to_str().unwrap());
+    config.components.artifacts = Some(ArtifactsConfig {
+        mirror: Some(MirrorConfig {
+            files: vec!["*.tar.gz".to_owned(), "*.zip".to_owned()],
+            max_size_mb: 1,
+            require_checksum: true,
+        }),
+        ..ArtifactsConfig::default()
+    });
+    let mut writer = MemoryWriter::new();
+    mirror_release(&mut release, &config, &mut writer).unwrap();
+
+    let urls = release
+        .artifacts
+        .files()
+        .map(|f| (f.name.as_str(), f.download_url.clone()))
+        .collect::<Vec<_>>();
+    assert!(urls.contains(&("app.tar.gz", "/dl/v1.0.0/app.tar.gz".to_owned())));
+    // The checksum doesn't match, so it keeps linking to the original
+    assert!(urls.contains(&("bad.zip", format!("{url}/bad.zip"))));
+
+    assert_eq!(writer.get("dl/v1.0.0/app.tar.gz").unwrap(), "test");
+    assert!(writer.get("dl/v1.0.0/bad.zip").is_none());
+    assert_eq!(
+        writer.get("dl/v1.0.0/SHA256SUMS").unwrap(),
+        format!("{HASH}  app.tar.gz\n")
+    );
+    // Nothing's written to the dist dir itself
+    assert!(!dist.path().join("dl").exists());
+}
@@ -1,5 +1,91 @@
 pub mod utils;
 
+mod accessibility;
+mod activity;
+mod api;
+mod archive;
+mod artifacts_schema;
+mod asciinema;
+mod attestations;
+mod authors;
 mod autodetect;
+mod benchmarks;
+mod blog;
+mod build_many;
+mod canonical;
+mod changelog;
+mod channels;
+mod comments;
+mod community;
+mod compat;
+mod config_overrides;
+mod consent_banner;
+mod data_pages;
+mod debug_context;
+mod demo;
+mod dependency_graph;
+mod deploy;
+mod dev_status;
+mod diagnostics;
+mod distros;
+mod docker;
+mod docs;
+mod doctor;
+mod download_checks;
+mod download_counts;
+mod export;
+mod features;
+mod fetch;
+mod file_history;
+mod footer;
+mod funding;
+mod github_action;
+mod github_sponsors;
+mod health;
+mod http_cache;
+mod include;
+mod install_counter;
 mod integration;
 mod integration_gallery;
+mod licenses;
+mod markdown_extensions;
+mod matching;
+mod mdbook;
+mod mirror;
+mod nav_order;
+mod newsletter;
+mod offline;
+mod org;
+mod preload;
+mod preview;
+mod private;
+mod profiles;
+mod qr_codes;
+mod rebuild;
+mod relative_links;
+mod releases;
+mod report;
+mod requirements;
+mod roadmap;
+mod rustdoc;
+mod sanitize;
+mod sbom;
+mod script_pages;
+mod security;
+mod shortcodes;
+mod sidebar;
+mod single_file;
+mod site_builder;
+mod sitemap;
+mod sponsors;
+mod stale;
+mod support_matrix;
+mod tags;
+mod targets;
+mod templates;
+mod theme_test;
+mod tls;
+mod web_manifest;
+mod well_known;
+mod workspaces;
+mod writer;
@@ -0,0 +1,82 @@
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use camino::Utf8PathBuf;
+use oranda::config::{Config, DataPage, DataPagesConfig};
+use oranda::site::templates::Templates;
+
+/// The names in the nav, up to the links of the components, which come after the pages
+fn nav_names(config: &Config, count: usize) -> Vec<String> {
+    let templates = Templates::new(config, None).unwrap();
+    templates
+        .layout
+        .nav_links()
+        .iter()
+        .map(|link| link.name.clone())
+        .take(count)
+        .collect()
+}
+
+#[test]
+fn it_orders_additional_pages_by_weight() {
+    let dir = TempDir::new().unwrap();
+    dir.child("setup.md").write_str("# Setup\n").unwrap();
+    dir.child("faq.md")
+        .write_str("---\nweight: 10\n---\n# FAQ\n")
+        .unwrap();
+    dir.child("intro.md")
+        .write_str("---\nweight: -1\n---\n# Intro\n")
+        .unwrap();
+    dir.child("usage.md").write_str("# Usage\n").unwrap();
+    let mut config = Config {
+        root_dir: Utf8PathBuf::from_path_buf(dir.path().to_owned()).unwrap(),
+        ..Config::default()
+    };
+    for (name, path) in [
+        ("Setup", "setup.md"),
+        ("FAQ", "faq.md"),
+        ("Intro", "intro.md"),
+        ("Usage", "usage.md"),
+    ] {
+        config
+            .build
+            .additional_pages
+            .insert(name.to_owned(), path.to_owned());
+    }
+    let mut data_pages = DataPagesConfig::default();
+    data_pages.pages.insert(
+        "plugins".to_owned(),
+        DataPage {
+            data: "plugins.json".to_owned(),
+            template: None,
+            title: Some("Plugins".to_owned()),
+            nav: None,
+            weight: Some(5),
+        },
+    );
+    config.components.data_pages = Some(data_pages);
+
+    assert_eq!(
+        nav_names(&config, 6),
+        ["Home", "Intro", "Setup", "Usage", "Plugins", "FAQ"]
+    );
+}
+
+#[test]
+fn it_keeps_the_configured_order_without_weights() {
+    let dir = TempDir::new().unwrap();
+    let mut config = Config {
+        root_dir: Utf8PathBuf::from_path_buf(dir.path().to_owned()).unwrap(),
+        ..Config::default()
+    };
+    for name in ["Zebra", "Apple", "Mango"] {
+        dir.child(format!("{name}.md"))
+            .write_str(&format!("# {name}\n"))
+            .unwrap();
+        config
+            .build
+            .additional_pages
+            .insert(name.to_owned(), format!("{name}.md"));
+    }
+
+    assert_eq!(nav_names(&config, 4), ["Home", "Zebra", "Apple", "Mango"]);
+}
@@ -0,0 +1,72 @@
+use minijinja::context;
+use oranda::config::{
+    Buttondown, Config, Mailchimp, NewsletterConfig, NewsletterForm, NewsletterProvider,
+};
+use oranda::site::templates::Templates;
+
+fn render(config: &Config, template: &str) -> String {
+    let templates = Templates::new(config, None).unwrap();
+    let page = context! {
+        posts => Vec::<()>::new(),
+        total_pages => 1,
+        title => "Hello",
+        body => "<p>Hi</p>",
+        reading_time => context! { minutes => 1 },
+    };
+    templates.render_to_string(template, page).unwrap()
+}
+
+fn config(provider: NewsletterProvider) -> Config {
+    let mut config = Config::default();
+    config.marketing.newsletter = Some(NewsletterConfig {
+        provider,
+        title: None,
+        description: Some("One email per release".to_owned()),
+        button_text: None,
+    });
+    config
+}
+
+#[test]
+fn it_renders_a_buttondown_form() {
+    let config = config(NewsletterProvider::Buttondown(Buttondown {
+        username: "axodotdev".to_owned(),
+    }));
+    let html = render(&config, "index.html");
+    assert!(
+        html.contains(r#"action="https://buttondown.com/api/emails/embed-subscribe/axodotdev""#)
+    );
+    assert!(html.contains(r#"name="email""#));
+    assert!(html.contains(r#"<input type="hidden" name="embed" value="1">"#));
+    assert!(html.contains("Get release announcements"));
+    assert!(html.contains("One email per release"));
+    assert!(html.contains(">Subscribe</button>"));
+    assert!(!html.contains("newsletter-honeypot"));
+}
+
+#[test]
+fn it_renders_a_mailchimp_form_with_its_bot_trap() {
+    let config = config(NewsletterProvider::Mailchimp(Mailchimp {
+        action: "https://example.us1.list-manage.com/subscribe/post?u=abc&id=123".to_owned(),
+    }));
+    let html = render(&config, "blog_index.html");
+    assert!(html.contains(r#"name="EMAIL""#));
+    assert!(html.contains(r#"name="b_abc_123""#));
+}
+
+#[test]
+fn it_renders_any_form() {
+    let config = config(NewsletterProvider::Form(NewsletterForm {
+        action: "https://example.com/subscribe".to_owned(),
+        email_field: Some("address".to_owned()),
+    }));
+    let html = render(&config, "blog_post.html");
+    assert!(html.contains(r#"action="https://example.com/subscribe""#));
+    assert!(html.contains(r#"name="address""#));
+}
+
+#[test]
+fn it_only_renders_a_form_when_asked() {
+    let html = render(&Config::default(), "index.html");
+    assert!(!html.contains("newsletter"));
+}
@@ -0,0 +1,69 @@
+use oranda::config::Config;
+use oranda::site::javascript::{build_service_worker, offline_urls, OFFLINE_RELEASES};
+use oranda::site::page::Page;
+
+fn page(filename: &str) -> Page {
+    Page {
+        contents: String::new(),
+        filename: filename.to_owned(),
+        reading_time: None,
+        stale: None,
+    }
+}
+
+#[test]
+fn it_points_the_service_worker_at_the_offline_page() {
+    let worker = build_service_worker(&None, &[]);
+    assert!(worker.contains(r#"const OFFLINE_URL = "/offline/";"#));
+
+    let worker = build_service_worker(&Some("my_project".to_owned()), &[]);
+    assert!(worker.contains(r#"const OFFLINE_URL = "/my_project/offline/";"#));
+}
+
+#[test]
+fn it_caches_the_shell_and_the_newest_releases() {
+    let mut config = Config::default();
+    config.build.path_prefix = Some("my_project".to_owned());
+    let mut pages = vec![page("index.html"), page("changelog.html")];
+    pages.extend(
+        (0..8)
+            .rev()
+            .map(|minor| page(&format!("changelog/v0.{minor}.0.html"))),
+    );
+    pages.push(page("changelog/upgrading/v1.html"));
+
+    let urls = offline_urls(&config, &pages).unwrap();
+    assert_eq!(urls[0], "/my_project/");
+    assert!(urls.iter().any(|url| url.ends_with(".css")));
+    assert!(urls.contains(&"/my_project/changelog/".to_owned()));
+    let releases: Vec<_> = urls
+        .iter()
+        .filter(|url| url.starts_with("/my_project/changelog/v"))
+        .collect();
+    assert_eq!(releases.len(), OFFLINE_RELEASES);
+    assert_eq!(releases[0], "/my_project/changelog/v0.7.0/");
+    assert!(!urls.iter().any(|url| url.contains("upgrading")));
+}
+
+#[test]
+fn it_makes_a_new_cache_for_new_pages() {
+    let urls = vec!["/".to_owned(), "/changelog/".to_owned()];
+    let worker = build_service_worker(&None, &urls);
+    assert!(worker.contains(r#"const PRECACHE_URLS = ["/","/changelog/"];"#));
+
+    let cache_name = |worker: &str| {
+        worker
+            .lines()
+            .find(|line| line.starts_with("const CACHE_NAME"))
+            .unwrap()
+            .to_owned()
+    };
+    assert_eq!(
+        cache_name(&worker),
+        cache_name(&build_service_worker(&None, &urls))
+    );
+    assert_ne!(
+        cache_name(&worker),
+        cache_name(&build_service_worker(&None, &urls[..1]))
+    );
+}
@@ -0,0 +1,84 @@
+use oranda::config::Config;
+use oranda::data::org::{self, OrgProject, OrgRelease, OrgRepo};
+use oranda::site::org_index::OrgIndexContext;
+use oranda::site::page::Page;
+use oranda::site::templates::Templates;
+
+fn repo(name: &str, stars: u64, topics: &[&str]) -> OrgRepo {
+    OrgRepo {
+        name: name.to_owned(),
+        description: Some(format!("the {name} project")),
+        html_url: format!("https://github.com/axodotdev/{name}"),
+        homepage: Some(String::new()),
+        language: Some("Rust".to_owned()),
+        topics: topics.iter().map(|topic| topic.to_string()).collect(),
+        stargazers_count: stars,
+        fork: false,
+        archived: false,
+    }
+}
+
+#[test]
+fn it_skips_badges_for_the_excerpt() {
+    let readme = "# oranda\n\n[![ci](https://example.com/ci.svg)](https://example.com/ci) ![logo](logo.png)\n\n<p align=\"center\"><img src=\"banner.png\"></p>\n\n🎁 generate beautiful landing pages for your\n*developer tools*\n";
+    assert_eq!(
+        org::excerpt(readme).as_deref(),
+        Some("🎁 generate beautiful landing pages for your developer tools")
+    );
+}
+
+#[test]
+fn it_cuts_long_excerpts_between_words() {
+    let readme = "word, ".repeat(100);
+    let excerpt = org::excerpt(&readme).unwrap();
+    assert!(excerpt.chars().count() <= 201);
+    assert!(excerpt.ends_with("word…"));
+    assert_eq!(org::excerpt("# Only a title\n"), None);
+}
+
+#[test]
+fn it_filters_repos_by_topic() {
+    let tool = repo("oranda", 0, &["cli", "rust"]);
+    assert!(org::has_topic(&tool, &[]));
+    assert!(org::has_topic(&tool, &["web".to_owned(), "cli".to_owned()]));
+    assert!(!org::has_topic(&tool, &["web".to_owned()]));
+}
+
+#[test]
+fn it_renders_the_most_starred_projects_first() {
+    let config = Config::default();
+    let projects = [
+        OrgProject {
+            repo: repo("axolotlsay", 12, &[]),
+            readme_excerpt: None,
+            latest_release: None,
+        },
+        OrgProject {
+            repo: repo("oranda", 400, &[]),
+            readme_excerpt: Some("generate beautiful landing pages".to_owned()),
+            latest_release: Some(OrgRelease {
+                tag_name: "v0.6.0".to_owned(),
+                html_url: "https://github.com/axodotdev/oranda/releases/tag/v0.6.0".to_owned(),
+                published_at: Some("2023-12-04T18:01:36Z".to_owned()),
+            }),
+        },
+    ];
+    let context = OrgIndexContext::new("axodotdev", &projects);
+    assert_eq!(context.projects[0].name, "oranda");
+    assert_eq!(context.projects[0].homepage, None);
+
+    let templates = Templates::new_for_workspace_index(&config).unwrap();
+    let page = Page::new_from_template(
+        "index.html",
+        &templates,
+        "workspace_index/org.html",
+        &context,
+    )
+    .unwrap();
+    let oranda = page.contents.find("<h4>oranda</h4>").unwrap();
+    let axolotlsay = page.contents.find("<h4>axolotlsay</h4>").unwrap();
+    assert!(oranda < axolotlsay);
+    assert!(page.contents.contains("generate beautiful landing pages"));
+    assert!(page.contents.contains(">v0.6.0</a>"));
+    assert!(!page.contents.contains(">Website</a>"));
+}
@@ -0,0 +1,65 @@
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use camino::Utf8PathBuf;
+use oranda::config::{ArtifactsConfig, Config};
+use oranda::data::Context;
+use oranda::site::oranda_theme::OrandaTheme;
+use oranda::site::page::Page;
+use oranda::site::templates::Templates;
+
+fn render(config: &Config) -> String {
+    let context = Context {
+        repo: None,
+        releases: vec![],
+        has_prereleases: false,
+        latest_release: None,
+        has_artifacts: false,
+        channels: vec![],
+    };
+    let templates = Templates::new(config, Some(&context)).unwrap();
+    let (page, _) = Page::new_from_markdown("README.md", &templates, config, None, true).unwrap();
+    page.contents
+}
+
+#[test]
+fn it_hints_the_stylesheets_and_next_pages() {
+    let root = TempDir::new().unwrap();
+    root.child("README.md").write_str("# axolotlsay").unwrap();
+    let mut config = Config {
+        root_dir: Utf8PathBuf::from_path_buf(root.path().to_owned()).unwrap(),
+        ..Default::default()
+    };
+    config.build.path_prefix = Some(String::from("axolotlsay"));
+    config.styles.theme = OrandaTheme::Hacker;
+    config.components.artifacts = Some(ArtifactsConfig {
+        cargo_dist: true,
+        ..Default::default()
+    });
+
+    let contents = render(&config);
+    let css_path = contents
+        .split(r#"<link rel="stylesheet" href=""#)
+        .nth(1)
+        .and_then(|rest| rest.split('"').next())
+        .unwrap();
+    assert!(contents.contains(&format!(
+        r#"<link rel="preload" href="{css_path}" as="style" />"#
+    )));
+    let fonts = contents
+        .lines()
+        .filter(|line| line.contains("rel=\"preload\"") && line.contains("fonts.googleapis.com"))
+        .count();
+    // Fira Sans, which every theme uses, and IBM Plex Mono for the hacker theme
+    assert_eq!(fonts, 2);
+    assert!(contents.contains("family=IBM+Plex+Mono"));
+    assert!(contents.contains(r#"<link rel="prefetch" href="/axolotlsay/artifacts/" />"#));
+    assert!(contents.contains(r#"<link rel="prefetch" href="/axolotlsay/changelog/" />"#));
+
+    // Without the pages, there's nothing to prefetch
+    config.components.artifacts = None;
+    config.components.changelog = None;
+    config.styles.theme = OrandaTheme::Light;
+    let contents = render(&config);
+    assert!(!contents.contains(r#"rel="prefetch""#));
+    assert_eq!(contents.matches("fonts.googleapis.com/css2").count(), 1);
+}
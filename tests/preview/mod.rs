@@ -0,0 +1,47 @@
+use oranda::config::Config;
+use oranda::site::layout::preview::{context, pull_request_link};
+
+#[test]
+fn it_nests_under_the_prefix_without_analytics() {
+    let mut config = Config::default();
+    config.build.path_prefix = Some("axolotlsay/".to_owned());
+    config.build.preview = Some("pr-42".to_owned());
+    config.marketing.analytics =
+        Some(serde_json::from_str(r#"{"plausible": {"domain": "example.com"}}"#).unwrap());
+    config.apply_preview();
+    assert_eq!(
+        config.build.path_prefix.as_deref(),
+        Some("axolotlsay/pr-42")
+    );
+    assert!(config.marketing.analytics.is_none());
+
+    let mut config = Config::default();
+    config.apply_preview();
+    assert_eq!(config.build.path_prefix, None);
+}
+
+#[test]
+fn it_links_pull_requests_on_github() {
+    let repo = Some("https://github.com/axodotdev/axolotlsay");
+    assert_eq!(
+        pull_request_link("pr-42", repo).as_deref(),
+        Some("https://github.com/axodotdev/axolotlsay/pull/42")
+    );
+    assert_eq!(
+        pull_request_link("PR42", repo).as_deref(),
+        Some("https://github.com/axodotdev/axolotlsay/pull/42")
+    );
+    assert_eq!(pull_request_link("staging", repo), None);
+    assert_eq!(pull_request_link("pr-42", None), None);
+    assert_eq!(
+        pull_request_link("pr-42", Some("https://gitlab.com/axodotdev/axolotlsay")),
+        None
+    );
+
+    let mut config = Config::default();
+    config.project.repository = repo.map(str::to_owned);
+    config.build.preview = Some("staging".to_owned());
+    let preview = context(&config).unwrap();
+    assert_eq!(preview.name, "staging");
+    assert_eq!(preview.link, None);
+}
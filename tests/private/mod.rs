@@ -0,0 +1,65 @@
+use oranda::config::{Config, PrivateHost};
+use oranda::errors::OrandaError;
+use oranda::site::page::PageFrontmatter;
+use oranda::site::private::{access_credentials, is_private_output, netlify_headers};
+
+#[test]
+fn it_reads_private_from_frontmatter() {
+    let (frontmatter, body) = PageFrontmatter::parse("---\nprivate: true\n---\n# Runbook\n");
+    assert!(frontmatter.private);
+    assert_eq!(body.trim(), "# Runbook");
+    assert!(!PageFrontmatter::parse("# Public\n").0.private);
+}
+
+#[test]
+fn it_matches_whole_path_segments() {
+    let mut config = Config::default();
+    assert!(is_private_output("internal/runbook.html", &config));
+    assert!(!is_private_output("internals.html", &config));
+    assert!(!is_private_output("internal-notes/index.html", &config));
+    config.build.private_path = String::from("./team/internal/");
+    assert!(is_private_output("team/internal/runbook.html", &config));
+    assert!(!is_private_output("team/internals/runbook.html", &config));
+    assert!(!is_private_output("team/runbook.html", &config));
+}
+
+#[test]
+fn it_protects_the_private_path_on_netlify() {
+    let mut config = Config::default();
+    config.build.path_prefix = Some("docs".to_owned());
+    config.build.private_host = Some(PrivateHost::Netlify);
+
+    let credentials = access_credentials(&config, Some(" ops:hunter2\n".to_owned())).unwrap();
+    assert_eq!(credentials.as_deref(), Some("ops:hunter2"));
+    let headers = netlify_headers(&config, "ops:hunter2");
+    assert_eq!(
+        headers,
+        "/docs/internal/*\n  X-Robots-Tag: noindex\n  Basic-Auth: ops:hunter2\n"
+    );
+}
+
+#[test]
+fn it_fails_closed() {
+    // No host chosen
+    let mut config = Config::default();
+    assert!(matches!(
+        access_credentials(&config, Some("ops:hunter2".to_owned())),
+        Err(OrandaError::PrivateHostMissing { .. })
+    ));
+    // Netlify without credentials
+    config.build.private_host = Some(PrivateHost::Netlify);
+    assert!(matches!(
+        access_credentials(&config, None),
+        Err(OrandaError::PrivateCredentialsMissing { .. })
+    ));
+    assert!(matches!(
+        access_credentials(&config, Some("  ".to_owned())),
+        Err(OrandaError::PrivateCredentialsMissing { .. })
+    ));
+    // Opting out, and nothing gets written
+    config.build.private_host = Some(PrivateHost::None);
+    assert_eq!(
+        access_credentials(&config, Some("ops:hunter2".to_owned())).unwrap(),
+        None
+    );
+}
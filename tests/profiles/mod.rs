@@ -0,0 +1,84 @@
+use assert_fs::prelude::*;
+use camino::Utf8Path;
+use oranda::config::profiles;
+use oranda::config::{Config, ConfigOptions, OrandaLayer};
+use oranda::errors::OrandaError;
+use oranda::site::oranda_theme::OrandaTheme;
+
+const CONFIG: &str = r#"{
+    "styles": { "theme": "light" },
+    "profiles": {
+        "dev": {
+            "marketing": { "analytics": false },
+            "build": { "path_prefix": "" }
+        },
+        "production": {
+            "styles": { "theme": "hacker" },
+            "build": { "path_prefix": "axolotlsay" }
+        }
+    }
+}"#;
+
+fn config() -> OrandaLayer {
+    serde_json::from_str(CONFIG).unwrap()
+}
+
+#[test]
+fn it_picks_a_profile_by_name() {
+    let profiles = config().profiles.unwrap();
+    let production = profiles::select(profiles, "production", true)
+        .unwrap()
+        .unwrap();
+    assert_eq!(production.styles.unwrap().theme, Some(OrandaTheme::Hacker));
+    assert_eq!(
+        production.build.unwrap().path_prefix.as_deref(),
+        Some("axolotlsay")
+    );
+    assert!(production.profiles.is_none());
+
+    let profiles = config().profiles.unwrap();
+    let dev = profiles::select(profiles, "dev", true).unwrap().unwrap();
+    let marketing = serde_json::to_value(dev.marketing.unwrap()).unwrap();
+    assert_eq!(marketing["analytics"], false);
+}
+
+#[test]
+fn it_applies_the_profile_its_given() {
+    let project = assert_fs::TempDir::new().unwrap();
+    project.child("oranda.json").write_str(CONFIG).unwrap();
+    let root = Utf8Path::from_path(project.path()).unwrap();
+    let options = ConfigOptions {
+        profile: Some("production".to_owned()),
+        ..Default::default()
+    };
+    let config = Config::build_in_with(root, Utf8Path::new("oranda.json"), &options).unwrap();
+    assert_eq!(config.styles.theme, OrandaTheme::Hacker);
+    assert_eq!(config.build.path_prefix.as_deref(), Some("axolotlsay"));
+
+    // Without one, the profiles are left out
+    let config = Config::build_in(root, Utf8Path::new("oranda.json")).unwrap();
+    assert_eq!(config.styles.theme, OrandaTheme::Light);
+}
+
+#[test]
+fn it_complains_about_profiles_that_dont_exist() {
+    let profiles = config().profiles.unwrap();
+    let error = profiles::select(profiles, "prod", true).unwrap_err();
+    assert!(matches!(
+        error,
+        OrandaError::UnknownProfile { ref name, ref available }
+            if name == "prod" && available == "dev, production"
+    ));
+
+    // Workspace members only use a profile if they have it
+    let profiles = config().profiles.unwrap();
+    assert!(profiles::select(profiles, "prod", false).unwrap().is_none());
+}
+
+#[test]
+fn profiles_cant_set_workspace_keys() {
+    let result = serde_json::from_str::<OrandaLayer>(
+        r#"{ "profiles": { "dev": { "workspace": { "auto": true } } } }"#,
+    );
+    assert!(result.is_err());
+}
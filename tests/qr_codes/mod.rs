@@ -0,0 +1,118 @@
+use oranda::config::{ArtifactsConfig, Config};
+use oranda::data::artifacts::ReleaseArtifacts;
+use oranda::data::{Context, Release, ReleaseSource};
+use oranda::site::artifacts::{qr, template_context};
+use oranda::site::page::Page;
+use oranda::site::templates::Templates;
+
+use crate::utils::github::release_with_assets;
+
+fn config(qr_codes: bool) -> Config {
+    let mut config = Config::default();
+    let mut artifacts = ArtifactsConfig {
+        qr_codes,
+        ..ArtifactsConfig::default()
+    };
+    artifacts.matching.installers = vec![r"\.apk$".to_owned()];
+    artifacts.target_labels.insert(
+        "aarch64-linux-android".to_owned(),
+        "Android (ARM64)".to_owned(),
+    );
+    config.components.artifacts = Some(artifacts);
+    config.project.homepage = Some("https://app.example.com".to_owned());
+    config
+}
+
+fn context(config: &Config) -> Context {
+    let artifacts_config = config.components.artifacts.as_ref().unwrap();
+    let gh_release = release_with_assets(
+        "v1.0.0",
+        &[
+            ("app-v1.0.0.apk", 0),
+            ("app-v1.0.0-aarch64-linux-android.tar.gz", 0),
+            ("app-v1.0.0-x86_64-unknown-linux-gnu.tar.gz", 0),
+        ],
+    );
+    let mut artifacts = ReleaseArtifacts::new(None);
+    artifacts.add_github(&gh_release);
+    artifacts.add_inference(artifacts_config);
+    artifacts.select_installers(artifacts_config);
+    Context {
+        repo: None,
+        releases: vec![Release {
+            manifest: None,
+            source: ReleaseSource::Github(gh_release),
+            artifacts,
+        }],
+        has_prereleases: false,
+        latest_release: Some(0),
+        has_artifacts: true,
+        channels: vec![],
+    }
+}
+
+fn artifacts_page(config: &Config) -> String {
+    let context = context(config);
+    let templates = Templates::new(config, Some(&context)).unwrap();
+    let template_context = template_context(&context, config).unwrap();
+    Page::new_from_template(
+        "artifacts.html",
+        &templates,
+        "artifacts.html",
+        &template_context,
+    )
+    .unwrap()
+    .contents
+}
+
+#[test]
+fn it_knows_which_files_are_for_phones() {
+    let none = Vec::<String>::new();
+    assert!(qr::is_mobile("app.apk", none.iter()));
+    assert!(qr::is_mobile("app.ipa", none.iter()));
+    assert!(!qr::is_mobile("app.tar.gz", none.iter()));
+    let android = ["aarch64-linux-android".to_owned()];
+    assert!(qr::is_mobile("app.tar.gz", android.iter()));
+    let linux = ["x86_64-unknown-linux-gnu".to_owned()];
+    assert!(!qr::is_mobile("app.tar.gz", linux.iter()));
+}
+
+#[test]
+fn it_draws_qr_codes_as_svgs() {
+    let svg = qr::svg("https://app.example.com", "QR code for <app>").unwrap();
+    assert!(svg.starts_with("<svg class=\"qr-code\""));
+    assert!(svg.contains("role=\"img\""));
+    assert!(svg.contains("aria-label=\"QR code for &lt;app>\""));
+    // The smallest QR code is 21 modules wide, plus the quiet zone on each side
+    assert!(svg.contains("viewBox=\"0 0 29 29\"") || svg.contains("viewBox=\"0 0 33 33\""));
+}
+
+#[test]
+fn it_shows_qr_codes_next_to_mobile_files() {
+    let page = artifacts_page(&config(true));
+    assert!(page.contains("<th>QR code</th>"));
+    assert!(page.contains("aria-label=\"QR code for downloading app-v1.0.0.apk\""));
+    assert!(page.contains(
+        "aria-label=\"QR code for downloading app-v1.0.0-aarch64-linux-android.tar.gz\""
+    ));
+    assert!(!page.contains("QR code for downloading app-v1.0.0-x86_64-unknown-linux-gnu.tar.gz"));
+    assert!(page.contains("class=\"site-qr-code\""));
+    assert!(page.contains("aria-label=\"QR code for https://app.example.com\""));
+}
+
+#[test]
+fn it_only_shows_qr_codes_when_asked() {
+    let page = artifacts_page(&config(false));
+    assert!(!page.contains("qr-code"));
+    assert!(!page.contains("<th>QR code</th>"));
+}
+
+#[test]
+fn it_needs_a_homepage_for_the_site_qr_code() {
+    let mut config = config(true);
+    config.project.homepage = None;
+    let page = artifacts_page(&config);
+    assert!(!page.contains("site-qr-code"));
+    // Files hosted elsewhere still get one
+    assert!(page.contains("QR code for downloading app-v1.0.0.apk"));
+}
@@ -0,0 +1,78 @@
+use assert_fs::prelude::*;
+use camino::Utf8PathBuf;
+use oranda::site::rebuild::{self, DependencyGraph, Incremental, Input, Output, Rebuild};
+
+fn path(dir: &assert_fs::TempDir, file: &str) -> Utf8PathBuf {
+    Utf8PathBuf::from_path_buf(dir.path().canonicalize().unwrap())
+        .unwrap()
+        .join(file)
+}
+
+fn graph(dir: &assert_fs::TempDir) -> DependencyGraph {
+    dir.child("oranda.json").write_str("{}").unwrap();
+    dir.child("README.md").write_str("# axolotlsay\n").unwrap();
+    dir.child("docs/install.md")
+        .write_str("# Install\n")
+        .unwrap();
+    dir.child("book/src/SUMMARY.md")
+        .write_str("# Summary\n")
+        .unwrap();
+    let mut graph = DependencyGraph::new();
+    graph.add(path(dir, "oranda.json"), Input::Config);
+    graph.add(path(dir, "README.md"), Input::Readme);
+    graph.add(path(dir, "docs/install.md"), Input::AdditionalPage);
+    graph.add(path(dir, "book/src"), Input::Mdbook);
+    graph
+}
+
+#[test]
+fn it_only_rerenders_pages_when_a_page_changes() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let rebuild = graph(&dir).affected(&[path(&dir, "docs/install.md")]);
+    assert!(rebuild.includes(Output::Pages));
+    assert!(!rebuild.includes(Output::Releases));
+    assert!(!rebuild.includes(Output::Mdbook));
+    assert_eq!(rebuild.to_string(), "pages");
+}
+
+#[test]
+fn it_only_rebuilds_the_book_when_its_sources_change() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let graph = graph(&dir);
+    // A new chapter isn't in the graph itself, but it's in the book's source directory
+    dir.child("book/src/new-chapter.md")
+        .write_str("# New\n")
+        .unwrap();
+    let rebuild = graph.affected(&[path(&dir, "book/src/new-chapter.md")]);
+    assert_eq!(rebuild, Rebuild::from(&[Output::Mdbook][..]));
+}
+
+#[test]
+fn it_rebuilds_everything_for_the_config_or_unknown_files() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let graph = graph(&dir);
+    assert!(graph.affected(&[path(&dir, "oranda.json")]).is_everything());
+    let mut rebuild = graph.affected(&[path(&dir, "README.md"), path(&dir, "deleted.md")]);
+    assert!(rebuild.is_everything());
+
+    rebuild = graph.affected(&[path(&dir, "README.md")]);
+    rebuild.merge(&graph.affected(&[path(&dir, "book/src/SUMMARY.md")]));
+    assert_eq!(rebuild.to_string(), "pages, mdbook");
+}
+
+#[test]
+fn it_only_reuses_what_was_fetched_when_the_plan_leaves_out_the_releases() {
+    let mut incremental = Incremental::new();
+    assert!(rebuild::is_full_build(Some(incremental.plan())));
+    incremental.keep("public", None, None);
+    assert!(incremental.kept("public").is_none());
+
+    incremental.set_plan(Rebuild::from(&[Output::Pages][..]));
+    assert!(!rebuild::is_full_build(Some(incremental.plan())));
+    assert!(incremental.kept("public").is_some());
+    assert!(incremental.kept("other/public").is_none());
+
+    // Builds outside of `oranda dev` have no plan, and make everything
+    assert!(rebuild::is_full_build(None));
+    assert!(rebuild::should_build(None, Output::Mdbook));
+}
@@ -0,0 +1,67 @@
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use oranda::config::{Config, RelativeLinkTarget};
+use oranda::site::markdown::relative_links::{is_relative, rewrite};
+
+fn config(dist: &TempDir) -> Config {
+    let mut config = Config::default();
+    config.build.dist_dir = dist.path().display().to_string();
+    config.project.repository = Some("https://github.com/axodotdev/oranda".to_owned());
+    config
+}
+
+#[test]
+fn it_only_rewrites_relative_links() {
+    assert!(is_relative("./docs/setup.md"));
+    assert!(is_relative("assets/logo.png"));
+    assert!(!is_relative("https://example.com"));
+    assert!(!is_relative("mailto:hi@example.com"));
+    assert!(!is_relative("/docs/"));
+    assert!(!is_relative("#install"));
+}
+
+#[test]
+fn it_links_to_the_repo_and_bundles_images() {
+    let dist = TempDir::new().unwrap();
+    let config = config(&dist);
+    let html = r#"<p><a href="./docs/src/cli.md#build">CLI</a> <a href="docs/src">Docs</a> <a href="https://example.com">Out</a> <img src="docs/src/images/quickstart-1.png" alt="Quickstart"></p>"#;
+    let html = rewrite(html, "README.md", &config).unwrap();
+    assert!(html
+        .contains(r#"href="https://github.com/axodotdev/oranda/blob/HEAD/docs/src/cli.md#build""#));
+    assert!(html.contains(r#"href="https://github.com/axodotdev/oranda/tree/HEAD/docs/src""#));
+    assert!(html.contains(r#"href="https://example.com""#));
+    assert!(html.contains(r#"<img src="/docs/src/images/quickstart-1.png" alt="Quickstart">"#));
+    assert!(dist.child("docs/src/images/quickstart-1.png").exists());
+}
+
+#[test]
+fn it_follows_the_configured_targets() {
+    let dist = TempDir::new().unwrap();
+    let mut config = config(&dist);
+    config.markdown.relative_links.links = RelativeLinkTarget::Keep;
+    config.markdown.relative_links.images = RelativeLinkTarget::Repo;
+    let html = r#"<a href="../cli.md">CLI</a><img src="../images/quickstart-1.png">"#;
+    let html = rewrite(html, "docs/src/configuration/reference.md", &config).unwrap();
+    assert!(html.contains(r#"href="../cli.md""#));
+    assert!(html.contains(
+        r#"src="https://raw.githubusercontent.com/axodotdev/oranda/HEAD/docs/src/images/quickstart-1.png""#
+    ));
+}
+
+#[test]
+fn it_links_to_additional_pages() {
+    let dist = TempDir::new().unwrap();
+    let mut config = config(&dist);
+    config.build.path_prefix = Some("oranda".to_owned());
+    config
+        .build
+        .additional_pages
+        .insert("CLI".to_owned(), "./docs/src/cli.md".to_owned());
+    let html = rewrite(
+        r#"<a href="docs/src/cli.md#build">CLI</a>"#,
+        "README.md",
+        &config,
+    )
+    .unwrap();
+    assert_eq!(html, r#"<a href="/oranda/docs/src/cli/#build">CLI</a>"#);
+}
@@ -0,0 +1,66 @@
+use axoproject::GithubRepo;
+use camino::Utf8Path;
+use oranda::data::github::GithubRelease;
+use oranda::data::{Context, Release, ReleaseSource, MAX_CONCURRENT_FETCHES};
+use serde_json::json;
+
+use super::utils::tokio_utils::TEST_RUNTIME;
+
+fn github_release(tag: &str) -> GithubRelease {
+    serde_json::from_value(json!({
+        "url": "",
+        "assets_url": "",
+        "html_url": "",
+        "id": 1,
+        "tag_name": tag,
+        "target_commitish": "main",
+        "name": null,
+        "draft": false,
+        "prerelease": false,
+        "created_at": "2023-01-01T00:00:00Z",
+        "published_at": "2023-01-01T00:00:00Z",
+        "assets": [],
+        "tarball_url": "",
+        "zipball_url": "",
+        "body": null,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn it_keeps_releases_in_order_when_processing_them_concurrently() {
+    let repo = GithubRepo::from_url("https://github.com/axodotdev/axolotlsay").unwrap();
+    let tags: Vec<_> = (0..MAX_CONCURRENT_FETCHES * 3)
+        .map(|n| format!("v0.{n}.0"))
+        .collect();
+    let sources = tags
+        .iter()
+        .map(|tag| ReleaseSource::Github(github_release(tag)));
+    let releases = TEST_RUNTIME
+        .block_on(Context::process_releases(
+            Utf8Path::new(""),
+            sources,
+            &repo,
+            None,
+        ))
+        .unwrap();
+    let processed: Vec<_> = releases
+        .iter()
+        .map(|release| release.source.version_tag())
+        .collect();
+    assert_eq!(processed, tags);
+}
+
+#[test]
+fn it_parses_dist_manifests() {
+    let manifest = json!({
+        "dist_version": "0.10.0",
+        "announcement_tag": "v0.1.0",
+        "releases": [],
+        "artifacts": {},
+    });
+    let body = serde_json::to_vec(&manifest).unwrap();
+    let manifest = Release::parse_manifest(&body, "v0.1.0").unwrap();
+    assert_eq!(manifest.announcement_tag.as_deref(), Some("v0.1.0"));
+    assert!(Release::parse_manifest(b"{ \"releases\": 5 }", "v0.1.0").is_none());
+}
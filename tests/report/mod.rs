@@ -0,0 +1,47 @@
+use std::time::Duration;
+
+use assert_fs::prelude::*;
+use camino::Utf8PathBuf;
+use oranda::report::{build_report, record_components, record_releases, start};
+
+#[test]
+fn it_reports_what_a_build_wrote() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    dir.child("index.html").write_str("<h1>hi</h1>").unwrap();
+    dir.child("changelog/index.html")
+        .write_str("<h1>new</h1>")
+        .unwrap();
+    dir.child("oranda.css").write_str("body {}").unwrap();
+    let dist = Utf8PathBuf::from_path_buf(dir.path().to_owned()).unwrap();
+
+    // Other tests build sites at the same time, so only check what this one recorded
+    start();
+    record_components(&["artifacts", "changelog"]);
+    record_components(&["changelog"]);
+    record_releases(3);
+    let report = build_report(&dist, Duration::from_millis(1500)).unwrap();
+    assert_eq!(report.pages, vec!["changelog/index.html", "index.html"]);
+    assert_eq!(report.files_written, 3);
+    assert_eq!(report.bytes_written, 30);
+    assert_eq!(report.duration_ms, 1500);
+    assert!(report.components.contains(&"artifacts"));
+    assert_eq!(
+        report
+            .components
+            .iter()
+            .filter(|component| **component == "changelog")
+            .count(),
+        1
+    );
+    assert!(report.releases_fetched >= 3);
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["pages"][1], "index.html");
+    assert!(json["warnings"].is_array());
+
+    // Building the report stops the recording, until the next build starts one
+    record_releases(3);
+    let report = build_report(&dist, Duration::from_millis(1500)).unwrap();
+    assert!(report.components.is_empty());
+    assert_eq!(report.releases_fetched, 0);
+}
@@ -0,0 +1,100 @@
+use assert_fs::prelude::*;
+use camino::Utf8PathBuf;
+use indexmap::IndexMap;
+use minijinja::context;
+use oranda::config::requirements::{from_manifest, from_pyproject, pretty};
+use oranda::config::Config;
+use oranda::site::templates::Templates;
+
+fn path(dir: &assert_fs::TempDir) -> Utf8PathBuf {
+    Utf8PathBuf::from_path_buf(dir.path().to_owned()).unwrap()
+}
+
+#[test]
+fn it_reads_rust_version() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    dir.child("Cargo.toml")
+        .write_str("[workspace.package]\nrust-version = \"1.74\"\n")
+        .unwrap();
+    dir.child("app/Cargo.toml")
+        .write_str("[package]\nname = \"app\"\nrust-version = \"1.70\"\n")
+        .unwrap();
+    dir.child("lib/Cargo.toml")
+        .write_str("[package]\nname = \"lib\"\nrust-version.workspace = true\n")
+        .unwrap();
+    dir.child("old/Cargo.toml")
+        .write_str("[package]\nname = \"old\"\n")
+        .unwrap();
+    let root = path(&dir);
+    let workspace = root.join("Cargo.toml");
+    assert_eq!(
+        from_manifest(&root.join("app/Cargo.toml"), &workspace),
+        IndexMap::from([("Rust".to_owned(), "≥ 1.70".to_owned())])
+    );
+    assert_eq!(
+        from_manifest(&root.join("lib/Cargo.toml"), &workspace),
+        IndexMap::from([("Rust".to_owned(), "≥ 1.74".to_owned())])
+    );
+    assert!(from_manifest(&root.join("old/Cargo.toml"), &workspace).is_empty());
+}
+
+#[test]
+fn it_reads_engines_and_requires_python() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    dir.child("package.json")
+        .write_str(r#"{ "name": "app", "engines": { "node": ">=18 <22", "pnpm": "^8" } }"#)
+        .unwrap();
+    dir.child("pyproject.toml")
+        .write_str("[project]\nname = \"app\"\nrequires-python = \">=3.8\"\n")
+        .unwrap();
+    let root = path(&dir);
+    assert_eq!(
+        from_manifest(&root.join("package.json"), &root.join("package.json")),
+        IndexMap::from([
+            ("Node.js".to_owned(), "≥ 18 <22".to_owned()),
+            ("pnpm".to_owned(), "^8".to_owned()),
+        ])
+    );
+    assert_eq!(
+        from_pyproject(&root),
+        IndexMap::from([("Python".to_owned(), "≥ 3.8".to_owned())])
+    );
+}
+
+#[test]
+fn it_prettifies_requirements() {
+    assert_eq!(pretty(">=1.70"), "≥ 1.70");
+    assert_eq!(pretty(">= 3.8, <=3.12"), "≥ 3.8, ≤ 3.12");
+    assert_eq!(pretty("~1.2"), "~1.2");
+}
+
+#[test]
+fn it_shows_requirements_on_the_platforms_page() {
+    let mut config = Config::default();
+    config
+        .project
+        .requirements
+        .insert("Rust".to_owned(), "≥ 1.70".to_owned());
+    config
+        .project
+        .requirements
+        .insert("glibc".to_owned(), "≥ 2.31".to_owned());
+    let templates = Templates::new(&config, None).unwrap();
+    let page = templates
+        .render_to_string(
+            "support_matrix.html",
+            context!(rows => Vec::<String>::new(), statuses => Vec::<String>::new()),
+        )
+        .unwrap();
+    assert!(page.contains(r#"<span class="requirement">Rust ≥ 1.70</span>"#));
+    assert!(page.contains(r#"<span class="requirement">glibc ≥ 2.31</span>"#));
+
+    let page = Templates::new(&Config::default(), None)
+        .unwrap()
+        .render_to_string(
+            "support_matrix.html",
+            context!(rows => Vec::<String>::new(), statuses => Vec::<String>::new()),
+        )
+        .unwrap();
+    assert!(!page.contains("Requires"));
+}
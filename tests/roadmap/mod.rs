@@ -0,0 +1,132 @@
+use minijinja::Value;
+use oranda::config::{Config, MarkdownConfig, MarkdownSanitize, RoadmapConfig};
+use oranda::data::roadmap::{parse_issues, parse_milestones};
+use oranda::site::roadmap::RoadmapContext;
+use oranda::site::templates::Templates;
+
+const MILESTONES: &str = r#"[
+  {
+    "number": 3,
+    "title": "Someday",
+    "description": "",
+    "due_on": null,
+    "open_issues": 4,
+    "closed_issues": 0,
+    "html_url": "https://github.com/axodotdev/axolotlsay/milestone/3"
+  },
+  {
+    "number": 2,
+    "title": "v1.0",
+    "description": "The **big** one",
+    "due_on": "2025-03-01T08:00:00Z",
+    "open_issues": 1,
+    "closed_issues": 3,
+    "html_url": "https://github.com/axodotdev/axolotlsay/milestone/2"
+  }
+]"#;
+
+const ISSUES: &str = r#"[
+  {
+    "number": 12,
+    "title": "Add a --loud flag",
+    "html_url": "https://github.com/axodotdev/axolotlsay/issues/12",
+    "state": "closed"
+  },
+  {
+    "number": 15,
+    "title": "Implement --loud",
+    "html_url": "https://github.com/axodotdev/axolotlsay/pull/15",
+    "state": "open",
+    "pull_request": { "url": "https://api.github.com/repos/axodotdev/axolotlsay/pulls/15" }
+  }
+]"#;
+
+#[test]
+fn it_reads_milestones_soonest_due_first() {
+    let milestones = parse_milestones(MILESTONES).unwrap();
+    assert_eq!(milestones.len(), 2);
+    assert_eq!(milestones[0].title, "v1.0");
+    assert_eq!(milestones[0].due_on.as_deref(), Some("2025-03-01"));
+    assert_eq!(milestones[0].percent_complete, 75);
+    assert_eq!(milestones[1].due_on, None);
+    assert_eq!(milestones[1].description, None);
+    assert_eq!(milestones[1].percent_complete, 0);
+}
+
+#[test]
+fn it_reads_milestone_issues() {
+    let issues = parse_issues(ISSUES).unwrap();
+    assert_eq!(issues.len(), 2);
+    assert!(issues[0].closed);
+    assert!(!issues[0].is_pull_request);
+    assert!(!issues[1].closed);
+    assert!(issues[1].is_pull_request);
+}
+
+#[test]
+fn it_renders_the_roadmap() {
+    let config = Config::default();
+    let mut milestones = parse_milestones(MILESTONES).unwrap();
+    milestones[0].issues = parse_issues(ISSUES).unwrap();
+    let context = RoadmapContext::from_milestones(milestones, &config).unwrap();
+    let templates = Templates::new(&config, None).unwrap();
+    let page = templates
+        .render_to_string("roadmap.html", Value::from_serializable(&context))
+        .unwrap();
+    assert!(page.contains(r#">v1.0</a></h2>"#));
+    assert!(page.contains(r#"<time datetime="2025-03-01">"#));
+    assert!(page.contains("75% complete"));
+    assert!(page.contains("<strong>big</strong>"));
+    assert!(page.contains(r#"<li class="milestone-issue-closed">"#));
+    assert!(page.find("v1.0").unwrap() < page.find("Someday").unwrap());
+}
+
+#[test]
+fn it_doesnt_trust_text_from_github() {
+    let config = Config {
+        markdown: MarkdownConfig {
+            sanitize: MarkdownSanitize::Releases,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut milestones = parse_milestones(MILESTONES).unwrap();
+    milestones[1].title = String::from("<img src=x onerror=alert(1)>");
+    milestones[1].description = Some(String::from(
+        "<p style=\"position:fixed\">Soon</p>\n\n[docs](javascript:alert(1))",
+    ));
+    milestones[1].issues = parse_issues(ISSUES).unwrap();
+    milestones[1].issues[0].title = String::from("<script>alert(1)</script>");
+    let context = RoadmapContext::from_milestones(milestones, &config).unwrap();
+    let templates = Templates::new(&config, None).unwrap();
+    let page = templates
+        .render_to_string("roadmap.html", Value::from_serializable(&context))
+        .unwrap();
+    assert!(page.contains("&lt;img src=x onerror=alert(1)&gt;"));
+    assert!(page.contains("&lt;script&gt;alert(1)&lt;&#x2f;script&gt;"));
+    assert!(!page.contains("<script>alert"));
+    assert!(!page.contains("position:fixed"));
+    assert!(!page.contains("javascript:"));
+}
+
+#[test]
+fn it_says_when_there_are_no_milestones() {
+    let config = Config::default();
+    let context = RoadmapContext::from_milestones(vec![], &config).unwrap();
+    let templates = Templates::new(&config, None).unwrap();
+    let page = templates
+        .render_to_string("roadmap.html", Value::from_serializable(&context))
+        .unwrap();
+    assert!(page.contains("has no open milestones right now"));
+}
+
+#[test]
+fn it_links_the_roadmap_from_the_nav() {
+    let mut config = Config::default();
+    config.components.roadmap = Some(RoadmapConfig::default());
+    let templates = Templates::new(&config, None).unwrap();
+    let links = templates.layout.nav_links();
+    assert!(links
+        .iter()
+        .any(|link| link.name == "Roadmap" && link.path == "/roadmap/"));
+}
@@ -0,0 +1,25 @@
+use oranda::config::{Config, RustdocConfig};
+use oranda::site::page::Page;
+use oranda::site::rustdoc::context;
+use oranda::site::templates::Templates;
+
+#[test]
+fn it_renders_the_api_page_under_rustdoc() {
+    let mut config = Config::default();
+    config.project.name = String::from("my-crate");
+    config.build.path_prefix = Some("docs".to_owned());
+    config.components.rustdoc = Some(RustdocConfig::default());
+    let templates = Templates::new(&config, None).unwrap();
+    let context = context(config.components.rustdoc.as_ref().unwrap(), &config);
+    let page =
+        Page::new_from_template("rustdoc.html", &templates, "rustdoc.html", &context).unwrap();
+
+    assert_eq!(page.filename, "rustdoc.html");
+    assert!(page
+        .contents
+        .contains(r#"<li><a href="/docs/rustdoc/">API</a></li>"#));
+    assert!(page
+        .contents
+        .contains(r#"<iframe class="rustdoc-frame" src="/docs/rustdoc/my_crate/index.html""#));
+    assert!(!page.contents.contains("/docs/api/"));
+}
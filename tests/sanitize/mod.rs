@@ -0,0 +1,67 @@
+use oranda::config::{Config, MarkdownSanitize};
+use oranda::site::markdown::{release_to_html, to_html};
+
+const NOTES: &str = r#"Fixed a bug[^1] in `main`.
+
+<script>alert("hi")</script>
+<iframe src="https://example.com"></iframe>
+<img src="x.png" onerror="alert('hi')">
+<p style="position: fixed" id="nav">Click me</p>
+<span style="position:fixed;inset:0">Overlay</span>
+<pre style="position:fixed;inset:0">Overlay</pre>
+<a href="javascript:alert('hi')">Innocent link</a>
+
+{{ button href="/" text="Shortcode" }}
+
+[^1]: A very bad one.
+"#;
+
+#[test]
+fn it_always_strips_scripts_and_event_handlers() {
+    let html = to_html(NOTES, &Config::default()).unwrap();
+    assert!(!html.contains("<script"));
+    assert!(!html.contains("<iframe"));
+    assert!(!html.contains("onerror"));
+    // Trusted markdown can still style things and use shortcodes
+    assert!(html.contains(r#"style="position: fixed""#));
+    assert!(html.contains(r#"<a class="button primary""#));
+}
+
+#[test]
+fn it_sanitizes_untrusted_release_notes_strictly() {
+    let mut config = Config::default();
+    config.markdown.sanitize = MarkdownSanitize::Releases;
+    let html = release_to_html(NOTES, &config).unwrap();
+    assert!(!html.contains("<script"));
+    assert!(!html.contains("onerror"));
+    assert!(!html.contains("position: fixed"));
+    assert!(!html.contains("position:fixed"));
+    assert!(!html.contains("javascript:"));
+    assert!(!html.contains(r#"<a class="button"#));
+    assert!(html.contains(r#"id="user-content-nav""#));
+    assert!(html.contains(r##"href="#user-content-fn-1""##));
+    assert!(html.contains(r#"id="user-content-fn-1""#));
+
+    // Other pages are still trusted
+    let html = to_html(NOTES, &config).unwrap();
+    assert!(html.contains(r#"style="position: fixed""#));
+
+    config.markdown.sanitize = MarkdownSanitize::All;
+    let html = to_html(NOTES, &config).unwrap();
+    assert!(!html.contains("position: fixed"));
+}
+
+#[test]
+fn it_keeps_syntax_highlighting_in_untrusted_markdown() {
+    let mut config = Config::default();
+    config.markdown.sanitize = MarkdownSanitize::All;
+    let html = to_html(
+        "```rust\nfn main() {}\n```\n\n<span style=\"position:fixed\">Overlay</span>\n",
+        &config,
+    )
+    .unwrap();
+    assert!(html.contains("<pre style=\"background-color:"));
+    assert!(html.contains("<span style=\"color:"));
+    assert!(!html.contains("position:fixed"));
+    assert!(!html.contains("ORANDAHIGHLIGHTEDCODE"));
+}
@@ -0,0 +1,38 @@
+use oranda::data::artifacts::{ReleaseArtifacts, SbomFormat};
+
+use crate::utils::github::release_with_assets;
+
+#[test]
+fn it_finds_spdx_and_cyclonedx_sboms() {
+    let mut artifacts = ReleaseArtifacts::new(None);
+    artifacts.add_github(&release_with_assets(
+        "v1.0.0",
+        &[
+            ("app-x86_64-unknown-linux-gnu.tar.gz", 0),
+            ("app.cdx.xml", 0),
+            ("app.spdx.json", 0),
+            ("app.spdx", 0),
+        ],
+    ));
+    artifacts.add_sboms();
+
+    let sboms = artifacts
+        .sboms()
+        .iter()
+        .map(|sbom| {
+            (
+                artifacts.file(sbom.file).name.as_str(),
+                sbom.format,
+                sbom.label.as_str(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        sboms,
+        vec![
+            ("app.cdx.xml", SbomFormat::CycloneDx, "CycloneDX (XML)"),
+            ("app.spdx.json", SbomFormat::Spdx, "SPDX (JSON)"),
+            ("app.spdx", SbomFormat::Spdx, "SPDX (tag-value)"),
+        ]
+    );
+}
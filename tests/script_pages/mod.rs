@@ -0,0 +1,53 @@
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use oranda::config::{ArtifactsConfig, Config};
+use oranda::data::artifacts::ReleaseArtifacts;
+use oranda::data::{Release, ReleaseSource};
+use oranda::site::artifacts::script_pages;
+use oranda::site::templates::Templates;
+
+use crate::utils::github::release_with_assets;
+
+const SCRIPT: &str = "#!/bin/sh\necho \"installing\"\nexit 0\n";
+
+#[test]
+fn it_renders_scripts_with_line_anchors_and_checksums() {
+    let dist = TempDir::new().unwrap();
+    dist.child("app-installer.sh.txt")
+        .write_str(SCRIPT)
+        .unwrap();
+    let mut config = Config::default();
+    dist.path()
+        .to_str()
+        .unwrap()
+        .clone_into(&mut config.build.dist_dir);
+
+    let gh_release = release_with_assets("v1.0.0", &[("app-installer.sh", 0)]);
+    let mut artifacts = ReleaseArtifacts::new(None);
+    artifacts.add_github(&gh_release);
+    artifacts.add_inference(&ArtifactsConfig::default());
+    let idx = artifacts.file_idx(&"app-installer.sh".to_owned()).unwrap();
+    artifacts.file_mut(idx).view_path = Some("app-installer.sh.txt".to_owned());
+    let mut release = Release {
+        manifest: None,
+        source: ReleaseSource::Github(gh_release),
+        artifacts,
+    };
+
+    let templates = Templates::new(&config, None).unwrap();
+    let pages = script_pages(&mut release, &templates, &config).unwrap();
+    assert_eq!(pages.len(), 1);
+    let page = &pages[0];
+    assert_eq!(page.filename, "app-installer.sh.html");
+    assert!(page.contents.contains(r##"<tr id="L3">"##));
+    assert!(page.contents.contains(r##"<a href="#L3">3</a>"##));
+    // sha256 of SCRIPT
+    assert!(page
+        .contents
+        .contains("ea7e694a976f5d9b0dc67d24118b8317e4a644cc56731b85104830b368fcc8d6"));
+    assert!(page.contents.contains("echo &quot;installing&quot;"));
+    assert_eq!(
+        release.artifacts.file(idx).view_path.as_deref(),
+        Some("app-installer.sh.html")
+    );
+}
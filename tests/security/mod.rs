@@ -0,0 +1,130 @@
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use camino::Utf8Path;
+use minijinja::Value;
+use oranda::config::{AdvisorySource, Config, SecurityConfig};
+use oranda::data::security::{parse_github, parse_osv};
+use oranda::site::security::SecurityContext;
+use oranda::site::templates::Templates;
+
+const GITHUB_ADVISORIES: &str = r#"[
+  {
+    "ghsa_id": "GHSA-aaaa-bbbb-cccc",
+    "cve_id": "CVE-2023-0001",
+    "summary": "Old bug",
+    "severity": "low",
+    "published_at": "2023-01-02T03:04:05Z",
+    "html_url": "https://github.com/axodotdev/axolotlsay/security/advisories/GHSA-aaaa-bbbb-cccc"
+  },
+  {
+    "ghsa_id": "GHSA-dddd-eeee-ffff",
+    "cve_id": null,
+    "summary": "New bug",
+    "severity": "high",
+    "published_at": "2024-05-06T07:08:09Z",
+    "html_url": "https://github.com/axodotdev/axolotlsay/security/advisories/GHSA-dddd-eeee-ffff"
+  }
+]"#;
+
+const OSV_RESPONSE: &str = r#"{
+  "vulns": [
+    { "id": "GHSA-dddd-eeee-ffff", "summary": "New bug", "aliases": ["RUSTSEC-2024-0002"], "published": "2024-05-06T07:08:09Z" },
+    { "id": "RUSTSEC-2024-0002", "summary": "New bug", "aliases": ["GHSA-dddd-eeee-ffff"], "published": "2024-05-07T00:00:00Z" },
+    { "id": "RUSTSEC-2022-0001", "details": "Withdrawn bug", "published": "2022-01-01T00:00:00Z", "withdrawn": "2022-02-01T00:00:00Z" }
+  ]
+}"#;
+
+#[test]
+fn it_reads_github_advisories_newest_first() {
+    let advisories = parse_github(GITHUB_ADVISORIES).unwrap();
+    assert_eq!(advisories.len(), 2);
+    assert_eq!(advisories[0].id, "GHSA-dddd-eeee-ffff");
+    assert_eq!(advisories[0].published.as_deref(), Some("2024-05-06"));
+    assert_eq!(advisories[1].aliases, vec!["CVE-2023-0001".to_owned()]);
+    assert_eq!(advisories[1].severity.as_deref(), Some("low"));
+}
+
+#[test]
+fn it_keeps_rustsec_advisories_from_osv() {
+    let advisories = parse_osv(OSV_RESPONSE).unwrap();
+    assert_eq!(advisories.len(), 1);
+    assert_eq!(advisories[0].id, "RUSTSEC-2024-0002");
+    assert_eq!(
+        advisories[0].url,
+        "https://rustsec.org/advisories/RUSTSEC-2024-0002.html"
+    );
+    assert!(parse_osv("{}").unwrap().is_empty());
+}
+
+#[test]
+fn it_renders_the_policy_and_advisories() {
+    let mut config = Config::default();
+    config.project.name = String::from("axolotlsay");
+    config.components.security = Some(SecurityConfig {
+        advisories: Some(AdvisorySource::GitHub),
+        ..SecurityConfig::default()
+    });
+    let security_config = config.components.security.clone().unwrap();
+    let context = SecurityContext::from_parts(
+        Some("<h1>Security policy</h1>".to_owned()),
+        &security_config,
+        parse_github(GITHUB_ADVISORIES).unwrap(),
+    );
+    let templates = Templates::new(&config, None).unwrap();
+    let page = templates
+        .render_to_string("security.html", Value::from_serializable(&context))
+        .unwrap();
+    assert!(page.contains("<h1>Security policy</h1>"));
+    assert!(page.contains(r#"GHSA-dddd-eeee-ffff">GHSA-dddd-eeee-ffff</a>"#));
+    assert!(page.contains("<code>CVE-2023-0001</code>"));
+    assert!(page.find("New bug").unwrap() < page.find("Old bug").unwrap());
+    assert!(page.contains(r#"<a href="/security/">Security</a>"#));
+
+    let context = SecurityContext::from_parts(None, &security_config, vec![]);
+    let page = templates
+        .render_to_string("security.html", Value::from_serializable(&context))
+        .unwrap();
+    assert!(page.contains("No advisories have been published for axolotlsay"));
+}
+
+#[test]
+fn it_escapes_advisories() {
+    let response = r#"{
+      "vulns": [
+        { "id": "RUSTSEC-2024-<b>1</b>", "summary": "<img src=x onerror=alert(1)>", "aliases": ["<script>alert(1)</script>"], "published": "2024-05-07T00:00:00Z" }
+      ]
+    }"#;
+    let config = Config::default();
+    let security_config = SecurityConfig {
+        advisories: Some(AdvisorySource::GitHub),
+        ..SecurityConfig::default()
+    };
+    let context = SecurityContext::from_parts(None, &security_config, parse_osv(response).unwrap());
+    let templates = Templates::new(&config, None).unwrap();
+    let page = templates
+        .render_to_string("security.html", Value::from_serializable(&context))
+        .unwrap();
+    assert!(page.contains("RUSTSEC-2024-&lt;b&gt;1&lt;&#x2f;b&gt;</a>"));
+    assert!(page.contains("<p>&lt;img src=x onerror=alert(1)&gt;</p>"));
+    assert!(page.contains("<code>&lt;script&gt;alert(1)&lt;&#x2f;script&gt;</code>"));
+    assert!(!page.contains("<script>alert"));
+}
+
+#[test]
+fn it_renders_security_md() {
+    let project = TempDir::new().unwrap();
+    project
+        .child("SECURITY.md")
+        .write_str("# Reporting a vulnerability\n\nEmail us.")
+        .unwrap();
+    let config = Config {
+        root_dir: Utf8Path::from_path(project.path()).unwrap().to_owned(),
+        ..Config::default()
+    };
+    let context = SecurityContext::new(&SecurityConfig::default(), &config).unwrap();
+    assert!(context
+        .policy
+        .unwrap()
+        .contains("Reporting a vulnerability"));
+    assert!(context.advisories_source.is_none());
+}
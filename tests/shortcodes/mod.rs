@@ -0,0 +1,65 @@
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use oranda::config::Config;
+use oranda::site::markdown::shortcodes::parse_attributes;
+use oranda::site::markdown::to_html;
+
+#[test]
+fn it_reads_shortcode_attributes() {
+    assert_eq!(
+        parse_attributes(r#"href="/docs/" text='Read the docs' data-x=1 big"#),
+        vec![
+            ("href".to_owned(), Some("/docs/".to_owned())),
+            ("text".to_owned(), Some("Read the docs".to_owned())),
+            ("data-x".to_owned(), Some("1".to_owned())),
+            ("big".to_owned(), None),
+        ]
+    );
+}
+
+#[test]
+fn it_renders_builtin_shortcodes() {
+    let html = to_html(
+        "{{ button href=\"/docs/\" text=\"<Docs>\" }}\n\n{{ youtube id=\"dQw4w9WgXcQ\" }}\n",
+        &Config::default(),
+    )
+    .unwrap();
+    assert!(
+        html.starts_with(r#"<a class="button primary" href="&#x2f;docs&#x2f;">&lt;Docs&gt;</a>"#)
+    );
+    assert!(html.contains(r#"src="https://www.youtube-nocookie.com/embed/dQw4w9WgXcQ""#));
+    assert!(!html.contains("<p><div"));
+}
+
+#[test]
+fn it_leaves_code_and_unknown_shortcodes_alone() {
+    let html = to_html(
+        "Use `{{ button href=\"/\" }}` or {{ mystery }}.\n\n```\n{{ button href=\"/\" }}\n```\n",
+        &Config::default(),
+    )
+    .unwrap();
+    assert!(!html.contains("class=\"button"));
+    assert!(html.contains("{{ mystery }}"));
+    assert!(html.contains("<code>{{ button href=\"/\" }}</code>"));
+}
+
+#[test]
+fn it_uses_shortcodes_from_the_template_dir() {
+    let dir = TempDir::new().unwrap();
+    dir.child("shortcodes/callout.html.j2")
+        .write_str("<aside class=\"callout {{ kind }}\">{{ text }}</aside>")
+        .unwrap();
+    dir.child("shortcodes/button.html.j2")
+        .write_str("<button>{{ text }}</button>")
+        .unwrap();
+    let mut config = Config::default();
+    config.build.template_dir = Some(dir.path().to_str().unwrap().to_owned());
+
+    let html = to_html(
+        "{{ callout kind=warning text=\"Careful\" }}\n\n{{ button text=Go }}\n",
+        &config,
+    )
+    .unwrap();
+    assert!(html.contains(r#"<aside class="callout warning">Careful</aside>"#));
+    assert!(html.contains("<button>Go</button>"));
+}
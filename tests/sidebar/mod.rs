@@ -0,0 +1,45 @@
+use camino::Utf8Path;
+use oranda::config::Config;
+use oranda::site::sidebar::Sidebar;
+
+const SIDEBAR: &str = r#"
+- [Introduction](./intro.md)
+
+# Guides
+
+- Configuration
+  - [Basics](./config/../config/basics.md)
+- [Chat](https://example.com/chat)
+"#;
+
+#[test]
+fn it_nests_entries_under_sections_and_labels() {
+    let sidebar = Sidebar::parse(SIDEBAR, Utf8Path::new("docs"), &Config::default());
+    let sources = sidebar
+        .sources()
+        .into_iter()
+        .map(|(_, source)| source.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(sources, vec!["docs/intro.md", "docs/config/basics.md"]);
+
+    let items = sidebar.context("./docs/config/basics.md");
+    assert_eq!(items.len(), 2);
+    assert!(!items[0].expanded);
+    assert_eq!(items[0].link.as_deref(), Some("/docs/intro/"));
+
+    let guides = &items[1];
+    assert!(guides.is_section);
+    assert!(guides.expanded);
+    let configuration = &guides.children[0];
+    assert_eq!(configuration.link, None);
+    assert!(configuration.expanded);
+    assert!(configuration.children[0].active);
+    assert_eq!(
+        configuration.children[0].link.as_deref(),
+        Some("/docs/config/basics/")
+    );
+    assert_eq!(
+        guides.children[1].link.as_deref(),
+        Some("https://example.com/chat")
+    );
+}
@@ -0,0 +1,58 @@
+use assert_fs::prelude::*;
+use camino::Utf8PathBuf;
+use oranda::site::single_file::{inline_html, Resolver, MAX_INLINE_IMAGE_SIZE};
+
+fn dist() -> (assert_fs::TempDir, Utf8PathBuf) {
+    let dir = assert_fs::TempDir::new().unwrap();
+    dir.child("oranda.css")
+        .write_str("body { background: url('img/dot.png'); }")
+        .unwrap();
+    dir.child("artifacts.js")
+        .write_str("document.write('</script>');")
+        .unwrap();
+    dir.child("img/dot.png").write_binary(b"\x89PNG").unwrap();
+    dir.child("img/huge.png")
+        .write_binary(&vec![0; MAX_INLINE_IMAGE_SIZE as usize + 1])
+        .unwrap();
+    let path = Utf8PathBuf::from_path_buf(dir.path().to_owned()).unwrap();
+    (dir, path)
+}
+
+#[test]
+fn it_inlines_stylesheets_and_scripts() {
+    let (_dir, dist) = dist();
+    let page_dir = dist.join("changelog");
+    let resolver = Resolver {
+        dist_dir: &dist,
+        page_dir: &page_dir,
+        path_prefix: Some("app"),
+    };
+    let html = r#"<link rel="preload" href="/app/oranda.css" as="style" />
+<link rel="stylesheet" href="/app/oranda.css" />
+<link rel="stylesheet" href="https://fonts.googleapis.com/css2?family=Fira+Sans" />
+<script src="../artifacts.js" defer></script>"#;
+    let inlined = inline_html(html, &resolver).unwrap();
+    assert_eq!(
+        inlined,
+        r#"
+<style>body { background: url("data:image/png;base64,iVBORw=="); }</style>
+<link rel="stylesheet" href="https://fonts.googleapis.com/css2?family=Fira+Sans" />
+<script>document.write('<\/script>');</script>"#
+    );
+}
+
+#[test]
+fn it_inlines_small_images_only() {
+    let (_dir, dist) = dist();
+    let resolver = Resolver {
+        dist_dir: &dist,
+        page_dir: &dist,
+        path_prefix: None,
+    };
+    let html = r#"<img src="img/dot.png" alt="" /><img src="/img/huge.png" alt="" /><img src="/missing.png" alt="" />"#;
+    let inlined = inline_html(html, &resolver).unwrap();
+    assert_eq!(
+        inlined,
+        r#"<img src="data:image/png;base64,iVBORw==" alt="" /><img src="/img/huge.png" alt="" /><img src="/missing.png" alt="" />"#
+    );
+}
@@ -0,0 +1,103 @@
+use assert_fs::prelude::*;
+use camino::{Utf8Path, Utf8PathBuf};
+use oranda::config::Config;
+use oranda::site::page::Page;
+use oranda::site::templates::Templates;
+use oranda::site::writer::MemoryWriter;
+use oranda::site::SiteBuilder;
+
+use super::utils::tokio_utils::TEST_RUNTIME;
+
+fn path(dir: &assert_fs::TempDir) -> Utf8PathBuf {
+    Utf8PathBuf::from_path_buf(dir.path().canonicalize().unwrap()).unwrap()
+}
+
+#[test]
+fn it_loads_a_project_from_another_directory() {
+    let _guard = TEST_RUNTIME.enter();
+    let project = assert_fs::TempDir::new().unwrap();
+    project
+        .child("README.md")
+        .write_str("# axolotlsay\n\nThis is the readme.\n")
+        .unwrap();
+    project
+        .child("docs/guide.md")
+        .write_str("# Guide\n\nThis is the guide.\n")
+        .unwrap();
+    project
+        .child("oranda.json")
+        .write_str(
+            r#"{
+                "project": { "name": "axolotlsay" },
+                "build": { "additional_pages": { "Guide": "docs/guide.md" } }
+            }"#,
+        )
+        .unwrap();
+    let root = path(&project);
+    assert_ne!(std::env::current_dir().unwrap(), root);
+
+    let config = Config::build_in(&root, Utf8Path::new("oranda.json")).unwrap();
+    assert_eq!(config.root_dir, root);
+    assert_eq!(config.project.name, "axolotlsay");
+    let templates = Templates::new(&config, None).unwrap();
+    let (page, _) =
+        Page::new_from_markdown("docs/guide.md", &templates, &config, None, true).unwrap();
+    assert!(page.contents.contains("This is the guide."));
+}
+
+#[test]
+fn it_resolves_the_dist_dir_against_the_base_dir() {
+    let project = assert_fs::TempDir::new().unwrap();
+    let root = path(&project);
+    let config = SiteBuilder::new(Config::default())
+        .base_dir(root.clone())
+        .config();
+    assert_eq!(config.root_dir, root);
+    assert_eq!(config.build.dist_dir, root.join("public").as_str());
+
+    let mut custom = Config::default();
+    custom.build.dist_dir = root.join("site").into_string();
+    let elsewhere = assert_fs::TempDir::new().unwrap();
+    let config = SiteBuilder::new(custom).base_dir(path(&elsewhere)).config();
+    assert_eq!(config.build.dist_dir, root.join("site").as_str());
+}
+
+fn project_with_readme() -> assert_fs::TempDir {
+    let project = assert_fs::TempDir::new().unwrap();
+    project
+        .child("README.md")
+        .write_str("# axolotlsay\n\nThis is the readme.\n")
+        .unwrap();
+    project
+}
+
+#[tokio::test]
+async fn it_builds_from_inside_a_runtime() {
+    let project = project_with_readme();
+    let config = Config::build_in(&path(&project), Utf8Path::new("oranda.json")).unwrap();
+    let builder = SiteBuilder::new(config);
+    let site = builder.build().unwrap();
+    assert!(
+        site.pages
+            .iter()
+            .any(|page| page.filename == "index.html"
+                && page.contents.contains("This is the readme."))
+    );
+
+    builder.write(site).unwrap();
+    assert!(path(&project).join("public/index.html").is_file());
+}
+
+#[test]
+fn it_builds_without_a_runtime() {
+    let project = project_with_readme();
+    let config = Config::build_in(&path(&project), Utf8Path::new("oranda.json")).unwrap();
+    let builder = SiteBuilder::new(config);
+    let site = builder.build().unwrap();
+    let mut writer = MemoryWriter::new();
+    builder.write_to(site, &mut writer).unwrap();
+    assert!(writer
+        .get("index.html")
+        .unwrap()
+        .contains("This is the readme."));
+}
@@ -0,0 +1,53 @@
+use oranda::config::Config;
+use oranda::site::page::Page;
+use oranda::site::sitemap::generate_sitemap;
+
+fn page(filename: &str) -> Page {
+    Page {
+        contents: String::new(),
+        filename: filename.to_owned(),
+        reading_time: None,
+        stale: None,
+    }
+}
+
+#[test]
+fn it_lists_public_pages() {
+    let mut config = Config::default();
+    config.build.base_url = Some("https://oranda.axo.dev".to_owned());
+    let pages = vec![
+        page("index.html"),
+        page("changelog.html"),
+        page("changelog/v0.6.0.html"),
+        page("changelog.rss"),
+        page("offline.html"),
+        page("widgets/whats-new.html"),
+        page("internal/runbook.html"),
+        page("q&a.html"),
+    ];
+    assert_eq!(
+        generate_sitemap(&pages, &config),
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <url>
+    <loc>https://oranda.axo.dev/</loc>
+  </url>
+  <url>
+    <loc>https://oranda.axo.dev/changelog/</loc>
+  </url>
+  <url>
+    <loc>https://oranda.axo.dev/changelog/v0.6.0/</loc>
+  </url>
+  <url>
+    <loc>https://oranda.axo.dev/q%26a/</loc>
+  </url>
+</urlset>
+"#
+    );
+}
+
+#[test]
+fn it_lists_nothing_without_a_base_url() {
+    let sitemap = generate_sitemap(&[page("index.html")], &Config::default());
+    assert!(!sitemap.contains("<url>"));
+}
@@ -0,0 +1,45 @@
+use oranda::config::Config;
+use oranda::site::markdown::sponsors::{extract, has_sections, SponsorSection};
+use oranda::site::markdown::{to_html, to_html_for_sponsors};
+
+const PAGE: &str = "# Tuning\n\nThe basics.\n\n<!-- sponsors-only: Advanced tuning -->\n## Advanced\n\nSecret sauce.\n<!-- /sponsors-only -->\n\nThe end.\n";
+
+#[test]
+fn it_extracts_sponsor_sections_outside_code() {
+    let (markdown, sections) = extract(PAGE);
+    assert_eq!(
+        sections,
+        vec![SponsorSection {
+            title: Some("Advanced tuning".to_owned())
+        }]
+    );
+    assert!(!markdown.contains("Secret sauce"));
+    assert!(markdown.contains("The end."));
+
+    let code = "```\n<!-- sponsors-only -->\nnot hidden\n```\n";
+    assert!(!has_sections(code));
+    assert_eq!(extract(code).0, code);
+}
+
+#[test]
+fn it_renders_a_teaser_linking_to_funding() {
+    let mut config = Config::default();
+    config.build.path_prefix = Some("docs".to_owned());
+    let html = to_html(PAGE, &config).unwrap();
+    assert!(!html.contains("Secret sauce"));
+    assert!(html.contains(r#"<aside class="sponsors-only">"#));
+    assert!(html.contains("Advanced tuning is for sponsors"));
+    assert!(html.contains(r#"href="&#x2f;docs&#x2f;funding&#x2f;""#));
+    assert!(html.contains("The end."));
+
+    config.components.funding = None;
+    let html = to_html(PAGE, &config).unwrap();
+    assert!(!html.contains("Become a sponsor"));
+}
+
+#[test]
+fn it_renders_everything_for_sponsors() {
+    let html = to_html_for_sponsors(PAGE, &Config::default()).unwrap();
+    assert!(html.contains("Secret sauce"));
+    assert!(!html.contains("sponsors-only"));
+}
@@ -0,0 +1,36 @@
+use chrono::NaiveDate;
+use oranda::data::git::FileHistory;
+use oranda::site::page::stale::staleness;
+
+fn date(date: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+}
+
+fn history(last_updated: &str) -> FileHistory {
+    FileHistory {
+        last_author: "Jane Doe".to_owned(),
+        last_updated: date(last_updated).format("%b %e %Y").to_string(),
+        last_updated_date: Some(date(last_updated)),
+        contributors: vec![],
+    }
+}
+
+#[test]
+fn it_flags_pages_past_their_review_date() {
+    let today = date("2024-06-01");
+    let stale = staleness(Some("2024-05-01"), None, None, today).unwrap();
+    assert_eq!(stale.reason, "it was due for review on May  1 2024");
+    assert!(staleness(Some("2024-07-01"), None, None, today).is_none());
+}
+
+#[test]
+fn it_flags_pages_by_git_age_unless_reviewed() {
+    let today = date("2024-06-01");
+    let old = history("2023-01-01");
+    let stale = staleness(None, Some(&old), Some(365), today).unwrap();
+    assert!(stale.reason.contains("more than 365 days ago"));
+    assert!(staleness(None, Some(&history("2024-05-01")), Some(365), today).is_none());
+    assert!(staleness(None, Some(&old), None, today).is_none());
+    // A review date that hasn't passed yet vouches for the page, however old it is
+    assert!(staleness(Some("2024-12-31"), Some(&old), Some(365), today).is_none());
+}
@@ -0,0 +1,137 @@
+use assert_fs::prelude::*;
+use camino::{Utf8Path, Utf8PathBuf};
+use oranda::config::{Config, SupportMatrixConfig, SupportStatus};
+use oranda::data::artifacts::ReleaseArtifacts;
+use oranda::data::{Context, Release, ReleaseSource};
+use oranda::site::page::Page;
+use oranda::site::support_matrix::context;
+use oranda::site::templates::Templates;
+use serde_json::json;
+
+use crate::utils::github::release_with_assets;
+
+fn release_built_for(targets: &[&str]) -> Release {
+    let manifest = json!({
+        "artifacts": {
+            "app.tar.gz": { "kind": "executable-zip", "target_triples": targets },
+        },
+    });
+    Release {
+        manifest: Some(serde_json::from_value(manifest).unwrap()),
+        source: ReleaseSource::Github(release_with_assets("v1.0.0", &[])),
+        artifacts: ReleaseArtifacts::new(None),
+    }
+}
+
+#[test]
+fn it_renders_configured_and_cargo_dist_targets() {
+    let project = assert_fs::TempDir::new().unwrap();
+    project
+        .child("oranda.json")
+        .write_str(
+            r#"{
+                "project": { "name": "axolotlsay" },
+                "components": {
+                    "support_matrix": {
+                        "targets": {
+                            "x86_64-unknown-linux-gnu": "tier1",
+                            "riscv64gc-unknown-linux-gnu": "community"
+                        },
+                        "default_status": "tested"
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+    let root = Utf8PathBuf::from_path_buf(project.path().to_owned()).unwrap();
+    let config = Config::build_in(&root, Utf8Path::new("oranda.json")).unwrap();
+    let support_matrix = config.components.support_matrix.as_ref().unwrap();
+    assert!(support_matrix.cargo_dist_targets);
+    assert_eq!(support_matrix.default_status, SupportStatus::Tested);
+
+    let release_context = Context {
+        repo: None,
+        releases: vec![release_built_for(&[
+            "x86_64-unknown-linux-gnu",
+            "aarch64-apple-darwin",
+        ])],
+        has_prereleases: false,
+        latest_release: Some(0),
+        has_artifacts: true,
+        channels: vec![],
+    };
+    let templates = Templates::new(&config, Some(&release_context)).unwrap();
+    let page_context = context(
+        support_matrix,
+        config.components.artifacts.as_ref(),
+        Some(&release_context),
+    );
+    let page = Page::new_from_template(
+        "platforms.html",
+        &templates,
+        "support_matrix.html",
+        &page_context,
+    )
+    .unwrap();
+    assert!(page
+        .contents
+        .contains(r#"<li><a href="/platforms/">Platforms</a></li>"#));
+
+    // Configured targets come first, in order, then the ones only cargo-dist knows about
+    let rows = page
+        .contents
+        .split("<tr>")
+        .skip(2)
+        .map(|row| {
+            // Targets we don't have a nicer name for are only shown as-is
+            let target = match row.split_once("<code>") {
+                Some((_, rest)) => rest.split("</code>").next().unwrap(),
+                None => row.split("<td>").nth(1).unwrap().split('<').next().unwrap(),
+            };
+            let status = row
+                .split(r#"<span title=""#)
+                .nth(1)
+                .and_then(|rest| rest.split('"').next());
+            (target.trim(), status.unwrap())
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        rows,
+        [
+            ("x86_64-unknown-linux-gnu", "Tier 1"),
+            ("riscv64gc-unknown-linux-gnu", "Community"),
+            ("aarch64-apple-darwin", "Tested"),
+        ]
+    );
+}
+
+#[test]
+fn it_only_shows_configured_targets_without_cargo_dist_targets() {
+    let mut config = Config::default();
+    config.components.support_matrix = Some(SupportMatrixConfig {
+        cargo_dist_targets: false,
+        ..Default::default()
+    });
+    let release_context = Context {
+        repo: None,
+        releases: vec![release_built_for(&["aarch64-apple-darwin"])],
+        has_prereleases: false,
+        latest_release: Some(0),
+        has_artifacts: true,
+        channels: vec![],
+    };
+    let templates = Templates::new(&config, Some(&release_context)).unwrap();
+    let page_context = context(
+        config.components.support_matrix.as_ref().unwrap(),
+        None,
+        Some(&release_context),
+    );
+    let page = Page::new_from_template(
+        "platforms.html",
+        &templates,
+        "support_matrix.html",
+        &page_context,
+    )
+    .unwrap();
+    assert!(page.contents.contains("No platforms have been listed yet."));
+}
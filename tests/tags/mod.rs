@@ -0,0 +1,30 @@
+use oranda::site::tags::{TaggedItem, TaggedItemKind, Taxonomy};
+
+fn item(title: &str) -> TaggedItem {
+    TaggedItem {
+        kind: TaggedItemKind::Post,
+        title: title.to_owned(),
+        link: format!("/blog/{title}/"),
+        date: None,
+        formatted_date: None,
+        description: None,
+    }
+}
+
+#[test]
+fn it_merges_tags_by_slug_and_weighs_them() {
+    let mut taxonomy = Taxonomy::default();
+    taxonomy.add(&["Release Notes".to_owned(), "cli".to_owned()], &item("a"));
+    taxonomy.add(&["release-notes".to_owned()], &item("b"));
+    taxonomy.add(&["release notes".to_owned(), "".to_owned()], &item("c"));
+
+    let cloud = taxonomy.cloud(&Some("my_project".to_owned()));
+    assert_eq!(cloud.len(), 2);
+    assert_eq!(cloud[0].name, "cli");
+    assert_eq!(cloud[0].count, 1);
+    assert_eq!(cloud[0].weight, 1);
+    assert_eq!(cloud[1].name, "Release Notes");
+    assert_eq!(cloud[1].link, "/my_project/tags/release-notes/");
+    assert_eq!(cloud[1].count, 3);
+    assert_eq!(cloud[1].weight, 5);
+}
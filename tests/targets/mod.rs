@@ -0,0 +1,85 @@
+use oranda::config::ArtifactsConfig;
+use oranda::data::artifacts::{target_label, ReleaseArtifacts};
+
+use crate::utils::github::release_with_assets;
+
+fn config_with_labels(labels: &[(&str, &str)]) -> ArtifactsConfig {
+    ArtifactsConfig {
+        target_labels: labels
+            .iter()
+            .map(|(triple, label)| (triple.to_string(), label.to_string()))
+            .collect(),
+        ..ArtifactsConfig::default()
+    }
+}
+
+#[test]
+fn it_labels_known_targets() {
+    let labels = [
+        "aarch64-pc-windows-msvc",
+        "x86_64-unknown-linux-gnu",
+        "x86_64-unknown-linux-musl",
+        "x86_64-unknown-freebsd",
+        "universal2-apple-darwin",
+        "riscv64gc-unknown-linux-gnu",
+    ]
+    .map(|triple| target_label(triple, None));
+    assert_eq!(
+        labels,
+        [
+            "Windows arm64",
+            "Linux x64",
+            "Linux x64 (musl)",
+            "FreeBSD x64",
+            "macOS Universal",
+            "riscv64gc-unknown-linux-gnu",
+        ]
+    );
+}
+
+#[test]
+fn it_prefers_configured_labels() {
+    let config = config_with_labels(&[("x86_64-unknown-linux-musl", "Linux x64 (static)")]);
+    assert_eq!(
+        target_label("x86_64-unknown-linux-musl", Some(&config)),
+        "Linux x64 (static)"
+    );
+    assert_eq!(
+        target_label("x86_64-unknown-linux-gnu", Some(&config)),
+        "Linux x64"
+    );
+}
+
+#[test]
+fn it_selects_installers_for_new_and_custom_targets() {
+    let config = config_with_labels(&[("riscv64gc-unknown-linux-gnu", "Linux RISC-V")]);
+    let mut artifacts = ReleaseArtifacts::new(None);
+    artifacts.add_github(&release_with_assets(
+        "v1.0.0",
+        &[
+            ("app-aarch64-pc-windows-msvc.zip", 0),
+            ("app-universal2-apple-darwin.tar.gz", 0),
+            ("app-x86_64-unknown-freebsd.tar.gz", 0),
+            ("app-x86_64-unknown-linux-musl.tar.gz", 0),
+            ("app-riscv64gc-unknown-linux-gnu.tar.gz", 0),
+        ],
+    ));
+    artifacts.add_inference(&config);
+    artifacts.select_installers(&config);
+
+    let targets = artifacts
+        .installers_by_target()
+        .keys()
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        targets,
+        vec![
+            "aarch64-pc-windows-msvc",
+            "riscv64gc-unknown-linux-gnu",
+            "universal2-apple-darwin",
+            "x86_64-unknown-freebsd",
+            "x86_64-unknown-linux-musl",
+        ]
+    );
+}
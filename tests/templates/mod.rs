@@ -0,0 +1,56 @@
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use minijinja::context;
+use oranda::config::Config;
+use oranda::data::fetch::fetch_json;
+use oranda::errors::OrandaError;
+use oranda::site::templates::Templates;
+
+#[test]
+fn it_prefers_templates_from_the_template_dir() {
+    let dir = TempDir::new().unwrap();
+    dir.child("includes/nav.html.j2")
+        .write_str("<nav>{% include \"includes/extra.html\" %}</nav>")
+        .unwrap();
+    dir.child("includes/extra.html.j2")
+        .write_str("my own nav")
+        .unwrap();
+    let mut config = Config::default();
+    config.build.template_dir = Some(dir.path().to_str().unwrap().to_owned());
+
+    let templates = Templates::new(&config, None).unwrap();
+    let html = templates
+        .render_to_string("offline.html", context!())
+        .unwrap();
+    assert!(html.contains("<nav>my own nav</nav>"));
+}
+
+#[test]
+fn it_gives_templates_the_config() {
+    let dir = TempDir::new().unwrap();
+    dir.child("includes/nav.html.j2")
+        .write_str(
+            "<nav>{{ config.project.name }} {{ config.styles.theme }} \
+             {% if \"changelog\" in config.components %}changelog{% endif %}</nav>",
+        )
+        .unwrap();
+    let mut config = Config::default();
+    config.project.name = String::from("axolotlsay");
+    config.build.template_dir = Some(dir.path().to_str().unwrap().to_owned());
+
+    let templates = Templates::new(&config, None).unwrap();
+    let html = templates
+        .render_to_string("offline.html", context!())
+        .unwrap();
+    assert!(html.contains("<nav>axolotlsay dark changelog</nav>"));
+}
+
+#[test]
+fn it_only_fetches_allowed_urls() {
+    let allowlist = vec!["https://api.example.com/stats/".to_owned()];
+    let result = fetch_json("https://api.example.com/secrets.json", &allowlist);
+    assert!(matches!(
+        result,
+        Err(OrandaError::FetchJsonNotAllowed { .. })
+    ));
+}
@@ -0,0 +1,59 @@
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use camino::Utf8Path;
+use oranda::site::theme_test::render_fixtures;
+
+#[test]
+fn it_renders_every_fixture_with_the_overrides() {
+    let dir = TempDir::new().unwrap();
+    dir.child("includes/nav.html.j2")
+        .write_str("<nav>my theme</nav>")
+        .unwrap();
+    dir.child("workspace_index/index.html.j2")
+        .write_str("{% for member in page.preferred_members %}<p>{{ member.name }}</p>{% endfor %}")
+        .unwrap();
+
+    let snapshots = render_fixtures(Utf8Path::from_path(dir.path()).unwrap()).unwrap();
+    let names = snapshots
+        .iter()
+        .map(|snapshot| snapshot.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            "minimal/index.html",
+            "minimal/markdown_page.html",
+            "release-rich/index.html",
+            "release-rich/artifacts.html",
+            "release-rich/changelog_index.html",
+            "release-rich/changelog_single.html",
+            "workspace/index.html",
+            "org/index.html",
+        ]
+    );
+    for snapshot in &snapshots[..6] {
+        assert!(
+            snapshot.contents.contains("<nav>my theme</nav>"),
+            "{} doesn't use the override",
+            snapshot.name
+        );
+    }
+    let artifacts = &snapshots[3].contents;
+    assert!(artifacts.contains("axolotlsay-x86_64-pc-windows-msvc.msi"));
+    assert!(artifacts.contains("cargo install axolotlsay"));
+    assert!(snapshots[4]
+        .contents
+        .contains("Said hello to more axolotls"));
+    assert_eq!(snapshots[6].contents, "<p>axolotlsay</p>");
+}
+
+#[test]
+fn it_renders_the_same_snapshots_every_time() {
+    let dir = TempDir::new().unwrap();
+    let dir = Utf8Path::from_path(dir.path()).unwrap();
+    let first = render_fixtures(dir).unwrap();
+    let second = render_fixtures(dir).unwrap();
+    for (first, second) in first.iter().zip(&second) {
+        assert_eq!(first.contents, second.contents, "{} changed", first.name);
+    }
+}
@@ -0,0 +1,34 @@
+use std::net::{IpAddr, Ipv4Addr};
+
+use camino::Utf8Path;
+use oranda::tls;
+
+#[test]
+fn it_reuses_the_certificate_until_the_names_change() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let dir = Utf8Path::from_path(dir.path()).unwrap();
+    let names = tls::names_for(Ipv4Addr::LOCALHOST.into());
+    assert_eq!(names, ["localhost", "127.0.0.1", "::1"]);
+
+    let first = tls::self_signed_in(dir, &names).unwrap();
+    assert!(first.cert_pem.starts_with("-----BEGIN CERTIFICATE-----"));
+    assert!(first.key_pem.contains("PRIVATE KEY"));
+    let again = tls::self_signed_in(dir, &names).unwrap();
+    assert_eq!(again.cert_pem, first.cert_pem);
+
+    let lan = IpAddr::from([192, 168, 1, 23]);
+    let names = tls::names_for(lan);
+    assert_eq!(names.last().unwrap(), "192.168.1.23");
+    let other = tls::self_signed_in(dir, &names).unwrap();
+    assert_ne!(other.cert_pem, first.cert_pem);
+}
+
+#[test]
+fn it_keeps_the_certificate_in_the_project() {
+    let project = assert_fs::TempDir::new().unwrap();
+    let root_dir = Utf8Path::from_path(project.path()).unwrap();
+    let names = tls::names_for(Ipv4Addr::LOCALHOST.into());
+    tls::self_signed(root_dir, &names).unwrap();
+    assert_eq!(tls::cert_dir(root_dir), root_dir.join(".oranda-cache/tls"));
+    assert!(root_dir.join(".oranda-cache/tls/cert.pem").exists());
+}
@@ -0,0 +1,39 @@
+use oranda::data::github::{GithubRelease, GithubReleaseAsset};
+
+/// A GitHub release with the given `(name, download_count)` assets, and nothing else of note
+pub fn release_with_assets(tag: &str, assets: &[(&str, i64)]) -> GithubRelease {
+    GithubRelease {
+        url: String::new(),
+        assets_url: String::new(),
+        html_url: String::new(),
+        id: 0,
+        tag_name: tag.to_owned(),
+        target_commitish: String::new(),
+        name: None,
+        draft: false,
+        prerelease: false,
+        created_at: String::new(),
+        published_at: String::new(),
+        assets: assets
+            .iter()
+            .map(|(name, download_count)| GithubReleaseAsset {
+                url: String::new(),
+                id: 0,
+                node_id: String::new(),
+                name: (*name).to_owned(),
+                label: None,
+                content_type: "application/octet-stream".to_owned(),
+                state: "uploaded".to_owned(),
+                size: 0,
+                download_count: *download_count,
+                created_at: String::new(),
+                updated_at: String::new(),
+                browser_download_url: format!("https://example.com/{name}"),
+            })
+            .collect(),
+        tarball_url: String::new(),
+        zipball_url: String::new(),
+        body: None,
+        author: None,
+    }
+}
@@ -1,2 +1,3 @@
+pub mod github;
 pub mod snapshots;
 pub mod tokio_utils;
@@ -0,0 +1,151 @@
+use std::io::Cursor;
+
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use camino::Utf8PathBuf;
+use image::{ImageOutputFormat, Rgba, RgbaImage};
+use oranda::config::{Config, WebManifestConfig};
+use oranda::errors::OrandaError;
+use oranda::site::layout::web_manifest::{
+    manifest, source_icon, write_web_manifest, WebManifestContext,
+};
+use oranda::site::oranda_theme::OrandaTheme;
+use oranda::site::writer::MemoryWriter;
+
+fn config(root: &TempDir) -> Config {
+    let mut config = Config {
+        root_dir: Utf8PathBuf::from_path_buf(root.path().to_owned()).unwrap(),
+        ..Default::default()
+    };
+    config.project.name = String::from("axolotlsay");
+    config.styles.web_manifest = Some(WebManifestConfig::default());
+    config
+}
+
+fn write_logo(root: &TempDir, name: &str) {
+    // A transparent, wider than it is tall logo, so the icons need padding
+    let logo = RgbaImage::from_pixel(64, 32, Rgba([0, 0, 0, 0]));
+    let mut bytes = Vec::new();
+    logo.write_to(&mut Cursor::new(&mut bytes), ImageOutputFormat::Png)
+        .unwrap();
+    root.child(name).write_binary(&bytes).unwrap();
+}
+
+#[test]
+fn it_falls_back_to_a_resizable_logo() {
+    let root = TempDir::new().unwrap();
+    let mut config = config(&root);
+    assert_eq!(source_icon(&config), None);
+    config.styles.logo = Some("./logo.svg".to_owned());
+    assert_eq!(source_icon(&config), None);
+    config.styles.logo = Some("https://example.com/logo.png".to_owned());
+    assert_eq!(source_icon(&config), None);
+    config.styles.logo = Some("./logo.PNG".to_owned());
+    assert_eq!(source_icon(&config), Some("./logo.PNG"));
+    config.styles.web_manifest.as_mut().unwrap().icon = Some("./icon.png".to_owned());
+    assert_eq!(source_icon(&config), Some("./icon.png"));
+}
+
+#[test]
+fn it_writes_the_manifest() {
+    let root = TempDir::new().unwrap();
+    let mut config = config(&root);
+    config.build.path_prefix = Some("axolotlsay".to_owned());
+    config.project.description = Some("a CLI for learning to distribute CLIs".to_owned());
+    config.styles.theme = OrandaTheme::Cupcake;
+    config.styles.web_manifest.as_mut().unwrap().short_name = Some("axo".to_owned());
+
+    let manifest = manifest(&config);
+    assert_eq!(manifest["name"], "axolotlsay");
+    assert_eq!(manifest["short_name"], "axo");
+    assert_eq!(manifest["start_url"], "/axolotlsay/");
+    assert_eq!(manifest["display"], "standalone");
+    assert_eq!(manifest["theme_color"], "#faf7f5");
+    assert_eq!(
+        manifest["description"],
+        "a CLI for learning to distribute CLIs"
+    );
+    // No image to make icons from
+    assert_eq!(manifest["icons"].as_array().unwrap().len(), 0);
+
+    let context = WebManifestContext::new(&config).unwrap();
+    assert_eq!(context.manifest_path, "/axolotlsay/site.webmanifest");
+    assert!(context.icons.is_empty());
+}
+
+#[test]
+fn it_makes_icons_from_the_logo() {
+    let root = TempDir::new().unwrap();
+    write_logo(&root, "logo.png");
+    let mut config = config(&root);
+    config.styles.logo = Some("./logo.png".to_owned());
+
+    let mut writer = MemoryWriter::new();
+    write_web_manifest(&config, &mut writer).unwrap();
+    for (filename, size) in [
+        ("favicon-16x16.png", 16),
+        ("favicon-32x32.png", 32),
+        ("apple-touch-icon.png", 180),
+        ("android-chrome-192x192.png", 192),
+        ("android-chrome-512x512.png", 512),
+    ] {
+        let icon = image::load_from_memory(&writer.files[&Utf8PathBuf::from(filename)]).unwrap();
+        assert_eq!((icon.width(), icon.height()), (size, size), "{filename}");
+    }
+    // The apple touch icon is filled in with the theme's background, the others stay transparent
+    let apple = image::load_from_memory(&writer.files[&Utf8PathBuf::from("apple-touch-icon.png")])
+        .unwrap()
+        .to_rgba8();
+    assert_eq!(*apple.get_pixel(0, 0), Rgba([0x14, 0x14, 0x14, 255]));
+    let chrome =
+        image::load_from_memory(&writer.files[&Utf8PathBuf::from("android-chrome-192x192.png")])
+            .unwrap()
+            .to_rgba8();
+    assert_eq!(chrome.get_pixel(0, 0)[3], 0);
+    assert!(writer.files.contains_key(&Utf8PathBuf::from("favicon.ico")));
+
+    let manifest: serde_json::Value =
+        serde_json::from_str(&writer.get("site.webmanifest").unwrap()).unwrap();
+    assert_eq!(manifest["icons"][1]["src"], "/android-chrome-512x512.png");
+
+    let context = WebManifestContext::new(&config).unwrap();
+    let rels: Vec<_> = context.icons.iter().map(|icon| icon.rel).collect();
+    assert_eq!(rels, ["icon", "icon", "apple-touch-icon"]);
+}
+
+#[test]
+fn it_keeps_a_configured_favicon() {
+    let root = TempDir::new().unwrap();
+    write_logo(&root, "logo.png");
+    let mut config = config(&root);
+    config.styles.logo = Some("./logo.png".to_owned());
+    config.styles.favicon = Some("./favicon.ico".to_owned());
+
+    let mut writer = MemoryWriter::new();
+    write_web_manifest(&config, &mut writer).unwrap();
+    assert!(!writer.files.contains_key(&Utf8PathBuf::from("favicon.ico")));
+}
+
+#[test]
+fn it_fails_on_an_icon_it_cant_read() {
+    let root = TempDir::new().unwrap();
+    root.child("icon.png").write_str("not a png").unwrap();
+    let mut config = config(&root);
+    config.styles.web_manifest.as_mut().unwrap().icon = Some("./icon.png".to_owned());
+
+    let result = write_web_manifest(&config, &mut MemoryWriter::new());
+    assert!(matches!(
+        result,
+        Err(OrandaError::WebManifestIconFailed { .. })
+    ));
+}
+
+#[test]
+fn it_writes_nothing_when_disabled() {
+    let mut config = Config::default();
+    config.styles.logo = Some("./logo.png".to_owned());
+    let mut writer = MemoryWriter::new();
+    write_web_manifest(&config, &mut writer).unwrap();
+    assert!(writer.files.is_empty());
+    assert!(WebManifestContext::new(&config).is_none());
+}
@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+
+use oranda::config::{AuthorsConfig, Config, FundingConfig, FundingManifestConfig};
+use oranda::data::funding::{Funding, FundingContent, FundingType};
+use oranda::site::funding;
+use oranda::site::well_known::{build_pages, funding_manifest, humans_txt};
+
+fn config() -> Config {
+    let mut config = Config::default();
+    "axolotlsay".clone_into(&mut config.project.name);
+    config.project.homepage = Some("https://axodotdev.github.io/axolotlsay/".to_owned());
+    config.project.repository = Some("https://github.com/axodotdev/axolotlsay".to_owned());
+    config.project.license = Some("MIT".to_owned());
+    config
+}
+
+#[test]
+fn it_credits_authors_in_humans_txt() {
+    let mut config = config();
+    let mut authors = AuthorsConfig::default();
+    authors.people.insert(
+        "ada".to_owned(),
+        serde_json::from_str(r#"{"name": "Ada", "github": "ada"}"#).unwrap(),
+    );
+    config.components.authors = Some(authors);
+    assert_eq!(
+        humans_txt(&config),
+        "/* TEAM */\nName: Ada\nGitHub: ada\n\n/* SITE */\nProject: axolotlsay\nHomepage: https://axodotdev.github.io/axolotlsay/\nRepository: https://github.com/axodotdev/axolotlsay\nLicense: MIT\nSoftware: oranda\n"
+    );
+}
+
+#[test]
+fn it_writes_a_funding_manifest_from_funding_links() {
+    let mut config = config();
+    let funding_cfg = FundingConfig::default();
+    let funding = Funding {
+        content: HashMap::from([
+            (
+                FundingType::Github,
+                FundingContent::One("axodotdev".to_owned()),
+            ),
+            (FundingType::KoFi, FundingContent::One("axo".to_owned())),
+        ]),
+        ..Default::default()
+    };
+    let context = funding::context(&funding_cfg, &funding).unwrap();
+    let manifest_cfg = FundingManifestConfig {
+        name: None,
+        email: "hi@axo.dev".to_owned(),
+        entity_type: Some("organisation".to_owned()),
+        description: None,
+    };
+    let manifest = funding_manifest(&config, &manifest_cfg, &context).unwrap();
+    let manifest: serde_json::Value = serde_json::from_str(&manifest).unwrap();
+    assert_eq!(manifest["entity"]["type"], "organisation");
+    assert_eq!(manifest["entity"]["name"], "axolotlsay");
+    assert_eq!(manifest["projects"][0]["licenses"][0], "spdx:MIT");
+    let channels = manifest["funding"]["channels"].as_array().unwrap();
+    assert_eq!(channels.len(), 2);
+    assert_eq!(channels[0]["guid"], "github");
+    assert_eq!(
+        channels[0]["address"],
+        "https://github.com/sponsors/axodotdev"
+    );
+    assert_eq!(
+        manifest["funding"]["plans"][0]["channels"],
+        serde_json::json!(["github", "ko-fi"])
+    );
+
+    config.marketing.well_known.funding_manifest = Some(manifest_cfg);
+    let pages = build_pages(&config, Some(&context)).unwrap();
+    let urls = pages
+        .iter()
+        .find(|page| page.filename == ".well-known/funding-manifest-urls")
+        .unwrap();
+    assert_eq!(
+        urls.contents,
+        "https://axodotdev.github.io/axolotlsay/funding.json\n"
+    );
+}
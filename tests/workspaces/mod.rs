@@ -0,0 +1,152 @@
+use assert_fs::prelude::*;
+use camino::Utf8PathBuf;
+use oranda::config::{Config, ConfigOptions, WorkspaceMember};
+use oranda::data::workspaces;
+use oranda::errors::OrandaError;
+use oranda::site::oranda_theme::OrandaTheme;
+use oranda::site::page::Page;
+use oranda::site::templates::Templates;
+
+use super::utils::tokio_utils::TEST_RUNTIME;
+
+fn path(dir: &assert_fs::TempDir) -> Utf8PathBuf {
+    Utf8PathBuf::from_path_buf(dir.path().canonicalize().unwrap()).unwrap()
+}
+
+/// A workspace whose members each have a README and a guide at the same relative paths
+fn workspace(root: &assert_fs::TempDir, slugs: &[&str]) -> Config {
+    root.child("oranda-workspace.json").write_str("{}").unwrap();
+    let mut config = Config::default();
+    for slug in slugs {
+        let member = root.child(slug);
+        member
+            .child("README.md")
+            .write_str(&format!("# {slug}\n\nThis is the {slug} readme.\n"))
+            .unwrap();
+        member
+            .child("docs/guide.md")
+            .write_str(&format!("# Guide\n\nThis is the {slug} guide.\n"))
+            .unwrap();
+        member
+            .child("oranda.json")
+            .write_str(r#"{ "build": { "additional_pages": { "Guide": "docs/guide.md" } } }"#)
+            .unwrap();
+        config.workspace.members.push(WorkspaceMember {
+            slug: slug.to_string(),
+            path: member.path().to_owned(),
+            weight: None,
+            config: None,
+        });
+    }
+    config
+}
+
+#[test]
+fn it_reads_member_files_from_the_member_directory() {
+    let _guard = TEST_RUNTIME.enter();
+    let root = assert_fs::TempDir::new().unwrap();
+    let config = workspace(&root, &["alpha", "beta"]);
+    let root_path = path(&root);
+    let members = workspaces::from_config(
+        &config,
+        &root_path,
+        &root_path.join("oranda-workspace.json"),
+        &ConfigOptions::default(),
+    )
+    .unwrap();
+
+    // Nothing here should care that we're not in either member's directory
+    assert_ne!(std::env::current_dir().unwrap(), root_path);
+    for member in &members {
+        let slug = &member.slug;
+        assert_eq!(member.config.root_dir, root_path.join(slug));
+        let templates = Templates::new(&member.config, None).unwrap();
+        let (page, _) =
+            Page::new_from_markdown("docs/guide.md", &templates, &member.config, None, true)
+                .unwrap();
+        assert_eq!(page.filename, "docs/guide.html");
+        assert!(page
+            .contents
+            .contains(&format!("This is the {slug} guide.")));
+        let (readme, _) =
+            Page::new_from_markdown("README.md", &templates, &member.config, None, true).unwrap();
+        assert!(readme
+            .contents
+            .contains(&format!("This is the {slug} readme.")));
+    }
+}
+
+#[test]
+fn it_orders_members_by_weight() {
+    let _guard = TEST_RUNTIME.enter();
+    let root = assert_fs::TempDir::new().unwrap();
+    let mut config = workspace(&root, &["alpha", "beta", "gamma"]);
+    config.workspace.members[2].weight = Some(-1);
+    let root_path = path(&root);
+    let members = workspaces::from_config(
+        &config,
+        &root_path,
+        &root_path.join("oranda-workspace.json"),
+        &ConfigOptions::default(),
+    )
+    .unwrap();
+
+    let slugs: Vec<_> = members.iter().map(|member| member.slug.as_str()).collect();
+    assert_eq!(slugs, ["gamma", "alpha", "beta"]);
+}
+
+#[test]
+fn it_merges_the_workspace_config_for_a_member_over_its_own() {
+    let root = assert_fs::TempDir::new().unwrap();
+    let member = root.child("alpha");
+    member
+        .child("oranda.json")
+        .write_str(
+            r#"{
+                "styles": { "theme": "light", "favicon": "favicon.ico" },
+                "build": { "additional_pages": { "Guide": "docs/guide.md" } }
+            }"#,
+        )
+        .unwrap();
+    let mut workspace_member = WorkspaceMember {
+        slug: "alpha".to_owned(),
+        path: member.path().to_owned(),
+        weight: None,
+        config: Some(
+            serde_json::from_str(
+                r#"{
+                    "styles": { "theme": "hacker" },
+                    "build": { "additional_pages": {
+                        "Code of Conduct": "../CODE_OF_CONDUCT.md",
+                        "Changelog": "../CHANGELOG.md"
+                    } },
+                    "components": { "changelog": false }
+                }"#,
+            )
+            .unwrap(),
+        ),
+    };
+    let layer = workspace_member
+        .load_layer(&path(&root).join("alpha/oranda.json"))
+        .unwrap()
+        .unwrap();
+    let styles = layer.styles.unwrap();
+    assert_eq!(styles.theme, Some(OrandaTheme::Hacker));
+    assert_eq!(styles.favicon.as_deref(), Some("favicon.ico"));
+    let pages = layer.build.unwrap().additional_pages.unwrap();
+    assert_eq!(
+        pages.keys().collect::<Vec<_>>(),
+        ["Guide", "Code of Conduct", "Changelog"]
+    );
+
+    // The workspace's own settings stay in the workspace
+    workspace_member.config =
+        Some(serde_json::from_str(r#"{ "workspace": { "auto": true } }"#).unwrap());
+    let error = workspace_member
+        .load_layer(&path(&root).join("alpha/oranda.json"))
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        OrandaError::WorkspaceMemberConfigInvalid { ref slug, .. } if slug == "alpha"
+    ));
+}
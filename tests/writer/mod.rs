@@ -0,0 +1,91 @@
+use assert_fs::prelude::*;
+use camino::Utf8PathBuf;
+use oranda::config::Config;
+use oranda::site::page::Page;
+use oranda::site::writer::{DirWriter, MemoryWriter};
+use oranda::site::Site;
+
+use super::utils::tokio_utils::TEST_RUNTIME;
+
+fn page(filename: &str, contents: &str) -> Page {
+    Page {
+        contents: contents.to_owned(),
+        filename: filename.to_owned(),
+        reading_time: None,
+        stale: None,
+    }
+}
+
+/// A site with a couple of pages, whose build already placed its CSS in the dist dir and kept
+/// its `artifacts.json` for the writer
+fn site(project: &assert_fs::TempDir) -> (Site, Config) {
+    project
+        .child("public/oranda.css")
+        .write_str("body {}")
+        .unwrap();
+    project
+        .child("static/logo.svg")
+        .write_str("<svg/>")
+        .unwrap();
+    let mut config = Config {
+        root_dir: Utf8PathBuf::from_path_buf(project.path().to_owned()).unwrap(),
+        ..Default::default()
+    };
+    config.build.dist_dir = config.path("public").into_string();
+    config.components.mdbook = None;
+    let mut files = MemoryWriter::new();
+    files
+        .files
+        .insert(Utf8PathBuf::from("artifacts.json"), b"{}".to_vec());
+    let site = Site {
+        workspace_data: None,
+        pages: vec![
+            page("index.html", "<h1>axolotlsay</h1>"),
+            page("docs/install.html", "<h1>Install</h1>"),
+            page("sitemap.xml", "<urlset/>"),
+        ],
+        files,
+        plan: None,
+    };
+    (site, config)
+}
+
+#[test]
+fn it_writes_the_whole_site_to_memory() {
+    let _guard = TEST_RUNTIME.enter();
+    let project = assert_fs::TempDir::new().unwrap();
+    let (site, config) = site(&project);
+    let mut writer = MemoryWriter::new();
+    site.write_to(Some(&config), &mut writer).unwrap();
+
+    assert_eq!(writer.get("index.html").unwrap(), "<h1>axolotlsay</h1>");
+    assert_eq!(
+        writer.get("docs/install/index.html").unwrap(),
+        "<h1>Install</h1>"
+    );
+    assert_eq!(writer.get("sitemap.xml").unwrap(), "<urlset/>");
+    assert_eq!(writer.get("oranda.css").unwrap(), "body {}");
+    assert_eq!(writer.get("static/logo.svg").unwrap(), "<svg/>");
+    assert!(writer.get("artifacts.js").is_some());
+    assert_eq!(writer.get("artifacts.json").unwrap(), "{}");
+    // Nothing but what the build placed there ends up in the dist dir
+    assert!(!project.child("public/index.html").exists());
+}
+
+#[test]
+fn it_writes_the_same_files_to_a_directory() {
+    let _guard = TEST_RUNTIME.enter();
+    let project = assert_fs::TempDir::new().unwrap();
+    let (site, config) = site(&project);
+    let out = assert_fs::TempDir::new().unwrap();
+    let out_path = Utf8PathBuf::from_path_buf(out.path().to_owned()).unwrap();
+    site.write_to(Some(&config), &mut DirWriter::new(out_path))
+        .unwrap();
+
+    out.child("index.html").assert("<h1>axolotlsay</h1>");
+    out.child("docs/install/index.html")
+        .assert("<h1>Install</h1>");
+    out.child("oranda.css").assert("body {}");
+    out.child("static/logo.svg").assert("<svg/>");
+    out.child("artifacts.json").assert("{}");
+}